@@ -52,7 +52,9 @@ impl std::fmt::Display for FailurePattern {
     }
 }
 
-const RETRYABLE_PATTERNS: [FailurePattern; 20] = [
+/// Transient failures: network trouble, throttling and 5xx responses.
+/// Checked before [`PERMANENT_PATTERNS`].
+const RETRYABLE_PATTERNS: [FailurePattern; 22] = [
     FailurePattern::Substring("too many requests"),
     FailurePattern::Token("429"),
     FailurePattern::Substring("timeout"),
@@ -73,6 +75,9 @@ const RETRYABLE_PATTERNS: [FailurePattern; 20] = [
     FailurePattern::Substring("broken pipe"),
     FailurePattern::Substring("reset by peer"),
     FailurePattern::Substring("network unreachable"),
+    FailurePattern::Substring("rate limit"),
+    // Cargo's own wording when it gives up retrying a flaky HTTP request.
+    FailurePattern::Substring("spurious network error"),
 ];
 
 fn contains_token(haystack: &str, token: &str) -> bool {
@@ -87,37 +92,45 @@ fn is_token_boundary(ch: Option<char>) -> bool {
     ch.is_none_or(|ch| !ch.is_ascii_alphanumeric())
 }
 
-const PERMANENT_PATTERNS: [&str; 26] = [
-    "failed to parse manifest",
-    "invalid",
-    "missing",
-    "license",
-    "description",
-    "readme",
-    "repository",
-    "could not compile",
-    "compilation failed",
-    "failed to verify",
-    "package is not allowed to be published",
-    "publish is disabled",
-    "yanked",
-    "forbidden",
-    "permission denied",
-    "not authorized",
-    "unauthorized",
-    "version already exists",
-    "is already uploaded",
-    "token is invalid",
-    "invalid credentials",
-    "checksum mismatch",
+/// Failures that retrying cannot fix: bad manifests, auth, and versions the
+/// registry already has.
+const PERMANENT_PATTERNS: [FailurePattern; 29] = [
+    FailurePattern::Substring("failed to parse manifest"),
+    FailurePattern::Substring("invalid"),
+    FailurePattern::Substring("missing"),
+    FailurePattern::Substring("license"),
+    FailurePattern::Substring("description"),
+    FailurePattern::Substring("readme"),
+    FailurePattern::Substring("repository"),
+    FailurePattern::Substring("could not compile"),
+    FailurePattern::Substring("compilation failed"),
+    FailurePattern::Substring("failed to verify"),
+    FailurePattern::Substring("package is not allowed to be published"),
+    FailurePattern::Substring("publish is disabled"),
+    FailurePattern::Substring("yanked"),
+    FailurePattern::Substring("forbidden"),
+    FailurePattern::Substring("permission denied"),
+    FailurePattern::Substring("not authorized"),
+    FailurePattern::Substring("unauthorized"),
+    FailurePattern::Substring("authentication"),
+    FailurePattern::Token("403"),
+    FailurePattern::Substring("version already exists"),
+    // Newer cargo: "crate foo@1.2.3 already exists on crates.io index".
+    // Bare "already exists" would also match local I/O errors (e.g. a
+    // target file that already exists), which are not registry verdicts.
+    FailurePattern::Substring("already exists on"),
+    FailurePattern::Substring("is already uploaded"),
+    FailurePattern::Substring("token is invalid"),
+    FailurePattern::Substring("invalid credentials"),
+    FailurePattern::Substring("checksum mismatch"),
     // Dep-resolution failures. These fire when cargo cannot find a
     // required dep on the registry — the exact failure mode of a wrong
     // publish order (#173). Without these the classifier falls through
     // to Ambiguous and the retry loop hides the real Cargo stderr.
-    "failed to select a version for the requirement",
-    "no matching package named",
-    "candidate versions found which didn't match",
-    "required dependency is missing from the registry",
+    FailurePattern::Substring("failed to select a version for the requirement"),
+    FailurePattern::Substring("no matching package named"),
+    FailurePattern::Substring("candidate versions found which didn't match"),
+    FailurePattern::Substring("required dependency is missing from the registry"),
 ];
 
 /// Classify cargo publish output into retry behavior categories.
@@ -139,7 +152,7 @@ pub fn classify_publish_failure(stderr: &str, stdout: &str) -> CargoFailureOutco
 
     if PERMANENT_PATTERNS
         .iter()
-        .any(|pattern| haystack.contains(pattern))
+        .any(|pattern| pattern.matches(&haystack))
     {
         return CargoFailureOutcome {
             class: CargoFailureClass::Permanent,
@@ -427,6 +440,21 @@ mod tests {
         assert_eq!(o.class, CargoFailureClass::Permanent);
     }
 
+    #[test]
+    fn permanent_already_exists_on_index() {
+        let o = classify_publish_failure(
+            "error: crate my-crate@1.2.3 already exists on crates.io index",
+            "",
+        );
+        assert_eq!(o.class, CargoFailureClass::Permanent);
+    }
+
+    #[test]
+    fn local_already_exists_is_not_permanent() {
+        let o = classify_publish_failure("error: destination `/tmp/out` already exists", "");
+        assert_eq!(o.class, CargoFailureClass::Ambiguous);
+    }
+
     #[test]
     fn permanent_already_uploaded() {
         let o = classify_publish_failure("crate version 1.0.0 is already uploaded", "");
@@ -1149,7 +1177,7 @@ mod tests {
         // However, some permanent patterns are substrings of retryable patterns
         // (e.g. "invalid" appears in both), so we skip patterns that overlap.
         for pattern in &PERMANENT_PATTERNS {
-            let o = classify_publish_failure(pattern, "");
+            let o = classify_publish_failure(pattern.as_str(), "");
             assert_ne!(
                 o.class,
                 CargoFailureClass::Ambiguous,
@@ -1175,7 +1203,7 @@ mod tests {
         let results: Vec<_> = PERMANENT_PATTERNS
             .iter()
             .map(|p| {
-                let o = classify_publish_failure(p, "");
+                let o = classify_publish_failure(p.as_str(), "");
                 format!("{p} => {:?}", o.class)
             })
             .collect();
@@ -1695,7 +1723,7 @@ mod property_tests {
         fn retryable_pattern_survives_noise(
             prefix in ascii_text(),
             suffix in ascii_text(),
-            idx in 0..20usize,
+            idx in 0..22usize,
         ) {
             let pattern = RETRYABLE_PATTERNS[idx];
             let stderr = format!("{prefix} {pattern} {suffix}");
@@ -1724,7 +1752,7 @@ mod property_tests {
         /// positions, retryable always wins regardless of ordering.
         #[test]
         fn retryable_always_dominates_permanent(
-            r_idx in 0..20usize,
+            r_idx in 0..22usize,
             p_idx in 0..22usize,
             sep in "[a-z ]{1,20}",
         ) {
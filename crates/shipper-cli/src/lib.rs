@@ -36,7 +36,8 @@ use clap::{Command, CommandFactory, FromArgMatches, Parser, Subcommand};
 use clap_complete::Shell;
 use serde::Serialize;
 
-use shipper_core::config::{CliOverrides, ShipperConfig};
+use shipper_core::cargo::redact_sensitive;
+use shipper_core::config::{self, CliOverrides, ShipperConfig};
 use shipper_core::engine::{self, Reporter};
 use shipper_core::plan;
 use shipper_core::runtime::execution::pkg_key;
@@ -124,6 +125,22 @@ struct Cli {
     #[arg(long, global = true)]
     no_verify: bool,
 
+    /// Rustup toolchain for cargo commands (runs `cargo +<TOOLCHAIN>`).
+    #[arg(long, global = true)]
+    cargo_toolchain: Option<String>,
+
+    /// Features to enable, scoped as <package>/<feature> (comma-separated).
+    #[arg(long, global = true)]
+    features: Option<String>,
+
+    /// Pass --no-default-features to cargo publish.
+    #[arg(long, global = true)]
+    no_default_features: bool,
+
+    /// Pass --all-features to cargo publish.
+    #[arg(long, global = true)]
+    all_features: bool,
+
     /// Max attempts per crate publish step (default: 6)
     #[arg(long, global = true)]
     max_attempts: Option<u32>,
@@ -184,7 +201,7 @@ struct Cli {
     #[arg(long, global = true)]
     policy: Option<String>,
 
-    /// Verify mode: workspace (default), package (per-crate), none (no verify)
+    /// Verify mode: workspace (default), package (per-crate), package-just-in-time (per-crate, right before each publish), none (no verify)
     #[arg(long, global = true)]
     verify_mode: Option<String>,
 
@@ -192,7 +209,7 @@ struct Cli {
     #[arg(long, global = true)]
     parallel: bool,
 
-    /// Maximum number of concurrent publish operations (implies --parallel)
+    /// Maximum number of concurrent publish operations; 0 = one per CPU (implies --parallel)
     #[arg(long, global = true)]
     max_concurrent: Option<usize>,
 
@@ -284,6 +301,10 @@ const ADVANCED_RELEASE_ARG_IDS: &[&str] = &[
     "skip_ownership_check",
     "strict_ownership",
     "no_verify",
+    "cargo_toolchain",
+    "features",
+    "no_default_features",
+    "all_features",
     "max_attempts",
     "base_delay",
     "max_delay",
@@ -918,23 +939,36 @@ pub fn run() -> Result<std::process::ExitCode> {
         .with_context(|| plan_failure_hint(&spec.manifest_path, &cli.packages, command_name))?;
 
     // Load configuration file
-    let config =
-        if let Some(ref config_path) = cli.config {
-            // Use custom config file specified via --config
-            Some(ShipperConfig::load_from_file(config_path).with_context(|| {
-                format!("Failed to load config from: {}", config_path.display())
-            })?)
-        } else {
-            // Try to load .shipper.toml from workspace root
-            ShipperConfig::load_from_workspace(&planned.workspace_root)
-                .with_context(|| "Failed to load config from workspace")?
-        };
+    let ignored_config_files = if cli.config.is_none() {
+        config::ignored_config_files(&planned.workspace_root)
+    } else {
+        Vec::new()
+    };
+    let config = if let Some(ref config_path) = cli.config {
+        // Use custom config file specified via --config
+        let profile = std::env::var(config::PROFILE_ENV)
+            .ok()
+            .filter(|name| !name.is_empty());
+        Some(
+            ShipperConfig::load_from_file_with_profile(config_path, profile.as_deref())
+                .with_context(|| {
+                    format!("Failed to load config from: {}", config_path.display())
+                })?,
+        )
+    } else {
+        // Try to load the workspace config file (.shipper.toml, or its
+        // YAML/JSON equivalent) from the workspace root, applying
+        // the SHIPPER_PROFILE profile if one is set
+        config::load_config_with_profile(&planned.workspace_root, None)
+            .with_context(|| "Failed to load config from workspace")?
+    };
 
     // Validate loaded configuration before using it for runtime options.
     if let Some(ref cfg) = config {
         let config_path = cli
             .config
             .clone()
+            .or_else(|| config::find_config_file(&planned.workspace_root))
             .unwrap_or_else(|| planned.workspace_root.join(".shipper.toml"));
         cfg.validate().with_context(|| {
             format!(
@@ -1006,6 +1040,10 @@ pub fn run() -> Result<std::process::ExitCode> {
         skip_ownership_check: cli.skip_ownership_check,
         strict_ownership: cli.strict_ownership,
         no_verify: cli.no_verify,
+        cargo_toolchain: cli.cargo_toolchain.clone(),
+        features: cli.features.as_deref().map(parse_features).transpose()?,
+        no_default_features: cli.no_default_features,
+        all_features: cli.all_features,
         no_readiness: cli.no_readiness,
         force: cli.force,
         force_resume: cli.force_resume,
@@ -1039,6 +1077,20 @@ pub fn run() -> Result<std::process::ExitCode> {
 
     let mut reporter = CliReporter::new(cli.quiet);
 
+    if let Some(chosen) = config::find_config_file(&planned.workspace_root)
+        && !ignored_config_files.is_empty()
+    {
+        let ignored: Vec<String> = ignored_config_files
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        reporter.warn(&format!(
+            "multiple config files found; using {} and ignoring {}",
+            chosen.display(),
+            ignored.join(", ")
+        ));
+    }
+
     match cli.cmd.expect("subcommand checked above") {
         Commands::Plan => {
             print_plan(&planned, cli.verbose, &cli.format);
@@ -1365,7 +1417,7 @@ pub fn run() -> Result<std::process::ExitCode> {
                         },
                         package: format!("{}@{}", entry.name, entry.version),
                     });
-                    if let Err(err) = log.write_to_file(&events_file) {
+                    if let Err(err) = log.write_to_file_redacted(&events_file, redact_sensitive) {
                         reporter.warn(&format!(
                             "failed to append PackageYanked event to {}: {err:#}",
                             events_file.display()
@@ -1466,7 +1518,7 @@ pub fn run() -> Result<std::process::ExitCode> {
                 package: format!("{crate_name}@{version}"),
             });
             let events_file = events_path(&opts.state_dir);
-            if let Err(err) = log.write_to_file(&events_file) {
+            if let Err(err) = log.write_to_file_redacted(&events_file, redact_sensitive) {
                 reporter.warn(&format!(
                     "failed to append PackageYanked event to {}: {err:#}",
                     events_file.display()
@@ -1735,7 +1787,7 @@ pub fn run() -> Result<std::process::ExitCode> {
                         },
                         package: format!("{}@{}", step.name, step.version),
                     });
-                    if let Err(err) = log.write_to_file(&events_file) {
+                    if let Err(err) = log.write_to_file_redacted(&events_file, redact_sensitive) {
                         reporter.warn(&format!(
                             "failed to append PackageYanked event to {}: {err:#}",
                             events_file.display()
@@ -2014,6 +2066,17 @@ fn parse_duration(s: &str) -> Result<Duration> {
     shipper_duration::parse_duration(s).with_context(|| format!("invalid duration: {s}"))
 }
 
+fn parse_features(s: &str) -> Result<Vec<String>> {
+    let features: Vec<String> = s
+        .split([',', ' '])
+        .map(str::trim)
+        .filter(|f| !f.is_empty())
+        .map(str::to_string)
+        .collect();
+    config::validate_features(&features).context("invalid --features")?;
+    Ok(features)
+}
+
 fn parse_policy(s: &str) -> Result<shipper_core::config::PublishPolicy> {
     match s.to_lowercase().as_str() {
         "safe" => Ok(shipper_core::config::PublishPolicy::Safe),
@@ -2027,8 +2090,13 @@ fn parse_verify_mode(s: &str) -> Result<shipper_core::config::VerifyMode> {
     match s.to_lowercase().as_str() {
         "workspace" => Ok(shipper_core::config::VerifyMode::Workspace),
         "package" => Ok(shipper_core::config::VerifyMode::Package),
+        "package-just-in-time" | "package_just_in_time" => {
+            Ok(shipper_core::config::VerifyMode::PackageJustInTime)
+        }
         "none" => Ok(shipper_core::config::VerifyMode::None),
-        _ => bail!("invalid verify-mode: {s} (expected: workspace, package, none)"),
+        _ => bail!(
+            "invalid verify-mode: {s} (expected: workspace, package, package-just-in-time, none)"
+        ),
     }
 }
 
@@ -2864,6 +2932,7 @@ struct PublishJsonReport<'a> {
     ambiguous: usize,
     uploaded: usize,
     skipped: usize,
+    cancelled: usize,
     packages: Vec<CommandJsonPackageReport>,
     artifacts: CommandJsonArtifacts,
     receipt: &'a shipper_core::types::Receipt,
@@ -2884,6 +2953,7 @@ struct ResumeJsonReport<'a> {
     ambiguous: usize,
     uploaded: usize,
     skipped: usize,
+    cancelled: usize,
     next_package: Option<String>,
     packages: Vec<CommandJsonPackageReport>,
     artifacts: CommandJsonArtifacts,
@@ -2897,6 +2967,7 @@ struct CommandJsonPackageCounts {
     ambiguous: usize,
     uploaded: usize,
     skipped: usize,
+    cancelled: usize,
     next_package: Option<String>,
 }
 
@@ -2982,8 +3053,11 @@ fn build_publish_json_report<'a>(
     let reconciled = reconciled_packages(state_dir)?;
     let packages = command_package_reports(receipt, &reconciled);
     let counts = command_package_counts(receipt);
-    let safe_to_rerun =
-        counts.pending == 0 && counts.failed == 0 && counts.ambiguous == 0 && counts.uploaded == 0;
+    let safe_to_rerun = counts.pending == 0
+        && counts.failed == 0
+        && counts.ambiguous == 0
+        && counts.uploaded == 0
+        && counts.cancelled == 0;
 
     Ok(PublishJsonReport {
         schema_version: "shipper.publish.v1",
@@ -2999,6 +3073,7 @@ fn build_publish_json_report<'a>(
         ambiguous: counts.ambiguous,
         uploaded: counts.uploaded,
         skipped: counts.skipped,
+        cancelled: counts.cancelled,
         packages,
         artifacts: command_json_artifacts(state_dir),
         receipt,
@@ -3028,6 +3103,7 @@ fn build_resume_json_report<'a>(
         ambiguous: counts.ambiguous,
         uploaded: counts.uploaded,
         skipped: counts.skipped,
+        cancelled: counts.cancelled,
         next_package: counts.next_package,
         packages,
         artifacts: command_json_artifacts(state_dir),
@@ -3043,6 +3119,7 @@ fn command_package_counts(receipt: &shipper_core::types::Receipt) -> CommandJson
         ambiguous: 0,
         uploaded: 0,
         skipped: 0,
+        cancelled: 0,
         next_package: None,
     };
 
@@ -3078,6 +3155,12 @@ fn command_package_counts(receipt: &shipper_core::types::Receipt) -> CommandJson
                     .next_package
                     .get_or_insert_with(|| package.name.clone());
             }
+            PackageState::Cancelled { .. } => {
+                counts.cancelled += 1;
+                counts
+                    .next_package
+                    .get_or_insert_with(|| package.name.clone());
+            }
         }
     }
 
@@ -3145,6 +3228,7 @@ fn package_state_name(state: &PackageState) -> &'static str {
         PackageState::Skipped { .. } => "skipped",
         PackageState::Failed { .. } => "failed",
         PackageState::Ambiguous { .. } => "ambiguous",
+        PackageState::Cancelled { .. } => "cancelled",
     }
 }
 
@@ -3491,6 +3575,9 @@ fn run_inspect_receipt(
             shipper_core::types::PackageState::Ambiguous { message } => {
                 &format!("\x1b[33mAmbiguous: {}\x1b[0m", message)
             }
+            shipper_core::types::PackageState::Cancelled { at_state } => {
+                &format!("\x1b[33mCancelled (while {})\x1b[0m", at_state)
+            }
         };
         println!(
             "  {}@{}: {} (attempts={}, {}ms)",
@@ -3932,6 +4019,7 @@ fn event_type_clears_next_action(event_type: &EventType) -> bool {
             | EventType::PackagePublished { .. }
             | EventType::PackageFailed { .. }
             | EventType::PackageSkipped { .. }
+            | EventType::PackageCancelled { .. }
             | EventType::PublishReconciled { .. }
             | EventType::ReadinessComplete { .. }
             | EventType::ReadinessTimeout { .. }
@@ -4027,6 +4115,7 @@ fn package_state_label(state: &PackageState) -> &'static str {
         PackageState::Skipped { .. } => "skipped",
         PackageState::Failed { .. } => "failed",
         PackageState::Ambiguous { .. } => "ambiguous",
+        PackageState::Cancelled { .. } => "cancelled",
     }
 }
 
@@ -4042,6 +4131,7 @@ fn event_type_name(event_type: &EventType) -> &'static str {
         EventType::PackagePublished { .. } => "package_published",
         EventType::PackageFailed { .. } => "package_failed",
         EventType::PackageSkipped { .. } => "package_skipped",
+        EventType::PackageCancelled { .. } => "package_cancelled",
         EventType::PublishWaiting { .. } => "publish_waiting",
         EventType::RateLimitObserved { .. } => "rate_limit_observed",
         EventType::PublishReconciling { .. } => "publish_reconciling",
@@ -4085,6 +4175,7 @@ fn summarize_event(event: &PublishEvent) -> String {
         }
         EventType::PackageFailed { class, message } => format!("failed ({:?}): {}", class, message),
         EventType::PackageSkipped { reason } => format!("skipped: {}", reason),
+        EventType::PackageCancelled { at_state } => format!("cancelled while {}", at_state),
         EventType::PublishWaiting {
             reason, delay_ms, ..
         } => {
@@ -4528,6 +4619,15 @@ mod tests {
         assert!(parse_duration("nope").is_err());
     }
 
+    #[test]
+    fn parse_features_requires_package_scope() {
+        assert_eq!(
+            parse_features("core/std, macros/nightly").expect("features"),
+            ["core/std", "macros/nightly"]
+        );
+        assert!(parse_features("std").is_err());
+    }
+
     #[test]
     fn exit_code_for_result_maps_correctly() {
         use std::process::ExitCode;
@@ -4864,6 +4964,76 @@ mod tests {
         assert_eq!(reporter.errors, vec!["e".to_string()]);
     }
 
+    fn receipt_with_states(states: &[(&str, PackageState)]) -> shipper_core::types::Receipt {
+        let now = Utc::now();
+        shipper_core::types::Receipt {
+            receipt_version: "shipper.receipt.v2".to_string(),
+            plan_id: "plan".to_string(),
+            registry: Registry::crates_io(),
+            started_at: now,
+            finished_at: now,
+            packages: states
+                .iter()
+                .map(|(name, state)| shipper_core::types::PackageReceipt {
+                    name: name.to_string(),
+                    version: "1.0.0".to_string(),
+                    attempts: 1,
+                    state: state.clone(),
+                    started_at: now,
+                    finished_at: now,
+                    duration_ms: 0,
+                    evidence: shipper_core::types::PackageEvidence {
+                        attempts: vec![],
+                        readiness_checks: vec![],
+                    },
+                    compromised_at: None,
+                    compromised_by: None,
+                    superseded_by: None,
+                    artifact_sha256: None,
+                })
+                .collect(),
+            event_log_path: PathBuf::from(".shipper/events.jsonl"),
+            git_context: None,
+            environment: shipper_core::types::EnvironmentFingerprint {
+                shipper_version: "0.0.0".to_string(),
+                cargo_version: None,
+                rust_version: None,
+                os: "linux".to_string(),
+                arch: "x86_64".to_string(),
+            },
+            auth_evidence: None,
+            execution_result: ExecutionResult::PartialFailure,
+        }
+    }
+
+    #[test]
+    fn json_reports_count_cancelled_packages_separately() {
+        let td = tempdir().expect("tempdir");
+        let receipt = receipt_with_states(&[
+            ("a", PackageState::Published),
+            (
+                "b",
+                PackageState::Cancelled {
+                    at_state: "uploaded".to_string(),
+                },
+            ),
+            ("c", PackageState::Pending),
+        ]);
+
+        let publish = build_publish_json_report(&receipt, td.path()).expect("publish report");
+        assert_eq!(publish.published, 1);
+        assert_eq!(publish.cancelled, 1);
+        assert_eq!(publish.pending, 1);
+        assert_eq!(publish.failed, 0);
+        assert!(!publish.safe_to_rerun);
+        assert_eq!(publish.packages[1].state, "cancelled");
+
+        let resume = build_resume_json_report(&receipt, td.path()).expect("resume report");
+        assert_eq!(resume.cancelled, 1);
+        assert!(resume.safe_to_resume);
+        assert_eq!(resume.next_package.as_deref(), Some("b"));
+    }
+
     #[test]
     fn status_watch_report_summarizes_state_and_scheduled_events() {
         let td = tempdir().expect("tempdir");
@@ -4871,6 +5041,7 @@ mod tests {
         let now = Utc::now();
         let ws = plan::PlannedWorkspace {
             workspace_root: td.path().to_path_buf(),
+            target_directory: td.path().join("target"),
             plan: ReleasePlan {
                 plan_version: "shipper.plan.v1".to_string(),
                 plan_id: "plan-watch".to_string(),
@@ -5052,6 +5223,7 @@ mod tests {
         let td = tempdir().expect("tempdir");
         let ws = plan::PlannedWorkspace {
             workspace_root: td.path().to_path_buf(),
+            target_directory: td.path().join("target"),
             plan: shipper_core::types::ReleasePlan {
                 plan_version: "1".to_string(),
                 plan_id: "plan-x".to_string(),
@@ -5069,6 +5241,10 @@ mod tests {
             skip_ownership_check: true,
             strict_ownership: false,
             no_verify: false,
+            features: Vec::new(),
+            no_default_features: false,
+            all_features: false,
+            cargo: Default::default(),
             max_attempts: 1,
             base_delay: Duration::from_millis(0),
             max_delay: Duration::from_millis(0),
@@ -5125,6 +5301,7 @@ mod tests {
         let td = tempdir().expect("tempdir");
         let ws = plan::PlannedWorkspace {
             workspace_root: td.path().to_path_buf(),
+            target_directory: td.path().join("target"),
             plan: shipper_core::types::ReleasePlan {
                 plan_version: "1".to_string(),
                 plan_id: "plan-y".to_string(),
@@ -5141,6 +5318,10 @@ mod tests {
             skip_ownership_check: true,
             strict_ownership: false,
             no_verify: false,
+            features: Vec::new(),
+            no_default_features: false,
+            all_features: false,
+            cargo: Default::default(),
             max_attempts: 1,
             base_delay: Duration::from_millis(0),
             max_delay: Duration::from_millis(0),
@@ -5374,6 +5555,7 @@ mode = "fast"
             registry: None,
             registries: shipper_core::config::MultiRegistryConfig::default(),
             parallel: shipper_core::config::ParallelConfig::default(),
+            cargo: shipper_core::config::CargoConfig::default(),
             webhook: shipper_core::config::WebhookConfig::default(),
             encryption: shipper_core::config::EncryptionConfigInner::default(),
             storage: shipper_core::config::StorageConfigInner::default(),
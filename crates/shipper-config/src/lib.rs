@@ -1,12 +1,14 @@
 //! # Configuration
 //!
-//! Project-specific configuration for Shipper via `.shipper.toml`.
+//! Project-specific configuration for Shipper via `.shipper.toml` (or its
+//! YAML and JSON equivalents).
 //!
 //! This crate loads, validates, and merges configuration from three layers
 //! (highest priority first):
 //!
 //! 1. **CLI flags** — passed via [`CliOverrides`]
-//! 2. **Config file** — `.shipper.toml` in the workspace root
+//! 2. **Config file** — `.shipper.toml` in the workspace root, see
+//!    [`CONFIG_FILE_NAMES`] for the YAML and JSON alternatives
 //! 3. **Built-in defaults** — sensible defaults for all settings
 //!
 //! The central type is [`ShipperConfig`], which maps 1:1 to the TOML file
@@ -30,6 +32,22 @@
 //! | `[webhook]`     | [`WebhookConfig`]      | Publish notifications                 |
 //! | `[encryption]`  | [`EncryptionConfigInner`] | State file encryption              |
 //! | `[storage]`     | [`StorageConfigInner`] | Cloud storage backend                 |
+//! | `[profile.<name>]` | same shape as the file | Named overrides, see below         |
+//!
+//! ## Profiles
+//!
+//! A `[profile.<name>]` table (e.g. `[profile.ci.parallel]`) overrides the
+//! top-level settings when that profile is selected, via
+//! [`load_config_with_profile`] or the `SHIPPER_PROFILE` environment
+//! variable. Without a selected profile the tables are ignored.
+//!
+//! ## Formats
+//!
+//! The same settings can be written as `.shipper.yaml`, `.shipper.yml` or
+//! `.shipper.json`; the file extension picks the parser and every format
+//! deserializes into the same [`ShipperConfig`]. When a workspace holds more
+//! than one, the first in [`CONFIG_FILE_NAMES`] wins;
+//! [`ignored_config_files`] names the others so the caller can warn.
 
 use std::path::{Path, PathBuf};
 use std::time::Duration;
@@ -268,6 +286,58 @@ pub struct FlagsConfig {
     pub strict_ownership: bool,
 }
 
+/// `[cargo]` section: how shipper invokes cargo.
+///
+/// ```toml
+/// [cargo]
+/// toolchain = "nightly"
+/// leading_args = ["-Zpackage-workspace"]
+/// features = ["my-crate/tls", "my-macros/nightly"]
+/// no_default_features = false
+/// all_features = false
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct CargoConfig {
+    /// Rustup toolchain, run as `cargo +<toolchain>`.
+    #[serde(default)]
+    pub toolchain: Option<String>,
+
+    /// Arguments placed before the subcommand (e.g. `-Z` flags).
+    #[serde(default)]
+    pub leading_args: Vec<String>,
+
+    /// Arguments appended after shipper's own.
+    #[serde(default)]
+    pub trailing_args: Vec<String>,
+
+    /// Features to enable, each scoped as `<package>/<feature>`; a crate
+    /// only receives its own entries.
+    #[serde(default)]
+    pub features: Vec<String>,
+
+    /// Pass `--no-default-features` to every crate's publish.
+    #[serde(default)]
+    pub no_default_features: bool,
+
+    /// Pass `--all-features` to every crate's publish.
+    #[serde(default)]
+    pub all_features: bool,
+}
+
+/// Check that every feature is scoped as `<package>/<feature>`.
+///
+/// The list is shared by every crate in the plan, so a bare name would be
+/// forced onto crates that do not define it.
+pub fn validate_features(features: &[String]) -> Result<()> {
+    for feature in features.iter().map(|f| f.trim()).filter(|f| !f.is_empty()) {
+        match feature.split_once('/') {
+            Some((package, name)) if !package.is_empty() && !name.is_empty() => {}
+            _ => bail!("feature `{feature}` must be scoped as `<package>/<feature>`"),
+        }
+    }
+    Ok(())
+}
+
 /// Project-specific configuration loaded from `.shipper.toml`.
 ///
 /// This is the root deserialization target for the config file.  Each
@@ -315,6 +385,10 @@ pub struct ShipperConfig {
     #[serde(default)]
     pub parallel: ParallelConfig,
 
+    /// Cargo toolchain, extra arguments and feature selection
+    #[serde(default)]
+    pub cargo: CargoConfig,
+
     /// Optional custom state directory
     #[serde(default)]
     pub state_dir: Option<PathBuf>,
@@ -483,6 +557,12 @@ pub struct CliOverrides {
     pub skip_ownership_check: bool,
     pub strict_ownership: bool,
     pub no_verify: bool,
+    /// Replaces `[cargo] toolchain` when set.
+    pub cargo_toolchain: Option<String>,
+    /// Replaces `[cargo] features` when set.
+    pub features: Option<Vec<String>>,
+    pub no_default_features: bool,
+    pub all_features: bool,
     pub no_readiness: bool,
     pub force: bool,
     pub force_resume: bool,
@@ -544,6 +624,7 @@ impl Default for ShipperConfig {
                 strict_ownership: false,
             },
             parallel: ParallelConfig::default(),
+            cargo: CargoConfig::default(),
             state_dir: None,
             registry: None,
             registries: MultiRegistryConfig::default(),
@@ -579,25 +660,196 @@ fn default_max_delay() -> Duration {
     Duration::from_mins(2) // 2 minutes
 }
 
+/// Environment variable naming the config profile to apply when the caller
+/// does not pick one.
+pub const PROFILE_ENV: &str = "SHIPPER_PROFILE";
+
+/// Config file names looked up in the workspace root, highest precedence
+/// first.
+pub const CONFIG_FILE_NAMES: [&str; 4] = [
+    ".shipper.toml",
+    ".shipper.yaml",
+    ".shipper.yml",
+    ".shipper.json",
+];
+
+/// The config file `workspace_root` would load, if any.
+///
+/// Picks the first of [`CONFIG_FILE_NAMES`] that exists.
+pub fn find_config_file(workspace_root: &Path) -> Option<PathBuf> {
+    config_files_in(workspace_root).into_iter().next()
+}
+
+/// Config files in `workspace_root` that [`find_config_file`] passes over:
+/// every existing entry of [`CONFIG_FILE_NAMES`] after the one it picks.
+pub fn ignored_config_files(workspace_root: &Path) -> Vec<PathBuf> {
+    config_files_in(workspace_root)
+        .into_iter()
+        .skip(1)
+        .collect()
+}
+
+fn config_files_in(workspace_root: &Path) -> Vec<PathBuf> {
+    CONFIG_FILE_NAMES
+        .iter()
+        .map(|name| workspace_root.join(name))
+        .filter(|path| path.exists())
+        .collect()
+}
+
+/// Config file syntax, picked from the file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConfigFormat {
+    Toml,
+    Yaml,
+    Json,
+}
+
+impl ConfigFormat {
+    /// `.yaml`/`.yml` and `.json` select those parsers; anything else,
+    /// including a custom `--config` name, is read as TOML.
+    fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml") => {
+                Self::Yaml
+            }
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Self::Json,
+            _ => Self::Toml,
+        }
+    }
+
+    /// Parse `content` straight into `T`, keeping the parser's own error
+    /// positions.
+    fn parse<T: serde::de::DeserializeOwned>(self, content: &str) -> Result<T> {
+        Ok(match self {
+            Self::Toml => toml::from_str(content)?,
+            Self::Yaml => serde_norway::from_str(content)?,
+            Self::Json => serde_json::from_str(content)?,
+        })
+    }
+}
+
+/// Load the workspace config file with a profile overlaid.
+///
+/// The file is the first of [`CONFIG_FILE_NAMES`] in `workspace_root`.
+/// `profile` falls back to [`PROFILE_ENV`]; with neither set, only the
+/// top-level settings apply. Returns `Ok(None)` if no config file exists.
+/// See [`ShipperConfig::load_from_file_with_profile`] for the overlay rules.
+pub fn load_config_with_profile(
+    workspace_root: &Path,
+    profile: Option<&str>,
+) -> Result<Option<ShipperConfig>> {
+    let Some(config_path) = find_config_file(workspace_root) else {
+        return Ok(None);
+    };
+    let from_env = std::env::var(PROFILE_ENV)
+        .ok()
+        .filter(|name| !name.is_empty());
+    let profile = profile.or(from_env.as_deref());
+    ShipperConfig::load_from_file_with_profile(&config_path, profile).map(Some)
+}
+
+/// Replace `table`'s `profile` section with the settings of profile `name`
+/// merged over the top level.
+///
+/// Works on a JSON object whatever the file format, so YAML and JSON nulls
+/// survive the overlay.
+fn apply_profile(table: &mut JsonTable, name: &str, path: &Path) -> Result<()> {
+    let mut profiles = match table.remove("profile") {
+        None => JsonTable::new(),
+        Some(serde_json::Value::Object(profiles)) => profiles,
+        Some(_) => bail!(
+            "`profile` must be a table of named profiles in file: {}",
+            path.display()
+        ),
+    };
+    match profiles.remove(name) {
+        Some(serde_json::Value::Object(overlay)) => {
+            merge_tables(table, overlay);
+            Ok(())
+        }
+        Some(_) => bail!("profile.{name} must be a table in file: {}", path.display()),
+        None => {
+            let available: Vec<&str> = profiles.keys().map(String::as_str).collect();
+            bail!(
+                "unknown config profile `{name}` in {}; available profiles: {}",
+                path.display(),
+                if available.is_empty() {
+                    "(none defined)".to_string()
+                } else {
+                    available.join(", ")
+                }
+            )
+        }
+    }
+}
+
+type JsonTable = serde_json::Map<String, serde_json::Value>;
+
+/// Merge `overlay` into `base`: tables merge recursively, other values in
+/// `overlay` replace those in `base`.
+fn merge_tables(base: &mut JsonTable, overlay: JsonTable) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (
+                Some(serde_json::Value::Object(base_table)),
+                serde_json::Value::Object(overlay_table),
+            ) => {
+                merge_tables(base_table, overlay_table);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
 impl ShipperConfig {
-    /// Load configuration from workspace root by searching for .shipper.toml
+    /// Load configuration from workspace root by searching for the first of
+    /// [`CONFIG_FILE_NAMES`]
     ///
     /// Returns `Ok(None)` if no config file exists.
     pub fn load_from_workspace(workspace_root: &Path) -> Result<Option<Self>> {
-        let config_path = workspace_root.join(".shipper.toml");
-        if !config_path.exists() {
+        let Some(config_path) = find_config_file(workspace_root) else {
             return Ok(None);
-        }
+        };
         Self::load_from_file(&config_path).map(Some)
     }
 
     /// Load configuration from a specific file path
+    ///
+    /// `.yaml`/`.yml` and `.json` files are parsed as YAML and JSON; any
+    /// other extension is parsed as TOML.
     pub fn load_from_file(path: &Path) -> Result<Self> {
+        Self::load_from_file_with_profile(path, None)
+    }
+
+    /// Load configuration from `path`, overlaying the `[profile.<name>]`
+    /// table when `profile` is set.
+    ///
+    /// A profile table has the same shape as the file itself. It is merged
+    /// key by key over the top-level settings: nested tables merge
+    /// recursively, anything else (including arrays) is replaced. Naming a
+    /// profile the file does not define is an error that lists the ones it
+    /// does.
+    pub fn load_from_file_with_profile(path: &Path, profile: Option<&str>) -> Result<Self> {
         let content = std::fs::read_to_string(path)
             .with_context(|| format!("Failed to read config file: {}", path.display()))?;
 
-        let config: ShipperConfig = toml::from_str(&content)
-            .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+        let format = ConfigFormat::from_path(path);
+        let config: ShipperConfig = match profile {
+            // Parse straight from the text so errors keep line and column.
+            None => format.parse(&content),
+            Some(name) => {
+                let mut table: JsonTable = format
+                    .parse(&content)
+                    .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+                apply_profile(&mut table, name, path)?;
+                serde_json::from_value(serde_json::Value::Object(table))
+                    .map_err(anyhow::Error::from)
+            }
+        }
+        .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
 
         // Validate schema version
         if let Err(e) = shipper_types::schema::validate_schema_version(
@@ -659,15 +911,16 @@ impl ShipperConfig {
             bail!("readiness.jitter_factor must be between 0.0 and 1.0");
         }
 
-        // Validate parallel config
-        if self.parallel.max_concurrent == 0 {
-            bail!("parallel.max_concurrent must be greater than 0");
-        }
-
+        // Validate parallel config (`max_concurrent = 0` means auto)
         if self.parallel.per_package_timeout.is_zero() {
             bail!("parallel.per_package_timeout must be greater than 0");
         }
 
+        validate_features(&self.cargo.features).context("invalid cargo.features")?;
+        if self.cargo.all_features && !self.cargo.features.is_empty() {
+            bail!("cargo.all_features cannot be combined with cargo.features");
+        }
+
         // Validate registry if present
         if let Some(ref registry) = self.registry {
             if registry.name.is_empty() {
@@ -723,7 +976,7 @@ schema_version = "shipper.config.v1"
 mode = "safe"
 
 [verify]
-# Verify mode: workspace (default, safest), package (per-crate), or none (no verify)
+# Verify mode: workspace (default, safest), package (per-crate), package_just_in_time, or none (no verify)
 mode = "workspace"
 
 [readiness]
@@ -795,11 +1048,23 @@ strict_ownership = false
 [parallel]
 # Enable parallel publishing (default: false for sequential)
 enabled = false
-# Maximum number of concurrent publish operations (default: 4)
+# Maximum number of concurrent publish operations (default: 4; 0 = one per CPU)
 max_concurrent = 4
 # Timeout per package publish operation (default: 30 minutes)
 per_package_timeout = "30m"
 
+# Optional: Cargo toolchain, extra arguments and feature selection
+# [cargo]
+# Rustup toolchain, run as `cargo +<toolchain>`
+# toolchain = "stable"
+# Arguments placed before / after shipper's own cargo arguments
+# leading_args = []
+# trailing_args = []
+# Features to enable, scoped as "<package>/<feature>"
+# features = ["my-crate/tls"]
+# no_default_features = false
+# all_features = false
+
 # Optional: Custom registry configuration
 # [registry]
 # name = "crates-io"
@@ -1226,6 +1491,78 @@ enabled = true
         assert_eq!(opts2.parallel.max_concurrent, 2); // from CLI
     }
 
+    #[test]
+    fn test_build_runtime_options_cargo_features_merge() {
+        let config: ShipperConfig = toml::from_str(
+            r#"
+[cargo]
+features = ["core/std", "macros/nightly"]
+no_default_features = true
+"#,
+        )
+        .expect("parse");
+        config.validate().expect("valid");
+
+        let opts = config.build_runtime_options(CliOverrides::default());
+        assert_eq!(opts.features, ["core/std", "macros/nightly"]);
+        assert!(opts.no_default_features);
+        assert!(!opts.all_features);
+
+        // CLI replaces the list; booleans are ORed
+        let opts = config.build_runtime_options(CliOverrides {
+            features: Some(vec!["core/alloc".to_string()]),
+            all_features: true,
+            ..Default::default()
+        });
+        assert_eq!(opts.features, ["core/alloc"]);
+        assert!(opts.no_default_features && opts.all_features);
+    }
+
+    #[test]
+    fn test_build_runtime_options_cargo_invocation_merge() {
+        let config: ShipperConfig = toml::from_str(
+            r#"
+[cargo]
+toolchain = "stable"
+leading_args = ["-Zunstable-options"]
+trailing_args = ["--locked"]
+"#,
+        )
+        .expect("parse");
+
+        let opts = config.build_runtime_options(CliOverrides::default());
+        assert_eq!(opts.cargo.toolchain.as_deref(), Some("stable"));
+        assert_eq!(opts.cargo.leading_args, ["-Zunstable-options"]);
+        assert_eq!(opts.cargo.trailing_args, ["--locked"]);
+
+        let opts = config.build_runtime_options(CliOverrides {
+            cargo_toolchain: Some("nightly".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(opts.cargo.toolchain.as_deref(), Some("nightly"));
+        assert_eq!(opts.cargo.trailing_args, ["--locked"]);
+    }
+
+    #[test]
+    fn test_validate_rejects_unscoped_or_conflicting_features() {
+        let mut config = ShipperConfig::default();
+        config.cargo.features = vec!["std".to_string()];
+        let err = config.validate().expect_err("bare feature");
+        assert!(
+            format!("{err:#}").contains("feature `std` must be scoped as `<package>/<feature>`"),
+            "{err:#}"
+        );
+
+        config.cargo.features = vec!["core/std".to_string()];
+        config.cargo.all_features = true;
+        let err = config.validate().expect_err("all_features + features");
+        assert!(err.to_string().contains("cargo.all_features"), "{err}");
+
+        config.cargo.features.clear();
+        config.cargo.no_default_features = true;
+        config.validate().expect("all + no-default is allowed");
+    }
+
     mod snapshot_tests {
         use super::*;
 
@@ -1279,6 +1616,7 @@ enabled = true
                     max_concurrent: 8,
                     per_package_timeout: Duration::from_hours(1),
                 },
+                cargo: CargoConfig::default(),
                 state_dir: Some(std::path::PathBuf::from("/custom/state")),
                 registry: Some(RegistryConfig {
                     name: "my-registry".to_string(),
@@ -1543,14 +1881,6 @@ per_package_timeout = "15m"
             insta::assert_snapshot!("error_msg_readiness_jitter_out_of_range", err.to_string());
         }
 
-        #[test]
-        fn snapshot_error_message_zero_max_concurrent() {
-            let mut config = ShipperConfig::default();
-            config.parallel.max_concurrent = 0;
-            let err = config.validate().unwrap_err();
-            insta::assert_snapshot!("error_msg_zero_max_concurrent", err.to_string());
-        }
-
         #[test]
         fn snapshot_error_message_registries_empty_name() {
             let config = ShipperConfig {
@@ -1755,6 +2085,7 @@ per_package_timeout = "15m"
                             max_concurrent,
                             per_package_timeout: Duration::from_secs(per_package_timeout),
                         },
+                        cargo: CargoConfig::default(),
                         state_dir: None,
                         registry: None,
                         registries: MultiRegistryConfig::default(),
@@ -2199,10 +2530,13 @@ max_delay = "5s"
         }
 
         #[test]
-        fn parallel_zero_max_concurrent_fails_validation() {
+        fn parallel_zero_max_concurrent_means_auto() {
             let mut config = ShipperConfig::default();
+            config.parallel.enabled = true;
             config.parallel.max_concurrent = 0;
-            assert!(config.validate().is_err());
+            assert!(config.validate().is_ok());
+            let opts = config.build_runtime_options(CliOverrides::default());
+            assert!(opts.parallel.resolve_concurrency() >= 1);
         }
 
         #[test]
@@ -2650,6 +2984,15 @@ mode = "turbo"
                 Some("SHIPPER_ENCRYPT_KEY")
             );
         }
+
+        #[test]
+        fn default_config_builds_runtime_options_default() {
+            let built = ShipperConfig::default().build_runtime_options(CliOverrides::default());
+            assert_eq!(
+                serde_json::to_value(built).unwrap(),
+                serde_json::to_value(RuntimeOptions::default()).unwrap()
+            );
+        }
     }
 
     // ── Snapshot tests for defaults and policy presets ───────────────
@@ -18,6 +18,10 @@ pub fn into_runtime_options(value: RuntimeOptions) -> shipper_types::RuntimeOpti
         skip_ownership_check: value.skip_ownership_check,
         strict_ownership: value.strict_ownership,
         no_verify: value.no_verify,
+        features: value.features,
+        no_default_features: value.no_default_features,
+        all_features: value.all_features,
+        cargo: value.cargo,
         max_attempts: value.max_attempts,
         base_delay: value.base_delay,
         max_delay: value.max_delay,
@@ -63,6 +67,10 @@ mod tests {
             skip_ownership_check: false,
             strict_ownership: true,
             no_verify: false,
+            features: vec!["core/std".to_string()],
+            no_default_features: true,
+            all_features: false,
+            cargo: Default::default(),
             max_attempts: 8,
             base_delay: Duration::from_secs(2),
             max_delay: Duration::from_secs(45),
@@ -152,6 +160,8 @@ mod tests {
         assert!(converted.encryption.enabled);
         assert_eq!(converted.encryption.passphrase.as_deref(), Some("password"));
         assert_eq!(converted.registries.len(), 2);
+        assert_eq!(converted.features, ["core/std"]);
+        assert!(converted.no_default_features);
     }
 
     #[test]
@@ -247,6 +257,10 @@ mod tests {
                 skip_ownership_check,
                 strict_ownership,
                 no_verify,
+                features: Vec::new(),
+                no_default_features: false,
+                all_features: false,
+                cargo: Default::default(),
                 max_attempts,
                 base_delay: Duration::from_millis(base_delay_ms),
                 max_delay: Duration::from_millis(max_delay_ms.max(base_delay_ms + 1)),
@@ -874,6 +888,10 @@ mod tests {
                 skip_ownership_check: false,
                 strict_ownership: false,
                 no_verify: false,
+                features: Vec::new(),
+                no_default_features: false,
+                all_features: false,
+                cargo: Default::default(),
                 max_attempts: 3,
                 base_delay: Duration::from_secs(5),
                 max_delay: Duration::from_mins(5),
@@ -1215,6 +1233,10 @@ mod tests {
                 skip_ownership_check: false,
                 strict_ownership: false,
                 no_verify: false,
+                features: Vec::new(),
+                no_default_features: false,
+                all_features: false,
+                cargo: Default::default(),
                 max_attempts: 3,
                 base_delay: Duration::from_secs(1),
                 max_delay: Duration::from_mins(1),
@@ -1402,6 +1424,10 @@ mod tests {
                 skip_ownership_check: false,
                 strict_ownership: false,
                 no_verify: false,
+                features: Vec::new(),
+                no_default_features: false,
+                all_features: false,
+                cargo: Default::default(),
                 max_attempts: 5,
                 base_delay: Duration::from_secs(2),
                 max_delay: Duration::from_mins(1),
@@ -1753,6 +1779,10 @@ mod tests {
                 skip_ownership_check: true,
                 strict_ownership: true,
                 no_verify: true,
+                features: Vec::new(),
+                no_default_features: false,
+                all_features: false,
+                cargo: Default::default(),
                 max_attempts: u32::MAX,
                 base_delay: Duration::ZERO,
                 max_delay: Duration::from_secs(u64::MAX / 2),
@@ -1,7 +1,7 @@
 use std::path::PathBuf;
 use std::time::Duration;
 
-use shipper_types::{ParallelConfig, ReadinessConfig, RuntimeOptions};
+use shipper_types::{CargoInvocation, ParallelConfig, ReadinessConfig, RuntimeOptions};
 
 use crate::{CliOverrides, ShipperConfig};
 
@@ -17,12 +17,19 @@ pub(crate) fn build(config: &ShipperConfig, cli: CliOverrides) -> RuntimeOptions
     let encryption = secrets::resolve_encryption(&config.encryption, &cli);
     let registries = registry::resolve(&config.registries, &cli);
     let rehearsal_registry = resolve_rehearsal_registry(config, &cli);
+    let cargo = resolve_cargo(config, &cli);
 
     RuntimeOptions {
         allow_dirty: cli.allow_dirty || config.flags.allow_dirty,
         skip_ownership_check: cli.skip_ownership_check || config.flags.skip_ownership_check,
         strict_ownership: cli.strict_ownership || config.flags.strict_ownership,
         no_verify: cli.no_verify,
+        features: cli
+            .features
+            .unwrap_or_else(|| config.cargo.features.clone()),
+        no_default_features: cli.no_default_features || config.cargo.no_default_features,
+        all_features: cli.all_features || config.cargo.all_features,
+        cargo,
         max_attempts: retry.max_attempts,
         base_delay: retry.base_delay,
         max_delay: retry.max_delay,
@@ -85,6 +92,17 @@ fn resolve_parallel(config: &ShipperConfig, cli: &CliOverrides) -> ParallelConfi
     }
 }
 
+fn resolve_cargo(config: &ShipperConfig, cli: &CliOverrides) -> CargoInvocation {
+    CargoInvocation {
+        toolchain: cli
+            .cargo_toolchain
+            .clone()
+            .or_else(|| config.cargo.toolchain.clone()),
+        leading_args: config.cargo.leading_args.clone(),
+        trailing_args: config.cargo.trailing_args.clone(),
+    }
+}
+
 fn resolve_rehearsal_registry(config: &ShipperConfig, cli: &CliOverrides) -> Option<String> {
     cli.rehearsal_registry.clone().or_else(|| {
         if config.rehearsal.enabled {
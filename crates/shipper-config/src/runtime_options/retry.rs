@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use shipper_retry::{PerErrorConfig, RetryPolicy, RetryStrategyType};
+use shipper_retry::{PerErrorConfig, RetryPolicy, RetryStrategyConfig, RetryStrategyType};
 
 use crate::{CliOverrides, RetryConfig};
 
@@ -13,48 +13,40 @@ pub(super) struct ResolvedRetry {
     pub(super) per_error: PerErrorConfig,
 }
 
+/// The retry engine settings a `[retry]` section describes.
+///
+/// Preset policies (`default`, `aggressive`, `conservative`) use
+/// [`RetryPolicy::to_config`] and ignore the explicit fields; `custom` maps
+/// `max_attempts`, `base_delay`, `max_delay`, `strategy` and `jitter`
+/// verbatim. CLI overrides are applied on top of this by the runtime builder.
+impl From<&RetryConfig> for RetryStrategyConfig {
+    fn from(config: &RetryConfig) -> Self {
+        if config.policy != RetryPolicy::Custom {
+            return config.policy.to_config();
+        }
+        RetryStrategyConfig {
+            strategy: config.strategy,
+            max_attempts: config.max_attempts,
+            base_delay: config.base_delay,
+            max_delay: config.max_delay,
+            jitter: config.jitter,
+        }
+    }
+}
+
 pub(super) fn resolve(config: &RetryConfig, cli: &CliOverrides) -> ResolvedRetry {
-    let policy_defaults = config.policy.to_config();
-    let custom_policy = config.policy == RetryPolicy::Custom;
+    let base = RetryStrategyConfig::from(config);
 
     ResolvedRetry {
-        max_attempts: cli.max_attempts.unwrap_or(select_policy_value(
-            custom_policy,
-            config.max_attempts,
-            policy_defaults.max_attempts,
-        )),
-        base_delay: cli.base_delay.unwrap_or(select_policy_value(
-            custom_policy,
-            config.base_delay,
-            policy_defaults.base_delay,
-        )),
-        max_delay: cli.max_delay.unwrap_or(select_policy_value(
-            custom_policy,
-            config.max_delay,
-            policy_defaults.max_delay,
-        )),
-        strategy: cli.retry_strategy.unwrap_or(select_policy_value(
-            custom_policy,
-            config.strategy,
-            policy_defaults.strategy,
-        )),
-        jitter: cli.retry_jitter.unwrap_or(select_policy_value(
-            custom_policy,
-            config.jitter,
-            policy_defaults.jitter,
-        )),
+        max_attempts: cli.max_attempts.unwrap_or(base.max_attempts),
+        base_delay: cli.base_delay.unwrap_or(base.base_delay),
+        max_delay: cli.max_delay.unwrap_or(base.max_delay),
+        strategy: cli.retry_strategy.unwrap_or(base.strategy),
+        jitter: cli.retry_jitter.unwrap_or(base.jitter),
         per_error: config.per_error.clone(),
     }
 }
 
-fn select_policy_value<T>(custom_policy: bool, custom_value: T, policy_value: T) -> T {
-    if custom_policy {
-        custom_value
-    } else {
-        policy_value
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -197,6 +189,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn strategy_config_from_custom_policy_maps_every_field() {
+        let config = make_config(RetryPolicy::Custom);
+
+        let strategy = RetryStrategyConfig::from(&config);
+
+        assert_eq!(strategy.strategy, RetryStrategyType::Constant);
+        assert_eq!(strategy.max_attempts, TEST_DEFAULT_MAX_ATTEMPTS);
+        assert_eq!(strategy.base_delay, Duration::from_secs(999));
+        assert_eq!(strategy.max_delay, Duration::from_secs(999));
+        assert!((strategy.jitter - 0.9).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn strategy_config_from_preset_policy_uses_preset() {
+        for policy in [
+            RetryPolicy::Default,
+            RetryPolicy::Aggressive,
+            RetryPolicy::Conservative,
+        ] {
+            let strategy = RetryStrategyConfig::from(&make_config(policy));
+            let preset = policy.to_config();
+
+            assert_eq!(strategy.strategy, preset.strategy, "{policy:?}");
+            assert_eq!(strategy.max_attempts, preset.max_attempts, "{policy:?}");
+            assert_eq!(strategy.base_delay, preset.base_delay, "{policy:?}");
+            assert_eq!(strategy.max_delay, preset.max_delay, "{policy:?}");
+            assert!((strategy.jitter - preset.jitter).abs() < f64::EPSILON);
+        }
+    }
+
+    #[test]
+    fn strategy_config_from_default_retry_config_matches_retry_default() {
+        let strategy = RetryStrategyConfig::from(&RetryConfig::default());
+        let expected = RetryStrategyConfig::default();
+
+        assert_eq!(strategy.strategy, expected.strategy);
+        assert_eq!(strategy.max_attempts, expected.max_attempts);
+        assert_eq!(strategy.base_delay, expected.base_delay);
+        assert_eq!(strategy.max_delay, expected.max_delay);
+    }
+
     #[test]
     fn resolve_preserves_per_error_config_verbatim() {
         let retryable = shipper_retry::RetryStrategyConfig {
@@ -60,6 +60,10 @@ mod tests {
             webhook_type: WebhookType::Generic,
             secret: Some("config-secret".to_string()),
             timeout_secs: 30,
+            retry: None,
+            max_list_items: None,
+            dry_run: false,
+            tls: Default::default(),
         }
     }
 
@@ -130,6 +134,10 @@ mod tests {
             webhook_type: WebhookType::Slack,
             secret: None,
             timeout_secs: 7,
+            retry: None,
+            max_list_items: None,
+            dry_run: false,
+            tls: Default::default(),
         };
         let cli = CliOverrides {
             webhook_url: Some("https://cli.example/hook".to_string()),
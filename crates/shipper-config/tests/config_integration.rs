@@ -1,9 +1,43 @@
 use std::path::Path;
 
+use serial_test::serial;
 use shipper_types::PublishPolicy;
 use tempfile::tempdir;
 
-use shipper_config::ShipperConfig;
+use shipper_config::{
+    PROFILE_ENV, ShipperConfig, find_config_file, ignored_config_files, load_config_with_profile,
+};
+
+const PROFILED_CONFIG: &str = r#"
+schema_version = "shipper.config.v1"
+
+[policy]
+mode = "safe"
+
+[retry]
+max_attempts = 6
+base_delay = "2s"
+
+[parallel]
+enabled = false
+max_concurrent = 4
+
+[profile.ci.policy]
+mode = "fast"
+
+[profile.ci.retry]
+max_attempts = 10
+
+[profile.ci.parallel]
+enabled = true
+
+[profile.local.flags]
+allow_dirty = true
+"#;
+
+fn write_profiled_config(dir: &Path) {
+    std::fs::write(dir.join(".shipper.toml"), PROFILED_CONFIG).expect("write config");
+}
 
 #[test]
 fn default_toml_template_is_loadable_via_file_api() {
@@ -30,3 +64,246 @@ fn build_runtime_options_can_merge_cli_and_file_defaults_without_panic() {
     assert_eq!(options.output_lines, 99);
     assert!(!options.no_verify);
 }
+
+#[test]
+#[serial]
+fn profile_overlays_top_level_settings() {
+    let td = tempdir().expect("tempdir");
+    write_profiled_config(td.path());
+
+    let config = temp_env::with_var_unset(PROFILE_ENV, || {
+        load_config_with_profile(td.path(), Some("ci"))
+    })
+    .expect("load")
+    .expect("config present");
+
+    // Set by the profile.
+    assert_eq!(config.policy.mode, PublishPolicy::Fast);
+    assert_eq!(config.retry.max_attempts, 10);
+    assert!(config.parallel.enabled);
+    // Sibling keys the profile does not mention keep their top-level values.
+    assert_eq!(config.retry.base_delay, std::time::Duration::from_secs(2));
+    assert_eq!(config.parallel.max_concurrent, 4);
+    // Other profiles are not applied.
+    assert!(!config.flags.allow_dirty);
+}
+
+#[test]
+#[serial]
+fn no_profile_falls_back_to_top_level_settings() {
+    let td = tempdir().expect("tempdir");
+    write_profiled_config(td.path());
+
+    let config =
+        temp_env::with_var_unset(PROFILE_ENV, || load_config_with_profile(td.path(), None))
+            .expect("load")
+            .expect("config present");
+
+    assert_eq!(config.policy.mode, PublishPolicy::Safe);
+    assert_eq!(config.retry.max_attempts, 6);
+    assert!(!config.parallel.enabled);
+    assert!(!config.flags.allow_dirty);
+}
+
+#[test]
+#[serial]
+fn profile_env_var_selects_profile_unless_overridden() {
+    let td = tempdir().expect("tempdir");
+    write_profiled_config(td.path());
+
+    let from_env = temp_env::with_var(PROFILE_ENV, Some("local"), || {
+        load_config_with_profile(td.path(), None)
+    })
+    .expect("load")
+    .expect("config present");
+    assert!(from_env.flags.allow_dirty);
+    assert_eq!(from_env.policy.mode, PublishPolicy::Safe);
+
+    let explicit = temp_env::with_var(PROFILE_ENV, Some("local"), || {
+        load_config_with_profile(td.path(), Some("ci"))
+    })
+    .expect("load")
+    .expect("config present");
+    assert!(!explicit.flags.allow_dirty);
+    assert_eq!(explicit.policy.mode, PublishPolicy::Fast);
+}
+
+#[test]
+fn unknown_profile_lists_available_profiles() {
+    let td = tempdir().expect("tempdir");
+    write_profiled_config(td.path());
+
+    let err =
+        ShipperConfig::load_from_file_with_profile(&td.path().join(".shipper.toml"), Some("prod"))
+            .expect_err("unknown profile");
+    let msg = err.to_string();
+    assert!(msg.contains("unknown config profile `prod`"), "{msg}");
+    assert!(msg.contains("available profiles: ci, local"), "{msg}");
+}
+
+#[test]
+#[serial]
+fn missing_config_file_loads_as_none() {
+    let td = tempdir().expect("tempdir");
+    let config = temp_env::with_var(PROFILE_ENV, Some("ci"), || {
+        load_config_with_profile(td.path(), None)
+    })
+    .expect("load");
+    assert!(config.is_none());
+}
+
+const EQUIVALENT_TOML: &str = r#"
+schema_version = "shipper.config.v1"
+
+[policy]
+mode = "fast"
+
+[retry]
+max_attempts = 9
+base_delay = "3s"
+jitter = 0.25
+
+[parallel]
+enabled = true
+max_concurrent = 8
+
+[profile.ci.retry]
+max_attempts = 12
+"#;
+
+const EQUIVALENT_YAML: &str = r#"
+schema_version: shipper.config.v1
+policy:
+  mode: fast
+retry:
+  max_attempts: 9
+  base_delay: 3s
+  jitter: 0.25
+parallel:
+  enabled: true
+  max_concurrent: 8
+profile:
+  ci:
+    retry:
+      max_attempts: 12
+"#;
+
+const EQUIVALENT_JSON: &str = r#"{
+  "schema_version": "shipper.config.v1",
+  "policy": { "mode": "fast" },
+  "retry": { "max_attempts": 9, "base_delay": "3s", "jitter": 0.25 },
+  "parallel": { "enabled": true, "max_concurrent": 8 },
+  "profile": { "ci": { "retry": { "max_attempts": 12 } } }
+}"#;
+
+#[test]
+#[serial]
+fn every_config_format_loads_to_the_same_config() {
+    for (name, content) in [
+        (".shipper.toml", EQUIVALENT_TOML),
+        (".shipper.yaml", EQUIVALENT_YAML),
+        (".shipper.yml", EQUIVALENT_YAML),
+        (".shipper.json", EQUIVALENT_JSON),
+    ] {
+        let td = tempdir().expect("tempdir");
+        std::fs::write(td.path().join(name), content).expect("write config");
+
+        let (plain, profiled) = temp_env::with_var_unset(PROFILE_ENV, || {
+            (
+                load_config_with_profile(td.path(), None),
+                load_config_with_profile(td.path(), Some("ci")),
+            )
+        });
+        let plain = plain.expect("load").expect("config present");
+        let profiled = profiled.expect("load profile").expect("config present");
+
+        assert_eq!(plain.policy.mode, PublishPolicy::Fast, "{name}");
+        assert_eq!(plain.retry.max_attempts, 9, "{name}");
+        assert_eq!(
+            plain.retry.base_delay,
+            std::time::Duration::from_secs(3),
+            "{name}"
+        );
+        assert_eq!(plain.retry.jitter, 0.25, "{name}");
+        assert!(plain.parallel.enabled, "{name}");
+        assert_eq!(plain.parallel.max_concurrent, 8, "{name}");
+        assert_eq!(profiled.retry.max_attempts, 12, "{name}");
+        assert!(plain.validate().is_ok(), "{name}");
+    }
+}
+
+#[test]
+#[serial]
+fn toml_wins_over_yaml_and_json_when_several_exist() {
+    let td = tempdir().expect("tempdir");
+    std::fs::write(td.path().join(".shipper.json"), EQUIVALENT_JSON).expect("write json");
+    std::fs::write(
+        td.path().join(".shipper.yaml"),
+        "policy:\n  mode: balanced\n",
+    )
+    .expect("write yaml");
+
+    assert_eq!(
+        find_config_file(td.path()),
+        Some(td.path().join(".shipper.yaml"))
+    );
+    assert_eq!(
+        ignored_config_files(td.path()),
+        [td.path().join(".shipper.json")]
+    );
+    let config =
+        temp_env::with_var_unset(PROFILE_ENV, || load_config_with_profile(td.path(), None))
+            .expect("load")
+            .expect("config present");
+    assert_eq!(config.policy.mode, PublishPolicy::Balanced);
+
+    std::fs::write(td.path().join(".shipper.toml"), PROFILED_CONFIG).expect("write toml");
+    assert_eq!(
+        find_config_file(td.path()),
+        Some(td.path().join(".shipper.toml"))
+    );
+    let config = ShipperConfig::load_from_workspace(td.path())
+        .expect("load")
+        .expect("config present");
+    assert_eq!(config.policy.mode, PublishPolicy::Safe);
+    assert_eq!(config.retry.max_attempts, 6);
+}
+
+#[test]
+#[serial]
+fn yaml_and_json_nulls_survive_a_profile_overlay() {
+    for (name, content) in [
+        (
+            ".shipper.yaml",
+            "state_dir: null\nprofile:\n  ci:\n    retry:\n      max_attempts: 12\n",
+        ),
+        (
+            ".shipper.json",
+            r#"{ "state_dir": null, "profile": { "ci": { "retry": { "max_attempts": 12 } } } }"#,
+        ),
+    ] {
+        let td = tempdir().expect("tempdir");
+        std::fs::write(td.path().join(name), content).expect("write config");
+
+        let config = temp_env::with_var_unset(PROFILE_ENV, || {
+            load_config_with_profile(td.path(), Some("ci"))
+        })
+        .expect(name)
+        .expect("config present");
+        assert_eq!(config.state_dir, None, "{name}");
+        assert_eq!(config.retry.max_attempts, 12, "{name}");
+    }
+}
+
+#[test]
+fn yaml_parse_errors_name_the_file() {
+    let td = tempdir().expect("tempdir");
+    let path = td.path().join(".shipper.yaml");
+    std::fs::write(&path, "retry: [unclosed\n").expect("write config");
+
+    let err = ShipperConfig::load_from_file(&path).expect_err("invalid yaml");
+    assert!(
+        err.to_string().contains("Failed to parse config file"),
+        "{err:#}"
+    );
+}
@@ -13,6 +13,10 @@ fn sample_runtime_options(base_url: &str, registry_count: usize) -> RuntimeOptio
         skip_ownership_check: true,
         strict_ownership: false,
         no_verify: false,
+        features: Vec::new(),
+        no_default_features: false,
+        all_features: false,
+        cargo: Default::default(),
         max_attempts: 9,
         base_delay: Duration::from_secs(2),
         max_delay: Duration::from_secs(30),
@@ -49,6 +53,10 @@ fn sample_runtime_options(base_url: &str, registry_count: usize) -> RuntimeOptio
             webhook_type: Default::default(),
             secret: Some("top-secret".to_string()),
             timeout_secs: 90,
+            retry: None,
+            max_list_items: None,
+            dry_run: false,
+            tls: Default::default(),
         },
         encryption: EncryptionConfig {
             enabled: true,
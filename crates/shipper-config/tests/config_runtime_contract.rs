@@ -54,6 +54,7 @@ fn custom_config() -> ShipperConfig {
             max_concurrent: 8,
             per_package_timeout: Duration::from_mins(1),
         },
+        cargo: shipper_config::CargoConfig::default(),
         state_dir: Some(PathBuf::from("custom-state")),
         registry: None,
         registries: MultiRegistryConfig::default(),
@@ -62,6 +63,10 @@ fn custom_config() -> ShipperConfig {
             webhook_type: Default::default(),
             secret: Some("file-secret".to_string()),
             timeout_secs: 45,
+            retry: None,
+            max_list_items: None,
+            dry_run: false,
+            tls: Default::default(),
         },
         encryption: shipper_config::EncryptionConfigInner {
             enabled: true,
@@ -109,6 +114,7 @@ fn converts_config_to_runtime_contract_with_registry_overrides() {
             max_concurrent: 9,
             per_package_timeout: Duration::from_secs(12),
         },
+        cargo: shipper_config::CargoConfig::default(),
         state_dir: Some(PathBuf::from(".shipper")),
         registry: None,
         registries: shipper_config::MultiRegistryConfig::default(),
@@ -117,6 +123,10 @@ fn converts_config_to_runtime_contract_with_registry_overrides() {
             webhook_type: Default::default(),
             secret: Some("abc".to_string()),
             timeout_secs: 20,
+            retry: None,
+            max_list_items: None,
+            dry_run: false,
+            tls: Default::default(),
         },
         encryption: shipper_config::EncryptionConfigInner::default(),
         storage: shipper_config::StorageConfigInner::default(),
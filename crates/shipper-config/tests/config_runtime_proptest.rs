@@ -150,6 +150,10 @@ fn arb_webhook_config() -> impl Strategy<Value = WebhookConfig> {
             webhook_type: Default::default(),
             secret,
             timeout_secs,
+            retry: None,
+            max_list_items: None,
+            dry_run: false,
+            tls: Default::default(),
         },
     )
 }
@@ -249,6 +253,7 @@ fn arb_shipper_config() -> impl Strategy<Value = ShipperConfig> {
                     retry,
                     flags,
                     parallel,
+                    cargo: shipper_config::CargoConfig::default(),
                     state_dir: if has_state_dir {
                         Some(PathBuf::from("custom-state"))
                     } else {
@@ -360,6 +365,10 @@ fn arb_cli_overrides() -> impl Strategy<Value = CliOverrides> {
                     skip_ownership_check,
                     strict_ownership,
                     no_verify,
+                    features: None,
+                    no_default_features: false,
+                    all_features: false,
+                    cargo_toolchain: None,
                     no_readiness,
                     force,
                     force_resume,
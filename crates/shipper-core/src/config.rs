@@ -1014,6 +1014,7 @@ timeout_secs = 15
                 max_concurrent: 12,
                 per_package_timeout: Duration::from_mins(10),
             },
+            cargo: CargoConfig::default(),
             state_dir: Some(PathBuf::from("custom-state")),
             registry: None,
             registries: MultiRegistryConfig::default(),
@@ -204,6 +204,7 @@ mod tests {
             compromised_at: compromised.map(|_| Utc::now()),
             compromised_by: compromised.map(str::to_string),
             superseded_by: None,
+            artifact_sha256: None,
         }
     }
 
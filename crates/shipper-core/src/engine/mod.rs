@@ -24,7 +24,7 @@ use crate::types::ExecutionResult;
 use crate::types::{
     AttemptDetail, AttemptEvidence, ErrorClass, EventType, ExecutionState, PackageProgress,
     PackageReceipt, PackageState, PreflightReport, PublishEvent, PublishRegime, ReadinessEvidence,
-    Receipt, ReconciliationOutcome, Registry, RuntimeOptions,
+    Receipt, ReconciliationOutcome, Registry, RuntimeOptions, SkipReason,
 };
 #[cfg(test)]
 use crate::types::{Finishability, PreflightPackage};
@@ -32,11 +32,13 @@ use crate::webhook::{self, WebhookEvent};
 
 mod preflight;
 mod publish;
+mod publish_or_skip;
 mod readiness;
 mod rehearsal;
 mod retry;
 
 pub use preflight::PreflightRunOptions;
+pub use publish_or_skip::{PackageOutcome, publish_or_skip};
 use readiness::verify_published;
 use retry::{
     emit_retry_backoff_event, record_rate_limit_observed_event, record_retry_backoff_event,
@@ -319,6 +321,9 @@ pub fn run_publish(
 
         // Track whether cargo publish already succeeded (e.g. from Uploaded state on resume)
         let mut cargo_succeeded = false;
+        // Only a tarball packaged by this run's `cargo publish` is hashed; a
+        // package resumed from Uploaded may have a stale or missing one.
+        let mut uploaded_this_run = false;
 
         match progress.state.clone() {
             PackageState::Published | PackageState::Skipped { .. } => {
@@ -339,6 +344,13 @@ pub fn run_publish(
                 ));
                 cargo_succeeded = true;
             }
+            PackageState::Cancelled { at_state } if at_state == "uploaded" => {
+                reporter.info(&format!(
+                    "{}@{}: resuming cancelled upload (skipping cargo publish)",
+                    p.name, p.version
+                ));
+                cargo_succeeded = true;
+            }
             PackageState::Ambiguous {
                 message: prior_reason,
             } => {
@@ -390,7 +402,7 @@ pub fn run_publish(
                 p.name, p.version
             ));
             let skipped = PackageState::Skipped {
-                reason: "already published".into(),
+                reason: SkipReason::AlreadyPublished,
             };
             update_state(&mut st, &state_dir, &key, skipped)?;
 
@@ -402,7 +414,7 @@ pub fn run_publish(
                 },
                 package: pkg_label.clone(),
             });
-            event_log.write_to_file(&events_path)?;
+            event_log.write_to_file_redacted(&events_path, cargo::redact_sensitive)?;
             event_log.clear();
 
             let progress = st
@@ -424,10 +436,39 @@ pub fn run_publish(
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             });
             continue;
         }
 
+        if !cargo_succeeded
+            && let Some(message) = preflight::dry_run::just_in_time_failure(
+                workspace_root,
+                &ws.plan.registry.name,
+                &p.name,
+                opts,
+                &effects,
+            )
+        {
+            reporter.error(&format!("{}@{}: {}", p.name, p.version, message));
+            let failed = PackageState::Failed {
+                class: ErrorClass::Permanent,
+                message: message.clone(),
+            };
+            update_state(&mut st, &state_dir, &key, failed)?;
+            event_log.record(PublishEvent {
+                timestamp: Utc::now(),
+                event_type: EventType::PackageFailed {
+                    class: ErrorClass::Permanent,
+                    message: message.clone(),
+                },
+                package: pkg_label.clone(),
+            });
+            event_log.write_to_file_redacted(&events_path, cargo::redact_sensitive)?;
+            event_log.clear();
+            bail!("{}@{}: {}", p.name, p.version, message);
+        }
+
         reporter.info(&format!("{}@{}: publishing...", p.name, p.version));
 
         // Registry-aware backoff (#94 / #106 PR 1): prefer the `PublishRegime`
@@ -458,6 +499,7 @@ pub fn run_publish(
                     .context("missing package progress in state during attempt")?;
                 pr.attempts = attempt;
                 pr.last_updated_at = Utc::now();
+                st.touch();
                 state::save_state(&state_dir, &st)?;
             }
 
@@ -483,15 +525,13 @@ pub fn run_publish(
                     package: pkg_label.clone(),
                 });
 
-                let out = cargo::cargo_publish(
+                // sequential mode: no per-package timeout
+                let out = cargo::cargo_publish(&cargo::PublishArgs::new(
                     workspace_root,
+                    opts,
                     &p.name,
                     &ws.plan.registry.name,
-                    opts.allow_dirty,
-                    opts.no_verify,
-                    opts.output_lines,
-                    None, // sequential mode: no per-package timeout
-                )?;
+                ))?;
                 let attempt_ended_at = Utc::now();
 
                 // Collect attempt evidence
@@ -514,7 +554,7 @@ pub fn run_publish(
                     },
                     package: pkg_label.clone(),
                 });
-                event_log.write_to_file(&events_path)?;
+                event_log.write_to_file_redacted(&events_path, cargo::redact_sensitive)?;
                 event_log.clear();
 
                 if out.exit_code == 0 {
@@ -534,6 +574,7 @@ pub fn run_publish(
                         },
                     )?;
                     cargo_succeeded = true;
+                    uploaded_this_run = true;
                     // Persist Uploaded state so resume skips cargo publish
                     update_state(&mut st, &state_dir, &key, PackageState::Uploaded)?;
                 } else {
@@ -587,7 +628,7 @@ pub fn run_publish(
                             },
                             package: pkg_label.clone(),
                         });
-                        event_log.write_to_file(&events_path)?;
+                        event_log.write_to_file_redacted(&events_path, cargo::redact_sensitive)?;
                         event_log.clear();
                         write_reconciliation_report_best_effort(
                             &state_dir,
@@ -614,7 +655,10 @@ pub fn run_publish(
                                     },
                                     package: pkg_label.clone(),
                                 });
-                                event_log.write_to_file(&events_path)?;
+                                event_log.write_to_file_redacted(
+                                    &events_path,
+                                    cargo::redact_sensitive,
+                                )?;
                                 event_log.clear();
                                 readiness_evidence = reconcile_evidence;
                                 last_err = None;
@@ -669,7 +713,27 @@ pub fn run_publish(
                             p.name, p.version, out.exit_code
                         ));
 
-                        if reg.version_exists(&p.name, &p.version)? {
+                        // A permanent registry answer (bad token, unexpected status)
+                        // aborts; a transient one leaves the decision to the retry
+                        // loop below.
+                        let present = match reg.version_exists(&p.name, &p.version) {
+                            Ok(present) => present,
+                            Err(err)
+                                if ErrorClass::from(err.classify()) == ErrorClass::Permanent =>
+                            {
+                                return Err(err.into());
+                            }
+                            Err(err) => {
+                                reporter.warn(&format!(
+                                    "{}@{}: registry check failed ({:#}); retrying",
+                                    p.name,
+                                    p.version,
+                                    anyhow::Error::from(err)
+                                ));
+                                false
+                            }
+                        };
+                        if present {
                             reporter.info(&format!(
                                 "{}@{}: version is present on registry; treating as published",
                                 p.name, p.version
@@ -683,7 +747,8 @@ pub fn run_publish(
                                 },
                                 package: pkg_label.clone(),
                             });
-                            event_log.write_to_file(&events_path)?;
+                            event_log
+                                .write_to_file_redacted(&events_path, cargo::redact_sensitive)?;
                             event_log.clear();
                             last_err = None;
                             break;
@@ -708,7 +773,8 @@ pub fn run_publish(
                                 },
                                 package: pkg_label.clone(),
                             });
-                            event_log.write_to_file(&events_path)?;
+                            event_log
+                                .write_to_file_redacted(&events_path, cargo::redact_sensitive)?;
                             event_log.clear();
 
                             return Err(anyhow::anyhow!(
@@ -814,7 +880,7 @@ pub fn run_publish(
                     },
                     package: pkg_label.clone(),
                 });
-                event_log.write_to_file(&events_path)?;
+                event_log.write_to_file_redacted(&events_path, cargo::redact_sensitive)?;
                 event_log.clear();
 
                 // Send webhook notification: package succeeded
@@ -871,7 +937,7 @@ pub fn run_publish(
                         },
                         package: pkg_label.clone(),
                     });
-                    event_log.write_to_file(&events_path)?;
+                    event_log.write_to_file_redacted(&events_path, cargo::redact_sensitive)?;
                     event_log.clear();
                 } else {
                     last_err = Some((
@@ -896,7 +962,7 @@ pub fn run_publish(
                     },
                     package: pkg_label.clone(),
                 });
-                event_log.write_to_file(&events_path)?;
+                event_log.write_to_file_redacted(&events_path, cargo::redact_sensitive)?;
                 event_log.clear();
             } else {
                 let failed = PackageState::Failed {
@@ -914,7 +980,7 @@ pub fn run_publish(
                     },
                     package: pkg_label.clone(),
                 });
-                event_log.write_to_file(&events_path)?;
+                event_log.write_to_file_redacted(&events_path, cargo::redact_sensitive)?;
                 event_log.clear();
 
                 // Send webhook notification: package failed
@@ -948,6 +1014,7 @@ pub fn run_publish(
                     compromised_at: None,
                     compromised_by: None,
                     superseded_by: None,
+                    artifact_sha256: None,
                 });
                 return Err(anyhow::anyhow!("{}@{}: failed: {}", p.name, p.version, msg));
             }
@@ -957,6 +1024,11 @@ pub fn run_publish(
             .packages
             .get(&key)
             .context("missing package progress in state for completed package")?;
+        let artifact_sha256 = if uploaded_this_run && progress.state == PackageState::Published {
+            cargo::published_artifact_sha256(&ws.target_directory, &p.name, &p.version)
+        } else {
+            None
+        };
         receipts.push(PackageReceipt {
             name: p.name.clone(),
             version: p.version.clone(),
@@ -972,6 +1044,7 @@ pub fn run_publish(
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256,
         });
     }
 
@@ -1155,7 +1228,7 @@ pub fn run_rehearsal(
         },
         package: "all".to_string(),
     });
-    event_log.write_to_file(&events_path)?;
+    event_log.write_to_file_redacted(&events_path, cargo::redact_sensitive)?;
     event_log.clear();
 
     let rehearsal_client = init_registry_client(rehearsal_reg.clone(), &state_dir)?;
@@ -1168,15 +1241,12 @@ pub fn run_rehearsal(
         reporter.info(&format!("rehearsing {pkg_label} â†’ {rehearsal_name}"));
         let start = Instant::now();
 
-        let out = cargo::cargo_publish(
+        let out = cargo::cargo_publish(&cargo::PublishArgs::new(
             workspace_root,
+            opts,
             &p.name,
             &rehearsal_reg.name,
-            opts.allow_dirty,
-            opts.no_verify,
-            opts.output_lines,
-            None,
-        )?;
+        ))?;
 
         if out.exit_code != 0 {
             let (class, msg) = classify_cargo_failure(&out.stderr_tail, &out.stdout_tail);
@@ -1194,7 +1264,7 @@ pub fn run_rehearsal(
                 },
                 package: pkg_label.clone(),
             });
-            event_log.write_to_file(&events_path)?;
+            event_log.write_to_file_redacted(&events_path, cargo::redact_sensitive)?;
             event_log.clear();
             first_failure = Some(format!("{pkg_label}: {msg}"));
             break;
@@ -1217,7 +1287,7 @@ pub fn run_rehearsal(
                 },
                 package: pkg_label.clone(),
             });
-            event_log.write_to_file(&events_path)?;
+            event_log.write_to_file_redacted(&events_path, cargo::redact_sensitive)?;
             event_log.clear();
             first_failure = Some(msg);
             break;
@@ -1233,7 +1303,7 @@ pub fn run_rehearsal(
             },
             package: pkg_label.clone(),
         });
-        event_log.write_to_file(&events_path)?;
+        event_log.write_to_file_redacted(&events_path, cargo::redact_sensitive)?;
         event_log.clear();
         packages_published += 1;
     }
@@ -1262,7 +1332,7 @@ pub fn run_rehearsal(
                     },
                     package: format!("{smoke_name}@{}", smoke_pkg.version),
                 });
-                event_log.write_to_file(&events_path)?;
+                event_log.write_to_file_redacted(&events_path, cargo::redact_sensitive)?;
                 event_log.clear();
 
                 let install_root = state_dir.join("smoke-install");
@@ -1313,7 +1383,7 @@ pub fn run_rehearsal(
                         smoke_pkg.version, out.exit_code
                     ));
                 }
-                event_log.write_to_file(&events_path)?;
+                event_log.write_to_file_redacted(&events_path, cargo::redact_sensitive)?;
                 event_log.clear();
             }
             None => {
@@ -1358,7 +1428,7 @@ pub fn run_rehearsal(
         },
         package: "all".to_string(),
     });
-    event_log.write_to_file(&events_path)?;
+    event_log.write_to_file_redacted(&events_path, cargo::redact_sensitive)?;
 
     // Persist the sidecar receipt so the hard gate in `run_publish`
     // can consult it without parsing events.jsonl. Best-effort â€” a
@@ -1707,6 +1777,7 @@ mod tests {
     fn planned_workspace(workspace_root: &Path, api_base: String) -> PlannedWorkspace {
         PlannedWorkspace {
             workspace_root: workspace_root.to_path_buf(),
+            target_directory: workspace_root.join("target"),
             plan: ReleasePlan {
                 plan_version: "1".to_string(),
                 plan_id: "plan-demo".to_string(),
@@ -1734,6 +1805,10 @@ mod tests {
             skip_ownership_check: true,
             strict_ownership: false,
             no_verify: false,
+            features: Vec::new(),
+            no_default_features: false,
+            all_features: false,
+            cargo: Default::default(),
             max_attempts: 2,
             base_delay: Duration::from_millis(1),
             max_delay: Duration::from_millis(2),
@@ -1813,7 +1888,7 @@ mod tests {
         assert_eq!(short_state(&PackageState::Published), "published");
         assert_eq!(
             short_state(&PackageState::Skipped {
-                reason: "r".to_string()
+                reason: SkipReason::Manual("r".to_string())
             }),
             "skipped"
         );
@@ -2730,7 +2805,10 @@ mod tests {
             let report = run_preflight(&ws, &opts, &mut reporter).expect("preflight");
 
             assert!(report.token_detected);
-            assert_eq!(report.packages[0].auth_type, Some(AuthType::Token));
+            assert_eq!(
+                report.packages[0].auth_type,
+                Some(AuthType::TrustedPublishing)
+            );
             let warnings = reporter.warns.join("\n");
             assert!(
                 warnings.contains("Trusted Publishing OIDC environment is present"),
@@ -3317,10 +3395,17 @@ mod tests {
             let mut opts = default_opts(PathBuf::from(".shipper"));
             opts.verify_timeout = Duration::from_millis(200);
             opts.verify_poll_interval = Duration::from_millis(1);
+            let tarball = cargo::packaged_crate_path(&ws.target_directory, "demo", "0.1.0");
+            fs::create_dir_all(tarball.parent().expect("parent")).expect("mkdir");
+            fs::write(&tarball, b"crate").expect("write");
 
             let mut reporter = CollectingReporter::default();
             let receipt = run_publish(&ws, &opts, &mut reporter).expect("publish");
             assert!(matches!(receipt.packages[0].state, PackageState::Published));
+            assert_eq!(
+                receipt.packages[0].artifact_sha256,
+                Some(cargo::hash_crate_file(&tarball).expect("hash"))
+            );
             server.join();
         });
     }
@@ -3667,6 +3752,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn run_publish_resumes_state_recorded_under_legacy_plan_id() {
+        let td = tempdir().expect("tempdir");
+        let ws = planned_workspace(td.path(), "http://127.0.0.1:9".to_string());
+        let state_dir = td.path().join(".shipper");
+
+        let mut packages = std::collections::BTreeMap::new();
+        packages.insert(
+            "demo@0.1.0".to_string(),
+            PackageProgress {
+                name: "demo".to_string(),
+                version: "0.1.0".to_string(),
+                attempts: 1,
+                state: PackageState::Published,
+                last_updated_at: Utc::now(),
+            },
+        );
+        let st = ExecutionState {
+            state_version: crate::state::execution_state::CURRENT_STATE_VERSION.to_string(),
+            plan_id: ReleasePlan::legacy_id(&ws.plan.registry.api_base, &ws.plan.packages),
+            registry: ws.plan.registry.clone(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            attempt_history: Vec::new(),
+            packages,
+        };
+        state::save_state(&state_dir, &st).expect("save");
+
+        let opts = default_opts(PathBuf::from(".shipper"));
+        let mut reporter = CollectingReporter::default();
+        run_publish(&ws, &opts, &mut reporter).expect("publish");
+        assert!(reporter.infos.iter().any(|i| i.contains("legacy plan_id")));
+        assert!(
+            reporter
+                .warns
+                .iter()
+                .all(|w| !w.contains("mismatched plan_id"))
+        );
+    }
+
     #[test]
     fn run_resume_errors_when_state_is_missing() {
         let td = tempdir().expect("tempdir");
@@ -4382,6 +4507,229 @@ mod tests {
         );
     }
 
+    #[test]
+    #[serial]
+    fn test_verify_mode_package_treats_already_uploaded_dry_run_as_published() {
+        let td = tempdir().expect("tempdir");
+        let bin = td.path().join("bin");
+        write_fake_tools(&bin);
+        with_test_env(
+            &bin,
+            vec![
+                ("SHIPPER_CARGO_EXIT", Some("101".to_string())),
+                (
+                    "SHIPPER_CARGO_STDERR",
+                    Some("error: crate demo@0.1.0 already exists on crates.io index".to_string()),
+                ),
+            ],
+            || {
+                // The API hasn't caught up with the index yet
+                let server = spawn_registry_server(
+                    std::collections::BTreeMap::from([
+                        (
+                            "/api/v1/crates/demo/0.1.0".to_string(),
+                            vec![(404, "{}".to_string())],
+                        ),
+                        (
+                            "/api/v1/crates/demo".to_string(),
+                            vec![(200, "{}".to_string())],
+                        ),
+                    ]),
+                    2,
+                );
+                let ws = planned_workspace(td.path(), server.base_url.clone());
+                let mut opts = default_opts(PathBuf::from(".shipper"));
+                opts.verify_mode = crate::types::VerifyMode::Package;
+
+                let mut reporter = CollectingReporter::default();
+                let report = run_preflight(&ws, &opts, &mut reporter).expect("preflight");
+
+                assert!(report.packages[0].already_published);
+                assert!(report.packages[0].dry_run_passed);
+                assert!(
+                    reporter
+                        .infos
+                        .iter()
+                        .any(|i| i.contains("already published"))
+                );
+                assert!(!reporter.warns.iter().any(|w| w.contains("dry-run failed")));
+                server.join();
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_verify_mode_just_in_time_dry_runs_right_before_publish() {
+        let td = tempdir().expect("tempdir");
+        let bin = td.path().join("bin");
+        write_fake_tools(&bin);
+        let args_log = td.path().join("cargo_args.txt");
+        let mut env_vars = fake_program_env_vars(&bin);
+        env_vars.extend([
+            ("SHIPPER_CARGO_EXIT", Some("0".to_string())),
+            (
+                "SHIPPER_CARGO_ARGS_LOG",
+                Some(args_log.to_str().expect("utf8").to_string()),
+            ),
+        ]);
+        temp_env::with_vars(env_vars, || {
+            let server = spawn_registry_server(
+                std::collections::BTreeMap::from([(
+                    "/api/v1/crates/demo/0.1.0".to_string(),
+                    vec![(404, "{}".to_string()), (200, "{}".to_string())],
+                )]),
+                2,
+            );
+            let ws = planned_workspace(td.path(), server.base_url.clone());
+            let mut opts = default_opts(PathBuf::from(".shipper"));
+            opts.verify_mode = crate::types::VerifyMode::PackageJustInTime;
+            opts.verify_poll_interval = Duration::from_millis(1);
+
+            let mut reporter = CollectingReporter::default();
+            let receipt = run_publish(&ws, &opts, &mut reporter).expect("publish");
+            assert!(matches!(receipt.packages[0].state, PackageState::Published));
+
+            let log = fs::read_to_string(&args_log).expect("args log");
+            let lines: Vec<&str> = log.lines().collect();
+            assert_eq!(lines.len(), 2, "{log}");
+            assert!(lines[0].contains("-p demo") && lines[0].contains("--dry-run"));
+            assert!(!lines[1].contains("--dry-run"));
+            server.join();
+        });
+    }
+
+    #[test]
+    #[serial]
+    fn test_verify_mode_just_in_time_failure_stops_before_publish() {
+        let td = tempdir().expect("tempdir");
+        let bin = td.path().join("bin");
+        write_fake_tools(&bin);
+        let args_log = td.path().join("cargo_args.txt");
+        let mut env_vars = fake_program_env_vars(&bin);
+        env_vars.extend([
+            ("SHIPPER_CARGO_EXIT", Some("101".to_string())),
+            (
+                "SHIPPER_CARGO_STDERR",
+                Some("error: failed to verify package tarball".to_string()),
+            ),
+            (
+                "SHIPPER_CARGO_ARGS_LOG",
+                Some(args_log.to_str().expect("utf8").to_string()),
+            ),
+        ]);
+        temp_env::with_vars(env_vars, || {
+            let server = spawn_registry_server(
+                std::collections::BTreeMap::from([(
+                    "/api/v1/crates/demo/0.1.0".to_string(),
+                    vec![(404, "{}".to_string())],
+                )]),
+                1,
+            );
+            let ws = planned_workspace(td.path(), server.base_url.clone());
+            let state_dir = td.path().join(".shipper");
+            let mut opts = default_opts(PathBuf::from(".shipper"));
+            opts.verify_mode = crate::types::VerifyMode::PackageJustInTime;
+
+            let mut reporter = CollectingReporter::default();
+            let err = run_publish(&ws, &opts, &mut reporter).expect_err("must fail");
+            assert!(format!("{err:#}").contains("just-in-time dry-run failed"));
+
+            let log = fs::read_to_string(&args_log).expect("args log");
+            assert_eq!(log.lines().count(), 1, "{log}");
+            assert!(log.contains("--dry-run"));
+
+            let st = state::load_state(&state_dir).expect("load").expect("state");
+            assert!(matches!(
+                st.packages.get("demo@0.1.0").expect("pkg").state,
+                PackageState::Failed {
+                    class: ErrorClass::Permanent,
+                    ..
+                }
+            ));
+            server.join();
+        });
+    }
+
+    #[test]
+    #[serial]
+    fn resume_from_cancelled_upload_skips_cargo_publish() {
+        let td = tempdir().expect("tempdir");
+        let bin = td.path().join("bin");
+        write_fake_tools(&bin);
+        let args_log = td.path().join("cargo_args.txt");
+        let mut env_vars = fake_program_env_vars(&bin);
+        env_vars.extend([
+            ("SHIPPER_CARGO_EXIT", Some("0".to_string())),
+            (
+                "SHIPPER_CARGO_ARGS_LOG",
+                Some(args_log.to_str().expect("utf8").to_string()),
+            ),
+        ]);
+        temp_env::with_vars(env_vars, || {
+            let server = spawn_registry_server(
+                std::collections::BTreeMap::from([(
+                    "/api/v1/crates/demo/0.1.0".to_string(),
+                    vec![(404, "{}".to_string()), (200, "{}".to_string())],
+                )]),
+                2,
+            );
+
+            let ws = planned_workspace(td.path(), server.base_url.clone());
+            let state_dir = td.path().join(".shipper");
+
+            let mut packages = std::collections::BTreeMap::new();
+            packages.insert(
+                "demo@0.1.0".to_string(),
+                PackageProgress {
+                    name: "demo".to_string(),
+                    version: "0.1.0".to_string(),
+                    attempts: 1,
+                    state: PackageState::Cancelled {
+                        at_state: "uploaded".to_string(),
+                    },
+                    last_updated_at: Utc::now(),
+                },
+            );
+            let st = ExecutionState {
+                state_version: crate::state::execution_state::CURRENT_STATE_VERSION.to_string(),
+                plan_id: ws.plan.plan_id.clone(),
+                registry: ws.plan.registry.clone(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                attempt_history: Vec::new(),
+                packages,
+            };
+            state::save_state(&state_dir, &st).expect("save");
+
+            let opts = default_opts(PathBuf::from(".shipper"));
+            let mut reporter = CollectingReporter::default();
+            let receipt = run_publish(&ws, &opts, &mut reporter).expect("publish");
+
+            assert!(
+                matches!(receipt.packages[0].state, PackageState::Published),
+                "expected Published, got {:?}",
+                receipt.packages[0].state
+            );
+            let cargo_invoked = args_log.exists()
+                && fs::read_to_string(&args_log)
+                    .unwrap_or_default()
+                    .contains("publish");
+            assert!(
+                !cargo_invoked,
+                "cancelled upload must not re-run cargo publish"
+            );
+            assert!(
+                reporter
+                    .infos
+                    .iter()
+                    .any(|i| i.contains("resuming cancelled upload"))
+            );
+
+            server.join();
+        });
+    }
+
     #[test]
     #[serial]
     fn resume_from_uploaded_skips_cargo_publish_and_reaches_published() {
@@ -4433,6 +4781,11 @@ mod tests {
             };
             state::save_state(&state_dir, &st).expect("save");
 
+            // A tarball left over from an earlier build is not what this run uploaded
+            let stale = cargo::packaged_crate_path(&ws.target_directory, "demo", "0.1.0");
+            fs::create_dir_all(stale.parent().expect("parent")).expect("mkdir");
+            fs::write(&stale, b"stale").expect("write");
+
             let opts = default_opts(PathBuf::from(".shipper"));
             let mut reporter = CollectingReporter::default();
             let receipt = run_publish(&ws, &opts, &mut reporter).expect("publish");
@@ -4444,6 +4797,7 @@ mod tests {
                 "expected Published, got {:?}",
                 receipt.packages[0].state
             );
+            assert_eq!(receipt.packages[0].artifact_sha256, None);
 
             // Cargo publish should NOT have been invoked
             // (args_log should not exist or be empty â€” no cargo publish calls)
@@ -4894,13 +5248,13 @@ mod tests {
                 assert_eq!(st.attempt_history[0].attempt, 1);
                 assert_eq!(
                     st.attempt_history[0].error_class,
-                    Some(ErrorClass::Ambiguous)
+                    Some(ErrorClass::Retryable)
                 );
                 assert!(st.attempt_history[0].next_attempt_at.is_some());
                 assert_eq!(st.attempt_history[1].attempt, 2);
                 assert_eq!(
                     st.attempt_history[1].error_class,
-                    Some(ErrorClass::Ambiguous)
+                    Some(ErrorClass::Retryable)
                 );
                 assert!(st.attempt_history[1].next_attempt_at.is_none());
                 let events =
@@ -5148,7 +5502,7 @@ mod tests {
         assert_eq!(short_state(&PackageState::Published), "published");
         assert_eq!(
             short_state(&PackageState::Skipped {
-                reason: "already published".to_string()
+                reason: SkipReason::AlreadyPublished
             }),
             "skipped"
         );
@@ -5509,13 +5863,13 @@ mod tests {
         let key = "demo@0.1.0";
 
         let skipped = PackageState::Skipped {
-            reason: "already published".to_string(),
+            reason: SkipReason::AlreadyPublished,
         };
         update_state(&mut st, &state_dir, key, skipped).expect("update");
 
         match &st.packages.get(key).unwrap().state {
             PackageState::Skipped { reason } => {
-                assert_eq!(reason, "already published");
+                assert_eq!(*reason, SkipReason::AlreadyPublished);
             }
             other => panic!("expected Skipped, got {other:?}"),
         }
@@ -5562,13 +5916,14 @@ mod tests {
                     compromised_at: None,
                     compromised_by: None,
                     superseded_by: None,
+                    artifact_sha256: None,
                 },
                 PackageReceipt {
                     name: "beta".to_string(),
                     version: "2.0.0".to_string(),
                     attempts: 0,
                     state: PackageState::Skipped {
-                        reason: "already published".to_string(),
+                        reason: SkipReason::AlreadyPublished,
                     },
                     started_at: Utc::now(),
                     finished_at: Utc::now(),
@@ -5580,6 +5935,7 @@ mod tests {
                     compromised_at: None,
                     compromised_by: None,
                     superseded_by: None,
+                    artifact_sha256: None,
                 },
             ],
             event_log_path: PathBuf::from(".shipper/events.jsonl"),
@@ -6051,6 +6407,7 @@ mod tests {
     ) -> PlannedWorkspace {
         PlannedWorkspace {
             workspace_root: workspace_root.to_path_buf(),
+            target_directory: workspace_root.join("target"),
             plan: ReleasePlan {
                 plan_version: "1".to_string(),
                 plan_id: "plan-sm-test".to_string(),
@@ -7187,7 +7544,9 @@ mod tests {
                 Just(PackageState::Pending),
                 Just(PackageState::Uploaded),
                 Just(PackageState::Published),
-                ".*".prop_map(|r| PackageState::Skipped { reason: r }),
+                ".*".prop_map(|r| PackageState::Skipped {
+                    reason: SkipReason::Manual(r)
+                }),
                 (arb_error_class(), ".*").prop_map(|(c, m)| PackageState::Failed {
                     class: c,
                     message: m
@@ -65,6 +65,7 @@ pub(super) fn collect_level_receipts_from_state(
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             })
         })
         .collect())
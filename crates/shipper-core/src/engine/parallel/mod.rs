@@ -24,10 +24,12 @@ mod policy;
 mod publish;
 mod readiness;
 mod reconcile;
+mod scheduler;
 mod webhook;
 
 /// Re-exported for parallel publish wave planning.
 pub use crate::plan::chunking::chunk_by_max_concurrent;
+pub use scheduler::{LevelsOutcome, publish_levels};
 
 use flow::{
     LevelResumeAction, collect_level_receipts_from_state, determine_level_resume_action,
@@ -251,7 +253,7 @@ pub fn run_publish_parallel(
     reporter: &mut dyn crate::engine::Reporter,
 ) -> Result<Vec<PackageReceipt>> {
     let api_base = reg.registry().api_base.trim_end_matches('/');
-    let reg_inner = shipper_registry::HttpRegistryClient::new(api_base);
+    let reg_inner = shipper_registry::HttpRegistryClient::new(api_base)?;
     let mut adapter = HostReporterAdapter { inner: reporter };
     run_publish_parallel_inner(ws, opts, st, state_dir, &reg_inner, &mut adapter)
 }
@@ -25,7 +25,7 @@ use shipper_registry::HttpRegistryClient as RegistryClient;
 use shipper_types::{
     AttemptDetail, AttemptEvidence, ErrorClass, EventType, ExecutionState, PackageEvidence,
     PackageReceipt, PackageState, PlannedPackage, PublishEvent, PublishLevel, PublishRegime,
-    ReadinessConfig, ReadinessEvidence, ReconciliationOutcome, RuntimeOptions,
+    ReadinessConfig, ReadinessEvidence, ReconciliationOutcome, RuntimeOptions, SkipReason,
 };
 
 use super::policy::policy_effects;
@@ -155,7 +155,7 @@ fn record_retry_backoff(
         },
         package: pkg_label.to_string(),
     });
-    let _ = log.write_to_file(events_path);
+    let _ = log.write_to_file_redacted(events_path, cargo::redact_sensitive);
     log.clear();
 }
 
@@ -179,7 +179,7 @@ fn record_rate_limit_observed(
         },
         package: pkg_label.to_string(),
     });
-    let _ = log.write_to_file(events_path);
+    let _ = log.write_to_file_redacted(events_path, cargo::redact_sensitive);
     log.clear();
 }
 
@@ -192,7 +192,7 @@ fn record_readiness_event(
         .lock()
         .map_err(|_| anyhow::anyhow!("event log lock poisoned while recording readiness event"))?;
     log.record(event);
-    log.write_to_file(events_path)?;
+    log.write_to_file_redacted(events_path, cargo::redact_sensitive)?;
     log.clear();
     Ok(())
 }
@@ -266,7 +266,7 @@ pub(super) fn publish_package(
             },
             package: pkg_label.clone(),
         });
-        let _ = log.write_to_file(events_path);
+        let _ = log.write_to_file_redacted(events_path, cargo::redact_sensitive);
         log.clear();
     }
 
@@ -278,7 +278,7 @@ pub(super) fn publish_package(
         ));
 
         let skipped = PackageState::Skipped {
-            reason: "already published".to_string(),
+            reason: SkipReason::AlreadyPublished,
         };
         {
             let Ok(mut state) = st.lock() else {
@@ -300,7 +300,7 @@ pub(super) fn publish_package(
                 },
                 package: pkg_label.clone(),
             });
-            let _ = log.write_to_file(events_path);
+            let _ = log.write_to_file_redacted(events_path, cargo::redact_sensitive);
             log.clear();
         }
 
@@ -320,6 +320,7 @@ pub(super) fn publish_package(
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             }),
         };
     }
@@ -331,6 +332,9 @@ pub(super) fn publish_package(
     let mut attempt_evidence: Vec<AttemptEvidence> = Vec::new();
     let mut readiness_evidence: Vec<ReadinessEvidence> = Vec::new();
     let mut cargo_succeeded = false;
+    // Only a tarball packaged by this run's `cargo publish` is hashed; a
+    // package resumed from Uploaded may have a stale or missing one.
+    let mut uploaded_this_run = false;
 
     // Check if resuming from Uploaded state (cargo publish succeeded previously)
     {
@@ -338,7 +342,8 @@ pub(super) fn publish_package(
             return poisoned_lock("execution state");
         };
         if let Some(pr) = state.packages.get(&key)
-            && matches!(pr.state, PackageState::Uploaded)
+            && (matches!(pr.state, PackageState::Uploaded)
+                || matches!(&pr.state, PackageState::Cancelled { at_state } if at_state == "uploaded"))
         {
             cargo_succeeded = true;
         }
@@ -400,7 +405,7 @@ pub(super) fn publish_package(
                 },
                 package: pkg_label.clone(),
             });
-            let _ = log.write_to_file(events_path);
+            let _ = log.write_to_file_redacted(events_path, cargo::redact_sensitive);
             log.clear();
         }
         write_reconciliation_report_best_effort(state_dir, ws, events_path, reporter);
@@ -437,6 +442,7 @@ pub(super) fn publish_package(
                         compromised_at: None,
                         compromised_by: None,
                         superseded_by: None,
+                        artifact_sha256: None,
                     }),
                 };
             }
@@ -497,6 +503,47 @@ pub(super) fn publish_package(
     // legacy lazy-cached behavior so we remain backward compatible.
     let mut is_new_crate_cached: Option<bool> = p.regime.map(PublishRegime::is_new_crate);
 
+    if !cargo_succeeded
+        && let Some(message) = crate::engine::preflight::dry_run::just_in_time_failure(
+            &ws.workspace_root,
+            &ws.plan.registry.name,
+            &p.name,
+            opts,
+            &effects,
+        )
+    {
+        reporter.error(&format!("{}@{}: {}", p.name, p.version, message));
+        let failed = PackageState::Failed {
+            class: ErrorClass::Permanent,
+            message: message.clone(),
+        };
+        {
+            let Ok(mut state) = st.lock() else {
+                return poisoned_lock("execution state");
+            };
+            update_state_locked(&mut state, &key, failed);
+            let _ = state::save_state(state_dir, &state);
+        }
+        {
+            let Ok(mut log) = event_log.lock() else {
+                return poisoned_lock("event log");
+            };
+            log.record(PublishEvent {
+                timestamp: Utc::now(),
+                event_type: EventType::PackageFailed {
+                    class: ErrorClass::Permanent,
+                    message: message.clone(),
+                },
+                package: pkg_label.clone(),
+            });
+            let _ = log.write_to_file_redacted(events_path, cargo::redact_sensitive);
+            log.clear();
+        }
+        return PackagePublishResult {
+            result: Err(anyhow::anyhow!("{}@{}: {}", p.name, p.version, message)),
+        };
+    }
+
     while attempt < opts.max_attempts {
         attempt += 1;
         {
@@ -507,6 +554,7 @@ pub(super) fn publish_package(
                 pr.attempts = attempt;
                 pr.last_updated_at = Utc::now();
             }
+            state.touch();
             let _ = state::save_state(state_dir, &state);
         }
 
@@ -537,15 +585,10 @@ pub(super) fn publish_package(
                 });
             }
 
-            let out = match cargo::cargo_publish(
-                &ws.workspace_root,
-                &p.name,
-                &ws.plan.registry.name,
-                opts.allow_dirty,
-                opts.no_verify,
-                opts.output_lines,
-                Some(opts.parallel.per_package_timeout),
-            ) {
+            let out = match cargo::cargo_publish(&cargo::PublishArgs {
+                timeout: Some(opts.parallel.per_package_timeout),
+                ..cargo::PublishArgs::new(&ws.workspace_root, opts, &p.name, &ws.plan.registry.name)
+            }) {
                 Ok(o) => o,
                 Err(e) => {
                     reporter.error(&format!(
@@ -581,7 +624,7 @@ pub(super) fn publish_package(
                     },
                     package: pkg_label.clone(),
                 });
-                let _ = log.write_to_file(events_path);
+                let _ = log.write_to_file_redacted(events_path, cargo::redact_sensitive);
                 log.clear();
             }
 
@@ -604,6 +647,7 @@ pub(super) fn publish_package(
                     return PackagePublishResult { result: Err(e) };
                 }
                 cargo_succeeded = true;
+                uploaded_this_run = true;
                 // Persist Uploaded state so resume skips cargo publish
                 {
                     let Ok(mut state) = st.lock() else {
@@ -620,7 +664,10 @@ pub(super) fn publish_package(
                 ));
 
                 let failure_output = format!("{}\n{}", out.stderr_tail, out.stdout_tail);
-                let (class, msg) = classify_cargo_failure(&out.stderr_tail, &out.stdout_tail);
+                // A per-package timeout is retryable even if the partial
+                // output looks like a permanent failure.
+                let (_, msg) = classify_cargo_failure(&out.stderr_tail, &out.stdout_tail);
+                let class = out.error_class();
                 last_err = Some((class.clone(), msg.clone()));
                 let mut attempt_detail = AttemptDetail {
                     package: p.name.clone(),
@@ -703,7 +750,7 @@ pub(super) fn publish_package(
                             },
                             package: pkg_label.clone(),
                         });
-                        let _ = log.write_to_file(events_path);
+                        let _ = log.write_to_file_redacted(events_path, cargo::redact_sensitive);
                         log.clear();
                     }
                     write_reconciliation_report_best_effort(state_dir, ws, events_path, reporter);
@@ -740,7 +787,8 @@ pub(super) fn publish_package(
                                     },
                                     package: pkg_label.clone(),
                                 });
-                                let _ = log.write_to_file(events_path);
+                                let _ = log
+                                    .write_to_file_redacted(events_path, cargo::redact_sensitive);
                                 log.clear();
                             }
 
@@ -783,7 +831,8 @@ pub(super) fn publish_package(
                                 let Ok(mut log) = event_log.lock() else {
                                     return poisoned_lock("event log");
                                 };
-                                let _ = log.write_to_file(events_path);
+                                let _ = log
+                                    .write_to_file_redacted(events_path, cargo::redact_sensitive);
                                 log.clear();
                             }
                             reporter.error(&format!(
@@ -839,7 +888,8 @@ pub(super) fn publish_package(
                             let Ok(mut log) = event_log.lock() else {
                                 return poisoned_lock("event log");
                             };
-                            let _ = log.write_to_file(events_path);
+                            let _ =
+                                log.write_to_file_redacted(events_path, cargo::redact_sensitive);
                             log.clear();
                         }
 
@@ -1004,7 +1054,7 @@ pub(super) fn publish_package(
                             },
                             package: pkg_label.clone(),
                         });
-                        let _ = log.write_to_file(events_path);
+                        let _ = log.write_to_file_redacted(events_path, cargo::redact_sensitive);
                         log.clear();
                     }
 
@@ -1127,6 +1177,11 @@ pub(super) fn publish_package(
 
     let finished_at = Utc::now();
     let duration_ms = start_instant.elapsed().as_millis();
+    let artifact_sha256 = if uploaded_this_run {
+        cargo::published_artifact_sha256(&ws.target_directory, &p.name, &p.version)
+    } else {
+        None
+    };
 
     if let Some((class, msg)) = last_err {
         // Final chance: maybe it eventually showed up.
@@ -1171,6 +1226,7 @@ pub(super) fn publish_package(
                     compromised_at: None,
                     compromised_by: None,
                     superseded_by: None,
+                    artifact_sha256,
                 }),
             };
         } else {
@@ -1200,7 +1256,7 @@ pub(super) fn publish_package(
                     },
                     package: pkg_label,
                 });
-                let _ = log.write_to_file(events_path);
+                let _ = log.write_to_file_redacted(events_path, cargo::redact_sensitive);
                 log.clear();
             }
 
@@ -1254,6 +1310,7 @@ pub(super) fn publish_package(
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256,
         }),
     }
 }
@@ -1273,7 +1330,7 @@ pub(super) fn run_publish_level(
     send_reporter: &Arc<SendReporter>,
 ) -> Result<Vec<PackageReceipt>> {
     let num_packages = level.packages.len();
-    let max_concurrent = opts.parallel.max_concurrent.min(num_packages);
+    let max_concurrent = opts.parallel.resolve_concurrency().min(num_packages);
 
     reporter.info(&format!(
         "Level {}: publishing {} packages (max concurrent: {})",
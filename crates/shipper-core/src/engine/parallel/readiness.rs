@@ -62,13 +62,7 @@ pub(super) fn is_version_visible_with_backoff_and_events(
         let jittered_delay = if attempt == 1 {
             Duration::ZERO
         } else {
-            let base_delay = config.poll_interval;
-            let exponential_delay =
-                base_delay.saturating_mul(2_u32.saturating_pow(attempt.saturating_sub(2).min(16)));
-            let capped_delay = exponential_delay.min(config.max_delay);
-            let jitter_range = config.jitter_factor;
-            let jitter = 1.0 + (rand::random::<f64>() * 2.0 * jitter_range - jitter_range);
-            Duration::from_millis((capped_delay.as_millis() as f64 * jitter).round() as u64)
+            config.apply_jitter(config.backoff_delay(attempt - 1))
         };
 
         let visible = match config.method {
@@ -109,14 +103,7 @@ pub(super) fn is_version_visible_with_backoff_and_events(
             return Ok((false, evidence));
         }
 
-        let base_delay = config.poll_interval;
-        let exponential_delay =
-            base_delay.saturating_mul(2_u32.saturating_pow(attempt.saturating_sub(1).min(16)));
-        let capped_delay = exponential_delay.min(config.max_delay);
-        let jitter_range = config.jitter_factor;
-        let jitter = 1.0 + (rand::random::<f64>() * 2.0 * jitter_range - jitter_range);
-        let next_delay =
-            Duration::from_millis((capped_delay.as_millis() as f64 * jitter).round() as u64);
+        let next_delay = config.apply_jitter(config.backoff_delay(attempt));
         emit_event(readiness_poll_scheduled_event(
             &package,
             attempt.saturating_add(1),
@@ -289,7 +276,7 @@ mod tests {
     #[test]
     fn disabled_returns_immediately_with_single_evidence_visible() {
         let server = spawn_mock_registry(vec![(200, "{}".to_string())]);
-        let reg = RegistryClient::new(&server.base_url);
+        let reg = RegistryClient::new(&server.base_url).expect("client");
 
         let (visible, evidence) =
             is_version_visible_with_backoff(&reg, "serde", "1.0.0", &config_disabled())
@@ -305,7 +292,7 @@ mod tests {
     #[test]
     fn disabled_returns_immediately_with_single_evidence_not_visible() {
         let server = spawn_mock_registry(vec![(404, "{}".to_string())]);
-        let reg = RegistryClient::new(&server.base_url);
+        let reg = RegistryClient::new(&server.base_url).expect("client");
 
         let (visible, evidence) =
             is_version_visible_with_backoff(&reg, "demo", "0.0.1", &config_disabled()).expect("ok");
@@ -319,7 +306,7 @@ mod tests {
     #[test]
     fn api_method_succeeds_on_first_attempt() {
         let server = spawn_mock_registry(vec![(200, "{}".to_string())]);
-        let reg = RegistryClient::new(&server.base_url);
+        let reg = RegistryClient::new(&server.base_url).expect("client");
 
         let (visible, evidence) = is_version_visible_with_backoff(
             &reg,
@@ -337,7 +324,7 @@ mod tests {
     #[test]
     fn api_method_returns_false_after_max_total_wait() {
         let server = spawn_mock_registry(vec![(404, "{}".to_string())]);
-        let reg = RegistryClient::new(&server.base_url);
+        let reg = RegistryClient::new(&server.base_url).expect("client");
 
         let mut cfg = config_enabled(ReadinessMethod::Api);
         cfg.max_total_wait = Duration::from_millis(50);
@@ -361,7 +348,7 @@ mod tests {
         let path = write_sparse_index(td.path(), &["0.1.0", "1.2.3"]);
 
         let server = spawn_mock_registry(vec![(404, "{}".to_string())]);
-        let reg = RegistryClient::new(&server.base_url);
+        let reg = RegistryClient::new(&server.base_url).expect("client");
 
         let mut cfg = config_enabled(ReadinessMethod::Index);
         cfg.index_path = Some(path);
@@ -383,7 +370,7 @@ mod tests {
         let path = write_sparse_index(td.path(), &["0.1.0"]);
 
         let server = spawn_mock_registry(vec![(404, "{}".to_string())]);
-        let reg = RegistryClient::new(&server.base_url);
+        let reg = RegistryClient::new(&server.base_url).expect("client");
 
         let mut cfg = config_enabled(ReadinessMethod::Index);
         cfg.index_path = Some(path);
@@ -398,7 +385,7 @@ mod tests {
     #[test]
     fn is_version_visible_via_index_returns_error_when_local_path_missing() {
         let server = spawn_mock_registry(vec![(404, "{}".to_string())]);
-        let reg = RegistryClient::new(&server.base_url);
+        let reg = RegistryClient::new(&server.base_url).expect("client");
 
         let mut cfg = config_enabled(ReadinessMethod::Index);
         cfg.index_path = Some(PathBuf::from("/this/path/definitely/does/not/exist.json"));
@@ -420,7 +407,7 @@ mod tests {
         let path = write_sparse_index(td.path(), &["1.0.0"]);
 
         let server = spawn_mock_registry(vec![(404, "{}".to_string())]);
-        let reg = RegistryClient::new(&server.base_url);
+        let reg = RegistryClient::new(&server.base_url).expect("client");
 
         let mut cfg = config_enabled(ReadinessMethod::Both);
         cfg.prefer_index = true;
@@ -443,7 +430,7 @@ mod tests {
         let path = write_sparse_index(td.path(), &["0.1.0"]);
 
         let server = spawn_mock_registry(vec![(200, "{}".to_string())]);
-        let reg = RegistryClient::new(&server.base_url);
+        let reg = RegistryClient::new(&server.base_url).expect("client");
 
         let mut cfg = config_enabled(ReadinessMethod::Both);
         cfg.prefer_index = true;
@@ -465,7 +452,7 @@ mod tests {
         let path = write_sparse_index(td.path(), &["1.0.0"]);
 
         let server = spawn_mock_registry(vec![(200, "{}".to_string())]);
-        let reg = RegistryClient::new(&server.base_url);
+        let reg = RegistryClient::new(&server.base_url).expect("client");
 
         let mut cfg = config_enabled(ReadinessMethod::Both);
         cfg.prefer_index = false;
@@ -486,7 +473,7 @@ mod tests {
     #[test]
     fn first_attempt_records_zero_pre_delay() {
         let server = spawn_mock_registry(vec![(200, "{}".to_string())]);
-        let reg = RegistryClient::new(&server.base_url);
+        let reg = RegistryClient::new(&server.base_url).expect("client");
 
         let cfg = config_enabled(ReadinessMethod::Api);
         let (_, evidence) =
@@ -503,7 +490,7 @@ mod tests {
             (404, "{}".to_string()),
             (200, "{}".to_string()),
         ]);
-        let reg = RegistryClient::new(&server.base_url);
+        let reg = RegistryClient::new(&server.base_url).expect("client");
 
         let mut cfg = config_enabled(ReadinessMethod::Api);
         cfg.max_total_wait = Duration::from_secs(5);
@@ -0,0 +1,389 @@
+//! Generic level-by-level publish scheduler.
+//!
+//! [`publish_levels`] walks `ReleasePlan::group_by_levels` and runs each
+//! level's packages through a caller-supplied `publish_fn`, keeping at most
+//! `max_concurrent` of them in flight. Unlike the chunked batches in
+//! `publish::run_publish_level`, a slot is refilled as soon as any package
+//! finishes. Level `n + 1` never starts until every package of level `n` has
+//! finished.
+//!
+//! A failure stops the run at the end of the current level: publishes already
+//! in flight are allowed to finish, but nothing new is started and dependent
+//! levels are reported as skipped. A package that outlives
+//! `per_package_timeout` stops the run the same way, but is reported as
+//! ambiguous: its worker cannot be cancelled and may still publish it.
+
+use std::collections::BTreeMap;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::Arc;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, anyhow};
+use shipper_types::{ParallelConfig, PlannedPackage, PublishLevel, ReleasePlan};
+
+/// Result of [`publish_levels`].
+#[derive(Debug)]
+pub struct LevelsOutcome<T> {
+    /// Packages whose `publish_fn` returned `Ok`, in completion order
+    pub completed: Vec<(PlannedPackage, T)>,
+    /// Packages whose `publish_fn` failed or panicked, in the order the
+    /// failure was observed
+    pub failed: Vec<(PlannedPackage, anyhow::Error)>,
+    /// Packages that exceeded `per_package_timeout`. Their publish may still
+    /// land, so callers must reconcile against the registry before retrying
+    pub ambiguous: Vec<(PlannedPackage, anyhow::Error)>,
+    /// Packages never started because an earlier failure stopped the run
+    pub skipped: Vec<PlannedPackage>,
+}
+
+impl<T> LevelsOutcome<T> {
+    /// Whether every package in the plan was published.
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty() && self.ambiguous.is_empty() && self.skipped.is_empty()
+    }
+
+    fn stopped(&self) -> bool {
+        !self.failed.is_empty() || !self.ambiguous.is_empty()
+    }
+}
+
+impl<T> Default for LevelsOutcome<T> {
+    fn default() -> Self {
+        Self {
+            completed: Vec::new(),
+            failed: Vec::new(),
+            ambiguous: Vec::new(),
+            skipped: Vec::new(),
+        }
+    }
+}
+
+/// Publish `plan` level by level with `publish_fn`.
+///
+/// Each call to `publish_fn` runs on its own thread. A package that has not
+/// returned within `parallel.per_package_timeout` is recorded as ambiguous
+/// (not failed: it may still publish) and its slot is released; the worker
+/// thread is detached and whatever it eventually returns is discarded, so
+/// `publish_fn` should enforce its own hard timeout (as `cargo_publish` does)
+/// rather than rely on this one.
+pub fn publish_levels<T, F>(
+    plan: &ReleasePlan,
+    parallel: &ParallelConfig,
+    publish_fn: F,
+) -> LevelsOutcome<T>
+where
+    T: Send + 'static,
+    F: Fn(&PlannedPackage) -> Result<T> + Send + Sync + 'static,
+{
+    let publish_fn = Arc::new(publish_fn);
+    let max_concurrent = parallel.resolve_concurrency();
+    let mut outcome = LevelsOutcome::default();
+
+    for level in plan.group_by_levels() {
+        if !outcome.stopped() {
+            run_level(
+                &level,
+                max_concurrent,
+                parallel.per_package_timeout,
+                &publish_fn,
+                &mut outcome,
+            );
+        } else {
+            outcome.skipped.extend(level.packages);
+        }
+    }
+
+    outcome
+}
+
+fn run_level<T, F>(
+    level: &PublishLevel,
+    max_concurrent: usize,
+    timeout: Duration,
+    publish_fn: &Arc<F>,
+    outcome: &mut LevelsOutcome<T>,
+) where
+    T: Send + 'static,
+    F: Fn(&PlannedPackage) -> Result<T> + Send + Sync + 'static,
+{
+    let (tx, rx) = mpsc::channel::<(usize, Result<T>)>();
+    let mut pending = level.packages.iter().enumerate();
+    // index into level.packages -> (deadline, package)
+    let mut in_flight: BTreeMap<usize, (Instant, &PlannedPackage)> = BTreeMap::new();
+    let mut level_failed = false;
+
+    loop {
+        while !level_failed && in_flight.len() < max_concurrent {
+            let Some((idx, package)) = pending.next() else {
+                break;
+            };
+            let tx = tx.clone();
+            let publish_fn = Arc::clone(publish_fn);
+            let owned = package.clone();
+            thread::spawn(move || {
+                let result = catch_unwind(AssertUnwindSafe(|| publish_fn(&owned)))
+                    .unwrap_or_else(|_| Err(anyhow!("publish thread panicked")));
+                let _ = tx.send((idx, result));
+            });
+            in_flight.insert(idx, (Instant::now() + timeout, package));
+        }
+
+        let Some(next_deadline) = in_flight.values().map(|(deadline, _)| *deadline).min() else {
+            break;
+        };
+
+        match rx.recv_timeout(next_deadline.saturating_duration_since(Instant::now())) {
+            Ok((idx, result)) => {
+                // A late result from a package that already timed out
+                let Some((_, package)) = in_flight.remove(&idx) else {
+                    continue;
+                };
+                let package = package.clone();
+                match result {
+                    Ok(value) => outcome.completed.push((package, value)),
+                    Err(err) => {
+                        level_failed = true;
+                        outcome.failed.push((package, err));
+                    }
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                let now = Instant::now();
+                let expired: Vec<usize> = in_flight
+                    .iter()
+                    .filter(|(_, (deadline, _))| *deadline <= now)
+                    .map(|(idx, _)| *idx)
+                    .collect();
+                for idx in expired {
+                    let Some((_, package)) = in_flight.remove(&idx) else {
+                        continue;
+                    };
+                    let package = package.clone();
+                    let err = anyhow!(
+                        "{}@{} exceeded per-package timeout of {}; the publish may still complete",
+                        package.name,
+                        package.version,
+                        humantime::format_duration(timeout)
+                    );
+                    level_failed = true;
+                    outcome.ambiguous.push((package, err));
+                }
+            }
+            // We hold `tx`, so the channel cannot disconnect
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    outcome
+        .skipped
+        .extend(pending.map(|(_, package)| package.clone()));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use chrono::Utc;
+    use shipper_types::Registry;
+
+    use super::*;
+
+    fn pkg(name: &str) -> PlannedPackage {
+        PlannedPackage {
+            name: name.to_string(),
+            version: "0.1.0".to_string(),
+            manifest_path: PathBuf::from(format!("{name}/Cargo.toml")),
+            regime: None,
+        }
+    }
+
+    fn plan(names: &[&str], deps: &[(&str, &[&str])]) -> ReleasePlan {
+        ReleasePlan {
+            plan_version: "1".to_string(),
+            plan_id: "plan-levels".to_string(),
+            created_at: Utc::now(),
+            registry: Registry::crates_io(),
+            packages: names.iter().map(|n| pkg(n)).collect(),
+            dependencies: deps
+                .iter()
+                .map(|(name, on)| (name.to_string(), on.iter().map(|d| d.to_string()).collect()))
+                .collect::<BTreeMap<_, _>>(),
+        }
+    }
+
+    fn config(max_concurrent: usize, timeout: Duration) -> ParallelConfig {
+        ParallelConfig {
+            enabled: true,
+            max_concurrent,
+            per_package_timeout: timeout,
+        }
+    }
+
+    fn names(packages: &[PlannedPackage]) -> Vec<String> {
+        packages.iter().map(|p| p.name.clone()).collect()
+    }
+
+    #[test]
+    fn levels_run_in_dependency_order() {
+        // core <- {a, b} <- app
+        let plan = plan(
+            &["core", "a", "b", "app"],
+            &[("a", &["core"]), ("b", &["core"]), ("app", &["a", "b"])],
+        );
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&log);
+
+        let outcome = publish_levels(&plan, &config(4, Duration::from_secs(10)), move |p| {
+            recorded.lock().unwrap().push(format!("start {}", p.name));
+            thread::sleep(Duration::from_millis(20));
+            recorded.lock().unwrap().push(format!("end {}", p.name));
+            Ok(p.name.clone())
+        });
+
+        assert!(outcome.is_success());
+        assert_eq!(outcome.completed.len(), 4);
+
+        let log = log.lock().unwrap();
+        let pos = |entry: &str| log.iter().position(|e| e == entry).expect(entry);
+        assert!(pos("end core") < pos("start a"));
+        assert!(pos("end core") < pos("start b"));
+        assert!(pos("end a") < pos("start app"));
+        assert!(pos("end b") < pos("start app"));
+    }
+
+    #[test]
+    fn concurrency_never_exceeds_cap() {
+        let plan = plan(&["a", "b", "c", "d", "e", "f", "g"], &[]);
+        let current = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let (cur, max) = (Arc::clone(&current), Arc::clone(&peak));
+
+        let outcome = publish_levels(&plan, &config(3, Duration::from_secs(10)), move |_| {
+            let now = cur.fetch_add(1, Ordering::SeqCst) + 1;
+            max.fetch_max(now, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(30));
+            cur.fetch_sub(1, Ordering::SeqCst);
+            Ok(())
+        });
+
+        assert!(outcome.is_success());
+        assert_eq!(outcome.completed.len(), 7);
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn zero_max_concurrent_uses_available_parallelism() {
+        let plan = plan(&["a", "b", "c"], &[]);
+        let current = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let (cur, max) = (Arc::clone(&current), Arc::clone(&peak));
+
+        let outcome = publish_levels(&plan, &config(0, Duration::from_secs(10)), move |_| {
+            let now = cur.fetch_add(1, Ordering::SeqCst) + 1;
+            max.fetch_max(now, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(10));
+            cur.fetch_sub(1, Ordering::SeqCst);
+            Ok(())
+        });
+
+        assert!(outcome.is_success());
+        let auto = config(0, Duration::from_secs(10)).resolve_concurrency();
+        let peak = peak.load(Ordering::SeqCst);
+        assert!((1..=auto).contains(&peak), "peak {peak} vs auto {auto}");
+    }
+
+    #[test]
+    fn failure_lets_in_flight_finish_and_skips_dependents() {
+        // level 0: bad, slow, later (cap 2: `later` would only start after a slot frees)
+        // level 1: app
+        let plan = plan(
+            &["bad", "slow", "later", "app"],
+            &[("app", &["bad", "slow", "later"])],
+        );
+
+        let outcome = publish_levels(&plan, &config(2, Duration::from_secs(10)), |p| {
+            match p.name.as_str() {
+                "bad" => Err(anyhow!("boom")),
+                "slow" => {
+                    thread::sleep(Duration::from_millis(100));
+                    Ok(())
+                }
+                _ => Ok(()),
+            }
+        });
+
+        assert!(!outcome.is_success());
+        assert_eq!(
+            names(
+                &outcome
+                    .completed
+                    .iter()
+                    .map(|(p, _)| p.clone())
+                    .collect::<Vec<_>>()
+            ),
+            ["slow"]
+        );
+        assert_eq!(outcome.failed.len(), 1);
+        assert_eq!(outcome.failed[0].0.name, "bad");
+        assert!(format!("{:#}", outcome.failed[0].1).contains("boom"));
+        assert_eq!(names(&outcome.skipped), ["later", "app"]);
+    }
+
+    #[test]
+    fn package_exceeding_timeout_is_ambiguous_and_stops_descent() {
+        let plan = plan(&["stuck", "ok", "app"], &[("app", &["stuck", "ok"])]);
+
+        let outcome = publish_levels(&plan, &config(4, Duration::from_millis(100)), |p| {
+            if p.name == "stuck" {
+                thread::sleep(Duration::from_secs(2));
+            }
+            Ok(())
+        });
+
+        assert!(!outcome.is_success());
+        assert!(outcome.failed.is_empty());
+        assert_eq!(outcome.ambiguous.len(), 1);
+        assert_eq!(outcome.ambiguous[0].0.name, "stuck");
+        assert!(
+            outcome.ambiguous[0]
+                .1
+                .to_string()
+                .contains("exceeded per-package timeout of 100ms")
+        );
+        assert_eq!(outcome.completed.len(), 1);
+        assert_eq!(names(&outcome.skipped), ["app"]);
+    }
+
+    #[test]
+    fn panicking_publish_is_reported_as_failure() {
+        let plan = plan(&["explodes"], &[]);
+
+        let outcome: LevelsOutcome<()> =
+            publish_levels(&plan, &config(1, Duration::from_secs(10)), |_| {
+                panic!("publish_fn bug")
+            });
+
+        assert_eq!(outcome.failed.len(), 1);
+        assert!(
+            outcome.failed[0]
+                .1
+                .to_string()
+                .contains("publish thread panicked")
+        );
+    }
+
+    #[test]
+    fn empty_plan_is_success() {
+        let outcome: LevelsOutcome<()> =
+            publish_levels(&plan(&[], &[]), &config(4, Duration::from_secs(1)), |_| {
+                Ok(())
+            });
+        assert!(outcome.is_success());
+        assert!(outcome.completed.is_empty());
+    }
+}
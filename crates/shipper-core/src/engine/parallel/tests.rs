@@ -20,7 +20,7 @@ use shipper_registry::HttpRegistryClient as RegistryClient;
 use shipper_types::{
     ErrorClass, EventType, ExecutionState, PackageEvidence, PackageProgress, PackageReceipt,
     PackageState, PlannedPackage, PublishLevel, ReadinessConfig, Registry, ReleasePlan,
-    RuntimeOptions,
+    RuntimeOptions, SkipReason,
 };
 
 fn make_send_reporter() -> Arc<SendReporter> {
@@ -256,6 +256,7 @@ fn spawn_registry_server(
 fn planned_workspace(workspace_root: &Path, api_base: String) -> PlannedWorkspace {
     PlannedWorkspace {
         workspace_root: workspace_root.to_path_buf(),
+        target_directory: workspace_root.join("target"),
         plan: ReleasePlan {
             plan_version: "1".to_string(),
             plan_id: "plan-parallel".to_string(),
@@ -283,6 +284,10 @@ fn default_opts(state_dir: PathBuf) -> RuntimeOptions {
         skip_ownership_check: true,
         strict_ownership: false,
         no_verify: false,
+        features: Vec::new(),
+        no_default_features: false,
+        all_features: false,
+        cargo: Default::default(),
         max_attempts: 2,
         base_delay: Duration::from_millis(0),
         max_delay: Duration::from_millis(0),
@@ -370,7 +375,7 @@ fn test_publish_package_skips_already_published() {
     );
 
     let ws = planned_workspace(td.path(), server.base_url.clone());
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let opts = default_opts(PathBuf::from(".shipper"));
     let state_dir = td.path().join(".shipper");
     let st = Arc::new(Mutex::new(init_state_for_package(
@@ -429,7 +434,7 @@ fn test_publish_package_publishes_successfully() {
     );
 
     let ws = planned_workspace(td.path(), server.base_url.clone());
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let opts = default_opts(PathBuf::from(".shipper"));
     let state_dir = td.path().join(".shipper");
     let st = Arc::new(Mutex::new(init_state_for_package(
@@ -488,7 +493,7 @@ fn test_publish_package_handles_permanent_failure() {
     );
 
     let ws = planned_workspace(td.path(), server.base_url.clone());
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let opts = default_opts(PathBuf::from(".shipper"));
     let state_dir = td.path().join(".shipper");
     let st = Arc::new(Mutex::new(init_state_for_package(
@@ -563,7 +568,7 @@ fn test_publish_package_retries_on_transient() {
     );
 
     let ws = planned_workspace(td.path(), server.base_url.clone());
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let mut opts = default_opts(PathBuf::from(".shipper"));
     opts.max_attempts = 2;
     let state_dir = td.path().join(".shipper");
@@ -632,6 +637,7 @@ fn test_run_publish_level_processes_packages() {
 
     let ws = PlannedWorkspace {
         workspace_root: td.path().to_path_buf(),
+        target_directory: td.path().join("target"),
         plan: ReleasePlan {
             plan_version: "1".to_string(),
             plan_id: "plan-level".to_string(),
@@ -660,7 +666,7 @@ fn test_run_publish_level_processes_packages() {
         skipped: vec![],
     };
 
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let opts = default_opts(PathBuf::from(".shipper"));
     let state_dir = td.path().join(".shipper");
     let mut packages = BTreeMap::new();
@@ -775,7 +781,7 @@ fn test_run_publish_parallel_single_package() {
     );
 
     let ws = planned_workspace(td.path(), server.base_url.clone());
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let state_dir = td.path().join(".shipper");
     let opts = default_opts(state_dir.clone());
     let mut st = init_state_for_package(&ws.plan.plan_id, &ws.plan.registry, "demo", "0.1.0");
@@ -824,6 +830,7 @@ fn test_run_publish_parallel_multiple_levels() {
     // "dependent" depends on "base" so they end up in different levels
     let ws = PlannedWorkspace {
         workspace_root: td.path().to_path_buf(),
+        target_directory: td.path().join("target"),
         plan: ReleasePlan {
             plan_version: "1".to_string(),
             plan_id: "plan-multi-level".to_string(),
@@ -852,7 +859,7 @@ fn test_run_publish_parallel_multiple_levels() {
         skipped: vec![],
     };
 
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let state_dir = td.path().join(".shipper");
     let opts = default_opts(state_dir.clone());
 
@@ -931,7 +938,7 @@ fn test_publish_package_handles_uploaded_resume() {
     );
 
     let ws = planned_workspace(td.path(), server.base_url.clone());
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let state_dir = td.path().join(".shipper");
     let opts = default_opts(state_dir.clone());
 
@@ -1016,7 +1023,7 @@ fn test_publish_package_records_attempt_evidence() {
     );
 
     let ws = planned_workspace(td.path(), server.base_url.clone());
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let state_dir = td.path().join(".shipper");
     let opts = default_opts(state_dir.clone());
     let st = Arc::new(Mutex::new(init_state_for_package(
@@ -1117,6 +1124,7 @@ fn test_run_publish_level_respects_max_concurrent() {
 
     let ws = PlannedWorkspace {
         workspace_root: td.path().to_path_buf(),
+        target_directory: td.path().join("target"),
         plan: ReleasePlan {
             plan_version: "1".to_string(),
             plan_id: "plan-concurrent".to_string(),
@@ -1132,7 +1140,7 @@ fn test_run_publish_level_respects_max_concurrent() {
         skipped: vec![],
     };
 
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let state_dir = td.path().join(".shipper");
     let mut opts = default_opts(state_dir.clone());
     // Limit concurrency to 2 (with 4 packages, should chunk into 2 batches)
@@ -1241,6 +1249,7 @@ fn test_levels_execute_in_dependency_order() {
 
     let ws = PlannedWorkspace {
         workspace_root: td.path().to_path_buf(),
+        target_directory: td.path().join("target"),
         plan: ReleasePlan {
             plan_version: "1".to_string(),
             plan_id: "plan-ordering".to_string(),
@@ -1285,7 +1294,7 @@ fn test_levels_execute_in_dependency_order() {
     assert_eq!(levels[1].packages[0].name, "b");
     assert_eq!(levels[2].packages[0].name, "c");
 
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let state_dir = td.path().join(".shipper");
     let opts = default_opts(state_dir.clone());
     let mut packages = BTreeMap::new();
@@ -1364,6 +1373,7 @@ fn test_failed_level_stops_subsequent_levels() {
 
     let ws = PlannedWorkspace {
         workspace_root: td.path().to_path_buf(),
+        target_directory: td.path().join("target"),
         plan: ReleasePlan {
             plan_version: "1".to_string(),
             plan_id: "plan-error-prop".to_string(),
@@ -1392,7 +1402,7 @@ fn test_failed_level_stops_subsequent_levels() {
         skipped: vec![],
     };
 
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let state_dir = td.path().join(".shipper");
     let mut opts = default_opts(state_dir.clone());
     opts.max_attempts = 1; // fail fast
@@ -1498,6 +1508,7 @@ fn test_partial_success_within_level() {
 
     let ws = PlannedWorkspace {
         workspace_root: td.path().to_path_buf(),
+        target_directory: td.path().join("target"),
         plan: ReleasePlan {
             plan_version: "1".to_string(),
             plan_id: "plan-partial".to_string(),
@@ -1513,7 +1524,7 @@ fn test_partial_success_within_level() {
         skipped: vec![],
     };
 
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let state_dir = td.path().join(".shipper");
     let mut opts = default_opts(state_dir.clone());
     opts.max_attempts = 1;
@@ -1646,7 +1657,7 @@ fn test_webhook_events_sent_on_publish() {
     });
 
     let ws = planned_workspace(td.path(), registry_server.base_url.clone());
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let state_dir = td.path().join(".shipper");
     let mut opts = default_opts(state_dir.clone());
     opts.webhook = shipper_webhook::WebhookConfig {
@@ -1701,6 +1712,7 @@ fn test_resume_from_skips_earlier_levels() {
 
     let ws = PlannedWorkspace {
         workspace_root: td.path().to_path_buf(),
+        target_directory: td.path().join("target"),
         plan: ReleasePlan {
             plan_version: "1".to_string(),
             plan_id: "plan-resume".to_string(),
@@ -1729,7 +1741,7 @@ fn test_resume_from_skips_earlier_levels() {
         skipped: vec![],
     };
 
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let state_dir = td.path().join(".shipper");
     let mut opts = default_opts(state_dir.clone());
     opts.resume_from = Some("dependent".to_string());
@@ -1891,6 +1903,7 @@ fn test_all_packages_already_published() {
 
     let ws = PlannedWorkspace {
         workspace_root: td.path().to_path_buf(),
+        target_directory: td.path().join("target"),
         plan: ReleasePlan {
             plan_version: "1".to_string(),
             plan_id: "plan-all-published".to_string(),
@@ -1928,7 +1941,7 @@ fn test_all_packages_already_published() {
         skipped: vec![],
     };
 
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let state_dir = td.path().join(".shipper");
     let opts = default_opts(state_dir.clone());
 
@@ -2036,6 +2049,7 @@ fn test_max_concurrency_one_serializes_execution() {
 
     let ws = PlannedWorkspace {
         workspace_root: td.path().to_path_buf(),
+        target_directory: td.path().join("target"),
         plan: ReleasePlan {
             plan_version: "1".to_string(),
             plan_id: "plan-serial".to_string(),
@@ -2051,7 +2065,7 @@ fn test_max_concurrency_one_serializes_execution() {
         skipped: vec![],
     };
 
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let state_dir = td.path().join(".shipper");
     let mut opts = default_opts(state_dir.clone());
     opts.parallel.max_concurrent = 1; // force serialization
@@ -2181,7 +2195,7 @@ fn test_execution_result_all_skipped_is_success() {
             version: "1.0.0".to_string(),
             attempts: 0,
             state: PackageState::Skipped {
-                reason: "already published".into(),
+                reason: SkipReason::AlreadyPublished,
             },
             started_at: Utc::now(),
             finished_at: Utc::now(),
@@ -2193,13 +2207,14 @@ fn test_execution_result_all_skipped_is_success() {
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256: None,
         },
         PackageReceipt {
             name: "b".to_string(),
             version: "1.0.0".to_string(),
             attempts: 0,
             state: PackageState::Skipped {
-                reason: "already published".into(),
+                reason: SkipReason::AlreadyPublished,
             },
             started_at: Utc::now(),
             finished_at: Utc::now(),
@@ -2211,6 +2226,7 @@ fn test_execution_result_all_skipped_is_success() {
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256: None,
         },
     ];
 
@@ -2244,7 +2260,7 @@ fn test_resume_from_nonexistent_skips_all_levels() {
     );
 
     let ws = planned_workspace(td.path(), server.base_url.clone());
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let state_dir = td.path().join(".shipper");
     let mut opts = default_opts(state_dir.clone());
     opts.resume_from = Some("nonexistent-pkg".to_string());
@@ -2907,6 +2923,7 @@ fn test_error_in_first_level_prevents_all_subsequent() {
 
     let ws = PlannedWorkspace {
         workspace_root: td.path().to_path_buf(),
+        target_directory: td.path().join("target"),
         plan: ReleasePlan {
             plan_version: "1".to_string(),
             plan_id: "plan-halt-chain".to_string(),
@@ -2944,7 +2961,7 @@ fn test_error_in_first_level_prevents_all_subsequent() {
         skipped: vec![],
     };
 
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let state_dir = td.path().join(".shipper");
     let mut opts = default_opts(state_dir.clone());
     opts.max_attempts = 1;
@@ -3019,6 +3036,7 @@ fn test_empty_plan_produces_no_receipts() {
 
     let ws = PlannedWorkspace {
         workspace_root: td.path().to_path_buf(),
+        target_directory: td.path().join("target"),
         plan: ReleasePlan {
             plan_version: "1".to_string(),
             plan_id: "plan-empty".to_string(),
@@ -3034,7 +3052,7 @@ fn test_empty_plan_produces_no_receipts() {
         skipped: vec![],
     };
 
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let state_dir = td.path().join(".shipper");
     let opts = default_opts(state_dir.clone());
     let mut st = ExecutionState {
@@ -3208,6 +3226,7 @@ fn test_max_concurrent_exceeds_package_count() {
 
     let ws = PlannedWorkspace {
         workspace_root: td.path().to_path_buf(),
+        target_directory: td.path().join("target"),
         plan: ReleasePlan {
             plan_version: "1".to_string(),
             plan_id: "plan-over-concurrent".to_string(),
@@ -3223,7 +3242,7 @@ fn test_max_concurrent_exceeds_package_count() {
         skipped: vec![],
     };
 
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let state_dir = td.path().join(".shipper");
     let mut opts = default_opts(state_dir.clone());
     opts.parallel.max_concurrent = 100; // far exceeds 2 packages
@@ -3322,6 +3341,7 @@ fn test_independent_failures_both_reported() {
 
     let ws = PlannedWorkspace {
         workspace_root: td.path().to_path_buf(),
+        target_directory: td.path().join("target"),
         plan: ReleasePlan {
             plan_version: "1".to_string(),
             plan_id: "plan-dual-fail".to_string(),
@@ -3337,7 +3357,7 @@ fn test_independent_failures_both_reported() {
         skipped: vec![],
     };
 
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let state_dir = td.path().join(".shipper");
     let mut opts = default_opts(state_dir.clone());
     opts.max_attempts = 1;
@@ -3438,6 +3458,7 @@ fn test_concurrent_state_updates_consistent() {
 
     let ws = PlannedWorkspace {
         workspace_root: td.path().to_path_buf(),
+        target_directory: td.path().join("target"),
         plan: ReleasePlan {
             plan_version: "1".to_string(),
             plan_id: "plan-concurrent-state".to_string(),
@@ -3453,7 +3474,7 @@ fn test_concurrent_state_updates_consistent() {
         skipped: vec![],
     };
 
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let state_dir = td.path().join(".shipper");
     let mut opts = default_opts(state_dir.clone());
     opts.parallel.max_concurrent = 4; // all run concurrently
@@ -3564,13 +3585,14 @@ fn test_execution_result_mixed_published_and_skipped_is_success() {
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256: None,
         },
         PackageReceipt {
             name: "b".to_string(),
             version: "1.0.0".to_string(),
             attempts: 0,
             state: PackageState::Skipped {
-                reason: "already published".into(),
+                reason: SkipReason::AlreadyPublished,
             },
             started_at: Utc::now(),
             finished_at: Utc::now(),
@@ -3582,6 +3604,7 @@ fn test_execution_result_mixed_published_and_skipped_is_success() {
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256: None,
         },
         PackageReceipt {
             name: "c".to_string(),
@@ -3598,6 +3621,7 @@ fn test_execution_result_mixed_published_and_skipped_is_success() {
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256: None,
         },
     ];
 
@@ -3635,6 +3659,7 @@ fn test_execution_result_partial_failure() {
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256: None,
         },
         PackageReceipt {
             name: "bad".to_string(),
@@ -3654,6 +3679,7 @@ fn test_execution_result_partial_failure() {
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256: None,
         },
     ];
 
@@ -3696,6 +3722,7 @@ fn test_execution_result_complete_failure() {
         compromised_at: None,
         compromised_by: None,
         superseded_by: None,
+        artifact_sha256: None,
     }];
 
     let success_count = receipts
@@ -3746,6 +3773,7 @@ fn test_level_message_includes_max_concurrent() {
 
     let ws = PlannedWorkspace {
         workspace_root: td.path().to_path_buf(),
+        target_directory: td.path().join("target"),
         plan: ReleasePlan {
             plan_version: "1".to_string(),
             plan_id: "plan-msg-test".to_string(),
@@ -3761,7 +3789,7 @@ fn test_level_message_includes_max_concurrent() {
         skipped: vec![],
     };
 
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let state_dir = td.path().join(".shipper");
     let mut opts = default_opts(state_dir.clone());
     opts.parallel.max_concurrent = 2;
@@ -3853,7 +3881,7 @@ fn test_state_persisted_to_disk_after_level() {
         ..ws
     };
 
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let state_dir = td.path().join(".shipper");
     let opts = default_opts(state_dir.clone());
     let mut st = init_state_for_package(&ws.plan.plan_id, &ws.plan.registry, "saved", "0.1.0");
@@ -4133,7 +4161,7 @@ fn reconcile_bdd_ambiguous_resolves_to_published() {
     );
 
     let ws = planned_workspace(td.path(), server.base_url.clone());
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let opts = reconcile_scenario_opts(PathBuf::from(".shipper"));
     let state_dir = td.path().join(".shipper");
     let st = Arc::new(Mutex::new(init_state_for_package(
@@ -4251,7 +4279,7 @@ fn reconcile_bdd_ambiguous_resolves_to_not_published_then_retries() {
     );
 
     let ws = planned_workspace(td.path(), server.base_url.clone());
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let mut opts = reconcile_scenario_opts(PathBuf::from(".shipper"));
     opts.max_attempts = 2;
     let state_dir = td.path().join(".shipper");
@@ -4357,7 +4385,7 @@ fn reconcile_bdd_resume_from_ambiguous_state_skips_republish() {
     );
 
     let ws = planned_workspace(td.path(), server.base_url.clone());
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let opts = reconcile_scenario_opts(PathBuf::from(".shipper"));
     let state_dir = td.path().join(".shipper");
 
@@ -4478,7 +4506,7 @@ fn reconcile_bdd_resume_from_ambiguous_state_still_unknown_writes_report() {
     );
 
     let ws = planned_workspace(td.path(), server.base_url.clone());
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let opts = reconcile_scenario_opts(PathBuf::from(".shipper"));
     let state_dir = td.path().join(".shipper");
 
@@ -4601,7 +4629,7 @@ fn reconcile_bdd_ambiguous_resolves_to_still_unknown() {
     );
 
     let ws = planned_workspace(td.path(), server.base_url.clone());
-    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str());
+    let reg = RegistryClient::new(ws.plan.registry.api_base.as_str()).expect("client");
     let opts = reconcile_scenario_opts(PathBuf::from(".shipper"));
     let state_dir = td.path().join(".shipper");
     let st = Arc::new(Mutex::new(init_state_for_package(
@@ -208,8 +208,10 @@ pub(crate) fn to_micro_payload(payload: &WebhookPayload) -> shipper_webhook::Web
         version,
         registry,
         error,
+        summary: None,
         extra: extra_fields,
     }
+    .redacted(crate::ops::cargo::redact_sensitive)
 }
 
 #[cfg(test)]
@@ -223,6 +225,10 @@ mod tests {
             webhook_type: WebhookType::Generic,
             secret: None,
             timeout_secs: 30,
+            retry: None,
+            max_list_items: None,
+            dry_run: false,
+            tls: Default::default(),
         }
     }
 
@@ -372,6 +378,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn to_micro_payload_redacts_tokens_in_failure_message() {
+        let micro = to_micro_payload(&sample_payload(WebhookEvent::PublishFailed {
+            plan_id: "plan-x".to_string(),
+            package_name: "cli".to_string(),
+            package_version: "0.4.0".to_string(),
+            error_class: "Permanent".to_string(),
+            message: "401 Unauthorized: Authorization: Bearer cio_s3cr3t_t0ken".to_string(),
+        }));
+        assert!(
+            !micro.message.contains("cio_s3cr3t_t0ken"),
+            "{}",
+            micro.message
+        );
+        assert!(micro.message.contains("[REDACTED]"), "{}", micro.message);
+        let error = micro.error.expect("error");
+        assert!(!error.contains("cio_s3cr3t_t0ken"), "{error}");
+    }
+
     #[test]
     fn to_micro_payload_publish_completed_success_when_no_failures() {
         let micro = to_micro_payload(&sample_payload(completed_event(0)));
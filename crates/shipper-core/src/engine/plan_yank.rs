@@ -254,6 +254,7 @@ mod tests {
     use chrono::Utc;
     use shipper_types::{
         EnvironmentFingerprint, PackageEvidence, PackageReceipt, PackageState, Receipt, Registry,
+        SkipReason,
     };
     use std::path::PathBuf;
 
@@ -273,6 +274,7 @@ mod tests {
             compromised_at: compromised.map(|_| Utc::now()),
             compromised_by: compromised.map(str::to_string),
             superseded_by: None,
+            artifact_sha256: None,
         }
     }
 
@@ -325,7 +327,7 @@ mod tests {
             pkg(
                 "c",
                 PackageState::Skipped {
-                    reason: "already there".into(),
+                    reason: SkipReason::Manual("already there".to_string()),
                 },
                 None,
             ),
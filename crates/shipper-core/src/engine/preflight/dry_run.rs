@@ -1,4 +1,5 @@
-//! Workspace- and package-scope dry-run verification for preflight.
+//! Workspace- and package-scope dry-run verification for preflight, plus the
+//! just-in-time per-package dry-run the publish loops run before each crate.
 
 use std::collections::BTreeMap;
 use std::path::Path;
@@ -14,11 +15,14 @@ use crate::types::{RuntimeOptions, VerifyMode};
 /// `workspace_passed` / `workspace_output` represent the workspace-level
 /// dry-run when `VerifyMode::Workspace` is active (or a synthesized
 /// "skipped" summary otherwise). `per_package` is populated only when
-/// `VerifyMode::Package` is active.
+/// `VerifyMode::Package` is active; `package_outputs` keeps the raw cargo
+/// output for each package that ran, so the registry check can fall back
+/// on cargo's "already uploaded" error.
 pub(in crate::engine) struct DryRunOutcome {
     pub workspace_passed: bool,
     pub workspace_output: String,
     pub per_package: BTreeMap<String, (bool, Option<String>)>,
+    pub package_outputs: BTreeMap<String, cargo::CargoOutput>,
 }
 
 pub(in crate::engine) fn execute(
@@ -31,12 +35,13 @@ pub(in crate::engine) fn execute(
     let (workspace_passed, workspace_output) =
         workspace_dry_run(ws, opts, effects, state_dir, reporter);
 
-    let per_package = per_package_dry_run(ws, opts, effects, reporter);
+    let (per_package, package_outputs) = per_package_dry_run(ws, opts, effects, reporter);
 
     DryRunOutcome {
         workspace_passed,
         workspace_output,
         per_package,
+        package_outputs,
     }
 }
 
@@ -61,6 +66,7 @@ fn workspace_dry_run(
         reporter.info("running workspace dry-run verification...");
         let dry_run_result = cargo::cargo_publish_dry_run_workspace(
             workspace_root,
+            &opts.cargo,
             &ws.plan.registry.name,
             opts.allow_dirty,
             opts.output_lines,
@@ -110,6 +116,12 @@ fn workspace_dry_run(
             true,
             "workspace dry-run skipped (policy, --no-verify, or verify_mode=none)".to_string(),
         )
+    } else if opts.verify_mode == VerifyMode::PackageJustInTime {
+        // Verified by the publish loop right before each crate goes out.
+        (
+            true,
+            "workspace dry-run skipped (verify_mode=package_just_in_time)".to_string(),
+        )
     } else {
         // Package mode - handled per-package below
         (
@@ -124,18 +136,21 @@ fn per_package_dry_run(
     opts: &RuntimeOptions,
     effects: &PolicyEffects,
     reporter: &mut dyn Reporter,
-) -> BTreeMap<String, (bool, Option<String>)> {
+) -> (
+    BTreeMap<String, (bool, Option<String>)>,
+    BTreeMap<String, cargo::CargoOutput>,
+) {
     if effects.run_dry_run && opts.verify_mode == VerifyMode::Package {
         reporter.info("running per-package dry-run verification...");
         let mut results = BTreeMap::new();
+        let mut outputs = BTreeMap::new();
         for p in &ws.plan.packages {
-            let result = cargo::cargo_publish_dry_run_package(
+            let result = cargo::cargo_publish_dry_run_package(&cargo::PublishArgs::new(
                 &ws.workspace_root,
+                opts,
                 &p.name,
                 &ws.plan.registry.name,
-                opts.allow_dirty,
-                opts.output_lines,
-            );
+            ));
             let (passed, output) = match &result {
                 Ok(out) => (
                     out.exit_code == 0,
@@ -147,12 +162,60 @@ fn per_package_dry_run(
                 Err(e) => (false, Some(format!("dry-run failed: {e:#}"))),
             };
             if !passed {
-                reporter.warn(&format!("{}@{}: dry-run failed", p.name, p.version));
+                if result.as_ref().is_ok_and(cargo::crate_already_published) {
+                    reporter.info(&format!(
+                        "{}@{}: dry-run reports version already published",
+                        p.name, p.version
+                    ));
+                } else {
+                    reporter.warn(&format!("{}@{}: dry-run failed", p.name, p.version));
+                }
             }
             results.insert(p.name.clone(), (passed, output));
+            if let Ok(out) = result {
+                outputs.insert(p.name.clone(), out);
+            }
         }
-        results
+        (results, outputs)
     } else {
-        BTreeMap::new()
+        (BTreeMap::new(), BTreeMap::new())
+    }
+}
+
+/// Dry-run `name` right before it is published under
+/// [`VerifyMode::PackageJustInTime`].
+///
+/// Returns `None` when the publish may go ahead: the mode is not
+/// just-in-time, policy disables dry-runs, the dry-run passed, or it failed
+/// only because the version is already on the registry (the publish step
+/// skips it). Otherwise returns a message describing the failure. Running
+/// here rather than in preflight means the dry-run resolves against the
+/// crates this run has already published.
+pub(in crate::engine) fn just_in_time_failure(
+    workspace_root: &Path,
+    registry_name: &str,
+    name: &str,
+    opts: &RuntimeOptions,
+    effects: &PolicyEffects,
+) -> Option<String> {
+    if !effects.run_dry_run || opts.verify_mode != VerifyMode::PackageJustInTime {
+        return None;
+    }
+    match cargo::cargo_publish_dry_run_package(&cargo::PublishArgs::new(
+        workspace_root,
+        opts,
+        name,
+        registry_name,
+    )) {
+        Ok(out) if out.exit_code == 0 || cargo::crate_already_published(&out) => None,
+        Ok(out) => Some(format!(
+            "just-in-time dry-run failed: exit_code={}; stderr_tail={:?}",
+            out.exit_code,
+            shipper_output_sanitizer::tail_lines(
+                &shipper_output_sanitizer::strip_ansi(&out.stderr_tail),
+                6
+            )
+        )),
+        Err(e) => Some(format!("just-in-time dry-run failed: {e:#}")),
     }
 }
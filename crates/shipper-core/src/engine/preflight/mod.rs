@@ -12,12 +12,11 @@ use chrono::Utc;
 use crate::engine::{Reporter, init_registry_client, policy_effects};
 use crate::git;
 use crate::ops::auth;
+use crate::ops::cargo::redact_sensitive;
 use crate::plan::PlannedWorkspace;
 use crate::runtime::execution::resolve_state_dir;
 use crate::state::events;
-use crate::types::{
-    AuthType, EventType, Finishability, PreflightReport, PublishEvent, RuntimeOptions,
-};
+use crate::types::{EventType, Finishability, PreflightReport, PublishEvent, RuntimeOptions};
 
 pub(in crate::engine) mod dry_run;
 pub(in crate::engine) mod duration;
@@ -81,8 +80,8 @@ pub(in crate::engine) fn run(
 
     let token = auth::resolve_token(&ws.plan.registry.name)?;
     let token_detected = token.as_ref().map(|s| !s.is_empty()).unwrap_or(false);
-    let auth_type = auth::detect_auth_type_from_token(token.as_deref());
-    warn_if_token_auth_overrides_oidc(&ws.plan.registry.name, &auth_type, reporter);
+    let auth_type = auth::detect_auth_type(&ws.plan.registry.name)?;
+    warn_if_token_auth_overrides_oidc(&ws.plan.registry.name, token_detected, reporter);
 
     if effects.strict_ownership && !token_detected {
         event_log.record(PublishEvent {
@@ -160,14 +159,14 @@ pub(in crate::engine) fn run(
 
 fn warn_if_token_auth_overrides_oidc(
     registry_name: &str,
-    auth_type: &Option<AuthType>,
+    token_detected: bool,
     reporter: &mut dyn Reporter,
 ) {
     let default_registry = matches!(
         registry_name,
         "" | auth::CRATES_IO_REGISTRY | "crates.io" | "crates_io"
     );
-    if !default_registry || auth_type != &Some(AuthType::Token) {
+    if !default_registry || !token_detected {
         return;
     }
 
@@ -175,7 +174,7 @@ fn warn_if_token_auth_overrides_oidc(
     let oidc_token_present = std::env::var_os("ACTIONS_ID_TOKEN_REQUEST_TOKEN").is_some();
     if oidc_url_present || oidc_token_present {
         reporter.warn(
-            "Trusted Publishing OIDC environment is present, but Shipper is using Cargo token auth. \
+            "Trusted Publishing OIDC environment is present, but a Cargo token is also configured and cargo will use it. \
              This is allowed as fallback; prefer the short-lived token minted by rust-lang/crates-io-auth-action@v1 for release runs.",
         );
     }
@@ -198,5 +197,5 @@ fn resolve_events_path(state_dir: &Path, run_opts: PreflightRunOptions) -> std::
 }
 
 fn flush_events(log: &events::EventLog, path: &Path) -> Result<()> {
-    log.write_to_file(path)
+    log.write_to_file_redacted(path, redact_sensitive)
 }
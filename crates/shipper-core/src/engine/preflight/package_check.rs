@@ -3,6 +3,7 @@
 use anyhow::Result;
 use chrono::Utc;
 
+use crate::cargo;
 use crate::engine::Reporter;
 use crate::plan::PlannedWorkspace;
 use crate::registry::RegistryClient;
@@ -37,7 +38,12 @@ pub(in crate::engine) fn check_packages(
     let mut any_ownership_unverified = false;
 
     for p in ws.plan.packages.iter_mut() {
-        let already_published = reg.version_exists(&p.name, &p.version)?;
+        let already_published = cargo::is_already_published(
+            reg,
+            &p.name,
+            &p.version,
+            dry_run.package_outputs.get(&p.name),
+        )?;
         let is_new_crate = reg.check_new_crate(&p.name)?;
 
         // #106 PR 1: stamp the detected regime onto the plan so the
@@ -66,6 +72,9 @@ pub(in crate::engine) fn check_packages(
                 .per_package
                 .get(&p.name)
                 .cloned()
+                // A dry-run that failed only because the version is already
+                // on the registry is not a blocker: the package is skipped.
+                .map(|(passed, output)| (passed || already_published, output))
                 .unwrap_or((true, None))
         } else {
             (
@@ -4,6 +4,7 @@ use anyhow::{Result, bail};
 use chrono::Utc;
 
 use crate::engine::{Reporter, sequential_reconcile, write_reconciliation_report_best_effort};
+use crate::ops::cargo::redact_sensitive;
 use crate::plan::PlannedWorkspace;
 use crate::registry::RegistryClient;
 use crate::runtime::execution::{pkg_key, update_state};
@@ -57,7 +58,7 @@ pub(crate) fn resolve_ambiguous_resume_state(
         },
         package: pkg_label,
     });
-    event_log.write_to_file(events_path)?;
+    event_log.write_to_file_redacted(events_path, redact_sensitive)?;
     event_log.clear();
     write_reconciliation_report_best_effort(state_dir, ws, events_path, reporter);
     let reconciliation_report_path = reconciliation_path(state_dir);
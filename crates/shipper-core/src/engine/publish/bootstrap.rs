@@ -7,6 +7,7 @@ use chrono::{DateTime, Utc};
 use crate::engine::{Reporter, init_registry_client, init_state, rehearsal};
 use crate::git;
 use crate::lock;
+use crate::ops::cargo::redact_sensitive;
 use crate::plan::PlannedWorkspace;
 use crate::registry::RegistryClient;
 use crate::runtime::environment;
@@ -126,6 +127,11 @@ fn load_or_initialize_state(
     match state::load_state(state_dir)? {
         Some(existing) => {
             if existing.plan_id != ws.plan.plan_id {
+                if ws.plan.matches_id(&existing.plan_id) {
+                    // Written before plan ids were computed by `compute_id`
+                    reporter.info("resuming state recorded under the legacy plan_id");
+                    return Ok(existing);
+                }
                 if !opts.force_resume {
                     bail!(
                         "existing state plan_id {} does not match current plan_id {}; delete state or use --force-resume",
@@ -179,7 +185,7 @@ fn record_execution_start(
         },
         package: "all".to_string(),
     });
-    event_log.write_to_file(events_path)?;
+    event_log.write_to_file_redacted(events_path, redact_sensitive)?;
     event_log.clear();
     Ok(())
 }
@@ -202,6 +208,6 @@ fn ensure_plan_package_entries(
                 last_updated_at: Utc::now(),
             });
     }
-    state.updated_at = Utc::now();
+    state.touch();
     state::save_state(state_dir, state)
 }
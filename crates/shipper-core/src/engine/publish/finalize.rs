@@ -4,6 +4,7 @@ use anyhow::Result;
 use chrono::{DateTime, Utc};
 
 use crate::engine::Reporter;
+use crate::ops::cargo::redact_sensitive;
 use crate::plan::PlannedWorkspace;
 use crate::state::events;
 use crate::state::execution_state as state;
@@ -55,7 +56,7 @@ pub(in crate::engine) fn finish_sequential_run(
         },
         package: "all".to_string(),
     });
-    event_log.write_to_file(events_path)?;
+    event_log.write_to_file_redacted(events_path, redact_sensitive)?;
 
     send_completion_webhook(ws, opts, &receipts, &exec_result);
 
@@ -95,7 +96,7 @@ pub(in crate::engine) fn finish_parallel_run(
         },
         package: "all".to_string(),
     });
-    event_log.write_to_file(events_path)?;
+    event_log.write_to_file_redacted(events_path, redact_sensitive)?;
 
     send_completion_webhook(ws, opts, &receipts, &exec_result);
 
@@ -248,6 +249,7 @@ mod tests {
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256: None,
         }
     }
 
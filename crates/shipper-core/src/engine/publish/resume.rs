@@ -2,6 +2,7 @@ use anyhow::Result;
 use chrono::Utc;
 
 use crate::engine::Reporter;
+use crate::ops::cargo::redact_sensitive;
 use crate::runtime::execution::short_state;
 use crate::state::events;
 use crate::types::{
@@ -87,7 +88,7 @@ pub(in crate::engine) fn record_terminal_resume_skip_event(
         },
         package: pkg_label.to_string(),
     });
-    event_log.write_to_file(events_path)?;
+    event_log.write_to_file_redacted(events_path, redact_sensitive)?;
     event_log.clear();
     Ok(())
 }
@@ -103,7 +104,8 @@ mod tests {
 
     use crate::encryption::EncryptionConfig;
     use crate::types::{
-        ParallelConfig, PublishPolicy, ReadinessConfig, ReadinessMethod, Registry, VerifyMode,
+        ParallelConfig, PublishPolicy, ReadinessConfig, ReadinessMethod, Registry, SkipReason,
+        VerifyMode,
     };
     use crate::webhook::WebhookConfig;
 
@@ -152,6 +154,10 @@ mod tests {
             skip_ownership_check: true,
             strict_ownership: false,
             no_verify: false,
+            features: Vec::new(),
+            no_default_features: false,
+            all_features: false,
+            cargo: Default::default(),
             max_attempts: 1,
             base_delay: Duration::from_millis(1),
             max_delay: Duration::from_millis(2),
@@ -322,7 +328,7 @@ mod tests {
         let decision = apply_resume_from_gate(
             &pkg("a"),
             &progress(PackageState::Skipped {
-                reason: "already published".into(),
+                reason: SkipReason::AlreadyPublished,
             }),
             &opts,
             &mut reached,
@@ -383,7 +389,7 @@ mod tests {
         record_terminal_resume_skip(
             &pkg("a"),
             &progress(PackageState::Skipped {
-                reason: "irrelevant".into(),
+                reason: SkipReason::Manual("irrelevant".to_string()),
             }),
             "a@1.2.3",
             &events_path,
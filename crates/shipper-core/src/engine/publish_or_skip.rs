@@ -0,0 +1,374 @@
+//! Idempotent single-package publish: skip if live, otherwise publish and
+//! wait for readiness.
+
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::cargo::{self, CargoOutput};
+use crate::registry::RegistryClient;
+use crate::runtime::execution::classify_cargo_failure;
+use crate::types::{ErrorClass, PackageState, ReadinessEvidence, RuntimeOptions, SkipReason};
+
+/// Result of [`publish_or_skip`].
+#[derive(Debug, Clone)]
+pub enum PackageOutcome {
+    /// The version was already on the registry; nothing was run.
+    Skipped { reason: SkipReason },
+    /// `cargo publish` succeeded and the version is visible (or readiness
+    /// checks are disabled).
+    Published {
+        output: CargoOutput,
+        readiness: Vec<ReadinessEvidence>,
+    },
+    /// `cargo publish` succeeded but the version did not become visible
+    /// within the readiness budget.
+    Uploaded {
+        output: CargoOutput,
+        readiness: Vec<ReadinessEvidence>,
+    },
+    /// `cargo publish` failed; `class` decides whether to retry.
+    Failed {
+        class: ErrorClass,
+        message: String,
+        output: CargoOutput,
+    },
+}
+
+impl PackageOutcome {
+    /// The [`PackageState`] to record for this outcome. An
+    /// [`ErrorClass::Ambiguous`] failure maps to [`PackageState::Ambiguous`]
+    /// so it gets reconciled against the registry instead of retried blind.
+    pub fn package_state(&self) -> PackageState {
+        match self {
+            Self::Skipped { reason } => PackageState::Skipped {
+                reason: reason.clone(),
+            },
+            Self::Published { .. } => PackageState::Published,
+            Self::Uploaded { .. } => PackageState::Uploaded,
+            Self::Failed {
+                class: ErrorClass::Ambiguous,
+                message,
+                ..
+            } => PackageState::Ambiguous {
+                message: message.clone(),
+            },
+            Self::Failed { class, message, .. } => PackageState::Failed {
+                class: class.clone(),
+                message: message.clone(),
+            },
+        }
+    }
+
+    /// Whether another `cargo publish` attempt could succeed.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::Failed {
+                class: ErrorClass::Retryable,
+                ..
+            }
+        )
+    }
+}
+
+/// Publish `name@version` once, unless the registry already has it.
+///
+/// Checks [`RegistryClient::version_exists`] first: a live version is
+/// skipped as [`SkipReason::AlreadyPublished`], a yanked one as
+/// [`SkipReason::YankedExists`]. Otherwise runs `cargo publish` with the
+/// flags from `opts` and, on success, waits for the version to become
+/// visible per `opts.readiness`. A failed publish is classified with the
+/// same tables the engine uses. Makes a single attempt; retrying
+/// [`PackageOutcome::is_retryable`] outcomes is up to the caller.
+///
+/// Errors only when the registry cannot be queried or cargo cannot be
+/// started.
+pub fn publish_or_skip(
+    client: &RegistryClient,
+    workspace_root: &Path,
+    name: &str,
+    version: &str,
+    opts: &RuntimeOptions,
+) -> Result<PackageOutcome> {
+    if client.version_exists(name, version)? {
+        let reason = if client.is_version_yanked(name, version)? {
+            SkipReason::YankedExists
+        } else {
+            SkipReason::AlreadyPublished
+        };
+        return Ok(PackageOutcome::Skipped { reason });
+    }
+
+    let output = cargo::cargo_publish(&cargo::PublishArgs::new(
+        workspace_root,
+        opts,
+        name,
+        &client.registry().name,
+    ))?;
+
+    if output.exit_code != 0 || output.timed_out {
+        let (class, message) = classify_cargo_failure(&output.stderr_tail, &output.stdout_tail);
+        return Ok(PackageOutcome::Failed {
+            class,
+            message,
+            output,
+        });
+    }
+
+    if !opts.readiness.enabled {
+        return Ok(PackageOutcome::Published {
+            output,
+            readiness: Vec::new(),
+        });
+    }
+
+    let (visible, readiness) =
+        client.is_version_visible_with_backoff(name, version, &opts.readiness)?;
+    Ok(if visible {
+        PackageOutcome::Published { output, readiness }
+    } else {
+        PackageOutcome::Uploaded { output, readiness }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::thread;
+    use std::time::Duration;
+
+    use serial_test::serial;
+    use tempfile::tempdir;
+    use tiny_http::{Response, Server, StatusCode};
+
+    use super::*;
+    use crate::types::{ReadinessConfig, ReadinessMethod, Registry};
+
+    /// Fake cargo that prints `$SHIPPER_CARGO_STDERR` and exits
+    /// `$SHIPPER_CARGO_EXIT`.
+    fn write_fake_cargo(bin_dir: &Path) -> std::path::PathBuf {
+        #[cfg(windows)]
+        {
+            let path = bin_dir.join("cargo.cmd");
+            fs::write(
+                &path,
+                "@echo off\r\nif not \"%SHIPPER_CARGO_STDERR%\"==\"\" echo %SHIPPER_CARGO_STDERR% 1>&2\r\nif \"%SHIPPER_CARGO_EXIT%\"==\"\" (exit /b 0) else (exit /b %SHIPPER_CARGO_EXIT%)\r\n",
+            )
+            .expect("write fake cargo");
+            path
+        }
+
+        #[cfg(not(windows))]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let path = bin_dir.join("cargo");
+            fs::write(
+                &path,
+                "#!/usr/bin/env sh\nif [ -n \"$SHIPPER_CARGO_STDERR\" ]; then\n  echo \"$SHIPPER_CARGO_STDERR\" >&2\nfi\nexit \"${SHIPPER_CARGO_EXIT:-0}\"\n",
+            )
+            .expect("write fake cargo");
+            let mut perms = fs::metadata(&path).expect("meta").permissions();
+            perms.set_mode(0o755);
+            fs::set_permissions(&path, perms).expect("chmod");
+            path
+        }
+    }
+
+    /// Serve `responses` in order, one per request, and return the paths
+    /// that were requested.
+    fn spawn_registry(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (String, thread::JoinHandle<Vec<String>>) {
+        let server = Server::http("127.0.0.1:0").expect("server");
+        let base_url = format!("http://{}", server.server_addr());
+        let handle = thread::spawn(move || {
+            let mut seen = Vec::new();
+            for (status, body) in responses {
+                let req = match server.recv_timeout(Duration::from_secs(30)) {
+                    Ok(Some(req)) => req,
+                    _ => break,
+                };
+                seen.push(req.url().to_string());
+                req.respond(Response::from_string(body).with_status_code(StatusCode(status)))
+                    .expect("respond");
+            }
+            seen
+        });
+        (base_url, handle)
+    }
+
+    fn client(api_base: String) -> RegistryClient {
+        RegistryClient::new(Registry {
+            name: "crates-io".to_string(),
+            api_base,
+            index_base: None,
+        })
+        .expect("client")
+    }
+
+    fn opts(readiness_enabled: bool) -> RuntimeOptions {
+        RuntimeOptions {
+            allow_dirty: true,
+            skip_ownership_check: true,
+            strict_ownership: false,
+            no_verify: false,
+            features: Vec::new(),
+            no_default_features: false,
+            all_features: false,
+            cargo: Default::default(),
+            max_attempts: 1,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(2),
+            verify_timeout: Duration::from_millis(20),
+            verify_poll_interval: Duration::from_millis(1),
+            state_dir: ".shipper".into(),
+            force_resume: false,
+            policy: crate::types::PublishPolicy::default(),
+            verify_mode: crate::types::VerifyMode::default(),
+            readiness: ReadinessConfig {
+                enabled: readiness_enabled,
+                method: ReadinessMethod::Api,
+                initial_delay: Duration::ZERO,
+                max_delay: Duration::from_millis(10),
+                max_total_wait: Duration::from_millis(100),
+                poll_interval: Duration::from_millis(1),
+                jitter_factor: 0.0,
+                index_path: None,
+                prefer_index: false,
+            },
+            output_lines: 50,
+            force: false,
+            lock_timeout: Duration::from_secs(10),
+            parallel: crate::types::ParallelConfig::default(),
+            webhook: crate::webhook::WebhookConfig::default(),
+            retry_strategy: crate::retry::RetryStrategyType::Exponential,
+            retry_jitter: 0.0,
+            retry_per_error: crate::retry::PerErrorConfig::default(),
+            encryption: crate::encryption::EncryptionConfig::default(),
+            registries: vec![],
+            resume_from: None,
+            rehearsal_registry: None,
+            rehearsal_skip: false,
+            rehearsal_smoke_install: None,
+        }
+    }
+
+    fn run_with_cargo(
+        api_base: String,
+        exit_code: &str,
+        stderr: Option<&str>,
+        opts: &RuntimeOptions,
+    ) -> PackageOutcome {
+        let td = tempdir().expect("tempdir");
+        let fake_cargo = write_fake_cargo(td.path());
+        temp_env::with_vars(
+            [
+                (
+                    "SHIPPER_CARGO_BIN",
+                    Some(fake_cargo.to_str().expect("utf8")),
+                ),
+                ("SHIPPER_CARGO_EXIT", Some(exit_code)),
+                ("SHIPPER_CARGO_STDERR", stderr),
+            ],
+            || publish_or_skip(&client(api_base), td.path(), "demo", "0.1.0", opts),
+        )
+        .expect("publish_or_skip")
+    }
+
+    #[test]
+    #[serial]
+    fn already_published_version_is_skipped_without_running_cargo() {
+        let (api_base, server) = spawn_registry(vec![
+            (200, "{}"),
+            (
+                200,
+                r#"{"versions":[{"num":"0.1.0","yanked":false,"created_at":"2024-01-01T00:00:00Z"}]}"#,
+            ),
+        ]);
+
+        // Exit code 1 would surface as Failed if cargo were run.
+        let outcome = run_with_cargo(api_base, "1", None, &opts(true));
+
+        assert!(matches!(
+            outcome,
+            PackageOutcome::Skipped {
+                reason: SkipReason::AlreadyPublished
+            }
+        ));
+        assert_eq!(
+            outcome.package_state(),
+            PackageState::Skipped {
+                reason: SkipReason::AlreadyPublished
+            }
+        );
+        assert_eq!(
+            server.join().expect("join"),
+            ["/api/v1/crates/demo/0.1.0", "/api/v1/crates/demo/versions"]
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn yanked_version_is_skipped_as_yanked() {
+        let (api_base, server) = spawn_registry(vec![
+            (200, "{}"),
+            (
+                200,
+                r#"{"versions":[{"num":"0.1.0","yanked":true,"created_at":"2024-01-01T00:00:00Z"}]}"#,
+            ),
+        ]);
+
+        let outcome = run_with_cargo(api_base, "0", None, &opts(true));
+
+        assert!(matches!(
+            outcome,
+            PackageOutcome::Skipped {
+                reason: SkipReason::YankedExists
+            }
+        ));
+        server.join().expect("join");
+    }
+
+    #[test]
+    #[serial]
+    fn fresh_version_is_published_and_waits_for_readiness() {
+        let (api_base, server) = spawn_registry(vec![(404, "{}"), (200, "{}")]);
+
+        let outcome = run_with_cargo(api_base, "0", None, &opts(true));
+
+        let PackageOutcome::Published { readiness, .. } = &outcome else {
+            panic!("expected Published, got {outcome:?}");
+        };
+        assert!(readiness.last().expect("readiness evidence").visible);
+        assert_eq!(outcome.package_state(), PackageState::Published);
+        assert_eq!(
+            server.join().expect("join"),
+            ["/api/v1/crates/demo/0.1.0", "/api/v1/crates/demo/0.1.0"]
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn retryable_cargo_failure_is_classified() {
+        let (api_base, server) = spawn_registry(vec![(404, "{}")]);
+
+        let outcome = run_with_cargo(
+            api_base,
+            "101",
+            Some("error: failed to publish: HTTP 429 too many requests"),
+            &opts(false),
+        );
+
+        assert!(outcome.is_retryable(), "{outcome:?}");
+        assert!(matches!(
+            outcome.package_state(),
+            PackageState::Failed {
+                class: ErrorClass::Retryable,
+                ..
+            }
+        ));
+        server.join().expect("join");
+    }
+}
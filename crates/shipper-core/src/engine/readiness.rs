@@ -5,6 +5,7 @@ use anyhow::Result;
 use chrono::Utc;
 
 use super::Reporter;
+use crate::ops::cargo::redact_sensitive;
 use crate::registry::RegistryClient;
 use crate::state::events;
 use crate::types::{EventType, PublishEvent, ReadinessEvidence};
@@ -89,7 +90,7 @@ fn record_readiness_event(
     event: PublishEvent,
 ) -> Result<()> {
     event_log.record(event);
-    event_log.write_to_file(events_path)?;
+    event_log.write_to_file_redacted(events_path, redact_sensitive)?;
     event_log.clear();
     Ok(())
 }
@@ -64,7 +64,7 @@ pub(crate) fn enforce_gate(
         ),
     };
 
-    if receipt.plan_id != ws.plan.plan_id {
+    if !ws.plan.matches_id(&receipt.plan_id) {
         bail!(
             "rehearsal receipt is stale: rehearsal ran for plan_id {} but the current plan_id is {}. \
              The workspace changed between rehearse and publish; re-run `shipper rehearse` against the current plan.",
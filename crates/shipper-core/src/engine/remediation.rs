@@ -317,6 +317,7 @@ mod tests {
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256: None,
         }
     }
 
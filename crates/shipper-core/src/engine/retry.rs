@@ -5,6 +5,7 @@ use anyhow::Result;
 use chrono::Utc;
 
 use super::Reporter;
+use crate::ops::cargo::redact_sensitive;
 use crate::runtime::execution::retry_next_attempt_at;
 use crate::state::events;
 use crate::types::{ErrorClass, EventType, PublishEvent};
@@ -92,7 +93,7 @@ pub(crate) fn record_retry_backoff_event(
         },
         package: pkg_label.to_string(),
     });
-    event_log.write_to_file(events_path)?;
+    event_log.write_to_file_redacted(events_path, redact_sensitive)?;
     event_log.clear();
     Ok(())
 }
@@ -114,7 +115,7 @@ pub(crate) fn record_rate_limit_observed_event(
         },
         package: pkg_label.to_string(),
     });
-    event_log.write_to_file(events_path)?;
+    event_log.write_to_file_redacted(events_path, redact_sensitive)?;
     event_log.clear();
     Ok(())
 }
@@ -136,7 +137,7 @@ fn record_publish_wait_event(
         },
         package: pkg_label.to_string(),
     });
-    event_log.write_to_file(events_path)?;
+    event_log.write_to_file_redacted(events_path, redact_sensitive)?;
     event_log.clear();
     Ok(())
 }
@@ -97,8 +97,9 @@ pub fn resolve_token(registry_name: &str) -> Result<Option<String>> {
 /// Detect the best-known authentication mode for publish/preflight diagnostics.
 ///
 /// Resolution order:
-/// 1) Explicit Cargo token configuration ([`AuthType::Token`])
-/// 2) Trusted publishing OIDC environment ([`AuthType::TrustedPublishing`])
+/// 1) Trusted publishing OIDC environment ([`AuthType::TrustedPublishing`]),
+///    even if a token is also configured: the job can mint its own
+/// 2) Explicit Cargo token configuration ([`AuthType::Token`])
 /// 3) Partial trusted-publishing environment ([`AuthType::Unknown`])
 /// 4) No known auth configured (`None`)
 pub fn detect_auth_type(registry_name: &str) -> Result<Option<AuthType>> {
@@ -106,18 +107,21 @@ pub fn detect_auth_type(registry_name: &str) -> Result<Option<AuthType>> {
     Ok(detect_auth_type_from_token(token.as_deref()))
 }
 
-pub(crate) fn detect_auth_type_from_token(token: Option<&str>) -> Option<AuthType> {
+fn detect_auth_type_from_token(token: Option<&str>) -> Option<AuthType> {
+    let has_oidc_url = env::var_os("ACTIONS_ID_TOKEN_REQUEST_URL").is_some();
+    let has_oidc_token = env::var_os("ACTIONS_ID_TOKEN_REQUEST_TOKEN").is_some();
+    if has_oidc_url && has_oidc_token {
+        return Some(AuthType::TrustedPublishing);
+    }
+
     if token.map(str::trim).map(|s| !s.is_empty()).unwrap_or(false) {
         return Some(AuthType::Token);
     }
 
-    let has_oidc_url = env::var_os("ACTIONS_ID_TOKEN_REQUEST_URL").is_some();
-    let has_oidc_token = env::var_os("ACTIONS_ID_TOKEN_REQUEST_TOKEN").is_some();
-
-    match (has_oidc_url, has_oidc_token) {
-        (true, true) => Some(AuthType::TrustedPublishing),
-        (true, false) | (false, true) => Some(AuthType::Unknown),
-        (false, false) => None,
+    if has_oidc_url || has_oidc_token {
+        Some(AuthType::Unknown)
+    } else {
+        None
     }
 }
 
@@ -308,7 +312,7 @@ token = "token-dot"
 
     #[test]
     #[serial]
-    fn detect_auth_type_prefers_token_when_present() {
+    fn detect_auth_type_prefers_oidc_over_token() {
         let td = tempdir().expect("tempdir");
         temp_env::with_vars(
             [
@@ -320,6 +324,27 @@ token = "token-dot"
                 ),
                 ("ACTIONS_ID_TOKEN_REQUEST_TOKEN", Some("oidc-token")),
             ],
+            || {
+                let auth = detect_auth_type("crates-io").expect("detect");
+                assert_eq!(auth, Some(AuthType::TrustedPublishing));
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn detect_auth_type_uses_token_without_full_oidc_env() {
+        let td = tempdir().expect("tempdir");
+        temp_env::with_vars(
+            [
+                ("CARGO_HOME", Some(td.path().to_str().expect("utf8"))),
+                ("CARGO_REGISTRY_TOKEN", Some("env-token")),
+                (
+                    "ACTIONS_ID_TOKEN_REQUEST_URL",
+                    Some("https://example.invalid/oidc"),
+                ),
+                ("ACTIONS_ID_TOKEN_REQUEST_TOKEN", None::<&str>),
+            ],
             || {
                 let auth = detect_auth_type("crates-io").expect("detect");
                 assert_eq!(auth, Some(AuthType::Token));
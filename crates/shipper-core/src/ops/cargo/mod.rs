@@ -3,18 +3,23 @@
 //! Absorbed from the former `shipper-cargo` microcrate. See
 //! `docs/decrating-plan.md` §6 for the overall plan.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::env;
 use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
-use cargo_metadata::{Metadata, MetadataCommand, Package};
+use cargo_metadata::{DependencyKind, Metadata, MetadataCommand, Package};
+use globset::{Glob, GlobSet, GlobSetBuilder};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 pub use shipper_output_sanitizer::redact_sensitive;
 use shipper_output_sanitizer::tail_lines as sanitize_tail_lines;
+pub use shipper_types::CargoInvocation;
+use shipper_types::{ErrorClass, RuntimeOptions};
 
 use crate::ops::process;
+pub use crate::ops::process::StreamKind;
 
 #[derive(Debug, Clone)]
 pub struct CargoOutput {
@@ -25,10 +30,39 @@ pub struct CargoOutput {
     pub timed_out: bool,
 }
 
+impl CargoOutput {
+    /// Retry semantics for this run; see [`classify_cargo_output`].
+    ///
+    /// A run killed by its timeout (e.g. `ParallelConfig::per_package_timeout`)
+    /// is [`ErrorClass::Retryable`] whatever its output says, so the engine
+    /// tries again instead of recording a permanent failure.
+    pub fn error_class(&self) -> ErrorClass {
+        classify_cargo_output(self)
+    }
+}
+
 fn tail_lines(s: &str, n: usize) -> String {
     sanitize_tail_lines(s, n)
 }
 
+fn as_strs(args: &[String]) -> Vec<&str> {
+    args.iter().map(String::as_str).collect()
+}
+
+/// Token cargo would use to publish to `registry_name`, if any.
+///
+/// Follows cargo's lookup (see [`crate::ops::auth`] for the full order):
+/// `CARGO_REGISTRY_TOKEN` for crates-io, `CARGO_REGISTRIES_<NAME>_TOKEN`
+/// with the name upper-cased and `-` mapped to `_`, then
+/// `$CARGO_HOME/credentials.toml`. An unreadable credentials file counts as
+/// no token. Pass the result to owner and readiness calls; never log it
+/// (output from cargo goes through [`redact_sensitive`]).
+pub fn resolve_registry_token(registry_name: &str) -> Option<String> {
+    crate::ops::auth::resolve_token(registry_name)
+        .ok()
+        .flatten()
+}
+
 /// Invoke `cargo yank` against the configured registry.
 ///
 /// Yanks a specific `<crate>@<version>` so the registry refuses to resolve
@@ -126,56 +160,196 @@ pub fn cargo_install_smoke(
     })
 }
 
-pub fn cargo_publish(
-    workspace_root: &Path,
-    package_name: &str,
-    registry_name: &str,
-    allow_dirty: bool,
-    no_verify: bool,
-    output_lines: usize,
-    timeout: Option<Duration>,
-) -> Result<CargoOutput> {
-    let start = Instant::now();
-    let mut args: Vec<&str> = Vec::new();
-    args.push("publish");
-    args.push("-p");
-    args.push(package_name);
+/// Everything one `cargo publish -p <package>` run needs.
+///
+/// Build it with [`PublishArgs::new`] to take the cargo settings (toolchain,
+/// extra args, features, `allow_dirty`, `no_verify`, `output_lines`) from
+/// [`RuntimeOptions`], then adjust fields such as `timeout` directly.
+#[derive(Debug, Clone, Copy)]
+pub struct PublishArgs<'a> {
+    pub workspace_root: &'a Path,
+    pub invocation: &'a CargoInvocation,
+    pub package_name: &'a str,
+    pub registry_name: &'a str,
+    pub allow_dirty: bool,
+    /// Ignored by [`cargo_publish_dry_run_package`].
+    pub no_verify: bool,
+    /// `<package>/<feature>` entries; see [`RuntimeOptions::features`].
+    pub features: &'a [String],
+    pub no_default_features: bool,
+    pub all_features: bool,
+    pub output_lines: usize,
+    /// Wall-clock limit; the child is killed when it expires.
+    pub timeout: Option<Duration>,
+}
 
-    // If the user configured a non-default registry, pass it through.
-    if !registry_name.trim().is_empty() && registry_name != "crates-io" {
-        args.push("--registry");
-        args.push(registry_name);
+impl<'a> PublishArgs<'a> {
+    /// Publish `package_name` to `registry_name` with the cargo settings from
+    /// `opts` and no timeout.
+    pub fn new(
+        workspace_root: &'a Path,
+        opts: &'a RuntimeOptions,
+        package_name: &'a str,
+        registry_name: &'a str,
+    ) -> Self {
+        Self {
+            workspace_root,
+            invocation: &opts.cargo,
+            package_name,
+            registry_name,
+            allow_dirty: opts.allow_dirty,
+            no_verify: opts.no_verify,
+            features: &opts.features,
+            no_default_features: opts.no_default_features,
+            all_features: opts.all_features,
+            output_lines: opts.output_lines,
+            timeout: None,
+        }
     }
 
-    if allow_dirty {
-        args.push("--allow-dirty");
+    /// The cargo argument list (without the invocation wrapper) for a
+    /// publish, or a `--dry-run` publish when `dry_run` is set.
+    fn cargo_args(&self, features_arg: &'a str, dry_run: bool) -> Result<Vec<&'a str>> {
+        let mut args: Vec<&str> = vec!["publish", "-p", self.package_name];
+        if dry_run {
+            args.push("--dry-run");
+        }
+
+        // If the user configured a non-default registry, pass it through.
+        if !self.registry_name.trim().is_empty() && self.registry_name != "crates-io" {
+            args.push("--registry");
+            args.push(self.registry_name);
+        }
+
+        if self.allow_dirty {
+            args.push("--allow-dirty");
+        }
+        if self.no_verify && !dry_run {
+            args.push("--no-verify");
+        }
+        push_feature_args(
+            &mut args,
+            features_arg,
+            self.no_default_features,
+            self.all_features,
+        )?;
+        Ok(args)
     }
-    if no_verify {
-        args.push("--no-verify");
+}
+
+/// The comma-separated `--features` value for `package_name`.
+///
+/// The feature list is shared by every crate in the plan, so each entry must
+/// be scoped as `<package>/<feature>`; only `package_name`'s entries are
+/// passed, with the prefix stripped. Blank entries are dropped and a bare
+/// feature name is an error rather than being forced onto every crate.
+fn package_features(features: &[String], package_name: &str) -> Result<String> {
+    let mut selected = Vec::new();
+    for feature in features.iter().map(|f| f.trim()).filter(|f| !f.is_empty()) {
+        let Some((package, name)) = feature.split_once('/') else {
+            anyhow::bail!(
+                "feature `{feature}` must name its package as `<package>/{feature}`; \
+                 the feature list applies to every crate in the plan"
+            );
+        };
+        if package == package_name {
+            selected.push(name);
+        }
     }
+    Ok(selected.join(","))
+}
 
-    let output =
-        process::run_command_with_timeout(&cargo_program(), &args, workspace_root, timeout)
-            .context("failed to execute cargo publish; is Cargo installed?")?;
+/// Append `--all-features`, `--no-default-features` and `--features <list>`.
+///
+/// `--all-features` already enables every feature, so combining it with an
+/// explicit list is rejected rather than leaving it unclear which feature set
+/// was verified. `--no-default-features` is passed through alongside it, as
+/// cargo accepts both.
+fn push_feature_args<'a>(
+    args: &mut Vec<&'a str>,
+    features_arg: &'a str,
+    no_default_features: bool,
+    all_features: bool,
+) -> Result<()> {
+    if all_features && !features_arg.is_empty() {
+        anyhow::bail!("--all-features cannot be combined with --features ({features_arg})");
+    }
+
+    if all_features {
+        args.push("--all-features");
+    }
+    if no_default_features {
+        args.push("--no-default-features");
+    }
+    if !features_arg.is_empty() {
+        args.push("--features");
+        args.push(features_arg);
+    }
+    Ok(())
+}
 
-    let exit_code = output.exit_code;
-    let stdout = output.stdout;
-    let stderr = output.stderr;
-    let timed_out = output.timed_out;
+/// Run `cargo publish -p <package>` as described by `args`.
+pub fn cargo_publish(args: &PublishArgs<'_>) -> Result<CargoOutput> {
+    let start = Instant::now();
+    let features_arg = package_features(args.features, args.package_name)?;
+    let argv = args
+        .invocation
+        .argv(&args.cargo_args(&features_arg, false)?);
+
+    let output = process::run_command_with_timeout(
+        &cargo_program(),
+        &as_strs(&argv),
+        args.workspace_root,
+        args.timeout,
+    )
+    .context("failed to execute cargo publish; is Cargo installed?")?;
 
-    let duration = start.elapsed();
+    Ok(CargoOutput {
+        exit_code: output.exit_code,
+        stdout_tail: tail_lines(&output.stdout, args.output_lines),
+        stderr_tail: tail_lines(&output.stderr, args.output_lines),
+        duration: start.elapsed(),
+        timed_out: output.timed_out,
+    })
+}
+
+/// Like [`cargo_publish`], but reports output live.
+///
+/// `on_line` is called for each stdout/stderr line as cargo writes it, with
+/// secrets already redacted, so long publishes (including the verify build)
+/// can show progress. The returned [`CargoOutput`] still carries the usual
+/// redacted tails for receipts.
+pub fn cargo_publish_streaming(
+    args: &PublishArgs<'_>,
+    mut on_line: impl FnMut(StreamKind, &str),
+) -> Result<CargoOutput> {
+    let start = Instant::now();
+    let features_arg = package_features(args.features, args.package_name)?;
+    let argv = args
+        .invocation
+        .argv(&args.cargo_args(&features_arg, false)?);
+
+    let output = process::run_command_with_lines(
+        &cargo_program(),
+        &as_strs(&argv),
+        args.workspace_root,
+        args.timeout,
+        &mut |kind, line| on_line(kind, &redact_sensitive(line)),
+    )
+    .context("failed to execute cargo publish; is Cargo installed?")?;
 
     Ok(CargoOutput {
-        exit_code,
-        stdout_tail: tail_lines(&stdout, output_lines),
-        stderr_tail: tail_lines(&stderr, output_lines),
-        duration,
-        timed_out,
+        exit_code: output.exit_code,
+        stdout_tail: tail_lines(&output.stdout, args.output_lines),
+        stderr_tail: tail_lines(&output.stderr, args.output_lines),
+        duration: start.elapsed(),
+        timed_out: output.timed_out,
     })
 }
 
 pub fn cargo_publish_dry_run_workspace(
     workspace_root: &Path,
+    invocation: &CargoInvocation,
     registry_name: &str,
     allow_dirty: bool,
     output_lines: usize,
@@ -192,11 +366,13 @@ pub fn cargo_publish_dry_run_workspace(
     if allow_dirty {
         args.push("--allow-dirty");
     }
+    let argv = invocation.argv(&args);
 
-    let output = process::run_command_with_timeout(&cargo_program(), &args, workspace_root, None)
-        .context(
-        "failed to execute cargo publish --dry-run --workspace; is Cargo installed?",
-    )?;
+    let output =
+        process::run_command_with_timeout(&cargo_program(), &as_strs(&argv), workspace_root, None)
+            .context(
+                "failed to execute cargo publish --dry-run --workspace; is Cargo installed?",
+            )?;
 
     let duration = start.elapsed();
     let exit_code = output.exit_code;
@@ -213,45 +389,369 @@ pub fn cargo_publish_dry_run_workspace(
     })
 }
 
-pub fn cargo_publish_dry_run_package(
+/// Outcome of [`verify_workspace`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyResult {
+    /// The dry-run exited 0 without timing out.
+    pub passed: bool,
+    /// Retry semantics of the failure; `None` when `passed`.
+    pub class: Option<ErrorClass>,
+    /// Tail of cargo's stderr, where the dry-run reports its progress and
+    /// errors.
+    pub output_tail: String,
+}
+
+/// Run `cargo publish --workspace --dry-run` and classify the result.
+///
+/// Failures go through [`classify_cargo_output`], so a network hiccup while
+/// fetching the index comes back [`ErrorClass::Retryable`] and a compile
+/// error [`ErrorClass::Permanent`]. Errors only if cargo cannot be spawned.
+pub fn verify_workspace(
     workspace_root: &Path,
-    package_name: &str,
+    invocation: &CargoInvocation,
     registry_name: &str,
     allow_dirty: bool,
     output_lines: usize,
-) -> Result<CargoOutput> {
+) -> Result<VerifyResult> {
+    let out = cargo_publish_dry_run_workspace(
+        workspace_root,
+        invocation,
+        registry_name,
+        allow_dirty,
+        output_lines,
+    )?;
+    let passed = out.exit_code == 0 && !out.timed_out;
+    Ok(VerifyResult {
+        passed,
+        class: (!passed).then(|| classify_cargo_output(&out)),
+        output_tail: out.stderr_tail,
+    })
+}
+
+/// Run `cargo publish -p <package> --dry-run` as described by `args`
+/// (`no_verify` does not apply).
+pub fn cargo_publish_dry_run_package(args: &PublishArgs<'_>) -> Result<CargoOutput> {
     let start = Instant::now();
-    let mut args: Vec<&str> = vec!["publish", "-p", package_name, "--dry-run"];
+    let features_arg = package_features(args.features, args.package_name)?;
+    let argv = args.invocation.argv(&args.cargo_args(&features_arg, true)?);
+
+    let output = process::run_command_with_timeout(
+        &cargo_program(),
+        &as_strs(&argv),
+        args.workspace_root,
+        args.timeout,
+    )
+    .with_context(|| {
+        format!(
+            "failed to execute cargo publish --dry-run -p {}; is Cargo installed?",
+            args.package_name
+        )
+    })?;
 
-    if !registry_name.trim().is_empty() && registry_name != "crates-io" {
-        args.push("--registry");
-        args.push(registry_name);
+    Ok(CargoOutput {
+        exit_code: output.exit_code,
+        stdout_tail: tail_lines(&output.stdout, args.output_lines),
+        stderr_tail: tail_lines(&output.stderr, args.output_lines),
+        duration: start.elapsed(),
+        timed_out: output.timed_out,
+    })
+}
+
+/// Stable fragments of cargo's "this version is already on the registry"
+/// error. Matched case-insensitively against both tails.
+///
+/// | Source                    | Message                                              |
+/// |---------------------------|------------------------------------------------------|
+/// | crates.io API, older cargo | ``crate version `0.1.0` is already uploaded``       |
+/// | cargo index check (newer) | `crate demo@0.1.0 already exists on crates.io index` |
+const ALREADY_PUBLISHED_PATTERNS: [&str; 2] = ["is already uploaded", "already exists on"];
+
+/// Whether cargo's output says the version is already on the registry.
+///
+/// Used on `cargo publish --dry-run` output so preflight can mark the
+/// package as already published (and skip it) instead of failing on a
+/// re-run.
+pub fn crate_already_published(out: &CargoOutput) -> bool {
+    parse_cargo_publish_result(out) == CargoPublishResult::AlreadyPublished
+}
+
+/// Preflight signal for `<name>@<version>` already being published.
+///
+/// True if the registry API reports the version, or if the package's
+/// dry-run output says so (the API and the index can disagree briefly
+/// after a publish). A registry error is only returned when the dry-run
+/// output doesn't already settle the question.
+pub fn is_already_published(
+    reg: &shipper_registry::RegistryClient,
+    name: &str,
+    version: &str,
+    dry_run: Option<&CargoOutput>,
+) -> Result<bool> {
+    let from_dry_run = dry_run.is_some_and(crate_already_published);
+    match reg.version_exists(name, version) {
+        Ok(exists) => Ok(exists || from_dry_run),
+        Err(_) if from_dry_run => Ok(true),
+        Err(err) => Err(err.into()),
     }
+}
+
+/// Invoke `cargo package -p <pkg>` to build the `.crate` tarball without
+/// publishing it.
+///
+/// The tarball lands at [`packaged_crate_path`] under the workspace's
+/// [`target_directory`]; callers use it for size checks and provenance
+/// hashing before the real publish.
+pub fn cargo_package(
+    workspace_root: &Path,
+    package_name: &str,
+    allow_dirty: bool,
+    output_lines: usize,
+    timeout: Option<Duration>,
+) -> Result<CargoOutput> {
+    let start = Instant::now();
+    let mut args: Vec<&str> = vec!["package", "-p", package_name];
 
     if allow_dirty {
         args.push("--allow-dirty");
     }
 
-    let output = process::run_command_with_timeout(&cargo_program(), &args, workspace_root, None)
-        .with_context(|| {
-        format!("failed to execute cargo publish --dry-run -p {package_name}; is Cargo installed?")
-    })?;
-
-    let duration = start.elapsed();
-    let exit_code = output.exit_code;
-    let stdout = output.stdout;
-    let stderr = output.stderr;
-    let timed_out = output.timed_out;
+    let output =
+        process::run_command_with_timeout(&cargo_program(), &args, workspace_root, timeout)
+            .with_context(|| {
+                format!("failed to execute cargo package -p {package_name}; is Cargo installed?")
+            })?;
 
     Ok(CargoOutput {
-        exit_code,
-        stdout_tail: tail_lines(&stdout, output_lines),
-        stderr_tail: tail_lines(&stderr, output_lines),
-        duration,
-        timed_out,
+        exit_code: output.exit_code,
+        stdout_tail: tail_lines(&output.stdout, output_lines),
+        stderr_tail: tail_lines(&output.stderr, output_lines),
+        duration: start.elapsed(),
+        timed_out: output.timed_out,
+    })
+}
+
+/// Where `cargo package` writes the tarball for `<name>@<version>`:
+/// `<target_dir>/package/<name>-<version>.crate`.
+///
+/// Pass [`target_directory`] rather than assuming `<workspace_root>/target`.
+pub fn packaged_crate_path(target_dir: &Path, name: &str, version: &str) -> PathBuf {
+    target_dir
+        .join("package")
+        .join(format!("{name}-{version}.crate"))
+}
+
+/// Hex-encoded SHA-256 of a `.crate` tarball.
+///
+/// This is the provenance hash recorded as
+/// [`PackageReceipt::artifact_sha256`](shipper_types::PackageReceipt::artifact_sha256).
+pub fn hash_crate_file(path: &Path) -> Result<String> {
+    let bytes = std::fs::read(path)
+        .with_context(|| format!("failed to read crate tarball {}", path.display()))?;
+    Ok(hex::encode(Sha256::digest(&bytes)))
+}
+
+/// crates.io's upload limit for a packaged `.crate` tarball (10 MiB).
+/// Other registries configure their own.
+pub const CRATES_IO_MAX_CRATE_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Result of [`check_crate_size`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SizeCheck {
+    /// Tarball size on disk.
+    pub size_bytes: u64,
+    /// Limit the tarball was checked against.
+    pub max_bytes: u64,
+    /// Whether `size_bytes` is over `max_bytes`; the registry would reject
+    /// the upload.
+    pub exceeds_limit: bool,
+}
+
+/// Compare the size of the `.crate` tarball at `crate_path` (see
+/// [`packaged_crate_path`]) with the registry's `max_bytes` limit, e.g.
+/// [`CRATES_IO_MAX_CRATE_BYTES`].
+///
+/// Run it after [`cargo_package`] to fail before a verify build and upload
+/// that the registry would reject anyway. A tarball exactly at the limit
+/// passes. Errors if the tarball cannot be read.
+pub fn check_crate_size(crate_path: &Path, max_bytes: u64) -> Result<SizeCheck> {
+    let size_bytes = std::fs::metadata(crate_path)
+        .with_context(|| format!("failed to read crate tarball {}", crate_path.display()))?
+        .len();
+    Ok(SizeCheck {
+        size_bytes,
+        max_bytes,
+        exceeds_limit: size_bytes > max_bytes,
+    })
+}
+
+/// Hash the tarball `cargo publish` left behind for `<name>@<version>`.
+///
+/// `target_dir` is the workspace's [`target_directory`]. Returns `None` when
+/// the tarball is missing or unreadable: provenance is best-effort and never
+/// fails a publish that already succeeded.
+pub fn published_artifact_sha256(target_dir: &Path, name: &str, version: &str) -> Option<String> {
+    hash_crate_file(&packaged_crate_path(target_dir, name, version)).ok()
+}
+
+/// What a `cargo publish` run amounted to, parsed from its exit status and
+/// output tails.
+///
+/// This is the one place cargo's wording is interpreted:
+/// [`classify_cargo_output`] and [`crate_already_published`] both build on
+/// [`parse_cargo_publish_result`]. The raw tails stay on the
+/// [`CargoOutput`] for receipts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CargoPublishResult {
+    /// Exit code 0 within the time limit.
+    Success,
+    /// The registry already has this version.
+    AlreadyPublished,
+    /// The registry throttled the upload (HTTP 429 or a rate-limit message).
+    RateLimited,
+    /// The registry rejected the credentials or the token lacks rights.
+    AuthFailed,
+    /// Packaging or the verification build failed; `first_error` is the
+    /// first `error` line cargo printed.
+    VerifyFailed { first_error: String },
+    /// A transient network or server-side failure (5xx, resets, DNS, TLS,
+    /// cargo's "spurious network error").
+    Network,
+    /// The run was killed by its timeout.
+    TimedOut,
+    /// None of the above; `tail` is the stderr tail.
+    Unknown { tail: String },
+}
+
+/// Rate-limit wording, checked before the generic retryable patterns.
+const RATE_LIMITED_PATTERNS: [&str; 2] = ["too many requests", "rate limit"];
+
+/// Credential and permission failures.
+const AUTH_FAILED_PATTERNS: [&str; 9] = [
+    "forbidden",
+    "unauthorized",
+    "not authorized",
+    "authentication",
+    "token is invalid",
+    "invalid credentials",
+    "permission denied",
+    "must be logged in",
+    "no token found",
+];
+
+/// Packaging and verification-build failures.
+const VERIFY_FAILED_PATTERNS: [&str; 3] = [
+    "failed to verify",
+    "could not compile",
+    "compilation failed",
+];
+
+/// Parse a `cargo publish` run into a [`CargoPublishResult`].
+///
+/// Matching is case-insensitive over both tails. An "already published"
+/// message wins over everything else (even a timeout), since it settles the
+/// question; throttling and transient failures win over auth and verify
+/// failures, matching the precedence of [`classify_cargo_output`].
+pub fn parse_cargo_publish_result(out: &CargoOutput) -> CargoPublishResult {
+    if out.exit_code == 0 && !out.timed_out {
+        return CargoPublishResult::Success;
+    }
+
+    let haystack = format!("{}\n{}", out.stderr_tail, out.stdout_tail).to_lowercase();
+    let any = |patterns: &[&str]| patterns.iter().any(|p| haystack.contains(p));
+
+    if any(&ALREADY_PUBLISHED_PATTERNS) {
+        return CargoPublishResult::AlreadyPublished;
+    }
+    if out.timed_out {
+        return CargoPublishResult::TimedOut;
+    }
+    if any(&RATE_LIMITED_PATTERNS) || contains_status(&haystack, "429") {
+        return CargoPublishResult::RateLimited;
+    }
+    if shipper_cargo_failure::classify_publish_failure(&out.stderr_tail, &out.stdout_tail).class
+        == shipper_cargo_failure::CargoFailureClass::Retryable
+    {
+        return CargoPublishResult::Network;
+    }
+    if any(&AUTH_FAILED_PATTERNS)
+        || contains_status(&haystack, "401")
+        || contains_status(&haystack, "403")
+    {
+        return CargoPublishResult::AuthFailed;
+    }
+    if any(&VERIFY_FAILED_PATTERNS) {
+        return CargoPublishResult::VerifyFailed {
+            first_error: first_error_line(out),
+        };
+    }
+    CargoPublishResult::Unknown {
+        tail: out.stderr_tail.clone(),
+    }
+}
+
+/// Whether `code` appears in `haystack` as a standalone number.
+fn contains_status(haystack: &str, code: &str) -> bool {
+    haystack.match_indices(code).any(|(start, matched)| {
+        let end = start + matched.len();
+        let boundary = |ch: Option<char>| ch.is_none_or(|ch| !ch.is_ascii_alphanumeric());
+        let before = haystack
+            .get(..start)
+            .and_then(|head| head.chars().next_back());
+        let after = haystack.get(end..).and_then(|tail| tail.chars().next());
+        boundary(before) && boundary(after)
     })
 }
 
+/// The first line starting with `error` in stderr (then stdout), or the
+/// last non-empty stderr line if cargo printed none.
+fn first_error_line(out: &CargoOutput) -> String {
+    out.stderr_tail
+        .lines()
+        .chain(out.stdout_tail.lines())
+        .map(str::trim)
+        .find(|line| line.to_lowercase().starts_with("error"))
+        .or_else(|| {
+            out.stderr_tail
+                .lines()
+                .map(str::trim)
+                .rfind(|line| !line.is_empty())
+        })
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Classify a failed cargo invocation into retry semantics.
+///
+/// Built on [`parse_cargo_publish_result`]: timeouts, throttling and
+/// network failures are [`ErrorClass::Retryable`]; already-published,
+/// auth and verify failures are [`ErrorClass::Permanent`]. Anything else
+/// goes through the remaining pattern tables in `shipper_cargo_failure`
+/// (e.g. manifest errors are permanent); output that matches nothing is
+/// [`ErrorClass::Ambiguous`], to be resolved against the registry. Only
+/// meaningful for non-zero exits.
+pub fn classify_cargo_output(out: &CargoOutput) -> ErrorClass {
+    match parse_cargo_publish_result(out) {
+        CargoPublishResult::TimedOut
+        | CargoPublishResult::RateLimited
+        | CargoPublishResult::Network => ErrorClass::Retryable,
+        CargoPublishResult::AlreadyPublished
+        | CargoPublishResult::AuthFailed
+        | CargoPublishResult::VerifyFailed { .. } => ErrorClass::Permanent,
+        CargoPublishResult::Success | CargoPublishResult::Unknown { .. } => {
+            match shipper_cargo_failure::classify_publish_failure(
+                &out.stderr_tail,
+                &out.stdout_tail,
+            )
+            .class
+            {
+                shipper_cargo_failure::CargoFailureClass::Retryable => ErrorClass::Retryable,
+                shipper_cargo_failure::CargoFailureClass::Permanent => ErrorClass::Permanent,
+                shipper_cargo_failure::CargoFailureClass::Ambiguous => ErrorClass::Ambiguous,
+            }
+        }
+    }
+}
+
 fn cargo_program() -> String {
     env::var("SHIPPER_CARGO_BIN").unwrap_or_else(|_| "cargo".to_string())
 }
@@ -278,6 +778,46 @@ pub struct WorkspaceMetadata {
     metadata: Metadata,
     /// Root directory of the workspace
     workspace_root: PathBuf,
+    /// Registry the workspace is being published to; checked against
+    /// each package's `publish = [...]` allowlist
+    target_registry: String,
+}
+
+/// Registry name Cargo uses for crates.io in `publish = [...]` lists.
+pub const DEFAULT_REGISTRY_NAME: &str = "crates-io";
+
+/// Why a package can or cannot be published, from
+/// [`WorkspaceMetadata::publishable_status`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Publishability {
+    /// The package can be published to the target registry.
+    Publishable,
+    /// `publish = false` in the manifest.
+    PublishFalse,
+    /// Version `0.0.0`, conventionally a never-published placeholder.
+    PlaceholderVersion,
+    /// `publish = [...]` does not list the target registry.
+    RegistryDisallowed {
+        /// Registries the manifest allows.
+        allowed: Vec<String>,
+    },
+}
+
+impl std::fmt::Display for Publishability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Publishable => f.write_str("publishable"),
+            Self::PublishFalse => f.write_str("publish = false"),
+            Self::PlaceholderVersion => f.write_str("placeholder version 0.0.0"),
+            Self::RegistryDisallowed { allowed } => {
+                write!(
+                    f,
+                    "publish = [{}] (registry not in list)",
+                    allowed.join(", ")
+                )
+            }
+        }
+    }
 }
 
 impl WorkspaceMetadata {
@@ -288,12 +828,25 @@ impl WorkspaceMetadata {
             .exec()
             .context("failed to load cargo metadata")?;
 
+        Ok(Self::from_metadata(metadata))
+    }
+
+    /// Wrap already-loaded `cargo metadata` output.
+    pub fn from_metadata(metadata: Metadata) -> Self {
         let workspace_root = metadata.workspace_root.clone().into_std_path_buf();
 
-        Ok(Self {
+        Self {
             metadata,
             workspace_root,
-        })
+            target_registry: DEFAULT_REGISTRY_NAME.to_string(),
+        }
+    }
+
+    /// Check `publish = [...]` allowlists against `registry` instead of
+    /// [`DEFAULT_REGISTRY_NAME`].
+    pub fn with_target_registry(mut self, registry: impl Into<String>) -> Self {
+        self.target_registry = registry.into();
+        self
     }
 
     /// Load metadata from the current directory.
@@ -324,19 +877,40 @@ impl WorkspaceMetadata {
             .collect()
     }
 
+    /// Publishable packages narrowed by name patterns; see [`filter_packages`].
+    pub fn select_packages(
+        &self,
+        include: Option<&[String]>,
+        exclude: &[String],
+    ) -> Result<Vec<&Package>> {
+        filter_packages(self.publishable_packages(), include, exclude)
+    }
+
     /// Check if a package is publishable.
     pub fn is_publishable(&self, package: &Package) -> bool {
-        if let Some(publish) = &package.publish
-            && publish.is_empty()
-        {
-            return false;
+        matches!(
+            self.publishable_status(package),
+            Publishability::Publishable
+        )
+    }
+
+    /// Whether a package is publishable to the target registry, and why not.
+    pub fn publishable_status(&self, package: &Package) -> Publishability {
+        match &package.publish {
+            Some(publish) if publish.is_empty() => return Publishability::PublishFalse,
+            Some(publish) if !publish.iter().any(|r| r == &self.target_registry) => {
+                return Publishability::RegistryDisallowed {
+                    allowed: publish.clone(),
+                };
+            }
+            _ => {}
         }
 
         if package.version.to_string() == "0.0.0" {
-            return false;
+            return Publishability::PlaceholderVersion;
         }
 
-        true
+        Publishability::Publishable
     }
 
     /// Look up a package by name.
@@ -374,12 +948,21 @@ impl WorkspaceMetadata {
     }
 
     /// Packages in topological order (dependencies first).
+    ///
+    /// Only normal and build dependencies order the publish; dev-dependencies
+    /// are stripped by `cargo publish`, so a test-only cycle is not an error.
     pub fn topological_order(&self) -> Result<Vec<String>> {
+        self.topological_order_with(&[DependencyKind::Normal, DependencyKind::Build])
+    }
+
+    /// Publishable packages in topological order, following only
+    /// dependencies of the given `kinds`.
+    pub fn topological_order_with(&self, kinds: &[DependencyKind]) -> Result<Vec<String>> {
         let mut order = Vec::new();
         let mut visited = HashSet::new();
         let mut visiting = HashSet::new();
 
-        let dep_graph = self.build_dependency_graph();
+        let dep_graph = self.build_dependency_graph(kinds);
 
         for package in self.publishable_packages() {
             let name = package.name.to_string();
@@ -423,16 +1006,88 @@ impl WorkspaceMetadata {
         Ok(())
     }
 
-    fn build_dependency_graph(&self) -> HashMap<String, Vec<String>> {
+    /// Internal dependency requirements the dependee's current version does
+    /// not satisfy.
+    ///
+    /// Pure metadata check, no network: for every normal or build dependency
+    /// between publishable packages, the requirement (`b = "1.2"`) must match
+    /// the version about to be published (`b 2.0.0` would not), otherwise the
+    /// depender fails to resolve once both are on the registry.
+    pub fn check_internal_version_reqs(&self) -> Vec<VersionReqIssue> {
+        let publishable = self.publishable_packages();
+        let mut issues = Vec::new();
+
+        for depender in &publishable {
+            for dep in &depender.dependencies {
+                if !matches!(dep.kind, DependencyKind::Normal | DependencyKind::Build) {
+                    continue;
+                }
+                let Some(dependee) = publishable.iter().find(|p| p.name == dep.name) else {
+                    continue;
+                };
+                if !dep.req.matches(&dependee.version) {
+                    issues.push(VersionReqIssue {
+                        depender: depender.name.to_string(),
+                        dependee: dependee.name.to_string(),
+                        required: dep.req.to_string(),
+                        actual: dependee.version.to_string(),
+                    });
+                }
+            }
+        }
+
+        issues
+    }
+
+    /// Publishable packages that depend on `name`, directly or transitively,
+    /// sorted by name.
+    ///
+    /// Walks the same normal + build dependency graph as
+    /// [`topological_order`](Self::topological_order), reversed, so it
+    /// answers "what breaks if `name` changes": everything here has to be
+    /// republished (or at least rechecked) after it. Dev-dependencies are not
+    /// followed. `name` itself is never included; an unknown or
+    /// non-publishable `name` has no dependents.
+    pub fn dependents_of(&self, name: &str) -> Vec<String> {
+        let mut reverse: HashMap<&str, Vec<&str>> = HashMap::new();
+        let graph = self.build_dependency_graph(&[DependencyKind::Normal, DependencyKind::Build]);
+        for (depender, deps) in &graph {
+            for dep in deps {
+                reverse
+                    .entry(dep.as_str())
+                    .or_default()
+                    .push(depender.as_str());
+            }
+        }
+
+        let mut dependents = BTreeSet::new();
+        let mut stack = vec![name];
+        while let Some(current) = stack.pop() {
+            for &depender in reverse.get(current).into_iter().flatten() {
+                if depender != name && dependents.insert(depender) {
+                    stack.push(depender);
+                }
+            }
+        }
+
+        dependents.into_iter().map(str::to_string).collect()
+    }
+
+    /// Edges between publishable packages via dependencies of `kinds`.
+    ///
+    /// Dependencies on packages that will not be published to the target
+    /// registry are left out: they cannot constrain this publish.
+    fn build_dependency_graph(&self, kinds: &[DependencyKind]) -> HashMap<String, Vec<String>> {
+        let publishable = self.publishable_packages();
         let mut graph = HashMap::new();
 
-        for package in self.publishable_packages() {
+        for package in &publishable {
             let deps: Vec<String> = package
                 .dependencies
                 .iter()
+                .filter(|dep| kinds.contains(&dep.kind))
                 .filter_map(|dep| {
-                    self.metadata
-                        .packages
+                    publishable
                         .iter()
                         .find(|p| p.name == dep.name)
                         .map(|p| p.name.to_string())
@@ -446,6 +1101,64 @@ impl WorkspaceMetadata {
     }
 }
 
+/// Narrow `all` by glob patterns on package names.
+///
+/// With `include` set, a package must match at least one include pattern;
+/// a package matching any `exclude` pattern is dropped even if it was
+/// included. Plain names are patterns too (`xtask` matches only `xtask`).
+pub fn filter_packages<'a>(
+    all: impl IntoIterator<Item = &'a Package>,
+    include: Option<&[String]>,
+    exclude: &[String],
+) -> Result<Vec<&'a Package>> {
+    let include = include.map(build_globset).transpose()?;
+    let exclude = build_globset(exclude)?;
+
+    Ok(all
+        .into_iter()
+        .filter(|pkg| {
+            let name = pkg.name.as_str();
+            include.as_ref().is_none_or(|set| set.is_match(name)) && !exclude.is_match(name)
+        })
+        .collect())
+}
+
+fn build_globset(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(
+            Glob::new(pattern).with_context(|| format!("invalid package pattern: {pattern}"))?,
+        );
+    }
+    builder
+        .build()
+        .context("failed to compile package patterns")
+}
+
+/// An internal dependency whose version requirement excludes the
+/// dependee's version, from [`WorkspaceMetadata::check_internal_version_reqs`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionReqIssue {
+    /// Package declaring the dependency
+    pub depender: String,
+    /// Workspace package depended on
+    pub dependee: String,
+    /// Declared version requirement (e.g. `^1.2`)
+    pub required: String,
+    /// Version of the dependee about to be published
+    pub actual: String,
+}
+
+impl std::fmt::Display for VersionReqIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} requires {} {} but the workspace has {}",
+            self.depender, self.dependee, self.required, self.actual
+        )
+    }
+}
+
 /// Simplified package information.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PackageInfo {
@@ -513,6 +1226,19 @@ pub fn is_valid_package_name(name: &str) -> bool {
     valid(first) && chars.all(valid)
 }
 
+/// The workspace's target directory, as reported by `cargo metadata`.
+///
+/// Honors `CARGO_TARGET_DIR`, `build.target-dir` and friends, so it can live
+/// far from `<workspace_root>/target` on CI. Use it as the base for
+/// [`packaged_crate_path`].
+pub fn target_directory(metadata: &WorkspaceMetadata) -> PathBuf {
+    metadata
+        .metadata
+        .target_directory
+        .clone()
+        .into_std_path_buf()
+}
+
 /// All workspace member package names.
 pub fn workspace_member_names(metadata: &WorkspaceMetadata) -> Vec<String> {
     metadata
@@ -522,40 +1248,219 @@ pub fn workspace_member_names(metadata: &WorkspaceMetadata) -> Vec<String> {
         .collect()
 }
 
-#[cfg(test)]
-mod tests {
-    use std::fs;
-    use std::path::{Path, PathBuf};
+// ──────────────────────────────────────────────────────────────────────────
+// Manifest editing
+// ──────────────────────────────────────────────────────────────────────────
 
-    use serial_test::serial;
-    use tempfile::tempdir;
+/// Dependency tables that carry version requirements.
+const DEPENDENCY_TABLES: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
 
-    use super::*;
+/// Set the version requirement of dependency `dep_name` to `new_req` in every
+/// workspace manifest, for coordinated workspace releases.
+///
+/// `manifest_dir` is the workspace root. The root manifest and every member
+/// manifest are edited with `toml_edit`, so formatting and comments survive.
+/// Only `[dependencies]`, `[dev-dependencies]`, `[build-dependencies]` (also
+/// under `[target.'cfg(..)']`) and `[workspace.dependencies]` are touched;
+/// entries are matched by key or by `package = "<dep_name>"` for renamed
+/// dependencies. Entries without a `version` (path-only or
+/// `workspace = true`) are left alone.
+///
+/// Returns the manifests that were rewritten.
+pub fn bump_workspace_dep(
+    manifest_dir: &Path,
+    dep_name: &str,
+    new_req: &str,
+) -> Result<Vec<PathBuf>> {
+    let root_manifest = manifest_dir.join("Cargo.toml");
+    let metadata = MetadataCommand::new()
+        .manifest_path(&root_manifest)
+        .no_deps()
+        .exec()
+        .context("failed to execute cargo metadata")?;
 
-    fn write_fake_cargo(bin_dir: &Path) -> PathBuf {
-        #[cfg(windows)]
-        {
-            let path = bin_dir.join("cargo.cmd");
-            fs::write(
-                &path,
-                "@echo off\r\necho %*>\"%SHIPPER_ARGS_LOG%\"\r\necho %CD%>\"%SHIPPER_CWD_LOG%\"\r\necho fake-stdout\r\necho fake-stderr 1>&2\r\nexit /b %SHIPPER_EXIT_CODE%\r\n",
-            )
-            .expect("write fake cargo");
-            path
+    let mut manifests = vec![root_manifest];
+    for pkg in metadata.workspace_packages() {
+        let path = pkg.manifest_path.clone().into_std_path_buf();
+        if !manifests.contains(&path) {
+            manifests.push(path);
         }
+    }
 
-        #[cfg(not(windows))]
-        {
-            use std::os::unix::fs::PermissionsExt;
+    let mut changed = Vec::new();
+    for manifest in manifests {
+        let content = std::fs::read_to_string(&manifest)
+            .with_context(|| format!("failed to read {}", manifest.display()))?;
+        let mut doc: toml_edit::DocumentMut = content
+            .parse()
+            .with_context(|| format!("failed to parse {}", manifest.display()))?;
 
-            let path = bin_dir.join("cargo");
-            fs::write(
-                &path,
-                "#!/usr/bin/env sh\nprintf '%s' \"$*\" >\"$SHIPPER_ARGS_LOG\"\npwd >\"$SHIPPER_CWD_LOG\"\necho fake-stdout\necho fake-stderr >&2\nexit \"${SHIPPER_EXIT_CODE:-0}\"\n",
-            )
-            .expect("write fake cargo");
-            let mut perms = fs::metadata(&path).expect("meta").permissions();
-            perms.set_mode(0o755);
+        if bump_dep_in_document(&mut doc, dep_name, new_req) {
+            std::fs::write(&manifest, doc.to_string())
+                .with_context(|| format!("failed to write {}", manifest.display()))?;
+            changed.push(manifest);
+        }
+    }
+
+    Ok(changed)
+}
+
+/// Apply [`bump_workspace_dep`] to one parsed manifest. Returns whether
+/// anything changed.
+fn bump_dep_in_document(doc: &mut toml_edit::DocumentMut, dep_name: &str, new_req: &str) -> bool {
+    let root = doc.as_table_mut();
+    let mut changed = false;
+
+    for table in DEPENDENCY_TABLES {
+        if let Some(deps) = root.get_mut(table) {
+            changed |= bump_dep_in_table(deps, dep_name, new_req);
+        }
+    }
+
+    if let Some(targets) = root.get_mut("target").and_then(|t| t.as_table_like_mut()) {
+        for (_, target) in targets.iter_mut() {
+            let Some(target) = target.as_table_like_mut() else {
+                continue;
+            };
+            for table in DEPENDENCY_TABLES {
+                if let Some(deps) = target.get_mut(table) {
+                    changed |= bump_dep_in_table(deps, dep_name, new_req);
+                }
+            }
+        }
+    }
+
+    if let Some(deps) = root
+        .get_mut("workspace")
+        .and_then(|w| w.as_table_like_mut())
+        .and_then(|w| w.get_mut("dependencies"))
+    {
+        changed |= bump_dep_in_table(deps, dep_name, new_req);
+    }
+
+    changed
+}
+
+fn bump_dep_in_table(deps: &mut toml_edit::Item, dep_name: &str, new_req: &str) -> bool {
+    let Some(deps) = deps.as_table_like_mut() else {
+        return false;
+    };
+    let mut changed = false;
+
+    for (key, dep) in deps.iter_mut() {
+        if let Some(req) = dep.as_value_mut().filter(|v| v.is_str()) {
+            if key.get() == dep_name {
+                changed |= set_version_req(req, new_req);
+            }
+            continue;
+        }
+
+        let Some(dep) = dep.as_table_like_mut() else {
+            continue;
+        };
+        let package = dep.get("package").and_then(|p| p.as_str());
+        if package.unwrap_or(key.get()) != dep_name {
+            continue;
+        }
+        if let Some(req) = dep.get_mut("version").and_then(|v| v.as_value_mut()) {
+            changed |= set_version_req(req, new_req);
+        }
+    }
+
+    changed
+}
+
+/// Replace a version requirement string, keeping surrounding whitespace and
+/// comments.
+fn set_version_req(req: &mut toml_edit::Value, new_req: &str) -> bool {
+    if req.as_str() == Some(new_req) {
+        return false;
+    }
+    let decor = req.decor().clone();
+    *req = toml_edit::Value::from(new_req);
+    *req.decor_mut() = decor;
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    use serial_test::serial;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    static DEFAULT_INVOCATION: CargoInvocation = CargoInvocation {
+        toolchain: None,
+        leading_args: Vec::new(),
+        trailing_args: Vec::new(),
+    };
+
+    /// Publish `package` to crates-io with every option off.
+    fn publish_args<'a>(workspace_root: &'a Path, package_name: &'a str) -> PublishArgs<'a> {
+        PublishArgs {
+            workspace_root,
+            invocation: &DEFAULT_INVOCATION,
+            package_name,
+            registry_name: "crates-io",
+            allow_dirty: false,
+            no_verify: false,
+            features: &[],
+            no_default_features: false,
+            all_features: false,
+            output_lines: 50,
+            timeout: None,
+        }
+    }
+
+    #[test]
+    fn publish_args_new_takes_cargo_settings_from_options() {
+        let opts = RuntimeOptions {
+            allow_dirty: true,
+            features: vec!["demo/std".to_string()],
+            output_lines: 7,
+            ..RuntimeOptions::default()
+        }
+        .with_cargo(CargoInvocation {
+            toolchain: Some("stable".to_string()),
+            leading_args: Vec::new(),
+            trailing_args: vec!["--locked".to_string()],
+        });
+
+        let args = PublishArgs::new(Path::new("."), &opts, "demo", "crates-io");
+        assert_eq!(args.invocation, &opts.cargo);
+        assert!(args.allow_dirty);
+        assert_eq!(args.features, ["demo/std"]);
+        assert_eq!(args.output_lines, 7);
+        assert_eq!(args.timeout, None);
+    }
+
+    fn write_fake_cargo(bin_dir: &Path) -> PathBuf {
+        #[cfg(windows)]
+        {
+            let path = bin_dir.join("cargo.cmd");
+            fs::write(
+                &path,
+                "@echo off\r\necho %*>\"%SHIPPER_ARGS_LOG%\"\r\necho %CD%>\"%SHIPPER_CWD_LOG%\"\r\necho fake-stdout\r\nif defined SHIPPER_FAKE_STDERR (echo %SHIPPER_FAKE_STDERR% 1>&2) else (echo fake-stderr 1>&2)\r\nexit /b %SHIPPER_EXIT_CODE%\r\n",
+            )
+            .expect("write fake cargo");
+            path
+        }
+
+        #[cfg(not(windows))]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let path = bin_dir.join("cargo");
+            fs::write(
+                &path,
+                "#!/usr/bin/env sh\nprintf '%s' \"$*\" >\"$SHIPPER_ARGS_LOG\"\npwd >\"$SHIPPER_CWD_LOG\"\necho fake-stdout\necho \"${SHIPPER_FAKE_STDERR:-fake-stderr}\" >&2\nexit \"${SHIPPER_EXIT_CODE:-0}\"\n",
+            )
+            .expect("write fake cargo");
+            let mut perms = fs::metadata(&path).expect("meta").permissions();
+            perms.set_mode(0o755);
             fs::set_permissions(&path, perms).expect("chmod");
             path
         }
@@ -586,8 +1491,13 @@ mod tests {
                 ("SHIPPER_EXIT_CODE", Some("7")),
             ],
             || {
-                let out = cargo_publish(&ws, "my-crate", "private-reg", true, true, 50, None)
-                    .expect("publish");
+                let out = cargo_publish(&PublishArgs {
+                    registry_name: "private-reg",
+                    allow_dirty: true,
+                    no_verify: true,
+                    ..publish_args(&ws, "my-crate")
+                })
+                .expect("publish");
 
                 assert_eq!(out.exit_code, 7);
                 assert!(out.stdout_tail.contains("fake-stdout"));
@@ -631,8 +1541,7 @@ mod tests {
                 ("SHIPPER_EXIT_CODE", Some("0")),
             ],
             || {
-                let _ = cargo_publish(&ws, "my-crate", "crates-io", false, false, 50, None)
-                    .expect("publish");
+                let _ = cargo_publish(&publish_args(&ws, "my-crate")).expect("publish");
 
                 let args = fs::read_to_string(&args_log).expect("args");
                 assert!(!args.contains("--registry"));
@@ -652,8 +1561,7 @@ mod tests {
             "SHIPPER_CARGO_BIN",
             Some(missing.to_str().expect("utf8")),
             || {
-                let err = cargo_publish(td.path(), "x", "crates-io", false, false, 50, None)
-                    .expect_err("must fail");
+                let err = cargo_publish(&publish_args(td.path(), "x")).expect_err("must fail");
                 assert!(format!("{err:#}").contains("failed to execute cargo publish"));
             },
         );
@@ -795,8 +1703,12 @@ mod tests {
                 ("SHIPPER_EXIT_CODE", Some("0")),
             ],
             || {
-                let out = cargo_publish_dry_run_package(&ws, "my-crate", "private-reg", true, 50)
-                    .expect("dry-run");
+                let out = cargo_publish_dry_run_package(&PublishArgs {
+                    registry_name: "private-reg",
+                    allow_dirty: true,
+                    ..publish_args(&ws, "my-crate")
+                })
+                .expect("dry-run");
 
                 assert_eq!(out.exit_code, 0);
                 let args = fs::read_to_string(&args_log).expect("args");
@@ -1142,15 +2054,10 @@ mod tests {
                 Some(fake_cargo_path.to_str().expect("utf8")),
             )],
             || {
-                let out = cargo_publish(
-                    &ws,
-                    "test-crate",
-                    "crates-io",
-                    false,
-                    false,
-                    50,
-                    Some(Duration::from_secs(1)),
-                )
+                let out = cargo_publish(&PublishArgs {
+                    timeout: Some(Duration::from_secs(1)),
+                    ..publish_args(&ws, "test-crate")
+                })
                 .expect("publish with timeout");
 
                 assert!(out.timed_out, "expected timed_out flag to be set");
@@ -1185,8 +2092,7 @@ mod tests {
                 ("SHIPPER_EXIT_CODE", Some("0")),
             ],
             || {
-                let out = cargo_publish(&ws, "crate-x", "crates-io", false, false, 50, None)
-                    .expect("publish");
+                let out = cargo_publish(&publish_args(&ws, "crate-x")).expect("publish");
                 assert!(!out.timed_out, "should not time out");
                 assert_eq!(out.exit_code, 0);
             },
@@ -1247,7 +2153,11 @@ mod tests {
                 ("SHIPPER_EXIT_CODE", Some("0")),
             ],
             || {
-                let _ = cargo_publish(&ws, "crate-y", "", false, false, 50, None).expect("publish");
+                let _ = cargo_publish(&PublishArgs {
+                    registry_name: "",
+                    ..publish_args(&ws, "crate-y")
+                })
+                .expect("publish");
                 let args = fs::read_to_string(&args_log).expect("args");
                 assert!(
                     !args.contains("--registry"),
@@ -1282,8 +2192,11 @@ mod tests {
                 ("SHIPPER_EXIT_CODE", Some("0")),
             ],
             || {
-                let _ =
-                    cargo_publish(&ws, "crate-z", "   ", false, false, 50, None).expect("publish");
+                let _ = cargo_publish(&PublishArgs {
+                    registry_name: "   ",
+                    ..publish_args(&ws, "crate-z")
+                })
+                .expect("publish");
                 let args = fs::read_to_string(&args_log).expect("args");
                 assert!(
                     !args.contains("--registry"),
@@ -1293,6 +2206,93 @@ mod tests {
         );
     }
 
+    // ── CargoInvocation ──
+
+    #[test]
+    fn default_invocation_leaves_args_unchanged() {
+        assert_eq!(
+            CargoInvocation::default().argv(&["publish", "-p", "demo"]),
+            ["publish", "-p", "demo"]
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn cargo_publish_applies_toolchain_and_extra_args() {
+        let td = tempdir().expect("tempdir");
+        let bin = td.path().join("bin");
+        fs::create_dir_all(&bin).expect("mkdir");
+        let fake_cargo = write_fake_cargo(&bin);
+
+        let args_log = td.path().join("args.txt");
+        let cwd_log = td.path().join("cwd.txt");
+
+        let invocation = CargoInvocation {
+            toolchain: Some("nightly".to_string()),
+            leading_args: vec!["-Zpublish-timeout".to_string()],
+            trailing_args: vec!["--quiet".to_string()],
+        };
+
+        temp_env::with_vars(
+            [
+                (
+                    "SHIPPER_CARGO_BIN",
+                    Some(fake_cargo.to_str().expect("utf8")),
+                ),
+                ("SHIPPER_ARGS_LOG", Some(args_log.to_str().expect("utf8"))),
+                ("SHIPPER_CWD_LOG", Some(cwd_log.to_str().expect("utf8"))),
+                ("SHIPPER_EXIT_CODE", Some("0")),
+            ],
+            || {
+                cargo_publish(&PublishArgs {
+                    invocation: &invocation,
+                    ..publish_args(td.path(), "demo")
+                })
+                .expect("publish");
+                let args = fs::read_to_string(&args_log).expect("args");
+                assert_eq!(
+                    args.trim(),
+                    "+nightly -Zpublish-timeout publish -p demo --quiet"
+                );
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn cargo_publish_dry_run_workspace_applies_toolchain() {
+        let td = tempdir().expect("tempdir");
+        let bin = td.path().join("bin");
+        fs::create_dir_all(&bin).expect("mkdir");
+        let fake_cargo = write_fake_cargo(&bin);
+
+        let args_log = td.path().join("args.txt");
+        let cwd_log = td.path().join("cwd.txt");
+
+        let invocation = CargoInvocation {
+            toolchain: Some("1.85.0".to_string()),
+            ..CargoInvocation::default()
+        };
+
+        temp_env::with_vars(
+            [
+                (
+                    "SHIPPER_CARGO_BIN",
+                    Some(fake_cargo.to_str().expect("utf8")),
+                ),
+                ("SHIPPER_ARGS_LOG", Some(args_log.to_str().expect("utf8"))),
+                ("SHIPPER_CWD_LOG", Some(cwd_log.to_str().expect("utf8"))),
+                ("SHIPPER_EXIT_CODE", Some("0")),
+            ],
+            || {
+                cargo_publish_dry_run_workspace(td.path(), &invocation, "crates-io", false, 50)
+                    .expect("dry-run");
+                let args = fs::read_to_string(&args_log).expect("args");
+                assert_eq!(args.trim(), "+1.85.0 publish --workspace --dry-run");
+            },
+        );
+    }
+
     // ── Dry-run workspace variant ──
 
     #[test]
@@ -1320,8 +2320,14 @@ mod tests {
                 ("SHIPPER_EXIT_CODE", Some("0")),
             ],
             || {
-                let out =
-                    cargo_publish_dry_run_workspace(&ws, "my-reg", true, 50).expect("dry-run ws");
+                let out = cargo_publish_dry_run_workspace(
+                    &ws,
+                    &CargoInvocation::default(),
+                    "my-reg",
+                    true,
+                    50,
+                )
+                .expect("dry-run ws");
 
                 assert_eq!(out.exit_code, 0);
                 let args = fs::read_to_string(&args_log).expect("args");
@@ -1359,37 +2365,1329 @@ mod tests {
                 ("SHIPPER_EXIT_CODE", Some("0")),
             ],
             || {
-                let _ =
-                    cargo_publish_dry_run_workspace(&ws, "crates-io", false, 50).expect("dry-run");
+                let _ = cargo_publish_dry_run_workspace(
+                    &ws,
+                    &CargoInvocation::default(),
+                    "crates-io",
+                    false,
+                    50,
+                )
+                .expect("dry-run");
+                let args = fs::read_to_string(&args_log).expect("args");
+                assert!(!args.contains("--registry"));
+                assert!(!args.contains("--allow-dirty"));
+            },
+        );
+    }
+
+    fn verify_workspace_with(exit_code: &str, stderr: Option<&str>) -> VerifyResult {
+        let td = tempdir().expect("tempdir");
+        let bin = td.path().join("bin");
+        fs::create_dir_all(&bin).expect("mkdir");
+        let fake_cargo = write_fake_cargo(&bin);
+
+        let args_log = td.path().join("args.txt");
+        let cwd_log = td.path().join("cwd.txt");
+
+        temp_env::with_vars(
+            [
+                (
+                    "SHIPPER_CARGO_BIN",
+                    Some(fake_cargo.to_str().expect("utf8")),
+                ),
+                ("SHIPPER_ARGS_LOG", Some(args_log.to_str().expect("utf8"))),
+                ("SHIPPER_CWD_LOG", Some(cwd_log.to_str().expect("utf8"))),
+                ("SHIPPER_EXIT_CODE", Some(exit_code)),
+                ("SHIPPER_FAKE_STDERR", stderr),
+            ],
+            || {
+                verify_workspace(td.path(), &DEFAULT_INVOCATION, "crates-io", false, 50)
+                    .expect("verify")
+            },
+        )
+    }
+
+    #[test]
+    #[serial]
+    fn verify_workspace_passes_on_clean_exit() {
+        let result = verify_workspace_with("0", None);
+        assert!(result.passed);
+        assert_eq!(result.class, None);
+        assert!(result.output_tail.contains("fake-stderr"));
+    }
+
+    #[test]
+    #[serial]
+    fn verify_workspace_classifies_compile_error_as_permanent() {
+        let result = verify_workspace_with(
+            "101",
+            Some("error: could not compile `demo` (lib) due to 1 previous error"),
+        );
+        assert!(!result.passed);
+        assert_eq!(result.class, Some(ErrorClass::Permanent));
+        assert!(result.output_tail.contains("could not compile"));
+    }
+
+    #[test]
+    #[serial]
+    fn verify_workspace_classifies_network_error_as_retryable() {
+        let result = verify_workspace_with(
+            "101",
+            Some("warning: spurious network error (2 tries remaining)"),
+        );
+        assert!(!result.passed);
+        assert_eq!(result.class, Some(ErrorClass::Retryable));
+    }
+
+    #[test]
+    #[serial]
+    fn cargo_publish_dry_run_workspace_errors_when_command_missing() {
+        let td = tempdir().expect("tempdir");
+        let missing = td.path().join("nonexistent-cargo");
+
+        temp_env::with_var(
+            "SHIPPER_CARGO_BIN",
+            Some(missing.to_str().expect("utf8")),
+            || {
+                let err = cargo_publish_dry_run_workspace(
+                    td.path(),
+                    &CargoInvocation::default(),
+                    "crates-io",
+                    false,
+                    50,
+                )
+                .expect_err("must fail");
+                assert!(format!("{err:#}").contains("failed to execute cargo publish"));
+            },
+        );
+    }
+
+    // ── Dry-run package variant additional tests ──
+
+    #[test]
+    #[serial]
+    fn cargo_publish_dry_run_package_omits_registry_for_crates_io() {
+        let td = tempdir().expect("tempdir");
+        let bin = td.path().join("bin");
+        fs::create_dir_all(&bin).expect("mkdir");
+        let fake_cargo = write_fake_cargo(&bin);
+
+        let args_log = td.path().join("args.txt");
+        let cwd_log = td.path().join("cwd.txt");
+
+        let ws = td.path().join("workspace");
+        fs::create_dir_all(&ws).expect("mkdir ws");
+
+        temp_env::with_vars(
+            [
+                (
+                    "SHIPPER_CARGO_BIN",
+                    Some(fake_cargo.to_str().expect("utf8")),
+                ),
+                ("SHIPPER_ARGS_LOG", Some(args_log.to_str().expect("utf8"))),
+                ("SHIPPER_CWD_LOG", Some(cwd_log.to_str().expect("utf8"))),
+                ("SHIPPER_EXIT_CODE", Some("0")),
+            ],
+            || {
+                let _ = cargo_publish_dry_run_package(&publish_args(&ws, "pkg")).expect("dry-run");
+                let args = fs::read_to_string(&args_log).expect("args");
+                assert!(!args.contains("--registry"));
+                assert!(!args.contains("--allow-dirty"));
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn cargo_publish_dry_run_package_errors_when_command_missing() {
+        let td = tempdir().expect("tempdir");
+        let missing = td.path().join("nonexistent-cargo");
+
+        temp_env::with_var(
+            "SHIPPER_CARGO_BIN",
+            Some(missing.to_str().expect("utf8")),
+            || {
+                let err = cargo_publish_dry_run_package(&publish_args(td.path(), "pkg"))
+                    .expect_err("must fail");
+                let msg = format!("{err:#}");
+                assert!(msg.contains("failed to execute cargo publish --dry-run -p pkg"));
+            },
+        );
+    }
+
+    // ── Output line truncation via tail_lines ──
+
+    #[test]
+    fn tail_lines_truncates_to_requested_count() {
+        let lines: Vec<String> = (0..100).map(|i| format!("line {i}")).collect();
+        let input = lines.join("\n");
+        let out = tail_lines(&input, 5);
+        assert_eq!(out.lines().count(), 5);
+        assert!(out.contains("line 95"));
+        assert!(out.contains("line 99"));
+        assert!(!out.contains("line 94"));
+    }
+
+    #[test]
+    fn tail_lines_one_line_requested() {
+        let input = "first\nsecond\nthird";
+        let out = tail_lines(input, 1);
+        assert_eq!(out, "third");
+    }
+
+    #[test]
+    fn tail_lines_redacts_token_in_last_line() {
+        let input = "safe1\nsafe2\nCARGO_REGISTRY_TOKEN=leaked";
+        let out = tail_lines(input, 2);
+        assert!(!out.contains("leaked"));
+        assert!(out.contains("CARGO_REGISTRY_TOKEN=[REDACTED]"));
+    }
+
+    #[test]
+    fn tail_lines_token_outside_window_not_visible() {
+        let input = "CARGO_REGISTRY_TOKEN=secret\nsafe1\nsafe2";
+        let out = tail_lines(input, 2);
+        assert!(!out.contains("secret"));
+        assert!(!out.contains("CARGO_REGISTRY_TOKEN"));
+        assert_eq!(out, "safe1\nsafe2");
+    }
+
+    // ── Error message patterns ──
+
+    #[test]
+    fn redact_token_in_error_message_context() {
+        let input =
+            "error: failed to publish: token = \"cio_leakedsecret\" was rejected by registry";
+        let out = redact_sensitive(input);
+        assert!(!out.contains("cio_leakedsecret"));
+        assert!(out.contains("[REDACTED]"));
+    }
+
+    #[test]
+    fn redact_bearer_in_http_error() {
+        let input =
+            "error: HTTP 403 Forbidden\nAuthorization: Bearer expired_tok_abc\nBody: access denied";
+        let out = redact_sensitive(input);
+        assert!(!out.contains("expired_tok_abc"));
+        assert!(out.contains("error: HTTP 403 Forbidden"));
+        assert!(out.contains("Body: access denied"));
+    }
+
+    #[test]
+    fn redact_registry_token_in_debug_output() {
+        let input = "debug: env CARGO_REGISTRY_TOKEN=cio_debug_tok resolved from environment";
+        let out = redact_sensitive(input);
+        assert!(!out.contains("cio_debug_tok"));
+        assert!(out.contains("[REDACTED]"));
+    }
+
+    // ── CargoOutput struct behavior ──
+
+    #[test]
+    fn cargo_output_default_fields() {
+        let out = CargoOutput {
+            exit_code: 0,
+            stdout_tail: String::new(),
+            stderr_tail: String::new(),
+            duration: Duration::from_secs(0),
+            timed_out: false,
+        };
+        assert_eq!(out.exit_code, 0);
+        assert!(out.stdout_tail.is_empty());
+        assert!(out.stderr_tail.is_empty());
+        assert!(!out.timed_out);
+    }
+
+    #[test]
+    fn cargo_output_clone_is_independent() {
+        let out = CargoOutput {
+            exit_code: 42,
+            stdout_tail: "hello".to_string(),
+            stderr_tail: "world".to_string(),
+            duration: Duration::from_millis(500),
+            timed_out: true,
+        };
+        let cloned = out.clone();
+        assert_eq!(cloned.exit_code, out.exit_code);
+        assert_eq!(cloned.stdout_tail, out.stdout_tail);
+        assert_eq!(cloned.stderr_tail, out.stderr_tail);
+        assert_eq!(cloned.timed_out, out.timed_out);
+    }
+
+    #[test]
+    fn cargo_output_debug_format() {
+        let out = CargoOutput {
+            exit_code: 1,
+            stdout_tail: "out".to_string(),
+            stderr_tail: "err".to_string(),
+            duration: Duration::from_secs(1),
+            timed_out: false,
+        };
+        let debug = format!("{out:?}");
+        assert!(debug.contains("CargoOutput"));
+        assert!(debug.contains("exit_code: 1"));
+    }
+
+    // ── Redaction idempotency ──
+
+    #[test]
+    fn redact_is_idempotent_bearer() {
+        let input = "Authorization: Bearer secret_value";
+        let once = redact_sensitive(input);
+        let twice = redact_sensitive(&once);
+        assert_eq!(once, twice);
+    }
+
+    #[test]
+    fn redact_is_idempotent_env_token() {
+        let input = "CARGO_REGISTRY_TOKEN=secret";
+        let once = redact_sensitive(input);
+        let twice = redact_sensitive(&once);
+        assert_eq!(once, twice);
+    }
+
+    #[test]
+    fn redact_is_idempotent_token_assignment() {
+        let input = r#"token = "secret_value""#;
+        let once = redact_sensitive(input);
+        let twice = redact_sensitive(&once);
+        assert_eq!(once, twice);
+    }
+
+    // ── Streaming output ──
+
+    #[cfg(not(windows))]
+    fn write_script(bin_dir: &Path, body: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = bin_dir.join("cargo");
+        fs::write(&path, format!("#!/usr/bin/env sh\n{body}")).expect("write fake cargo");
+        let mut perms = fs::metadata(&path).expect("meta").permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(&path, perms).expect("chmod");
+        path
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    #[serial]
+    fn cargo_publish_streaming_reports_lines_in_order() {
+        let td = tempdir().expect("tempdir");
+        let fake_cargo = write_script(
+            td.path(),
+            "echo '   Packaging demo v0.1.0'\nsleep 0.2\necho '   Verifying demo v0.1.0' >&2\nsleep 0.2\necho 'token: CARGO_REGISTRY_TOKEN=tok123'\nsleep 0.2\necho '   Uploading demo v0.1.0' >&2\nexit 3\n",
+        );
+
+        temp_env::with_var(
+            "SHIPPER_CARGO_BIN",
+            Some(fake_cargo.to_str().expect("utf8")),
+            || {
+                let mut seen = Vec::new();
+                let out =
+                    cargo_publish_streaming(&publish_args(td.path(), "demo"), |kind, line| {
+                        seen.push((kind, line.to_string()))
+                    })
+                    .expect("publish");
+
+                assert_eq!(
+                    seen,
+                    vec![
+                        (StreamKind::Stdout, "   Packaging demo v0.1.0".to_string()),
+                        (StreamKind::Stderr, "   Verifying demo v0.1.0".to_string()),
+                        (
+                            StreamKind::Stdout,
+                            "token: CARGO_REGISTRY_TOKEN=[REDACTED]".to_string()
+                        ),
+                        (StreamKind::Stderr, "   Uploading demo v0.1.0".to_string()),
+                    ]
+                );
+                assert_eq!(out.exit_code, 3);
+                assert!(!out.timed_out);
+                assert!(out.stdout_tail.contains("Packaging demo"));
+                assert!(out.stdout_tail.contains("CARGO_REGISTRY_TOKEN=[REDACTED]"));
+                assert!(!out.stdout_tail.contains("tok123"));
+                assert!(out.stderr_tail.contains("Uploading demo"));
+            },
+        );
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    #[serial]
+    fn cargo_publish_streaming_keeps_only_tail_in_output() {
+        let td = tempdir().expect("tempdir");
+        let fake_cargo = write_script(td.path(), "for i in 1 2 3 4 5; do echo \"line $i\"; done\n");
+
+        temp_env::with_var(
+            "SHIPPER_CARGO_BIN",
+            Some(fake_cargo.to_str().expect("utf8")),
+            || {
+                let mut count = 0;
+                let out = cargo_publish_streaming(
+                    &PublishArgs {
+                        output_lines: 2,
+                        ..publish_args(td.path(), "demo")
+                    },
+                    |_, _| count += 1,
+                )
+                .expect("publish");
+
+                assert_eq!(count, 5);
+                assert_eq!(out.stdout_tail.trim_end(), "line 4\nline 5");
+            },
+        );
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    #[serial]
+    fn cargo_publish_streaming_times_out_with_partial_output() {
+        let td = tempdir().expect("tempdir");
+        let fake_cargo = write_script(td.path(), "echo started\nexec sleep 10\n");
+
+        temp_env::with_var(
+            "SHIPPER_CARGO_BIN",
+            Some(fake_cargo.to_str().expect("utf8")),
+            || {
+                let mut seen = Vec::new();
+                let out = cargo_publish_streaming(
+                    &PublishArgs {
+                        timeout: Some(Duration::from_millis(500)),
+                        ..publish_args(td.path(), "demo")
+                    },
+                    |_, line| seen.push(line.to_string()),
+                )
+                .expect("publish");
+
+                assert_eq!(seen, vec!["started".to_string()]);
+                assert!(out.timed_out);
+                assert_eq!(out.exit_code, -1);
+                assert!(out.stderr_tail.contains("timed out"));
+            },
+        );
+    }
+
+    // ── Feature flags ──
+
+    fn features(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    #[serial]
+    fn cargo_publish_passes_feature_flag_combinations() {
+        let td = tempdir().expect("tempdir");
+        let bin = td.path().join("bin");
+        fs::create_dir_all(&bin).expect("mkdir");
+        let fake_cargo = write_fake_cargo(&bin);
+
+        let args_log = td.path().join("args.txt");
+        let cwd_log = td.path().join("cwd.txt");
+
+        let cases: Vec<(Vec<String>, bool, bool, &str)> = vec![
+            (vec![], false, false, "publish -p demo"),
+            (
+                features(&["demo/serde", " demo/tokio ", "", "other/std"]),
+                false,
+                false,
+                "publish -p demo --features serde,tokio",
+            ),
+            (vec![], true, false, "publish -p demo --no-default-features"),
+            (
+                features(&["demo/std"]),
+                true,
+                false,
+                "publish -p demo --no-default-features --features std",
+            ),
+            (vec![], false, true, "publish -p demo --all-features"),
+            (
+                vec![],
+                true,
+                true,
+                "publish -p demo --all-features --no-default-features",
+            ),
+            (
+                features(&["other/serde"]),
+                false,
+                true,
+                "publish -p demo --all-features",
+            ),
+        ];
+
+        temp_env::with_vars(
+            [
+                (
+                    "SHIPPER_CARGO_BIN",
+                    Some(fake_cargo.to_str().expect("fake cargo utf8")),
+                ),
+                ("SHIPPER_ARGS_LOG", Some(args_log.to_str().expect("utf8"))),
+                ("SHIPPER_CWD_LOG", Some(cwd_log.to_str().expect("utf8"))),
+                ("SHIPPER_EXIT_CODE", Some("0")),
+            ],
+            || {
+                for (feats, no_default, all, expected) in &cases {
+                    cargo_publish(&PublishArgs {
+                        features: feats,
+                        no_default_features: *no_default,
+                        all_features: *all,
+                        ..publish_args(td.path(), "demo")
+                    })
+                    .expect("publish");
+                    let args = fs::read_to_string(&args_log).expect("args");
+                    assert_eq!(args.trim_end(), *expected);
+                }
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn cargo_publish_dry_run_package_passes_feature_flags() {
+        let td = tempdir().expect("tempdir");
+        let bin = td.path().join("bin");
+        fs::create_dir_all(&bin).expect("mkdir");
+        let fake_cargo = write_fake_cargo(&bin);
+
+        let args_log = td.path().join("args.txt");
+        let cwd_log = td.path().join("cwd.txt");
+
+        temp_env::with_vars(
+            [
+                (
+                    "SHIPPER_CARGO_BIN",
+                    Some(fake_cargo.to_str().expect("fake cargo utf8")),
+                ),
+                ("SHIPPER_ARGS_LOG", Some(args_log.to_str().expect("utf8"))),
+                ("SHIPPER_CWD_LOG", Some(cwd_log.to_str().expect("utf8"))),
+                ("SHIPPER_EXIT_CODE", Some("0")),
+            ],
+            || {
+                cargo_publish_dry_run_package(&PublishArgs {
+                    features: &features(&["demo/a", "demo/b"]),
+                    no_default_features: true,
+                    ..publish_args(td.path(), "demo")
+                })
+                .expect("dry-run");
+                let args = fs::read_to_string(&args_log).expect("args");
+                assert_eq!(
+                    args.trim_end(),
+                    "publish -p demo --dry-run --no-default-features --features a,b"
+                );
+
+                cargo_publish_dry_run_package(&PublishArgs {
+                    all_features: true,
+                    ..publish_args(td.path(), "demo")
+                })
+                .expect("dry-run");
+                let args = fs::read_to_string(&args_log).expect("args");
+                assert_eq!(args.trim_end(), "publish -p demo --dry-run --all-features");
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn feature_conflicts_are_rejected_before_running_cargo() {
+        let td = tempdir().expect("tempdir");
+        let missing = td.path().join("does-not-exist-cargo");
+
+        temp_env::with_var(
+            "SHIPPER_CARGO_BIN",
+            Some(missing.to_str().expect("utf8")),
+            || {
+                let err = cargo_publish(&PublishArgs {
+                    features: &features(&["demo/serde"]),
+                    all_features: true,
+                    ..publish_args(td.path(), "demo")
+                })
+                .expect_err("features + all-features must fail");
+                assert!(
+                    format!("{err:#}")
+                        .contains("--all-features cannot be combined with --features (serde)")
+                );
+
+                let err = cargo_publish_dry_run_package(&PublishArgs {
+                    features: &features(&["serde"]),
+                    ..publish_args(td.path(), "demo")
+                })
+                .expect_err("unscoped feature must fail");
+                assert!(
+                    format!("{err:#}").contains("feature `serde` must name its package"),
+                    "{err:#}"
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn blank_feature_names_add_no_features_flag() {
+        let joined = package_features(&features(&["", "  "]), "demo").expect("features");
+        let mut args = Vec::new();
+        push_feature_args(&mut args, &joined, false, false).expect("args");
+        assert!(args.is_empty());
+    }
+
+    // ── crate_already_published / is_already_published ──
+
+    fn dry_run_output(exit_code: i32, stderr: &str) -> CargoOutput {
+        CargoOutput {
+            exit_code,
+            stdout_tail: String::new(),
+            stderr_tail: stderr.to_string(),
+            duration: Duration::from_secs(2),
+            timed_out: false,
+        }
+    }
+
+    #[test]
+    fn crate_already_published_recognizes_api_wording() {
+        // crates.io API rejection as reported by older cargo releases
+        let out = dry_run_output(
+            101,
+            "    Updating crates.io index\n   Packaging demo v0.1.0 (/ws/demo)\n   Uploading demo v0.1.0 (/ws/demo)\nerror: failed to publish to registry at https://crates.io\n\nCaused by:\n  the remote server responded with an error: crate version `0.1.0` is already uploaded",
+        );
+        assert!(crate_already_published(&out));
+    }
+
+    #[test]
+    fn crate_already_published_recognizes_index_wording() {
+        // Newer cargo checks the index before packaging
+        let out = dry_run_output(
+            101,
+            "    Updating crates.io index\nerror: crate demo@0.1.0 already exists on crates.io index",
+        );
+        assert!(crate_already_published(&out));
+
+        let out = dry_run_output(
+            101,
+            "error: crate demo@0.1.0 already exists on my-registry index",
+        );
+        assert!(crate_already_published(&out));
+    }
+
+    #[test]
+    fn crate_already_published_is_case_insensitive_and_scans_stdout() {
+        let out = CargoOutput {
+            stdout_tail: "ERROR: Crate Version `0.1.0` Is Already Uploaded".to_string(),
+            ..dry_run_output(101, "")
+        };
+        assert!(crate_already_published(&out));
+    }
+
+    #[test]
+    fn crate_already_published_false_for_other_failures() {
+        for stderr in [
+            "warning: aborting upload due to dry run",
+            "error: failed to verify package tarball",
+            "error: the remote server responded with an error (status 403 Forbidden)",
+            "error: destination `/tmp/x` already exists",
+        ] {
+            assert!(
+                !crate_already_published(&dry_run_output(101, stderr)),
+                "{stderr}"
+            );
+        }
+    }
+
+    fn registry_answering(
+        status: u16,
+    ) -> (
+        shipper_registry::RegistryClient,
+        std::thread::JoinHandle<()>,
+    ) {
+        let server = tiny_http::Server::http("127.0.0.1:0").expect("server");
+        let api_base = format!("http://{}", server.server_addr());
+        let handle = std::thread::spawn(move || {
+            if let Ok(Some(req)) = server.recv_timeout(Duration::from_secs(10)) {
+                let _ = req.respond(tiny_http::Response::empty(tiny_http::StatusCode(status)));
+            }
+        });
+        let reg = shipper_registry::RegistryClient::new(crate::types::Registry {
+            name: "crates-io".to_string(),
+            api_base,
+            index_base: None,
+        })
+        .expect("client");
+        (reg, handle)
+    }
+
+    #[test]
+    fn is_already_published_trusts_registry_hit() {
+        let (reg, handle) = registry_answering(200);
+        assert!(is_already_published(&reg, "demo", "0.1.0", None).expect("check"));
+        handle.join().expect("join");
+    }
+
+    #[test]
+    fn is_already_published_uses_dry_run_when_registry_misses() {
+        let (reg, handle) = registry_answering(404);
+        let out = dry_run_output(
+            101,
+            "error: crate demo@0.1.0 already exists on crates.io index",
+        );
+        assert!(is_already_published(&reg, "demo", "0.1.0", Some(&out)).expect("check"));
+        handle.join().expect("join");
+    }
+
+    #[test]
+    fn is_already_published_false_when_neither_source_says_so() {
+        let (reg, handle) = registry_answering(404);
+        let out = dry_run_output(0, "warning: aborting upload due to dry run");
+        assert!(!is_already_published(&reg, "demo", "0.1.0", Some(&out)).expect("check"));
+        handle.join().expect("join");
+    }
+
+    #[test]
+    fn is_already_published_falls_back_to_dry_run_on_registry_error() {
+        let (reg, handle) = registry_answering(500);
+        let out = dry_run_output(101, "error: crate version `0.1.0` is already uploaded");
+        assert!(is_already_published(&reg, "demo", "0.1.0", Some(&out)).expect("check"));
+        handle.join().expect("join");
+
+        let (reg, handle) = registry_answering(500);
+        assert!(is_already_published(&reg, "demo", "0.1.0", None).is_err());
+        handle.join().expect("join");
+    }
+
+    // ── target_directory ──
+
+    fn metadata_fixture(workspace_root: &str, target_directory: &str) -> WorkspaceMetadata {
+        let json = serde_json::json!({
+            "packages": [],
+            "workspace_members": [],
+            "workspace_default_members": [],
+            "resolve": null,
+            "workspace_root": workspace_root,
+            "target_directory": target_directory,
+            "version": 1,
+            "metadata": null
+        });
+        let metadata: Metadata = serde_json::from_value(json).expect("metadata fixture");
+        WorkspaceMetadata::from_metadata(metadata)
+    }
+
+    // ── publishable_status ──
+
+    fn package_fixture(name: &str, version: &str, publish: Option<&[&str]>) -> serde_json::Value {
+        serde_json::json!({
+            "name": name,
+            "version": version,
+            "id": format!("path+file:///work/{name}#{version}"),
+            "source": null,
+            "dependencies": [],
+            "targets": [],
+            "features": {},
+            "manifest_path": format!("/work/{name}/Cargo.toml"),
+            "publish": publish,
+            "edition": "2021",
+        })
+    }
+
+    fn publish_metadata_fixture() -> WorkspaceMetadata {
+        let json = serde_json::json!({
+            "packages": [
+                package_fixture("open", "1.0.0", None),
+                package_fixture("private", "1.0.0", Some(&[])),
+                package_fixture("placeholder", "0.0.0", None),
+                package_fixture("internal", "1.0.0", Some(&["my-registry"])),
+                package_fixture("both", "1.0.0", Some(&["crates-io", "my-registry"])),
+            ],
+            "workspace_members": [],
+            "workspace_default_members": [],
+            "resolve": null,
+            "workspace_root": "/work",
+            "target_directory": "/work/target",
+            "version": 1,
+            "metadata": null
+        });
+        let metadata: Metadata = serde_json::from_value(json).expect("metadata fixture");
+        WorkspaceMetadata::from_metadata(metadata)
+    }
+
+    fn dep_fixture(name: &str, kind: Option<&str>) -> serde_json::Value {
+        serde_json::json!({
+            "name": name,
+            "source": null,
+            "req": "^1.0.0",
+            "kind": kind,
+            "rename": null,
+            "optional": false,
+            "uses_default_features": true,
+            "features": [],
+            "target": null,
+            "registry": null,
+            "path": format!("/work/{name}"),
+        })
+    }
+
+    /// `core` dev-depends on `testkit`, which depends on `core`: a cycle
+    /// only through a dev-dependency. `app` build-depends on `codegen`.
+    fn dev_cycle_metadata_fixture() -> WorkspaceMetadata {
+        let mut core = package_fixture("core", "1.0.0", None);
+        core["dependencies"] = serde_json::json!([dep_fixture("testkit", Some("dev"))]);
+        let mut testkit = package_fixture("testkit", "1.0.0", None);
+        testkit["dependencies"] = serde_json::json!([dep_fixture("core", None)]);
+        let mut app = package_fixture("app", "1.0.0", None);
+        app["dependencies"] = serde_json::json!([
+            dep_fixture("codegen", Some("build")),
+            dep_fixture("private", None),
+        ]);
+        let codegen = package_fixture("codegen", "1.0.0", None);
+        let private = package_fixture("private", "1.0.0", Some(&[]));
+
+        let json = serde_json::json!({
+            "packages": [app, core, testkit, codegen, private],
+            "workspace_members": [],
+            "workspace_default_members": [],
+            "resolve": null,
+            "workspace_root": "/work",
+            "target_directory": "/work/target",
+            "version": 1,
+            "metadata": null
+        });
+        let metadata: Metadata = serde_json::from_value(json).expect("metadata fixture");
+        WorkspaceMetadata::from_metadata(metadata)
+    }
+
+    #[test]
+    fn topological_order_ignores_dev_dependency_cycles() {
+        let metadata = dev_cycle_metadata_fixture();
+
+        let order = metadata
+            .topological_order()
+            .expect("dev cycle is not a cycle");
+
+        let pos = |name: &str| order.iter().position(|n| n == name).expect(name);
+        assert_eq!(order.len(), 4);
+        assert!(pos("core") < pos("testkit"));
+        assert!(pos("codegen") < pos("app"));
+        assert!(!order.contains(&"private".to_string()));
+    }
+
+    #[test]
+    fn topological_order_with_dev_kind_reports_the_cycle() {
+        let metadata = dev_cycle_metadata_fixture();
+
+        let err = metadata
+            .topological_order_with(&[DependencyKind::Normal, DependencyKind::Development])
+            .expect_err("dev edges close the cycle");
+        assert!(err.to_string().contains("circular dependency detected"));
+    }
+
+    /// Diamond: `left` and `right` depend on `base`, `top` on both; `cli`
+    /// build-depends on `top`. `bench` only dev-depends on `base` and
+    /// `private` is not publishable.
+    fn diamond_metadata_fixture() -> WorkspaceMetadata {
+        let base = package_fixture("base", "1.0.0", None);
+        let mut left = package_fixture("left", "1.0.0", None);
+        left["dependencies"] = serde_json::json!([dep_fixture("base", None)]);
+        let mut right = package_fixture("right", "1.0.0", None);
+        right["dependencies"] = serde_json::json!([dep_fixture("base", None)]);
+        let mut top = package_fixture("top", "1.0.0", None);
+        top["dependencies"] =
+            serde_json::json!([dep_fixture("left", None), dep_fixture("right", None)]);
+        let mut cli = package_fixture("cli", "1.0.0", None);
+        cli["dependencies"] = serde_json::json!([dep_fixture("top", Some("build"))]);
+        let mut bench = package_fixture("bench", "1.0.0", None);
+        bench["dependencies"] = serde_json::json!([dep_fixture("base", Some("dev"))]);
+        let mut private = package_fixture("private", "1.0.0", Some(&[]));
+        private["dependencies"] = serde_json::json!([dep_fixture("base", None)]);
+
+        let json = serde_json::json!({
+            "packages": [base, left, right, top, cli, bench, private],
+            "workspace_members": [],
+            "workspace_default_members": [],
+            "resolve": null,
+            "workspace_root": "/work",
+            "target_directory": "/work/target",
+            "version": 1,
+            "metadata": null
+        });
+        let metadata: Metadata = serde_json::from_value(json).expect("metadata fixture");
+        WorkspaceMetadata::from_metadata(metadata)
+    }
+
+    #[test]
+    fn dependents_of_returns_the_transitive_dependent_set() {
+        let metadata = diamond_metadata_fixture();
+
+        assert_eq!(
+            metadata.dependents_of("base"),
+            ["cli", "left", "right", "top"]
+        );
+        assert_eq!(metadata.dependents_of("left"), ["cli", "top"]);
+        assert_eq!(metadata.dependents_of("top"), ["cli"]);
+    }
+
+    #[test]
+    fn dependents_of_leaves_and_unknown_crates_is_empty() {
+        let metadata = diamond_metadata_fixture();
+
+        assert!(metadata.dependents_of("cli").is_empty());
+        assert!(metadata.dependents_of("bench").is_empty());
+        assert!(metadata.dependents_of("missing").is_empty());
+    }
+
+    #[test]
+    fn check_internal_version_reqs_flags_only_violations() {
+        let mut app = package_fixture("app", "1.0.0", None);
+        let mut ok_dep = dep_fixture("util", None);
+        ok_dep["req"] = serde_json::json!("^1.2");
+        let mut bad_dep = dep_fixture("proto", None);
+        bad_dep["req"] = serde_json::json!("^1.2");
+        let mut dev_dep = dep_fixture("proto", Some("dev"));
+        dev_dep["req"] = serde_json::json!("^0.9");
+        app["dependencies"] = serde_json::json!([ok_dep, bad_dep, dev_dep]);
+
+        let json = serde_json::json!({
+            "packages": [
+                app,
+                package_fixture("util", "1.4.0", None),
+                package_fixture("proto", "2.0.0", None),
+            ],
+            "workspace_members": [],
+            "workspace_default_members": [],
+            "resolve": null,
+            "workspace_root": "/work",
+            "target_directory": "/work/target",
+            "version": 1,
+            "metadata": null
+        });
+        let metadata: Metadata = serde_json::from_value(json).expect("metadata fixture");
+        let metadata = WorkspaceMetadata::from_metadata(metadata);
+
+        let issues = metadata.check_internal_version_reqs();
+
+        assert_eq!(
+            issues,
+            vec![VersionReqIssue {
+                depender: "app".to_string(),
+                dependee: "proto".to_string(),
+                required: "^1.2".to_string(),
+                actual: "2.0.0".to_string(),
+            }]
+        );
+        assert_eq!(
+            issues[0].to_string(),
+            "app requires proto ^1.2 but the workspace has 2.0.0"
+        );
+    }
+
+    #[test]
+    fn check_internal_version_reqs_empty_when_all_satisfied() {
+        assert!(
+            dev_cycle_metadata_fixture()
+                .check_internal_version_reqs()
+                .is_empty()
+        );
+    }
+
+    // ── filter_packages ──
+
+    fn patterns(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn filter_fixture() -> WorkspaceMetadata {
+        let json = serde_json::json!({
+            "packages": [
+                package_fixture("api", "1.0.0", None),
+                package_fixture("api-internal", "1.0.0", None),
+                package_fixture("db", "1.0.0", None),
+                package_fixture("db-internal", "1.0.0", None),
+                package_fixture("xtask", "1.0.0", None),
+                package_fixture("private", "1.0.0", Some(&[])),
+            ],
+            "workspace_members": [],
+            "workspace_default_members": [],
+            "resolve": null,
+            "workspace_root": "/work",
+            "target_directory": "/work/target",
+            "version": 1,
+            "metadata": null
+        });
+        let metadata: Metadata = serde_json::from_value(json).expect("metadata fixture");
+        WorkspaceMetadata::from_metadata(metadata)
+    }
+
+    fn selected_names(
+        metadata: &WorkspaceMetadata,
+        include: Option<&[&str]>,
+        exclude: &[&str],
+    ) -> Vec<String> {
+        let include = include.map(patterns);
+        metadata
+            .select_packages(include.as_deref(), &patterns(exclude))
+            .expect("select")
+            .iter()
+            .map(|p| p.name.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn filter_packages_include_only() {
+        let metadata = filter_fixture();
+        assert_eq!(
+            selected_names(&metadata, Some(&["api*", "xtask"]), &[]),
+            vec!["api", "api-internal", "xtask"]
+        );
+        // Publishable filter runs first: an include cannot resurrect `private`.
+        assert!(selected_names(&metadata, Some(&["private"]), &[]).is_empty());
+    }
+
+    #[test]
+    fn filter_packages_exclude_only() {
+        let metadata = filter_fixture();
+        assert_eq!(
+            selected_names(&metadata, None, &["*-internal", "xtask"]),
+            vec!["api", "db"]
+        );
+    }
+
+    #[test]
+    fn filter_packages_exclude_wins_over_include() {
+        let metadata = filter_fixture();
+        assert_eq!(
+            selected_names(&metadata, Some(&["db*", "api"]), &["*-internal"]),
+            vec!["api", "db"]
+        );
+        assert!(selected_names(&metadata, Some(&["xtask"]), &["xtask"]).is_empty());
+    }
+
+    #[test]
+    fn filter_packages_works_on_plain_package_slices() {
+        let metadata = filter_fixture();
+        let all: Vec<Package> = metadata.all_packages().into_iter().cloned().collect();
+        let names: Vec<&str> = filter_packages(&all, None, &patterns(&["*-internal"]))
+            .expect("filter")
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["api", "db", "xtask", "private"]);
+    }
+
+    #[test]
+    fn filter_packages_rejects_invalid_pattern() {
+        let err = filter_fixture()
+            .select_packages(None, &patterns(&["api[", "db"]))
+            .expect_err("unclosed class");
+        assert!(err.to_string().contains("invalid package pattern: api["));
+    }
+
+    fn status_of(metadata: &WorkspaceMetadata, name: &str) -> Publishability {
+        metadata.publishable_status(metadata.get_package(name).expect("package"))
+    }
+
+    #[test]
+    fn publishable_status_reports_each_exclusion_reason() {
+        let metadata = publish_metadata_fixture();
+
+        assert_eq!(status_of(&metadata, "open"), Publishability::Publishable);
+        assert_eq!(status_of(&metadata, "both"), Publishability::Publishable);
+        assert_eq!(
+            status_of(&metadata, "private"),
+            Publishability::PublishFalse
+        );
+        assert_eq!(
+            status_of(&metadata, "placeholder"),
+            Publishability::PlaceholderVersion
+        );
+        assert_eq!(
+            status_of(&metadata, "internal"),
+            Publishability::RegistryDisallowed {
+                allowed: vec!["my-registry".to_string()]
+            }
+        );
+        assert_eq!(
+            status_of(&metadata, "internal").to_string(),
+            "publish = [my-registry] (registry not in list)"
+        );
+    }
+
+    #[test]
+    fn publishable_status_honors_target_registry_allowlist() {
+        let metadata = publish_metadata_fixture().with_target_registry("my-registry");
+
+        assert_eq!(
+            status_of(&metadata, "internal"),
+            Publishability::Publishable
+        );
+        assert_eq!(status_of(&metadata, "both"), Publishability::Publishable);
+        assert_eq!(status_of(&metadata, "open"), Publishability::Publishable);
+
+        let publishable: Vec<&str> = metadata
+            .publishable_packages()
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(publishable, vec!["open", "internal", "both"]);
+    }
+
+    #[test]
+    fn is_publishable_matches_publishable_status() {
+        let metadata = publish_metadata_fixture();
+        let publishable: Vec<&str> = metadata
+            .publishable_packages()
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(publishable, vec!["open", "both"]);
+    }
+
+    #[test]
+    fn target_directory_comes_from_metadata() {
+        // e.g. CARGO_TARGET_DIR=/ci/cache/target on a shared runner
+        let metadata = metadata_fixture("/work/repo", "/ci/cache/target");
+
+        assert_eq!(metadata.workspace_root(), Path::new("/work/repo"));
+        assert_eq!(
+            target_directory(&metadata),
+            PathBuf::from("/ci/cache/target")
+        );
+        assert_eq!(
+            packaged_crate_path(&target_directory(&metadata), "demo", "0.1.0"),
+            PathBuf::from("/ci/cache/target/package/demo-0.1.0.crate")
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn target_directory_honors_cargo_target_dir() {
+        let td = tempdir().expect("tempdir");
+        write_bump_fixture(td.path());
+        let custom = td.path().join("custom-target");
+
+        let metadata = temp_env::with_var(
+            "CARGO_TARGET_DIR",
+            Some(custom.to_str().expect("utf8")),
+            || WorkspaceMetadata::load(&td.path().join("Cargo.toml")),
+        )
+        .expect("metadata");
+
+        assert_eq!(target_directory(&metadata), custom);
+    }
+
+    // ── bump_workspace_dep ──
+
+    const BUMP_ROOT_MANIFEST: &str = r#"[workspace]
+members = ["core", "app"]
+resolver = "2"
+
+[workspace.dependencies]
+# shared crates
+demo-core = { version = "0.1.0", path = "core" } # keep in sync
+log = "0.4"
+"#;
+
+    const BUMP_CORE_MANIFEST: &str = r#"[package]
+name = "demo-core"
+version = "0.1.0"
+edition = "2021"
+"#;
+
+    const BUMP_APP_MANIFEST: &str = r#"[package]
+name = "demo-app"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+demo-core = { version = "0.1.0", path = "../core" }
+
+[dev-dependencies]
+demo-core = { version = "0.1.0", path = "../core" }   # tests pin the release
+
+[build-dependencies.demo-core]
+version = "0.1.0"
+path = "../core"
+
+[target.'cfg(unix)'.dependencies]
+demo-core = { path = "../core", version = "=0.1.0" }
+
+[package.metadata.release]
+demo-core = "0.1.0"
+"#;
+
+    fn write_bump_fixture(root: &Path) {
+        for (dir, manifest) in [
+            ("", BUMP_ROOT_MANIFEST),
+            ("core", BUMP_CORE_MANIFEST),
+            ("app", BUMP_APP_MANIFEST),
+        ] {
+            let dir = root.join(dir);
+            fs::create_dir_all(dir.join("src")).expect("mkdir");
+            fs::write(dir.join("Cargo.toml"), manifest).expect("write manifest");
+        }
+        fs::write(root.join("core/src/lib.rs"), "").expect("write lib");
+        fs::write(root.join("app/src/lib.rs"), "").expect("write lib");
+    }
+
+    #[test]
+    fn bump_workspace_dep_edits_dependency_tables_only() {
+        let td = tempdir().expect("tempdir");
+        write_bump_fixture(td.path());
+
+        let changed = bump_workspace_dep(td.path(), "demo-core", "0.2.0").expect("bump");
+
+        assert_eq!(
+            changed,
+            vec![
+                td.path().join("Cargo.toml"),
+                td.path().join("app").join("Cargo.toml"),
+            ]
+        );
+        assert_eq!(
+            fs::read_to_string(td.path().join("Cargo.toml")).expect("read"),
+            r#"[workspace]
+members = ["core", "app"]
+resolver = "2"
+
+[workspace.dependencies]
+# shared crates
+demo-core = { version = "0.2.0", path = "core" } # keep in sync
+log = "0.4"
+"#
+        );
+        assert_eq!(
+            fs::read_to_string(td.path().join("app/Cargo.toml")).expect("read"),
+            r#"[package]
+name = "demo-app"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+demo-core = { version = "0.2.0", path = "../core" }
+
+[dev-dependencies]
+demo-core = { version = "0.2.0", path = "../core" }   # tests pin the release
+
+[build-dependencies.demo-core]
+version = "0.2.0"
+path = "../core"
+
+[target.'cfg(unix)'.dependencies]
+demo-core = { path = "../core", version = "0.2.0" }
+
+[package.metadata.release]
+demo-core = "0.1.0"
+"#
+        );
+        assert_eq!(
+            fs::read_to_string(td.path().join("core/Cargo.toml")).expect("read"),
+            BUMP_CORE_MANIFEST
+        );
+    }
+
+    #[test]
+    fn bump_workspace_dep_is_noop_when_already_current() {
+        let td = tempdir().expect("tempdir");
+        write_bump_fixture(td.path());
+
+        bump_workspace_dep(td.path(), "demo-core", "0.2.0").expect("first bump");
+        let changed = bump_workspace_dep(td.path(), "demo-core", "0.2.0").expect("second bump");
+        assert!(changed.is_empty());
+    }
+
+    #[test]
+    fn bump_workspace_dep_unknown_dep_changes_nothing() {
+        let td = tempdir().expect("tempdir");
+        write_bump_fixture(td.path());
+
+        let changed = bump_workspace_dep(td.path(), "not-a-dep", "9.9.9").expect("bump");
+        assert!(changed.is_empty());
+        assert_eq!(
+            fs::read_to_string(td.path().join("app/Cargo.toml")).expect("read"),
+            BUMP_APP_MANIFEST
+        );
+    }
+
+    #[test]
+    fn bump_workspace_dep_errors_without_manifest() {
+        let td = tempdir().expect("tempdir");
+        assert!(bump_workspace_dep(td.path(), "demo-core", "0.2.0").is_err());
+    }
+
+    #[test]
+    fn bump_dep_in_document_follows_renames_and_skips_inherited() {
+        let mut doc: toml_edit::DocumentMut = r#"[dependencies]
+aliased = { package = "demo-core", version = "0.1" }
+demo-core = { package = "other-core", version = "0.1" }
+path-only = { package = "demo-core", path = "../core" }
+
+[dev-dependencies]
+demo-core = { workspace = true }
+
+[build-dependencies]
+demo-core = "0.1"  # plain requirement
+"#
+        .parse()
+        .expect("parse");
+
+        assert!(bump_dep_in_document(&mut doc, "demo-core", "0.2"));
+        assert_eq!(
+            doc.to_string(),
+            r#"[dependencies]
+aliased = { package = "demo-core", version = "0.2" }
+demo-core = { package = "other-core", version = "0.1" }
+path-only = { package = "demo-core", path = "../core" }
+
+[dev-dependencies]
+demo-core = { workspace = true }
+
+[build-dependencies]
+demo-core = "0.2"  # plain requirement
+"#
+        );
+    }
+
+    // ── cargo_package ──
+
+    #[test]
+    #[serial]
+    fn cargo_package_passes_flags_and_captures_output() {
+        let td = tempdir().expect("tempdir");
+        let bin = td.path().join("bin");
+        fs::create_dir_all(&bin).expect("mkdir");
+        let fake_cargo = write_fake_cargo(&bin);
+
+        let args_log = td.path().join("args.txt");
+        let cwd_log = td.path().join("cwd.txt");
+
+        let ws = td.path().join("workspace");
+        fs::create_dir_all(&ws).expect("mkdir ws");
+
+        temp_env::with_vars(
+            [
+                (
+                    "SHIPPER_CARGO_BIN",
+                    Some(fake_cargo.to_str().expect("fake cargo utf8")),
+                ),
+                ("SHIPPER_ARGS_LOG", Some(args_log.to_str().expect("utf8"))),
+                ("SHIPPER_CWD_LOG", Some(cwd_log.to_str().expect("utf8"))),
+                ("SHIPPER_EXIT_CODE", Some("0")),
+            ],
+            || {
+                let out = cargo_package(&ws, "my-crate", true, 50, None).expect("package");
+
+                assert_eq!(out.exit_code, 0);
+                assert!(!out.timed_out);
+                assert!(out.stdout_tail.contains("fake-stdout"));
+                assert!(out.stderr_tail.contains("fake-stderr"));
+
                 let args = fs::read_to_string(&args_log).expect("args");
+                assert!(args.contains("package -p my-crate"));
+                assert!(args.contains("--allow-dirty"));
+                assert!(!args.contains("publish"));
                 assert!(!args.contains("--registry"));
-                assert!(!args.contains("--allow-dirty"));
-            },
-        );
-    }
-
-    #[test]
-    #[serial]
-    fn cargo_publish_dry_run_workspace_errors_when_command_missing() {
-        let td = tempdir().expect("tempdir");
-        let missing = td.path().join("nonexistent-cargo");
 
-        temp_env::with_var(
-            "SHIPPER_CARGO_BIN",
-            Some(missing.to_str().expect("utf8")),
-            || {
-                let err = cargo_publish_dry_run_workspace(td.path(), "crates-io", false, 50)
-                    .expect_err("must fail");
-                assert!(format!("{err:#}").contains("failed to execute cargo publish"));
+                let cwd = fs::read_to_string(&cwd_log).expect("cwd");
+                assert!(cwd.trim_end().ends_with("workspace"));
             },
         );
     }
 
-    // ── Dry-run package variant additional tests ──
-
     #[test]
     #[serial]
-    fn cargo_publish_dry_run_package_omits_registry_for_crates_io() {
+    fn cargo_package_omits_allow_dirty_by_default() {
         let td = tempdir().expect("tempdir");
         let bin = td.path().join("bin");
         fs::create_dir_all(&bin).expect("mkdir");
@@ -1398,184 +3696,380 @@ mod tests {
         let args_log = td.path().join("args.txt");
         let cwd_log = td.path().join("cwd.txt");
 
-        let ws = td.path().join("workspace");
-        fs::create_dir_all(&ws).expect("mkdir ws");
-
         temp_env::with_vars(
             [
                 (
                     "SHIPPER_CARGO_BIN",
-                    Some(fake_cargo.to_str().expect("utf8")),
+                    Some(fake_cargo.to_str().expect("fake cargo utf8")),
                 ),
                 ("SHIPPER_ARGS_LOG", Some(args_log.to_str().expect("utf8"))),
                 ("SHIPPER_CWD_LOG", Some(cwd_log.to_str().expect("utf8"))),
                 ("SHIPPER_EXIT_CODE", Some("0")),
             ],
             || {
-                let _ = cargo_publish_dry_run_package(&ws, "pkg", "crates-io", false, 50)
-                    .expect("dry-run");
+                let _ = cargo_package(td.path(), "my-crate", false, 50, None).expect("package");
                 let args = fs::read_to_string(&args_log).expect("args");
-                assert!(!args.contains("--registry"));
                 assert!(!args.contains("--allow-dirty"));
             },
         );
     }
 
+    #[cfg(not(windows))]
     #[test]
     #[serial]
-    fn cargo_publish_dry_run_package_errors_when_command_missing() {
+    fn cargo_package_reports_timeout() {
         let td = tempdir().expect("tempdir");
-        let missing = td.path().join("nonexistent-cargo");
+        let fake_cargo = write_script(td.path(), "exec sleep 10\n");
+
+        temp_env::with_var(
+            "SHIPPER_CARGO_BIN",
+            Some(fake_cargo.to_str().expect("utf8")),
+            || {
+                let out = cargo_package(
+                    td.path(),
+                    "my-crate",
+                    false,
+                    50,
+                    Some(Duration::from_secs(1)),
+                )
+                .expect("package with timeout");
+
+                assert!(out.timed_out, "expected timed_out flag to be set");
+                assert_eq!(out.exit_code, -1);
+                assert!(out.stderr_tail.contains("timed out"));
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn cargo_package_errors_when_command_missing() {
+        let td = tempdir().expect("tempdir");
+        let missing = td.path().join("does-not-exist-cargo");
 
         temp_env::with_var(
             "SHIPPER_CARGO_BIN",
             Some(missing.to_str().expect("utf8")),
             || {
-                let err = cargo_publish_dry_run_package(td.path(), "pkg", "crates-io", false, 50)
-                    .expect_err("must fail");
-                let msg = format!("{err:#}");
-                assert!(msg.contains("failed to execute cargo publish --dry-run -p pkg"));
+                let err = cargo_package(td.path(), "x", false, 50, None).expect_err("must fail");
+                assert!(format!("{err:#}").contains("failed to execute cargo package -p x"));
             },
         );
     }
 
-    // ── Output line truncation via tail_lines ──
+    #[test]
+    fn packaged_crate_path_follows_cargo_layout() {
+        let path = packaged_crate_path(Path::new("target"), "my-crate", "1.2.3-beta.1");
+        assert_eq!(
+            path,
+            Path::new("target")
+                .join("package")
+                .join("my-crate-1.2.3-beta.1.crate")
+        );
+    }
 
     #[test]
-    fn tail_lines_truncates_to_requested_count() {
-        let lines: Vec<String> = (0..100).map(|i| format!("line {i}")).collect();
-        let input = lines.join("\n");
-        let out = tail_lines(&input, 5);
-        assert_eq!(out.lines().count(), 5);
-        assert!(out.contains("line 95"));
-        assert!(out.contains("line 99"));
-        assert!(!out.contains("line 94"));
+    fn hash_crate_file_is_sha256_of_tarball_bytes() {
+        let td = tempdir().expect("tempdir");
+        let path = td.path().join("demo-0.1.0.crate");
+        std::fs::write(&path, b"abc").expect("write");
+
+        let first = hash_crate_file(&path).expect("hash");
+        assert_eq!(
+            first,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(hash_crate_file(&path).expect("hash again"), first);
     }
 
     #[test]
-    fn tail_lines_one_line_requested() {
-        let input = "first\nsecond\nthird";
-        let out = tail_lines(input, 1);
-        assert_eq!(out, "third");
+    fn hash_crate_file_errors_on_missing_tarball() {
+        let td = tempdir().expect("tempdir");
+        let err = hash_crate_file(&td.path().join("missing.crate")).expect_err("missing");
+        assert!(err.to_string().contains("failed to read crate tarball"));
     }
 
     #[test]
-    fn tail_lines_redacts_token_in_last_line() {
-        let input = "safe1\nsafe2\nCARGO_REGISTRY_TOKEN=leaked";
-        let out = tail_lines(input, 2);
-        assert!(!out.contains("leaked"));
-        assert!(out.contains("CARGO_REGISTRY_TOKEN=[REDACTED]"));
+    fn check_crate_size_flags_tarballs_over_the_limit() {
+        let td = tempdir().expect("tempdir");
+        let tarball = packaged_crate_path(&td.path().join("target"), "demo", "0.1.0");
+        std::fs::create_dir_all(tarball.parent().unwrap()).expect("mkdir");
+        std::fs::write(&tarball, vec![0u8; 1024]).expect("write");
+
+        assert_eq!(
+            check_crate_size(&tarball, 2048).expect("under"),
+            SizeCheck {
+                size_bytes: 1024,
+                max_bytes: 2048,
+                exceeds_limit: false,
+            }
+        );
+        assert!(
+            !check_crate_size(&tarball, 1024)
+                .expect("at limit")
+                .exceeds_limit
+        );
+        let over = check_crate_size(&tarball, 512).expect("over");
+        assert!(over.exceeds_limit);
+        assert_eq!(over.size_bytes, 1024);
     }
 
     #[test]
-    fn tail_lines_token_outside_window_not_visible() {
-        let input = "CARGO_REGISTRY_TOKEN=secret\nsafe1\nsafe2";
-        let out = tail_lines(input, 2);
-        assert!(!out.contains("secret"));
-        assert!(!out.contains("CARGO_REGISTRY_TOKEN"));
-        assert_eq!(out, "safe1\nsafe2");
+    fn check_crate_size_errors_on_missing_tarball() {
+        let td = tempdir().expect("tempdir");
+        let err = check_crate_size(&td.path().join("missing.crate"), CRATES_IO_MAX_CRATE_BYTES)
+            .expect_err("missing");
+        assert!(err.to_string().contains("failed to read crate tarball"));
+        assert_eq!(CRATES_IO_MAX_CRATE_BYTES, 10_485_760);
     }
 
-    // ── Error message patterns ──
+    #[test]
+    fn published_artifact_sha256_reads_target_dir() {
+        let td = tempdir().expect("tempdir");
+        let tarball = packaged_crate_path(td.path(), "demo", "0.1.0");
+        std::fs::create_dir_all(tarball.parent().unwrap()).expect("mkdir");
+        std::fs::write(&tarball, b"abc").expect("write");
+
+        assert_eq!(
+            published_artifact_sha256(td.path(), "demo", "0.1.0"),
+            Some(hash_crate_file(&tarball).expect("hash"))
+        );
+        assert_eq!(published_artifact_sha256(td.path(), "demo", "0.2.0"), None);
+    }
+
+    // ── classify_cargo_output ──
+
+    fn failed_output(stderr: &str) -> CargoOutput {
+        CargoOutput {
+            exit_code: 101,
+            stdout_tail: String::new(),
+            stderr_tail: stderr.to_string(),
+            duration: Duration::from_secs(1),
+            timed_out: false,
+        }
+    }
 
     #[test]
-    fn redact_token_in_error_message_context() {
-        let input =
-            "error: failed to publish: token = \"cio_leakedsecret\" was rejected by registry";
-        let out = redact_sensitive(input);
-        assert!(!out.contains("cio_leakedsecret"));
-        assert!(out.contains("[REDACTED]"));
+    fn classify_cargo_output_already_exists_is_permanent() {
+        let out = failed_output("error: crate demo@0.1.0 already exists on crates.io index");
+        assert_eq!(classify_cargo_output(&out), ErrorClass::Permanent);
+        let out = failed_output("error: crate version `0.1.0` is already uploaded");
+        assert_eq!(classify_cargo_output(&out), ErrorClass::Permanent);
     }
 
     #[test]
-    fn redact_bearer_in_http_error() {
-        let input =
-            "error: HTTP 403 Forbidden\nAuthorization: Bearer expired_tok_abc\nBody: access denied";
-        let out = redact_sensitive(input);
-        assert!(!out.contains("expired_tok_abc"));
-        assert!(out.contains("error: HTTP 403 Forbidden"));
-        assert!(out.contains("Body: access denied"));
+    fn classify_cargo_output_auth_failures_are_permanent() {
+        for stderr in [
+            "error: failed to publish to registry at https://crates.io\n\nCaused by:\n  the remote server responded with an error (status 403 Forbidden): must be logged in",
+            "error: the remote server responded with an error (status 403): access denied",
+            "error: authentication required for registry `private`",
+        ] {
+            assert_eq!(
+                classify_cargo_output(&failed_output(stderr)),
+                ErrorClass::Permanent,
+                "{stderr}"
+            );
+        }
     }
 
     #[test]
-    fn redact_registry_token_in_debug_output() {
-        let input = "debug: env CARGO_REGISTRY_TOKEN=cio_debug_tok resolved from environment";
-        let out = redact_sensitive(input);
-        assert!(!out.contains("cio_debug_tok"));
-        assert!(out.contains("[REDACTED]"));
+    fn classify_cargo_output_throttling_and_network_are_retryable() {
+        for stderr in [
+            "error: the remote server responded with an error (status 429 Too Many Requests): You have published too many new crates",
+            "error: rate limit exceeded, try again later",
+            "warning: spurious network error (3 tries remaining): [7] Couldn't connect to server",
+        ] {
+            assert_eq!(
+                classify_cargo_output(&failed_output(stderr)),
+                ErrorClass::Retryable,
+                "{stderr}"
+            );
+        }
     }
 
-    // ── CargoOutput struct behavior ──
+    #[test]
+    fn classify_cargo_output_retryable_wins_over_permanent() {
+        let out = failed_output(
+            "warning: spurious network error (2 tries remaining)\nerror: status 403 Forbidden",
+        );
+        assert_eq!(classify_cargo_output(&out), ErrorClass::Retryable);
+    }
 
     #[test]
-    fn cargo_output_default_fields() {
+    fn classify_cargo_output_timeout_is_retryable() {
         let out = CargoOutput {
-            exit_code: 0,
+            exit_code: -1,
             stdout_tail: String::new(),
             stderr_tail: String::new(),
-            duration: Duration::from_secs(0),
-            timed_out: false,
+            duration: Duration::from_mins(1),
+            timed_out: true,
         };
-        assert_eq!(out.exit_code, 0);
-        assert!(out.stdout_tail.is_empty());
-        assert!(out.stderr_tail.is_empty());
-        assert!(!out.timed_out);
+        assert_eq!(classify_cargo_output(&out), ErrorClass::Retryable);
     }
 
+    // ── parse_cargo_publish_result ──
+
     #[test]
-    fn cargo_output_clone_is_independent() {
+    fn parse_cargo_publish_result_success() {
         let out = CargoOutput {
-            exit_code: 42,
-            stdout_tail: "hello".to_string(),
-            stderr_tail: "world".to_string(),
-            duration: Duration::from_millis(500),
-            timed_out: true,
+            exit_code: 0,
+            ..failed_output("   Uploading demo v0.1.0 (/ws/demo)")
         };
-        let cloned = out.clone();
-        assert_eq!(cloned.exit_code, out.exit_code);
-        assert_eq!(cloned.stdout_tail, out.stdout_tail);
-        assert_eq!(cloned.stderr_tail, out.stderr_tail);
-        assert_eq!(cloned.timed_out, out.timed_out);
+        assert_eq!(
+            parse_cargo_publish_result(&out),
+            CargoPublishResult::Success
+        );
     }
 
     #[test]
-    fn cargo_output_debug_format() {
+    fn parse_cargo_publish_result_already_published() {
+        for stderr in [
+            "error: crate demo@0.1.0 already exists on crates.io index",
+            "Caused by:\n  the remote server responded with an error: crate version `0.1.0` is already uploaded",
+        ] {
+            assert_eq!(
+                parse_cargo_publish_result(&failed_output(stderr)),
+                CargoPublishResult::AlreadyPublished,
+                "{stderr}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_cargo_publish_result_rate_limited() {
+        for stderr in [
+            "error: the remote server responded with an error (status 429 Too Many Requests): You have published too many new crates",
+            "error: rate limit exceeded, try again later",
+            // Multi-byte characters right next to the status code
+            "error: le serveur a répondu «429»",
+        ] {
+            assert_eq!(
+                parse_cargo_publish_result(&failed_output(stderr)),
+                CargoPublishResult::RateLimited,
+                "{stderr}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_cargo_publish_result_auth_failed() {
+        for stderr in [
+            "error: the remote server responded with an error (status 403 Forbidden): must be logged in",
+            "error: no token found, please run `cargo login`",
+            "error: the remote server responded with an error (status 401): unauthorized",
+        ] {
+            assert_eq!(
+                parse_cargo_publish_result(&failed_output(stderr)),
+                CargoPublishResult::AuthFailed,
+                "{stderr}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_cargo_publish_result_verify_failed_keeps_first_error() {
+        let out = failed_output(
+            "   Verifying demo v0.1.0 (/ws/demo)\n   Compiling demo v0.1.0\nerror[E0425]: cannot find value `x` in this scope\nerror: could not compile `demo` (lib) due to 1 previous error\nerror: failed to verify package tarball",
+        );
+        assert_eq!(
+            parse_cargo_publish_result(&out),
+            CargoPublishResult::VerifyFailed {
+                first_error: "error[E0425]: cannot find value `x` in this scope".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn parse_cargo_publish_result_network() {
+        for stderr in [
+            "warning: spurious network error (3 tries remaining): [7] Couldn't connect to server",
+            "error: the remote server responded with an error (status 503 Service Unavailable)",
+            // Transient wins over auth, as in `classify_cargo_output`.
+            "warning: spurious network error (2 tries remaining)\nerror: status 403 Forbidden",
+        ] {
+            assert_eq!(
+                parse_cargo_publish_result(&failed_output(stderr)),
+                CargoPublishResult::Network,
+                "{stderr}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_cargo_publish_result_timed_out() {
         let out = CargoOutput {
-            exit_code: 1,
-            stdout_tail: "out".to_string(),
-            stderr_tail: "err".to_string(),
-            duration: Duration::from_secs(1),
-            timed_out: false,
+            exit_code: -1,
+            timed_out: true,
+            ..failed_output("   Uploading demo v0.1.0\ncargo timed out after 30s")
         };
-        let debug = format!("{out:?}");
-        assert!(debug.contains("CargoOutput"));
-        assert!(debug.contains("exit_code: 1"));
+        assert_eq!(
+            parse_cargo_publish_result(&out),
+            CargoPublishResult::TimedOut
+        );
+
+        // A timeout after cargo already reported the version is not a retry.
+        let out = CargoOutput {
+            timed_out: true,
+            ..failed_output("error: crate demo@0.1.0 already exists on crates.io index")
+        };
+        assert_eq!(
+            parse_cargo_publish_result(&out),
+            CargoPublishResult::AlreadyPublished
+        );
     }
 
-    // ── Redaction idempotency ──
+    #[test]
+    fn parse_cargo_publish_result_unknown_keeps_tail() {
+        let out = failed_output("error: something unexpected happened");
+        assert_eq!(
+            parse_cargo_publish_result(&out),
+            CargoPublishResult::Unknown {
+                tail: "error: something unexpected happened".to_string()
+            }
+        );
+        // Manifest problems have no variant of their own but still classify
+        // as permanent.
+        let out = failed_output("error: failed to parse manifest at `/ws/demo/Cargo.toml`");
+        assert!(matches!(
+            parse_cargo_publish_result(&out),
+            CargoPublishResult::Unknown { .. }
+        ));
+        assert_eq!(classify_cargo_output(&out), ErrorClass::Permanent);
+    }
 
     #[test]
-    fn redact_is_idempotent_bearer() {
-        let input = "Authorization: Bearer secret_value";
-        let once = redact_sensitive(input);
-        let twice = redact_sensitive(&once);
-        assert_eq!(once, twice);
+    fn error_class_timed_out_is_retryable_despite_permanent_output() {
+        let out = CargoOutput {
+            exit_code: -1,
+            timed_out: true,
+            ..failed_output(
+                "error: failed to parse manifest at `/ws/demo/Cargo.toml`\ncargo timed out after 30s",
+            )
+        };
+        assert_eq!(out.error_class(), ErrorClass::Retryable);
     }
 
     #[test]
-    fn redact_is_idempotent_env_token() {
-        let input = "CARGO_REGISTRY_TOKEN=secret";
-        let once = redact_sensitive(input);
-        let twice = redact_sensitive(&once);
-        assert_eq!(once, twice);
+    fn error_class_clean_failure_uses_stderr_classifier() {
+        let out = failed_output("error: crate demo@0.1.0 already exists on crates.io index");
+        assert_eq!(out.error_class(), ErrorClass::Permanent);
+        let out = failed_output("error: something unexpected happened");
+        assert_eq!(out.error_class(), ErrorClass::Ambiguous);
     }
 
     #[test]
-    fn redact_is_idempotent_token_assignment() {
-        let input = r#"token = "secret_value""#;
-        let once = redact_sensitive(input);
-        let twice = redact_sensitive(&once);
-        assert_eq!(once, twice);
+    fn classify_cargo_output_scans_stdout_tail() {
+        let out = CargoOutput {
+            stdout_tail: "crate version `demo@0.1.0` is already uploaded".to_string(),
+            ..failed_output("")
+        };
+        assert_eq!(classify_cargo_output(&out), ErrorClass::Permanent);
+    }
+
+    #[test]
+    fn classify_cargo_output_unrecognized_is_ambiguous() {
+        let out = failed_output("error: something unexpected happened");
+        assert_eq!(classify_cargo_output(&out), ErrorClass::Ambiguous);
     }
 
     // ── Non-default exit codes ──
@@ -1605,8 +4099,7 @@ mod tests {
                 ("SHIPPER_EXIT_CODE", Some("101")),
             ],
             || {
-                let out = cargo_publish(&ws, "crate-a", "crates-io", false, false, 50, None)
-                    .expect("publish");
+                let out = cargo_publish(&publish_args(&ws, "crate-a")).expect("publish");
                 assert_eq!(out.exit_code, 101);
                 assert!(!out.timed_out);
             },
@@ -2168,4 +4661,54 @@ mod tests {
             assert_yaml_snapshot!(info);
         }
     }
+
+    #[test]
+    #[serial]
+    fn resolve_registry_token_prefers_registry_specific_env_for_alt_registries() {
+        let cargo_home = tempdir().expect("tempdir");
+        temp_env::with_vars(
+            [
+                (
+                    "CARGO_HOME",
+                    Some(cargo_home.path().to_str().expect("utf8")),
+                ),
+                ("CARGO_REGISTRY_TOKEN", Some("default-token")),
+                ("CARGO_REGISTRIES_MY_REG_TOKEN", Some("alt-token")),
+                ("CARGO_REGISTRIES_CRATES_IO_TOKEN", None),
+            ],
+            || {
+                assert_eq!(
+                    resolve_registry_token("my-reg").as_deref(),
+                    Some("alt-token")
+                );
+                assert_eq!(
+                    resolve_registry_token("crates-io").as_deref(),
+                    Some("default-token")
+                );
+                assert_eq!(resolve_registry_token("other-reg"), None);
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn resolve_registry_token_falls_back_to_per_registry_env_for_crates_io() {
+        let cargo_home = tempdir().expect("tempdir");
+        temp_env::with_vars(
+            [
+                (
+                    "CARGO_HOME",
+                    Some(cargo_home.path().to_str().expect("utf8")),
+                ),
+                ("CARGO_REGISTRY_TOKEN", None),
+                ("CARGO_REGISTRIES_CRATES_IO_TOKEN", Some("  io-token  ")),
+            ],
+            || {
+                assert_eq!(
+                    resolve_registry_token("crates-io").as_deref(),
+                    Some("io-token")
+                );
+            },
+        );
+    }
 }
@@ -0,0 +1,132 @@
+//! Command execution that reports output line-by-line as it arrives.
+
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+
+use super::types::{CommandOutput, StreamKind};
+
+/// Run a command, invoking `on_line` for every stdout/stderr line as soon as
+/// the child writes it.
+///
+/// Each pipe is drained by its own reader thread, so a chatty stderr can't
+/// block stdout (or vice versa). Lines are passed without their trailing
+/// newline; the full output is still accumulated and returned in
+/// [`CommandOutput`]. Timeout semantics match
+/// [`run_command_with_timeout`](super::run_command_with_timeout).
+pub(crate) fn run_command_with_lines(
+    program: &str,
+    args: &[&str],
+    working_dir: &std::path::Path,
+    timeout: Option<Duration>,
+    on_line: &mut dyn FnMut(StreamKind, &str),
+) -> Result<CommandOutput> {
+    let start = Instant::now();
+
+    let mut child = Command::new(program)
+        .args(args)
+        .current_dir(working_dir)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("failed to spawn command: {}", program))?;
+
+    let (tx, rx) = mpsc::channel();
+    if let Some(stdout) = child.stdout.take() {
+        spawn_reader(stdout, StreamKind::Stdout, tx.clone());
+    }
+    if let Some(stderr) = child.stderr.take() {
+        spawn_reader(stderr, StreamKind::Stderr, tx.clone());
+    }
+    drop(tx);
+
+    let deadline = timeout.map(|t| Instant::now() + t);
+    let mut stdout = String::new();
+    let mut stderr = String::new();
+    let mut record = |kind: StreamKind, line: String| {
+        on_line(kind, line.trim_end_matches(['\n', '\r']));
+        match kind {
+            StreamKind::Stdout => stdout.push_str(&line),
+            StreamKind::Stderr => stderr.push_str(&line),
+        }
+    };
+
+    loop {
+        let received = match deadline {
+            Some(deadline) => {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    break;
+                }
+                rx.recv_timeout(remaining)
+            }
+            None => rx.recv().map_err(|_| mpsc::RecvTimeoutError::Disconnected),
+        };
+        match received {
+            Ok((kind, line)) => record(kind, line),
+            // Both pipes closed: the child has exited (or closed its output).
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                let status = child
+                    .wait()
+                    .with_context(|| format!("failed to wait for command: {}", program))?;
+                return Ok(CommandOutput {
+                    exit_code: status.code().unwrap_or(-1),
+                    stdout,
+                    stderr,
+                    timed_out: false,
+                    duration: start.elapsed(),
+                });
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => break,
+        }
+    }
+
+    let _ = child.kill();
+    let _ = child.wait();
+    // Keep whatever was already written; grandchildren may hold the pipes
+    // open, so don't wait for the readers to finish.
+    while let Ok((kind, line)) = rx.try_recv() {
+        record(kind, line);
+    }
+
+    let timeout_dur = timeout.unwrap_or_default();
+    stderr.push_str(&format!(
+        "\n{} timed out after {}",
+        program,
+        humantime::format_duration(timeout_dur)
+    ));
+
+    Ok(CommandOutput {
+        exit_code: -1,
+        stdout,
+        stderr,
+        timed_out: true,
+        duration: start.elapsed(),
+    })
+}
+
+fn spawn_reader<R: Read + Send + 'static>(
+    stream: R,
+    kind: StreamKind,
+    tx: mpsc::Sender<(StreamKind, String)>,
+) {
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(stream);
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            match reader.read_until(b'\n', &mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    let line = String::from_utf8_lossy(&buf).into_owned();
+                    if tx.send((kind, line)).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+}
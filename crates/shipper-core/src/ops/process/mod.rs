@@ -8,6 +8,7 @@
 //! decrating effort. See `docs/decrating-plan.md` §6.
 
 mod cargo;
+mod lines;
 mod run;
 mod timeout;
 mod types;
@@ -19,6 +20,7 @@ mod which;
 // `cross_platform_edge_case_tests.rs`).
 #[allow(unused_imports)]
 pub(crate) use self::cargo::{cargo_dry_run, cargo_publish, run_cargo, run_cargo_in_dir};
+pub(crate) use self::lines::run_command_with_lines;
 #[allow(unused_imports)]
 pub(crate) use self::run::{
     run_command, run_command_in_dir, run_command_simple, run_command_streaming,
@@ -26,6 +28,7 @@ pub(crate) use self::run::{
 };
 #[allow(unused_imports)]
 pub(crate) use self::timeout::run_command_with_timeout;
+pub use self::types::StreamKind;
 #[allow(unused_imports)]
 pub(crate) use self::types::{CommandOutput, CommandResult};
 #[allow(unused_imports)]
@@ -431,6 +431,59 @@ fn run_command_with_timeout_captures_stderr() {
     );
 }
 
+// run_command_with_lines tests
+
+#[test]
+fn run_command_with_lines_reports_each_line_and_accumulates_output() {
+    let tmp = tempfile::tempdir().expect("tmpdir");
+    let mut seen = Vec::new();
+    let r = run_command_with_lines(
+        "cargo",
+        &["--version"],
+        tmp.path(),
+        Some(Duration::from_secs(30)),
+        &mut |kind, line| seen.push((kind, line.to_string())),
+    )
+    .expect("run");
+    assert!(!r.timed_out);
+    assert_eq!(r.exit_code, 0);
+    assert_eq!(seen.len(), 1);
+    assert_eq!(seen[0].0, StreamKind::Stdout);
+    assert!(seen[0].1.starts_with("cargo "));
+    assert!(!seen[0].1.ends_with('\n'));
+    assert_eq!(r.stdout.trim_end(), seen[0].1);
+}
+
+#[test]
+fn run_command_with_lines_tags_stderr() {
+    let tmp = tempfile::tempdir().expect("tmpdir");
+    let mut kinds = Vec::new();
+    let r = run_command_with_lines(
+        "cargo",
+        &["--nonexistent-flag-xyz"],
+        tmp.path(),
+        None,
+        &mut |kind, _| kinds.push(kind),
+    )
+    .expect("run");
+    assert_ne!(r.exit_code, 0);
+    assert!(kinds.contains(&StreamKind::Stderr));
+    assert!(!r.stderr.is_empty());
+}
+
+#[test]
+fn run_command_with_lines_nonexistent_program() {
+    let tmp = tempfile::tempdir().expect("tmpdir");
+    let err = run_command_with_lines(
+        "bogus-not-a-command-123",
+        &[],
+        tmp.path(),
+        None,
+        &mut |_, _| {},
+    );
+    assert!(err.is_err());
+}
+
 // CommandOutput tests
 
 #[test]
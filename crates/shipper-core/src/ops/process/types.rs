@@ -66,3 +66,12 @@ pub(crate) struct CommandOutput {
     #[allow(dead_code)]
     pub(crate) duration: Duration,
 }
+
+/// Which output stream a line came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamKind {
+    /// Child process stdout.
+    Stdout,
+    /// Child process stderr.
+    Stderr,
+}
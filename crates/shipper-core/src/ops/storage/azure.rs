@@ -0,0 +1,694 @@
+//! Azure Blob Storage backend.
+//!
+//! `bucket` names the container. Requests are authenticated with Shared Key
+//! (account name + key from `access_key_id` / `secret_access_key`) or with a
+//! SAS token carried in `session_token`. A custom `endpoint` (for example
+//! Azurite's `http://127.0.0.1:10000/devstoreaccount1`) replaces the default
+//! `https://<account>.blob.core.windows.net`.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD;
+use chrono::{DateTime, Utc};
+use reqwest::blocking::{Client, Response};
+use reqwest::{Method, StatusCode};
+
+use super::credentials::{Credentials, resolve_credentials};
+use super::rest::{expect_success, hmac_sha256, uri_encode, xml_elements};
+use super::{CloudStorageConfig, ListPage, StorageBackend, StorageType, list_all_pages};
+
+/// Blob service REST API version sent as `x-ms-version`.
+const API_VERSION: &str = "2021-08-06";
+
+/// Storage backend for an Azure Blob Storage container.
+pub(crate) struct AzureBlobStorage {
+    container: String,
+    base_path: String,
+    endpoint: String,
+    auth: AzureAuth,
+    http: Client,
+}
+
+enum AzureAuth {
+    SharedKey {
+        account: String,
+        key: Vec<u8>,
+    },
+    /// SAS query string without the leading `?`.
+    Sas(String),
+}
+
+impl AzureBlobStorage {
+    /// Build a backend from `config`.
+    ///
+    /// Credentials come from [`resolve_credentials`]; Shared Key credentials
+    /// win over a SAS token when both are configured.
+    pub(crate) fn new(config: &CloudStorageConfig) -> Result<Self> {
+        let (auth, account) = match resolve_credentials(config)?.credentials {
+            Credentials::AccessKey {
+                access_key_id,
+                secret_access_key,
+                ..
+            } => (
+                AzureAuth::SharedKey {
+                    account: access_key_id.clone(),
+                    key: STANDARD
+                        .decode(secret_access_key.trim())
+                        .context("Azure account key is not valid base64")?,
+                },
+                Some(access_key_id),
+            ),
+            Credentials::SasToken { account, token } => (AzureAuth::Sas(token), account),
+            Credentials::None
+            | Credentials::ServiceAccountFile(_)
+            | Credentials::AuthorizedUserFile(_) => {
+                bail!("Azure storage needs an account key or SAS token")
+            }
+        };
+
+        let endpoint = match (&config.endpoint, &account) {
+            (Some(endpoint), _) => endpoint.trim_end_matches('/').to_string(),
+            (None, Some(account)) => format!("https://{account}.blob.core.windows.net"),
+            (None, None) => {
+                bail!("Azure storage needs an endpoint or the storage account name")
+            }
+        };
+
+        let http = Client::builder()
+            .user_agent(concat!("shipper/", env!("CARGO_PKG_VERSION")))
+            .timeout(Duration::from_mins(1))
+            .build()
+            .context("failed to build Azure HTTP client")?;
+
+        Ok(Self {
+            container: config.bucket.clone(),
+            base_path: config.base_path.trim_matches('/').to_string(),
+            endpoint,
+            auth,
+            http,
+        })
+    }
+
+    /// Blob name for a path relative to `base_path`.
+    fn blob_name(&self, path: &str) -> String {
+        let path = path.trim_start_matches('/');
+        if self.base_path.is_empty() {
+            path.to_string()
+        } else {
+            format!("{}/{}", self.base_path, path)
+        }
+    }
+
+    /// Strip `base_path/` from a blob name returned by a listing.
+    fn relative_name(&self, name: &str) -> Option<String> {
+        if self.base_path.is_empty() {
+            return Some(name.to_string());
+        }
+        name.strip_prefix(&self.base_path)
+            .and_then(|rest| rest.strip_prefix('/'))
+            .map(str::to_string)
+    }
+
+    fn blob_path(&self, name: &str) -> String {
+        format!(
+            "/{}/{}",
+            uri_encode(&self.container, false),
+            uri_encode(name, true)
+        )
+    }
+
+    /// Send an authenticated request to `<endpoint><resource>?<query>`.
+    fn send(
+        &self,
+        method: Method,
+        resource: &str,
+        query: &[(&str, &str)],
+        extra_headers: &[(&str, &str)],
+        body: &[u8],
+    ) -> Result<Response> {
+        let url = reqwest::Url::parse(&format!("{}{}", self.endpoint, resource))
+            .with_context(|| format!("invalid Azure endpoint: {}", self.endpoint))?;
+        let mut query_string: Vec<String> = query
+            .iter()
+            .map(|(k, v)| format!("{}={}", uri_encode(k, false), uri_encode(v, false)))
+            .collect();
+
+        let mut headers: Vec<(String, String)> = vec![
+            ("x-ms-date".to_string(), http_date(Utc::now())),
+            ("x-ms-version".to_string(), API_VERSION.to_string()),
+        ];
+        headers.extend(
+            extra_headers
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string())),
+        );
+
+        match &self.auth {
+            AzureAuth::SharedKey { account, key } => {
+                let signature = shared_key_signature(&SharedKeyRequest {
+                    method: method.as_str(),
+                    account,
+                    path: url.path(),
+                    query,
+                    headers: &headers,
+                    content_length: body.len(),
+                    if_match: header_value(extra_headers, "if-match"),
+                    if_none_match: header_value(extra_headers, "if-none-match"),
+                    key,
+                })?;
+                headers.push((
+                    "authorization".to_string(),
+                    format!("SharedKey {account}:{signature}"),
+                ));
+            }
+            AzureAuth::Sas(sas) => query_string.push(sas.clone()),
+        }
+
+        let mut full_url = url.to_string();
+        if !query_string.is_empty() {
+            full_url.push('?');
+            full_url.push_str(&query_string.join("&"));
+        }
+        let mut request = self.http.request(method, full_url);
+        for (name, value) in headers {
+            request = request.header(name, value);
+        }
+        if !body.is_empty() {
+            request = request
+                .header("content-type", "application/octet-stream")
+                .body(body.to_vec());
+        }
+        request.send().context("Azure request failed")
+    }
+}
+
+impl StorageBackend for AzureBlobStorage {
+    fn read(&self, path: &str) -> Result<Vec<u8>> {
+        let name = self.blob_name(path);
+        let response = self.send(Method::GET, &self.blob_path(&name), &[], &[], &[])?;
+        if response.status() == StatusCode::NOT_FOUND {
+            bail!("Azure blob not found: {}/{}", self.container, name);
+        }
+        let bytes = expect_success(response, "reading Azure blob")?
+            .bytes()
+            .with_context(|| format!("failed to read Azure blob {}/{name}", self.container))?;
+        Ok(bytes.to_vec())
+    }
+
+    fn write(&self, path: &str, data: &[u8]) -> Result<()> {
+        let name = self.blob_name(path);
+        let response = self.send(
+            Method::PUT,
+            &self.blob_path(&name),
+            &[],
+            &[("x-ms-blob-type", "BlockBlob")],
+            data,
+        )?;
+        expect_success(response, "writing Azure blob")?;
+        Ok(())
+    }
+
+    /// Uses the blob ETag as the version: `If-Match` when an ETag is
+    /// expected, `If-None-Match: *` when the blob must not exist yet.
+    fn write_if_version(
+        &self,
+        path: &str,
+        data: &[u8],
+        expected_etag: Option<String>,
+    ) -> Result<Option<String>> {
+        let name = self.blob_name(path);
+        let precondition = match &expected_etag {
+            Some(etag) => ("If-Match", etag.as_str()),
+            None => ("If-None-Match", "*"),
+        };
+        let response = self.send(
+            Method::PUT,
+            &self.blob_path(&name),
+            &[],
+            &[("x-ms-blob-type", "BlockBlob"), precondition],
+            data,
+        )?;
+        // 412 for a stale If-Match; 409 (BlobAlreadyExists) for If-None-Match.
+        if matches!(
+            response.status(),
+            StatusCode::PRECONDITION_FAILED | StatusCode::CONFLICT
+        ) {
+            return Ok(None);
+        }
+        let response = expect_success(response, "writing Azure blob")?;
+        response
+            .headers()
+            .get("etag")
+            .and_then(|v| v.to_str().ok())
+            .map(|etag| Some(etag.to_string()))
+            .context("Azure upload response did not include an ETag")
+    }
+
+    fn delete(&self, path: &str) -> Result<()> {
+        let name = self.blob_name(path);
+        let response = self.send(Method::DELETE, &self.blob_path(&name), &[], &[], &[])?;
+        // Deleting a missing blob is a no-op, matching `FileStorage`.
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(());
+        }
+        expect_success(response, "deleting Azure blob")?;
+        Ok(())
+    }
+
+    fn exists(&self, path: &str) -> Result<bool> {
+        let name = self.blob_name(path);
+        let response = self.send(Method::HEAD, &self.blob_path(&name), &[], &[], &[])?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(false);
+        }
+        expect_success(response, "checking Azure blob")?;
+        Ok(true)
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        list_all_pages(self, prefix)
+    }
+
+    fn list_paginated(&self, prefix: &str, continuation: Option<String>) -> Result<ListPage> {
+        let full_prefix = self.blob_name(prefix);
+        let resource = format!("/{}", uri_encode(&self.container, false));
+        let mut query = vec![
+            ("restype", "container"),
+            ("comp", "list"),
+            ("prefix", full_prefix.as_str()),
+        ];
+        if let Some(marker) = &continuation {
+            query.push(("marker", marker));
+        }
+        let response = self.send(Method::GET, &resource, &query, &[], &[])?;
+        let body = expect_success(response, "listing Azure blobs")?
+            .text()
+            .context("failed to read Azure blob listing")?;
+        Ok(ListPage {
+            keys: xml_elements(&body, "Name")
+                .iter()
+                .filter_map(|name| self.relative_name(name))
+                .collect(),
+            next: xml_elements(&body, "NextMarker")
+                .pop()
+                .filter(|marker| !marker.is_empty()),
+        })
+    }
+
+    fn storage_type(&self) -> StorageType {
+        StorageType::Azure
+    }
+
+    fn bucket(&self) -> &str {
+        &self.container
+    }
+
+    fn base_path(&self) -> &str {
+        &self.base_path
+    }
+}
+
+/// Value of the first header named `name` (case-insensitive).
+fn header_value<'a>(headers: &[(&str, &'a str)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(header, _)| header.eq_ignore_ascii_case(name))
+        .map(|(_, value)| *value)
+}
+
+/// RFC 1123 date as Azure expects in `x-ms-date`.
+fn http_date(now: DateTime<Utc>) -> String {
+    now.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+struct SharedKeyRequest<'a> {
+    method: &'a str,
+    account: &'a str,
+    /// URL path as sent, e.g. `/container/blob` or, path-style against
+    /// Azurite, `/devstoreaccount1/container/blob`.
+    path: &'a str,
+    query: &'a [(&'a str, &'a str)],
+    headers: &'a [(String, String)],
+    content_length: usize,
+    if_match: Option<&'a str>,
+    if_none_match: Option<&'a str>,
+    key: &'a [u8],
+}
+
+/// Base64 HMAC-SHA256 Shared Key signature over the Blob service
+/// string-to-sign.
+fn shared_key_signature(req: &SharedKeyRequest<'_>) -> Result<String> {
+    let content_length = if req.content_length == 0 {
+        String::new()
+    } else {
+        req.content_length.to_string()
+    };
+    let content_type = if req.content_length == 0 {
+        ""
+    } else {
+        "application/octet-stream"
+    };
+
+    let mut ms_headers: Vec<(String, &str)> = req
+        .headers
+        .iter()
+        .filter(|(name, _)| name.to_ascii_lowercase().starts_with("x-ms-"))
+        .map(|(name, value)| (name.to_ascii_lowercase(), value.trim()))
+        .collect();
+    ms_headers.sort();
+    let canonical_headers: String = ms_headers
+        .iter()
+        .map(|(name, value)| format!("{name}:{value}\n"))
+        .collect();
+
+    let mut params: Vec<(String, &str)> = req
+        .query
+        .iter()
+        .map(|(name, value)| (name.to_ascii_lowercase(), *value))
+        .collect();
+    params.sort();
+    let mut canonical_resource = format!("/{}{}", req.account, req.path);
+    for (name, value) in params {
+        canonical_resource.push_str(&format!("\n{name}:{value}"));
+    }
+
+    // VERB, Content-Encoding, Content-Language, Content-Length, Content-MD5,
+    // Content-Type, Date, If-Modified-Since, If-Match, If-None-Match,
+    // If-Unmodified-Since, Range — then the canonicalized headers/resource.
+    let string_to_sign = format!(
+        "{}\n\n\n{content_length}\n\n{content_type}\n\n\n{}\n{}\n\n\n{canonical_headers}{canonical_resource}",
+        req.method,
+        req.if_match.unwrap_or(""),
+        req.if_none_match.unwrap_or("")
+    );
+    Ok(STANDARD.encode(hmac_sha256(req.key, string_to_sign.as_bytes())?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Azurite's published development account key.
+    const AZURITE_KEY: &str =
+        "Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw==";
+
+    fn mock_server() -> (tiny_http::Server, String) {
+        let server = tiny_http::Server::http("127.0.0.1:0").expect("server");
+        let base = format!("http://{}", server.server_addr());
+        (server, base)
+    }
+
+    fn respond(req: tiny_http::Request, status: u16, body: &[u8]) {
+        let resp = tiny_http::Response::from_data(body.to_vec()).with_status_code(status);
+        req.respond(resp).expect("respond");
+    }
+
+    fn header(req: &tiny_http::Request, name: &str) -> Option<String> {
+        req.headers()
+            .iter()
+            .find(|h| h.field.as_str().as_str().eq_ignore_ascii_case(name))
+            .map(|h| h.value.as_str().to_string())
+    }
+
+    fn shared_key_storage(endpoint: &str, base_path: &str) -> AzureBlobStorage {
+        let config = CloudStorageConfig::azure("state")
+            .with_endpoint(endpoint)
+            .with_base_path(base_path)
+            .with_credentials("devstoreaccount1", AZURITE_KEY);
+        AzureBlobStorage::new(&config).expect("build")
+    }
+
+    #[test]
+    fn new_requires_credentials() {
+        let config = CloudStorageConfig::azure("state").with_endpoint("http://127.0.0.1:10000");
+        let err = AzureBlobStorage::new(&config).err().expect("should fail");
+        assert!(err.to_string().contains("session_token"));
+    }
+
+    #[test]
+    fn new_defaults_endpoint_from_account_name() {
+        let config = CloudStorageConfig::azure("state").with_credentials("acct", AZURITE_KEY);
+        let storage = AzureBlobStorage::new(&config).expect("build");
+        assert_eq!(storage.endpoint, "https://acct.blob.core.windows.net");
+        assert_eq!(storage.bucket(), "state");
+        assert_eq!(storage.storage_type(), StorageType::Azure);
+    }
+
+    #[test]
+    fn new_rejects_non_base64_account_key() {
+        let config = CloudStorageConfig::azure("state").with_credentials("acct", "not base64!");
+        assert!(AzureBlobStorage::new(&config).is_err());
+    }
+
+    #[test]
+    fn write_puts_block_blob_with_shared_key() {
+        let (server, base) = mock_server();
+        let handle = std::thread::spawn(move || {
+            let req = server.recv().expect("request");
+            assert_eq!(req.method(), &tiny_http::Method::Put);
+            assert_eq!(req.url(), "/state/ci/state.json");
+            assert_eq!(header(&req, "x-ms-blob-type").as_deref(), Some("BlockBlob"));
+            assert_eq!(header(&req, "x-ms-version").as_deref(), Some(API_VERSION));
+            let auth = header(&req, "Authorization").expect("authorization");
+            assert!(auth.starts_with("SharedKey devstoreaccount1:"), "{auth}");
+            respond(req, 201, b"");
+        });
+
+        shared_key_storage(&base, "ci")
+            .write("state.json", b"{}")
+            .expect("write");
+        handle.join().expect("join");
+    }
+
+    #[test]
+    fn write_if_version_sends_etag_preconditions() {
+        let (server, base) = mock_server();
+        let handle = std::thread::spawn(move || {
+            let req = server.recv().expect("request");
+            assert_eq!(header(&req, "If-None-Match").as_deref(), Some("*"));
+            let resp = tiny_http::Response::from_data(Vec::new())
+                .with_status_code(201)
+                .with_header(
+                    tiny_http::Header::from_bytes(&b"ETag"[..], &b"\"0x8D1\""[..]).unwrap(),
+                );
+            req.respond(resp).expect("respond");
+
+            let req = server.recv().expect("request");
+            assert_eq!(header(&req, "If-Match").as_deref(), Some("\"0x8D1\""));
+            respond(req, 412, b"ConditionNotMet");
+
+            let req = server.recv().expect("request");
+            respond(req, 409, b"BlobAlreadyExists");
+        });
+
+        let storage = shared_key_storage(&base, "");
+        let etag = storage
+            .write_if_version("state.json", b"{}", None)
+            .expect("create");
+        assert_eq!(etag.as_deref(), Some("\"0x8D1\""));
+        assert_eq!(
+            storage
+                .write_if_version("state.json", b"{}", etag)
+                .expect("stale"),
+            None
+        );
+        assert_eq!(
+            storage
+                .write_if_version("state.json", b"{}", None)
+                .expect("exists"),
+            None
+        );
+        handle.join().expect("join");
+    }
+
+    #[test]
+    fn shared_key_signature_covers_conditional_headers() {
+        let key = STANDARD.decode(AZURITE_KEY).unwrap();
+        let headers = vec![("x-ms-version".to_string(), API_VERSION.to_string())];
+        let sign = |if_match: Option<&str>| {
+            shared_key_signature(&SharedKeyRequest {
+                method: "PUT",
+                account: "devstoreaccount1",
+                path: "/state/state.json",
+                query: &[],
+                headers: &headers,
+                content_length: 2,
+                if_match,
+                if_none_match: None,
+                key: &key,
+            })
+            .expect("sign")
+        };
+        assert_ne!(sign(Some("\"a\"")), sign(None));
+    }
+
+    #[test]
+    fn sas_token_is_appended_instead_of_signing() {
+        let (server, base) = mock_server();
+        let handle = std::thread::spawn(move || {
+            let req = server.recv().expect("request");
+            assert_eq!(req.url(), "/state/a.json?sv=2021-08-06&sig=abc");
+            assert!(header(&req, "Authorization").is_none());
+            respond(req, 200, b"data");
+        });
+
+        let config = CloudStorageConfig::azure("state")
+            .with_endpoint(base)
+            .with_session_token("?sv=2021-08-06&sig=abc");
+        let storage = AzureBlobStorage::new(&config).expect("build");
+        assert_eq!(storage.read("a.json").expect("read"), b"data");
+        handle.join().expect("join");
+    }
+
+    #[test]
+    fn delete_and_exists_treat_404_as_absent() {
+        let (server, base) = mock_server();
+        let handle = std::thread::spawn(move || {
+            let req = server.recv().expect("request");
+            assert_eq!(req.method(), &tiny_http::Method::Delete);
+            respond(req, 404, b"");
+            let req = server.recv().expect("request");
+            assert_eq!(req.method(), &tiny_http::Method::Head);
+            respond(req, 404, b"");
+        });
+
+        let storage = shared_key_storage(&base, "");
+        storage.delete("gone.json").expect("delete");
+        assert!(!storage.exists("gone.json").expect("exists"));
+        handle.join().expect("join");
+    }
+
+    #[test]
+    fn list_follows_markers_and_strips_base_path() {
+        let (server, base) = mock_server();
+        let handle = std::thread::spawn(move || {
+            let req = server.recv().expect("request");
+            assert_eq!(
+                req.url(),
+                "/state?restype=container&comp=list&prefix=ci%2Fruns"
+            );
+            respond(
+                req,
+                200,
+                b"<EnumerationResults><Blobs><Blob><Name>ci/runs/a.json</Name></Blob></Blobs>\
+                  <NextMarker>m2</NextMarker></EnumerationResults>",
+            );
+            let req = server.recv().expect("request");
+            assert_eq!(
+                req.url(),
+                "/state?restype=container&comp=list&prefix=ci%2Fruns&marker=m2"
+            );
+            respond(
+                req,
+                200,
+                b"<EnumerationResults><Blobs><Blob><Name>ci/runs/b.json</Name></Blob></Blobs>\
+                  <NextMarker /></EnumerationResults>",
+            );
+        });
+
+        let keys = shared_key_storage(&base, "ci").list("runs").expect("list");
+        assert_eq!(keys, vec!["runs/a.json", "runs/b.json"]);
+        handle.join().expect("join");
+    }
+
+    #[test]
+    fn shared_key_signature_covers_query_and_ms_headers() {
+        let key = STANDARD.decode(AZURITE_KEY).unwrap();
+        let headers = vec![
+            ("x-ms-version".to_string(), API_VERSION.to_string()),
+            (
+                "x-ms-date".to_string(),
+                "Fri, 02 Jan 2026 03:04:05 GMT".to_string(),
+            ),
+        ];
+        let sign = |query: &[(&str, &str)]| {
+            shared_key_signature(&SharedKeyRequest {
+                method: "GET",
+                account: "devstoreaccount1",
+                path: "/state",
+                query,
+                headers: &headers,
+                content_length: 0,
+                if_match: None,
+                if_none_match: None,
+                key: &key,
+            })
+            .expect("sign")
+        };
+
+        let listing = sign(&[("restype", "container"), ("comp", "list")]);
+        assert_eq!(
+            sign(&[("comp", "list"), ("restype", "container")]),
+            listing,
+            "query parameter order must not matter"
+        );
+        assert_ne!(sign(&[("comp", "list")]), listing);
+    }
+
+    #[test]
+    fn http_date_is_rfc1123() {
+        let now = "2026-01-02T03:04:05Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(http_date(now), "Fri, 02 Jan 2026 03:04:05 GMT");
+    }
+
+    /// Round-trip against a running Azurite. Run with
+    /// `SHIPPER_STORAGE_ENDPOINT=http://127.0.0.1:10000/devstoreaccount1 cargo test
+    /// -p shipper-core --features azurite-tests azurite`; the container named by
+    /// `SHIPPER_STORAGE_BUCKET` (default `shipper-test`) must already exist.
+    #[cfg(feature = "azurite-tests")]
+    #[test]
+    fn azurite_round_trip() {
+        let endpoint = std::env::var("SHIPPER_STORAGE_ENDPOINT")
+            .expect("SHIPPER_STORAGE_ENDPOINT must point at Azurite");
+        let container =
+            std::env::var("SHIPPER_STORAGE_BUCKET").unwrap_or_else(|_| "shipper-test".to_string());
+        let config = CloudStorageConfig::azure(container)
+            .with_endpoint(endpoint)
+            .with_base_path(format!("run-{}", std::process::id()))
+            .with_credentials("devstoreaccount1", AZURITE_KEY);
+        let storage = AzureBlobStorage::new(&config).expect("build");
+
+        storage
+            .write("state/state.json", b"{\"ok\":true}")
+            .expect("write");
+        assert!(storage.exists("state/state.json").expect("exists"));
+        assert_eq!(
+            storage.read("state/state.json").expect("read"),
+            b"{\"ok\":true}"
+        );
+        assert_eq!(
+            storage.list("state").expect("list"),
+            vec!["state/state.json"]
+        );
+
+        let version = storage
+            .write_if_version("lock.json", b"1", None)
+            .expect("create")
+            .expect("created");
+        assert_eq!(
+            storage
+                .write_if_version("lock.json", b"2", None)
+                .expect("create again"),
+            None
+        );
+        assert!(
+            storage
+                .write_if_version("lock.json", b"2", Some(version))
+                .expect("update")
+                .is_some()
+        );
+        storage.delete("lock.json").expect("delete lock");
+
+        storage.delete("state/state.json").expect("delete");
+        storage
+            .delete("state/state.json")
+            .expect("second delete is a no-op");
+        assert!(
+            !storage
+                .exists("state/state.json")
+                .expect("exists after delete")
+        );
+    }
+}
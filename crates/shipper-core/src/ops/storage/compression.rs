@@ -0,0 +1,241 @@
+//! Transparent gzip compression over any [`StorageBackend`].
+
+use std::io::{Read, Write};
+
+use anyhow::{Context, Result};
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+
+use super::{ListPage, StorageBackend, StorageType};
+
+/// The two bytes every gzip stream starts with.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Default gzip level: zlib's balance of speed and ratio.
+pub(crate) const DEFAULT_COMPRESSION_LEVEL: u32 = 6;
+
+/// Wraps a backend so blobs are gzipped on write and gunzipped on read.
+///
+/// Reads check the gzip magic bytes first, so blobs written before
+/// compression was enabled (plain JSON) still load. `list`, `exists` and
+/// `delete` pass straight through, as do `copy`/`mv`, which move the stored
+/// bytes without recompressing them.
+pub(crate) struct CompressingStorage<B> {
+    inner: B,
+    level: u32,
+}
+
+impl<B: StorageBackend> CompressingStorage<B> {
+    /// Wrap `inner` with [`DEFAULT_COMPRESSION_LEVEL`].
+    pub(crate) fn new(inner: B) -> Self {
+        Self {
+            inner,
+            level: DEFAULT_COMPRESSION_LEVEL,
+        }
+    }
+
+    /// Set the gzip level: 0 (store only) through 9 (smallest). Larger
+    /// values are clamped to 9.
+    pub(crate) fn with_level(mut self, level: u32) -> Self {
+        self.level = level.min(9);
+        self
+    }
+
+    /// The configured gzip level.
+    pub(crate) fn level(&self) -> u32 {
+        self.level
+    }
+
+    /// The wrapped backend.
+    pub(crate) fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    fn compress(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::new(self.level));
+        encoder.write_all(data).context("failed to gzip blob")?;
+        encoder.finish().context("failed to gzip blob")
+    }
+}
+
+/// Gunzip `stored` if it carries the gzip magic, otherwise return it as-is.
+fn decompress(path: &str, stored: Vec<u8>) -> Result<Vec<u8>> {
+    if !stored.starts_with(&GZIP_MAGIC) {
+        return Ok(stored);
+    }
+    let mut data = Vec::new();
+    GzDecoder::new(stored.as_slice())
+        .read_to_end(&mut data)
+        .with_context(|| format!("failed to gunzip blob: {path}"))?;
+    Ok(data)
+}
+
+impl<B: StorageBackend> StorageBackend for CompressingStorage<B> {
+    fn read(&self, path: &str) -> Result<Vec<u8>> {
+        decompress(path, self.inner.read(path)?)
+    }
+
+    fn write(&self, path: &str, data: &[u8]) -> Result<()> {
+        self.inner.write(path, &self.compress(data)?)
+    }
+
+    fn write_if_version(
+        &self,
+        path: &str,
+        data: &[u8],
+        expected_etag: Option<String>,
+    ) -> Result<Option<String>> {
+        self.inner
+            .write_if_version(path, &self.compress(data)?, expected_etag)
+    }
+
+    fn delete(&self, path: &str) -> Result<()> {
+        self.inner.delete(path)
+    }
+
+    fn exists(&self, path: &str) -> Result<bool> {
+        self.inner.exists(path)
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        self.inner.list(prefix)
+    }
+
+    fn list_paginated(&self, prefix: &str, continuation: Option<String>) -> Result<ListPage> {
+        self.inner.list_paginated(prefix, continuation)
+    }
+
+    fn storage_type(&self) -> StorageType {
+        self.inner.storage_type()
+    }
+
+    fn bucket(&self) -> &str {
+        self.inner.bucket()
+    }
+
+    fn base_path(&self) -> &str {
+        self.inner.base_path()
+    }
+
+    fn copy(&self, from: &str, to: &str) -> Result<()> {
+        self.inner.copy(from, to)
+    }
+
+    fn mv(&self, from: &str, to: &str) -> Result<()> {
+        self.inner.mv(from, to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::storage::FileStorage;
+    use tempfile::tempdir;
+
+    /// A receipt-shaped document with the repetition real receipts have.
+    fn sample_json() -> Vec<u8> {
+        let packages: Vec<_> = (0..50)
+            .map(|i| {
+                serde_json::json!({
+                    "name": format!("crate-{i}"),
+                    "version": "0.1.0",
+                    "state": { "state": "published" },
+                    "evidence": { "attempts": [], "readiness_checks": [] },
+                })
+            })
+            .collect();
+        serde_json::to_vec_pretty(&serde_json::json!({
+            "receipt_version": "shipper.receipt.v2",
+            "packages": packages,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn round_trips_json_and_stores_fewer_bytes() {
+        let td = tempdir().expect("tempdir");
+        let storage = CompressingStorage::new(FileStorage::new(td.path().to_path_buf()));
+        let json = sample_json();
+
+        storage.write("receipt.json", &json).expect("write");
+
+        let stored = storage.inner().read("receipt.json").expect("raw read");
+        assert!(stored.starts_with(&GZIP_MAGIC));
+        assert!(
+            stored.len() < json.len() / 4,
+            "{} compressed vs {} raw",
+            stored.len(),
+            json.len()
+        );
+        assert_eq!(storage.read("receipt.json").expect("read"), json);
+    }
+
+    #[test]
+    fn reads_legacy_uncompressed_blobs() {
+        let td = tempdir().expect("tempdir");
+        let inner = FileStorage::new(td.path().to_path_buf());
+        inner
+            .write("state.json", br#"{"state_version":"shipper.state.v1"}"#)
+            .expect("legacy write");
+
+        let storage = CompressingStorage::new(inner);
+        assert_eq!(
+            storage.read("state.json").expect("read"),
+            br#"{"state_version":"shipper.state.v1"}"#
+        );
+    }
+
+    #[test]
+    fn level_is_configurable_and_clamped() {
+        let td = tempdir().expect("tempdir");
+        let json = sample_json();
+
+        let store_only =
+            CompressingStorage::new(FileStorage::new(td.path().join("l0"))).with_level(0);
+        let best = CompressingStorage::new(FileStorage::new(td.path().join("l9"))).with_level(42);
+        assert_eq!(best.level(), 9);
+
+        store_only.write("r.json", &json).expect("write l0");
+        best.write("r.json", &json).expect("write l9");
+        let stored_l0 = store_only.inner().read("r.json").expect("raw l0");
+        let stored_l9 = best.inner().read("r.json").expect("raw l9");
+        assert!(stored_l9.len() < stored_l0.len());
+        assert_eq!(store_only.read("r.json").expect("read l0"), json);
+    }
+
+    #[test]
+    fn list_exists_delete_and_copy_pass_through() {
+        let td = tempdir().expect("tempdir");
+        let storage = CompressingStorage::new(FileStorage::new(td.path().to_path_buf()));
+
+        storage.write("runs/a.json", b"{}").expect("write");
+        assert!(storage.exists("runs/a.json").expect("exists"));
+        assert_eq!(storage.list("runs").expect("list"), vec!["runs/a.json"]);
+
+        storage.copy("runs/a.json", "runs/b.json").expect("copy");
+        assert_eq!(
+            storage.inner().read("runs/b.json").expect("raw copy"),
+            storage.inner().read("runs/a.json").expect("raw original")
+        );
+        assert_eq!(storage.read("runs/b.json").expect("read copy"), b"{}");
+
+        storage.delete("runs/a.json").expect("delete");
+        assert!(!storage.exists("runs/a.json").expect("exists after delete"));
+        assert_eq!(storage.storage_type(), StorageType::File);
+    }
+
+    #[test]
+    fn corrupt_gzip_is_an_error() {
+        let td = tempdir().expect("tempdir");
+        let inner = FileStorage::new(td.path().to_path_buf());
+        inner
+            .write("bad.json", &[0x1f, 0x8b, 0x00, 0x01])
+            .expect("write");
+
+        let err = CompressingStorage::new(inner)
+            .read("bad.json")
+            .expect_err("corrupt");
+        assert!(err.to_string().contains("failed to gunzip blob: bad.json"));
+    }
+}
@@ -0,0 +1,739 @@
+//! Credential resolution shared by the cloud storage backends.
+//!
+//! [`resolve_credentials`] layers three sources, first match wins:
+//!
+//! 1. explicit [`CloudStorageConfig`] fields (`access_key_id`,
+//!    `secret_access_key`, `session_token`);
+//! 2. the provider's conventional environment variables;
+//! 3. the provider's default credential files.
+//!
+//! Backends consume the normalized [`ResolvedCredentials`] instead of reading
+//! config fields or the environment themselves.
+
+use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+
+use super::{CloudStorageConfig, StorageType};
+
+/// Env var naming a GCS service-account JSON key file.
+pub(crate) const GOOGLE_APPLICATION_CREDENTIALS: &str = "GOOGLE_APPLICATION_CREDENTIALS";
+/// Overrides the gcloud config directory (default `~/.config/gcloud`).
+pub(crate) const CLOUDSDK_CONFIG: &str = "CLOUDSDK_CONFIG";
+pub(crate) const AWS_ACCESS_KEY_ID: &str = "AWS_ACCESS_KEY_ID";
+pub(crate) const AWS_SECRET_ACCESS_KEY: &str = "AWS_SECRET_ACCESS_KEY";
+pub(crate) const AWS_SESSION_TOKEN: &str = "AWS_SESSION_TOKEN";
+/// Overrides the AWS shared credentials file (default `~/.aws/credentials`).
+pub(crate) const AWS_SHARED_CREDENTIALS_FILE: &str = "AWS_SHARED_CREDENTIALS_FILE";
+pub(crate) const AWS_PROFILE: &str = "AWS_PROFILE";
+pub(crate) const AZURE_STORAGE_ACCOUNT: &str = "AZURE_STORAGE_ACCOUNT";
+pub(crate) const AZURE_STORAGE_KEY: &str = "AZURE_STORAGE_KEY";
+pub(crate) const AZURE_STORAGE_SAS_TOKEN: &str = "AZURE_STORAGE_SAS_TOKEN";
+pub(crate) const AZURE_STORAGE_CONNECTION_STRING: &str = "AZURE_STORAGE_CONNECTION_STRING";
+
+/// Public GCS endpoint; anything else is treated as an emulator.
+pub(crate) const DEFAULT_GCS_ENDPOINT: &str = "https://storage.googleapis.com";
+
+/// Which layer produced a [`ResolvedCredentials`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CredentialSource {
+    Config,
+    Environment,
+    DefaultChain,
+}
+
+/// Normalized credentials handed to a backend.
+#[derive(Clone, PartialEq, Eq)]
+pub(crate) enum Credentials {
+    /// No credentials: filesystem storage, or anonymous access to a GCS
+    /// emulator endpoint.
+    None,
+    /// Key pair: S3 access keys, GCS HMAC interop keys, or an Azure account
+    /// name (`access_key_id`) and account key (`secret_access_key`).
+    AccessKey {
+        access_key_id: String,
+        secret_access_key: String,
+        session_token: Option<String>,
+    },
+    /// Path to a GCS service-account JSON key.
+    ServiceAccountFile(PathBuf),
+    /// Path to a GCS `authorized_user` JSON file, as written by
+    /// `gcloud auth application-default login`.
+    AuthorizedUserFile(PathBuf),
+    /// Azure SAS token (without the leading `?`), plus the account name when
+    /// known so the default endpoint can be derived.
+    SasToken {
+        account: Option<String>,
+        token: String,
+    },
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => f.write_str("None"),
+            Self::AccessKey {
+                access_key_id,
+                session_token,
+                ..
+            } => f
+                .debug_struct("AccessKey")
+                .field("access_key_id", access_key_id)
+                .field("secret_access_key", &"<redacted>")
+                .field(
+                    "session_token",
+                    &session_token.as_ref().map(|_| "<redacted>"),
+                )
+                .finish(),
+            Self::ServiceAccountFile(path) => {
+                f.debug_tuple("ServiceAccountFile").field(path).finish()
+            }
+            Self::AuthorizedUserFile(path) => {
+                f.debug_tuple("AuthorizedUserFile").field(path).finish()
+            }
+            Self::SasToken { account, .. } => f
+                .debug_struct("SasToken")
+                .field("account", account)
+                .field("token", &"<redacted>")
+                .finish(),
+        }
+    }
+}
+
+/// Credentials plus the layer they came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ResolvedCredentials {
+    pub(crate) credentials: Credentials,
+    pub(crate) source: CredentialSource,
+}
+
+impl ResolvedCredentials {
+    fn new(credentials: Credentials, source: CredentialSource) -> Self {
+        Self {
+            credentials,
+            source,
+        }
+    }
+}
+
+/// Resolve credentials for `config`: config fields, then provider env vars,
+/// then default credential files.
+///
+/// Errors when a cloud backend is selected and nothing resolves, or when the
+/// config carries only half of a key pair (which would otherwise silently
+/// fall through to another identity).
+pub(crate) fn resolve_credentials(config: &CloudStorageConfig) -> Result<ResolvedCredentials> {
+    match config.storage_type {
+        StorageType::File => Ok(ResolvedCredentials::new(
+            Credentials::None,
+            CredentialSource::Config,
+        )),
+        StorageType::S3 => resolve_s3(config),
+        StorageType::Gcs => resolve_gcs(config),
+        StorageType::Azure => resolve_azure(config),
+    }
+}
+
+/// Both halves of the configured key pair, `None` when neither is set.
+fn config_key_pair(config: &CloudStorageConfig) -> Result<Option<(String, String)>> {
+    match (&config.access_key_id, &config.secret_access_key) {
+        (Some(id), Some(secret)) => Ok(Some((id.clone(), secret.clone()))),
+        (None, None) => Ok(None),
+        _ => bail!(
+            "{} storage config sets only one of access_key_id/secret_access_key; set both",
+            config.storage_type
+        ),
+    }
+}
+
+fn resolve_s3(config: &CloudStorageConfig) -> Result<ResolvedCredentials> {
+    if let Some((access_key_id, secret_access_key)) = config_key_pair(config)? {
+        return Ok(ResolvedCredentials::new(
+            Credentials::AccessKey {
+                access_key_id,
+                secret_access_key,
+                session_token: config.session_token.clone(),
+            },
+            CredentialSource::Config,
+        ));
+    }
+
+    if let (Some(access_key_id), Some(secret_access_key)) =
+        (env_var(AWS_ACCESS_KEY_ID), env_var(AWS_SECRET_ACCESS_KEY))
+    {
+        return Ok(ResolvedCredentials::new(
+            Credentials::AccessKey {
+                access_key_id,
+                secret_access_key,
+                session_token: env_var(AWS_SESSION_TOKEN),
+            },
+            CredentialSource::Environment,
+        ));
+    }
+
+    let path = env_var(AWS_SHARED_CREDENTIALS_FILE)
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".aws").join("credentials")));
+    let profile = env_var(AWS_PROFILE).unwrap_or_else(|| "default".to_string());
+    if let Some(path) = path.filter(|p| p.is_file())
+        && let Some(credentials) = aws_profile_credentials(&path, &profile)?
+    {
+        return Ok(ResolvedCredentials::new(
+            credentials,
+            CredentialSource::DefaultChain,
+        ));
+    }
+
+    bail!(
+        "no credentials found for s3 storage: set access_key_id/secret_access_key, \
+         {AWS_ACCESS_KEY_ID}/{AWS_SECRET_ACCESS_KEY}, or a [{profile}] profile in the \
+         AWS shared credentials file"
+    )
+}
+
+fn resolve_gcs(config: &CloudStorageConfig) -> Result<ResolvedCredentials> {
+    if let Some((access_key_id, secret_access_key)) = config_key_pair(config)? {
+        return Ok(ResolvedCredentials::new(
+            Credentials::AccessKey {
+                access_key_id,
+                secret_access_key,
+                session_token: None,
+            },
+            CredentialSource::Config,
+        ));
+    }
+
+    if let Some(path) = env_var(GOOGLE_APPLICATION_CREDENTIALS) {
+        let path = PathBuf::from(path);
+        // An unreadable or untyped file is left for the backend to report.
+        let credentials = match gcs_credential_type(&path).as_deref() {
+            Some("authorized_user") => Credentials::AuthorizedUserFile(path),
+            None | Some("service_account") => Credentials::ServiceAccountFile(path),
+            Some(other) => bail!(
+                "unsupported credential type `{other}` in {GOOGLE_APPLICATION_CREDENTIALS} \
+                 ({}): expected service_account or authorized_user",
+                path.display()
+            ),
+        };
+        return Ok(ResolvedCredentials::new(
+            credentials,
+            CredentialSource::Environment,
+        ));
+    }
+
+    // Emulators such as fake-gcs-server accept anonymous requests; an ambient
+    // gcloud login must not turn those into authenticated ones.
+    if config
+        .endpoint
+        .as_deref()
+        .is_some_and(|endpoint| endpoint.trim_end_matches('/') != DEFAULT_GCS_ENDPOINT)
+    {
+        return Ok(ResolvedCredentials::new(
+            Credentials::None,
+            CredentialSource::DefaultChain,
+        ));
+    }
+
+    let adc = env_var(CLOUDSDK_CONFIG)
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".config").join("gcloud")))
+        .map(|dir| dir.join("application_default_credentials.json"));
+    let mut skipped = String::new();
+    if let Some(path) = adc.filter(|p| p.is_file()) {
+        match gcs_credential_type(&path).as_deref() {
+            Some("authorized_user") => {
+                return Ok(ResolvedCredentials::new(
+                    Credentials::AuthorizedUserFile(path),
+                    CredentialSource::DefaultChain,
+                ));
+            }
+            Some("service_account") => {
+                return Ok(ResolvedCredentials::new(
+                    Credentials::ServiceAccountFile(path),
+                    CredentialSource::DefaultChain,
+                ));
+            }
+            kind => {
+                skipped = format!(
+                    " ({} was ignored: credential type `{}` is not supported)",
+                    path.display(),
+                    kind.unwrap_or("<missing>")
+                );
+            }
+        }
+    }
+
+    bail!(
+        "no credentials found for gcs storage: set access_key_id/secret_access_key to HMAC \
+         keys, {GOOGLE_APPLICATION_CREDENTIALS} to a service account key file, or run \
+         `gcloud auth application-default login`{skipped}"
+    )
+}
+
+/// The `"type"` field of a GCS JSON credential file, if it can be read.
+fn gcs_credential_type(path: &Path) -> Option<String> {
+    let raw = std::fs::read_to_string(path).ok()?;
+    let value: serde_json::Value = serde_json::from_str(&raw).ok()?;
+    value.get("type")?.as_str().map(str::to_string)
+}
+
+fn resolve_azure(config: &CloudStorageConfig) -> Result<ResolvedCredentials> {
+    if let Some((account, key)) = config_key_pair(config)? {
+        return Ok(ResolvedCredentials::new(
+            Credentials::AccessKey {
+                access_key_id: account,
+                secret_access_key: key,
+                session_token: None,
+            },
+            CredentialSource::Config,
+        ));
+    }
+    if let Some(token) = &config.session_token {
+        return Ok(ResolvedCredentials::new(
+            sas_token(config.access_key_id.clone(), token),
+            CredentialSource::Config,
+        ));
+    }
+
+    let account = env_var(AZURE_STORAGE_ACCOUNT);
+    if let (Some(account), Some(key)) = (&account, env_var(AZURE_STORAGE_KEY)) {
+        return Ok(ResolvedCredentials::new(
+            Credentials::AccessKey {
+                access_key_id: account.clone(),
+                secret_access_key: key,
+                session_token: None,
+            },
+            CredentialSource::Environment,
+        ));
+    }
+    if let Some(token) = env_var(AZURE_STORAGE_SAS_TOKEN) {
+        return Ok(ResolvedCredentials::new(
+            sas_token(account, &token),
+            CredentialSource::Environment,
+        ));
+    }
+    if let Some(connection_string) = env_var(AZURE_STORAGE_CONNECTION_STRING)
+        && let Some(credentials) = azure_connection_string_credentials(&connection_string)
+    {
+        return Ok(ResolvedCredentials::new(
+            credentials,
+            CredentialSource::Environment,
+        ));
+    }
+
+    bail!(
+        "no credentials found for azure storage: set access_key_id/secret_access_key to the \
+         account name and key, session_token to a SAS token, or {AZURE_STORAGE_ACCOUNT}/\
+         {AZURE_STORAGE_KEY}, {AZURE_STORAGE_SAS_TOKEN}, or {AZURE_STORAGE_CONNECTION_STRING}"
+    )
+}
+
+fn sas_token(account: Option<String>, token: &str) -> Credentials {
+    Credentials::SasToken {
+        account,
+        token: token.trim_start_matches('?').to_string(),
+    }
+}
+
+/// Non-empty value of an environment variable.
+fn env_var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+/// Read `[profile]` from an AWS shared credentials file.
+fn aws_profile_credentials(path: &Path, profile: &str) -> Result<Option<Credentials>> {
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read AWS credentials file: {}", path.display()))?;
+
+    let mut in_profile = false;
+    let mut access_key_id = None;
+    let mut secret_access_key = None;
+    let mut session_token = None;
+    for line in raw.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            in_profile = section.trim() == profile;
+            continue;
+        }
+        if !in_profile {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            let value = Some(value.trim().to_string());
+            match key.trim() {
+                "aws_access_key_id" => access_key_id = value,
+                "aws_secret_access_key" => secret_access_key = value,
+                "aws_session_token" => session_token = value,
+                _ => {}
+            }
+        }
+    }
+
+    Ok(match (access_key_id, secret_access_key) {
+        (Some(access_key_id), Some(secret_access_key)) => Some(Credentials::AccessKey {
+            access_key_id,
+            secret_access_key,
+            session_token,
+        }),
+        _ => None,
+    })
+}
+
+/// Shared Key or SAS credentials from an Azure storage connection string.
+fn azure_connection_string_credentials(connection_string: &str) -> Option<Credentials> {
+    let mut account = None;
+    let mut key = None;
+    let mut sas = None;
+    for part in connection_string.split(';') {
+        let Some((name, value)) = part.split_once('=') else {
+            continue;
+        };
+        match name.trim() {
+            "AccountName" => account = Some(value.trim().to_string()),
+            "AccountKey" => key = Some(value.trim().to_string()),
+            "SharedAccessSignature" => sas = Some(value.trim().to_string()),
+            _ => {}
+        }
+    }
+
+    match (account, key, sas) {
+        (Some(account), Some(key), _) => Some(Credentials::AccessKey {
+            access_key_id: account,
+            secret_access_key: key,
+            session_token: None,
+        }),
+        (account, None, Some(token)) => Some(sas_token(account, &token)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const ALL_VARS: &[&str] = &[
+        GOOGLE_APPLICATION_CREDENTIALS,
+        CLOUDSDK_CONFIG,
+        AWS_ACCESS_KEY_ID,
+        AWS_SECRET_ACCESS_KEY,
+        AWS_SESSION_TOKEN,
+        AWS_SHARED_CREDENTIALS_FILE,
+        AWS_PROFILE,
+        AZURE_STORAGE_ACCOUNT,
+        AZURE_STORAGE_KEY,
+        AZURE_STORAGE_SAS_TOKEN,
+        AZURE_STORAGE_CONNECTION_STRING,
+    ];
+
+    /// Run `f` with every credential variable cleared except `vars`, and with
+    /// the default credential files pointed at an empty directory.
+    fn with_env<R>(vars: &[(&str, &str)], f: impl FnOnce() -> R) -> R {
+        let empty = tempdir().expect("tempdir");
+        let missing = empty.path().join("missing");
+        let missing = missing.to_str().expect("utf-8 path").to_string();
+        let mut all: Vec<(&str, Option<String>)> =
+            ALL_VARS.iter().map(|name| (*name, None)).collect();
+        all.push((AWS_SHARED_CREDENTIALS_FILE, Some(missing.clone())));
+        all.push((CLOUDSDK_CONFIG, Some(missing)));
+        for (name, value) in vars {
+            all.push((name, Some(value.to_string())));
+        }
+        temp_env::with_vars(all, f)
+    }
+
+    fn access_key(id: &str, secret: &str, token: Option<&str>) -> Credentials {
+        Credentials::AccessKey {
+            access_key_id: id.to_string(),
+            secret_access_key: secret.to_string(),
+            session_token: token.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn file_storage_needs_no_credentials() {
+        let resolved = resolve_credentials(&CloudStorageConfig::file("/tmp/x")).expect("resolve");
+        assert_eq!(resolved.credentials, Credentials::None);
+    }
+
+    #[test]
+    fn s3_config_fields_beat_environment() {
+        let config = CloudStorageConfig::s3("bucket")
+            .with_credentials("CONFIG_ID", "config-secret")
+            .with_session_token("config-token");
+        let resolved = with_env(
+            &[
+                (AWS_ACCESS_KEY_ID, "ENV_ID"),
+                (AWS_SECRET_ACCESS_KEY, "env"),
+            ],
+            || resolve_credentials(&config),
+        )
+        .expect("resolve");
+        assert_eq!(resolved.source, CredentialSource::Config);
+        assert_eq!(
+            resolved.credentials,
+            access_key("CONFIG_ID", "config-secret", Some("config-token"))
+        );
+    }
+
+    #[test]
+    fn s3_environment_beats_shared_credentials_file() {
+        let td = tempdir().expect("tempdir");
+        let file = td.path().join("credentials");
+        std::fs::write(
+            &file,
+            "[default]\naws_access_key_id = FILE_ID\naws_secret_access_key = file\n",
+        )
+        .expect("write");
+        let resolved = with_env(
+            &[
+                (AWS_ACCESS_KEY_ID, "ENV_ID"),
+                (AWS_SECRET_ACCESS_KEY, "env-secret"),
+                (AWS_SESSION_TOKEN, "env-token"),
+                (AWS_SHARED_CREDENTIALS_FILE, file.to_str().unwrap()),
+            ],
+            || resolve_credentials(&CloudStorageConfig::s3("bucket")),
+        )
+        .expect("resolve");
+        assert_eq!(resolved.source, CredentialSource::Environment);
+        assert_eq!(
+            resolved.credentials,
+            access_key("ENV_ID", "env-secret", Some("env-token"))
+        );
+    }
+
+    #[test]
+    fn s3_falls_back_to_shared_credentials_profile() {
+        let td = tempdir().expect("tempdir");
+        let file = td.path().join("credentials");
+        std::fs::write(
+            &file,
+            "[default]\naws_access_key_id = DEFAULT_ID\naws_secret_access_key = d\n\n\
+             [release]\naws_access_key_id = RELEASE_ID\naws_secret_access_key = r\n",
+        )
+        .expect("write");
+        let resolved = with_env(
+            &[
+                (AWS_SHARED_CREDENTIALS_FILE, file.to_str().unwrap()),
+                (AWS_PROFILE, "release"),
+            ],
+            || resolve_credentials(&CloudStorageConfig::s3("bucket")),
+        )
+        .expect("resolve");
+        assert_eq!(resolved.source, CredentialSource::DefaultChain);
+        assert_eq!(resolved.credentials, access_key("RELEASE_ID", "r", None));
+    }
+
+    #[test]
+    fn half_configured_key_pair_is_an_error() {
+        let mut config = CloudStorageConfig::s3("bucket");
+        config.access_key_id = Some("ONLY_ID".to_string());
+        let err = with_env(
+            &[
+                (AWS_ACCESS_KEY_ID, "ENV_ID"),
+                (AWS_SECRET_ACCESS_KEY, "env"),
+            ],
+            || resolve_credentials(&config),
+        )
+        .expect_err("partial config");
+        assert!(err.to_string().contains("only one of"), "{err}");
+    }
+
+    #[test]
+    fn gcs_prefers_hmac_then_service_account_then_adc() {
+        let td = tempdir().expect("tempdir");
+        let adc_path = td.path().join("application_default_credentials.json");
+        std::fs::write(&adc_path, r#"{"type":"authorized_user"}"#).expect("write adc");
+        let gcloud_dir = td.path().to_str().unwrap();
+
+        let hmac = CloudStorageConfig::gcs("bucket").with_credentials("GOOG1", "secret");
+        let resolved = with_env(&[(GOOGLE_APPLICATION_CREDENTIALS, "/sa.json")], || {
+            resolve_credentials(&hmac)
+        })
+        .expect("resolve");
+        assert_eq!(resolved.credentials, access_key("GOOG1", "secret", None));
+
+        let plain = CloudStorageConfig::gcs("bucket");
+        let resolved = with_env(
+            &[
+                (GOOGLE_APPLICATION_CREDENTIALS, "/sa.json"),
+                (CLOUDSDK_CONFIG, gcloud_dir),
+            ],
+            || resolve_credentials(&plain),
+        )
+        .expect("resolve");
+        assert_eq!(resolved.source, CredentialSource::Environment);
+        assert_eq!(
+            resolved.credentials,
+            Credentials::ServiceAccountFile(PathBuf::from("/sa.json"))
+        );
+
+        let resolved = with_env(&[(CLOUDSDK_CONFIG, gcloud_dir)], || {
+            resolve_credentials(&plain)
+        })
+        .expect("resolve");
+        assert_eq!(resolved.source, CredentialSource::DefaultChain);
+        assert_eq!(
+            resolved.credentials,
+            Credentials::AuthorizedUserFile(adc_path.clone())
+        );
+
+        std::fs::write(&adc_path, r#"{"type":"service_account"}"#).expect("write adc");
+        let resolved = with_env(&[(CLOUDSDK_CONFIG, gcloud_dir)], || {
+            resolve_credentials(&plain)
+        })
+        .expect("resolve");
+        assert_eq!(
+            resolved.credentials,
+            Credentials::ServiceAccountFile(adc_path)
+        );
+    }
+
+    #[test]
+    fn gcs_env_key_file_type_selects_credentials() {
+        let td = tempdir().expect("tempdir");
+        let path = td.path().join("key.json");
+        let path_str = path.to_str().unwrap();
+        let plain = CloudStorageConfig::gcs("bucket");
+
+        std::fs::write(&path, r#"{"type":"authorized_user"}"#).expect("write key");
+        let resolved = with_env(&[(GOOGLE_APPLICATION_CREDENTIALS, path_str)], || {
+            resolve_credentials(&plain)
+        })
+        .expect("resolve");
+        assert_eq!(
+            resolved.credentials,
+            Credentials::AuthorizedUserFile(path.clone())
+        );
+
+        std::fs::write(&path, r#"{"type":"external_account"}"#).expect("write key");
+        let err = with_env(&[(GOOGLE_APPLICATION_CREDENTIALS, path_str)], || {
+            resolve_credentials(&plain)
+        })
+        .expect_err("unsupported type");
+        assert!(err.to_string().contains("external_account"), "{err}");
+    }
+
+    #[test]
+    fn gcs_unsupported_adc_file_is_skipped_with_reason() {
+        let td = tempdir().expect("tempdir");
+        std::fs::write(
+            td.path().join("application_default_credentials.json"),
+            r#"{"type":"external_account"}"#,
+        )
+        .expect("write adc");
+        let gcloud_dir = td.path().to_str().unwrap();
+
+        let err = with_env(&[(CLOUDSDK_CONFIG, gcloud_dir)], || {
+            resolve_credentials(&CloudStorageConfig::gcs("bucket"))
+        })
+        .expect_err("nothing usable");
+        let message = err.to_string();
+        assert!(message.contains("no credentials found"), "{message}");
+        assert!(
+            message.contains("`external_account` is not supported"),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn gcs_emulator_endpoint_resolves_anonymously() {
+        let config = CloudStorageConfig::gcs("bucket").with_endpoint("http://localhost:4443");
+        let resolved = with_env(&[], || resolve_credentials(&config)).expect("resolve");
+        assert_eq!(resolved.credentials, Credentials::None);
+    }
+
+    #[test]
+    fn gcs_adc_file_does_not_shadow_emulator_endpoint() {
+        let td = tempdir().expect("tempdir");
+        std::fs::write(
+            td.path().join("application_default_credentials.json"),
+            r#"{"type":"authorized_user"}"#,
+        )
+        .expect("write adc");
+        let config = CloudStorageConfig::gcs("bucket").with_endpoint("http://localhost:4443");
+        let resolved = with_env(&[(CLOUDSDK_CONFIG, td.path().to_str().unwrap())], || {
+            resolve_credentials(&config)
+        })
+        .expect("resolve");
+        assert_eq!(resolved.credentials, Credentials::None);
+    }
+
+    #[test]
+    fn azure_resolves_config_then_env_layers() {
+        let sas = CloudStorageConfig::azure("container").with_session_token("?sv=1&sig=x");
+        let resolved = with_env(
+            &[(AZURE_STORAGE_ACCOUNT, "acct"), (AZURE_STORAGE_KEY, "a2V5")],
+            || resolve_credentials(&sas),
+        )
+        .expect("resolve");
+        assert_eq!(resolved.source, CredentialSource::Config);
+        assert_eq!(
+            resolved.credentials,
+            Credentials::SasToken {
+                account: None,
+                token: "sv=1&sig=x".to_string()
+            }
+        );
+
+        let plain = CloudStorageConfig::azure("container");
+        let resolved = with_env(
+            &[(AZURE_STORAGE_ACCOUNT, "acct"), (AZURE_STORAGE_KEY, "a2V5")],
+            || resolve_credentials(&plain),
+        )
+        .expect("resolve");
+        assert_eq!(resolved.source, CredentialSource::Environment);
+        assert_eq!(resolved.credentials, access_key("acct", "a2V5", None));
+
+        let resolved = with_env(
+            &[(
+                AZURE_STORAGE_CONNECTION_STRING,
+                "DefaultEndpointsProtocol=https;AccountName=conn;AccountKey=Y29ubg==;\
+                 EndpointSuffix=core.windows.net",
+            )],
+            || resolve_credentials(&plain),
+        )
+        .expect("resolve");
+        assert_eq!(resolved.credentials, access_key("conn", "Y29ubg==", None));
+    }
+
+    #[test]
+    fn missing_credentials_error_names_every_layer() {
+        for (config, needles) in [
+            (
+                CloudStorageConfig::s3("bucket"),
+                vec![AWS_ACCESS_KEY_ID, "shared credentials file"],
+            ),
+            (
+                CloudStorageConfig::gcs("bucket"),
+                vec![GOOGLE_APPLICATION_CREDENTIALS, "application-default"],
+            ),
+            (
+                CloudStorageConfig::azure("container"),
+                vec![AZURE_STORAGE_ACCOUNT, AZURE_STORAGE_CONNECTION_STRING],
+            ),
+        ] {
+            let err = with_env(&[], || resolve_credentials(&config)).expect_err("no credentials");
+            let message = err.to_string();
+            assert!(message.starts_with("no credentials found"), "{message}");
+            for needle in needles {
+                assert!(
+                    message.contains(needle),
+                    "{message} should mention {needle}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn debug_output_redacts_secrets() {
+        let debug = format!("{:?}", access_key("ID", "super-secret", Some("tok")));
+        assert!(debug.contains("ID"));
+        assert!(!debug.contains("super-secret"));
+        assert!(!debug.contains("tok\""));
+    }
+}
@@ -0,0 +1,121 @@
+//! In-memory [`StorageBackend`] for tests.
+
+use std::collections::BTreeMap;
+use std::ops::Bound;
+use std::sync::{Mutex, MutexGuard};
+
+use anyhow::{Context, Result};
+
+use super::{ListPage, StorageBackend, StorageType, list_all_pages};
+
+/// In-memory backend that versions every object with a counter, the way
+/// GCS generations do, so compare-and-swap paths can be exercised.
+///
+/// With [`with_page_size`](Self::with_page_size) set, listings are split
+/// into pages like a cloud bucket's, using the last key of each page as the
+/// continuation token.
+#[derive(Default)]
+pub(crate) struct MemoryStorage {
+    objects: Mutex<BTreeMap<String, (Vec<u8>, u64)>>,
+    next_version: Mutex<u64>,
+    page_size: Option<usize>,
+}
+
+impl MemoryStorage {
+    /// Return at most `page_size` keys per `list_paginated` page.
+    pub(crate) fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = Some(page_size.max(1));
+        self
+    }
+
+    fn objects(&self) -> Result<MutexGuard<'_, BTreeMap<String, (Vec<u8>, u64)>>> {
+        self.objects
+            .lock()
+            .map_err(|_| anyhow::anyhow!("memory storage lock poisoned"))
+    }
+
+    fn bump(&self) -> Result<u64> {
+        let mut next = self
+            .next_version
+            .lock()
+            .map_err(|_| anyhow::anyhow!("memory storage version lock poisoned"))?;
+        *next += 1;
+        Ok(*next)
+    }
+}
+
+impl StorageBackend for MemoryStorage {
+    fn read(&self, path: &str) -> Result<Vec<u8>> {
+        self.objects()?
+            .get(path)
+            .map(|(data, _)| data.clone())
+            .with_context(|| format!("missing {path}"))
+    }
+
+    fn write(&self, path: &str, data: &[u8]) -> Result<()> {
+        let version = self.bump()?;
+        self.objects()?
+            .insert(path.to_string(), (data.to_vec(), version));
+        Ok(())
+    }
+
+    fn delete(&self, path: &str) -> Result<()> {
+        self.objects()?.remove(path);
+        Ok(())
+    }
+
+    fn exists(&self, path: &str) -> Result<bool> {
+        Ok(self.objects()?.contains_key(path))
+    }
+
+    fn write_if_version(
+        &self,
+        path: &str,
+        data: &[u8],
+        expected_etag: Option<String>,
+    ) -> Result<Option<String>> {
+        let mut objects = self.objects()?;
+        let current = objects.get(path).map(|(_, version)| version.to_string());
+        if current != expected_etag {
+            return Ok(None);
+        }
+        let version = self.bump()?;
+        objects.insert(path.to_string(), (data.to_vec(), version));
+        Ok(Some(version.to_string()))
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        list_all_pages(self, prefix)
+    }
+
+    fn list_paginated(&self, prefix: &str, continuation: Option<String>) -> Result<ListPage> {
+        let objects = self.objects()?;
+        let after = match &continuation {
+            Some(last) => Bound::Excluded(last.as_str()),
+            None => Bound::Unbounded,
+        };
+        let mut keys = objects
+            .range::<str, _>((after, Bound::Unbounded))
+            .map(|(key, _)| key)
+            .filter(|key| key.starts_with(prefix));
+        let limit = self.page_size.unwrap_or(usize::MAX);
+        let page: Vec<String> = keys.by_ref().take(limit).cloned().collect();
+        let next = match keys.next() {
+            Some(_) => page.last().cloned(),
+            None => None,
+        };
+        Ok(ListPage { keys: page, next })
+    }
+
+    fn storage_type(&self) -> StorageType {
+        StorageType::File
+    }
+
+    fn bucket(&self) -> &str {
+        "memory"
+    }
+
+    fn base_path(&self) -> &str {
+        ""
+    }
+}
@@ -1,12 +1,12 @@
-//! Storage backend trait and filesystem-backed implementation.
+//! Storage backend trait with filesystem and GCS implementations.
 //!
 //! **Layer:** ops (internal)
 //!
 //! This module was the runtime portion of the standalone `shipper-storage`
-//! crate. It is now crate-private inside `shipper` because only filesystem
-//! storage is fully implemented — cloud backends (S3/GCS/Azure) currently
-//! bail with "not yet implemented". Promising a public `StorageBackend`
-//! trait via crates.io would freeze a half-finished design.
+//! crate. It is now crate-private inside `shipper` because the cloud backends
+//! are incomplete — GCS ([`gcs`]) and Azure Blob ([`azure`]) are implemented,
+//! while S3 still bails with "not yet implemented". Promising a public
+//! `StorageBackend` trait via crates.io would freeze a half-finished design.
 //!
 //! The configuration data types (`CloudStorageConfig`, `StorageType`) live
 //! in the stable `shipper_types::storage` contract crate — embedders can
@@ -16,14 +16,29 @@ use std::env;
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
 
 pub(crate) use shipper_types::storage::{CloudStorageConfig, StorageType};
 
+mod azure;
+mod compression;
+pub(crate) mod credentials;
+mod gcs;
+#[cfg(test)]
+pub(crate) mod memory;
+mod read_only;
+mod rest;
+
+pub(crate) use azure::AzureBlobStorage;
+pub(crate) use compression::CompressingStorage;
+pub(crate) use gcs::GcsStorage;
+pub(crate) use read_only::ReadOnlyStorage;
+
 /// Common trait for all storage backends.
 ///
 /// Provides a unified interface for storage operations across different
-/// providers. Today only the filesystem implementation is real; S3/GCS/Azure
-/// adapters are stubbed in [`build_storage_backend`] pending implementation.
+/// providers. Filesystem, GCS and Azure implementations are real; the S3
+/// adapter is stubbed in [`build_storage_backend`] pending implementation.
 pub(crate) trait StorageBackend: Send + Sync {
     /// Read data from storage at the given path
     fn read(&self, path: &str) -> Result<Vec<u8>>;
@@ -37,9 +52,47 @@ pub(crate) trait StorageBackend: Send + Sync {
     /// Check if data exists at the given path
     fn exists(&self, path: &str) -> Result<bool>;
 
+    /// Compare-and-swap write for optimistic locking across machines.
+    ///
+    /// Writes `data` only if the object's current version matches
+    /// `expected_etag`; `None` means the object must not exist yet. Returns
+    /// the new version on success and `Ok(None)` on a version conflict, in
+    /// which case nothing was written.
+    ///
+    /// The default refuses: a backend that cannot check versions must not
+    /// pretend to. Backends with server-side preconditions (GCS
+    /// generations, Azure ETags) and [`FileStorage`] override it.
+    fn write_if_version(
+        &self,
+        path: &str,
+        data: &[u8],
+        expected_etag: Option<String>,
+    ) -> Result<Option<String>> {
+        let _ = (data, expected_etag);
+        anyhow::bail!(
+            "{} storage does not support conditional writes ({path})",
+            self.storage_type()
+        )
+    }
+
     /// List all paths matching a prefix
     fn list(&self, prefix: &str) -> Result<Vec<String>>;
 
+    /// List one page of paths matching a prefix.
+    ///
+    /// Pass `None` for the first page, then each returned
+    /// [`ListPage::next`] until it is `None`. Cloud backends map this onto
+    /// their provider's continuation tokens, so a huge bucket never has to
+    /// be held in memory at once. The default returns everything from
+    /// [`list`](Self::list) as a single page.
+    fn list_paginated(&self, prefix: &str, continuation: Option<String>) -> Result<ListPage> {
+        let _ = continuation;
+        Ok(ListPage {
+            keys: self.list(prefix)?,
+            next: None,
+        })
+    }
+
     /// Get the storage type
     fn storage_type(&self) -> StorageType;
 
@@ -62,6 +115,91 @@ pub(crate) trait StorageBackend: Send + Sync {
     }
 }
 
+/// One page of a [`StorageBackend::list_paginated`] listing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ListPage {
+    /// Paths on this page.
+    pub(crate) keys: Vec<String>,
+    /// Continuation token for the next page, or `None` on the last page.
+    pub(crate) next: Option<String>,
+}
+
+/// Drain every [`StorageBackend::list_paginated`] page for `prefix`.
+///
+/// Paginating backends implement [`StorageBackend::list`] with this.
+pub(crate) fn list_all_pages<B: StorageBackend + ?Sized>(
+    backend: &B,
+    prefix: &str,
+) -> Result<Vec<String>> {
+    let mut keys = Vec::new();
+    let mut continuation = None;
+    loop {
+        let page = backend.list_paginated(prefix, continuation)?;
+        keys.extend(page.keys);
+        match page.next {
+            Some(next) => continuation = Some(next),
+            None => return Ok(keys),
+        }
+    }
+}
+
+/// Boxed backends are backends too, so wrappers like [`ReadOnlyStorage`]
+/// can sit on top of whatever [`build_storage_backend`] picked.
+impl<B: StorageBackend + ?Sized> StorageBackend for Box<B> {
+    fn read(&self, path: &str) -> Result<Vec<u8>> {
+        (**self).read(path)
+    }
+
+    fn write(&self, path: &str, data: &[u8]) -> Result<()> {
+        (**self).write(path, data)
+    }
+
+    fn delete(&self, path: &str) -> Result<()> {
+        (**self).delete(path)
+    }
+
+    fn exists(&self, path: &str) -> Result<bool> {
+        (**self).exists(path)
+    }
+
+    fn write_if_version(
+        &self,
+        path: &str,
+        data: &[u8],
+        expected_etag: Option<String>,
+    ) -> Result<Option<String>> {
+        (**self).write_if_version(path, data, expected_etag)
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        (**self).list(prefix)
+    }
+
+    fn list_paginated(&self, prefix: &str, continuation: Option<String>) -> Result<ListPage> {
+        (**self).list_paginated(prefix, continuation)
+    }
+
+    fn storage_type(&self) -> StorageType {
+        (**self).storage_type()
+    }
+
+    fn bucket(&self) -> &str {
+        (**self).bucket()
+    }
+
+    fn base_path(&self) -> &str {
+        (**self).base_path()
+    }
+
+    fn copy(&self, from: &str, to: &str) -> Result<()> {
+        (**self).copy(from, to)
+    }
+
+    fn mv(&self, from: &str, to: &str) -> Result<()> {
+        (**self).mv(from, to)
+    }
+}
+
 /// Filesystem-based storage backend. Writes atomically via temp file + rename.
 #[derive(Debug, Clone)]
 pub(crate) struct FileStorage {
@@ -159,6 +297,28 @@ impl StorageBackend for FileStorage {
         Ok(full_path.exists())
     }
 
+    /// Versions are the SHA-256 of the file contents. The check and the
+    /// rename are separate steps, so this guards against stale writers on
+    /// one machine (under the publish lock), not against a concurrent
+    /// writer racing between them.
+    fn write_if_version(
+        &self,
+        path: &str,
+        data: &[u8],
+        expected_etag: Option<String>,
+    ) -> Result<Option<String>> {
+        let current = if self.exists(path)? {
+            Some(hex::encode(Sha256::digest(self.read(path)?)))
+        } else {
+            None
+        };
+        if current != expected_etag {
+            return Ok(None);
+        }
+        self.write(path, data)?;
+        Ok(Some(hex::encode(Sha256::digest(data))))
+    }
+
     fn list(&self, prefix: &str) -> Result<Vec<String>> {
         let base = self.base_path.join(prefix);
         let mut results = Vec::new();
@@ -202,10 +362,13 @@ impl StorageBackend for FileStorage {
 
 /// Build a storage backend from configuration.
 ///
-/// Currently only filesystem storage is fully implemented. S3/GCS/Azure
-/// return an error — the trait exists so future cloud backends can plug in
-/// without breaking embedders that already depend on the stable config
-/// types in `shipper_types::storage`.
+/// Filesystem, GCS and Azure storage are implemented. S3 returns an error —
+/// the trait exists so future cloud backends can plug in without breaking
+/// embedders that already depend on the stable config types in
+/// `shipper_types::storage`.
+///
+/// With `compression_level` set, blobs go through [`CompressingStorage`];
+/// with `read_only` set, the backend is wrapped in [`ReadOnlyStorage`].
 pub(crate) fn build_storage_backend(
     config: &CloudStorageConfig,
 ) -> Result<Box<dyn StorageBackend>> {
@@ -213,17 +376,23 @@ pub(crate) fn build_storage_backend(
         .validate()
         .map_err(|e| anyhow::anyhow!(e.to_string()))?;
 
-    match config.storage_type {
-        StorageType::File => Ok(Box::new(FileStorage::new(PathBuf::from(&config.base_path)))),
+    let backend: Box<dyn StorageBackend> = match config.storage_type {
+        StorageType::File => Box::new(FileStorage::new(PathBuf::from(&config.base_path))),
         StorageType::S3 => {
             anyhow::bail!("S3 storage is not yet implemented. Use file storage for now.")
         }
-        StorageType::Gcs => {
-            anyhow::bail!("GCS storage is not yet implemented. Use file storage for now.")
-        }
-        StorageType::Azure => {
-            anyhow::bail!("Azure storage is not yet implemented. Use file storage for now.")
-        }
+        StorageType::Gcs => Box::new(GcsStorage::new(config)?),
+        StorageType::Azure => Box::new(AzureBlobStorage::new(config)?),
+    };
+    let backend: Box<dyn StorageBackend> = match config.compression_level {
+        Some(level) => Box::new(CompressingStorage::new(backend).with_level(level)),
+        None => backend,
+    };
+
+    if config.read_only {
+        Ok(Box::new(ReadOnlyStorage::new(backend)))
+    } else {
+        Ok(backend)
     }
 }
 
@@ -237,10 +406,11 @@ pub(crate) fn build_storage_backend(
 /// - `SHIPPER_STORAGE_BUCKET`: bucket/container name (required when type is set)
 /// - `SHIPPER_STORAGE_REGION`: region (for S3) or project ID (for GCS)
 /// - `SHIPPER_STORAGE_BASE_PATH`: base path within bucket or local path
-/// - `SHIPPER_STORAGE_ENDPOINT`: custom endpoint (for S3-compatible services)
+/// - `SHIPPER_STORAGE_ENDPOINT`: custom endpoint (S3-compatible services, GCS emulators, Azurite)
 /// - `SHIPPER_STORAGE_ACCESS_KEY_ID`: access key ID
 /// - `SHIPPER_STORAGE_SECRET_ACCESS_KEY`: secret access key
 /// - `SHIPPER_STORAGE_SESSION_TOKEN`: session token (optional)
+/// - `SHIPPER_STORAGE_COMPRESSION_LEVEL`: gzip stored blobs at this level, 0-9 (optional)
 pub(crate) fn config_from_env() -> Option<CloudStorageConfig> {
     let storage_type_str = env::var("SHIPPER_STORAGE_TYPE").ok()?;
     let storage_type = match storage_type_str.as_str() {
@@ -272,15 +442,158 @@ pub(crate) fn config_from_env() -> Option<CloudStorageConfig> {
     if let Ok(session_token) = env::var("SHIPPER_STORAGE_SESSION_TOKEN") {
         config.session_token = Some(session_token);
     }
+    if let Some(level) = env::var("SHIPPER_STORAGE_COMPRESSION_LEVEL")
+        .ok()
+        .and_then(|level| level.parse().ok())
+    {
+        config.compression_level = Some(level);
+    }
 
     Some(config)
 }
 
 #[cfg(test)]
 mod tests {
+    use super::memory::MemoryStorage;
     use super::*;
     use tempfile::tempdir;
 
+    #[test]
+    fn write_if_version_creates_only_when_absent() {
+        let storage = MemoryStorage::default();
+
+        let etag = storage
+            .write_if_version("state.json", b"v1", None)
+            .expect("create")
+            .expect("created");
+        assert_eq!(storage.read("state.json").expect("read"), b"v1");
+
+        // A second "create" loses: the object exists now.
+        assert_eq!(
+            storage
+                .write_if_version("state.json", b"other", None)
+                .expect("conflict"),
+            None
+        );
+        assert_eq!(storage.read("state.json").expect("read"), b"v1");
+        assert!(!etag.is_empty());
+    }
+
+    #[test]
+    fn write_if_version_succeeds_with_current_etag() {
+        let storage = MemoryStorage::default();
+        let v1 = storage
+            .write_if_version("state.json", b"v1", None)
+            .expect("create")
+            .expect("created");
+
+        let v2 = storage
+            .write_if_version("state.json", b"v2", Some(v1.clone()))
+            .expect("update")
+            .expect("updated");
+        assert_ne!(v1, v2);
+        assert_eq!(storage.read("state.json").expect("read"), b"v2");
+    }
+
+    #[test]
+    fn write_if_version_reports_conflict_on_stale_etag() {
+        let storage = MemoryStorage::default();
+        let v1 = storage
+            .write_if_version("state.json", b"v1", None)
+            .expect("create")
+            .expect("created");
+
+        // Runner A updates first...
+        storage
+            .write_if_version("state.json", b"runner-a", Some(v1.clone()))
+            .expect("update")
+            .expect("runner A wins");
+        // ...so runner B's update against the same base version conflicts.
+        assert_eq!(
+            storage
+                .write_if_version("state.json", b"runner-b", Some(v1))
+                .expect("conflict"),
+            None
+        );
+        assert_eq!(storage.read("state.json").expect("read"), b"runner-a");
+    }
+
+    #[test]
+    fn file_storage_write_if_version_checks_content_hash() {
+        let td = tempdir().expect("tempdir");
+        let storage = FileStorage::new(td.path().to_path_buf());
+
+        let v1 = storage
+            .write_if_version("state.json", b"old", None)
+            .expect("create")
+            .expect("created");
+        assert_eq!(v1, hex::encode(Sha256::digest(b"old")));
+        assert_eq!(
+            storage
+                .write_if_version("state.json", b"other", None)
+                .expect("conflict"),
+            None
+        );
+        assert_eq!(
+            storage
+                .write_if_version("state.json", b"new", Some("stale".to_string()))
+                .expect("conflict"),
+            None
+        );
+        assert_eq!(storage.read("state.json").expect("read"), b"old");
+
+        let v2 = storage
+            .write_if_version("state.json", b"new", Some(v1))
+            .expect("update")
+            .expect("updated");
+        assert_eq!(v2, hex::encode(Sha256::digest(b"new")));
+        assert_eq!(storage.read("state.json").expect("read"), b"new");
+    }
+
+    /// A backend that only implements the required methods.
+    struct Unversioned(MemoryStorage);
+
+    impl StorageBackend for Unversioned {
+        fn read(&self, path: &str) -> Result<Vec<u8>> {
+            self.0.read(path)
+        }
+        fn write(&self, path: &str, data: &[u8]) -> Result<()> {
+            self.0.write(path, data)
+        }
+        fn delete(&self, path: &str) -> Result<()> {
+            self.0.delete(path)
+        }
+        fn exists(&self, path: &str) -> Result<bool> {
+            self.0.exists(path)
+        }
+        fn list(&self, prefix: &str) -> Result<Vec<String>> {
+            self.0.list(prefix)
+        }
+        fn storage_type(&self) -> StorageType {
+            StorageType::S3
+        }
+        fn bucket(&self) -> &str {
+            "unversioned"
+        }
+        fn base_path(&self) -> &str {
+            ""
+        }
+    }
+
+    #[test]
+    fn write_if_version_default_refuses_without_writing() {
+        let storage = Unversioned(MemoryStorage::default());
+        let err = storage
+            .write_if_version("state.json", b"new", None)
+            .expect_err("unsupported");
+        assert!(
+            err.to_string()
+                .contains("does not support conditional writes"),
+            "{err}"
+        );
+        assert!(!storage.exists("state.json").expect("exists"));
+    }
+
     #[test]
     fn file_storage_new_and_paths() {
         let td = tempdir().expect("tempdir");
@@ -373,6 +686,56 @@ mod tests {
         assert_eq!(files.len(), 2);
     }
 
+    #[test]
+    fn file_storage_list_paginated_returns_a_single_page() {
+        let td = tempdir().expect("tempdir");
+        let storage = FileStorage::new(td.path().to_path_buf());
+        storage.write("state/a.json", b"a").expect("write");
+        storage.write("state/b.json", b"b").expect("write");
+
+        let page = storage.list_paginated("state", None).expect("page");
+        assert_eq!(page.keys.len(), 2);
+        assert_eq!(page.next, None);
+    }
+
+    #[test]
+    fn memory_storage_list_paginated_walks_pages_by_continuation() {
+        let storage = memory::MemoryStorage::default().with_page_size(2);
+        for key in ["runs/a", "runs/b", "runs/c", "runs/d", "runs/e", "other/x"] {
+            storage.write(key, b"{}").expect("write");
+        }
+
+        let first = storage.list_paginated("runs/", None).expect("page 1");
+        assert_eq!(first.keys, ["runs/a", "runs/b"]);
+        let second = storage
+            .list_paginated("runs/", first.next.clone())
+            .expect("page 2");
+        assert_eq!(second.keys, ["runs/c", "runs/d"]);
+        let third = storage
+            .list_paginated("runs/", second.next)
+            .expect("page 3");
+        assert_eq!(third.keys, ["runs/e"]);
+        assert_eq!(third.next, None);
+
+        assert_eq!(
+            storage.list("runs/").expect("list"),
+            ["runs/a", "runs/b", "runs/c", "runs/d", "runs/e"]
+        );
+    }
+
+    #[test]
+    fn list_paginated_passes_through_wrappers() {
+        let inner = memory::MemoryStorage::default().with_page_size(1);
+        inner.write("runs/a", b"{}").expect("write");
+        inner.write("runs/b", b"{}").expect("write");
+        let storage: Box<dyn StorageBackend> = Box::new(ReadOnlyStorage::new(inner));
+
+        let first = storage.list_paginated("runs/", None).expect("page 1");
+        assert_eq!(first.keys, ["runs/a"]);
+        assert_eq!(first.next.as_deref(), Some("runs/a"));
+        assert_eq!(storage.list("runs/").expect("list"), ["runs/a", "runs/b"]);
+    }
+
     #[test]
     fn file_storage_copy() {
         let td = tempdir().expect("tempdir");
@@ -414,6 +777,40 @@ mod tests {
         assert_eq!(backend.storage_type(), StorageType::File);
     }
 
+    #[test]
+    fn build_storage_backend_read_only_refuses_writes() {
+        let td = tempdir().expect("tempdir");
+        FileStorage::new(td.path().to_path_buf())
+            .write("state.json", b"{}")
+            .expect("seed");
+
+        let config =
+            CloudStorageConfig::file(td.path().to_str().expect("utf8")).with_read_only(true);
+        let backend = build_storage_backend(&config).expect("build");
+        assert_eq!(backend.storage_type(), StorageType::File);
+        assert_eq!(backend.read("state.json").expect("read"), b"{}");
+        let err = backend.write("state.json", b"new").expect_err("read-only");
+        assert!(err.to_string().contains("storage is read-only"), "{err}");
+    }
+
+    #[test]
+    fn build_storage_backend_compresses_when_a_level_is_set() {
+        let td = tempdir().expect("tempdir");
+        let json = serde_json::to_vec(&vec!["shipper"; 200]).expect("json");
+
+        let config =
+            CloudStorageConfig::file(td.path().to_str().expect("utf8")).with_compression(9);
+        let backend = build_storage_backend(&config).expect("build");
+        backend.write("receipt.json", &json).expect("write");
+        assert_eq!(backend.read("receipt.json").expect("read"), json);
+
+        let stored = FileStorage::new(td.path().to_path_buf())
+            .read("receipt.json")
+            .expect("raw read");
+        assert!(stored.len() < json.len());
+        assert_eq!(stored[..2], [0x1f, 0x8b]);
+    }
+
     #[test]
     fn build_storage_backend_s3_not_implemented() {
         let config = CloudStorageConfig::s3("bucket");
@@ -421,13 +818,33 @@ mod tests {
     }
 
     #[test]
-    fn build_storage_backend_gcs_not_implemented() {
-        let config = CloudStorageConfig::gcs("bucket");
-        assert!(build_storage_backend(&config).is_err());
+    fn build_storage_backend_gcs() {
+        let config = CloudStorageConfig::gcs("bucket").with_endpoint("http://127.0.0.1:4443");
+        let empty = tempdir().expect("tempdir");
+        let backend = temp_env::with_vars(
+            [
+                ("GOOGLE_APPLICATION_CREDENTIALS", None),
+                ("CLOUDSDK_CONFIG", Some(empty.path().as_os_str())),
+            ],
+            || build_storage_backend(&config),
+        )
+        .expect("build");
+        assert_eq!(backend.storage_type(), StorageType::Gcs);
+        assert_eq!(backend.bucket(), "bucket");
+    }
+
+    #[test]
+    fn build_storage_backend_azure() {
+        let config = CloudStorageConfig::azure("container")
+            .with_endpoint("http://127.0.0.1:10000/devstoreaccount1")
+            .with_session_token("sv=1&sig=x");
+        let backend = build_storage_backend(&config).expect("build");
+        assert_eq!(backend.storage_type(), StorageType::Azure);
+        assert_eq!(backend.bucket(), "container");
     }
 
     #[test]
-    fn build_storage_backend_azure_not_implemented() {
+    fn build_storage_backend_azure_requires_credentials() {
         let config = CloudStorageConfig::azure("container");
         assert!(build_storage_backend(&config).is_err());
     }
@@ -577,6 +994,7 @@ mod tests {
                 ("SHIPPER_STORAGE_ACCESS_KEY_ID", Some("AKIA123")),
                 ("SHIPPER_STORAGE_SECRET_ACCESS_KEY", Some("secret")),
                 ("SHIPPER_STORAGE_SESSION_TOKEN", None::<&str>),
+                ("SHIPPER_STORAGE_COMPRESSION_LEVEL", Some("9")),
             ],
             || {
                 let config = config_from_env().expect("config");
@@ -585,6 +1003,7 @@ mod tests {
                 assert_eq!(config.region, Some("us-west-2".to_string()));
                 assert_eq!(config.base_path, "state");
                 assert_eq!(config.access_key_id, Some("AKIA123".to_string()));
+                assert_eq!(config.compression_level, Some(9));
             },
         );
     }
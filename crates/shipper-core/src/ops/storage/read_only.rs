@@ -0,0 +1,131 @@
+//! Read-only view over any [`StorageBackend`].
+
+use anyhow::{Result, bail};
+
+use super::{ListPage, StorageBackend, StorageType};
+
+/// Wraps a backend so every mutation fails and reads pass through.
+///
+/// `write`, `write_if_version`, `delete`, `copy` and `mv` return a
+/// "storage is read-only" error without touching the inner backend, so a
+/// run inspecting another runner's shared state cannot clobber it. `read`,
+/// `exists`, `list` and the metadata accessors delegate unchanged.
+pub(crate) struct ReadOnlyStorage<B> {
+    inner: B,
+}
+
+impl<B: StorageBackend> ReadOnlyStorage<B> {
+    /// Wrap `inner` read-only.
+    pub(crate) fn new(inner: B) -> Self {
+        Self { inner }
+    }
+
+    /// The wrapped backend.
+    pub(crate) fn inner(&self) -> &B {
+        &self.inner
+    }
+}
+
+impl<B: StorageBackend> StorageBackend for ReadOnlyStorage<B> {
+    fn read(&self, path: &str) -> Result<Vec<u8>> {
+        self.inner.read(path)
+    }
+
+    fn write(&self, path: &str, _data: &[u8]) -> Result<()> {
+        bail!("storage is read-only: refusing to write {path}")
+    }
+
+    fn write_if_version(
+        &self,
+        path: &str,
+        _data: &[u8],
+        _expected_etag: Option<String>,
+    ) -> Result<Option<String>> {
+        bail!("storage is read-only: refusing to write {path}")
+    }
+
+    fn delete(&self, path: &str) -> Result<()> {
+        bail!("storage is read-only: refusing to delete {path}")
+    }
+
+    fn exists(&self, path: &str) -> Result<bool> {
+        self.inner.exists(path)
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        self.inner.list(prefix)
+    }
+
+    fn list_paginated(&self, prefix: &str, continuation: Option<String>) -> Result<ListPage> {
+        self.inner.list_paginated(prefix, continuation)
+    }
+
+    fn storage_type(&self) -> StorageType {
+        self.inner.storage_type()
+    }
+
+    fn bucket(&self) -> &str {
+        self.inner.bucket()
+    }
+
+    fn base_path(&self) -> &str {
+        self.inner.base_path()
+    }
+
+    fn copy(&self, from: &str, to: &str) -> Result<()> {
+        bail!("storage is read-only: refusing to copy {from} to {to}")
+    }
+
+    fn mv(&self, from: &str, to: &str) -> Result<()> {
+        bail!("storage is read-only: refusing to move {from} to {to}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::storage::FileStorage;
+    use tempfile::tempdir;
+
+    fn seeded() -> (tempfile::TempDir, ReadOnlyStorage<FileStorage>) {
+        let td = tempdir().expect("tempdir");
+        let inner = FileStorage::new(td.path().to_path_buf());
+        inner.write("runs/state.json", b"{}").expect("seed");
+        (td, ReadOnlyStorage::new(inner))
+    }
+
+    #[test]
+    fn mutations_are_refused_and_leave_data_untouched() {
+        let (_td, storage) = seeded();
+
+        let err = storage.write("runs/state.json", b"new").expect_err("write");
+        assert_eq!(
+            err.to_string(),
+            "storage is read-only: refusing to write runs/state.json"
+        );
+        assert!(
+            storage
+                .write_if_version("runs/new.json", b"new", None)
+                .is_err()
+        );
+        assert!(storage.delete("runs/state.json").is_err());
+        assert!(storage.copy("runs/state.json", "runs/b.json").is_err());
+        assert!(storage.mv("runs/state.json", "runs/b.json").is_err());
+
+        assert_eq!(storage.inner().read("runs/state.json").expect("raw"), b"{}");
+        assert!(!storage.inner().exists("runs/new.json").expect("exists"));
+        assert!(!storage.inner().exists("runs/b.json").expect("exists"));
+    }
+
+    #[test]
+    fn reads_pass_through() {
+        let (td, storage) = seeded();
+
+        assert_eq!(storage.read("runs/state.json").expect("read"), b"{}");
+        assert!(storage.exists("runs/state.json").expect("exists"));
+        assert_eq!(storage.list("runs").expect("list"), vec!["runs/state.json"]);
+        assert_eq!(storage.storage_type(), StorageType::File);
+        assert_eq!(storage.bucket(), "local");
+        assert_eq!(storage.base_path(), td.path().to_str().expect("utf8"));
+    }
+}
@@ -0,0 +1,89 @@
+//! HTTP helpers shared by the cloud storage backends.
+
+use anyhow::{Result, bail};
+use hmac::{Hmac, KeyInit, Mac};
+use reqwest::blocking::Response;
+use sha2::Sha256;
+
+/// Turn a non-2xx response into an error carrying the status and body.
+pub(super) fn expect_success(response: Response, action: &str) -> Result<Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().unwrap_or_default();
+    bail!(
+        "storage backend returned {status} while {action}: {}",
+        body.trim()
+    )
+}
+
+/// HMAC-SHA256 of `data` under `key`.
+pub(super) fn hmac_sha256(key: &[u8], data: &[u8]) -> Result<Vec<u8>> {
+    let mut mac = <Hmac<Sha256> as KeyInit>::new_from_slice(key)
+        .map_err(|e| anyhow::anyhow!("invalid HMAC key: {e}"))?;
+    mac.update(data);
+    Ok(mac.finalize().into_bytes().to_vec())
+}
+
+/// Percent-encode everything except RFC 3986 unreserved characters
+/// (and `/` when `keep_slash` is set).
+pub(super) fn uri_encode(value: &str, keep_slash: bool) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char)
+            }
+            b'/' if keep_slash => out.push('/'),
+            _ => out.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    out
+}
+
+/// Text of every `<tag>…</tag>` element, XML entities unescaped.
+pub(super) fn xml_elements(xml: &str, tag: &str) -> Vec<String> {
+    let open = format!("<{tag}>");
+    let close = format!("</{tag}>");
+    let mut values = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find(&open) {
+        let Some(after) = rest.get(start + open.len()..) else {
+            break;
+        };
+        let Some((text, tail)) = after
+            .find(&close)
+            .and_then(|end| Some((after.get(..end)?, after.get(end + close.len()..)?)))
+        else {
+            break;
+        };
+        values.push(
+            text.replace("&lt;", "<")
+                .replace("&gt;", ">")
+                .replace("&quot;", "\"")
+                .replace("&apos;", "'")
+                .replace("&amp;", "&"),
+        );
+        rest = tail;
+    }
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uri_encode_keeps_unreserved_characters() {
+        assert_eq!(uri_encode("a b/c~d", false), "a%20b%2Fc~d");
+        assert_eq!(uri_encode("a b/c~d", true), "a%20b/c~d");
+    }
+
+    #[test]
+    fn xml_elements_collects_every_match_and_unescapes() {
+        let xml = "<R><Name>a&amp;b</Name><Name>c</Name><Other>x</Other></R>";
+        assert_eq!(xml_elements(xml, "Name"), vec!["a&b", "c"]);
+        assert!(xml_elements(xml, "Missing").is_empty());
+    }
+}
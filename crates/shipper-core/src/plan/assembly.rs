@@ -1,7 +1,6 @@
 use std::collections::{BTreeMap, BTreeSet};
 
 use cargo_metadata::PackageId;
-use sha2::{Digest, Sha256};
 use shipper_types::PlannedPackage;
 
 use anyhow::Result;
@@ -60,17 +59,3 @@ pub(super) fn dependency_map(
 
     Ok(dependencies)
 }
-
-pub(super) fn compute_plan_id(registry_api_base: &str, packages: &[PlannedPackage]) -> String {
-    let mut hasher = Sha256::new();
-    hasher.update(registry_api_base.as_bytes());
-    hasher.update(b"\n");
-    for p in packages {
-        hasher.update(p.name.as_bytes());
-        hasher.update(b"@");
-        hasher.update(p.version.as_bytes());
-        hasher.update(b"\n");
-    }
-    let digest = hasher.finalize();
-    hex::encode(digest)
-}
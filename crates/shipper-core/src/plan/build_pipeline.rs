@@ -5,7 +5,7 @@ use cargo_metadata::PackageId;
 use chrono::Utc;
 use shipper_types::{PlannedWorkspace, ReleasePlan, ReleaseSpec};
 
-use super::assembly::{compute_plan_id, dependency_map, planned_packages};
+use super::assembly::{dependency_map, planned_packages};
 use super::graph::{build_dependency_graph, topo_sort, validate_publishable_dependencies};
 use super::metadata::load_metadata;
 use super::publishability::analyze_publishability;
@@ -14,6 +14,7 @@ use super::selection::resolve_included_packages;
 pub(super) fn build_plan(spec: &ReleaseSpec) -> Result<PlannedWorkspace> {
     let metadata = load_metadata(&spec.manifest_path)?;
     let workspace_root = metadata.workspace_root.clone().into_std_path_buf();
+    let target_directory = metadata.target_directory.clone().into_std_path_buf();
 
     let pkg_map = metadata
         .packages
@@ -36,10 +37,11 @@ pub(super) fn build_plan(spec: &ReleaseSpec) -> Result<PlannedWorkspace> {
     let order = topo_sort(&included, &graph.deps_of, &graph.dependents_of, &pkg_map)?;
     let packages = planned_packages(&order, &pkg_map)?;
     let dependencies = dependency_map(&order, &included, &graph.deps_of, &pkg_map)?;
-    let plan_id = compute_plan_id(&spec.registry.api_base, &packages);
+    let plan_id = ReleasePlan::compute_id(&spec.registry, &packages, &dependencies);
 
     Ok(PlannedWorkspace {
         workspace_root,
+        target_directory,
         plan: ReleasePlan {
             plan_version: crate::state::execution_state::CURRENT_PLAN_VERSION.to_string(),
             plan_id,
@@ -51,9 +51,9 @@ mod publishability;
 mod selection;
 
 #[cfg(test)]
-use publishability::publish_allowed;
+use graph::topo_sort;
 #[cfg(test)]
-use {assembly::compute_plan_id, graph::topo_sort};
+use publishability::publish_allowed;
 
 #[cfg(test)]
 mod tests {
@@ -62,11 +62,20 @@ mod tests {
 
     use cargo_metadata::{MetadataCommand, PackageId};
     use proptest::prelude::*;
-    use shipper_types::{PlannedPackage, Registry};
+    use shipper_types::{PlannedPackage, Registry, ReleasePlan};
     use tempfile::tempdir;
 
     use super::*;
 
+    /// Plan id for `packages` on a registry at `api_base` with no dependency edges.
+    fn compute_plan_id(api_base: &str, packages: &[PlannedPackage]) -> String {
+        let registry = Registry {
+            api_base: api_base.to_string(),
+            ..Registry::crates_io()
+        };
+        ReleasePlan::compute_id(&registry, packages, &BTreeMap::new())
+    }
+
     fn write_file(path: &Path, content: &str) {
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent).expect("mkdir");
@@ -543,6 +552,17 @@ edition = "2021"
         assert_eq!(ws1.plan.dependencies, ws2.plan.dependencies);
     }
 
+    #[test]
+    fn build_plan_id_is_computed_from_plan_content() {
+        let td = tempdir().expect("tempdir");
+        create_workspace(td.path());
+        let ws = build_plan(&spec_for(td.path())).expect("plan");
+
+        let expected =
+            ReleasePlan::compute_id(&ws.plan.registry, &ws.plan.packages, &ws.plan.dependencies);
+        assert_eq!(ws.plan.plan_id, expected);
+    }
+
     // --- Skipped packages tracking ---
 
     #[test]
@@ -1949,10 +1969,10 @@ core = { path = "../core", version = "2.5.0" }
         assert_ne!(id_a, id_b);
     }
 
-    // ── compute_plan_id is order-sensitive ────────────────────────────
+    // ── compute_plan_id ignores package order ─────────────────────────
 
     #[test]
-    fn compute_plan_id_is_order_sensitive() {
+    fn compute_plan_id_is_order_insensitive() {
         let pkg_a = PlannedPackage {
             name: "aaa".to_string(),
             version: "1.0.0".to_string(),
@@ -1967,7 +1987,7 @@ core = { path = "../core", version = "2.5.0" }
         };
         let id_ab = compute_plan_id("https://crates.io", &[pkg_a.clone(), pkg_b.clone()]);
         let id_ba = compute_plan_id("https://crates.io", &[pkg_b, pkg_a]);
-        assert_ne!(id_ab, id_ba);
+        assert_eq!(id_ab, id_ba);
     }
 
     // ── compute_plan_id is valid SHA256 hex ──────────────────────────
@@ -23,7 +23,7 @@ mod tests {
                 Just(PackageState::Pending),
                 Just(PackageState::Uploaded),
                 Just(PackageState::Published),
-                Just(PackageState::Skipped { reason: "test".to_string() }),
+                Just(PackageState::Skipped { reason: SkipReason::Manual("test".to_string()) }),
                 Just(PackageState::Failed { class: ErrorClass::Retryable, message: "error".to_string() }),
                 Just(PackageState::Ambiguous { message: "maybe".to_string() }),
             ]
@@ -93,12 +93,15 @@ mod state_machine_tests {
                 PackageState::Uploaded,
                 PackageState::Published,
                 PackageState::Skipped {
-                    reason: String::new(),
+                    reason: SkipReason::Manual(String::new()),
                 },
                 PackageState::Failed {
                     class: ErrorClass::Retryable,
                     message: String::new(),
                 },
+                PackageState::Cancelled {
+                    at_state: "pending".to_string(),
+                },
             ],
             PackageState::Uploaded => vec![
                 PackageState::Published,
@@ -109,6 +112,9 @@ mod state_machine_tests {
                     class: ErrorClass::Permanent,
                     message: String::new(),
                 },
+                PackageState::Cancelled {
+                    at_state: "uploaded".to_string(),
+                },
             ],
             PackageState::Published => vec![], // Terminal state
             PackageState::Skipped { .. } => vec![], // Terminal state
@@ -120,6 +126,15 @@ mod state_machine_tests {
                     message: String::new(),
                 },
             ],
+            // Terminal for the interrupted run, picked up again on resume
+            PackageState::Cancelled { .. } => vec![
+                PackageState::Uploaded,
+                PackageState::Published,
+                PackageState::Failed {
+                    class: ErrorClass::Retryable,
+                    message: String::new(),
+                },
+            ],
         }
     }
 
@@ -150,7 +165,7 @@ mod state_machine_tests {
     #[test]
     fn test_skipped_is_terminal() {
         let skipped = PackageState::Skipped {
-            reason: "already published".into(),
+            reason: SkipReason::AlreadyPublished,
         };
         assert!(valid_transitions(&skipped).is_empty());
     }
@@ -163,6 +178,21 @@ mod state_machine_tests {
         let valid = valid_transitions(&ambiguous);
         assert!(valid.contains(&PackageState::Published));
     }
+
+    #[test]
+    fn test_cancelled_is_resumable() {
+        let uploaded = PackageState::Uploaded;
+        assert!(
+            valid_transitions(&uploaded).contains(&PackageState::Cancelled {
+                at_state: "uploaded".into(),
+            })
+        );
+
+        let cancelled = PackageState::Cancelled {
+            at_state: "uploaded".into(),
+        };
+        assert!(valid_transitions(&cancelled).contains(&PackageState::Published));
+    }
 }
 
 #[cfg(test)]
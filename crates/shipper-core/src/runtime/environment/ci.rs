@@ -16,6 +16,12 @@ pub(crate) fn get_ci_branch() -> Option<String> {
         CiEnvironment::AzurePipelines => env::var("BUILD_SOURCEBRANCHNAME").ok(),
         CiEnvironment::Jenkins => env::var("GIT_BRANCH").ok(),
         CiEnvironment::BitbucketPipelines => env::var("BITBUCKET_BRANCH").ok(),
+        CiEnvironment::Buildkite => env::var("BUILDKITE_BRANCH").ok(),
+        CiEnvironment::Woodpecker => env::var("CI_COMMIT_BRANCH").ok(),
+        CiEnvironment::Drone => env::var("DRONE_BRANCH").ok(),
+        // TeamCity does not export the branch by default; builds map
+        // `%teamcity.build.branch%` to this variable.
+        CiEnvironment::TeamCity => env::var("TEAMCITY_BUILD_BRANCH").ok(),
         CiEnvironment::Local => None,
     }
 }
@@ -32,6 +38,10 @@ pub(crate) fn get_ci_commit_sha() -> Option<String> {
         CiEnvironment::AzurePipelines => env::var("BUILD_SOURCEVERSION").ok(),
         CiEnvironment::Jenkins => env::var("GIT_COMMIT").ok(),
         CiEnvironment::BitbucketPipelines => env::var("BITBUCKET_COMMIT").ok(),
+        CiEnvironment::Buildkite => env::var("BUILDKITE_COMMIT").ok(),
+        CiEnvironment::Woodpecker => env::var("CI_COMMIT_SHA").ok(),
+        CiEnvironment::Drone => env::var("DRONE_COMMIT_SHA").ok(),
+        CiEnvironment::TeamCity => env::var("BUILD_VCS_NUMBER").ok(),
         CiEnvironment::Local => None,
     }
 }
@@ -52,10 +62,49 @@ pub(crate) fn is_pull_request() -> bool {
         CiEnvironment::AzurePipelines => env::var("BUILD_REASON")
             .map(|v| v == "PullRequest")
             .unwrap_or(false),
+        CiEnvironment::Buildkite => env::var("BUILDKITE_PULL_REQUEST")
+            .map(|v| v != "false")
+            .unwrap_or(false),
+        CiEnvironment::Woodpecker => env::var("CI_PIPELINE_EVENT")
+            .map(|v| v == "pull_request")
+            .unwrap_or(false),
+        CiEnvironment::Drone => env::var("DRONE_BUILD_EVENT")
+            .map(|v| v == "pull_request")
+            .unwrap_or(false),
         _ => false,
     }
 }
 
+/// Get the pull/merge request number from the CI environment.
+///
+/// Returns `None` on non-PR builds, on providers without a PR number
+/// variable, and when the value cannot be parsed.
+pub(crate) fn get_pr_number() -> Option<u64> {
+    let env_ = detect_environment();
+
+    match env_ {
+        // `refs/pull/<n>/merge` (or `/head`) on pull_request events
+        CiEnvironment::GitHubActions => env::var("GITHUB_REF").ok().and_then(|r| {
+            r.strip_prefix("refs/pull/")?
+                .split('/')
+                .next()?
+                .parse()
+                .ok()
+        }),
+        CiEnvironment::GitLabCI => env::var("CI_MERGE_REQUEST_IID").ok()?.parse().ok(),
+        // Full PR URL, e.g. `https://github.com/org/repo/pull/42`
+        CiEnvironment::CircleCI => env::var("CIRCLE_PULL_REQUEST")
+            .ok()?
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()?
+            .parse()
+            .ok(),
+        CiEnvironment::BitbucketPipelines => env::var("BITBUCKET_PR_ID").ok()?.parse().ok(),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -69,6 +118,11 @@ mod tests {
         "TF_BUILD",
         "JENKINS_URL",
         "BITBUCKET_BUILD_NUMBER",
+        "BUILDKITE",
+        "WOODPECKER",
+        "CI",
+        "DRONE",
+        "TEAMCITY_VERSION",
     ];
 
     fn ci_env<'a>(overrides: &'a [(&'a str, Option<&'a str>)]) -> Vec<(&'a str, Option<&'a str>)> {
@@ -183,6 +237,59 @@ mod tests {
         );
     }
 
+    #[test]
+    #[serial]
+    fn get_ci_branch_buildkite() {
+        temp_env::with_vars(
+            ci_env(&[
+                ("BUILDKITE", Some("true")),
+                ("BUILDKITE_BRANCH", Some("main")),
+            ]),
+            || {
+                assert_eq!(get_ci_branch(), Some("main".to_string()));
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn get_ci_branch_woodpecker() {
+        temp_env::with_vars(
+            ci_env(&[
+                ("WOODPECKER", Some("true")),
+                ("CI_COMMIT_BRANCH", Some("main")),
+            ]),
+            || {
+                assert_eq!(get_ci_branch(), Some("main".to_string()));
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn get_ci_branch_drone() {
+        temp_env::with_vars(
+            ci_env(&[("DRONE", Some("true")), ("DRONE_BRANCH", Some("main"))]),
+            || {
+                assert_eq!(get_ci_branch(), Some("main".to_string()));
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn get_ci_branch_teamcity() {
+        temp_env::with_vars(
+            ci_env(&[
+                ("TEAMCITY_VERSION", Some("2024.03")),
+                ("TEAMCITY_BUILD_BRANCH", Some("main")),
+            ]),
+            || {
+                assert_eq!(get_ci_branch(), Some("main".to_string()));
+            },
+        );
+    }
+
     #[test]
     #[serial]
     fn get_ci_branch_returns_none_for_local() {
@@ -326,6 +433,62 @@ mod tests {
         );
     }
 
+    #[test]
+    #[serial]
+    fn get_ci_commit_sha_buildkite() {
+        temp_env::with_vars(
+            ci_env(&[
+                ("BUILDKITE", Some("true")),
+                ("BUILDKITE_COMMIT", Some("abc123")),
+            ]),
+            || {
+                assert_eq!(get_ci_commit_sha(), Some("abc123".to_string()));
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn get_ci_commit_sha_woodpecker() {
+        temp_env::with_vars(
+            ci_env(&[
+                ("WOODPECKER", Some("true")),
+                ("CI_COMMIT_SHA", Some("abc123")),
+            ]),
+            || {
+                assert_eq!(get_ci_commit_sha(), Some("abc123".to_string()));
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn get_ci_commit_sha_drone() {
+        temp_env::with_vars(
+            ci_env(&[
+                ("DRONE", Some("true")),
+                ("DRONE_COMMIT_SHA", Some("abc123")),
+            ]),
+            || {
+                assert_eq!(get_ci_commit_sha(), Some("abc123".to_string()));
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn get_ci_commit_sha_teamcity() {
+        temp_env::with_vars(
+            ci_env(&[
+                ("TEAMCITY_VERSION", Some("2024.03")),
+                ("BUILD_VCS_NUMBER", Some("abc123")),
+            ]),
+            || {
+                assert_eq!(get_ci_commit_sha(), Some("abc123".to_string()));
+            },
+        );
+    }
+
     #[test]
     #[serial]
     fn get_ci_commit_sha_returns_none_for_local() {
@@ -526,6 +689,81 @@ mod tests {
         });
     }
 
+    #[test]
+    #[serial]
+    fn is_pull_request_buildkite_true() {
+        temp_env::with_vars(
+            ci_env(&[
+                ("BUILDKITE", Some("true")),
+                ("BUILDKITE_PULL_REQUEST", Some("17")),
+            ]),
+            || {
+                assert!(is_pull_request());
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn is_pull_request_buildkite_false_when_false_string() {
+        temp_env::with_vars(
+            ci_env(&[
+                ("BUILDKITE", Some("true")),
+                ("BUILDKITE_PULL_REQUEST", Some("false")),
+            ]),
+            || {
+                assert!(!is_pull_request());
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn is_pull_request_woodpecker_true() {
+        temp_env::with_vars(
+            ci_env(&[
+                ("CI", Some("woodpecker")),
+                ("CI_PIPELINE_EVENT", Some("pull_request")),
+            ]),
+            || {
+                assert!(is_pull_request());
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn is_pull_request_drone_true() {
+        temp_env::with_vars(
+            ci_env(&[
+                ("DRONE", Some("true")),
+                ("DRONE_BUILD_EVENT", Some("pull_request")),
+            ]),
+            || {
+                assert!(is_pull_request());
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn is_pull_request_drone_false_on_push() {
+        temp_env::with_vars(
+            ci_env(&[("DRONE", Some("true")), ("DRONE_BUILD_EVENT", Some("push"))]),
+            || {
+                assert!(!is_pull_request());
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn is_pull_request_teamcity_always_false() {
+        temp_env::with_vars(ci_env(&[("TEAMCITY_VERSION", Some("2024.03"))]), || {
+            assert!(!is_pull_request());
+        });
+    }
+
     #[test]
     #[serial]
     fn is_pull_request_false_for_local() {
@@ -541,4 +779,142 @@ mod tests {
             assert!(!is_pull_request());
         });
     }
+
+    // ── get_pr_number ──
+
+    #[test]
+    #[serial]
+    fn get_pr_number_github_actions_merge_ref() {
+        temp_env::with_vars(
+            ci_env(&[
+                ("GITHUB_ACTIONS", Some("true")),
+                ("GITHUB_REF", Some("refs/pull/123/merge")),
+            ]),
+            || {
+                assert_eq!(get_pr_number(), Some(123));
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn get_pr_number_github_actions_branch_ref_is_none() {
+        temp_env::with_vars(
+            ci_env(&[
+                ("GITHUB_ACTIONS", Some("true")),
+                ("GITHUB_REF", Some("refs/heads/main")),
+            ]),
+            || {
+                assert_eq!(get_pr_number(), None);
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn get_pr_number_github_actions_malformed_ref_is_none() {
+        for bad in ["refs/pull/abc/merge", "refs/pull//merge", "refs/pull/"] {
+            temp_env::with_vars(
+                ci_env(&[("GITHUB_ACTIONS", Some("true")), ("GITHUB_REF", Some(bad))]),
+                || {
+                    assert_eq!(get_pr_number(), None, "{bad}");
+                },
+            );
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn get_pr_number_gitlab_ci() {
+        temp_env::with_vars(
+            ci_env(&[
+                ("GITLAB_CI", Some("true")),
+                ("CI_MERGE_REQUEST_IID", Some("42")),
+            ]),
+            || {
+                assert_eq!(get_pr_number(), Some(42));
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn get_pr_number_gitlab_ci_without_mr_is_none() {
+        temp_env::with_vars(
+            ci_env(&[("GITLAB_CI", Some("true")), ("CI_MERGE_REQUEST_IID", None)]),
+            || {
+                assert_eq!(get_pr_number(), None);
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn get_pr_number_circleci_url() {
+        temp_env::with_vars(
+            ci_env(&[
+                ("CIRCLECI", Some("true")),
+                (
+                    "CIRCLE_PULL_REQUEST",
+                    Some("https://github.com/org/repo/pull/77"),
+                ),
+            ]),
+            || {
+                assert_eq!(get_pr_number(), Some(77));
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn get_pr_number_circleci_malformed_url_is_none() {
+        temp_env::with_vars(
+            ci_env(&[
+                ("CIRCLECI", Some("true")),
+                (
+                    "CIRCLE_PULL_REQUEST",
+                    Some("https://github.com/org/repo/pull/"),
+                ),
+            ]),
+            || {
+                assert_eq!(get_pr_number(), None);
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn get_pr_number_bitbucket() {
+        temp_env::with_vars(
+            ci_env(&[
+                ("BITBUCKET_BUILD_NUMBER", Some("1")),
+                ("BITBUCKET_PR_ID", Some("9")),
+            ]),
+            || {
+                assert_eq!(get_pr_number(), Some(9));
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn get_pr_number_bitbucket_non_numeric_is_none() {
+        temp_env::with_vars(
+            ci_env(&[
+                ("BITBUCKET_BUILD_NUMBER", Some("1")),
+                ("BITBUCKET_PR_ID", Some("nope")),
+            ]),
+            || {
+                assert_eq!(get_pr_number(), None);
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn get_pr_number_none_locally() {
+        temp_env::with_vars(ci_env(&[]), || {
+            assert_eq!(get_pr_number(), None);
+        });
+    }
 }
@@ -0,0 +1,237 @@
+//! Container runtime detection.
+//!
+//! Linux-only heuristics: marker files dropped by the runtime and the cgroup
+//! path of PID 1. Every probe is a single stat or small read, and any I/O
+//! failure simply means "no evidence". Other platforms report
+//! [`ContainerRuntime::Unknown`].
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// Container runtime the process is running under.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum ContainerRuntime {
+    /// Docker (`/.dockerenv` or a `docker` cgroup)
+    Docker,
+    /// Podman (`/run/.containerenv` or a `libpod` cgroup)
+    Podman,
+    /// containerd / Kubernetes (`containerd` or `kubepods` cgroup)
+    Containerd,
+    /// Bare metal or VM: no container evidence found
+    None,
+    /// Detection not supported on this platform
+    #[default]
+    Unknown,
+}
+
+impl std::fmt::Display for ContainerRuntime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContainerRuntime::Docker => write!(f, "Docker"),
+            ContainerRuntime::Podman => write!(f, "Podman"),
+            ContainerRuntime::Containerd => write!(f, "containerd"),
+            ContainerRuntime::None => write!(f, "None"),
+            ContainerRuntime::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+/// Filesystem and environment inputs used by container detection.
+///
+/// Abstracted so tests can feed fixed answers instead of probing the host.
+pub(crate) trait ContainerProbe {
+    /// Whether `path` exists.
+    fn exists(&self, path: &Path) -> bool;
+    /// Contents of `path`, or `None` if it cannot be read.
+    fn read(&self, path: &Path) -> Option<String>;
+    /// Value of environment variable `key`.
+    fn var(&self, key: &str) -> Option<String>;
+}
+
+/// Probe backed by the real filesystem and process environment.
+struct SystemProbe;
+
+impl ContainerProbe for SystemProbe {
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    fn read(&self, path: &Path) -> Option<String> {
+        std::fs::read_to_string(path).ok()
+    }
+
+    fn var(&self, key: &str) -> Option<String> {
+        std::env::var(key).ok()
+    }
+}
+
+/// Detect the container runtime of the current process.
+pub(crate) fn detect_container() -> ContainerRuntime {
+    if cfg!(target_os = "linux") {
+        detect_container_with(&SystemProbe)
+    } else {
+        ContainerRuntime::Unknown
+    }
+}
+
+/// Linux detection against an arbitrary probe.
+///
+/// Marker files are checked before the cgroup path because they are the
+/// cheapest and most specific signal; Podman's marker wins over Docker's
+/// since Podman can be configured to create `/.dockerenv` too.
+pub(crate) fn detect_container_with(probe: &dyn ContainerProbe) -> ContainerRuntime {
+    if probe.exists(Path::new("/run/.containerenv"))
+        || probe.var("container").is_some_and(|v| v == "podman")
+    {
+        return ContainerRuntime::Podman;
+    }
+    if probe.exists(Path::new("/.dockerenv")) {
+        return ContainerRuntime::Docker;
+    }
+
+    if let Some(cgroup) = probe.read(Path::new("/proc/1/cgroup")) {
+        if cgroup.contains("libpod") {
+            return ContainerRuntime::Podman;
+        }
+        if cgroup.contains("docker") {
+            return ContainerRuntime::Docker;
+        }
+        if cgroup.contains("containerd") || cgroup.contains("kubepods") {
+            return ContainerRuntime::Containerd;
+        }
+    }
+
+    ContainerRuntime::None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use super::*;
+
+    #[derive(Default)]
+    struct FakeProbe {
+        files: BTreeSet<&'static str>,
+        contents: BTreeMap<&'static str, &'static str>,
+        vars: BTreeMap<&'static str, &'static str>,
+    }
+
+    impl ContainerProbe for FakeProbe {
+        fn exists(&self, path: &Path) -> bool {
+            path.to_str().is_some_and(|p| self.files.contains(p))
+        }
+
+        fn read(&self, path: &Path) -> Option<String> {
+            path.to_str()
+                .and_then(|p| self.contents.get(p))
+                .map(|s| s.to_string())
+        }
+
+        fn var(&self, key: &str) -> Option<String> {
+            self.vars.get(key).map(|s| s.to_string())
+        }
+    }
+
+    fn with_file(path: &'static str) -> FakeProbe {
+        FakeProbe {
+            files: [path].into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    fn with_cgroup(contents: &'static str) -> FakeProbe {
+        FakeProbe {
+            contents: [("/proc/1/cgroup", contents)].into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn dockerenv_marker_means_docker() {
+        assert_eq!(
+            detect_container_with(&with_file("/.dockerenv")),
+            ContainerRuntime::Docker
+        );
+    }
+
+    #[test]
+    fn containerenv_marker_means_podman() {
+        assert_eq!(
+            detect_container_with(&with_file("/run/.containerenv")),
+            ContainerRuntime::Podman
+        );
+    }
+
+    #[test]
+    fn podman_marker_wins_over_dockerenv() {
+        let probe = FakeProbe {
+            files: ["/.dockerenv", "/run/.containerenv"].into_iter().collect(),
+            ..Default::default()
+        };
+        assert_eq!(detect_container_with(&probe), ContainerRuntime::Podman);
+    }
+
+    #[test]
+    fn container_env_var_podman() {
+        let probe = FakeProbe {
+            vars: [("container", "podman")].into_iter().collect(),
+            ..Default::default()
+        };
+        assert_eq!(detect_container_with(&probe), ContainerRuntime::Podman);
+    }
+
+    #[test]
+    fn cgroup_hints_identify_runtime() {
+        assert_eq!(
+            detect_container_with(&with_cgroup("0::/system.slice/docker-abc123.scope\n")),
+            ContainerRuntime::Docker
+        );
+        assert_eq!(
+            detect_container_with(&with_cgroup("0::/machine.slice/libpod-abc123.scope\n")),
+            ContainerRuntime::Podman
+        );
+        assert_eq!(
+            detect_container_with(&with_cgroup("0::/kubepods/besteffort/pod1234/abc\n")),
+            ContainerRuntime::Containerd
+        );
+        assert_eq!(
+            detect_container_with(&with_cgroup("0::/system.slice/containerd.service/abc\n")),
+            ContainerRuntime::Containerd
+        );
+    }
+
+    #[test]
+    fn host_cgroup_means_none() {
+        assert_eq!(
+            detect_container_with(&with_cgroup("0::/init.scope\n")),
+            ContainerRuntime::None
+        );
+    }
+
+    #[test]
+    fn unreadable_probes_mean_none() {
+        assert_eq!(
+            detect_container_with(&FakeProbe::default()),
+            ContainerRuntime::None
+        );
+    }
+
+    #[test]
+    fn detect_container_never_panics() {
+        let detected = detect_container();
+        if !cfg!(target_os = "linux") {
+            assert_eq!(detected, ContainerRuntime::Unknown);
+        }
+    }
+
+    #[test]
+    fn display_all_variants() {
+        assert_eq!(ContainerRuntime::Docker.to_string(), "Docker");
+        assert_eq!(ContainerRuntime::Podman.to_string(), "Podman");
+        assert_eq!(ContainerRuntime::Containerd.to_string(), "containerd");
+        assert_eq!(ContainerRuntime::None.to_string(), "None");
+        assert_eq!(ContainerRuntime::Unknown.to_string(), "Unknown");
+    }
+}
@@ -5,11 +5,14 @@
 
 use std::collections::BTreeMap;
 use std::env;
+use std::ffi::OsStr;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
+use super::container::{ContainerRuntime, detect_container};
 use super::{CiEnvironment, detect_environment};
 
 /// Environment information collected for fingerprinting.
@@ -21,6 +24,9 @@ pub(crate) struct EnvironmentInfo {
     pub(crate) os: String,
     /// Architecture
     pub(crate) arch: String,
+    /// Container runtime (`Unknown` for info recorded before detection existed)
+    #[serde(default)]
+    pub(crate) container: ContainerRuntime,
     /// Rust version
     pub(crate) rust_version: String,
     /// Cargo version
@@ -37,6 +43,7 @@ impl EnvironmentInfo {
         let ci_environment = detect_environment();
         let os = env::consts::OS.to_string();
         let arch = env::consts::ARCH.to_string();
+        let container = detect_container();
 
         let rust_version = get_rust_version().unwrap_or_else(|_| "unknown".to_string());
         let cargo_version = get_cargo_version().unwrap_or_else(|_| "unknown".to_string());
@@ -46,6 +53,7 @@ impl EnvironmentInfo {
             ci_environment,
             os,
             arch,
+            container,
             rust_version,
             cargo_version,
             env_vars,
@@ -59,6 +67,7 @@ impl EnvironmentInfo {
         components.push(format!("ci:{}", self.ci_environment));
         components.push(format!("os:{}", self.os));
         components.push(format!("arch:{}", self.arch));
+        components.push(format!("container:{}", self.container));
         components.push(format!("rust:{}", self.rust_version));
         components.push(format!("cargo:{}", self.cargo_version));
 
@@ -68,6 +77,18 @@ impl EnvironmentInfo {
 
         components.join("|")
     }
+
+    /// Short, stable environment ID: the first 16 hex chars of the SHA-256 of
+    /// [`fingerprint`](Self::fingerprint).
+    ///
+    /// `collected_at` is not part of the fingerprint, so two collections in
+    /// the same environment hash identically.
+    pub(crate) fn fingerprint_hash(&self) -> String {
+        let digest = Sha256::digest(self.fingerprint().as_bytes());
+        let mut hash = hex::encode(digest);
+        hash.truncate(16);
+        hash
+    }
 }
 
 /// Get a quick environment fingerprint (pipe-separated string form).
@@ -88,31 +109,63 @@ pub(super) fn normalize_tool_version(raw: &str) -> Option<String> {
     raw.split_whitespace().nth(1).map(ToOwned::to_owned)
 }
 
-/// Run `rustc --version` and return its trimmed stdout.
+/// `rustc --version` output, e.g. `"rustc 1.92.0 (ded5c06cf 2025-12-08)"`.
+///
+/// See [`tool_version`] for where it comes from.
 pub(crate) fn get_rust_version() -> Result<String> {
-    let output = std::process::Command::new("rustc")
-        .args(["--version"])
-        .output()?;
+    tool_version("rustc", "SHIPPER_RUSTC_VERSION", "RUSTC")
+}
 
-    if output.status.success() {
-        let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        Ok(version)
-    } else {
-        Err(anyhow::anyhow!("failed to get rust version"))
+/// `cargo --version` output, e.g. `"cargo 1.92.0 (344c4567c 2025-10-21)"`.
+///
+/// See [`tool_version`] for where it comes from.
+pub(crate) fn get_cargo_version() -> Result<String> {
+    tool_version("cargo", "SHIPPER_CARGO_VERSION", "CARGO")
+}
+
+/// Resolve a tool's version string, spawning as little as possible.
+///
+/// Tries, in order:
+/// 1. `version_var` (e.g. `SHIPPER_RUSTC_VERSION`), taken verbatim without
+///    spawning anything; a bare `1.92.0` is prefixed with `tool`.
+/// 2. The binary named by `path_var` (`RUSTC` / `CARGO`, which cargo sets
+///    for subcommands and build scripts), so the version matches the
+///    toolchain cargo is actually using.
+/// 3. `tool` on `PATH`.
+///
+/// Errors only when every source fails; callers record `"unknown"`.
+fn tool_version(tool: &str, version_var: &str, path_var: &str) -> Result<String> {
+    if let Ok(raw) = env::var(version_var) {
+        let raw = raw.trim();
+        if !raw.is_empty() {
+            return Ok(if raw.split_whitespace().nth(1).is_some() {
+                raw.to_string()
+            } else {
+                format!("{tool} {raw}")
+            });
+        }
     }
+
+    if let Some(bin) = env::var_os(path_var).filter(|bin| !bin.is_empty())
+        && let Ok(version) = run_version(&bin)
+    {
+        return Ok(version);
+    }
+
+    run_version(OsStr::new(tool)).with_context(|| format!("failed to get {tool} version"))
 }
 
-/// Run `cargo --version` and return its trimmed stdout.
-pub(crate) fn get_cargo_version() -> Result<String> {
-    let output = std::process::Command::new("cargo")
-        .args(["--version"])
-        .output()?;
+/// Run `<bin> --version` and return its trimmed stdout.
+fn run_version(bin: &OsStr) -> Result<String> {
+    let output = std::process::Command::new(bin).arg("--version").output()?;
 
     if output.status.success() {
-        let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        Ok(version)
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
     } else {
-        Err(anyhow::anyhow!("failed to get cargo version"))
+        Err(anyhow::anyhow!(
+            "{} --version failed",
+            bin.to_string_lossy()
+        ))
     }
 }
 
@@ -244,17 +297,77 @@ mod tests {
     // ── get_rust_version / get_cargo_version ──
 
     #[test]
+    #[serial]
     fn get_rust_version_succeeds() {
         let version = get_rust_version().expect("rustc should be available");
         assert!(version.starts_with("rustc"));
     }
 
     #[test]
+    #[serial]
     fn get_cargo_version_succeeds() {
         let version = get_cargo_version().expect("cargo should be available");
         assert!(version.starts_with("cargo"));
     }
 
+    #[test]
+    #[serial]
+    fn stubbed_version_var_is_used_without_spawning() {
+        temp_env::with_vars(
+            [
+                ("SHIPPER_RUSTC_VERSION", Some("1.80.0")),
+                (
+                    "SHIPPER_CARGO_VERSION",
+                    Some("cargo 1.80.0 (376290515 2024-07-16)"),
+                ),
+                ("RUSTC", Some("/nonexistent/rustc")),
+                ("CARGO", Some("/nonexistent/cargo")),
+            ],
+            || {
+                assert_eq!(get_rust_version().expect("rust"), "rustc 1.80.0");
+                assert_eq!(
+                    get_cargo_version().expect("cargo"),
+                    "cargo 1.80.0 (376290515 2024-07-16)"
+                );
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn bogus_rustc_path_falls_back_to_path() {
+        temp_env::with_vars(
+            [
+                ("SHIPPER_RUSTC_VERSION", None),
+                ("RUSTC", Some("/nonexistent/rustc")),
+            ],
+            || {
+                let version = get_rust_version().expect("rustc on PATH");
+                assert!(version.starts_with("rustc"), "{version}");
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn missing_tool_is_an_error_not_a_panic() {
+        temp_env::with_vars(
+            [
+                ("SHIPPER_TEST_MISSING_VERSION", None::<&str>),
+                ("SHIPPER_TEST_MISSING_BIN", Some("/nonexistent/tool")),
+            ],
+            || {
+                let err = tool_version(
+                    "shipper-test-missing-tool",
+                    "SHIPPER_TEST_MISSING_VERSION",
+                    "SHIPPER_TEST_MISSING_BIN",
+                )
+                .expect_err("no source available");
+                assert!(err.to_string().contains("shipper-test-missing-tool"));
+            },
+        );
+    }
+
     // ── collect_env_vars ──
 
     #[test]
@@ -363,6 +476,7 @@ mod tests {
             ci_environment: CiEnvironment::Local,
             os: "linux".to_string(),
             arch: "x86_64".to_string(),
+            container: ContainerRuntime::None,
             rust_version: "1.70.0".to_string(),
             cargo_version: "1.70.0".to_string(),
             env_vars: BTreeMap::new(),
@@ -387,6 +501,7 @@ mod tests {
             ci_environment: CiEnvironment::GitHubActions,
             os: "linux".to_string(),
             arch: "x86_64".to_string(),
+            container: ContainerRuntime::None,
             rust_version: "1.70.0".to_string(),
             cargo_version: "1.70.0".to_string(),
             env_vars: vars,
@@ -405,6 +520,7 @@ mod tests {
             ci_environment: CiEnvironment::Local,
             os: "windows".to_string(),
             arch: "aarch64".to_string(),
+            container: ContainerRuntime::None,
             rust_version: "1.80.0".to_string(),
             cargo_version: "1.80.0".to_string(),
             env_vars: BTreeMap::new(),
@@ -412,7 +528,7 @@ mod tests {
         };
 
         let fp = info.fingerprint();
-        assert_eq!(fp.matches('|').count(), 4);
+        assert_eq!(fp.matches('|').count(), 5);
     }
 
     #[test]
@@ -446,6 +562,7 @@ mod tests {
             ci_environment: CiEnvironment::Local,
             os: String::new(),
             arch: String::new(),
+            container: ContainerRuntime::None,
             rust_version: String::new(),
             cargo_version: String::new(),
             env_vars: BTreeMap::new(),
@@ -467,6 +584,7 @@ mod tests {
             ci_environment: CiEnvironment::Local,
             os: "linux".to_string(),
             arch: "x86_64".to_string(),
+            container: ContainerRuntime::None,
             rust_version: "1.70.0".to_string(),
             cargo_version: "1.70.0".to_string(),
             env_vars: vars,
@@ -485,13 +603,14 @@ mod tests {
             ci_environment: CiEnvironment::Local,
             os: "test-os".to_string(),
             arch: "test-arch".to_string(),
+            container: ContainerRuntime::None,
             rust_version: "unknown".to_string(),
             cargo_version: "unknown".to_string(),
             env_vars: BTreeMap::new(),
             collected_at: Utc::now(),
         };
         let fp = info.fingerprint();
-        assert_eq!(fp.matches('|').count(), 4);
+        assert_eq!(fp.matches('|').count(), 5);
         assert!(!fp.ends_with('|'));
     }
 
@@ -501,6 +620,7 @@ mod tests {
             ci_environment: CiEnvironment::GitHubActions,
             os: "linux".to_string(),
             arch: "x86_64".to_string(),
+            container: ContainerRuntime::None,
             rust_version: "1.80.0".to_string(),
             cargo_version: "1.80.0".to_string(),
             env_vars: BTreeMap::new(),
@@ -515,6 +635,7 @@ mod tests {
             ci_environment: CiEnvironment::Local,
             os: "linux".to_string(),
             arch: "x86_64".to_string(),
+            container: ContainerRuntime::None,
             rust_version: "1.80.0".to_string(),
             cargo_version: "1.80.0".to_string(),
             env_vars: BTreeMap::new(),
@@ -523,6 +644,69 @@ mod tests {
         assert!(info.fingerprint().starts_with("ci:"));
     }
 
+    // ── fingerprint_hash ──
+
+    fn sample_info() -> EnvironmentInfo {
+        EnvironmentInfo {
+            ci_environment: CiEnvironment::GitHubActions,
+            os: "linux".to_string(),
+            arch: "x86_64".to_string(),
+            container: ContainerRuntime::None,
+            rust_version: "1.80.0".to_string(),
+            cargo_version: "1.80.0".to_string(),
+            env_vars: BTreeMap::from([("CI".to_string(), "true".to_string())]),
+            collected_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn fingerprint_hash_is_sixteen_lowercase_hex_chars() {
+        let hash = sample_info().fingerprint_hash();
+        assert_eq!(hash.len(), 16);
+        assert!(
+            hash.chars()
+                .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c))
+        );
+    }
+
+    #[test]
+    fn fingerprint_hash_is_deterministic_and_ignores_collected_at() {
+        let a = sample_info();
+        let mut b = sample_info();
+        b.collected_at = a.collected_at + chrono::Duration::hours(1);
+        assert_eq!(a.fingerprint_hash(), a.fingerprint_hash());
+        assert_eq!(a.fingerprint_hash(), b.fingerprint_hash());
+    }
+
+    #[test]
+    fn fingerprint_hash_changes_with_each_component() {
+        let base = sample_info().fingerprint_hash();
+        let variants: Vec<(&str, fn(&mut EnvironmentInfo))> = vec![
+            ("ci", |i| i.ci_environment = CiEnvironment::Local),
+            ("os", |i| i.os = "macos".to_string()),
+            ("arch", |i| i.arch = "aarch64".to_string()),
+            ("container", |i| i.container = ContainerRuntime::Docker),
+            ("rust", |i| i.rust_version = "1.81.0".to_string()),
+            ("cargo", |i| i.cargo_version = "1.81.0".to_string()),
+            ("env value", |i| {
+                i.env_vars.insert("CI".to_string(), "false".to_string());
+            }),
+            ("env key", |i| {
+                i.env_vars
+                    .insert("GITHUB_SHA".to_string(), "abc".to_string());
+            }),
+        ];
+        for (component, mutate) in variants {
+            let mut info = sample_info();
+            mutate(&mut info);
+            assert_ne!(
+                info.fingerprint_hash(),
+                base,
+                "{component} must affect the hash"
+            );
+        }
+    }
+
     // ── get_environment_fingerprint ──
 
     #[test]
@@ -586,6 +770,7 @@ mod tests {
             ci_environment: CiEnvironment::GitHubActions,
             os: "linux".to_string(),
             arch: "x86_64".to_string(),
+            container: ContainerRuntime::None,
             rust_version: "1.70.0".to_string(),
             cargo_version: "1.70.0".to_string(),
             env_vars: BTreeMap::new(),
@@ -610,6 +795,7 @@ mod tests {
             ci_environment: CiEnvironment::GitHubActions,
             os: "linux".to_string(),
             arch: "x86_64".to_string(),
+            container: ContainerRuntime::None,
             rust_version: "1.80.0".to_string(),
             cargo_version: "1.80.0".to_string(),
             env_vars: vars,
@@ -636,6 +822,10 @@ mod tests {
                 Just(CiEnvironment::AzurePipelines),
                 Just(CiEnvironment::Jenkins),
                 Just(CiEnvironment::BitbucketPipelines),
+                Just(CiEnvironment::Buildkite),
+                Just(CiEnvironment::Woodpecker),
+                Just(CiEnvironment::Drone),
+                Just(CiEnvironment::TeamCity),
                 Just(CiEnvironment::Local),
             ]
         }
@@ -653,6 +843,7 @@ mod tests {
                     ci_environment: ci_env,
                     os: os.clone(),
                     arch: arch.clone(),
+                    container: ContainerRuntime::None,
                     rust_version: rust_ver.clone(),
                     cargo_version: cargo_ver.clone(),
                     env_vars: BTreeMap::new(),
@@ -684,13 +875,14 @@ mod tests {
                     ci_environment: ci_env,
                     os: "os".to_string(),
                     arch: "arch".to_string(),
+                    container: ContainerRuntime::None,
                     rust_version: "1.0.0".to_string(),
                     cargo_version: "1.0.0".to_string(),
                     env_vars,
                     collected_at: Utc::now(),
                 };
                 let fp = info.fingerprint();
-                let expected_pipes = 5 + n_vars - 1;
+                let expected_pipes = 6 + n_vars - 1;
                 prop_assert_eq!(fp.matches('|').count(), expected_pipes);
             }
 
@@ -704,6 +896,7 @@ mod tests {
                         ci_environment: CiEnvironment::Local,
                         os: os.clone(),
                         arch: arch.clone(),
+                        container: ContainerRuntime::None,
                         rust_version: "1.80.0".to_string(),
                         cargo_version: "1.80.0".to_string(),
                         env_vars: BTreeMap::new(),
@@ -744,6 +937,7 @@ mod tests {
                     ci_environment: ci_env,
                     os,
                     arch,
+                    container: ContainerRuntime::None,
                     rust_version: rust_ver,
                     cargo_version: cargo_ver,
                     env_vars: BTreeMap::new(),
@@ -811,6 +1005,7 @@ mod tests {
                     ci_environment: ci_env,
                     os,
                     arch,
+                    container: ContainerRuntime::None,
                     rust_version: rust_ver,
                     cargo_version: cargo_ver,
                     env_vars: BTreeMap::new(),
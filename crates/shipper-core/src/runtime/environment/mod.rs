@@ -13,6 +13,7 @@ use std::env;
 use serde::{Deserialize, Serialize};
 
 pub(crate) mod ci;
+pub(crate) mod container;
 pub(crate) mod fingerprint;
 
 pub(crate) use fingerprint::EnvironmentInfo;
@@ -36,6 +37,14 @@ pub(crate) enum CiEnvironment {
     Jenkins,
     /// Bitbucket Pipelines
     BitbucketPipelines,
+    /// Buildkite
+    Buildkite,
+    /// Woodpecker CI
+    Woodpecker,
+    /// Drone CI
+    Drone,
+    /// TeamCity
+    TeamCity,
     /// No CI detected (local)
     #[default]
     Local,
@@ -51,12 +60,21 @@ impl std::fmt::Display for CiEnvironment {
             CiEnvironment::AzurePipelines => write!(f, "Azure Pipelines"),
             CiEnvironment::Jenkins => write!(f, "Jenkins"),
             CiEnvironment::BitbucketPipelines => write!(f, "Bitbucket Pipelines"),
+            CiEnvironment::Buildkite => write!(f, "Buildkite"),
+            CiEnvironment::Woodpecker => write!(f, "Woodpecker"),
+            CiEnvironment::Drone => write!(f, "Drone"),
+            CiEnvironment::TeamCity => write!(f, "TeamCity"),
             CiEnvironment::Local => write!(f, "Local"),
         }
     }
 }
 
 /// Detect the current CI environment.
+///
+/// Providers are checked in a fixed order and the first match wins:
+/// GitHub Actions, GitLab CI, CircleCI, Travis CI, Azure Pipelines, Jenkins,
+/// Bitbucket Pipelines, Buildkite, Woodpecker, Drone, TeamCity. Woodpecker
+/// is checked before Drone because it can export Drone-compatible variables.
 pub(crate) fn detect_environment() -> CiEnvironment {
     if env::var("GITHUB_ACTIONS").is_ok() {
         return CiEnvironment::GitHubActions;
@@ -79,6 +97,18 @@ pub(crate) fn detect_environment() -> CiEnvironment {
     if env::var("BITBUCKET_BUILD_NUMBER").is_ok() {
         return CiEnvironment::BitbucketPipelines;
     }
+    if env::var("BUILDKITE").is_ok() {
+        return CiEnvironment::Buildkite;
+    }
+    if env::var("WOODPECKER").is_ok() || env::var("CI").is_ok_and(|v| v == "woodpecker") {
+        return CiEnvironment::Woodpecker;
+    }
+    if env::var("DRONE").is_ok() {
+        return CiEnvironment::Drone;
+    }
+    if env::var("TEAMCITY_VERSION").is_ok() {
+        return CiEnvironment::TeamCity;
+    }
     CiEnvironment::Local
 }
 
@@ -104,6 +134,7 @@ pub(crate) fn collect_environment_fingerprint() -> EnvironmentFingerprint {
         ci_environment: detect_environment(),
         os: env::consts::OS.to_string(),
         arch: env::consts::ARCH.to_string(),
+        container: container::detect_container(),
         rust_version: "unknown".to_string(),
         cargo_version: "unknown".to_string(),
         env_vars: std::collections::BTreeMap::new(),
@@ -133,6 +164,11 @@ mod tests {
         "TF_BUILD",
         "JENKINS_URL",
         "BITBUCKET_BUILD_NUMBER",
+        "BUILDKITE",
+        "WOODPECKER",
+        "CI",
+        "DRONE",
+        "TEAMCITY_VERSION",
     ];
 
     pub(super) fn ci_env<'a>(
@@ -163,6 +199,10 @@ mod tests {
             CiEnvironment::BitbucketPipelines.to_string(),
             "Bitbucket Pipelines"
         );
+        assert_eq!(CiEnvironment::Buildkite.to_string(), "Buildkite");
+        assert_eq!(CiEnvironment::Woodpecker.to_string(), "Woodpecker");
+        assert_eq!(CiEnvironment::Drone.to_string(), "Drone");
+        assert_eq!(CiEnvironment::TeamCity.to_string(), "TeamCity");
         assert_eq!(CiEnvironment::Local.to_string(), "Local");
     }
 
@@ -249,6 +289,78 @@ mod tests {
         });
     }
 
+    #[test]
+    #[serial]
+    fn detect_buildkite() {
+        temp_env::with_vars(ci_env(&[("BUILDKITE", Some("true"))]), || {
+            assert_eq!(detect_environment(), CiEnvironment::Buildkite);
+            assert!(is_ci());
+        });
+    }
+
+    #[test]
+    #[serial]
+    fn detect_woodpecker() {
+        temp_env::with_vars(ci_env(&[("WOODPECKER", Some("true"))]), || {
+            assert_eq!(detect_environment(), CiEnvironment::Woodpecker);
+        });
+        temp_env::with_vars(ci_env(&[("CI", Some("woodpecker"))]), || {
+            assert_eq!(detect_environment(), CiEnvironment::Woodpecker);
+            assert!(is_ci());
+        });
+    }
+
+    #[test]
+    #[serial]
+    fn detect_drone() {
+        temp_env::with_vars(ci_env(&[("DRONE", Some("true"))]), || {
+            assert_eq!(detect_environment(), CiEnvironment::Drone);
+            assert!(is_ci());
+        });
+    }
+
+    #[test]
+    #[serial]
+    fn detect_teamcity() {
+        temp_env::with_vars(ci_env(&[("TEAMCITY_VERSION", Some("2024.03"))]), || {
+            assert_eq!(detect_environment(), CiEnvironment::TeamCity);
+            assert!(is_ci());
+        });
+    }
+
+    #[test]
+    #[serial]
+    fn detect_generic_ci_var_alone_is_local() {
+        temp_env::with_vars(ci_env(&[("CI", Some("true"))]), || {
+            assert_eq!(detect_environment(), CiEnvironment::Local);
+        });
+    }
+
+    #[test]
+    #[serial]
+    fn detect_environment_priority_woodpecker_over_drone() {
+        temp_env::with_vars(
+            ci_env(&[("WOODPECKER", Some("true")), ("DRONE", Some("true"))]),
+            || {
+                assert_eq!(detect_environment(), CiEnvironment::Woodpecker);
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn detect_environment_priority_bitbucket_over_buildkite() {
+        temp_env::with_vars(
+            ci_env(&[
+                ("BITBUCKET_BUILD_NUMBER", Some("1")),
+                ("BUILDKITE", Some("true")),
+            ]),
+            || {
+                assert_eq!(detect_environment(), CiEnvironment::BitbucketPipelines);
+            },
+        );
+    }
+
     #[test]
     #[serial]
     fn detect_local_when_no_ci_vars() {
@@ -495,6 +607,10 @@ mod tests {
                 Just(CiEnvironment::AzurePipelines),
                 Just(CiEnvironment::Jenkins),
                 Just(CiEnvironment::BitbucketPipelines),
+                Just(CiEnvironment::Buildkite),
+                Just(CiEnvironment::Woodpecker),
+                Just(CiEnvironment::Drone),
+                Just(CiEnvironment::TeamCity),
                 Just(CiEnvironment::Local),
             ]
         }
@@ -508,6 +624,10 @@ mod tests {
                 CiEnvironment::AzurePipelines => Some("TF_BUILD"),
                 CiEnvironment::Jenkins => Some("JENKINS_URL"),
                 CiEnvironment::BitbucketPipelines => Some("BITBUCKET_BUILD_NUMBER"),
+                CiEnvironment::Buildkite => Some("BUILDKITE"),
+                CiEnvironment::Woodpecker => Some("WOODPECKER"),
+                CiEnvironment::Drone => Some("DRONE"),
+                CiEnvironment::TeamCity => Some("TEAMCITY_VERSION"),
                 CiEnvironment::Local => None,
             }
         }
@@ -550,7 +670,7 @@ mod tests {
             #[test]
             #[serial]
             fn setting_single_ci_var_detects_that_provider(
-                idx in 0usize..7,
+                idx in 0usize..11,
                 value in "[a-zA-Z0-9_.-]{1,50}",
             ) {
                 let providers = [
@@ -561,6 +681,10 @@ mod tests {
                     CiEnvironment::AzurePipelines,
                     CiEnvironment::Jenkins,
                     CiEnvironment::BitbucketPipelines,
+                    CiEnvironment::Buildkite,
+                    CiEnvironment::Woodpecker,
+                    CiEnvironment::Drone,
+                    CiEnvironment::TeamCity,
                 ];
                 let expected = providers[idx];
                 let var = ci_var_for(&expected).unwrap();
@@ -606,6 +730,7 @@ mod tests {
 
 #[cfg(test)]
 mod snapshot_tests {
+    use super::container::ContainerRuntime;
     use super::*;
     use crate::types::EnvironmentFingerprint;
     use insta::assert_yaml_snapshot;
@@ -676,6 +801,7 @@ mod snapshot_tests {
             ci_environment: CiEnvironment::Local,
             os: "linux".to_string(),
             arch: "x86_64".to_string(),
+            container: ContainerRuntime::None,
             rust_version: "1.80.0".to_string(),
             cargo_version: "1.80.0".to_string(),
             env_vars: BTreeMap::new(),
@@ -699,6 +825,7 @@ mod snapshot_tests {
             ci_environment: CiEnvironment::GitHubActions,
             os: "linux".to_string(),
             arch: "x86_64".to_string(),
+            container: ContainerRuntime::None,
             rust_version: "1.80.0".to_string(),
             cargo_version: "1.80.0".to_string(),
             env_vars,
@@ -716,6 +843,7 @@ mod snapshot_tests {
             ci_environment: CiEnvironment::Local,
             os: "macos".to_string(),
             arch: "aarch64".to_string(),
+            container: ContainerRuntime::None,
             rust_version: "1.82.0".to_string(),
             cargo_version: "1.82.0".to_string(),
             env_vars: BTreeMap::new(),
@@ -737,6 +865,7 @@ mod snapshot_tests {
             ci_environment: CiEnvironment::GitHubActions,
             os: "linux".to_string(),
             arch: "x86_64".to_string(),
+            container: ContainerRuntime::None,
             rust_version: "1.80.0".to_string(),
             cargo_version: "1.80.0".to_string(),
             env_vars,
@@ -783,6 +912,7 @@ mod snapshot_tests {
             ci_environment: CiEnvironment::GitLabCI,
             os: "linux".to_string(),
             arch: "x86_64".to_string(),
+            container: ContainerRuntime::None,
             rust_version: "1.79.0".to_string(),
             cargo_version: "1.79.0".to_string(),
             env_vars,
@@ -813,6 +943,7 @@ mod snapshot_tests {
             ci_environment: CiEnvironment::AzurePipelines,
             os: "windows".to_string(),
             arch: "aarch64".to_string(),
+            container: ContainerRuntime::None,
             rust_version: "1.80.0".to_string(),
             cargo_version: "1.80.0".to_string(),
             env_vars: BTreeMap::new(),
@@ -838,6 +969,7 @@ mod snapshot_tests {
             ci_environment: CiEnvironment::GitHubActions,
             os: "linux".to_string(),
             arch: "x86_64".to_string(),
+            container: ContainerRuntime::None,
             rust_version: "1.80.0".to_string(),
             cargo_version: "1.80.0".to_string(),
             env_vars,
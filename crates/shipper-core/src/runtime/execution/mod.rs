@@ -27,14 +27,14 @@ pub fn update_state(
         .context("missing package in state")?;
     pr.state = new_state;
     pr.last_updated_at = Utc::now();
-    st.updated_at = Utc::now();
+    st.touch();
     crate::state::execution_state::save_state(state_dir, st)
 }
 
 /// Append an attempt detail to an in-memory state and refresh its timestamp.
 pub fn append_attempt_detail(st: &mut ExecutionState, detail: AttemptDetail) {
     st.attempt_history.push(detail);
-    st.updated_at = Utc::now();
+    st.touch();
 }
 
 /// Append an attempt detail and persist the execution state.
@@ -75,6 +75,7 @@ pub fn short_state(st: &PackageState) -> &'static str {
         PackageState::Skipped { .. } => "skipped",
         PackageState::Failed { .. } => "failed",
         PackageState::Ambiguous { .. } => "ambiguous",
+        PackageState::Cancelled { .. } => "cancelled",
     }
 }
 
@@ -331,7 +332,7 @@ pub fn update_state_locked(st: &mut ExecutionState, key: &str, new_state: Packag
         pr.state = new_state;
         pr.last_updated_at = Utc::now();
     }
-    st.updated_at = Utc::now();
+    st.touch();
 }
 
 #[cfg(test)]
@@ -341,6 +342,7 @@ mod tests {
 
     use chrono::Utc;
     use proptest::prelude::*;
+    use shipper_types::SkipReason;
     use tempfile::tempdir;
 
     use super::*;
@@ -646,7 +648,9 @@ mod tests {
             "published"
         );
         assert_eq!(
-            short_state(&shipper_types::PackageState::Skipped { reason: "x".into() }),
+            short_state(&shipper_types::PackageState::Skipped {
+                reason: SkipReason::Manual("x".to_string())
+            }),
             "skipped"
         );
         assert_eq!(
@@ -821,7 +825,7 @@ mod tests {
         let key = "a@1.0.0";
         let mut st = sample_state(key, PackageState::Pending);
         let skip = PackageState::Skipped {
-            reason: "already published".into(),
+            reason: SkipReason::AlreadyPublished,
         };
         update_state_locked(&mut st, key, skip.clone());
         assert_eq!(st.packages[key].state, skip);
@@ -1139,7 +1143,7 @@ mod tests {
             td.path(),
             key,
             PackageState::Skipped {
-                reason: "already on registry".into(),
+                reason: SkipReason::Manual("already on registry".to_string()),
             },
         )
         .expect("persist");
@@ -1240,7 +1244,7 @@ mod tests {
             &mut st,
             "c@3.0.0",
             PackageState::Skipped {
-                reason: "dep failed".into(),
+                reason: SkipReason::Manual("dep failed".to_string()),
             },
         );
         assert_eq!(st.packages["a@1.0.0"].state, PackageState::Published);
@@ -1267,7 +1271,7 @@ mod tests {
             td.path(),
             "b@2.0.0",
             PackageState::Skipped {
-                reason: "skip".into(),
+                reason: SkipReason::Manual("skip".to_string()),
             },
         )
         .unwrap();
@@ -1301,7 +1305,9 @@ mod tests {
             Just(PackageState::Pending),
             Just(PackageState::Uploaded),
             Just(PackageState::Published),
-            ".*".prop_map(|r| PackageState::Skipped { reason: r }),
+            ".*".prop_map(|r| PackageState::Skipped {
+                reason: SkipReason::Manual(r)
+            }),
             (arb_error_class(), ".*").prop_map(|(c, m)| PackageState::Failed {
                 class: c,
                 message: m
@@ -1879,7 +1885,7 @@ mod tests {
                 &mut st,
                 "demo@1.0.0",
                 PackageState::Skipped {
-                    reason: "already published on registry".to_string(),
+                    reason: SkipReason::Manual("already published on registry".to_string()),
                 },
             );
             stabilize_timestamps(&mut st);
@@ -1924,7 +1930,7 @@ mod tests {
                 &mut st,
                 "cli@1.0.0",
                 PackageState::Skipped {
-                    reason: "dependency utils@1.0.0 failed".to_string(),
+                    reason: SkipReason::Manual("dependency utils@1.0.0 failed".to_string()),
                 },
             );
             stabilize_timestamps(&mut st);
@@ -1964,7 +1970,7 @@ mod tests {
                 "a",
                 "1.0.0",
                 PackageState::Skipped {
-                    reason: "already on registry".into(),
+                    reason: SkipReason::Manual("already on registry".to_string()),
                 },
             )]);
             insta::assert_debug_snapshot!(st);
@@ -2061,14 +2067,14 @@ mod tests {
                 &mut st,
                 "a@1.0.0",
                 PackageState::Skipped {
-                    reason: "already published".into(),
+                    reason: SkipReason::AlreadyPublished,
                 },
             );
             update_state_locked(
                 &mut st,
                 "b@1.0.0",
                 PackageState::Skipped {
-                    reason: "already published".into(),
+                    reason: SkipReason::AlreadyPublished,
                 },
             );
             stabilize_timestamps(&mut st);
@@ -2094,7 +2100,7 @@ mod tests {
             &mut st,
             key,
             PackageState::Skipped {
-                reason: "pre-existing".into(),
+                reason: SkipReason::Manual("pre-existing".to_string()),
             },
         );
         assert!(matches!(
@@ -2248,7 +2254,7 @@ mod tests {
         let mut st = sample_state(
             key,
             PackageState::Skipped {
-                reason: "skip".into(),
+                reason: SkipReason::Manual("skip".to_string()),
             },
         );
         update_state_locked(&mut st, key, PackageState::Published);
@@ -2414,7 +2420,7 @@ mod tests {
             td.path(),
             key,
             PackageState::Skipped {
-                reason: "already exists".into(),
+                reason: SkipReason::Manual("already exists".to_string()),
             },
         )
         .unwrap();
@@ -2507,7 +2513,7 @@ mod tests {
             td.path(),
             "a@1.0.0",
             PackageState::Skipped {
-                reason: "exists".into(),
+                reason: SkipReason::Manual("exists".to_string()),
             },
         )
         .unwrap();
@@ -2516,7 +2522,7 @@ mod tests {
             td.path(),
             "b@2.0.0",
             PackageState::Skipped {
-                reason: "exists".into(),
+                reason: SkipReason::Manual("exists".to_string()),
             },
         )
         .unwrap();
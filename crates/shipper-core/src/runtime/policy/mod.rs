@@ -357,6 +357,10 @@ mod apply_policy_tests {
             skip_ownership_check: false,
             strict_ownership: false,
             no_verify: false,
+            features: Vec::new(),
+            no_default_features: false,
+            all_features: false,
+            cargo: Default::default(),
             max_attempts: 3,
             base_delay: Duration::from_millis(100),
             max_delay: Duration::from_secs(3),
@@ -644,6 +648,10 @@ mod property_tests {
                         skip_ownership_check,
                         strict_ownership,
                         no_verify,
+                        features: Vec::new(),
+                        no_default_features: false,
+                        all_features: false,
+                        cargo: Default::default(),
                         max_attempts: 3,
                         base_delay: Duration::from_millis(100),
                         max_delay: Duration::from_secs(3),
@@ -1053,6 +1061,10 @@ mod absorbed_integration_tests {
             skip_ownership_check: false,
             strict_ownership: false,
             no_verify: false,
+            features: Vec::new(),
+            no_default_features: false,
+            all_features: false,
+            cargo: Default::default(),
             max_attempts: 3,
             base_delay: Duration::from_millis(100),
             max_delay: Duration::from_secs(3),
@@ -339,6 +339,7 @@ fn state_name(state: &PackageState) -> &'static str {
         PackageState::Skipped { .. } => "skipped",
         PackageState::Failed { .. } => "failed",
         PackageState::Ambiguous { .. } => "ambiguous",
+        PackageState::Cancelled { .. } => "cancelled",
     }
 }
 
@@ -350,7 +351,7 @@ mod tests {
     use shipper_types::{
         EnvironmentFingerprint, PackageEvidence, PackageProgress, PackageReceipt, PublishEvent,
         ReconciliationEvidenceKind, ReconciliationEvidenceSource, ReconciliationOperatorAction,
-        ReconciliationRecord, ReconciliationReport, ReconciliationTrigger, Registry,
+        ReconciliationRecord, ReconciliationReport, ReconciliationTrigger, Registry, SkipReason,
     };
     use tempfile::tempdir;
 
@@ -453,6 +454,7 @@ mod tests {
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256: None,
         }
     }
 
@@ -674,7 +676,7 @@ mod tests {
             "a",
             "1.0.0",
             PackageState::Skipped {
-                reason: "manual mismatch".to_string(),
+                reason: SkipReason::Manual("manual mismatch".to_string()),
             },
         )]);
 
@@ -720,7 +722,7 @@ mod tests {
                 "c",
                 "3.0.0",
                 PackageState::Skipped {
-                    reason: "already published".to_string(),
+                    reason: SkipReason::AlreadyPublished,
                 },
             ),
             pkg_progress("d", "4.0.0", PackageState::Pending),
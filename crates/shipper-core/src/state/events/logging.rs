@@ -0,0 +1,63 @@
+//! Opt-in structured (JSON) logs mirroring the event log.
+//!
+//! [`EventLog::record`](super::EventLog::record) emits a `tracing` event for
+//! every [`PublishEvent`] under the `shipper::events` target, with the
+//! package, event kind and (where the event has one) attempt number as
+//! fields. Nothing is printed unless a subscriber is installed: call
+//! [`init_json_logging`] from a binary, or compose [`json_subscriber`] into
+//! your own setup. Free-text payloads (commands, output tails, messages) are
+//! deliberately not traced so tokens cannot leak through logs.
+
+use anyhow::{Result, anyhow};
+use tracing::{Level, Subscriber};
+use tracing_subscriber::fmt::MakeWriter;
+
+use crate::types::{EventType, PublishEvent};
+
+/// `tracing` target used for recorded events.
+pub const EVENTS_TARGET: &str = "shipper::events";
+
+/// A subscriber writing one JSON object per line to `writer`, keeping
+/// events and spans at `level` or more severe.
+pub fn json_subscriber<W>(level: Level, writer: W) -> impl Subscriber + Send + Sync + 'static
+where
+    W: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+    tracing_subscriber::fmt()
+        .json()
+        .with_max_level(level)
+        .with_current_span(true)
+        .with_writer(writer)
+        .finish()
+}
+
+/// Install [`json_subscriber`] writing to stderr as the global default.
+///
+/// Errors if a global subscriber is already set.
+pub fn init_json_logging(level: Level) -> Result<()> {
+    tracing::subscriber::set_global_default(json_subscriber(level, std::io::stderr))
+        .map_err(|err| anyhow!("failed to install JSON logging: {err}"))
+}
+
+/// The attempt number an event refers to, if any.
+fn attempt_of(event_type: &EventType) -> Option<u32> {
+    match event_type {
+        EventType::PackageAttempted { attempt, .. }
+        | EventType::RetryBackoffStarted { attempt, .. }
+        | EventType::RetryScheduled { attempt, .. }
+        | EventType::ReadinessPoll { attempt, .. }
+        | EventType::ReadinessPollScheduled { attempt, .. } => Some(*attempt),
+        _ => None,
+    }
+}
+
+/// Emit `event` as a `tracing` event on [`EVENTS_TARGET`].
+pub(crate) fn trace_event(event: &PublishEvent) {
+    tracing::info!(
+        target: EVENTS_TARGET,
+        package = %event.package,
+        kind = ?event.event_type.kind(),
+        attempt = attempt_of(&event.event_type),
+        "event recorded"
+    );
+}
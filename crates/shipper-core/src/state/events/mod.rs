@@ -39,12 +39,19 @@
 //! log.write_to_file(&path).expect("write events");
 //! ```
 
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs::{self, File, OpenOptions};
-use std::io::{BufRead, BufReader, Write};
+use std::io::{BufRead, BufReader, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
 use anyhow::{Context, Result};
-use shipper_types::PublishEvent;
+use chrono::{DateTime, Utc};
+use shipper_types::{EventKind, EventType, ExecutionResult, PublishEvent};
+
+mod logging;
+pub use logging::{EVENTS_TARGET, init_json_logging, json_subscriber};
 
 #[cfg(test)]
 mod proptests;
@@ -83,6 +90,38 @@ pub fn preflight_only_events_path(state_dir: &Path, session_id: &str) -> PathBuf
     ))
 }
 
+/// Number of rotated event files kept by [`EventLog::write_to_file_rotating`].
+pub const MAX_EVENT_ROTATIONS: usize = 5;
+
+/// Get the path of the `n`th rotation of an events file (`<path>.<n>`).
+pub fn rotated_events_path(path: &Path, n: usize) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{n}"));
+    path.with_file_name(name)
+}
+
+fn rotate_events_file(path: &Path) -> Result<()> {
+    let oldest = rotated_events_path(path, MAX_EVENT_ROTATIONS);
+    if oldest.exists() {
+        fs::remove_file(&oldest)
+            .with_context(|| format!("failed to remove {}", oldest.display()))?;
+    }
+
+    for n in (1..MAX_EVENT_ROTATIONS).rev() {
+        let from = rotated_events_path(path, n);
+        if from.exists() {
+            let to = rotated_events_path(path, n + 1);
+            fs::rename(&from, &to).with_context(|| {
+                format!("failed to rotate {} to {}", from.display(), to.display())
+            })?;
+        }
+    }
+
+    let first = rotated_events_path(path, 1);
+    fs::rename(path, &first)
+        .with_context(|| format!("failed to rotate {} to {}", path.display(), first.display()))
+}
+
 /// Return all preflight-only event sidecars in lexical order.
 pub fn preflight_only_events_paths(state_dir: &Path) -> Result<Vec<PathBuf>> {
     let mut paths = Vec::new();
@@ -137,6 +176,7 @@ impl EventLog {
     ///
     /// Added events are appended and remain in order.
     pub fn record(&mut self, event: PublishEvent) {
+        logging::trace_event(&event);
         self.events.push(event);
     }
 
@@ -144,6 +184,26 @@ impl EventLog {
     ///
     /// The file is opened in append mode and existing contents are preserved.
     pub fn write_to_file(&self, path: &Path) -> Result<()> {
+        self.append_to_file(path, None)
+    }
+
+    /// Write all recorded events to a file in JSONL format, passing each
+    /// free-text field (commands, output tails, messages, reasons) through
+    /// `redactor` first.
+    ///
+    /// The in-memory events are left untouched. Callers normally pass
+    /// [`crate::ops::cargo::redact_sensitive`] so registry tokens never reach
+    /// disk.
+    pub fn write_to_file_redacted(&self, path: &Path, redactor: fn(&str) -> String) -> Result<()> {
+        self.append_to_file(path, Some(redactor))
+    }
+
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(path = %path.display(), events = self.events.len())
+    )]
+    fn append_to_file(&self, path: &Path, redactor: Option<fn(&str) -> String>) -> Result<()> {
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)
                 .with_context(|| format!("failed to create events dir {}", parent.display()))?;
@@ -158,17 +218,48 @@ impl EventLog {
 
         let mut writer = std::io::BufWriter::new(file);
 
-        self.write_events_to(&mut writer)?;
+        self.write_events_to(&mut writer, redactor)?;
 
         writer.flush().context("failed to flush events file")?;
 
         Ok(())
     }
 
-    fn write_events_to<W: Write>(&self, writer: &mut W) -> Result<()> {
+    /// Write all recorded events, rotating the file first if it is too large.
+    ///
+    /// When the existing file exceeds `max_bytes` it is renamed to
+    /// `<path>.1`, shifting older rotations up to [`MAX_EVENT_ROTATIONS`]
+    /// (the oldest is dropped), and the events are written to a fresh file.
+    pub fn write_to_file_rotating(&self, path: &Path, max_bytes: u64) -> Result<()> {
+        let needs_rotation = match fs::metadata(path) {
+            Ok(metadata) => metadata.len() > max_bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => false,
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("failed to stat events file {}", path.display()));
+            }
+        };
+
+        if needs_rotation {
+            rotate_events_file(path)?;
+        }
+
+        self.write_to_file(path)
+    }
+
+    fn write_events_to<W: Write>(
+        &self,
+        writer: &mut W,
+        redactor: Option<fn(&str) -> String>,
+    ) -> Result<()> {
         for event in &self.events {
-            serde_json::to_writer(&mut *writer, event)
-                .context("failed to serialize event to JSON")?;
+            match redactor {
+                Some(redactor) => {
+                    serde_json::to_writer(&mut *writer, &redact_event(event, redactor))
+                }
+                None => serde_json::to_writer(&mut *writer, event),
+            }
+            .context("failed to serialize event to JSON")?;
             writer
                 .write_all(b"\n")
                 .context("failed to write event line")?;
@@ -203,6 +294,65 @@ impl EventLog {
         Ok(Self { events })
     }
 
+    /// Read every parseable event from a JSONL file, collecting the rest.
+    ///
+    /// Unlike [`read_from_file`](Self::read_from_file), a bad line does not
+    /// fail the read: it is reported as a [`LineError`] and the remaining
+    /// lines are still parsed. This recovers the timeline from a log whose
+    /// writer was killed mid-append, leaving a truncated final line. Blank
+    /// lines are skipped. Returns an empty log when the file does not exist;
+    /// only failing to read the file itself is an error.
+    pub fn read_from_file_lenient(path: &Path) -> Result<(Self, Vec<LineError>)> {
+        if !path.exists() {
+            return Ok((Self::new(), Vec::new()));
+        }
+
+        let content = fs::read(path)
+            .with_context(|| format!("failed to read events file {}", path.display()))?;
+
+        let mut events = Vec::new();
+        let mut errors = Vec::new();
+        for (index, raw) in content.split(|&b| b == b'\n').enumerate() {
+            let line = String::from_utf8_lossy(raw);
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<PublishEvent>(&line) {
+                Ok(event) => events.push(event),
+                Err(err) => errors.push(LineError {
+                    line: index + 1,
+                    content: line.into_owned(),
+                    message: err.to_string(),
+                }),
+            }
+        }
+
+        Ok((Self { events }, errors))
+    }
+
+    /// Read the live events file together with its rotations.
+    ///
+    /// Rotated files (`<path>.N` down to `<path>.1`) are read oldest first,
+    /// followed by `path` itself, so events come back in chronological order.
+    pub fn read_all_rotations(path: &Path) -> Result<Self> {
+        let mut rotations = Vec::new();
+        for n in 1.. {
+            let rotated = rotated_events_path(path, n);
+            if !rotated.exists() {
+                break;
+            }
+            rotations.push(rotated);
+        }
+
+        let mut events = Vec::new();
+        for rotated in rotations.iter().rev() {
+            events.extend(Self::read_from_file(rotated)?.events);
+        }
+        events.extend(Self::read_from_file(path)?.events);
+
+        Ok(Self { events })
+    }
+
     /// Get all events for a specific package.
     ///
     /// Matching is exact against the `package` field.
@@ -213,6 +363,31 @@ impl EventLog {
             .collect()
     }
 
+    /// Get the most recently recorded event for a specific package.
+    ///
+    /// Matching is exact against the `package` field.
+    pub fn last_event_for_package(&self, package: &str) -> Option<&PublishEvent> {
+        self.events.iter().rev().find(|e| e.package == package)
+    }
+
+    /// Get all events whose timestamp falls within `start..=end`.
+    ///
+    /// Both bounds are inclusive. Events are returned in insertion order.
+    pub fn events_between(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<&PublishEvent> {
+        self.events
+            .iter()
+            .filter(|e| e.timestamp >= start && e.timestamp <= end)
+            .collect()
+    }
+
+    /// Get all events of a given kind, ignoring the variant's payload.
+    pub fn events_of_type(&self, kind: EventKind) -> Vec<&PublishEvent> {
+        self.events
+            .iter()
+            .filter(|e| e.event_type.kind() == kind)
+            .collect()
+    }
+
     /// Get all recorded events.
     pub fn all_events(&self) -> &[PublishEvent] {
         &self.events
@@ -232,4 +407,310 @@ impl EventLog {
     pub fn is_empty(&self) -> bool {
         self.events.is_empty()
     }
+
+    /// Summarize the log into per-outcome package counts and run totals.
+    ///
+    /// Package outcomes use the latest `PackagePublished`, `PackageFailed`,
+    /// `PackageSkipped` or `PackageCancelled` event per package, so a failure
+    /// followed by a successful retry counts as published.
+    pub fn summary(&self) -> EventSummary {
+        let mut started = BTreeSet::new();
+        let mut outcomes: BTreeMap<&str, EventKind> = BTreeMap::new();
+        let mut summary = EventSummary::default();
+        let mut finished_result = None;
+
+        for event in &self.events {
+            let kind = event.event_type.kind();
+            match &event.event_type {
+                EventType::PackageStarted { .. } => {
+                    started.insert(event.package.as_str());
+                }
+                EventType::PackageAttempted { .. } => summary.attempts += 1,
+                EventType::ReadinessPoll { .. } => summary.readiness_polls += 1,
+                EventType::PackagePublished { .. }
+                | EventType::PackageFailed { .. }
+                | EventType::PackageSkipped { .. }
+                | EventType::PackageCancelled { .. } => {
+                    outcomes.insert(event.package.as_str(), kind);
+                }
+                EventType::ExecutionFinished { result } => finished_result = Some(result.clone()),
+                _ => {}
+            }
+        }
+
+        summary.packages_started = started.len();
+        for kind in outcomes.values() {
+            match kind {
+                EventKind::PackagePublished => summary.packages_published += 1,
+                EventKind::PackageFailed => summary.packages_failed += 1,
+                EventKind::PackageSkipped => summary.packages_skipped += 1,
+                EventKind::PackageCancelled => summary.packages_cancelled += 1,
+                // Only the outcome kinds above are recorded
+                _ => continue,
+            }
+        }
+
+        summary.result = finished_result.unwrap_or_else(|| {
+            let succeeded = summary.packages_published + summary.packages_skipped;
+            let unfinished = started
+                .iter()
+                .filter(|pkg| !outcomes.contains_key(*pkg))
+                .count();
+            if summary.packages_failed + summary.packages_cancelled + unfinished == 0 {
+                ExecutionResult::Success
+            } else if succeeded == 0 {
+                ExecutionResult::CompleteFailure
+            } else {
+                ExecutionResult::PartialFailure
+            }
+        });
+
+        let first = self.events.iter().map(|e| e.timestamp).min();
+        let last = self.events.iter().map(|e| e.timestamp).max();
+        summary.span = first
+            .zip(last)
+            .map(|(first, last)| (last - first).to_std().unwrap_or_default());
+
+        summary
+    }
+
+    /// Follow a JSONL event file starting at byte offset `from_offset`.
+    ///
+    /// The returned iterator yields every complete line written after the
+    /// offset and ends at the current end of file. A partially written final
+    /// line is left unread until its newline arrives, so iterating again later
+    /// picks up where the previous pass stopped (see [`EventFollower::offset`]).
+    pub fn follow(path: &Path, from_offset: u64) -> EventFollower {
+        EventFollower::new(path, from_offset)
+    }
+
+    /// Stream events appended to a JSONL event file, like `tail -f`.
+    ///
+    /// Starts after the last complete line currently in the file and blocks,
+    /// polling every `poll_interval`, until new lines are written. Once `stop`
+    /// is set the stream drains any remaining complete lines and ends.
+    pub fn stream_new<'a>(
+        path: &Path,
+        poll_interval: Duration,
+        stop: &'a AtomicBool,
+    ) -> Result<EventStream<'a>> {
+        let from_offset = match fs::read(path) {
+            Ok(bytes) => bytes
+                .iter()
+                .rposition(|b| *b == b'\n')
+                .map_or(0, |pos| pos as u64 + 1),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => 0,
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("failed to read events file {}", path.display()));
+            }
+        };
+
+        Ok(EventStream {
+            follower: EventFollower::new(path, from_offset),
+            poll_interval,
+            stop,
+        })
+    }
+}
+
+/// Clone `event` with every free-text field passed through `redactor`.
+///
+/// Identifiers (package names, versions, plan ids, registries) and structured
+/// data are left as-is; only fields that may echo command lines, process
+/// output or error text are rewritten.
+fn redact_event(event: &PublishEvent, redactor: fn(&str) -> String) -> PublishEvent {
+    let mut event = event.clone();
+    let redact = |field: &mut String| *field = redactor(field);
+
+    match &mut event.event_type {
+        EventType::PackageAttempted { command, .. } => redact(command),
+        EventType::PackageOutput {
+            stdout_tail,
+            stderr_tail,
+        } => {
+            redact(stdout_tail);
+            redact(stderr_tail);
+        }
+        EventType::PackageFailed { message, .. }
+        | EventType::RateLimitObserved { message, .. }
+        | EventType::RehearsalPackageFailed { message, .. }
+        | EventType::RehearsalSmokeCheckFailed { message, .. }
+        | EventType::RetryBackoffStarted { message, .. }
+        | EventType::RetryScheduled { message, .. } => redact(message),
+        EventType::PackageSkipped { reason }
+        | EventType::PublishWaiting { reason, .. }
+        | EventType::PackageYanked { reason, .. } => redact(reason),
+        EventType::RehearsalComplete { summary, .. } => redact(summary),
+        EventType::PreflightWorkspaceVerify { output, .. } => redact(output),
+        _ => {}
+    }
+
+    event
+}
+
+/// A line of an event file that [`EventLog::read_from_file_lenient`] skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineError {
+    /// 1-based line number within the file.
+    pub line: usize,
+    /// The raw line, lossily decoded as UTF-8.
+    pub content: String,
+    /// Why the line could not be parsed as a [`PublishEvent`].
+    pub message: String,
+}
+
+impl std::fmt::Display for LineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Aggregate counts computed from an [`EventLog`] by [`EventLog::summary`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventSummary {
+    /// Distinct packages with a `PackageStarted` event.
+    pub packages_started: usize,
+    /// Distinct packages whose latest outcome is `PackagePublished`.
+    pub packages_published: usize,
+    /// Distinct packages whose latest outcome is `PackageFailed`.
+    pub packages_failed: usize,
+    /// Distinct packages whose latest outcome is `PackageSkipped`.
+    pub packages_skipped: usize,
+    /// Distinct packages whose latest outcome is `PackageCancelled`.
+    pub packages_cancelled: usize,
+    /// Total `ReadinessPoll` events across all packages.
+    pub readiness_polls: usize,
+    /// Total `PackageAttempted` events across all packages.
+    pub attempts: usize,
+    /// Result from the last `ExecutionFinished` event, or inferred from
+    /// package outcomes when the run never finished.
+    ///
+    /// Inference treats started packages without an outcome as unfinished:
+    /// any failed, cancelled or unfinished package makes the result
+    /// `PartialFailure`, or `CompleteFailure` if nothing succeeded.
+    pub result: ExecutionResult,
+    /// Wall-clock time between the earliest and latest event timestamps, or
+    /// `None` for an empty log.
+    pub span: Option<Duration>,
+}
+
+/// Non-blocking reader over complete lines of a JSONL event file.
+///
+/// Created by [`EventLog::follow`]. Yields `None` at end of file; calling
+/// `next` again after more lines are appended resumes from [`offset`](Self::offset).
+#[derive(Debug)]
+pub struct EventFollower {
+    path: PathBuf,
+    reader: Option<BufReader<File>>,
+    offset: u64,
+    pending: Vec<u8>,
+}
+
+impl EventFollower {
+    fn new(path: &Path, from_offset: u64) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            reader: None,
+            offset: from_offset,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Byte offset just past the last complete line consumed.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    fn read_line(&mut self) -> Result<Option<Vec<u8>>> {
+        let reader = match &mut self.reader {
+            Some(reader) => reader,
+            None => {
+                let mut file = match File::open(&self.path) {
+                    Ok(file) => file,
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+                    Err(err) => {
+                        return Err(err).with_context(|| {
+                            format!("failed to open events file {}", self.path.display())
+                        });
+                    }
+                };
+                file.seek(SeekFrom::Start(self.offset)).with_context(|| {
+                    format!("failed to seek events file {}", self.path.display())
+                })?;
+                self.reader.insert(BufReader::new(file))
+            }
+        };
+        reader
+            .read_until(b'\n', &mut self.pending)
+            .with_context(|| format!("failed to read events file {}", self.path.display()))?;
+
+        if self.pending.last() != Some(&b'\n') {
+            // Partial (or no) line: keep the bytes and wait for the newline.
+            return Ok(None);
+        }
+
+        let line = std::mem::take(&mut self.pending);
+        self.offset += line.len() as u64;
+        Ok(Some(line))
+    }
+}
+
+impl Iterator for EventFollower {
+    type Item = Result<PublishEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.read_line() {
+                Ok(Some(line)) => line,
+                Ok(None) => return None,
+                Err(err) => return Some(Err(err)),
+            };
+            if line.iter().all(u8::is_ascii_whitespace) {
+                continue;
+            }
+            return Some(serde_json::from_slice(&line).with_context(|| {
+                format!(
+                    "failed to parse event JSON from line: {}",
+                    String::from_utf8_lossy(&line).trim_end()
+                )
+            }));
+        }
+    }
+}
+
+/// Blocking stream of newly appended events.
+///
+/// Created by [`EventLog::stream_new`].
+#[derive(Debug)]
+pub struct EventStream<'a> {
+    follower: EventFollower,
+    poll_interval: Duration,
+    stop: &'a AtomicBool,
+}
+
+impl EventStream<'_> {
+    /// Byte offset just past the last complete line consumed.
+    pub fn offset(&self) -> u64 {
+        self.follower.offset()
+    }
+}
+
+impl Iterator for EventStream<'_> {
+    type Item = Result<PublishEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // Load `stop` before reading so lines written before it was set
+            // are always drained.
+            let stopping = self.stop.load(Ordering::Acquire);
+            if let Some(item) = self.follower.next() {
+                return Some(item);
+            }
+            if stopping {
+                return None;
+            }
+            std::thread::sleep(self.poll_interval);
+        }
+    }
 }
@@ -5,8 +5,8 @@
 use super::*;
 use chrono::{DateTime, Utc};
 use shipper_types::{
-    AuthEvidence, AuthEvidenceMode, ErrorClass, EventType, ExecutionResult, Finishability,
-    ReadinessMethod,
+    AuthEvidence, AuthEvidenceMode, ErrorClass, EventKind, EventType, ExecutionResult,
+    Finishability, ReadinessMethod,
 };
 use std::fs::{self, File, OpenOptions};
 use std::io::Write;
@@ -136,6 +136,233 @@ fn events_for_package_matching_is_exact() {
     assert_eq!(log.events_for_package("pkg").len(), 0);
 }
 
+fn event_at(offset_secs: i64, event_type: EventType, package: &str) -> PublishEvent {
+    PublishEvent {
+        timestamp: fixed_time() + chrono::Duration::seconds(offset_secs),
+        event_type,
+        package: package.to_string(),
+    }
+}
+
+fn mixed_log() -> EventLog {
+    let mut log = EventLog::new();
+    log.record(event_at(0, EventType::ExecutionStarted, "all"));
+    log.record(event_at(
+        10,
+        EventType::PackageStarted {
+            name: "a".to_string(),
+            version: "1.0.0".to_string(),
+        },
+        "a@1.0.0",
+    ));
+    log.record(event_at(
+        20,
+        EventType::PackagePublished { duration_ms: 100 },
+        "a@1.0.0",
+    ));
+    log.record(event_at(
+        30,
+        EventType::PackageStarted {
+            name: "b".to_string(),
+            version: "2.0.0".to_string(),
+        },
+        "b@2.0.0",
+    ));
+    log.record(event_at(
+        40,
+        EventType::PackageFailed {
+            class: ErrorClass::Retryable,
+            message: "timeout".to_string(),
+        },
+        "b@2.0.0",
+    ));
+    log
+}
+
+#[test]
+fn events_between_is_inclusive_on_both_bounds() {
+    let log = mixed_log();
+    let start = fixed_time() + chrono::Duration::seconds(10);
+    let end = fixed_time() + chrono::Duration::seconds(30);
+
+    let events = log.events_between(start, end);
+    let offsets: Vec<_> = events
+        .iter()
+        .map(|e| (e.timestamp - fixed_time()).num_seconds())
+        .collect();
+    assert_eq!(offsets, vec![10, 20, 30]);
+}
+
+#[test]
+fn events_between_returns_empty_for_inverted_range() {
+    let log = mixed_log();
+    let start = fixed_time() + chrono::Duration::seconds(30);
+    let end = fixed_time();
+    assert!(log.events_between(start, end).is_empty());
+}
+
+#[test]
+fn events_of_type_ignores_variant_payload() {
+    let log = mixed_log();
+
+    let started = log.events_of_type(EventKind::PackageStarted);
+    assert_eq!(started.len(), 2);
+    assert_eq!(started[0].package, "a@1.0.0");
+    assert_eq!(started[1].package, "b@2.0.0");
+
+    assert_eq!(log.events_of_type(EventKind::PackageFailed).len(), 1);
+    assert_eq!(log.events_of_type(EventKind::ExecutionStarted).len(), 1);
+    assert!(log.events_of_type(EventKind::PackageSkipped).is_empty());
+}
+
+#[test]
+fn last_event_for_package_returns_most_recent() {
+    let log = mixed_log();
+
+    let last_a = log.last_event_for_package("a@1.0.0").expect("event for a");
+    assert_eq!(last_a.event_type.kind(), EventKind::PackagePublished);
+
+    let last_b = log.last_event_for_package("b@2.0.0").expect("event for b");
+    assert_eq!(last_b.event_type.kind(), EventKind::PackageFailed);
+
+    assert!(log.last_event_for_package("c@1.0.0").is_none());
+    assert!(log.last_event_for_package("a").is_none());
+}
+
+// -- Summary --
+
+fn representative_log() -> EventLog {
+    let mut log = EventLog::new();
+    let started = |name: &str| EventType::PackageStarted {
+        name: name.to_string(),
+        version: "1.0.0".to_string(),
+    };
+    let attempted = |attempt| EventType::PackageAttempted {
+        attempt,
+        command: "cargo publish".to_string(),
+    };
+    let poll = |attempt| EventType::ReadinessPoll {
+        attempt,
+        visible: attempt > 1,
+    };
+    let failed = || EventType::PackageFailed {
+        class: ErrorClass::Retryable,
+        message: "timeout".to_string(),
+    };
+
+    log.record(event_at(0, EventType::ExecutionStarted, "all"));
+    // a: fails once, then publishes on retry.
+    log.record(event_at(1, started("a"), "a@1.0.0"));
+    log.record(event_at(2, attempted(1), "a@1.0.0"));
+    log.record(event_at(3, failed(), "a@1.0.0"));
+    log.record(event_at(4, attempted(2), "a@1.0.0"));
+    log.record(event_at(5, poll(1), "a@1.0.0"));
+    log.record(event_at(6, poll(2), "a@1.0.0"));
+    log.record(event_at(
+        7,
+        EventType::PackagePublished { duration_ms: 500 },
+        "a@1.0.0",
+    ));
+    // b: already published.
+    log.record(event_at(8, started("b"), "b@1.0.0"));
+    log.record(event_at(
+        9,
+        EventType::PackageSkipped {
+            reason: "already published".to_string(),
+        },
+        "b@1.0.0",
+    ));
+    // c: fails permanently.
+    log.record(event_at(10, started("c"), "c@1.0.0"));
+    log.record(event_at(11, attempted(1), "c@1.0.0"));
+    log.record(event_at(12, failed(), "c@1.0.0"));
+    log
+}
+
+#[test]
+fn summary_counts_each_package_outcome_once() {
+    let summary = representative_log().summary();
+
+    assert_eq!(summary.packages_started, 3);
+    assert_eq!(summary.packages_published, 1);
+    assert_eq!(summary.packages_failed, 1);
+    assert_eq!(summary.packages_skipped, 1);
+    assert_eq!(summary.packages_cancelled, 0);
+    assert_eq!(summary.attempts, 3);
+    assert_eq!(summary.readiness_polls, 2);
+    assert_eq!(summary.span, Some(std::time::Duration::from_secs(12)));
+}
+
+#[test]
+fn summary_infers_result_without_execution_finished() {
+    assert_eq!(
+        representative_log().summary().result,
+        ExecutionResult::PartialFailure
+    );
+
+    let mut log = EventLog::new();
+    log.record(event_at(
+        0,
+        EventType::PackageFailed {
+            class: ErrorClass::Permanent,
+            message: "denied".to_string(),
+        },
+        "a@1.0.0",
+    ));
+    assert_eq!(log.summary().result, ExecutionResult::CompleteFailure);
+
+    let mut log = EventLog::new();
+    log.record(event_at(
+        0,
+        EventType::PackagePublished { duration_ms: 1 },
+        "a@1.0.0",
+    ));
+    assert_eq!(log.summary().result, ExecutionResult::Success);
+}
+
+#[test]
+fn summary_treats_started_package_without_outcome_as_unfinished() {
+    let mut log = EventLog::new();
+    log.record(event_at(
+        0,
+        EventType::PackagePublished { duration_ms: 1 },
+        "a@1.0.0",
+    ));
+    log.record(event_at(
+        1,
+        EventType::PackageStarted {
+            name: "b".to_string(),
+            version: "1.0.0".to_string(),
+        },
+        "b@1.0.0",
+    ));
+    assert_eq!(log.summary().result, ExecutionResult::PartialFailure);
+}
+
+#[test]
+fn summary_prefers_execution_finished_result() {
+    let mut log = representative_log();
+    log.record(event_at(
+        13,
+        EventType::ExecutionFinished {
+            result: ExecutionResult::CompleteFailure,
+        },
+        "all",
+    ));
+
+    let summary = log.summary();
+    assert_eq!(summary.result, ExecutionResult::CompleteFailure);
+    assert_eq!(summary.span, Some(std::time::Duration::from_secs(13)));
+}
+
+#[test]
+fn summary_of_empty_log_is_default() {
+    let summary = EventLog::new().summary();
+    assert_eq!(summary, EventSummary::default());
+    assert_eq!(summary.result, ExecutionResult::Success);
+    assert_eq!(summary.span, None);
+}
+
 // -- Clear --
 
 #[test]
@@ -245,6 +472,65 @@ fn read_from_file_returns_empty_log_when_missing() {
     assert!(loaded.is_empty());
 }
 
+#[test]
+fn read_from_file_lenient_recovers_events_before_truncated_line() {
+    let td = tempdir().expect("tempdir");
+    let path = td.path().join("events.jsonl");
+
+    let mut log = EventLog::new();
+    log.record(sample_event("a@1.0.0"));
+    log.record(sample_event("b@1.0.0"));
+    log.record(sample_event("c@1.0.0"));
+    log.write_to_file(&path).expect("write");
+
+    // Simulate a writer killed mid-append.
+    let mut file = OpenOptions::new().append(true).open(&path).expect("open");
+    file.write_all(br#"{"timestamp":"2025-01-15T12:00:00Z","event_typ"#)
+        .expect("append partial line");
+    drop(file);
+
+    assert!(EventLog::read_from_file(&path).is_err());
+
+    let (loaded, errors) = EventLog::read_from_file_lenient(&path).expect("lenient read");
+    let packages: Vec<_> = loaded
+        .all_events()
+        .iter()
+        .map(|e| e.package.as_str())
+        .collect();
+    assert_eq!(packages, ["a@1.0.0", "b@1.0.0", "c@1.0.0"]);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].line, 4);
+    assert!(errors[0].content.ends_with("\"event_typ"));
+    assert!(
+        errors[0]
+            .to_string()
+            .starts_with("line 4: EOF while parsing")
+    );
+}
+
+#[test]
+fn read_from_file_lenient_skips_bad_middle_lines_and_blanks() {
+    let td = tempdir().expect("tempdir");
+    let path = td.path().join("events.jsonl");
+    let good = serde_json::to_string(&sample_event("a@1.0.0")).expect("serialize");
+    fs::write(&path, format!("{good}\nnot json\n\n{good}\n")).expect("write");
+
+    let (loaded, errors) = EventLog::read_from_file_lenient(&path).expect("lenient read");
+    assert_eq!(loaded.len(), 2);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].line, 2);
+    assert_eq!(errors[0].content, "not json");
+}
+
+#[test]
+fn read_from_file_lenient_returns_empty_log_when_missing() {
+    let td = tempdir().expect("tempdir");
+    let (loaded, errors) =
+        EventLog::read_from_file_lenient(&td.path().join("missing.jsonl")).expect("read");
+    assert!(loaded.is_empty());
+    assert!(errors.is_empty());
+}
+
 #[test]
 fn read_from_file_errors_on_invalid_json() {
     let td = tempdir().expect("tempdir");
@@ -2086,6 +2372,299 @@ fn concurrent_appends_from_multiple_threads() {
     assert_eq!(loaded.len(), num_threads * events_per_thread);
 }
 
+// -- Redaction --
+
+#[test]
+fn write_to_file_redacted_scrubs_tokens_on_disk() {
+    use crate::ops::cargo::redact_sensitive;
+
+    let td = tempdir().expect("tempdir");
+    let path = td.path().join("events.jsonl");
+    let mut log = EventLog::new();
+    log.record(fixed_event(
+        EventType::PackageAttempted {
+            attempt: 1,
+            command: "CARGO_REGISTRY_TOKEN=abc cargo publish -p demo".to_string(),
+        },
+        "demo@1.0.0",
+    ));
+    log.record(fixed_event(
+        EventType::PackageOutput {
+            stdout_tail: "ok".to_string(),
+            stderr_tail: "Authorization: Bearer abc".to_string(),
+        },
+        "demo@1.0.0",
+    ));
+
+    log.write_to_file_redacted(&path, redact_sensitive)
+        .expect("write");
+
+    let content = fs::read_to_string(&path).expect("read");
+    assert!(!content.contains("abc"), "token leaked: {content}");
+    assert!(content.contains("CARGO_REGISTRY_TOKEN=[REDACTED]"));
+    assert!(content.contains("cargo publish -p demo"));
+
+    // In-memory events are not modified.
+    match &log.all_events()[0].event_type {
+        EventType::PackageAttempted { command, .. } => assert!(command.contains("abc")),
+        other => panic!("unexpected event: {other:?}"),
+    }
+}
+
+#[test]
+fn write_to_file_redacted_leaves_identifiers_alone() {
+    let td = tempdir().expect("tempdir");
+    let path = td.path().join("events.jsonl");
+    let mut log = EventLog::new();
+    log.record(fixed_event(
+        EventType::PackageFailed {
+            class: ErrorClass::Permanent,
+            message: "denied".to_string(),
+        },
+        "demo@1.0.0",
+    ));
+
+    log.write_to_file_redacted(&path, |s| s.to_uppercase())
+        .expect("write");
+
+    let loaded = EventLog::read_from_file(&path).expect("read");
+    let event = &loaded.all_events()[0];
+    assert_eq!(event.package, "demo@1.0.0");
+    match &event.event_type {
+        EventType::PackageFailed { message, .. } => assert_eq!(message, "DENIED"),
+        other => panic!("unexpected event: {other:?}"),
+    }
+}
+
+// -- Rotation --
+
+fn single_event_log(package: &str) -> EventLog {
+    let mut log = EventLog::new();
+    log.record(fixed_event(
+        EventType::PackagePublished { duration_ms: 1 },
+        package,
+    ));
+    log
+}
+
+#[test]
+fn rotated_events_path_appends_index() {
+    let path = Path::new("state").join("events.jsonl");
+    assert_eq!(
+        rotated_events_path(&path, 2),
+        Path::new("state").join("events.jsonl.2")
+    );
+}
+
+#[test]
+fn write_to_file_rotating_appends_below_threshold() {
+    let td = tempdir().expect("tempdir");
+    let path = td.path().join("events.jsonl");
+
+    single_event_log("a@1.0.0")
+        .write_to_file_rotating(&path, 1024 * 1024)
+        .expect("write");
+    single_event_log("b@1.0.0")
+        .write_to_file_rotating(&path, 1024 * 1024)
+        .expect("write");
+
+    assert!(!rotated_events_path(&path, 1).exists());
+    assert_eq!(EventLog::read_from_file(&path).expect("read").len(), 2);
+}
+
+#[test]
+fn write_to_file_rotating_drives_several_rotations_in_order() {
+    let td = tempdir().expect("tempdir");
+    let path = td.path().join("events.jsonl");
+
+    // A threshold of one byte rotates before every write after the first.
+    for i in 0..4 {
+        single_event_log(&format!("pkg{i}@1.0.0"))
+            .write_to_file_rotating(&path, 1)
+            .expect("write");
+    }
+
+    for n in 1..=3 {
+        assert!(rotated_events_path(&path, n).exists(), "missing .{n}");
+    }
+    assert!(!rotated_events_path(&path, 4).exists());
+    assert_eq!(EventLog::read_from_file(&path).expect("read").len(), 1);
+
+    let all = EventLog::read_all_rotations(&path).expect("read rotations");
+    let packages: Vec<_> = all
+        .all_events()
+        .iter()
+        .map(|e| e.package.as_str())
+        .collect();
+    assert_eq!(
+        packages,
+        vec!["pkg0@1.0.0", "pkg1@1.0.0", "pkg2@1.0.0", "pkg3@1.0.0"]
+    );
+}
+
+#[test]
+fn write_to_file_rotating_drops_oldest_beyond_keep_count() {
+    let td = tempdir().expect("tempdir");
+    let path = td.path().join("events.jsonl");
+    let writes = MAX_EVENT_ROTATIONS + 3;
+
+    for i in 0..writes {
+        single_event_log(&format!("pkg{i}@1.0.0"))
+            .write_to_file_rotating(&path, 1)
+            .expect("write");
+    }
+
+    assert!(rotated_events_path(&path, MAX_EVENT_ROTATIONS).exists());
+    assert!(!rotated_events_path(&path, MAX_EVENT_ROTATIONS + 1).exists());
+
+    let all = EventLog::read_all_rotations(&path).expect("read rotations");
+    let packages: Vec<_> = all.all_events().iter().map(|e| e.package.clone()).collect();
+    let expected: Vec<_> = (writes - MAX_EVENT_ROTATIONS - 1..writes)
+        .map(|i| format!("pkg{i}@1.0.0"))
+        .collect();
+    assert_eq!(packages, expected);
+}
+
+#[test]
+fn read_all_rotations_without_rotations_matches_read_from_file() {
+    let td = tempdir().expect("tempdir");
+    let path = td.path().join("events.jsonl");
+    assert!(
+        EventLog::read_all_rotations(&path)
+            .expect("read")
+            .is_empty()
+    );
+
+    single_event_log("a@1.0.0")
+        .write_to_file(&path)
+        .expect("write");
+    assert_eq!(EventLog::read_all_rotations(&path).expect("read").len(), 1);
+}
+
+// -- Following a live events file --
+
+fn event_line(package: &str) -> String {
+    let event = fixed_event(EventType::PackagePublished { duration_ms: 1 }, package);
+    let mut line = serde_json::to_string(&event).expect("serialize");
+    line.push('\n');
+    line
+}
+
+#[test]
+fn follow_reads_from_offset_and_resumes_after_append() {
+    let td = tempdir().expect("tempdir");
+    let path = td.path().join("events.jsonl");
+    let first = event_line("a@1.0.0");
+    fs::write(&path, format!("{first}{}", event_line("b@1.0.0"))).expect("write");
+
+    let mut follower = EventLog::follow(&path, first.len() as u64);
+    let packages: Vec<_> = follower
+        .by_ref()
+        .map(|e| e.expect("event").package)
+        .collect();
+    assert_eq!(packages, vec!["b@1.0.0"]);
+    let offset = follower.offset();
+    assert_eq!(offset, fs::metadata(&path).expect("metadata").len());
+
+    let mut file = OpenOptions::new().append(true).open(&path).expect("open");
+    file.write_all(event_line("c@1.0.0").as_bytes())
+        .expect("append");
+
+    let next = follower.next().expect("appended event").expect("parse");
+    assert_eq!(next.package, "c@1.0.0");
+    assert!(follower.next().is_none());
+    assert!(follower.offset() > offset);
+}
+
+#[test]
+fn follow_waits_for_newline_on_partial_final_line() {
+    let td = tempdir().expect("tempdir");
+    let path = td.path().join("events.jsonl");
+    let partial = event_line("b@1.0.0");
+    let (head, tail) = partial.split_at(partial.len() / 2);
+    fs::write(&path, format!("{}{head}", event_line("a@1.0.0"))).expect("write");
+
+    let mut follower = EventLog::follow(&path, 0);
+    assert_eq!(
+        follower.next().expect("event").expect("parse").package,
+        "a@1.0.0"
+    );
+    assert!(
+        follower.next().is_none(),
+        "partial line must not be yielded"
+    );
+
+    let mut file = OpenOptions::new().append(true).open(&path).expect("open");
+    file.write_all(tail.as_bytes()).expect("append");
+
+    assert_eq!(
+        follower.next().expect("event").expect("parse").package,
+        "b@1.0.0"
+    );
+    assert!(follower.next().is_none());
+}
+
+#[test]
+fn follow_missing_file_yields_nothing() {
+    let td = tempdir().expect("tempdir");
+    let mut follower = EventLog::follow(&td.path().join("missing.jsonl"), 0);
+    assert!(follower.next().is_none());
+    assert_eq!(follower.offset(), 0);
+}
+
+#[test]
+fn follow_reports_malformed_line_and_continues() {
+    let td = tempdir().expect("tempdir");
+    let path = td.path().join("events.jsonl");
+    fs::write(&path, format!("not json\n{}", event_line("a@1.0.0"))).expect("write");
+
+    let mut follower = EventLog::follow(&path, 0);
+    assert!(follower.next().expect("item").is_err());
+    assert_eq!(
+        follower.next().expect("event").expect("parse").package,
+        "a@1.0.0"
+    );
+}
+
+#[test]
+fn stream_new_collects_events_written_by_another_thread() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    let td = tempdir().expect("tempdir");
+    let path = td.path().join("events.jsonl");
+    fs::write(&path, event_line("existing@1.0.0")).expect("write");
+
+    let stop = AtomicBool::new(false);
+    let stream = EventLog::stream_new(&path, Duration::from_millis(5), &stop).expect("stream");
+
+    let collected = thread::scope(|scope| {
+        let reader = scope.spawn(move || {
+            stream
+                .map(|e| e.expect("event").package)
+                .collect::<Vec<_>>()
+        });
+
+        let mut file = OpenOptions::new().append(true).open(&path).expect("open");
+        for i in 0..5 {
+            let line = event_line(&format!("pkg{i}@1.0.0"));
+            let (head, tail) = line.split_at(line.len() / 2);
+            file.write_all(head.as_bytes()).expect("write head");
+            file.flush().expect("flush");
+            thread::sleep(Duration::from_millis(10));
+            file.write_all(tail.as_bytes()).expect("write tail");
+            file.flush().expect("flush");
+        }
+        stop.store(true, Ordering::Release);
+
+        reader.join().expect("reader thread")
+    });
+
+    let expected: Vec<_> = (0..5).map(|i| format!("pkg{i}@1.0.0")).collect();
+    assert_eq!(collected, expected);
+}
+
 // -- Additional hardening tests --
 
 #[test]
@@ -2458,3 +3037,79 @@ fn snapshot_readiness_lifecycle_debug() {
     ];
     insta::assert_debug_snapshot!("readiness_lifecycle_debug", events);
 }
+
+// --- JSON logging ---
+
+/// `MakeWriter` collecting everything written into a shared buffer.
+#[derive(Clone, Default)]
+struct CapturedLogs(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+impl Write for CapturedLogs {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for CapturedLogs {
+    type Writer = CapturedLogs;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+impl CapturedLogs {
+    fn json_lines(&self) -> Vec<serde_json::Value> {
+        let bytes = self.0.lock().unwrap().clone();
+        String::from_utf8(bytes)
+            .expect("utf8")
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("JSON log line"))
+            .collect()
+    }
+}
+
+#[test]
+fn record_emits_json_log_with_package_and_attempt() {
+    let logs = CapturedLogs::default();
+    let subscriber = json_subscriber(tracing::Level::INFO, logs.clone());
+
+    tracing::subscriber::with_default(subscriber, || {
+        let mut log = EventLog::new();
+        log.record(sample_event("demo@1.0.0"));
+        log.record(PublishEvent {
+            timestamp: Utc::now(),
+            event_type: EventType::PackageAttempted {
+                attempt: 2,
+                command: "cargo publish -p demo".to_string(),
+            },
+            package: "demo@1.0.0".to_string(),
+        });
+    });
+
+    let lines = logs.json_lines();
+    assert_eq!(lines.len(), 2);
+    for line in &lines {
+        assert_eq!(line["target"], EVENTS_TARGET);
+        assert_eq!(line["fields"]["package"], "demo@1.0.0");
+    }
+    assert_eq!(lines[0]["fields"]["kind"], "PackageStarted");
+    assert!(lines[0]["fields"].get("attempt").is_none());
+    assert_eq!(lines[1]["fields"]["kind"], "PackageAttempted");
+    assert_eq!(lines[1]["fields"]["attempt"], 2);
+    // Free-text payloads stay out of the logs.
+    assert!(!lines[1].to_string().contains("cargo publish"));
+}
+
+#[test]
+fn record_is_silent_without_a_subscriber() {
+    // No subscriber installed: recording must still work and not panic.
+    let mut log = EventLog::new();
+    log.record(sample_event("demo@1.0.0"));
+    assert_eq!(log.len(), 1);
+}
@@ -0,0 +1,67 @@
+//! Typed errors for state persistence.
+//!
+//! [`StateError`] lets callers tell a missing state file (expected on a first
+//! run) apart from a corrupt or unreadable one without matching on message
+//! text. It implements [`std::error::Error`], so `anyhow::Result` callers keep
+//! working through `?`, and an `anyhow::Error` from [`load_state`] or
+//! [`save_state`] can be downcast back to it.
+//!
+//! [`load_state`]: super::load_state
+//! [`save_state`]: super::save_state
+
+use std::fmt;
+use std::path::PathBuf;
+
+/// Result alias for typed state persistence.
+pub type StateResult<T> = std::result::Result<T, StateError>;
+
+/// A failed state load or save.
+#[derive(Debug)]
+pub enum StateError {
+    /// No state file exists at this path
+    NotFound(PathBuf),
+    /// Reading or writing failed (the message says which step)
+    Io {
+        message: String,
+        source: std::io::Error,
+    },
+    /// The document is not valid state JSON, or could not be serialized
+    Parse {
+        message: String,
+        source: serde_json::Error,
+    },
+    /// The document's `state_version` is not one this shipper reads
+    VersionUnsupported { path: PathBuf, version: String },
+}
+
+impl StateError {
+    /// Whether the state file is simply absent, so a run can start fresh.
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, Self::NotFound(_))
+    }
+}
+
+impl fmt::Display for StateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound(path) => write!(f, "state file {} not found", path.display()),
+            Self::Io { message, .. } | Self::Parse { message, .. } => f.write_str(message),
+            Self::VersionUnsupported { path, version } => write!(
+                f,
+                "unsupported state schema version {version} in {} (supported: {})",
+                path.display(),
+                super::CURRENT_STATE_VERSION
+            ),
+        }
+    }
+}
+
+impl std::error::Error for StateError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            Self::Parse { source, .. } => Some(source),
+            Self::NotFound(_) | Self::VersionUnsupported { .. } => None,
+        }
+    }
+}
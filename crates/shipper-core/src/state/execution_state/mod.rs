@@ -23,6 +23,9 @@ use anyhow::{Context, Result};
 use crate::runtime::environment::collect_environment_fingerprint;
 use shipper_types::{ExecutionState, Receipt, ReconciliationReport};
 
+mod error;
+pub use error::{StateError, StateResult};
+
 #[cfg(test)]
 mod tests;
 
@@ -33,15 +36,19 @@ pub const CURRENT_RECEIPT_VERSION: &str = "shipper.receipt.v2";
 pub const MINIMUM_SUPPORTED_VERSION: &str = "shipper.receipt.v1";
 
 /// Current state schema version
-pub const CURRENT_STATE_VERSION: &str = "shipper.state.v1";
+pub const CURRENT_STATE_VERSION: &str = shipper_types::CURRENT_STATE_VERSION;
 
 /// Current plan schema version
-pub const CURRENT_PLAN_VERSION: &str = "shipper.plan.v1";
+pub const CURRENT_PLAN_VERSION: &str = shipper_types::CURRENT_PLAN_VERSION;
+
+/// Current preflight report schema version
+pub const CURRENT_PREFLIGHT_VERSION: &str = "shipper.preflight.v1";
 
 pub const STATE_FILE: &str = "state.json";
 pub const RECEIPT_FILE: &str = "receipt.json";
 pub const RECONCILIATION_FILE: &str = "reconciliation.json";
 pub const REMEDIATION_PLAN_FILE: &str = "remediation-plan.json";
+pub const PREFLIGHT_FILE: &str = "preflight.json";
 
 pub fn state_path(state_dir: &Path) -> PathBuf {
     state_dir.join(STATE_FILE)
@@ -59,24 +66,81 @@ pub fn remediation_plan_path(state_dir: &Path) -> PathBuf {
     state_dir.join(REMEDIATION_PLAN_FILE)
 }
 
+pub fn preflight_path(state_dir: &Path) -> PathBuf {
+    state_dir.join(PREFLIGHT_FILE)
+}
+
+/// Load `state.json`, or `None` if there is none yet.
+///
+/// Errors downcast to [`StateError`]. Unlike [`try_load_state`], the
+/// `state_version` is not checked.
 pub fn load_state(state_dir: &Path) -> Result<Option<ExecutionState>> {
+    match read_state(&state_path(state_dir)) {
+        Ok(st) => Ok(Some(st)),
+        Err(StateError::NotFound(_)) => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Load `state.json` with a typed error.
+///
+/// A missing file is [`StateError::NotFound`], so callers can start fresh
+/// on it while surfacing [`StateError::Parse`] (corruption) and
+/// [`StateError::VersionUnsupported`] (written by another shipper).
+pub fn try_load_state(state_dir: &Path) -> StateResult<ExecutionState> {
     let path = state_path(state_dir);
+    let st = read_state(&path)?;
+    let supported = st
+        .state_version
+        .strip_prefix("shipper.state.v")
+        .is_some_and(|_| {
+            shipper_types::schema::validate_schema_version_range(
+                &st.state_version,
+                CURRENT_STATE_VERSION,
+                CURRENT_STATE_VERSION,
+                "state",
+            )
+            .is_ok()
+        });
+    if !supported {
+        return Err(StateError::VersionUnsupported {
+            path,
+            version: st.state_version,
+        });
+    }
+    Ok(st)
+}
+
+fn read_state(path: &Path) -> StateResult<ExecutionState> {
     if !path.exists() {
-        return Ok(None);
+        return Err(StateError::NotFound(path.to_path_buf()));
     }
-    let content = fs::read_to_string(&path)
-        .with_context(|| format!("failed to read state file {}", path.display()))?;
-    let st: ExecutionState = serde_json::from_str(&content)
-        .with_context(|| format!("failed to parse state JSON {}", path.display()))?;
-    Ok(Some(st))
+    let content = fs::read_to_string(path).map_err(|source| StateError::Io {
+        message: format!("failed to read state file {}", path.display()),
+        source,
+    })?;
+    serde_json::from_str(&content).map_err(|source| StateError::Parse {
+        message: format!("failed to parse state JSON {}", path.display()),
+        source,
+    })
 }
 
 pub fn save_state(state_dir: &Path, state: &ExecutionState) -> Result<()> {
-    fs::create_dir_all(state_dir)
-        .with_context(|| format!("failed to create state dir {}", state_dir.display()))?;
+    Ok(try_save_state(state_dir, state)?)
+}
 
-    let path = state_path(state_dir);
-    atomic_write_json(&path, state)
+/// Atomically write `state.json` with a typed error.
+pub fn try_save_state(state_dir: &Path, state: &ExecutionState) -> StateResult<()> {
+    fs::create_dir_all(state_dir).map_err(|source| StateError::Io {
+        message: format!("failed to create state dir {}", state_dir.display()),
+        source,
+    })?;
+
+    let data = serde_json::to_vec_pretty(state).map_err(|source| StateError::Parse {
+        message: "failed to serialize JSON".to_string(),
+        source,
+    })?;
+    try_atomic_write(&state_path(state_dir), &data)
 }
 
 pub fn write_receipt(state_dir: &Path, receipt: &Receipt) -> Result<()> {
@@ -110,9 +174,43 @@ pub fn has_incomplete_state(state_dir: &Path) -> bool {
     state_path(state_dir).exists() && !receipt_path(state_dir).exists()
 }
 
+/// Encryption bound to `plan_id` as associated data, so an encrypted file
+/// copied in from another plan fails to decrypt.
+fn plan_encryption(
+    encrypt_config: &shipper_encrypt::EncryptionConfig,
+    plan_id: &str,
+) -> Result<shipper_encrypt::StateEncryption> {
+    Ok(shipper_encrypt::StateEncryption::new(encrypt_config.clone())?.with_aad(plan_id))
+}
+
+/// Read `path` bound to `plan_id`, falling back to files encrypted before
+/// state was bound to its plan (no associated data).
+fn read_plan_file(
+    path: &Path,
+    encrypt_config: &shipper_encrypt::EncryptionConfig,
+    plan_id: &str,
+) -> Result<String> {
+    let bound_err = match plan_encryption(encrypt_config, plan_id)?.read_file(path) {
+        Ok(content) => return Ok(content),
+        Err(err) => err,
+    };
+    // Unbound reads hand back undecryptable content as-is; only accept the
+    // legacy read if it actually decrypted.
+    let legacy = shipper_encrypt::StateEncryption::new(encrypt_config.clone())?.read_file(path)?;
+    if shipper_encrypt::is_encrypted(legacy.trim()) {
+        return Err(bound_err);
+    }
+    Ok(legacy)
+}
+
 /// Load state with encryption support
+///
+/// The file must have been written for `plan_id` (see
+/// [`save_state_encrypted`]); files encrypted before state was bound to its
+/// plan are still read.
 pub fn load_state_encrypted(
     state_dir: &Path,
+    plan_id: &str,
     encrypt_config: &shipper_encrypt::EncryptionConfig,
 ) -> Result<Option<ExecutionState>> {
     let path = state_path(state_dir);
@@ -120,8 +218,7 @@ pub fn load_state_encrypted(
         return Ok(None);
     }
 
-    let encryption = shipper_encrypt::StateEncryption::new(encrypt_config.clone())?;
-    let content = encryption.read_file(&path)?;
+    let content = read_plan_file(&path, encrypt_config, plan_id)?;
 
     let st: ExecutionState = serde_json::from_str(&content)
         .with_context(|| format!("failed to parse state JSON {}", path.display()))?;
@@ -129,6 +226,8 @@ pub fn load_state_encrypted(
 }
 
 /// Save state with encryption support
+///
+/// The ciphertext is bound to `state.plan_id`.
 pub fn save_state_encrypted(
     state_dir: &Path,
     state: &ExecutionState,
@@ -139,12 +238,14 @@ pub fn save_state_encrypted(
 
     let path = state_path(state_dir);
 
-    let encryption = shipper_encrypt::StateEncryption::new(encrypt_config.clone())?;
+    let encryption = plan_encryption(encrypt_config, &state.plan_id)?;
     let data = serde_json::to_vec_pretty(state).context("failed to serialize state JSON")?;
     encryption.write_file(&path, &data)
 }
 
 /// Write receipt with encryption support
+///
+/// The ciphertext is bound to `receipt.plan_id`.
 pub fn write_receipt_encrypted(
     state_dir: &Path,
     receipt: &Receipt,
@@ -155,14 +256,17 @@ pub fn write_receipt_encrypted(
 
     let path = receipt_path(state_dir);
 
-    let encryption = shipper_encrypt::StateEncryption::new(encrypt_config.clone())?;
+    let encryption = plan_encryption(encrypt_config, &receipt.plan_id)?;
     let data = serde_json::to_vec_pretty(receipt).context("failed to serialize receipt JSON")?;
     encryption.write_file(&path, &data)
 }
 
 /// Load receipt with encryption support
+///
+/// Like [`load_state_encrypted`], the file must belong to `plan_id`.
 pub fn load_receipt_encrypted(
     state_dir: &Path,
+    plan_id: &str,
     encrypt_config: &shipper_encrypt::EncryptionConfig,
 ) -> Result<Option<Receipt>> {
     let path = receipt_path(state_dir);
@@ -170,8 +274,7 @@ pub fn load_receipt_encrypted(
         return Ok(None);
     }
 
-    let encryption = shipper_encrypt::StateEncryption::new(encrypt_config.clone())?;
-    let content = encryption.read_file(&path)?;
+    let content = read_plan_file(&path, encrypt_config, plan_id)?;
 
     // Try to parse as Receipt directly
     if let Ok(receipt) = serde_json::from_str::<Receipt>(&content) {
@@ -179,22 +282,22 @@ pub fn load_receipt_encrypted(
         if let Err(_e) = validate_receipt_version(&receipt.receipt_version) {
             // If version is too old, attempt migration
             // Note: migration requires raw file access, so we'll handle this case separately
-            return migrate_receipt_encrypted(&path, encrypt_config).map(Some);
+            return migrate_receipt_encrypted(&path, plan_id, encrypt_config).map(Some);
         }
         return Ok(Some(receipt));
     }
 
     // If direct parsing failed, attempt migration
-    migrate_receipt_encrypted(&path, encrypt_config).map(Some)
+    migrate_receipt_encrypted(&path, plan_id, encrypt_config).map(Some)
 }
 
 /// Migrate receipt with encryption support
 fn migrate_receipt_encrypted(
     path: &Path,
+    plan_id: &str,
     encrypt_config: &shipper_encrypt::EncryptionConfig,
 ) -> Result<Receipt> {
-    let encryption = shipper_encrypt::StateEncryption::new(encrypt_config.clone())?;
-    let content = encryption.read_file(path)?;
+    let content = read_plan_file(path, encrypt_config, plan_id)?;
 
     let value: serde_json::Value = serde_json::from_str(&content)
         .with_context(|| format!("failed to parse receipt JSON {}", path.display()))?;
@@ -268,7 +371,17 @@ pub fn migrate_receipt(path: &Path) -> Result<Receipt> {
 }
 
 /// Migrate v1 receipt to v2
-fn migrate_v1_to_v2(mut receipt: serde_json::Value) -> Result<Receipt> {
+fn migrate_v1_to_v2(receipt: serde_json::Value) -> Result<Receipt> {
+    let receipt = migrate_receipt_value_v1_to_v2(receipt)?;
+    serde_json::from_value(receipt).context("failed to deserialize migrated receipt")
+}
+
+/// Rewrite a v1 receipt document into the v2 shape without deserializing it.
+///
+/// Registered as the receipt v1 → v2 step in [`crate::store::Migrator::receipts`].
+pub(crate) fn migrate_receipt_value_v1_to_v2(
+    mut receipt: serde_json::Value,
+) -> Result<serde_json::Value> {
     let obj = receipt
         .as_object_mut()
         .context("receipt root is not an object during v1->v2 migration")?;
@@ -291,11 +404,10 @@ fn migrate_v1_to_v2(mut receipt: serde_json::Value) -> Result<Receipt> {
     // Update receipt_version to v2
     obj.insert(
         "receipt_version".to_string(),
-        serde_json::Value::String(CURRENT_RECEIPT_VERSION.to_string()),
+        serde_json::Value::String("shipper.receipt.v2".to_string()),
     );
 
-    // Deserialize as Receipt
-    serde_json::from_value(receipt).context("failed to deserialize migrated receipt")
+    Ok(receipt)
 }
 
 /// Load receipt from state directory with migration support
@@ -335,23 +447,37 @@ pub fn fsync_parent_dir(path: &Path) {
 }
 
 pub(crate) fn atomic_write_json<T: serde::Serialize>(path: &Path, value: &T) -> Result<()> {
-    let tmp = path.with_extension("tmp");
     let data = serde_json::to_vec_pretty(value).context("failed to serialize JSON")?;
+    atomic_write_bytes(path, &data)
+}
+
+/// Atomically replace `path` with `data` (temp file, `sync_all`, rename).
+pub(crate) fn atomic_write_bytes(path: &Path, data: &[u8]) -> Result<()> {
+    Ok(try_atomic_write(path, data)?)
+}
+
+fn try_atomic_write(path: &Path, data: &[u8]) -> StateResult<()> {
+    let tmp = path.with_extension("tmp");
 
     {
-        let mut f = fs::File::create(&tmp)
-            .with_context(|| format!("failed to create tmp file {}", tmp.display()))?;
-        f.write_all(&data)
-            .with_context(|| format!("failed to write tmp file {}", tmp.display()))?;
+        let mut f = fs::File::create(&tmp).map_err(|source| StateError::Io {
+            message: format!("failed to create tmp file {}", tmp.display()),
+            source,
+        })?;
+        f.write_all(data).map_err(|source| StateError::Io {
+            message: format!("failed to write tmp file {}", tmp.display()),
+            source,
+        })?;
         f.sync_all().ok();
     }
 
-    fs::rename(&tmp, path).with_context(|| {
-        format!(
+    fs::rename(&tmp, path).map_err(|source| StateError::Io {
+        message: format!(
             "failed to rename tmp file {} to {}",
             tmp.display(),
             path.display()
-        )
+        ),
+        source,
     })?;
 
     fsync_parent_dir(path);
@@ -10,7 +10,7 @@ use tempfile::tempdir;
 
 use super::*;
 use shipper_types::{
-    ExecutionState, PackageProgress, PackageReceipt, PackageState, Receipt, Registry,
+    ExecutionState, PackageProgress, PackageReceipt, PackageState, Receipt, Registry, SkipReason,
 };
 
 fn sample_state() -> ExecutionState {
@@ -59,6 +59,7 @@ fn sample_receipt() -> Receipt {
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256: None,
         }],
         event_log_path: PathBuf::from(".shipper/events.jsonl"),
         git_context: None,
@@ -270,6 +271,70 @@ fn save_state_surfaces_rename_error() {
     assert!(format!("{err:#}").contains("failed to rename tmp file"));
 }
 
+#[test]
+fn try_load_state_reports_missing_file_as_not_found() {
+    let td = tempdir().expect("tempdir");
+
+    let err = try_load_state(td.path()).expect_err("must fail");
+    assert!(err.is_not_found(), "{err:?}");
+    assert!(matches!(err, StateError::NotFound(path) if path == state_path(td.path())));
+}
+
+#[test]
+fn try_load_state_reports_malformed_json_as_parse() {
+    let td = tempdir().expect("tempdir");
+    fs::write(state_path(td.path()), "{not-json").expect("write");
+
+    let err = try_load_state(td.path()).expect_err("must fail");
+    assert!(matches!(err, StateError::Parse { .. }), "{err:?}");
+    assert!(err.to_string().contains("failed to parse state JSON"));
+}
+
+#[test]
+fn try_load_state_rejects_unsupported_state_version() {
+    let td = tempdir().expect("tempdir");
+    let mut st = sample_state();
+    st.state_version = "shipper.state.v99".to_string();
+    save_state(td.path(), &st).expect("save");
+
+    let err = try_load_state(td.path()).expect_err("must fail");
+    assert!(
+        matches!(&err, StateError::VersionUnsupported { version, .. } if version == "shipper.state.v99"),
+        "{err:?}"
+    );
+    // The untyped loader stays lenient about the version.
+    assert!(load_state(td.path()).expect("load").is_some());
+}
+
+#[test]
+fn try_save_state_roundtrips_through_try_load_state() {
+    let td = tempdir().expect("tempdir");
+    try_save_state(td.path(), &sample_state()).expect("save");
+
+    let loaded = try_load_state(td.path()).expect("load");
+    assert_eq!(loaded.plan_id, sample_state().plan_id);
+}
+
+#[test]
+fn anyhow_state_errors_downcast_to_state_error() {
+    let td = tempdir().expect("tempdir");
+    fs::write(state_path(td.path()), "").expect("write");
+
+    let err = load_state(td.path()).expect_err("must fail");
+    assert!(matches!(
+        err.downcast_ref::<StateError>(),
+        Some(StateError::Parse { .. })
+    ));
+
+    let dir = td.path().join("state-dir");
+    fs::create_dir_all(state_path(&dir)).expect("mkdir conflicting state path");
+    let err = save_state(&dir, &sample_state()).expect_err("must fail");
+    assert!(matches!(
+        err.downcast_ref::<StateError>(),
+        Some(StateError::Io { .. })
+    ));
+}
+
 #[test]
 fn validate_receipt_version_rejects_non_shipper_version() {
     let result = validate_receipt_version("other.receipt.v2");
@@ -911,6 +976,7 @@ fn receipt_with_high_attempt_count() {
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256: None,
         }],
         ..sample_receipt()
     };
@@ -989,6 +1055,7 @@ fn deterministic_receipt() -> Receipt {
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             },
             PackageReceipt {
                 name: "beta".to_string(),
@@ -1008,6 +1075,7 @@ fn deterministic_receipt() -> Receipt {
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             },
         ],
         event_log_path: PathBuf::from(".shipper/events.jsonl"),
@@ -1089,7 +1157,7 @@ fn snapshot_state_transition_pending_to_skipped() {
     // Transition alpha from Pending â†’ Skipped
     if let Some(pkg) = state.packages.get_mut("alpha@0.1.0") {
         pkg.state = PackageState::Skipped {
-            reason: "already published".to_string(),
+            reason: SkipReason::AlreadyPublished,
         };
         pkg.attempts = 0;
         pkg.last_updated_at = fixed;
@@ -1143,7 +1211,7 @@ fn snapshot_state_all_lifecycle_variants() {
             version: "1.0.0".to_string(),
             attempts: 0,
             state: PackageState::Skipped {
-                reason: "already published".to_string(),
+                reason: SkipReason::AlreadyPublished,
             },
             last_updated_at: fixed,
         },
@@ -1220,6 +1288,7 @@ fn snapshot_receipt_all_failed() {
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             },
             PackageReceipt {
                 name: "utils".to_string(),
@@ -1239,6 +1308,7 @@ fn snapshot_receipt_all_failed() {
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             },
         ],
         event_log_path: PathBuf::from(".shipper/events.jsonl"),
@@ -1277,7 +1347,9 @@ mod proptests {
             Just(PackageState::Pending),
             Just(PackageState::Uploaded),
             Just(PackageState::Published),
-            "\\PC{1,50}".prop_map(|reason| PackageState::Skipped { reason }),
+            "\\PC{1,50}".prop_map(|reason| PackageState::Skipped {
+                reason: SkipReason::Manual(reason)
+            }),
             (arb_error_class(), "\\PC{1,50}")
                 .prop_map(|(class, message)| PackageState::Failed { class, message }),
             "\\PC{1,50}".prop_map(|message| PackageState::Ambiguous { message }),
@@ -1399,6 +1471,7 @@ mod proptests {
                     compromised_at: None,
                     compromised_by: None,
                     superseded_by: None,
+                    artifact_sha256: None,
                 }
             })
     }
@@ -1693,6 +1766,7 @@ fn receipt_all_fields_individually_verified_after_roundtrip() {
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256: None,
         }],
         event_log_path: PathBuf::from("custom/events.jsonl"),
         git_context: Some(shipper_types::GitContext {
@@ -1821,6 +1895,7 @@ fn receipt_with_evidence_data_roundtrip() {
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256: None,
         }],
         ..sample_receipt()
     };
@@ -2087,7 +2162,7 @@ fn each_package_state_variant_disk_roundtrip() {
         (
             "skipped",
             PackageState::Skipped {
-                reason: "already on registry".to_string(),
+                reason: SkipReason::Manual("already on registry".to_string()),
             },
         ),
         (
@@ -2179,6 +2254,7 @@ fn snapshot_receipt_with_git_context() {
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256: None,
         }],
         event_log_path: PathBuf::from(".shipper/events.jsonl"),
         git_context: Some(shipper_types::GitContext {
@@ -2252,6 +2328,7 @@ fn snapshot_receipt_with_evidence() {
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256: None,
         }],
         event_log_path: PathBuf::from(".shipper/events.jsonl"),
         git_context: None,
@@ -2376,7 +2453,9 @@ mod proptests_extended {
             Just(PackageState::Pending),
             Just(PackageState::Uploaded),
             Just(PackageState::Published),
-            "\\PC{1,50}".prop_map(|reason| PackageState::Skipped { reason }),
+            "\\PC{1,50}".prop_map(|reason| PackageState::Skipped {
+                reason: SkipReason::Manual(reason)
+            }),
             (arb_error_class(), "\\PC{1,50}")
                 .prop_map(|(class, message)| PackageState::Failed { class, message }),
             "\\PC{1,50}".prop_map(|message| PackageState::Ambiguous { message }),
@@ -2455,6 +2534,7 @@ mod proptests_extended {
                                     compromised_at: None,
                     compromised_by: None,
                     superseded_by: None,
+                    artifact_sha256: None,
                 })
                 .collect();
 
@@ -2580,7 +2660,7 @@ fn sample_encryption_config() -> shipper_encrypt::EncryptionConfig {
 fn load_state_encrypted_returns_none_when_file_missing() {
     let td = tempdir().expect("tempdir");
     let cfg = sample_encryption_config();
-    let loaded = load_state_encrypted(td.path(), &cfg).expect("load");
+    let loaded = load_state_encrypted(td.path(), "p1", &cfg).expect("load");
     assert!(loaded.is_none());
 }
 
@@ -2592,7 +2672,7 @@ fn save_and_load_state_encrypted_roundtrip() {
     let st = sample_state();
 
     save_state_encrypted(&dir, &st, &cfg).expect("save");
-    let loaded = load_state_encrypted(&dir, &cfg)
+    let loaded = load_state_encrypted(&dir, &st.plan_id, &cfg)
         .expect("load")
         .expect("exists");
 
@@ -2626,7 +2706,8 @@ fn load_state_encrypted_fails_with_wrong_passphrase() {
     let read_cfg = shipper_encrypt::EncryptionConfig::new("wrong".to_string());
 
     save_state_encrypted(td.path(), &st, &write_cfg).expect("save");
-    let err = load_state_encrypted(td.path(), &read_cfg).expect_err("must fail to decrypt");
+    let err =
+        load_state_encrypted(td.path(), &st.plan_id, &read_cfg).expect_err("must fail to decrypt");
     let msg = format!("{err:#}");
     assert!(
         !msg.is_empty(),
@@ -2634,13 +2715,47 @@ fn load_state_encrypted_fails_with_wrong_passphrase() {
     );
 }
 
+#[test]
+fn load_state_encrypted_rejects_state_from_another_plan() {
+    let td = tempdir().expect("tempdir");
+    let cfg = sample_encryption_config();
+    let st = sample_state();
+
+    save_state_encrypted(td.path(), &st, &cfg).expect("save");
+    let err = load_state_encrypted(td.path(), "some-other-plan", &cfg)
+        .expect_err("state bound to another plan must not load");
+    assert!(
+        format!("{err:#}").contains("does not match this context"),
+        "{err:#}"
+    );
+}
+
+#[test]
+fn load_state_encrypted_reads_legacy_unbound_file() {
+    let td = tempdir().expect("tempdir");
+    let cfg = sample_encryption_config();
+    let st = sample_state();
+
+    // Written the way state was encrypted before it was bound to a plan.
+    let legacy = shipper_encrypt::StateEncryption::new(cfg.clone()).expect("encryption client");
+    let data = serde_json::to_vec_pretty(&st).expect("serialize");
+    legacy
+        .write_file(&state_path(td.path()), &data)
+        .expect("legacy write");
+
+    let loaded = load_state_encrypted(td.path(), &st.plan_id, &cfg)
+        .expect("load")
+        .expect("exists");
+    assert_eq!(loaded.plan_id, st.plan_id);
+}
+
 // â”€â”€ Encrypted receipt I/O â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
 
 #[test]
 fn load_receipt_encrypted_returns_none_when_file_missing() {
     let td = tempdir().expect("tempdir");
     let cfg = sample_encryption_config();
-    let loaded = load_receipt_encrypted(td.path(), &cfg).expect("load");
+    let loaded = load_receipt_encrypted(td.path(), "p1", &cfg).expect("load");
     assert!(loaded.is_none());
 }
 
@@ -2652,7 +2767,7 @@ fn write_and_load_receipt_encrypted_roundtrip() {
     let receipt = sample_receipt();
 
     write_receipt_encrypted(&dir, &receipt, &cfg).expect("write");
-    let loaded = load_receipt_encrypted(&dir, &cfg)
+    let loaded = load_receipt_encrypted(&dir, &receipt.plan_id, &cfg)
         .expect("load")
         .expect("exists");
 
@@ -2707,7 +2822,7 @@ fn load_receipt_encrypted_migrates_v1_to_v2() {
         .write_file(&receipt_path(dir), &data)
         .expect("encrypt-write v1");
 
-    let migrated = load_receipt_encrypted(dir, &cfg)
+    let migrated = load_receipt_encrypted(dir, "p1", &cfg)
         .expect("load")
         .expect("exists");
 
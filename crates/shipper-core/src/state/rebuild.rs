@@ -11,7 +11,7 @@ use anyhow::{Context, Result, bail};
 use chrono::{DateTime, Utc};
 use shipper_types::{
     ErrorClass, EventType, ExecutionState, PackageProgress, PackageState, PublishEvent,
-    ReconciliationOutcome, Registry,
+    ReconciliationOutcome, Registry, SkipReason,
 };
 
 use crate::runtime::execution::pkg_key;
@@ -128,7 +128,15 @@ fn apply_event(
         EventType::PackageSkipped { reason } => {
             if let Some(progress) = ensure_event_package(packages, event, event.timestamp) {
                 progress.state = PackageState::Skipped {
-                    reason: reason.clone(),
+                    reason: SkipReason::from(reason.as_str()),
+                };
+                progress.last_updated_at = event.timestamp;
+            }
+        }
+        EventType::PackageCancelled { at_state } => {
+            if let Some(progress) = ensure_event_package(packages, event, event.timestamp) {
+                progress.state = PackageState::Cancelled {
+                    at_state: at_state.clone(),
                 };
                 progress.last_updated_at = event.timestamp;
             }
@@ -370,6 +378,13 @@ mod tests {
                         message: "auth failed".to_string(),
                     },
                 ),
+                event(
+                    4,
+                    "cancelled@1.0.0",
+                    EventType::PackageCancelled {
+                        at_state: "uploaded".to_string(),
+                    },
+                ),
             ],
         );
 
@@ -382,7 +397,7 @@ mod tests {
         assert_eq!(
             state.packages["skipped@1.0.0"].state,
             PackageState::Skipped {
-                reason: "already present".to_string()
+                reason: SkipReason::Manual("already present".to_string())
             }
         );
         assert_eq!(
@@ -392,6 +407,12 @@ mod tests {
                 message: "auth failed".to_string()
             }
         );
+        assert_eq!(
+            state.packages["cancelled@1.0.0"].state,
+            PackageState::Cancelled {
+                at_state: "uploaded".to_string()
+            }
+        );
     }
 
     #[test]
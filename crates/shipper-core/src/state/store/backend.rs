@@ -0,0 +1,331 @@
+//! [`StateStore`] over any [`StorageBackend`], for sharing resume state
+//! between machines through cloud storage.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::ops::storage::StorageBackend;
+use crate::state::events::{EVENTS_FILE, EventLog};
+use crate::state::execution_state as state;
+use crate::types::{ExecutionState, PublishEvent, Receipt};
+
+use super::StateStore;
+use super::fs::{check_schema_version, migrate_receipt};
+use super::layout::StateLayout;
+
+/// State store that keeps its documents as objects in a [`StorageBackend`].
+///
+/// Documents use the same names as [`FileStore`](super::FileStore)
+/// (`state.json`, `receipt.json`, `events.jsonl`) under a configurable key
+/// prefix, and go through the same schema-version checks and receipt
+/// migrations on load. [`for_plan`](Self::for_plan) instead places them at
+/// the [`StateLayout`] keys for one plan. Each save is a single backend
+/// `write`, so it is as
+/// atomic as the backend makes it. Blobs cannot be appended to, so
+/// `save_events` reads the existing log and writes it back with the new
+/// events added.
+pub(crate) struct StorageStateStore<B> {
+    backend: B,
+    prefix: String,
+    /// Receipt key when it lives outside `prefix` (see [`Self::for_plan`])
+    receipt_key: Option<String>,
+}
+
+impl<B: StorageBackend> StorageStateStore<B> {
+    /// Store documents in `backend` under `prefix` (e.g. `runs/<plan_id>`).
+    /// Leading and trailing slashes are ignored; an empty prefix stores them
+    /// at the backend root.
+    pub(crate) fn new(backend: B, prefix: impl Into<String>) -> Self {
+        Self {
+            backend,
+            prefix: prefix.into().trim_matches('/').to_string(),
+            receipt_key: None,
+        }
+    }
+
+    /// Store one plan's documents at their [`StateLayout`] keys: state and
+    /// events under `plans/<plan_id>/`, the receipt at
+    /// `receipts/<plan_id>.json`.
+    pub(crate) fn for_plan(backend: B, plan_id: &str) -> Self {
+        Self {
+            backend,
+            prefix: StateLayout::plan_prefix(plan_id),
+            receipt_key: Some(StateLayout::receipt_key(plan_id)),
+        }
+    }
+
+    /// The key prefix documents are stored under.
+    pub(crate) fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// The wrapped backend.
+    pub(crate) fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    /// Backend key for the document `name`.
+    pub(crate) fn key(&self, name: &str) -> String {
+        if name == state::RECEIPT_FILE
+            && let Some(key) = &self.receipt_key
+        {
+            return key.clone();
+        }
+        if self.prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}/{name}", self.prefix)
+        }
+    }
+
+    fn save_json<T: Serialize>(&self, name: &str, data: &T) -> Result<()> {
+        let key = self.key(name);
+        let payload = serde_json::to_vec_pretty(data)
+            .with_context(|| format!("failed to serialize {key}"))?;
+        self.backend
+            .write(&key, &payload)
+            .with_context(|| format!("failed to write {key}"))
+    }
+
+    /// Read `name` and check its schema version, or `None` if it is absent.
+    fn load_versioned(
+        &self,
+        name: &str,
+        expected_prefix: &str,
+    ) -> Result<Option<(String, serde_json::Value)>> {
+        let key = self.key(name);
+        let Some(bytes) = self.read_if_exists(&key)? else {
+            return Ok(None);
+        };
+        let value: serde_json::Value = serde_json::from_slice(&bytes)
+            .with_context(|| format!("failed to parse JSON {key}"))?;
+        check_schema_version(&value, expected_prefix, &key)?;
+        Ok(Some((key, value)))
+    }
+
+    fn read_if_exists(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        if !self.backend.exists(key)? {
+            return Ok(None);
+        }
+        let bytes = self
+            .backend
+            .read(key)
+            .with_context(|| format!("failed to read {key}"))?;
+        Ok(Some(bytes))
+    }
+}
+
+impl<B: StorageBackend> StateStore for StorageStateStore<B> {
+    fn save_state(&self, state: &ExecutionState) -> Result<()> {
+        self.save_json(state::STATE_FILE, state)
+    }
+
+    fn load_state(&self) -> Result<Option<ExecutionState>> {
+        let Some((key, value)) = self.load_versioned(state::STATE_FILE, "shipper.state")? else {
+            return Ok(None);
+        };
+        let state = serde_json::from_value(value)
+            .with_context(|| format!("failed to deserialize {key}"))?;
+        Ok(Some(state))
+    }
+
+    fn save_receipt(&self, receipt: &Receipt) -> Result<()> {
+        self.save_json(state::RECEIPT_FILE, receipt)
+    }
+
+    fn load_receipt(&self) -> Result<Option<Receipt>> {
+        let Some((key, value)) = self.load_versioned(state::RECEIPT_FILE, "shipper.receipt")?
+        else {
+            return Ok(None);
+        };
+        let receipt = serde_json::from_value(migrate_receipt(value)?)
+            .with_context(|| format!("failed to deserialize receipt {key}"))?;
+        Ok(Some(receipt))
+    }
+
+    fn save_events(&self, events: &EventLog) -> Result<()> {
+        let key = self.key(EVENTS_FILE);
+        let mut payload = self.read_if_exists(&key)?.unwrap_or_default();
+        for event in events.all_events() {
+            serde_json::to_writer(&mut payload, event)
+                .context("failed to serialize event to JSON")?;
+            payload.push(b'\n');
+        }
+        self.backend
+            .write(&key, &payload)
+            .with_context(|| format!("failed to write {key}"))
+    }
+
+    fn load_events(&self) -> Result<Option<EventLog>> {
+        let key = self.key(EVENTS_FILE);
+        let Some(bytes) = self.read_if_exists(&key)? else {
+            return Ok(None);
+        };
+        let content =
+            String::from_utf8(bytes).with_context(|| format!("{key} is not valid UTF-8"))?;
+
+        let mut log = EventLog::new();
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            let event: PublishEvent = serde_json::from_str(line)
+                .with_context(|| format!("failed to parse event JSON from line: {line}"))?;
+            log.record(event);
+        }
+        Ok(Some(log))
+    }
+
+    fn clear(&self) -> Result<()> {
+        for name in [
+            state::STATE_FILE,
+            state::RECEIPT_FILE,
+            state::RECONCILIATION_FILE,
+            EVENTS_FILE,
+        ] {
+            let key = self.key(name);
+            if self.backend.exists(&key)? {
+                self.backend
+                    .delete(&key)
+                    .with_context(|| format!("failed to delete {key}"))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use chrono::Utc;
+
+    use super::*;
+    use crate::ops::storage::memory::MemoryStorage;
+    use crate::types::{EventType, PackageProgress, PackageState};
+
+    fn sample_state() -> ExecutionState {
+        let mut packages = BTreeMap::new();
+        packages.insert(
+            "demo@0.1.0".to_string(),
+            PackageProgress {
+                name: "demo".to_string(),
+                version: "0.1.0".to_string(),
+                attempts: 1,
+                state: PackageState::Published,
+                last_updated_at: Utc::now(),
+            },
+        );
+        ExecutionState {
+            state_version: state::CURRENT_STATE_VERSION.to_string(),
+            plan_id: "plan-1".to_string(),
+            registry: crate::types::Registry::crates_io(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            attempt_history: Vec::new(),
+            packages,
+        }
+    }
+
+    fn sample_event(package: &str) -> PublishEvent {
+        PublishEvent {
+            timestamp: Utc::now(),
+            event_type: EventType::ExecutionStarted,
+            package: package.to_string(),
+        }
+    }
+
+    #[test]
+    fn save_load_and_clear_state() {
+        let store = StorageStateStore::new(MemoryStorage::default(), "runs/plan-1/");
+        assert_eq!(store.prefix(), "runs/plan-1");
+        assert!(store.load_state().expect("load empty").is_none());
+
+        let state = sample_state();
+        store.save_state(&state).expect("save");
+        assert!(
+            store
+                .backend()
+                .exists("runs/plan-1/state.json")
+                .expect("exists")
+        );
+
+        let loaded = store.load_state().expect("load").expect("present");
+        assert_eq!(loaded.plan_id, "plan-1");
+        assert_eq!(loaded.packages.len(), 1);
+
+        store.clear().expect("clear");
+        assert!(store.load_state().expect("load after clear").is_none());
+        assert!(
+            !store
+                .backend()
+                .exists("runs/plan-1/state.json")
+                .expect("exists")
+        );
+    }
+
+    #[test]
+    fn missing_documents_load_as_none() {
+        let store = StorageStateStore::new(MemoryStorage::default(), "");
+        assert!(store.load_state().expect("state").is_none());
+        assert!(store.load_receipt().expect("receipt").is_none());
+        assert!(store.load_events().expect("events").is_none());
+        store.clear().expect("clear on empty store");
+    }
+
+    #[test]
+    fn corrupt_or_foreign_state_is_an_error() {
+        let store = StorageStateStore::new(MemoryStorage::default(), "ci");
+
+        store
+            .backend()
+            .write("ci/state.json", b"{not json")
+            .expect("write");
+        let err = store.load_state().expect_err("corrupt");
+        assert!(
+            err.to_string()
+                .contains("failed to parse JSON ci/state.json")
+        );
+
+        store
+            .backend()
+            .write(
+                "ci/state.json",
+                br#"{"receipt_version":"shipper.receipt.v2"}"#,
+            )
+            .expect("write");
+        let err = store.load_state().expect_err("wrong document type");
+        assert!(
+            err.to_string()
+                .contains("expected a shipper.state document"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn save_events_appends_to_existing_log() {
+        let store = StorageStateStore::new(MemoryStorage::default(), "ci");
+
+        let mut first = EventLog::new();
+        first.record(sample_event("a@0.1.0"));
+        store.save_events(&first).expect("save first");
+
+        let mut second = EventLog::new();
+        second.record(sample_event("b@0.1.0"));
+        store.save_events(&second).expect("save second");
+
+        let loaded = store.load_events().expect("load").expect("present");
+        let packages: Vec<_> = loaded
+            .all_events()
+            .iter()
+            .map(|e| e.package.as_str())
+            .collect();
+        assert_eq!(packages, ["a@0.1.0", "b@0.1.0"]);
+    }
+
+    #[test]
+    fn works_as_a_trait_object() {
+        let store: Box<dyn StateStore> =
+            Box::new(StorageStateStore::new(MemoryStorage::default(), "x"));
+        store.save_state(&sample_state()).expect("save");
+        assert!(store.load_state().expect("load").is_some());
+        assert_eq!(store.stats().expect("stats").file_count, 1);
+    }
+}
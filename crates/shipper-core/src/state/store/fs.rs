@@ -2,13 +2,18 @@
 
 use std::path::{Path, PathBuf};
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha256};
+use shipper_types::schema::{parse_schema_version, validate_schema_version_range};
 
 use crate::state::events::EventLog;
 use crate::state::execution_state as state;
-use crate::types::{ExecutionState, Receipt};
+use crate::types::{ExecutionState, PreflightReport, Receipt};
 
-use super::StateStore;
+use super::{Migrator, StateMetadata, StateStore, StoreStats};
 
 /// Filesystem-based state store implementation.
 ///
@@ -27,6 +32,230 @@ impl FileStore {
     pub fn state_dir(&self) -> &Path {
         &self.state_dir
     }
+
+    /// Files written by this store: state, receipt, reconciliation report,
+    /// preflight report, event log, and the integrity sidecars.
+    fn managed_paths(&self) -> Vec<PathBuf> {
+        vec![
+            state::state_path(&self.state_dir),
+            state::receipt_path(&self.state_dir),
+            state::reconciliation_path(&self.state_dir),
+            state::preflight_path(&self.state_dir),
+            crate::state::events::events_path(&self.state_dir),
+            metadata_path(&self.state_dir, state::STATE_FILE),
+            metadata_path(&self.state_dir, state::RECEIPT_FILE),
+        ]
+    }
+
+    /// Write `report` to `<state_dir>/preflight.json`, stamped with
+    /// `schema_version = shipper.preflight.v1`, so a later job can read the
+    /// finishability verdict without re-running preflight.
+    pub fn save_preflight(&self, report: &PreflightReport) -> Result<()> {
+        let mut value = serde_json::to_value(report).context("failed to serialize preflight")?;
+        if let Some(object) = value.as_object_mut() {
+            object.insert(
+                "schema_version".to_string(),
+                state::CURRENT_PREFLIGHT_VERSION.into(),
+            );
+        }
+        std::fs::create_dir_all(&self.state_dir)
+            .with_context(|| format!("failed to create state dir {}", self.state_dir.display()))?;
+        state::atomic_write_json(&state::preflight_path(&self.state_dir), &value)
+    }
+
+    /// Load the report written by [`FileStore::save_preflight`], rejecting
+    /// documents of another type or schema version. Returns `None` if no
+    /// preflight report has been saved.
+    pub fn load_preflight(&self) -> Result<Option<PreflightReport>> {
+        self.load_json_versioned(state::PREFLIGHT_FILE, "shipper.preflight")
+    }
+
+    /// Load `<state_dir>/<name>` as `T` after validating its schema version.
+    ///
+    /// The version is read from the first of `schema_version`,
+    /// `receipt_version` or `state_version` present in the file. It must start
+    /// with `expected_prefix` (e.g. `shipper.receipt`) and lie within the
+    /// range this build supports for that document type; only then is the
+    /// full `T` deserialized. Returns `None` if the file does not exist.
+    pub fn load_json_versioned<T: DeserializeOwned>(
+        &self,
+        name: &str,
+        expected_prefix: &str,
+    ) -> Result<Option<T>> {
+        let Some(value) = self.read_versioned_value(name, expected_prefix)? else {
+            return Ok(None);
+        };
+        let path = self.state_dir.join(name);
+        let parsed = serde_json::from_value(value)
+            .with_context(|| format!("failed to deserialize {}", path.display()))?;
+        Ok(Some(parsed))
+    }
+
+    /// Write `data` to `<state_dir>/<name>` with an integrity sidecar.
+    ///
+    /// The payload is serialized as pretty JSON and its SHA-256 is recorded,
+    /// along with timestamps and the payload's schema version, in
+    /// `<name>.meta.json`. `created_at` is carried over from an existing
+    /// sidecar. Both files are written atomically, payload first.
+    pub fn save_json_with_metadata<T: Serialize>(
+        &self,
+        name: &str,
+        data: &T,
+    ) -> Result<StateMetadata> {
+        std::fs::create_dir_all(&self.state_dir)
+            .with_context(|| format!("failed to create state dir {}", self.state_dir.display()))?;
+
+        let payload = serde_json::to_vec_pretty(data).context("failed to serialize JSON")?;
+        let value: serde_json::Value =
+            serde_json::from_slice(&payload).context("failed to serialize JSON")?;
+        let meta_path = metadata_path(&self.state_dir, name);
+        let now = Utc::now();
+        let created_at = match read_metadata(&meta_path) {
+            Ok(Some(existing)) => existing.created_at,
+            _ => now,
+        };
+
+        let metadata = StateMetadata {
+            checksum: Some(sha256_hex(&payload)),
+            created_at,
+            updated_at: now,
+            schema_version: schema_version_of(&value).map(str::to_string),
+        };
+
+        state::atomic_write_bytes(&self.state_dir.join(name), &payload)?;
+        state::atomic_write_json(&meta_path, &metadata)?;
+        Ok(metadata)
+    }
+
+    /// Load `<state_dir>/<name>` after verifying it against its sidecar.
+    ///
+    /// Recomputes the payload's SHA-256 and errors with both hashes if it
+    /// differs from the checksum in `<name>.meta.json`. A missing sidecar or
+    /// one without a checksum is also an error. Returns `None` if the payload
+    /// does not exist.
+    pub fn load_json_verified<T: DeserializeOwned>(&self, name: &str) -> Result<Option<T>> {
+        let path = self.state_dir.join(name);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let meta_path = metadata_path(&self.state_dir, name);
+        let metadata = read_metadata(&meta_path)?
+            .with_context(|| format!("missing metadata sidecar {}", meta_path.display()))?;
+        let stored = metadata
+            .checksum
+            .with_context(|| format!("metadata {} has no checksum", meta_path.display()))?;
+
+        let payload =
+            std::fs::read(&path).with_context(|| format!("failed to read {}", path.display()))?;
+        let computed = sha256_hex(&payload);
+        if stored != computed {
+            bail!(
+                "checksum mismatch for {}: stored {stored}, computed {computed}",
+                path.display()
+            );
+        }
+
+        let parsed = serde_json::from_slice(&payload)
+            .with_context(|| format!("failed to parse JSON {}", path.display()))?;
+        Ok(Some(parsed))
+    }
+
+    fn read_versioned_value(
+        &self,
+        name: &str,
+        expected_prefix: &str,
+    ) -> Result<Option<serde_json::Value>> {
+        let path = self.state_dir.join(name);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let value: serde_json::Value = serde_json::from_str(&content)
+            .with_context(|| format!("failed to parse JSON {}", path.display()))?;
+
+        check_schema_version(&value, expected_prefix, &path.display().to_string())?;
+        Ok(Some(value))
+    }
+}
+
+/// Check that `value` (read from `source`) is an `expected_prefix` document
+/// whose schema version this build supports.
+pub(super) fn check_schema_version(
+    value: &serde_json::Value,
+    expected_prefix: &str,
+    source: &str,
+) -> Result<()> {
+    let version = schema_version_of(value)
+        .with_context(|| format!("{source} has no schema version field"))?;
+
+    let Some((minimum, current)) = supported_versions(expected_prefix) else {
+        bail!("no supported schema versions registered for {expected_prefix}");
+    };
+    if version
+        .strip_prefix(expected_prefix)
+        .and_then(|rest| rest.strip_prefix(".v"))
+        .is_none()
+    {
+        bail!("{source} has schema version {version}, expected a {expected_prefix} document");
+    }
+    validate_schema_version_range(version, minimum, current, expected_prefix)
+        .with_context(|| format!("unsupported schema version in {source}"))
+}
+
+/// Migrate a version-checked receipt document to the current schema.
+pub(super) fn migrate_receipt(value: serde_json::Value) -> Result<serde_json::Value> {
+    // Checked by `check_schema_version`, so the version field is present.
+    let from = parse_schema_version(schema_version_of(&value).unwrap_or_default())?;
+    let to = parse_schema_version(state::CURRENT_RECEIPT_VERSION)?;
+    Migrator::receipts().migrate(value, from, to)
+}
+
+/// Fields checked, in order, for a document's schema version.
+const VERSION_FIELDS: [&str; 3] = ["schema_version", "receipt_version", "state_version"];
+
+fn schema_version_of(value: &serde_json::Value) -> Option<&str> {
+    VERSION_FIELDS
+        .iter()
+        .find_map(|field| value.get(*field).and_then(|v| v.as_str()))
+}
+
+/// Path of the integrity sidecar for `<state_dir>/<name>`.
+fn metadata_path(state_dir: &Path, name: &str) -> PathBuf {
+    state_dir.join(format!("{name}.meta.json"))
+}
+
+fn read_metadata(path: &Path) -> Result<Option<StateMetadata>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read metadata {}", path.display()))?;
+    let metadata = serde_json::from_str(&content)
+        .with_context(|| format!("failed to parse metadata {}", path.display()))?;
+    Ok(Some(metadata))
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// Minimum and current schema versions this build reads for a document type.
+fn supported_versions(prefix: &str) -> Option<(&'static str, &'static str)> {
+    match prefix {
+        "shipper.receipt" => Some((
+            state::MINIMUM_SUPPORTED_VERSION,
+            state::CURRENT_RECEIPT_VERSION,
+        )),
+        "shipper.state" => Some((state::CURRENT_STATE_VERSION, state::CURRENT_STATE_VERSION)),
+        "shipper.preflight" => Some((
+            state::CURRENT_PREFLIGHT_VERSION,
+            state::CURRENT_PREFLIGHT_VERSION,
+        )),
+        _ => None,
+    }
 }
 
 impl StateStore for FileStore {
@@ -35,7 +264,7 @@ impl StateStore for FileStore {
     }
 
     fn load_state(&self) -> Result<Option<ExecutionState>> {
-        state::load_state(&self.state_dir)
+        self.load_json_versioned(state::STATE_FILE, "shipper.state")
     }
 
     fn save_receipt(&self, receipt: &Receipt) -> Result<()> {
@@ -43,7 +272,16 @@ impl StateStore for FileStore {
     }
 
     fn load_receipt(&self) -> Result<Option<Receipt>> {
-        state::load_receipt(&self.state_dir)
+        let Some(value) = self.read_versioned_value(state::RECEIPT_FILE, "shipper.receipt")? else {
+            return Ok(None);
+        };
+
+        let value = migrate_receipt(value)?;
+
+        let path = state::receipt_path(&self.state_dir);
+        let receipt = serde_json::from_value(value)
+            .with_context(|| format!("failed to deserialize receipt {}", path.display()))?;
+        Ok(Some(receipt))
     }
 
     fn save_events(&self, events: &EventLog) -> Result<()> {
@@ -59,6 +297,32 @@ impl StateStore for FileStore {
         Ok(Some(EventLog::read_from_file(&path)?))
     }
 
+    /// Stats over the files this store manages, including their mtimes.
+    fn stats(&self) -> Result<StoreStats> {
+        let mut stats = StoreStats::default();
+
+        for path in self.managed_paths() {
+            let metadata = match std::fs::metadata(&path) {
+                Ok(metadata) => metadata,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(err) => {
+                    return Err(err).with_context(|| format!("failed to stat {}", path.display()));
+                }
+            };
+            stats.add_document(metadata.len());
+
+            if let Ok(modified) = metadata.modified() {
+                let modified = DateTime::<Utc>::from(modified);
+                stats.oldest_modified =
+                    Some(stats.oldest_modified.map_or(modified, |t| t.min(modified)));
+                stats.newest_modified =
+                    Some(stats.newest_modified.map_or(modified, |t| t.max(modified)));
+            }
+        }
+
+        Ok(stats)
+    }
+
     fn clear(&self) -> Result<()> {
         let state_path = state::state_path(&self.state_dir);
         let receipt_path = state::receipt_path(&self.state_dir);
@@ -88,6 +352,14 @@ impl StateStore for FileStore {
                 format!("failed to remove events file {}", events_path.display())
             })?;
         }
+        for name in [state::STATE_FILE, state::RECEIPT_FILE] {
+            let meta_path = metadata_path(&self.state_dir, name);
+            if meta_path.exists() {
+                std::fs::remove_file(&meta_path).with_context(|| {
+                    format!("failed to remove metadata file {}", meta_path.display())
+                })?;
+            }
+        }
 
         Ok(())
     }
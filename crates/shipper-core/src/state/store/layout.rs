@@ -0,0 +1,171 @@
+//! Canonical key layout for keeping several plans in one storage backend.
+//!
+//! ```text
+//! plans/<plan_id>/state.json
+//! plans/<plan_id>/events.jsonl
+//! receipts/<plan_id>.json
+//! ```
+//!
+//! [`StorageStateStore::for_plan`](super::StorageStateStore::for_plan) reads
+//! and writes exactly these keys, so a bucket can hold concurrent and
+//! archived plans side by side.
+
+use std::collections::BTreeSet;
+
+use anyhow::Result;
+
+use crate::ops::storage::StorageBackend;
+use crate::state::events::EVENTS_FILE;
+use crate::state::execution_state::STATE_FILE;
+
+/// Key prefix holding one directory of live documents per plan.
+pub(crate) const PLANS_PREFIX: &str = "plans";
+
+/// Key prefix holding one receipt per finished plan.
+pub(crate) const RECEIPTS_PREFIX: &str = "receipts";
+
+/// Builds storage keys namespaced by plan id.
+pub(crate) struct StateLayout;
+
+impl StateLayout {
+    /// `plans/<plan_id>`: the prefix for a plan's state and events.
+    pub(crate) fn plan_prefix(plan_id: &str) -> String {
+        format!("{PLANS_PREFIX}/{plan_id}")
+    }
+
+    /// `plans/<plan_id>/state.json`
+    pub(crate) fn state_key(plan_id: &str) -> String {
+        format!("{}/{STATE_FILE}", Self::plan_prefix(plan_id))
+    }
+
+    /// `plans/<plan_id>/events.jsonl`
+    pub(crate) fn events_key(plan_id: &str) -> String {
+        format!("{}/{EVENTS_FILE}", Self::plan_prefix(plan_id))
+    }
+
+    /// `receipts/<plan_id>.json`
+    pub(crate) fn receipt_key(plan_id: &str) -> String {
+        format!("{RECEIPTS_PREFIX}/{plan_id}.json")
+    }
+
+    /// Plan ids with any document in `backend`, sorted and deduplicated.
+    ///
+    /// A plan counts if it has a key under `plans/<plan_id>/` or a
+    /// `receipts/<plan_id>.json`, so archived plans whose live documents
+    /// were cleared still show up. Keys that do not fit the layout are
+    /// ignored.
+    pub(crate) fn list_plans<B: StorageBackend + ?Sized>(backend: &B) -> Result<Vec<String>> {
+        let mut plans = BTreeSet::new();
+        for key in backend.list(&format!("{PLANS_PREFIX}/"))? {
+            if let Some(plan_id) = Self::plan_id_from_plan_key(&key) {
+                plans.insert(plan_id.to_string());
+            }
+        }
+        for key in backend.list(&format!("{RECEIPTS_PREFIX}/"))? {
+            if let Some(plan_id) = Self::plan_id_from_receipt_key(&key) {
+                plans.insert(plan_id.to_string());
+            }
+        }
+        Ok(plans.into_iter().collect())
+    }
+
+    fn plan_id_from_plan_key(key: &str) -> Option<&str> {
+        let rest = key.strip_prefix(PLANS_PREFIX)?.strip_prefix('/')?;
+        let (plan_id, document) = rest.split_once('/')?;
+        (!plan_id.is_empty() && !document.is_empty()).then_some(plan_id)
+    }
+
+    fn plan_id_from_receipt_key(key: &str) -> Option<&str> {
+        let rest = key.strip_prefix(RECEIPTS_PREFIX)?.strip_prefix('/')?;
+        let plan_id = rest.strip_suffix(".json")?;
+        (!plan_id.is_empty() && !plan_id.contains('/')).then_some(plan_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::storage::memory::MemoryStorage;
+    use crate::state::events::EventLog;
+    use crate::store::tests::{sample_receipt, sample_state};
+    use crate::store::{StateStore, StorageStateStore};
+
+    #[test]
+    fn keys_are_namespaced_by_plan_id() {
+        assert_eq!(StateLayout::state_key("abc"), "plans/abc/state.json");
+        assert_eq!(StateLayout::events_key("abc"), "plans/abc/events.jsonl");
+        assert_eq!(StateLayout::receipt_key("abc"), "receipts/abc.json");
+    }
+
+    #[test]
+    fn list_plans_finds_live_and_archived_plans() {
+        let storage = MemoryStorage::default();
+        for key in [
+            StateLayout::state_key("plan-b"),
+            StateLayout::events_key("plan-b"),
+            StateLayout::state_key("plan-a"),
+            StateLayout::receipt_key("plan-a"),
+            StateLayout::receipt_key("plan-c"),
+        ] {
+            storage.write(&key, b"{}").expect("write");
+        }
+
+        assert_eq!(
+            StateLayout::list_plans(&storage).expect("list"),
+            ["plan-a", "plan-b", "plan-c"]
+        );
+    }
+
+    #[test]
+    fn list_plans_ignores_keys_outside_the_layout() {
+        let storage = MemoryStorage::default();
+        for key in [
+            "state.json",
+            "plans/orphan.json",
+            "receipts/nested/plan.json",
+            "receipts/plan-x.txt",
+            "other/plan-y/state.json",
+        ] {
+            storage.write(key, b"{}").expect("write");
+        }
+        storage
+            .write(&StateLayout::events_key("plan-z"), b"")
+            .expect("write");
+
+        assert_eq!(StateLayout::list_plans(&storage).expect("list"), ["plan-z"]);
+    }
+
+    #[test]
+    fn list_plans_is_empty_for_an_empty_backend() {
+        let storage = MemoryStorage::default();
+        assert!(StateLayout::list_plans(&storage).expect("list").is_empty());
+    }
+
+    #[test]
+    fn storage_state_store_for_plan_uses_layout_keys() {
+        let store = StorageStateStore::for_plan(MemoryStorage::default(), "plan-1");
+        store.save_state(&sample_state()).expect("save state");
+        store.save_events(&EventLog::new()).expect("save events");
+        store.save_receipt(&sample_receipt()).expect("save receipt");
+
+        for key in [
+            StateLayout::state_key("plan-1"),
+            StateLayout::events_key("plan-1"),
+            StateLayout::receipt_key("plan-1"),
+        ] {
+            assert!(store.backend().exists(&key).expect("exists"), "{key}");
+        }
+        assert!(store.load_receipt().expect("load").is_some());
+        assert_eq!(
+            StateLayout::list_plans(store.backend()).expect("list"),
+            ["plan-1"]
+        );
+
+        store.clear().expect("clear");
+        assert!(
+            StateLayout::list_plans(store.backend())
+                .expect("list")
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,91 @@
+//! Ordered registry of schema migrations for persisted JSON documents.
+//!
+//! Each [`Migration`] rewrites a raw [`serde_json::Value`] from one schema
+//! version number to the next; [`Migrator::migrate`] chains them so callers
+//! never branch on individual versions.
+
+use anyhow::{Context, Result, bail};
+use serde_json::Value;
+
+use crate::state::execution_state as state;
+
+/// One step that rewrites a document from schema version `from` to `to`.
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    /// Version number (the `N` in `shipper.<doc>.vN`) the step reads.
+    pub from: u32,
+    /// Version number the step produces.
+    pub to: u32,
+    /// Transform applied to the raw document.
+    pub apply: fn(Value) -> Result<Value>,
+}
+
+/// Ordered set of [`Migration`]s for one document type.
+#[derive(Debug, Clone, Default)]
+pub struct Migrator {
+    migrations: Vec<Migration>,
+}
+
+impl Migrator {
+    /// Create an empty migrator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Migrator holding every registered receipt migration.
+    pub fn receipts() -> Self {
+        Self::new().with(Migration {
+            from: 1,
+            to: 2,
+            apply: state::migrate_receipt_value_v1_to_v2,
+        })
+    }
+
+    /// Migrator holding every registered execution-state migration.
+    ///
+    /// Empty while `shipper.state.v1` is the only state schema.
+    pub fn state() -> Self {
+        Self::new()
+    }
+
+    /// Register a migration, keeping the list ordered by `from`.
+    pub fn with(mut self, migration: Migration) -> Self {
+        let index = self
+            .migrations
+            .partition_point(|existing| existing.from <= migration.from);
+        self.migrations.insert(index, migration);
+        self
+    }
+
+    /// Registered migrations, ordered by `from`.
+    pub fn migrations(&self) -> &[Migration] {
+        &self.migrations
+    }
+
+    /// Migrate `value` from version `from` up to version `to`.
+    ///
+    /// Steps are chained in order; each must start where the previous one
+    /// ended and must not overshoot `to`. Errors if no such path exists or if
+    /// `to` is older than `from`.
+    pub fn migrate(&self, mut value: Value, from: u32, to: u32) -> Result<Value> {
+        if to < from {
+            bail!("cannot migrate backwards from v{from} to v{to}");
+        }
+
+        let mut current = from;
+        while current < to {
+            let Some(step) = self
+                .migrations
+                .iter()
+                .find(|m| m.from == current && m.to > current && m.to <= to)
+            else {
+                bail!("no migration registered from v{current} toward v{to}");
+            };
+            value = (step.apply)(value)
+                .with_context(|| format!("migration v{} -> v{} failed", step.from, step.to))?;
+            current = step.to;
+        }
+
+        Ok(value)
+    }
+}
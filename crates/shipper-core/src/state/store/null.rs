@@ -0,0 +1,70 @@
+//! No-op implementation of [`StateStore`] for preview runs.
+
+use anyhow::Result;
+
+use crate::state::events::EventLog;
+use crate::types::{ExecutionState, Receipt};
+
+use super::{StateStore, StoreStats};
+
+/// State store that persists nothing.
+///
+/// Saves are discarded, loads always return `None` and `clear` does
+/// nothing, so a dry-run publish can drive the same engine code path
+/// without touching disk.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NullStore;
+
+impl NullStore {
+    /// Label used in place of a storage location when reporting.
+    pub const LOCATION: &'static str = "<null>";
+
+    /// Create a new no-op store.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Always [`NullStore::LOCATION`]; nothing is stored anywhere.
+    pub fn location(&self) -> &'static str {
+        Self::LOCATION
+    }
+
+    /// Always `false`: the store never holds any state.
+    pub fn exists(&self) -> bool {
+        false
+    }
+}
+
+impl StateStore for NullStore {
+    fn save_state(&self, _state: &ExecutionState) -> Result<()> {
+        Ok(())
+    }
+
+    fn load_state(&self) -> Result<Option<ExecutionState>> {
+        Ok(None)
+    }
+
+    fn save_receipt(&self, _receipt: &Receipt) -> Result<()> {
+        Ok(())
+    }
+
+    fn load_receipt(&self) -> Result<Option<Receipt>> {
+        Ok(None)
+    }
+
+    fn save_events(&self, _events: &EventLog) -> Result<()> {
+        Ok(())
+    }
+
+    fn load_events(&self) -> Result<Option<EventLog>> {
+        Ok(None)
+    }
+
+    fn clear(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn stats(&self) -> Result<StoreStats> {
+        Ok(StoreStats::default())
+    }
+}
@@ -10,7 +10,7 @@ use crate::state::events::EventLog;
 use crate::types::{
     EnvironmentFingerprint, ErrorClass, EventType, ExecutionResult, ExecutionState, GitContext,
     PackageEvidence, PackageProgress, PackageReceipt, PackageState, PublishEvent, Receipt,
-    Registry,
+    Registry, SkipReason,
 };
 
 use super::*;
@@ -92,7 +92,7 @@ fn snapshot_execution_state_all_package_states() {
             version: "1.0.0".to_string(),
             attempts: 0,
             state: PackageState::Skipped {
-                reason: "already published".to_string(),
+                reason: SkipReason::AlreadyPublished,
             },
             last_updated_at: t,
         },
@@ -180,6 +180,7 @@ fn snapshot_receipt_minimal() {
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256: None,
         }],
         event_log_path: PathBuf::from(".shipper/events.jsonl"),
         git_context: None,
@@ -222,6 +223,7 @@ fn snapshot_receipt_with_git_context() {
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256: None,
         }],
         event_log_path: PathBuf::from(".shipper/events.jsonl"),
         git_context: Some(GitContext {
@@ -270,13 +272,14 @@ fn snapshot_receipt_mixed_outcomes() {
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             },
             PackageReceipt {
                 name: "utils".to_string(),
                 version: "1.0.0".to_string(),
                 attempts: 0,
                 state: PackageState::Skipped {
-                    reason: "already published".to_string(),
+                    reason: SkipReason::AlreadyPublished,
                 },
                 started_at: t,
                 finished_at: t,
@@ -288,6 +291,7 @@ fn snapshot_receipt_mixed_outcomes() {
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             },
             PackageReceipt {
                 name: "cli".to_string(),
@@ -307,6 +311,7 @@ fn snapshot_receipt_mixed_outcomes() {
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             },
         ],
         event_log_path: PathBuf::from(".shipper/events.jsonl"),
@@ -401,6 +406,7 @@ fn snapshot_receipt_persisted_json() {
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256: None,
         }],
         event_log_path: PathBuf::from(".shipper/events.jsonl"),
         git_context: None,
@@ -689,6 +695,7 @@ fn snapshot_receipt_all_published() {
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             },
             PackageReceipt {
                 name: "utils".to_string(),
@@ -705,6 +712,7 @@ fn snapshot_receipt_all_published() {
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             },
         ],
         event_log_path: PathBuf::from(".shipper/events.jsonl"),
@@ -751,6 +759,7 @@ fn snapshot_receipt_some_failed() {
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             },
             PackageReceipt {
                 name: "cli".to_string(),
@@ -770,6 +779,7 @@ fn snapshot_receipt_some_failed() {
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             },
         ],
         event_log_path: PathBuf::from(".shipper/events.jsonl"),
@@ -840,6 +850,7 @@ fn snapshot_directory_layout_after_full_save() {
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256: None,
         }],
         event_log_path: PathBuf::from("events.jsonl"),
         git_context: None,
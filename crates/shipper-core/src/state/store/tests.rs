@@ -6,10 +6,10 @@ use std::path::PathBuf;
 use tempfile::tempdir;
 
 use super::*;
-use crate::types::{PackageProgress, PackageReceipt, PackageState, Registry};
+use crate::types::{PackageProgress, PackageReceipt, PackageState, Registry, SkipReason};
 use chrono::Utc;
 
-fn sample_state() -> ExecutionState {
+pub(super) fn sample_state() -> ExecutionState {
     let mut packages = BTreeMap::new();
     packages.insert(
         "demo@0.1.0".to_string(),
@@ -33,7 +33,7 @@ fn sample_state() -> ExecutionState {
     }
 }
 
-fn sample_receipt() -> Receipt {
+pub(super) fn sample_receipt() -> Receipt {
     Receipt {
         receipt_version: "shipper.receipt.v2".to_string(),
         plan_id: "p1".to_string(),
@@ -55,6 +55,7 @@ fn sample_receipt() -> Receipt {
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256: None,
         }],
         event_log_path: PathBuf::from(".shipper/events.jsonl"),
         git_context: None,
@@ -345,7 +346,7 @@ fn file_store_state_with_all_package_states() {
             version: "0.1.0".to_string(),
             attempts: 1,
             state: PackageState::Skipped {
-                reason: "already published".to_string(),
+                reason: SkipReason::AlreadyPublished,
             },
             last_updated_at: now,
         },
@@ -481,6 +482,7 @@ fn file_store_receipt_with_multiple_packages() {
         compromised_at: None,
         compromised_by: None,
         superseded_by: None,
+        artifact_sha256: None,
     });
 
     store.save_receipt(&receipt).expect("save");
@@ -808,6 +810,461 @@ fn validate_schema_version_rejects_negative_looking_version() {
     assert!(result.is_err());
 }
 
+// --- Versioned load tests ---
+
+fn write_receipt_with_version(store: &FileStore, version: &str) {
+    let mut value = serde_json::to_value(sample_receipt()).expect("serialize");
+    value["receipt_version"] = serde_json::Value::String(version.to_string());
+    std::fs::create_dir_all(store.state_dir()).expect("mkdir");
+    std::fs::write(
+        store
+            .state_dir()
+            .join(crate::state::execution_state::RECEIPT_FILE),
+        serde_json::to_string(&value).expect("json"),
+    )
+    .expect("write receipt");
+}
+
+#[test]
+fn load_json_versioned_accepts_supported_version() {
+    let td = tempdir().expect("tempdir");
+    let store = FileStore::new(td.path().to_path_buf());
+    write_receipt_with_version(&store, "shipper.receipt.v2");
+
+    let receipt: Receipt = store
+        .load_json_versioned("receipt.json", "shipper.receipt")
+        .expect("load")
+        .expect("receipt exists");
+    assert_eq!(receipt.plan_id, "p1");
+    assert!(store.load_receipt().expect("load").is_some());
+}
+
+#[test]
+fn load_json_versioned_returns_none_for_missing_file() {
+    let td = tempdir().expect("tempdir");
+    let store = FileStore::new(td.path().to_path_buf());
+    let loaded: Option<Receipt> = store
+        .load_json_versioned("receipt.json", "shipper.receipt")
+        .expect("load");
+    assert!(loaded.is_none());
+}
+
+#[test]
+fn load_receipt_rejects_v0() {
+    let td = tempdir().expect("tempdir");
+    let store = FileStore::new(td.path().to_path_buf());
+    write_receipt_with_version(&store, "shipper.receipt.v0");
+
+    let err = format!("{:#}", store.load_receipt().unwrap_err());
+    assert!(err.contains("too old"), "{err}");
+}
+
+#[test]
+fn load_receipt_rejects_newer_than_current() {
+    let td = tempdir().expect("tempdir");
+    let store = FileStore::new(td.path().to_path_buf());
+    write_receipt_with_version(&store, "shipper.receipt.v99");
+
+    let err = format!("{:#}", store.load_receipt().unwrap_err());
+    assert!(err.contains("newer than this shipper supports"), "{err}");
+}
+
+#[test]
+fn load_state_rejects_newer_than_current() {
+    let td = tempdir().expect("tempdir");
+    let store = FileStore::new(td.path().to_path_buf());
+    let mut state = sample_state();
+    state.state_version = "shipper.state.v2".to_string();
+    store.save_state(&state).expect("save state");
+
+    let err = format!("{:#}", store.load_state().unwrap_err());
+    assert!(err.contains("newer than this shipper supports"), "{err}");
+}
+
+#[test]
+fn load_json_versioned_rejects_wrong_document_type() {
+    let td = tempdir().expect("tempdir");
+    let store = FileStore::new(td.path().to_path_buf());
+    store.save_state(&sample_state()).expect("save state");
+
+    let err = store
+        .load_json_versioned::<Receipt>("state.json", "shipper.receipt")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("expected a shipper.receipt document"), "{err}");
+}
+
+#[test]
+fn load_json_versioned_rejects_missing_version_field() {
+    let td = tempdir().expect("tempdir");
+    let store = FileStore::new(td.path().to_path_buf());
+    std::fs::write(td.path().join("state.json"), "{}").expect("write");
+
+    let err = store
+        .load_json_versioned::<ExecutionState>("state.json", "shipper.state")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("no schema version field"), "{err}");
+}
+
+// --- Integrity metadata tests ---
+
+#[test]
+fn save_json_with_metadata_roundtrips_through_verified_load() {
+    let td = tempdir().expect("tempdir");
+    let store = FileStore::new(td.path().to_path_buf());
+    let receipt = sample_receipt();
+
+    let metadata = store
+        .save_json_with_metadata("receipt.json", &receipt)
+        .expect("save");
+    let checksum = metadata.checksum.clone().expect("checksum");
+    assert_eq!(checksum.len(), 64);
+    assert_eq!(
+        metadata.schema_version.as_deref(),
+        Some("shipper.receipt.v2")
+    );
+    assert!(td.path().join("receipt.json.meta.json").exists());
+
+    let loaded: Receipt = store
+        .load_json_verified("receipt.json")
+        .expect("load")
+        .expect("receipt exists");
+    assert_eq!(loaded.plan_id, receipt.plan_id);
+    assert_eq!(loaded.packages.len(), receipt.packages.len());
+}
+
+#[test]
+fn save_json_with_metadata_preserves_created_at() {
+    let td = tempdir().expect("tempdir");
+    let store = FileStore::new(td.path().to_path_buf());
+
+    let first = store
+        .save_json_with_metadata("state.json", &sample_state())
+        .expect("save");
+    let second = store
+        .save_json_with_metadata("state.json", &sample_state())
+        .expect("save");
+    assert_eq!(second.created_at, first.created_at);
+    assert!(second.updated_at >= first.updated_at);
+}
+
+#[test]
+fn load_json_verified_rejects_tampered_payload() {
+    let td = tempdir().expect("tempdir");
+    let store = FileStore::new(td.path().to_path_buf());
+    let metadata = store
+        .save_json_with_metadata("receipt.json", &sample_receipt())
+        .expect("save");
+
+    let path = td.path().join("receipt.json");
+    let tampered = std::fs::read_to_string(&path)
+        .expect("read")
+        .replace("\"p1\"", "\"p2\"");
+    std::fs::write(&path, tampered).expect("write");
+
+    let err = store
+        .load_json_verified::<Receipt>("receipt.json")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("checksum mismatch"), "{err}");
+    assert!(
+        err.contains(&metadata.checksum.expect("checksum")),
+        "error should include the stored hash: {err}"
+    );
+}
+
+#[test]
+fn clear_removes_metadata_sidecars() {
+    let td = tempdir().expect("tempdir");
+    let store = FileStore::new(td.path().to_path_buf());
+    store
+        .save_json_with_metadata("state.json", &sample_state())
+        .expect("save");
+
+    store.clear().expect("clear");
+    assert!(!td.path().join("state.json").exists());
+    assert!(!td.path().join("state.json.meta.json").exists());
+}
+
+#[test]
+fn load_json_verified_requires_sidecar() {
+    let td = tempdir().expect("tempdir");
+    let store = FileStore::new(td.path().to_path_buf());
+    store.save_receipt(&sample_receipt()).expect("save");
+
+    let err = store
+        .load_json_verified::<Receipt>("receipt.json")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("missing metadata sidecar"), "{err}");
+    assert!(
+        store
+            .load_json_verified::<Receipt>("missing.json")
+            .expect("load")
+            .is_none()
+    );
+}
+
+// --- Migration registry tests ---
+
+fn bump_step(mut value: serde_json::Value) -> anyhow::Result<serde_json::Value> {
+    let steps = value["steps"].as_u64().unwrap_or(0);
+    value["steps"] = serde_json::json!(steps + 1);
+    Ok(value)
+}
+
+fn failing_step(_: serde_json::Value) -> anyhow::Result<serde_json::Value> {
+    anyhow::bail!("boom")
+}
+
+fn step(from: u32, to: u32) -> Migration {
+    Migration {
+        from,
+        to,
+        apply: bump_step,
+    }
+}
+
+#[test]
+fn migrator_chains_steps_in_order() {
+    // Registered out of order on purpose.
+    let migrator = Migrator::new()
+        .with(step(3, 4))
+        .with(step(1, 2))
+        .with(step(2, 3));
+    let froms: Vec<_> = migrator.migrations().iter().map(|m| m.from).collect();
+    assert_eq!(froms, vec![1, 2, 3]);
+
+    let migrated = migrator
+        .migrate(serde_json::json!({}), 1, 4)
+        .expect("migrate");
+    assert_eq!(migrated["steps"], 3);
+
+    let partial = migrator
+        .migrate(serde_json::json!({}), 2, 3)
+        .expect("migrate");
+    assert_eq!(partial["steps"], 1);
+}
+
+#[test]
+fn migrator_same_version_is_noop() {
+    let value = serde_json::json!({"a": 1});
+    let migrated = Migrator::new()
+        .migrate(value.clone(), 2, 2)
+        .expect("migrate");
+    assert_eq!(migrated, value);
+}
+
+#[test]
+fn migrator_errors_on_missing_step() {
+    let migrator = Migrator::new().with(step(1, 2)).with(step(3, 4));
+    let err = migrator
+        .migrate(serde_json::json!({}), 1, 4)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("no migration registered from v2"), "{err}");
+}
+
+#[test]
+fn migrator_errors_on_backwards_or_failing_step() {
+    let err = Migrator::new()
+        .migrate(serde_json::json!({}), 2, 1)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("backwards"), "{err}");
+
+    let migrator = Migrator::new().with(Migration {
+        from: 1,
+        to: 2,
+        apply: failing_step,
+    });
+    let err = format!(
+        "{:#}",
+        migrator.migrate(serde_json::json!({}), 1, 2).unwrap_err()
+    );
+    assert!(err.contains("migration v1 -> v2 failed"), "{err}");
+    assert!(err.contains("boom"), "{err}");
+}
+
+#[test]
+fn receipt_migrator_upgrades_v1_document() {
+    let mut value = serde_json::to_value(sample_receipt()).expect("serialize");
+    let obj = value.as_object_mut().expect("object");
+    obj.insert("receipt_version".to_string(), "shipper.receipt.v1".into());
+    obj.remove("git_context");
+    obj.remove("environment");
+
+    let migrated = Migrator::receipts().migrate(value, 1, 2).expect("migrate");
+    assert_eq!(migrated["receipt_version"], "shipper.receipt.v2");
+    assert!(migrated["git_context"].is_null());
+    assert!(migrated["environment"].is_object());
+    let receipt: Receipt = serde_json::from_value(migrated).expect("deserialize");
+    assert_eq!(receipt.plan_id, "p1");
+}
+
+#[test]
+fn file_store_load_receipt_migrates_v1() {
+    let td = tempdir().expect("tempdir");
+    let store = FileStore::new(td.path().to_path_buf());
+    write_receipt_with_version(&store, "shipper.receipt.v1");
+
+    let receipt = store.load_receipt().expect("load").expect("receipt");
+    assert_eq!(receipt.receipt_version, "shipper.receipt.v2");
+}
+
+// --- Store stats tests ---
+
+/// In-memory store that relies on the default `StateStore::stats`.
+#[derive(Default)]
+struct MemoryStore {
+    state: std::sync::Mutex<Option<ExecutionState>>,
+    receipt: std::sync::Mutex<Option<Receipt>>,
+    events: std::sync::Mutex<Vec<crate::types::PublishEvent>>,
+}
+
+impl StateStore for MemoryStore {
+    fn save_state(&self, state: &ExecutionState) -> anyhow::Result<()> {
+        *self.state.lock().unwrap() = Some(state.clone());
+        Ok(())
+    }
+
+    fn load_state(&self) -> anyhow::Result<Option<ExecutionState>> {
+        Ok(self.state.lock().unwrap().clone())
+    }
+
+    fn save_receipt(&self, receipt: &Receipt) -> anyhow::Result<()> {
+        *self.receipt.lock().unwrap() = Some(receipt.clone());
+        Ok(())
+    }
+
+    fn load_receipt(&self) -> anyhow::Result<Option<Receipt>> {
+        Ok(self.receipt.lock().unwrap().clone())
+    }
+
+    fn save_events(&self, events: &EventLog) -> anyhow::Result<()> {
+        self.events
+            .lock()
+            .unwrap()
+            .extend(events.all_events().iter().cloned());
+        Ok(())
+    }
+
+    fn load_events(&self) -> anyhow::Result<Option<EventLog>> {
+        let events = self.events.lock().unwrap();
+        if events.is_empty() {
+            return Ok(None);
+        }
+        let mut log = EventLog::new();
+        for event in events.iter() {
+            log.record(event.clone());
+        }
+        Ok(Some(log))
+    }
+
+    fn clear(&self) -> anyhow::Result<()> {
+        *self.state.lock().unwrap() = None;
+        *self.receipt.lock().unwrap() = None;
+        self.events.lock().unwrap().clear();
+        Ok(())
+    }
+}
+
+#[test]
+fn default_stats_counts_loadable_documents() {
+    let store = MemoryStore::default();
+    assert_eq!(store.stats().expect("stats"), StoreStats::default());
+
+    let state = sample_state();
+    let receipt = sample_receipt();
+    store.save_state(&state).expect("save state");
+    store.save_receipt(&receipt).expect("save receipt");
+    let mut log = EventLog::new();
+    log.record(crate::types::PublishEvent {
+        timestamp: Utc::now(),
+        event_type: crate::types::EventType::ExecutionStarted,
+        package: "all".to_string(),
+    });
+    store.save_events(&log).expect("save events");
+
+    let expected_size = serde_json::to_vec_pretty(&state).unwrap().len()
+        + serde_json::to_vec_pretty(&receipt).unwrap().len()
+        + serde_json::to_vec(&log.all_events()[0]).unwrap().len()
+        + 1;
+
+    let stats = store.stats().expect("stats");
+    assert_eq!(stats.file_count, 3);
+    assert_eq!(stats.total_size, expected_size as u64);
+    assert!(stats.oldest_modified.is_none());
+    assert!(stats.newest_modified.is_none());
+}
+
+#[test]
+fn file_store_stats_reports_sizes_and_mtimes() {
+    let td = tempdir().expect("tempdir");
+    let store = FileStore::new(td.path().to_path_buf());
+    assert_eq!(store.stats().expect("stats"), StoreStats::default());
+
+    store.save_state(&sample_state()).expect("save state");
+    store.save_receipt(&sample_receipt()).expect("save receipt");
+    std::fs::write(td.path().join("unrelated.txt"), "ignored").expect("write");
+
+    let expected_size = std::fs::metadata(td.path().join("state.json"))
+        .unwrap()
+        .len()
+        + std::fs::metadata(td.path().join("receipt.json"))
+            .unwrap()
+            .len();
+
+    let stats = store.stats().expect("stats");
+    assert_eq!(stats.file_count, 2);
+    assert_eq!(stats.total_size, expected_size);
+    let oldest = stats.oldest_modified.expect("oldest");
+    let newest = stats.newest_modified.expect("newest");
+    assert!(oldest <= newest);
+}
+
+// --- NullStore tests ---
+
+#[test]
+fn null_store_discards_saves_and_loads_nothing() {
+    let store = NullStore::new();
+    let mut log = EventLog::new();
+    log.record(crate::types::PublishEvent {
+        timestamp: Utc::now(),
+        event_type: crate::types::EventType::ExecutionStarted,
+        package: "all".to_string(),
+    });
+
+    store.save_state(&sample_state()).expect("save state");
+    store.save_receipt(&sample_receipt()).expect("save receipt");
+    store.save_events(&log).expect("save events");
+
+    assert!(store.load_state().expect("load state").is_none());
+    assert!(store.load_receipt().expect("load receipt").is_none());
+    assert!(store.load_events().expect("load events").is_none());
+    assert_eq!(store.stats().expect("stats"), StoreStats::default());
+}
+
+#[test]
+fn null_store_clear_is_inert_and_never_exists() {
+    let store = NullStore::new();
+    assert!(!store.exists());
+    store.clear().expect("clear");
+    store.save_state(&sample_state()).expect("save state");
+    assert!(!store.exists());
+    assert_eq!(store.location(), "<null>");
+}
+
+#[test]
+fn null_store_usable_as_dyn_state_store() {
+    let store: Box<dyn StateStore> = Box::new(NullStore);
+    store
+        .save_state(&sample_state())
+        .expect("save via trait object");
+    assert!(store.load_state().expect("load via trait object").is_none());
+}
+
 // --- StateStore trait as trait object ---
 
 #[test]
@@ -838,6 +1295,80 @@ fn file_store_save_creates_parent_directories() {
     assert_eq!(loaded.plan_id, "p1");
 }
 
+// --- Preflight report ---
+
+fn sample_preflight() -> crate::types::PreflightReport {
+    crate::types::PreflightReport {
+        plan_id: "plan-1".to_string(),
+        token_detected: true,
+        finishability: crate::types::Finishability::NotProven,
+        packages: vec![crate::types::PreflightPackage {
+            name: "demo".to_string(),
+            version: "0.1.0".to_string(),
+            already_published: false,
+            is_new_crate: true,
+            auth_type: None,
+            ownership_verified: false,
+            dry_run_passed: true,
+            dry_run_output: None,
+        }],
+        timestamp: Utc::now(),
+        estimated_publish_duration: None,
+        dry_run_output: Some("ok".to_string()),
+    }
+}
+
+#[test]
+fn file_store_preflight_roundtrip_stamps_schema_version() {
+    let td = tempdir().expect("tempdir");
+    let store = FileStore::new(td.path().to_path_buf());
+    assert!(store.load_preflight().expect("load missing").is_none());
+
+    let report = sample_preflight();
+    store.save_preflight(&report).expect("save");
+
+    let raw: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(td.path().join("preflight.json")).expect("read"),
+    )
+    .expect("json");
+    assert_eq!(raw["schema_version"], "shipper.preflight.v1");
+
+    let loaded = store.load_preflight().expect("load").expect("present");
+    assert_eq!(loaded.plan_id, report.plan_id);
+    assert_eq!(loaded.finishability, crate::types::Finishability::NotProven);
+    assert_eq!(loaded.packages.len(), 1);
+    assert!(loaded.packages[0].is_new_crate);
+    assert_eq!(loaded.timestamp, report.timestamp);
+    assert_eq!(loaded.dry_run_output.as_deref(), Some("ok"));
+}
+
+#[test]
+fn file_store_load_preflight_rejects_other_versions_and_documents() {
+    let td = tempdir().expect("tempdir");
+    let store = FileStore::new(td.path().to_path_buf());
+    store.save_preflight(&sample_preflight()).expect("save");
+    let path = td.path().join("preflight.json");
+
+    let mut value: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).expect("read")).expect("json");
+    value["schema_version"] = "shipper.preflight.v2".into();
+    std::fs::write(&path, value.to_string()).expect("write");
+    let err = store.load_preflight().expect_err("newer version");
+    assert!(
+        format!("{err:#}").contains("unsupported schema version"),
+        "{err:#}"
+    );
+
+    value["schema_version"] = "shipper.receipt.v2".into();
+    std::fs::write(&path, value.to_string()).expect("write");
+    let err = store.load_preflight().expect_err("wrong document type");
+    assert!(
+        err.to_string()
+            .contains("expected a shipper.preflight document"),
+        "{err}"
+    );
+}
+
 // --- Property-based tests (proptest) ---
 
 mod proptests {
@@ -890,6 +1421,7 @@ mod proptests {
                                     compromised_at: None,
                     compromised_by: None,
                     superseded_by: None,
+                    artifact_sha256: None,
                 }],
                 event_log_path: PathBuf::from(".shipper/events.jsonl"),
                 git_context: None,
@@ -951,6 +1483,7 @@ mod proptests {
                                     compromised_at: None,
                     compromised_by: None,
                     superseded_by: None,
+                    artifact_sha256: None,
                 }],
                 event_log_path: PathBuf::from(".shipper/events.jsonl"),
                 git_context: None,
@@ -1225,6 +1758,7 @@ fn file_store_receipt_all_published() {
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             },
             PackageReceipt {
                 name: "b".to_string(),
@@ -1241,6 +1775,7 @@ fn file_store_receipt_all_published() {
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             },
         ],
         event_log_path: PathBuf::from(".shipper/events.jsonl"),
@@ -1297,6 +1832,7 @@ fn file_store_receipt_some_failed() {
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             },
             PackageReceipt {
                 name: "b".to_string(),
@@ -1316,6 +1852,7 @@ fn file_store_receipt_some_failed() {
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             },
         ],
         event_log_path: PathBuf::from(".shipper/events.jsonl"),
@@ -1577,6 +2114,7 @@ fn file_store_receipt_empty_strings_roundtrip() {
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256: None,
         }],
         event_log_path: PathBuf::from(""),
         git_context: None,
@@ -7,6 +7,8 @@ use anyhow::Result;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::types::{EventType, ExecutionResult, PublishEvent};
+
 /// Webhook configuration type provided by the `shipper-webhook` microcrate.
 pub type WebhookConfig = shipper_webhook::WebhookConfig;
 
@@ -116,8 +118,51 @@ pub fn maybe_send_event(config: &WebhookConfig, event: WebhookEvent) {
     });
 }
 
+/// Convert to the wire payload, scrubbing tokens from error text.
 pub(crate) fn to_micro_payload(payload: &WebhookPayload) -> shipper_webhook::WebhookPayload {
-    micro_payload::convert(payload)
+    micro_payload::convert(payload).redacted(crate::ops::cargo::redact_sensitive)
+}
+
+/// Map a notable [`PublishEvent`] from the event log to a wire payload.
+///
+/// `PackagePublished`, `PackageFailed` and `ExecutionFinished` become
+/// notifications worded like the `shipper-webhook` payload helpers; every
+/// other event (readiness polls, output tails, attempts, ...) is too noisy to
+/// forward and yields `None`. Failure messages are scrubbed of tokens.
+pub fn payload_from_event(event: &PublishEvent) -> Option<shipper_webhook::WebhookPayload> {
+    let (name, version) = event
+        .package
+        .rsplit_once('@')
+        .unwrap_or((event.package.as_str(), ""));
+    let payload = match &event.event_type {
+        EventType::PackagePublished { .. } => shipper_webhook::WebhookPayload {
+            registry: None,
+            ..shipper_webhook::publish_success_payload(name, version, "")
+        },
+        EventType::PackageFailed { message, .. } => {
+            shipper_webhook::publish_failure_payload(name, version, message)
+        }
+        EventType::ExecutionFinished { result } => {
+            let success = *result == ExecutionResult::Success;
+            let outcome = match result {
+                ExecutionResult::Success => "success",
+                ExecutionResult::PartialFailure => "partial failure",
+                ExecutionResult::CompleteFailure => "complete failure",
+            };
+            shipper_webhook::WebhookPayload {
+                message: format!("Publish run finished: {outcome}"),
+                title: Some(if success {
+                    "Publish Run Succeeded".to_string()
+                } else {
+                    "Publish Run Failed".to_string()
+                }),
+                success,
+                ..Default::default()
+            }
+        }
+        _ => return None,
+    };
+    Some(payload.redacted(crate::ops::cargo::redact_sensitive))
 }
 
 mod micro_payload {
@@ -149,6 +194,7 @@ mod micro_payload {
             version: fields.version,
             registry: fields.registry,
             error: fields.error,
+            summary: None,
             extra,
         }
     }
@@ -304,6 +350,10 @@ mod tests {
             webhook_type: WebhookType::Generic,
             secret: None,
             timeout_secs: 30,
+            retry: None,
+            max_list_items: None,
+            dry_run: false,
+            tls: Default::default(),
         }
     }
 
@@ -478,6 +528,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn to_micro_payload_redacts_tokens_in_failure_message() {
+        let event = WebhookEvent::PublishFailed {
+            plan_id: "plan-1".to_string(),
+            package_name: "pkg-b".to_string(),
+            package_version: "0.1.0".to_string(),
+            error_class: "Permanent".to_string(),
+            message: "403 Forbidden\nCARGO_REGISTRY_TOKEN=cio_s3cr3t".to_string(),
+        };
+        let micro = to_micro_payload(&sample_payload(event));
+        let rendered = serde_json::to_string(&micro).expect("serialize");
+        assert!(!rendered.contains("cio_s3cr3t"), "{rendered}");
+        assert_eq!(
+            micro.error.as_deref(),
+            Some("403 Forbidden\nCARGO_REGISTRY_TOKEN=[REDACTED]")
+        );
+        assert!(micro.message.ends_with("CARGO_REGISTRY_TOKEN=[REDACTED]"));
+    }
+
     #[test]
     fn to_micro_payload_publish_completed_success_when_no_failures() {
         let micro = to_micro_payload(&sample_payload(completed_event(0)));
@@ -583,4 +652,58 @@ mod tests {
         assert!(json.contains("\"timestamp\""));
         assert!(json.contains("\"event\":\"publish_started\""));
     }
+
+    fn log_event(event_type: crate::types::EventType) -> PublishEvent {
+        PublishEvent {
+            timestamp: Utc::now(),
+            event_type,
+            package: "pkg-a@1.2.3".to_string(),
+        }
+    }
+
+    #[test]
+    fn payload_from_event_maps_package_failed_to_failure_payload() {
+        let payload = payload_from_event(&log_event(EventType::PackageFailed {
+            class: crate::types::ErrorClass::Permanent,
+            message: "403 Forbidden\nCARGO_REGISTRY_TOKEN=cio_s3cr3t".to_string(),
+        }))
+        .expect("notable event");
+
+        assert!(!payload.success);
+        assert_eq!(payload.message, "Failed to publish pkg-a@1.2.3");
+        assert_eq!(payload.title.as_deref(), Some("Publish Failed"));
+        assert_eq!(payload.package.as_deref(), Some("pkg-a"));
+        assert_eq!(payload.version.as_deref(), Some("1.2.3"));
+        assert_eq!(
+            payload.error.as_deref(),
+            Some("403 Forbidden\nCARGO_REGISTRY_TOKEN=[REDACTED]")
+        );
+    }
+
+    #[test]
+    fn payload_from_event_maps_published_and_finished() {
+        let published =
+            payload_from_event(&log_event(EventType::PackagePublished { duration_ms: 10 }))
+                .expect("published");
+        assert!(published.success);
+        assert_eq!(published.message, "Successfully published pkg-a@1.2.3");
+        assert_eq!(published.registry, None);
+
+        let finished = payload_from_event(&log_event(EventType::ExecutionFinished {
+            result: ExecutionResult::PartialFailure,
+        }))
+        .expect("finished");
+        assert!(!finished.success);
+        assert_eq!(finished.message, "Publish run finished: partial failure");
+        assert_eq!(finished.title.as_deref(), Some("Publish Run Failed"));
+    }
+
+    #[test]
+    fn payload_from_event_skips_readiness_polls() {
+        let event = log_event(EventType::ReadinessPoll {
+            attempt: 3,
+            visible: false,
+        });
+        assert!(payload_from_event(&event).is_none());
+    }
 }
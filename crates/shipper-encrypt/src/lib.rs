@@ -30,13 +30,14 @@ use std::path::Path;
 
 use aes_gcm::{
     Aes256Gcm, Nonce,
-    aead::{Aead, KeyInit, OsRng, rand_core::RngCore},
+    aead::{Aead, KeyInit, OsRng, Payload, rand_core::RngCore},
 };
 use anyhow::{Context, Result, bail};
 use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
 use pbkdf2::pbkdf2_hmac_array;
 use serde::{Deserialize, Serialize};
 use sha2::Sha256;
+pub use zeroize::Zeroizing;
 
 /// Size of the salt for key derivation (16 bytes)
 const SALT_SIZE: usize = 16;
@@ -47,14 +48,19 @@ const PBKDF2_ITERATIONS: u32 = 100_000;
 /// Size of the derived key (256 bits for AES-256)
 const KEY_SIZE: usize = 32;
 
+/// A passphrase that is wiped from memory when dropped. Derefs to
+/// `String`, so pass `&passphrase` wherever a `&str` is expected.
+pub type Passphrase = Zeroizing<String>;
+
 /// Encryption configuration
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct EncryptionConfig {
     /// Whether encryption is enabled
     #[serde(default)]
     pub enabled: bool,
-    /// Passphrase for encryption/decryption (if enabled)
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Passphrase for encryption/decryption (if enabled). Accepted when
+    /// deserializing but never written back out.
+    #[serde(default, skip_serializing)]
     pub passphrase: Option<String>,
     /// Environment variable name to read passphrase from
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -81,13 +87,13 @@ impl EncryptionConfig {
     }
 
     /// Get the passphrase, either directly or from environment
-    pub fn get_passphrase(&self) -> Result<Option<String>> {
+    pub fn get_passphrase(&self) -> Result<Option<Passphrase>> {
         if let Some(passphrase) = &self.passphrase {
-            return Ok(Some(passphrase.clone()));
+            return Ok(Some(Zeroizing::new(passphrase.clone())));
         }
 
         if let Some(ref env_var) = self.env_var {
-            return Ok(std::env::var(env_var).ok());
+            return Ok(std::env::var(env_var).ok().map(Zeroizing::new));
         }
 
         Ok(None)
@@ -151,30 +157,7 @@ impl fmt::Display for StateEncryption {
 /// // encrypted is base64-encoded and can be safely stored as text
 /// ```
 pub fn encrypt(data: &[u8], passphrase: &str) -> Result<Vec<u8>> {
-    // Generate random salt and nonce
-    let mut salt = [0u8; SALT_SIZE];
-    let mut nonce_bytes = [0u8; NONCE_SIZE];
-    OsRng.fill_bytes(&mut salt);
-    OsRng.fill_bytes(&mut nonce_bytes);
-
-    // Derive key from passphrase using PBKDF2
-    let key = derive_key(passphrase, &salt);
-
-    // Create cipher and encrypt
-    let cipher = Aes256Gcm::new_from_slice(&key).context("failed to create AES-256-GCM cipher")?;
-    let nonce = Nonce::from_slice(&nonce_bytes);
-    let ciphertext = cipher
-        .encrypt(nonce, data)
-        .map_err(|e| anyhow::anyhow!("encryption failed: {:?}", e))?;
-
-    // Format: salt || nonce || ciphertext
-    let mut result = Vec::with_capacity(SALT_SIZE + NONCE_SIZE + ciphertext.len());
-    result.extend_from_slice(&salt);
-    result.extend_from_slice(&nonce_bytes);
-    result.extend_from_slice(&ciphertext);
-
-    // Return base64-encoded result
-    Ok(BASE64.encode(&result).into_bytes())
+    encrypt_with_aad(data, passphrase, &[])
 }
 
 /// Decrypt data using AES-256-GCM with PBKDF2 key derivation
@@ -201,6 +184,62 @@ pub fn encrypt(data: &[u8], passphrase: &str) -> Result<Vec<u8>> {
 /// assert_eq!(data.to_vec(), decrypted);
 /// ```
 pub fn decrypt(encrypted_data: impl AsRef<str>, passphrase: &str) -> Result<Vec<u8>> {
+    decrypt_with_aad(encrypted_data, passphrase, &[])
+}
+
+/// Like [`encrypt`], but binds the ciphertext to `aad` (associated data).
+///
+/// The AAD is authenticated but not stored: decrypting needs the same bytes
+/// passed to [`decrypt_with_aad`], so a blob encrypted for one context
+/// (e.g. a `plan_id`) fails the auth tag when presented in another. An empty
+/// `aad` produces the same blobs as [`encrypt`].
+///
+/// # Example
+///
+/// ```
+/// use shipper_encrypt::{decrypt_with_aad, encrypt_with_aad};
+///
+/// let encrypted = encrypt_with_aad(b"state", "my-passphrase", b"plan-a").expect("encrypt");
+/// let encrypted = String::from_utf8(encrypted).expect("valid UTF-8");
+///
+/// assert!(decrypt_with_aad(&encrypted, "my-passphrase", b"plan-b").is_err());
+/// let decrypted = decrypt_with_aad(&encrypted, "my-passphrase", b"plan-a").expect("decrypt");
+/// assert_eq!(decrypted, b"state");
+/// ```
+pub fn encrypt_with_aad(data: &[u8], passphrase: &str, aad: &[u8]) -> Result<Vec<u8>> {
+    // Generate random salt and nonce
+    let mut salt = [0u8; SALT_SIZE];
+    let mut nonce_bytes = [0u8; NONCE_SIZE];
+    OsRng.fill_bytes(&mut salt);
+    OsRng.fill_bytes(&mut nonce_bytes);
+
+    // Derive key from passphrase using PBKDF2
+    let key = derive_key(passphrase, &salt);
+
+    // Create cipher and encrypt
+    let cipher = Aes256Gcm::new_from_slice(&key).context("failed to create AES-256-GCM cipher")?;
+    let nonce = Nonce::from_slice(&nonce_bytes);
+    let ciphertext = cipher
+        .encrypt(nonce, Payload { msg: data, aad })
+        .map_err(|e| anyhow::anyhow!("encryption failed: {:?}", e))?;
+
+    // Format: salt || nonce || ciphertext
+    let mut result = Vec::with_capacity(SALT_SIZE + NONCE_SIZE + ciphertext.len());
+    result.extend_from_slice(&salt);
+    result.extend_from_slice(&nonce_bytes);
+    result.extend_from_slice(&ciphertext);
+
+    // Return base64-encoded result
+    Ok(BASE64.encode(&result).into_bytes())
+}
+
+/// Like [`decrypt`], but checks the ciphertext against `aad`. Fails if the
+/// blob was encrypted with different associated data.
+pub fn decrypt_with_aad(
+    encrypted_data: impl AsRef<str>,
+    passphrase: &str,
+    aad: &[u8],
+) -> Result<Vec<u8>> {
     let encrypted_str = encrypted_data.as_ref();
     // Decode base64
     let data = BASE64
@@ -223,12 +262,20 @@ pub fn decrypt(encrypted_data: impl AsRef<str>, passphrase: &str) -> Result<Vec<
     // Create cipher and decrypt
     let cipher = Aes256Gcm::new_from_slice(&key).context("failed to create AES-256-GCM cipher")?;
     let nonce = Nonce::from_slice(nonce_bytes);
-    let plaintext = cipher.decrypt(nonce, ciphertext).map_err(|e| {
-        anyhow::anyhow!(
-            "decryption failed - wrong passphrase or corrupted data: {:?}",
-            e
+    let plaintext = cipher
+        .decrypt(
+            nonce,
+            Payload {
+                msg: ciphertext,
+                aad,
+            },
         )
-    })?;
+        .map_err(|e| {
+            anyhow::anyhow!(
+                "decryption failed - wrong passphrase or corrupted data: {:?}",
+                e
+            )
+        })?;
 
     Ok(plaintext)
 }
@@ -301,16 +348,36 @@ pub fn write_encrypted(path: &Path, data: &[u8], passphrase: &str) -> Result<()>
 /// transparently without changing the rest of the codebase.
 pub struct StateEncryption {
     config: EncryptionConfig,
+    aad: Vec<u8>,
 }
 
 impl StateEncryption {
     /// Create a new state encryption handler
     pub fn new(config: EncryptionConfig) -> Result<Self> {
-        Ok(Self { config })
+        Ok(Self {
+            config,
+            aad: Vec::new(),
+        })
+    }
+
+    /// Bind everything this handler encrypts to `context` (e.g. a `plan_id`)
+    /// via AES-GCM associated data; see [`encrypt_with_aad`].
+    ///
+    /// Once bound, an encrypted file written under a different context (or
+    /// without one) is rejected on read instead of falling back to plain
+    /// text.
+    pub fn with_aad(mut self, context: impl Into<Vec<u8>>) -> Self {
+        self.aad = context.into();
+        self
+    }
+
+    /// The associated data encryption is bound to; empty when unbound.
+    pub fn aad(&self) -> &[u8] {
+        &self.aad
     }
 
     /// Get the passphrase, trying environment variable first if configured
-    fn get_passphrase(&self) -> Result<Option<String>> {
+    fn get_passphrase(&self) -> Result<Option<Passphrase>> {
         if !self.config.enabled {
             return Ok(None);
         }
@@ -319,7 +386,7 @@ impl StateEncryption {
         if let Some(ref env_var) = self.config.env_var
             && let Ok(passphrase) = std::env::var(env_var)
         {
-            return Ok(Some(passphrase));
+            return Ok(Some(Zeroizing::new(passphrase)));
         }
 
         // Fall back to direct passphrase
@@ -337,7 +404,7 @@ impl StateEncryption {
             "encryption is enabled but no passphrase available. Set SHIPPER_ENCRYPT_KEY environment variable or provide passphrase in config.",
         )?;
 
-        encrypt(data, &passphrase)
+        encrypt_with_aad(data, &passphrase, &self.aad)
     }
 
     /// Decrypt data if encryption is enabled
@@ -345,7 +412,9 @@ impl StateEncryption {
         // First, try to decrypt assuming it's encrypted
         if let Some(passphrase) = self.get_passphrase()? {
             // Try decryption first
-            if let Ok(decrypted) = decrypt(String::from_utf8_lossy(data), &passphrase) {
+            if let Ok(decrypted) =
+                decrypt_with_aad(String::from_utf8_lossy(data), &passphrase, &self.aad)
+            {
                 return Ok(decrypted);
             }
         }
@@ -371,10 +440,17 @@ impl StateEncryption {
             .with_context(|| format!("failed to read file: {}", path.display()))?;
 
         // Try to decrypt - if it fails, assume it's not encrypted
-        match decrypt(&content, &passphrase) {
+        match decrypt_with_aad(&content, &passphrase, &self.aad) {
             Ok(decrypted) => {
                 String::from_utf8(decrypted).context("decrypted data is not valid UTF-8")
             }
+            Err(err) if !self.aad.is_empty() && is_encrypted(content.trim()) => Err(err)
+                .with_context(|| {
+                    format!(
+                        "encrypted file {} does not match this context",
+                        path.display()
+                    )
+                }),
             Err(_) => {
                 // File might not be encrypted yet - try reading as plain
                 Ok(content)
@@ -394,7 +470,7 @@ impl StateEncryption {
             .get_passphrase()?
             .context("encryption is enabled but no passphrase available")?;
 
-        let encrypted = encrypt(data, &passphrase)?;
+        let encrypted = encrypt_with_aad(data, &passphrase, &self.aad)?;
         let encrypted_str =
             String::from_utf8(encrypted).context("encrypted data is not valid UTF-8")?;
 
@@ -803,7 +879,10 @@ mod tests {
     #[test]
     fn encryption_config_get_passphrase_direct() {
         let cfg = EncryptionConfig::new("hello".to_string());
-        assert_eq!(cfg.get_passphrase().unwrap(), Some("hello".to_string()));
+        assert_eq!(
+            cfg.get_passphrase().unwrap().as_deref().map(String::as_str),
+            Some("hello")
+        );
     }
 
     #[test]
@@ -814,11 +893,35 @@ mod tests {
 
     #[test]
     fn encryption_config_serde_roundtrip() {
-        let cfg = EncryptionConfig::new("test".to_string());
+        let cfg = EncryptionConfig::from_env("SHIPPER_ENCRYPT_KEY".to_string());
         let json = serde_json::to_string(&cfg).expect("serialize");
         let deserialized: EncryptionConfig = serde_json::from_str(&json).expect("deserialize");
         assert_eq!(deserialized.enabled, cfg.enabled);
-        assert_eq!(deserialized.passphrase, cfg.passphrase);
+        assert_eq!(deserialized.env_var, cfg.env_var);
+    }
+
+    #[test]
+    fn encryption_config_serde_never_writes_passphrase() {
+        let cfg = EncryptionConfig::new("top-secret".to_string());
+        let json = serde_json::to_string(&cfg).expect("serialize");
+        assert!(!json.contains("top-secret"), "{json}");
+        assert!(!json.contains("passphrase"), "{json}");
+
+        let read: EncryptionConfig =
+            serde_json::from_str(r#"{"enabled":true,"passphrase":"from-file"}"#)
+                .expect("deserialize");
+        assert_eq!(read.passphrase.as_deref(), Some("from-file"));
+    }
+
+    #[test]
+    fn passphrase_derefs_to_the_secret() {
+        let cfg = EncryptionConfig::new("hunter2".to_string());
+        let passphrase: Passphrase = cfg.get_passphrase().unwrap().expect("present");
+        assert_eq!(passphrase.as_str(), "hunter2");
+
+        let encrypted = encrypt(b"data", &passphrase).expect("encrypt");
+        let encrypted = String::from_utf8(encrypted).expect("utf8");
+        assert_eq!(decrypt(&encrypted, &passphrase).expect("decrypt"), b"data");
     }
 
     #[test]
@@ -891,6 +994,61 @@ mod tests {
         assert_eq!(garbage.to_vec(), result);
     }
 
+    // ── Associated data ─────────────────────────────────────────────────
+
+    #[test]
+    fn aad_roundtrip_and_wrong_aad_fails() {
+        let encrypted = encrypt_with_aad(b"state", "pass", b"plan-a").expect("encrypt");
+        let encrypted = String::from_utf8(encrypted).expect("utf8");
+
+        assert_eq!(
+            decrypt_with_aad(&encrypted, "pass", b"plan-a").expect("decrypt"),
+            b"state"
+        );
+        assert!(decrypt_with_aad(&encrypted, "pass", b"plan-b").is_err());
+        assert!(decrypt(&encrypted, "pass").is_err());
+    }
+
+    #[test]
+    fn empty_aad_matches_legacy_format() {
+        let legacy = String::from_utf8(encrypt(b"state", "pass").expect("encrypt")).unwrap();
+        assert_eq!(
+            decrypt_with_aad(&legacy, "pass", &[]).expect("decrypt"),
+            b"state"
+        );
+
+        let empty = String::from_utf8(encrypt_with_aad(b"state", "pass", &[]).unwrap()).unwrap();
+        assert_eq!(decrypt(&empty, "pass").expect("decrypt"), b"state");
+    }
+
+    #[test]
+    fn state_encryption_rejects_file_bound_to_other_context() {
+        let td = tempdir().expect("tempdir");
+        let path = td.path().join("state.json");
+        let config = EncryptionConfig::new("pass".to_string());
+
+        let plan_a = StateEncryption::new(config.clone())
+            .expect("create")
+            .with_aad("plan-a");
+        assert_eq!(plan_a.aad(), b"plan-a");
+        plan_a
+            .write_file(&path, br#"{"plan_id":"plan-a"}"#)
+            .expect("write");
+        assert_eq!(
+            plan_a.read_file(&path).expect("read"),
+            r#"{"plan_id":"plan-a"}"#
+        );
+
+        let plan_b = StateEncryption::new(config)
+            .expect("create")
+            .with_aad("plan-b");
+        let err = plan_b.read_file(&path).expect_err("wrong context");
+        assert!(
+            err.to_string().contains("does not match this context"),
+            "{err}"
+        );
+    }
+
     // ── encrypt output format ───────────────────────────────────────────
 
     #[test]
@@ -1414,7 +1572,10 @@ mod tests {
         let cfg = EncryptionConfig::from_env("SHIPPER_TEST_PASS_1".to_string());
         temp_env::with_var("SHIPPER_TEST_PASS_1", Some("env-secret"), || {
             let passphrase = cfg.get_passphrase().unwrap();
-            assert_eq!(passphrase, Some("env-secret".to_string()));
+            assert_eq!(
+                passphrase.as_deref().map(String::as_str),
+                Some("env-secret")
+            );
         });
     }
 
@@ -2248,12 +2409,12 @@ mod proptests {
         }
 
         #[test]
-        fn encryption_config_serde_roundtrip_arbitrary(passphrase in "\\PC{1,100}") {
+        fn encryption_config_serde_drops_arbitrary_passphrase(passphrase in "\\PC{1,100}") {
             let cfg = EncryptionConfig::new(passphrase.clone());
             let json = serde_json::to_string(&cfg).expect("serialize");
             let de: EncryptionConfig = serde_json::from_str(&json).expect("deserialize");
             prop_assert_eq!(de.enabled, true);
-            prop_assert_eq!(de.passphrase.as_deref(), Some(passphrase.as_str()));
+            prop_assert_eq!(de.passphrase, None);
         }
 
         #[test]
@@ -0,0 +1,516 @@
+//! Async HTTP registry client.
+//!
+//! [`AsyncRegistryClient`] mirrors [`crate::HttpRegistryClient`] on top of
+//! `reqwest::Client` so it can be awaited from async contexts (webhooks, an
+//! async engine) without `spawn_blocking`. URL layout and status mapping are
+//! shared with the blocking client through the helpers in [`crate::http`], so
+//! both clients return the same results and error messages for the same
+//! registry responses.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+
+use crate::error::{RegistryError, RegistryResult};
+use crate::http::{
+    CrateInfo, CrateResponse, Owner, OwnersResponse, crate_info_status, crate_url,
+    exists_from_status, owners_from_response, owners_status, owners_url, sparse_index_status_error,
+    sparse_index_url, validate_timeouts, version_url,
+};
+use crate::proxy::ProxyConfig;
+use crate::{CRATES_IO_API, DEFAULT_TIMEOUT_SECS, USER_AGENT};
+
+/// Async HTTP registry client that operates on a raw base-URL.
+///
+/// The blocking [`crate::HttpRegistryClient`] remains the client of choice
+/// for synchronous callers.
+#[derive(Debug, Clone)]
+pub struct AsyncRegistryClient {
+    base_url: String,
+    timeout: Duration,
+    connect_timeout: Duration,
+    client: reqwest::Client,
+    proxy: ProxyConfig,
+}
+
+impl AsyncRegistryClient {
+    /// Create a new async registry client for the given base URL
+    ///
+    /// Proxy settings are read from `HTTPS_PROXY` / `HTTP_PROXY` / `NO_PROXY`.
+    /// Fails if those hold an invalid URL rather than silently connecting
+    /// directly.
+    pub fn new(base_url: &str) -> Result<Self> {
+        Self::with_proxy_config(base_url, ProxyConfig::from_env())
+    }
+
+    fn with_proxy_config(base_url: &str, proxy: ProxyConfig) -> Result<Self> {
+        let timeout = Duration::from_secs(DEFAULT_TIMEOUT_SECS);
+        let client = build_client(timeout, timeout, &proxy)?;
+        Ok(Self::with_parts(base_url, proxy, client))
+    }
+
+    fn with_parts(base_url: &str, proxy: ProxyConfig, client: reqwest::Client) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            connect_timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            client,
+            proxy,
+        }
+    }
+
+    /// Create an async client for crates.io
+    ///
+    /// Fails like [`Self::new`] on invalid proxy settings.
+    pub fn crates_io() -> Result<Self> {
+        Self::new(CRATES_IO_API)
+    }
+
+    /// Set the request timeout, used for both connecting and the whole
+    /// request
+    pub fn with_timeout(mut self, timeout: Duration) -> Result<Self> {
+        self.client = build_client(timeout, timeout, &self.proxy)?;
+        self.timeout = timeout;
+        self.connect_timeout = timeout;
+        Ok(self)
+    }
+
+    /// Set separate timeouts for connecting and for the whole request.
+    ///
+    /// See [`crate::HttpRegistryClient::with_timeouts`].
+    pub fn with_timeouts(mut self, connect: Duration, read: Duration) -> Result<Self> {
+        validate_timeouts(connect, read)?;
+        self.client = build_client(read, connect, &self.proxy)?;
+        self.timeout = read;
+        self.connect_timeout = connect;
+        Ok(self)
+    }
+
+    /// The connect and overall request timeouts, in that order
+    pub fn timeouts(&self) -> (Duration, Duration) {
+        (self.connect_timeout, self.timeout)
+    }
+
+    /// Route all requests through `proxy_url` (`http://`, `https://` or
+    /// `socks5://`), replacing any proxy picked up from the environment.
+    pub fn with_proxy(mut self, proxy_url: &str) -> Result<Self> {
+        let proxy = ProxyConfig::all(proxy_url);
+        self.client = build_client(self.timeout, self.connect_timeout, &proxy)?;
+        self.proxy = proxy;
+        Ok(self)
+    }
+
+    /// The proxy settings this client was built with
+    pub fn proxy(&self) -> &ProxyConfig {
+        &self.proxy
+    }
+
+    /// Get the base URL
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Check if a crate exists in the registry
+    pub async fn crate_exists(&self, name: &str) -> RegistryResult<bool> {
+        let url = crate_url(&self.base_url, name);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| RegistryError::network("failed to send request to registry", e))?;
+
+        exists_from_status(response.status(), response.headers())
+    }
+
+    /// Check if a specific version of a crate exists
+    pub async fn version_exists(&self, name: &str, version: &str) -> RegistryResult<bool> {
+        let url = version_url(&self.base_url, name, version);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| RegistryError::network("failed to send request to registry", e))?;
+
+        exists_from_status(response.status(), response.headers())
+    }
+
+    /// Get crate information
+    pub async fn get_crate_info(&self, name: &str) -> RegistryResult<Option<CrateInfo>> {
+        let url = crate_url(&self.base_url, name);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| RegistryError::network("failed to send request to registry", e))?;
+
+        if !crate_info_status(response.status(), response.headers())? {
+            return Ok(None);
+        }
+
+        let crate_response: CrateResponse = response
+            .json()
+            .await
+            .map_err(|e| RegistryError::Parse(format!("failed to parse crate response: {e}")))?;
+
+        Ok(Some(crate_response.into()))
+    }
+
+    /// Get the list of owners for a crate.
+    pub async fn get_owners(&self, name: &str) -> RegistryResult<Vec<Owner>> {
+        let url = owners_url(&self.base_url, name);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| RegistryError::network("failed to query owners", e))?;
+
+        if !owners_status(response.status(), response.headers())? {
+            return Ok(Vec::new());
+        }
+
+        let owners_response: OwnersResponse = response
+            .json()
+            .await
+            .map_err(|e| RegistryError::Parse(format!("failed to parse owners response: {e}")))?;
+        Ok(owners_from_response(owners_response))
+    }
+
+    /// Check if a version exists in sparse-index metadata.
+    pub async fn is_version_visible_in_sparse_index(
+        &self,
+        index_base: &str,
+        name: &str,
+        version: &str,
+    ) -> RegistryResult<bool> {
+        let content = self.fetch_sparse_index_file(index_base, name).await?;
+        Ok(shipper_sparse_index::contains_version(&content, version))
+    }
+
+    /// Fetch sparse-index content for a crate.
+    pub async fn fetch_sparse_index_file(
+        &self,
+        index_base: &str,
+        name: &str,
+    ) -> RegistryResult<String> {
+        let url = sparse_index_url(index_base, name);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| RegistryError::network("index request failed", e))?;
+
+        match response.status() {
+            reqwest::StatusCode::OK => response.text().await.map_err(|e| {
+                RegistryError::Parse(format!("failed to read index response body: {e}"))
+            }),
+            status => Err(sparse_index_status_error(status, response.headers(), &url)),
+        }
+    }
+}
+
+fn build_client(
+    timeout: Duration,
+    connect_timeout: Duration,
+    proxy: &ProxyConfig,
+) -> Result<reqwest::Client> {
+    let builder = reqwest::Client::builder()
+        .timeout(timeout)
+        .connect_timeout(connect_timeout)
+        .user_agent(USER_AGENT);
+    proxy
+        .apply_async(builder)?
+        .build()
+        .context("failed to build HTTP client")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HttpRegistryClient;
+
+    fn mock_server() -> (tiny_http::Server, String) {
+        let server = tiny_http::Server::http("127.0.0.1:0").expect("mock server");
+        let base = format!("http://{}", server.server_addr());
+        (server, base)
+    }
+
+    fn respond(req: tiny_http::Request, status: u16, body: &str) {
+        let resp =
+            tiny_http::Response::from_string(body).with_status_code(tiny_http::StatusCode(status));
+        req.respond(resp).expect("respond");
+    }
+
+    /// Serve one response with `status`, then return the base URL and the
+    /// join handle for the server thread.
+    fn serve_once(status: u16, body: &'static str) -> (String, std::thread::JoinHandle<()>) {
+        let (server, base) = mock_server();
+        let handle = std::thread::spawn(move || {
+            respond(server.recv().expect("req"), status, body);
+        });
+        (base, handle)
+    }
+
+    #[test]
+    fn client_trims_trailing_slashes() {
+        let client = AsyncRegistryClient::new("https://example.com///").expect("client");
+        assert_eq!(client.base_url(), "https://example.com");
+        assert_eq!(
+            AsyncRegistryClient::crates_io().expect("client").base_url(),
+            CRATES_IO_API
+        );
+    }
+
+    #[test]
+    fn with_timeout_sets_timeout() {
+        let client = AsyncRegistryClient::crates_io()
+            .expect("client")
+            .with_timeout(Duration::from_secs(5))
+            .expect("client");
+        assert_eq!(client.timeout, Duration::from_secs(5));
+    }
+
+    #[test]
+    fn with_timeouts_sets_connect_and_read_and_rejects_zero() {
+        let client = AsyncRegistryClient::crates_io()
+            .expect("client")
+            .with_timeouts(Duration::from_secs(3), Duration::from_mins(1))
+            .expect("distinct timeouts build");
+        assert_eq!(
+            client.timeouts(),
+            (Duration::from_secs(3), Duration::from_mins(1))
+        );
+        assert!(
+            AsyncRegistryClient::crates_io()
+                .expect("client")
+                .with_timeouts(Duration::ZERO, Duration::from_secs(1))
+                .is_err()
+        );
+    }
+
+    // ── status handling ──────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn crate_exists_returns_true_on_200() {
+        let (server, base) = mock_server();
+        let handle = std::thread::spawn(move || {
+            let req = server.recv().expect("request");
+            assert_eq!(req.url(), "/api/v1/crates/serde");
+            respond(req, 200, r#"{"crate":{}}"#);
+        });
+        let client = AsyncRegistryClient::new(&base).expect("client");
+        assert!(client.crate_exists("serde").await.expect("ok"));
+        handle.join().expect("join");
+    }
+
+    #[tokio::test]
+    async fn crate_exists_returns_false_on_404() {
+        let (base, handle) = serve_once(404, "");
+        let client = AsyncRegistryClient::new(&base).expect("client");
+        assert!(!client.crate_exists("nonexistent").await.expect("ok"));
+        handle.join().expect("join");
+    }
+
+    #[tokio::test]
+    async fn version_exists_returns_true_on_200() {
+        let (server, base) = mock_server();
+        let handle = std::thread::spawn(move || {
+            let req = server.recv().expect("request");
+            assert_eq!(req.url(), "/api/v1/crates/serde/1.0.0");
+            respond(req, 200, "{}");
+        });
+        let client = AsyncRegistryClient::new(&base).expect("client");
+        assert!(client.version_exists("serde", "1.0.0").await.expect("ok"));
+        handle.join().expect("join");
+    }
+
+    #[tokio::test]
+    async fn version_exists_returns_error_on_429() {
+        let (base, handle) = serve_once(429, "");
+        let client = AsyncRegistryClient::new(&base).expect("client");
+        let err = client.version_exists("serde", "1.0.0").await.unwrap_err();
+        assert!(err.to_string().contains("429"));
+        handle.join().expect("join");
+    }
+
+    #[tokio::test]
+    async fn version_exists_returns_error_on_500() {
+        let (base, handle) = serve_once(500, "");
+        let client = AsyncRegistryClient::new(&base).expect("client");
+        let err = client.version_exists("serde", "1.0.0").await.unwrap_err();
+        assert!(err.to_string().contains("unexpected status code"));
+        handle.join().expect("join");
+    }
+
+    #[tokio::test]
+    async fn get_crate_info_returns_some_on_200() {
+        let body = r#"{"crate":{"name":"serde","newest_version":"1.0.200","created_at":"2017-01-01T00:00:00Z","updated_at":"2024-01-01T00:00:00Z"}}"#;
+        let (base, handle) = serve_once(200, body);
+        let client = AsyncRegistryClient::new(&base).expect("client");
+        let info = client
+            .get_crate_info("serde")
+            .await
+            .expect("ok")
+            .expect("some");
+        assert_eq!(info.name, "serde");
+        assert_eq!(info.newest_version, "1.0.200");
+        handle.join().expect("join");
+    }
+
+    #[tokio::test]
+    async fn get_crate_info_returns_none_on_404() {
+        let (base, handle) = serve_once(404, "");
+        let client = AsyncRegistryClient::new(&base).expect("client");
+        assert!(
+            client
+                .get_crate_info("missing")
+                .await
+                .expect("ok")
+                .is_none()
+        );
+        handle.join().expect("join");
+    }
+
+    #[tokio::test]
+    async fn get_crate_info_returns_error_on_429() {
+        let (base, handle) = serve_once(429, "");
+        let client = AsyncRegistryClient::new(&base).expect("client");
+        assert!(client.get_crate_info("serde").await.is_err());
+        handle.join().expect("join");
+    }
+
+    #[tokio::test]
+    async fn get_owners_returns_owners_on_200() {
+        let body = r#"{"users":[{"id":1,"login":"alice","name":"Alice","avatar":null}]}"#;
+        let (base, handle) = serve_once(200, body);
+        let client = AsyncRegistryClient::new(&base).expect("client");
+        let owners = client.get_owners("demo").await.expect("ok");
+        assert_eq!(owners.len(), 1);
+        assert_eq!(owners[0].login, "alice");
+        handle.join().expect("join");
+    }
+
+    #[tokio::test]
+    async fn get_owners_returns_empty_on_404() {
+        let (base, handle) = serve_once(404, "");
+        let client = AsyncRegistryClient::new(&base).expect("client");
+        assert!(client.get_owners("demo").await.expect("ok").is_empty());
+        handle.join().expect("join");
+    }
+
+    #[tokio::test]
+    async fn get_owners_returns_error_on_500() {
+        let (base, handle) = serve_once(500, "");
+        let client = AsyncRegistryClient::new(&base).expect("client");
+        let err = client.get_owners("demo").await.unwrap_err();
+        assert!(matches!(err, RegistryError::Server(500)));
+        handle.join().expect("join");
+    }
+
+    #[tokio::test]
+    async fn sparse_index_visibility_with_mock() {
+        let (server, base) = mock_server();
+        let handle = std::thread::spawn(move || {
+            let req = server.recv().expect("req");
+            assert_eq!(req.url(), "/de/mo/demo");
+            respond(
+                req,
+                200,
+                "{\"name\":\"demo\",\"vers\":\"0.1.0\",\"deps\":[]}",
+            );
+        });
+        let client = AsyncRegistryClient::new(&base).expect("client");
+        assert!(
+            client
+                .is_version_visible_in_sparse_index(&base, "demo", "0.1.0")
+                .await
+                .expect("ok")
+        );
+        handle.join().expect("join");
+    }
+
+    #[tokio::test]
+    async fn sparse_index_not_found_errors() {
+        let (base, handle) = serve_once(404, "");
+        let client = AsyncRegistryClient::new(&base).expect("client");
+        let err = client
+            .fetch_sparse_index_file(&base, "xy")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("index file not found"));
+        handle.join().expect("join");
+    }
+
+    #[tokio::test]
+    async fn with_proxy_routes_requests_through_proxy() {
+        let (proxy, proxy_url) = mock_server();
+        let handle = std::thread::spawn(move || {
+            let req = proxy.recv().expect("req");
+            assert_eq!(
+                req.url(),
+                "http://registry.invalid/api/v1/crates/demo/1.0.0"
+            );
+            respond(req, 200, "{}");
+        });
+
+        let client = AsyncRegistryClient::new("http://registry.invalid")
+            .expect("client")
+            .with_proxy(&proxy_url)
+            .expect("valid proxy");
+        assert!(client.version_exists("demo", "1.0.0").await.expect("ok"));
+        handle.join().expect("join");
+    }
+
+    #[test]
+    fn new_rejects_invalid_proxy_env_instead_of_connecting_directly() {
+        let proxy = ProxyConfig::from_lookup(|name| {
+            (name == "HTTPS_PROXY").then(|| "ftp://proxy.corp".to_string())
+        });
+        assert!(AsyncRegistryClient::with_proxy_config(CRATES_IO_API, proxy).is_err());
+    }
+
+    #[test]
+    fn with_proxy_rejects_invalid_url() {
+        assert!(
+            AsyncRegistryClient::crates_io()
+                .expect("client")
+                .with_proxy("ftp://proxy.corp")
+                .is_err()
+        );
+    }
+
+    // ── parity with the blocking client ──────────────────────────────
+
+    #[tokio::test]
+    async fn error_messages_match_blocking_client() {
+        let (base, handle) = serve_once(500, "");
+        let async_err = AsyncRegistryClient::new(&base)
+            .expect("client")
+            .crate_exists("bad")
+            .await
+            .unwrap_err();
+        handle.join().expect("join");
+
+        let (base, handle) = serve_once(500, "");
+        let blocking_err = tokio::task::spawn_blocking(move || {
+            HttpRegistryClient::new(&base)
+                .expect("client")
+                .crate_exists("bad")
+                .unwrap_err()
+        })
+        .await
+        .expect("join blocking");
+        handle.join().expect("join");
+
+        assert_eq!(async_err.to_string(), blocking_err.to_string());
+    }
+}
@@ -1,85 +1,253 @@
-use anyhow::{Context, Result, bail};
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use reqwest::StatusCode;
-use reqwest::blocking::Client;
+use reqwest::blocking::{Client, RequestBuilder, Response};
 use serde::Deserialize;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::{Duration, Instant};
 
 use shipper_types::{
     EventType, PublishEvent, ReadinessConfig, ReadinessEvidence, ReadinessMethod, Registry,
 };
 
+use crate::error::{RegistryError, RegistryResult, retry_after};
+use crate::http::{VersionInfo, VersionsResponse, is_yanked_in, versions_url};
+use crate::index_cache::IndexCache;
+use crate::proxy::ProxyConfig;
+use crate::rate_limit::RateLimiter;
+use crate::tls::{TlsConfig, TlsVersion};
+
 #[derive(Debug, Clone)]
 pub struct RegistryClient {
     registry: Registry,
     http: Client,
     cache_dir: Option<std::path::PathBuf>,
+    index_cache: IndexCache,
+    rate_limiter: Option<RateLimiter>,
+    proxy: ProxyConfig,
+    tls: TlsConfig,
 }
 
 impl RegistryClient {
+    /// Create a client for `registry`.
+    ///
+    /// Proxy settings are read from `HTTPS_PROXY` / `HTTP_PROXY` / `NO_PROXY`;
+    /// a malformed proxy URL fails construction.
     pub fn new(registry: Registry) -> Result<Self> {
-        let http = Client::builder()
-            .user_agent(format!("shipper/{}", env!("CARGO_PKG_VERSION")))
-            .build()
-            .context("failed to build HTTP client")?;
+        let proxy = ProxyConfig::from_env();
+        let tls = TlsConfig::default();
+        let http = build_client(&proxy, &tls)?;
 
         Ok(Self {
             registry,
             http,
             cache_dir: None,
+            index_cache: IndexCache::new(),
+            rate_limiter: None,
+            proxy,
+            tls,
         })
     }
 
+    /// Route all requests through `proxy_url` (`http://`, `https://` or
+    /// `socks5://`), replacing any proxy picked up from the environment.
+    pub fn with_proxy(mut self, proxy_url: &str) -> Result<Self> {
+        self.proxy = ProxyConfig::all(proxy_url);
+        self.http = build_client(&self.proxy, &self.tls)?;
+        Ok(self)
+    }
+
+    /// Refuse to negotiate TLS below `version` (e.g. [`TlsVersion::TLS_1_2`]).
+    pub fn with_min_tls(mut self, version: TlsVersion) -> Result<Self> {
+        self.tls.min_version = Some(version);
+        self.http = build_client(&self.proxy, &self.tls)?;
+        Ok(self)
+    }
+
+    /// Trust only `der_bytes`, a DER-encoded certificate, instead of the
+    /// built-in web PKI roots.
+    ///
+    /// Pin the registry's own certificate or the CA that issued it. Fails
+    /// if the bytes are not a valid certificate.
+    pub fn with_pinned_cert(mut self, der_bytes: &[u8]) -> Result<Self> {
+        self.tls.pinned_cert_der = Some(der_bytes.to_vec());
+        self.http = build_client(&self.proxy, &self.tls)?;
+        Ok(self)
+    }
+
     /// Set the cache directory for sparse index fragments
     pub fn with_cache_dir(mut self, cache_dir: std::path::PathBuf) -> Self {
         self.cache_dir = Some(cache_dir);
         self
     }
 
+    /// Limit outbound requests to `requests_per_minute`.
+    ///
+    /// Every request method waits on the limiter before sending. Clones of
+    /// this client share the same bucket.
+    pub fn with_rate_limit(mut self, requests_per_minute: u32) -> Self {
+        self.rate_limiter = Some(RateLimiter::new(requests_per_minute));
+        self
+    }
+
+    /// The in-memory sparse-index cache shared by this client and its clones
+    pub fn index_cache(&self) -> &IndexCache {
+        &self.index_cache
+    }
+
+    fn send(&self, request: RequestBuilder) -> reqwest::Result<Response> {
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.acquire();
+        }
+        request.send()
+    }
+
     pub fn registry(&self) -> &Registry {
         &self.registry
     }
 
-    pub fn version_exists(&self, crate_name: &str, version: &str) -> Result<bool> {
+    /// Check that the registry is reachable before starting a run.
+    ///
+    /// Sends `GET /api/v1/summary`, a cheap endpoint crates.io always
+    /// serves, with a [`PING_TIMEOUT_SECS`](crate::PING_TIMEOUT_SECS)
+    /// timeout independent of other requests. 429 and 5xx mean the
+    /// registry is up but unhealthy and are returned as errors; any other
+    /// response (including a 404 from registries without the endpoint)
+    /// counts as reachable. Use [`RegistryError::classify`] to tell a
+    /// transient outage from a permanent one.
+    pub fn ping(&self) -> RegistryResult<()> {
+        let api_base = self.registry.api_base.trim_end_matches('/');
+        let url = format!("{api_base}/api/v1/summary");
+
+        let resp = self
+            .send(
+                self.http
+                    .get(url)
+                    .timeout(Duration::from_secs(crate::PING_TIMEOUT_SECS)),
+            )
+            .map_err(|e| RegistryError::network(&format!("registry unreachable: {api_base}"), e))?;
+
+        let status = resp.status();
+        if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+            return Err(RegistryError::from_status(
+                status,
+                retry_after(resp.headers()),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn version_exists(&self, crate_name: &str, version: &str) -> RegistryResult<bool> {
+        self.version_exists_with_token(crate_name, version, None)
+    }
+
+    /// Check if a specific version exists, sending `token` as the
+    /// `Authorization` header when present.
+    ///
+    /// Private registries often reject anonymous API reads; crates.io does not
+    /// need a token, so pass `None` there.
+    pub fn version_exists_with_token(
+        &self,
+        crate_name: &str,
+        version: &str,
+        token: Option<&str>,
+    ) -> RegistryResult<bool> {
         let url = format!(
             "{}/api/v1/crates/{}/{}",
             self.registry.api_base.trim_end_matches('/'),
             crate_name,
             version
         );
+        self.exists_with_token(url, token)
+    }
 
-        let resp = self
-            .http
-            .get(url)
-            .send()
-            .context("registry request failed")?;
-        match resp.status() {
-            StatusCode::OK => Ok(true),
-            StatusCode::NOT_FOUND => Ok(false),
-            s => bail!("unexpected status while checking version existence: {s}"),
-        }
+    /// Check several `(crate, version)` pairs via the API in one call.
+    ///
+    /// Runs [`version_exists`](Self::version_exists) for each item on up to
+    /// [`BATCH_CHECK_CONCURRENCY`](crate::BATCH_CHECK_CONCURRENCY) threads,
+    /// all drawing from this client's rate limiter. Results come back in
+    /// input order; an item whose check fails (network error, unexpected
+    /// status) is reported as not visible, as in readiness polling.
+    pub fn check_versions_visible(
+        &self,
+        items: &[(String, String)],
+    ) -> Vec<(String, String, bool)> {
+        let next = AtomicUsize::new(0);
+        let workers = items.len().min(crate::BATCH_CHECK_CONCURRENCY);
+        let mut visible = vec![false; items.len()];
+
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut checked = Vec::new();
+                        loop {
+                            let i = next.fetch_add(1, Ordering::Relaxed);
+                            let Some((name, version)) = items.get(i) else {
+                                break;
+                            };
+                            checked.push((i, self.version_exists(name, version).unwrap_or(false)));
+                        }
+                        checked
+                    })
+                })
+                .collect();
+            for handle in handles {
+                let checked = match handle.join() {
+                    Ok(checked) => checked,
+                    Err(payload) => std::panic::resume_unwind(payload),
+                };
+                for (i, found) in checked {
+                    if let Some(slot) = visible.get_mut(i) {
+                        *slot = found;
+                    }
+                }
+            }
+        });
+
+        items
+            .iter()
+            .zip(visible)
+            .map(|((name, version), found)| (name.clone(), version.clone(), found))
+            .collect()
     }
 
-    pub fn crate_exists(&self, crate_name: &str) -> Result<bool> {
+    pub fn crate_exists(&self, crate_name: &str) -> RegistryResult<bool> {
+        self.crate_exists_with_token(crate_name, None)
+    }
+
+    /// Check if a crate exists, sending `token` as the `Authorization` header
+    /// when present.
+    pub fn crate_exists_with_token(
+        &self,
+        crate_name: &str,
+        token: Option<&str>,
+    ) -> RegistryResult<bool> {
         let url = format!(
             "{}/api/v1/crates/{}",
             self.registry.api_base.trim_end_matches('/'),
             crate_name
         );
+        self.exists_with_token(url, token)
+    }
+
+    fn exists_with_token(&self, url: String, token: Option<&str>) -> RegistryResult<bool> {
+        let mut request = self.http.get(url);
+        if let Some(token) = token {
+            request = request.header("Authorization", token);
+        }
 
         let resp = self
-            .http
-            .get(url)
-            .send()
-            .context("registry request failed")?;
+            .send(request)
+            .map_err(|e| RegistryError::network("registry request failed", e))?;
         match resp.status() {
             StatusCode::OK => Ok(true),
             StatusCode::NOT_FOUND => Ok(false),
-            s => bail!("unexpected status while checking crate existence: {s}"),
+            s => Err(RegistryError::from_status(s, retry_after(resp.headers()))),
         }
     }
 
-    pub fn list_owners(&self, crate_name: &str, token: &str) -> Result<OwnersResponse> {
+    pub fn list_owners(&self, crate_name: &str, token: &str) -> RegistryResult<OwnersResponse> {
         let url = format!(
             "{}/api/v1/crates/{}/owners",
             self.registry.api_base.trim_end_matches('/'),
@@ -87,29 +255,74 @@ impl RegistryClient {
         );
 
         let resp = self
-            .http
-            .get(url)
-            .header("Authorization", token)
-            .send()
-            .context("registry owners request failed")?;
+            .send(self.http.get(url).header("Authorization", token))
+            .map_err(|e| RegistryError::network("registry owners request failed", e))?;
 
         match resp.status() {
-            StatusCode::OK => {
-                let parsed: OwnersResponse = resp.json().context("failed to parse owners JSON")?;
-                Ok(parsed)
-            }
-            StatusCode::NOT_FOUND => bail!("crate not found when querying owners: {crate_name}"),
-            StatusCode::FORBIDDEN => bail!(
+            StatusCode::OK => resp
+                .json::<OwnersResponse>()
+                .map_err(|e| RegistryError::Parse(format!("failed to parse owners JSON: {e}"))),
+            StatusCode::NOT_FOUND => Err(RegistryError::NotFound(format!(
+                "crate not found when querying owners: {crate_name}"
+            ))),
+            StatusCode::UNAUTHORIZED => Err(RegistryError::Unauthorized(
+                "unauthorized when querying owners; token may be invalid".to_string(),
+            )),
+            StatusCode::FORBIDDEN => Err(RegistryError::Forbidden(
                 "forbidden when querying owners; token may be invalid or missing required scope"
-            ),
-            s => bail!("unexpected status while querying owners: {s}"),
+                    .to_string(),
+            )),
+            s => Err(RegistryError::from_status(s, retry_after(resp.headers()))),
+        }
+    }
+
+    /// Compare a crate's current owners against `expected`.
+    ///
+    /// Team logins (`github:org:team`) match case-insensitively; user logins
+    /// match exactly. See [`OwnershipReport`].
+    pub fn verify_owners(
+        &self,
+        crate_name: &str,
+        expected: &[String],
+        token: &str,
+    ) -> RegistryResult<OwnershipReport> {
+        let owners = self.list_owners(crate_name, token)?;
+        Ok(OwnershipReport::compare(&owners.users, expected))
+    }
+
+    /// List every published version of a crate, including yanked ones.
+    ///
+    /// Returns an empty list when the crate does not exist.
+    pub fn list_versions(&self, crate_name: &str) -> RegistryResult<Vec<VersionInfo>> {
+        let url = versions_url(self.registry.api_base.trim_end_matches('/'), crate_name);
+
+        let resp = self
+            .send(self.http.get(url))
+            .map_err(|e| RegistryError::network("registry versions request failed", e))?;
+
+        match resp.status() {
+            StatusCode::OK => resp
+                .json::<VersionsResponse>()
+                .map(|parsed| parsed.versions)
+                .map_err(|e| RegistryError::Parse(format!("failed to parse versions JSON: {e}"))),
+            StatusCode::NOT_FOUND => Ok(Vec::new()),
+            s => Err(RegistryError::from_status(s, retry_after(resp.headers()))),
         }
     }
 
+    /// Check whether a published version has been yanked.
+    ///
+    /// A yanked version still "exists" for [`Self::version_exists`], but
+    /// cannot be depended on. Versions that were never published are
+    /// reported as not yanked.
+    pub fn is_version_yanked(&self, crate_name: &str, version: &str) -> RegistryResult<bool> {
+        Ok(is_yanked_in(&self.list_versions(crate_name)?, version))
+    }
+
     /// Check if a crate is new (doesn't exist in the registry).
     ///
     /// Returns true if the crate doesn't exist, false if it does.
-    pub fn check_new_crate(&self, crate_name: &str) -> Result<bool> {
+    pub fn check_new_crate(&self, crate_name: &str) -> RegistryResult<bool> {
         let exists = self.crate_exists(crate_name)?;
         Ok(!exists)
     }
@@ -118,12 +331,12 @@ impl RegistryClient {
     ///
     /// Returns true if the version is found in the index, false otherwise.
     /// Parse errors and network errors are treated as "not visible" rather than failures.
-    pub fn check_index_visibility(&self, crate_name: &str, version: &str) -> Result<bool> {
+    pub fn check_index_visibility(&self, crate_name: &str, version: &str) -> RegistryResult<bool> {
         // Calculate the index path for the crate using the 2+2+N scheme
         let index_path = self.calculate_index_path(crate_name);
 
         // Fetch the index file content
-        let content = match self.fetch_index_file(&index_path) {
+        let content = match self.fetch_index_file(crate_name, &index_path) {
             Ok(content) => content,
             Err(_e) => {
                 // Network errors or missing files are treated as "not visible"
@@ -152,26 +365,40 @@ impl RegistryClient {
     ///
     /// All names are lowercased per Cargo convention.
     fn calculate_index_path(&self, crate_name: &str) -> String {
-        shipper_sparse_index::sparse_index_path(crate_name)
+        shipper_sparse_index::sparse_index_path_unchecked(crate_name)
     }
 
     /// Fetch the index file content from the registry.
-    fn fetch_index_file(&self, index_path: &str) -> Result<String> {
-        let index_base = self.registry.get_index_base();
-        let url = format!("{}/{}", index_base.trim_end_matches('/'), index_path);
+    ///
+    /// Sends `If-None-Match` with the ETag from the in-memory [`IndexCache`]
+    /// (or, failing that, from the on-disk cache) and reuses the cached body
+    /// when the registry answers `304 Not Modified`, so readiness polling
+    /// does not re-download an unchanged index file.
+    fn fetch_index_file(&self, crate_name: &str, index_path: &str) -> RegistryResult<String> {
+        let url = self
+            .registry
+            .resolve_sparse_index_url(crate_name)
+            .map_err(|e| RegistryError::Parse(format!("{e:#}")))?;
 
         let cache_file = self.cache_dir.as_ref().map(|d| d.join(index_path));
         let etag_file = cache_file.as_ref().map(|f| f.with_extension("etag"));
 
-        let mut request = self.http.get(&url);
+        let cached = self.index_cache.get(crate_name);
+        let etag = cached.as_ref().map(|c| c.etag.clone()).or_else(|| {
+            etag_file
+                .as_ref()
+                .and_then(|path| std::fs::read_to_string(path).ok())
+                .map(|etag| etag.trim().to_string())
+        });
 
-        if let Some(ref path) = etag_file
-            && let Ok(etag) = std::fs::read_to_string(path)
-        {
-            request = request.header(reqwest::header::IF_NONE_MATCH, etag.trim());
+        let mut request = self.http.get(&url);
+        if let Some(ref etag) = etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
         }
 
-        let resp = request.send().context("index request failed")?;
+        let resp = self
+            .send(request)
+            .map_err(|e| RegistryError::network("index request failed", e))?;
 
         match resp.status() {
             StatusCode::OK => {
@@ -180,31 +407,43 @@ impl RegistryClient {
                     .get(reqwest::header::ETAG)
                     .and_then(|h| h.to_str().ok())
                     .map(|s| s.to_string());
-                let content = resp.text().context("failed to read index response body")?;
+                let content = resp.text().map_err(|e| {
+                    RegistryError::Parse(format!("failed to read index response body: {e}"))
+                })?;
 
                 if let Some(ref path) = cache_file {
                     if let Some(parent) = path.parent() {
                         let _ = std::fs::create_dir_all(parent);
                     }
                     let _ = std::fs::write(path, &content);
-                    if let (Some(ref etag_val), Some(etag_path)) = (etag, etag_file) {
+                    if let (Some(etag_val), Some(etag_path)) = (&etag, etag_file) {
                         let _ = std::fs::write(etag_path, etag_val);
                     }
                 }
+                match etag {
+                    Some(etag) => self.index_cache.insert(crate_name, etag, content.clone()),
+                    None => self.index_cache.remove(crate_name),
+                }
                 Ok(content)
             }
             StatusCode::NOT_MODIFIED => {
-                if let Some(ref path) = cache_file {
-                    std::fs::read_to_string(path).context("failed to read cached index file")
+                if let Some(cached) = cached {
+                    Ok(cached.body)
+                } else if let Some(ref path) = cache_file {
+                    std::fs::read_to_string(path).map_err(|e| {
+                        RegistryError::Parse(format!("failed to read cached index file: {e}"))
+                    })
                 } else {
-                    bail!("received 304 Not Modified but no cache file available")
+                    Err(RegistryError::Status(304))
                 }
             }
             StatusCode::NOT_FOUND => {
                 // The crate doesn't exist in the index yet
-                bail!("index file not found: {}", url)
+                Err(RegistryError::NotFound(format!(
+                    "index file not found: {url}"
+                )))
             }
-            s => bail!("unexpected status while fetching index: {}", s),
+            s => Err(RegistryError::from_status(s, retry_after(resp.headers()))),
         }
     }
 
@@ -218,25 +457,17 @@ impl RegistryClient {
     /// Returns true if ownership is verified, false if verification fails or endpoint is unavailable.
     /// This function implements graceful degradation - if the ownership check fails due to API
     /// limitations, it returns false rather than an error.
-    pub fn verify_ownership(&self, crate_name: &str, token: &str) -> Result<bool> {
+    pub fn verify_ownership(&self, crate_name: &str, token: &str) -> RegistryResult<bool> {
         match self.list_owners(crate_name, token) {
             Ok(_) => Ok(true),
-            Err(e) => {
-                // Graceful degradation: if the endpoint is unavailable or returns forbidden,
-                // return false rather than failing the entire preflight
-                let msg = format!("{e:#}");
-                if msg.contains("forbidden")
-                    || msg.contains("403")
-                    || msg.contains("unauthorized")
-                    || msg.contains("401")
-                    || msg.contains("not found")
-                    || msg.contains("404")
-                {
-                    Ok(false)
-                } else {
-                    Err(e)
-                }
-            }
+            // Graceful degradation: if the endpoint is unavailable or returns forbidden,
+            // return false rather than failing the entire preflight
+            Err(
+                RegistryError::Forbidden(_)
+                | RegistryError::Unauthorized(_)
+                | RegistryError::NotFound(_),
+            ) => Ok(false),
+            Err(e) => Err(e),
         }
     }
 
@@ -303,13 +534,7 @@ impl RegistryClient {
             let jittered_delay = if attempt == 1 {
                 Duration::ZERO
             } else {
-                let base_delay = config.poll_interval;
-                let exponential_delay = base_delay
-                    .saturating_mul(2_u32.saturating_pow(attempt.saturating_sub(2).min(16)));
-                let capped_delay = exponential_delay.min(config.max_delay);
-                let jitter_range = config.jitter_factor;
-                let jitter = 1.0 + (rand::random::<f64>() * 2.0 * jitter_range - jitter_range);
-                Duration::from_millis((capped_delay.as_millis() as f64 * jitter).round() as u64)
+                config.apply_jitter(config.backoff_delay(attempt - 1))
             };
 
             // Check visibility based on method
@@ -354,15 +579,7 @@ impl RegistryClient {
             }
 
             // Calculate next delay with exponential backoff and jitter
-            let base_delay = config.poll_interval;
-            let exponential_delay =
-                base_delay.saturating_mul(2_u32.saturating_pow(attempt.saturating_sub(1).min(16)));
-            let capped_delay = exponential_delay.min(config.max_delay);
-
-            let jitter_range = config.jitter_factor;
-            let jitter = 1.0 + (rand::random::<f64>() * 2.0 * jitter_range - jitter_range);
-            let next_delay =
-                Duration::from_millis((capped_delay.as_millis() as f64 * jitter).round() as u64);
+            let next_delay = config.apply_jitter(config.backoff_delay(attempt));
 
             emit_event(readiness_poll_scheduled_event(
                 &package,
@@ -397,6 +614,17 @@ impl RegistryClient {
     }
 }
 
+fn build_client(proxy: &ProxyConfig, tls: &TlsConfig) -> Result<Client> {
+    let builder = Client::builder().user_agent(format!("shipper/{}", env!("CARGO_PKG_VERSION")));
+    let builder = tls.apply_blocking(proxy.apply_blocking(builder)?)?;
+    let client = builder.build();
+    if tls.pinned_cert_der.is_some() {
+        client.context("failed to build HTTP client with pinned certificate")
+    } else {
+        client.context("failed to build HTTP client")
+    }
+}
+
 fn readiness_poll_event(package: &str, attempt: u32, visible: bool) -> PublishEvent {
     PublishEvent {
         timestamp: Utc::now(),
@@ -433,6 +661,67 @@ pub struct Owner {
     pub name: Option<String>,
 }
 
+/// Expected vs actual owners of a crate, from [`RegistryClient::verify_owners`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OwnershipReport {
+    /// Expected owners that currently own the crate.
+    pub present: Vec<String>,
+    /// Expected owners that do not own the crate.
+    pub missing: Vec<String>,
+    /// Owners of the crate that were not expected.
+    pub unexpected: Vec<String>,
+}
+
+impl OwnershipReport {
+    /// Compare `owners` against `expected`, preserving the order of each.
+    pub fn compare(owners: &[Owner], expected: &[String]) -> Self {
+        let mut report = Self::default();
+        for login in expected {
+            if owners.iter().any(|o| same_owner(&o.login, login)) {
+                report.present.push(login.clone());
+            } else {
+                report.missing.push(login.clone());
+            }
+        }
+        report.unexpected = owners
+            .iter()
+            .filter(|o| !expected.iter().any(|login| same_owner(&o.login, login)))
+            .map(|o| o.login.clone())
+            .collect();
+        report
+    }
+
+    /// Every expected owner is present and no one else owns the crate.
+    pub fn is_exact_match(&self) -> bool {
+        self.missing.is_empty() && self.unexpected.is_empty()
+    }
+}
+
+impl std::fmt::Display for OwnershipReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_exact_match() {
+            return write!(f, "owners match: {}", self.present.join(", "));
+        }
+        let mut parts = Vec::new();
+        if !self.missing.is_empty() {
+            parts.push(format!("missing owners: {}", self.missing.join(", ")));
+        }
+        if !self.unexpected.is_empty() {
+            parts.push(format!("unexpected owners: {}", self.unexpected.join(", ")));
+        }
+        f.write_str(&parts.join("; "))
+    }
+}
+
+/// Team logins (`github:org:team`) are case-insensitive on crates.io.
+fn same_owner(actual: &str, expected: &str) -> bool {
+    if actual.starts_with("github:") || expected.starts_with("github:") {
+        actual.eq_ignore_ascii_case(expected)
+    } else {
+        actual == expected
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::thread;
@@ -504,6 +793,47 @@ mod tests {
         (addr, handle)
     }
 
+    #[test]
+    fn ping_ok_when_registry_up() {
+        let (api_base, handle) = with_server(|req| {
+            assert_eq!(req.url(), "/api/v1/summary");
+            req.respond(Response::from_string("{}").with_status_code(StatusCode(200)))
+                .expect("respond");
+        });
+
+        let cli = RegistryClient::new(test_registry(api_base)).expect("client");
+        cli.ping().expect("ping");
+        handle.join().expect("join");
+    }
+
+    #[test]
+    fn ping_reports_server_error_as_retryable() {
+        let (api_base, handle) = with_server(|req| {
+            req.respond(Response::empty(StatusCode(503)))
+                .expect("respond");
+        });
+
+        let cli = RegistryClient::new(test_registry(api_base)).expect("client");
+        let err = cli.ping().expect_err("503");
+        assert!(matches!(err, RegistryError::Server(503)));
+        assert_eq!(err.classify(), shipper_retry::ErrorClass::Retryable);
+        handle.join().expect("join");
+    }
+
+    #[test]
+    fn ping_reports_connection_refused_as_unreachable() {
+        let api_base = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+            format!("http://{}", listener.local_addr().expect("addr"))
+        };
+
+        let cli = RegistryClient::new(test_registry(api_base.clone())).expect("client");
+        let err = cli.ping().expect_err("refused");
+        assert!(matches!(err, RegistryError::Network { timeout: false, .. }));
+        assert_eq!(err.to_string(), format!("registry unreachable: {api_base}"));
+        assert_eq!(err.classify(), shipper_retry::ErrorClass::Retryable);
+    }
+
     #[test]
     fn version_exists_true_for_200() {
         let (api_base, handle) = with_server(|req| {
@@ -543,7 +873,8 @@ mod tests {
         let err = cli
             .version_exists("demo", "1.2.3")
             .expect_err("unexpected status must fail");
-        assert!(format!("{err:#}").contains("unexpected status while checking version existence"));
+        assert!(matches!(err, RegistryError::Server(500)));
+        assert_eq!(err.classify(), shipper_retry::ErrorClass::Retryable);
         handle.join().expect("join");
     }
 
@@ -585,7 +916,72 @@ mod tests {
         let err = cli
             .crate_exists("demo")
             .expect_err("unexpected status must fail");
-        assert!(format!("{err:#}").contains("unexpected status while checking crate existence"));
+        assert!(matches!(err, RegistryError::Server(500)));
+        assert_eq!(err.classify(), shipper_retry::ErrorClass::Retryable);
+        handle.join().expect("join");
+    }
+
+    fn respond_if_authorized(req: tiny_http::Request, token: &str) {
+        let authorized = req
+            .headers()
+            .iter()
+            .any(|h| h.field.equiv("Authorization") && h.value.as_str() == token);
+        let status = if authorized { 200 } else { 401 };
+        req.respond(Response::empty(StatusCode(status)))
+            .expect("respond");
+    }
+
+    #[test]
+    fn version_exists_with_token_sends_authorization() {
+        let (api_base, handle) = with_server(|req| {
+            assert_eq!(req.url(), "/api/v1/crates/private/1.0.0");
+            respond_if_authorized(req, "secret");
+        });
+
+        let cli = RegistryClient::new(test_registry(api_base)).expect("client");
+        let exists = cli
+            .version_exists_with_token("private", "1.0.0", Some("secret"))
+            .expect("exists");
+        assert!(exists);
+        handle.join().expect("join");
+    }
+
+    #[test]
+    fn version_exists_without_token_is_unauthorized() {
+        let (api_base, handle) = with_server(|req| respond_if_authorized(req, "secret"));
+
+        let cli = RegistryClient::new(test_registry(api_base)).expect("client");
+        let err = cli
+            .version_exists("private", "1.0.0")
+            .expect_err("anonymous read must fail");
+        assert!(matches!(err, RegistryError::Status(401)));
+        handle.join().expect("join");
+    }
+
+    #[test]
+    fn crate_exists_with_token_sends_authorization() {
+        let (api_base, handle) = with_server(|req| {
+            assert_eq!(req.url(), "/api/v1/crates/private");
+            respond_if_authorized(req, "secret");
+        });
+
+        let cli = RegistryClient::new(test_registry(api_base)).expect("client");
+        let exists = cli
+            .crate_exists_with_token("private", Some("secret"))
+            .expect("exists");
+        assert!(exists);
+        handle.join().expect("join");
+    }
+
+    #[test]
+    fn crate_exists_with_none_token_is_unauthorized() {
+        let (api_base, handle) = with_server(|req| respond_if_authorized(req, "secret"));
+
+        let cli = RegistryClient::new(test_registry(api_base)).expect("client");
+        let err = cli
+            .crate_exists_with_token("private", None)
+            .expect_err("anonymous read must fail");
+        assert!(matches!(err, RegistryError::Status(401)));
         handle.join().expect("join");
     }
 
@@ -627,6 +1023,7 @@ mod tests {
         let err_404 = cli_404
             .list_owners("missing", "token")
             .expect_err("404 must fail");
+        assert!(matches!(err_404, RegistryError::NotFound(_)));
         assert!(format!("{err_404:#}").contains("crate not found when querying owners"));
         h1.join().expect("join");
 
@@ -638,7 +1035,8 @@ mod tests {
         let err_403 = cli_403
             .list_owners("demo", "token")
             .expect_err("403 must fail");
-        assert!(format!("{err_403:#}").contains("forbidden when querying owners"));
+        assert!(matches!(err_403, RegistryError::Forbidden(_)));
+        assert_eq!(err_403.classify(), shipper_retry::ErrorClass::Permanent);
         h2.join().expect("join");
 
         let (api_base_500, h3) = with_server(|req| {
@@ -649,10 +1047,202 @@ mod tests {
         let err_500 = cli_500
             .list_owners("demo", "token")
             .expect_err("500 must fail");
-        assert!(format!("{err_500:#}").contains("unexpected status while querying owners"));
+        assert!(matches!(err_500, RegistryError::Server(500)));
         h3.join().expect("join");
     }
 
+    #[test]
+    fn with_proxy_routes_requests_through_proxy() {
+        let (proxy_url, handle) = with_server(|req| {
+            assert_eq!(req.url(), "http://registry.invalid/api/v1/crates/demo");
+            req.respond(Response::empty(StatusCode(200)))
+                .expect("respond");
+        });
+
+        let cli = RegistryClient::new(test_registry("http://registry.invalid".to_string()))
+            .expect("client")
+            .with_proxy(&proxy_url)
+            .expect("valid proxy");
+        assert!(cli.crate_exists("demo").expect("exists"));
+        handle.join().expect("join");
+    }
+
+    #[test]
+    fn with_proxy_rejects_invalid_url() {
+        let err = RegistryClient::new(test_registry("https://crates.io".to_string()))
+            .expect("client")
+            .with_proxy("http://")
+            .expect_err("missing host must fail");
+        assert!(format!("{err:#}").contains("invalid HTTPS proxy URL"));
+    }
+
+    #[test]
+    fn rate_limit_applies_to_every_request_method() {
+        // 60 rpm = one token per second with a burst of one: the second
+        // request has to wait for the bucket to refill.
+        let (api_base, handle) = with_multi_server(
+            |req| {
+                req.respond(Response::empty(StatusCode(200)))
+                    .expect("respond");
+            },
+            2,
+        );
+
+        let cli = RegistryClient::new(test_registry(api_base))
+            .expect("client")
+            .with_rate_limit(60);
+        let start = Instant::now();
+        assert!(cli.crate_exists("demo").expect("crate"));
+        assert!(cli.version_exists("demo", "1.0.0").expect("version"));
+        let elapsed = start.elapsed();
+        handle.join().expect("join");
+        assert!(
+            elapsed >= Duration::from_millis(900),
+            "expected throttling, finished in {elapsed:?}"
+        );
+    }
+
+    #[test]
+    fn check_versions_visible_reports_each_item_in_order() {
+        let (api_base, handle) = with_multi_server(
+            |req| {
+                let status = match req.url() {
+                    "/api/v1/crates/alpha/1.0.0" | "/api/v1/crates/gamma/0.3.0" => 200,
+                    "/api/v1/crates/delta/2.0.0" => 500,
+                    _ => 404,
+                };
+                req.respond(Response::empty(StatusCode(status)))
+                    .expect("respond");
+            },
+            4,
+        );
+
+        let cli = RegistryClient::new(test_registry(api_base)).expect("client");
+        let items: Vec<(String, String)> = [
+            ("alpha", "1.0.0"),
+            ("beta", "0.2.0"),
+            ("gamma", "0.3.0"),
+            ("delta", "2.0.0"),
+        ]
+        .into_iter()
+        .map(|(n, v)| (n.to_string(), v.to_string()))
+        .collect();
+
+        let results = cli.check_versions_visible(&items);
+        handle.join().expect("join");
+        assert_eq!(
+            results,
+            vec![
+                ("alpha".to_string(), "1.0.0".to_string(), true),
+                ("beta".to_string(), "0.2.0".to_string(), false),
+                ("gamma".to_string(), "0.3.0".to_string(), true),
+                // Errors count as not visible rather than failing the batch.
+                ("delta".to_string(), "2.0.0".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn check_versions_visible_respects_rate_limit() {
+        let (api_base, handle) = with_multi_server(
+            |req| {
+                req.respond(Response::empty(StatusCode(200)))
+                    .expect("respond");
+            },
+            2,
+        );
+
+        // 60 rpm allows one request, then one per second, however many
+        // threads are asking.
+        let cli = RegistryClient::new(test_registry(api_base))
+            .expect("client")
+            .with_rate_limit(60);
+        let items = vec![
+            ("a".to_string(), "1.0.0".to_string()),
+            ("b".to_string(), "1.0.0".to_string()),
+        ];
+        let start = Instant::now();
+        let results = cli.check_versions_visible(&items);
+        let elapsed = start.elapsed();
+        handle.join().expect("join");
+
+        assert!(results.iter().all(|(_, _, visible)| *visible));
+        assert!(
+            elapsed >= Duration::from_millis(900),
+            "expected throttling, finished in {elapsed:?}"
+        );
+    }
+
+    #[test]
+    fn check_versions_visible_empty_batch_sends_nothing() {
+        let cli =
+            RegistryClient::new(test_registry("http://127.0.0.1:9".to_string())).expect("client");
+        assert!(cli.check_versions_visible(&[]).is_empty());
+    }
+
+    #[test]
+    fn list_versions_parses_yanked_flags() {
+        let (api_base, handle) = with_server(|req| {
+            assert_eq!(req.url(), "/api/v1/crates/demo/versions");
+            let body = r#"{"versions":[
+                {"num":"1.1.0","yanked":false,"created_at":"2024-02-01T00:00:00Z","downloads":3},
+                {"num":"1.0.0","yanked":true,"created_at":"2024-01-01T00:00:00Z","downloads":9}
+            ],"meta":{"total":2,"next_page":null}}"#;
+            req.respond(Response::from_string(body).with_status_code(StatusCode(200)))
+                .expect("respond");
+        });
+
+        let cli = RegistryClient::new(test_registry(api_base)).expect("client");
+        let versions = cli.list_versions("demo").expect("versions");
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[0].num, "1.1.0");
+        assert!(!versions[0].yanked);
+        assert!(versions[1].yanked);
+        assert_eq!(versions[1].created_at, "2024-01-01T00:00:00Z");
+        handle.join().expect("join");
+    }
+
+    #[test]
+    fn list_versions_empty_for_404_and_errors_for_other_statuses() {
+        let (api_base_404, h1) = with_server(|req| {
+            req.respond(Response::empty(StatusCode(404)))
+                .expect("respond");
+        });
+        let cli_404 = RegistryClient::new(test_registry(api_base_404)).expect("client");
+        assert!(cli_404.list_versions("missing").expect("404").is_empty());
+        h1.join().expect("join");
+
+        let (api_base_500, h2) = with_server(|req| {
+            req.respond(Response::empty(StatusCode(500)))
+                .expect("respond");
+        });
+        let cli_500 = RegistryClient::new(test_registry(api_base_500)).expect("client");
+        let err = cli_500.list_versions("demo").expect_err("500 must fail");
+        assert!(matches!(err, RegistryError::Server(500)));
+        h2.join().expect("join");
+    }
+
+    #[test]
+    fn is_version_yanked_distinguishes_live_and_yanked() {
+        let body = r#"{"versions":[
+            {"num":"1.1.0","yanked":false,"created_at":"2024-02-01T00:00:00Z"},
+            {"num":"1.0.0","yanked":true,"created_at":"2024-01-01T00:00:00Z"}
+        ]}"#;
+        let (api_base, handle) = with_multi_server(
+            move |req| {
+                req.respond(Response::from_string(body).with_status_code(StatusCode(200)))
+                    .expect("respond");
+            },
+            3,
+        );
+
+        let cli = RegistryClient::new(test_registry(api_base)).expect("client");
+        assert!(cli.is_version_yanked("demo", "1.0.0").expect("yanked"));
+        assert!(!cli.is_version_yanked("demo", "1.1.0").expect("live"));
+        assert!(!cli.is_version_yanked("demo", "2.0.0").expect("missing"));
+        handle.join().expect("join");
+    }
+
     #[test]
     fn calculate_backoff_delay_is_bounded_with_jitter() {
         let (api_base, _handle) = with_server(|req| {
@@ -834,6 +1424,41 @@ mod tests {
         handle.join().expect("join");
     }
 
+    #[test]
+    fn check_index_visibility_reuses_in_memory_body_on_not_modified() {
+        let full_bodies = std::sync::Arc::new(AtomicUsize::new(0));
+        let served = full_bodies.clone();
+        let (api_base, handle) = with_multi_server(
+            move |req| {
+                let if_none_match = req
+                    .headers()
+                    .iter()
+                    .find(|h| h.field.equiv("If-None-Match"))
+                    .map(|h| h.value.as_str().to_string());
+                let resp = if if_none_match.as_deref() == Some("\"v1\"") {
+                    Response::from_string("").with_status_code(StatusCode(304))
+                } else {
+                    served.fetch_add(1, Ordering::SeqCst);
+                    Response::from_string("{\"vers\":\"1.0.1\"}\n")
+                        .with_status_code(StatusCode(200))
+                        .with_header(
+                            tiny_http::Header::from_bytes("ETag", "\"v1\"").expect("header"),
+                        )
+                };
+                req.respond(resp).expect("respond");
+            },
+            2,
+        );
+
+        // No cache dir: the 304 can only be served from the in-memory cache
+        let cli = RegistryClient::new(test_registry_with_index(api_base)).expect("client");
+        assert!(cli.check_index_visibility("demo", "1.0.1").expect("first"));
+        assert!(cli.check_index_visibility("demo", "1.0.1").expect("second"));
+        handle.join().expect("join");
+        assert_eq!(full_bodies.load(Ordering::SeqCst), 1);
+        assert_eq!(cli.index_cache().len(), 1);
+    }
+
     #[test]
     fn check_index_visibility_returns_false_for_missing_version() {
         let index_content = "{\"vers\":\"1.0.0\"}\n";
@@ -1921,7 +2546,8 @@ mod tests {
 
         let cli = RegistryClient::new(test_registry(api_base)).expect("client");
         let err = cli.list_owners("demo", "token").expect_err("429 must fail");
-        assert!(format!("{err:#}").contains("unexpected status while querying owners"));
+        assert!(matches!(err, RegistryError::RateLimited { .. }));
+        assert_eq!(err.classify(), shipper_retry::ErrorClass::Retryable);
         handle.join().expect("join");
     }
 
@@ -2333,7 +2959,7 @@ mod tests {
 
             #[test]
             fn random_crate_names_produce_valid_index_path(name in crate_name_strategy()) {
-                let path = shipper_sparse_index::sparse_index_path(&name);
+                let path = shipper_sparse_index::sparse_index_path_unchecked(&name);
                 // Path must not be empty
                 prop_assert!(!path.is_empty());
                 // Path must contain the lowercased crate name
@@ -2444,7 +3070,8 @@ mod tests {
 
         let cli = RegistryClient::new(test_registry(api_base)).expect("client");
         let err = cli.list_owners("demo", "token").expect_err("401 must fail");
-        assert!(format!("{err:#}").contains("unexpected status while querying owners"));
+        assert!(matches!(err, RegistryError::Unauthorized(_)));
+        assert_eq!(err.classify(), shipper_retry::ErrorClass::Permanent);
         handle.join().expect("join");
     }
 
@@ -2457,7 +3084,7 @@ mod tests {
 
         let cli = RegistryClient::new(test_registry(api_base)).expect("client");
         let err = cli.list_owners("demo", "token").expect_err("502 must fail");
-        assert!(format!("{err:#}").contains("unexpected status while querying owners"));
+        assert!(matches!(err, RegistryError::Server(502)));
         handle.join().expect("join");
     }
 
@@ -2606,6 +3233,81 @@ mod tests {
         handle.join().expect("join");
     }
 
+    fn verify_owners_against(body: &'static str, expected: &[&str]) -> OwnershipReport {
+        let (api_base, handle) = with_server(move |req| {
+            assert_eq!(req.url(), "/api/v1/crates/demo/owners");
+            let resp = Response::from_string(body)
+                .with_status_code(StatusCode(200))
+                .with_header(
+                    tiny_http::Header::from_bytes("Content-Type", "application/json")
+                        .expect("header"),
+                );
+            req.respond(resp).expect("respond");
+        });
+
+        let cli = RegistryClient::new(test_registry(api_base)).expect("client");
+        let expected: Vec<String> = expected.iter().map(|s| s.to_string()).collect();
+        let report = cli
+            .verify_owners("demo", &expected, "token")
+            .expect("report");
+        handle.join().expect("join");
+        report
+    }
+
+    const OWNERS_WITH_TEAM: &str = r#"{"users":[
+        {"id":1,"login":"alice","name":"Alice"},
+        {"id":2,"login":"github:Acme:Release-Team","name":"Release"}
+    ]}"#;
+
+    #[test]
+    fn verify_owners_exact_match_with_case_insensitive_team() {
+        let report =
+            verify_owners_against(OWNERS_WITH_TEAM, &["alice", "github:acme:release-team"]);
+        assert!(report.is_exact_match());
+        assert_eq!(report.present, vec!["alice", "github:acme:release-team"]);
+        assert!(report.missing.is_empty());
+        assert!(report.unexpected.is_empty());
+    }
+
+    #[test]
+    fn verify_owners_reports_missing_owners() {
+        let report = verify_owners_against(
+            OWNERS_WITH_TEAM,
+            &["alice", "github:acme:release-team", "bob"],
+        );
+        assert!(!report.is_exact_match());
+        assert_eq!(report.missing, vec!["bob"]);
+        assert!(report.unexpected.is_empty());
+        assert_eq!(report.to_string(), "missing owners: bob");
+    }
+
+    #[test]
+    fn verify_owners_reports_unexpected_owners() {
+        let report = verify_owners_against(OWNERS_WITH_TEAM, &["Alice"]);
+        assert!(report.present.is_empty());
+        assert_eq!(report.missing, vec!["Alice"]);
+        assert_eq!(report.unexpected, vec!["alice", "github:Acme:Release-Team"]);
+        assert_eq!(
+            report.to_string(),
+            "missing owners: Alice; unexpected owners: alice, github:Acme:Release-Team"
+        );
+    }
+
+    #[test]
+    fn verify_owners_propagates_owner_lookup_errors() {
+        let (api_base, handle) = with_server(|req| {
+            req.respond(Response::empty(StatusCode(403)))
+                .expect("respond");
+        });
+
+        let cli = RegistryClient::new(test_registry(api_base)).expect("client");
+        let err = cli
+            .verify_owners("demo", &["alice".to_string()], "token")
+            .expect_err("403 must fail");
+        assert!(format!("{err:#}").contains("forbidden when querying owners"));
+        handle.join().expect("join");
+    }
+
     #[test]
     fn list_owners_parses_empty_users_array() {
         let (api_base, handle) = with_server(|req| {
@@ -3450,7 +4152,12 @@ mod tests {
         let err = cli
             .list_owners("demo", "token")
             .expect_err("429 with Retry-After must fail");
-        assert!(format!("{err:#}").contains("unexpected status while querying owners"));
+        assert!(matches!(
+            err,
+            RegistryError::RateLimited {
+                retry_after: Some(d)
+            } if d == Duration::from_mins(2)
+        ));
         handle.join().expect("join");
     }
 
@@ -3507,7 +4214,7 @@ mod tests {
 
         let cli = RegistryClient::new(test_registry(api_base)).expect("client");
         let err = cli.list_owners("demo", "token").expect_err("503 must fail");
-        assert!(format!("{err:#}").contains("unexpected status while querying owners"));
+        assert!(matches!(err, RegistryError::Server(503)));
         handle.join().expect("join");
     }
 
@@ -5297,7 +6004,7 @@ mod tests {
     /// Index visibility check against a URL that returns 301 redirect — the
     /// reqwest blocking client follows redirects by default, so we set the
     /// mock to redirect to itself once, then respond 200 with content. This
-    /// also exercises the "unexpected status while fetching index" path
+    /// also exercises the catch-all `from_status` path
     /// when the redirected response is something other than 2xx/3xx/4xx —
     /// here we keep it simple by using 418 (I'm a teapot) which is treated
     /// as unexpected.
@@ -5315,7 +6022,7 @@ mod tests {
     }
 
     /// `crate_exists` against an unexpected 3xx (other than redirect that
-    /// reqwest auto-follows): tests the catch-all `s => bail!` arm. Because
+    /// reqwest auto-follows): tests the catch-all `from_status` arm. Because
     /// reqwest follows redirects, we use 304 Not Modified (which is not
     /// auto-followed for a non-conditional request) — the client should
     /// surface it via the catch-all error arm.
@@ -5328,7 +6035,7 @@ mod tests {
 
         let cli = RegistryClient::new(test_registry(api_base)).expect("client");
         let err = cli.crate_exists("demo").expect_err("304 must fail");
-        assert!(format!("{err:#}").contains("unexpected status while checking crate existence"));
+        assert!(matches!(err, RegistryError::Status(304)));
         handle.join().expect("join");
     }
 
@@ -5345,12 +6052,12 @@ mod tests {
         let err = cli
             .version_exists("demo", "1.0.0")
             .expect_err("304 must fail");
-        assert!(format!("{err:#}").contains("unexpected status while checking version existence"));
+        assert!(matches!(err, RegistryError::Status(304)));
         handle.join().expect("join");
     }
 
     /// `list_owners` against an unexpected 3xx — exercises the
-    /// catch-all `s => bail!` arm of the owners endpoint.
+    /// catch-all `from_status` arm of the owners endpoint.
     #[test]
     fn list_owners_errors_for_unexpected_304_not_modified() {
         let (api_base, handle) = with_server(|req| {
@@ -5360,7 +6067,7 @@ mod tests {
 
         let cli = RegistryClient::new(test_registry(api_base)).expect("client");
         let err = cli.list_owners("demo", "token").expect_err("304 must fail");
-        assert!(format!("{err:#}").contains("unexpected status while querying owners"));
+        assert!(matches!(err, RegistryError::Status(304)));
         handle.join().expect("join");
     }
 
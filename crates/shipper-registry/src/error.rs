@@ -0,0 +1,304 @@
+//! Typed registry errors.
+//!
+//! [`RegistryError`] keeps the HTTP status (and `Retry-After`, when the
+//! registry sends one) that a plain `anyhow` string would lose, so the publish
+//! engine can decide retryability with [`RegistryError::classify`] instead of
+//! matching on message text. `RegistryError` implements
+//! [`std::error::Error`], so existing `anyhow::Result` callers keep working
+//! through `?`.
+
+use std::fmt;
+use std::time::Duration;
+
+use shipper_retry::ErrorClass;
+
+/// Result alias for registry operations.
+pub type RegistryResult<T> = std::result::Result<T, RegistryError>;
+
+/// A failed registry request.
+#[derive(Debug)]
+pub enum RegistryError {
+    /// 404 for a resource that had to exist (the message says which)
+    NotFound(String),
+    /// 429 Too Many Requests
+    RateLimited {
+        /// Delay requested by the registry's `Retry-After` header
+        retry_after: Option<Duration>,
+    },
+    /// 401 Unauthorized (the message says which operation)
+    Unauthorized(String),
+    /// 403 Forbidden (the message says which operation)
+    Forbidden(String),
+    /// 5xx server error
+    Server(u16),
+    /// Any other unexpected status code
+    Status(u16),
+    /// The request never produced a response
+    Network {
+        /// What was being attempted
+        message: String,
+        /// Whether the request timed out (the server may still have acted)
+        timeout: bool,
+        /// The transport failure (DNS, TLS, connection refused, ...),
+        /// reachable through [`std::error::Error::source`]
+        source: reqwest::Error,
+    },
+    /// The response body could not be read or parsed
+    Parse(String),
+    /// The registry answered successfully but refused the operation
+    Rejected(String),
+}
+
+impl RegistryError {
+    /// Map this error onto the retry policy's [`ErrorClass`].
+    ///
+    /// Errors with a response use [`ErrorClass::from_http_status`] (429,
+    /// 408 and 5xx retryable, other 4xx permanent). Without one:
+    ///
+    /// - connection failures are [`ErrorClass::Retryable`]
+    /// - timeouts are [`ErrorClass::Ambiguous`]: the request may have
+    ///   reached the registry
+    /// - parse failures and operations the registry rejected are
+    ///   [`ErrorClass::Permanent`]
+    pub fn classify(&self) -> ErrorClass {
+        match self {
+            Self::Network { timeout: true, .. } => ErrorClass::Ambiguous,
+            Self::Network { timeout: false, .. } => ErrorClass::Retryable,
+            Self::Parse(_) | Self::Rejected(_) => ErrorClass::Permanent,
+            _ => self
+                .status()
+                .map_or(ErrorClass::Permanent, ErrorClass::from_http_status),
+        }
+    }
+
+    /// HTTP status code behind this error, if there was a response.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Self::NotFound(_) => Some(404),
+            Self::RateLimited { .. } => Some(429),
+            Self::Unauthorized(_) => Some(401),
+            Self::Forbidden(_) => Some(403),
+            Self::Server(code) | Self::Status(code) => Some(*code),
+            Self::Network { .. } | Self::Parse(_) | Self::Rejected(_) => None,
+        }
+    }
+
+    /// Build the error for an unexpected status with no operation-specific
+    /// message.
+    pub(crate) fn from_status(status: reqwest::StatusCode, retry_after: Option<Duration>) -> Self {
+        match status.as_u16() {
+            429 => Self::RateLimited { retry_after },
+            code @ 500..=599 => Self::Server(code),
+            code => Self::Status(code),
+        }
+    }
+
+    /// Build the error for a transport failure.
+    pub(crate) fn network(message: &str, err: reqwest::Error) -> Self {
+        Self::Network {
+            message: message.to_string(),
+            timeout: err.is_timeout(),
+            source: err,
+        }
+    }
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound(msg)
+            | Self::Unauthorized(msg)
+            | Self::Forbidden(msg)
+            | Self::Parse(msg)
+            | Self::Rejected(msg) => f.write_str(msg),
+            Self::RateLimited { retry_after } => {
+                write!(f, "unexpected status code: {}", status_text(429))?;
+                if let Some(delay) = retry_after {
+                    write!(f, " (retry after {}s)", delay.as_secs())?;
+                }
+                Ok(())
+            }
+            Self::Server(code) | Self::Status(code) => {
+                write!(f, "unexpected status code: {}", status_text(*code))
+            }
+            Self::Network { message, .. } => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Network { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+fn status_text(code: u16) -> String {
+    reqwest::StatusCode::from_u16(code)
+        .map(|s| s.to_string())
+        .unwrap_or_else(|_| code.to_string())
+}
+
+/// Parse a `Retry-After` header given in seconds.
+///
+/// The HTTP-date form is not used by crates.io and is ignored.
+pub(crate) fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    headers
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_code(code: u16) -> RegistryError {
+        RegistryError::from_status(reqwest::StatusCode::from_u16(code).expect("code"), None)
+    }
+
+    #[test]
+    fn rate_limited_is_retryable() {
+        assert_eq!(from_code(429).classify(), ErrorClass::Retryable);
+    }
+
+    #[test]
+    fn request_timeout_status_is_retryable() {
+        assert_eq!(from_code(408).classify(), ErrorClass::Retryable);
+    }
+
+    #[test]
+    fn server_errors_are_retryable() {
+        for code in [500, 502, 503, 504] {
+            let err = from_code(code);
+            assert!(matches!(err, RegistryError::Server(c) if c == code));
+            assert_eq!(err.classify(), ErrorClass::Retryable);
+        }
+    }
+
+    #[test]
+    fn auth_failures_are_permanent() {
+        assert_eq!(
+            RegistryError::Unauthorized("bad token".into()).classify(),
+            ErrorClass::Permanent
+        );
+        assert_eq!(
+            RegistryError::Forbidden("no scope".into()).classify(),
+            ErrorClass::Permanent
+        );
+    }
+
+    #[test]
+    fn other_client_errors_and_parse_failures_are_permanent() {
+        assert_eq!(from_code(400).classify(), ErrorClass::Permanent);
+        assert_eq!(from_code(409).classify(), ErrorClass::Permanent);
+        assert_eq!(
+            RegistryError::NotFound("missing".into()).classify(),
+            ErrorClass::Permanent
+        );
+        assert_eq!(
+            RegistryError::Parse("bad json".into()).classify(),
+            ErrorClass::Permanent
+        );
+    }
+
+    /// A real transport failure: a request to a port nothing listens on.
+    fn refused() -> reqwest::Error {
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .expect("bind")
+            .local_addr()
+            .expect("addr");
+        reqwest::blocking::get(format!("http://{addr}")).expect_err("refused")
+    }
+
+    /// A real timeout: the server accepts the connection but never answers.
+    fn timed_out() -> reqwest::Error {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        reqwest::blocking::Client::builder()
+            .timeout(Duration::from_millis(50))
+            .build()
+            .expect("client")
+            .get(format!("http://{addr}"))
+            .send()
+            .expect_err("timed out")
+    }
+
+    #[test]
+    fn network_failures_split_on_timeout() {
+        let refused = RegistryError::network("failed", refused());
+        let timed_out = RegistryError::network("failed", timed_out());
+        assert!(matches!(
+            refused,
+            RegistryError::Network { timeout: false, .. }
+        ));
+        assert!(matches!(
+            timed_out,
+            RegistryError::Network { timeout: true, .. }
+        ));
+        assert_eq!(refused.classify(), ErrorClass::Retryable);
+        assert_eq!(timed_out.classify(), ErrorClass::Ambiguous);
+    }
+
+    #[test]
+    fn network_error_keeps_transport_cause_in_chain() {
+        use std::error::Error as _;
+
+        let err = RegistryError::network("registry request failed", refused());
+        assert_eq!(err.to_string(), "registry request failed");
+        let source = err.source().expect("transport cause");
+        assert!(source.downcast_ref::<reqwest::Error>().is_some());
+
+        let chained = format!("{:#}", anyhow::Error::from(err));
+        assert!(
+            chained.starts_with("registry request failed: "),
+            "{chained}"
+        );
+        assert!(chained.len() > "registry request failed: ".len());
+    }
+
+    #[test]
+    fn status_reports_http_code() {
+        assert_eq!(from_code(429).status(), Some(429));
+        assert_eq!(from_code(503).status(), Some(503));
+        assert_eq!(from_code(418).status(), Some(418));
+        assert_eq!(RegistryError::Parse("x".into()).status(), None);
+    }
+
+    #[test]
+    fn display_keeps_legacy_status_wording() {
+        assert_eq!(
+            from_code(500).to_string(),
+            "unexpected status code: 500 Internal Server Error"
+        );
+        let limited = RegistryError::RateLimited {
+            retry_after: Some(Duration::from_secs(30)),
+        };
+        assert_eq!(
+            limited.to_string(),
+            "unexpected status code: 429 Too Many Requests (retry after 30s)"
+        );
+    }
+
+    #[test]
+    fn retry_after_parses_seconds_only() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+        headers.insert(reqwest::header::RETRY_AFTER, "12".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(12)));
+        headers.insert(
+            reqwest::header::RETRY_AFTER,
+            "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap(),
+        );
+        assert_eq!(retry_after(&headers), None);
+    }
+
+    #[test]
+    fn converts_into_anyhow() {
+        let err: anyhow::Error = from_code(503).into();
+        assert!(err.downcast_ref::<RegistryError>().is_some());
+    }
+}
@@ -3,7 +3,7 @@
 //! This module provides [`HttpRegistryClient`] — a thin reqwest wrapper that
 //! takes a bare base-URL string. Intended for callers that do not have a full
 //! [`shipper_types::Registry`] handy (e.g. the parallel engine helper crate).
-//! For the complete registry surface, use the [`crate::HttpRegistryClient`] from
+//! For the complete registry surface, use the [`crate::RegistryClient`] from
 //! the [`crate::context`] module.
 
 use std::time::Duration;
@@ -11,7 +11,193 @@ use std::time::Duration;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 
-use crate::{CRATES_IO_API, DEFAULT_TIMEOUT_SECS, USER_AGENT, sparse_index_path};
+use reqwest::header::HeaderMap;
+
+use crate::error::{RegistryError, RegistryResult, retry_after};
+use crate::index_cache::IndexCache;
+use crate::proxy::ProxyConfig;
+use crate::rate_limit::RateLimiter;
+use crate::{CRATES_IO_API, DEFAULT_TIMEOUT_SECS, USER_AGENT, sparse_index_path_unchecked};
+
+// ── Shared request/response helpers ──────────────────────────────────
+//
+// These free functions are the single source of truth for URL layout and
+// status mapping. Both [`HttpRegistryClient`] and
+// [`crate::AsyncRegistryClient`] route through them so the blocking and
+// async clients behave identically.
+
+/// URL of the crate metadata endpoint: `{base}/api/v1/crates/{name}`.
+pub(crate) fn crate_url(base_url: &str, name: &str) -> String {
+    format!("{}/api/v1/crates/{}", base_url, name)
+}
+
+/// URL of a single version endpoint: `{base}/api/v1/crates/{name}/{version}`.
+pub(crate) fn version_url(base_url: &str, name: &str, version: &str) -> String {
+    format!("{}/api/v1/crates/{}/{}", base_url, name, version)
+}
+
+/// URL of the versions endpoint: `{base}/api/v1/crates/{name}/versions`.
+pub(crate) fn versions_url(base_url: &str, name: &str) -> String {
+    format!("{}/api/v1/crates/{}/versions", base_url, name)
+}
+
+/// URL of the owners endpoint: `{base}/api/v1/crates/{name}/owners`.
+pub(crate) fn owners_url(base_url: &str, name: &str) -> String {
+    format!("{}/api/v1/crates/{}/owners", base_url, name)
+}
+
+/// URL of a crate's sparse-index file under `index_base`.
+pub(crate) fn sparse_index_url(index_base: &str, name: &str) -> String {
+    format!(
+        "{}/{}",
+        index_base.trim_end_matches('/'),
+        sparse_index_path_unchecked(name)
+    )
+}
+
+/// Map an existence-check status: 200 → `true`, 404 → `false`, else error.
+pub(crate) fn exists_from_status(
+    status: reqwest::StatusCode,
+    headers: &HeaderMap,
+) -> RegistryResult<bool> {
+    match status {
+        reqwest::StatusCode::OK => Ok(true),
+        reqwest::StatusCode::NOT_FOUND => Ok(false),
+        status => Err(RegistryError::from_status(status, retry_after(headers))),
+    }
+}
+
+/// Map a crate-info status: `Ok(false)` for 404, `Ok(true)` when the body
+/// should be parsed, error otherwise.
+pub(crate) fn crate_info_status(
+    status: reqwest::StatusCode,
+    headers: &HeaderMap,
+) -> RegistryResult<bool> {
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Ok(false);
+    }
+    if !status.is_success() {
+        return Err(RegistryError::from_status(status, retry_after(headers)));
+    }
+    Ok(true)
+}
+
+/// Map an owners-query status: `Ok(true)` when the body should be parsed,
+/// `Ok(false)` for 404, error for auth failures and anything else.
+pub(crate) fn owners_status(
+    status: reqwest::StatusCode,
+    headers: &HeaderMap,
+) -> RegistryResult<bool> {
+    const AUTH_MSG: &str =
+        "forbidden when querying owners; token may be invalid or missing required scope";
+    match status {
+        reqwest::StatusCode::OK => Ok(true),
+        reqwest::StatusCode::NOT_FOUND => Ok(false),
+        reqwest::StatusCode::UNAUTHORIZED => Err(RegistryError::Unauthorized(AUTH_MSG.into())),
+        reqwest::StatusCode::FORBIDDEN => Err(RegistryError::Forbidden(AUTH_MSG.into())),
+        status => Err(RegistryError::from_status(status, retry_after(headers))),
+    }
+}
+
+/// Map an owner add/remove response to the registry's message or an error.
+///
+/// crates.io answers success with `{"ok":true,"msg":"..."}` and failures with
+/// `{"errors":[{"detail":"..."}]}`; the detail is folded into the error.
+pub(crate) fn owner_change_result(
+    status: reqwest::StatusCode,
+    headers: &HeaderMap,
+    body: &str,
+    name: &str,
+) -> RegistryResult<String> {
+    let detail = serde_json::from_str::<RegistryErrorsResponse>(body)
+        .ok()
+        .and_then(|r| r.errors.into_iter().next())
+        .map(|e| format!(": {}", e.detail))
+        .unwrap_or_default();
+    let auth_msg = || {
+        format!(
+            "forbidden when changing owners of {name}; token may be invalid, missing required scope, or not an owner{detail}"
+        )
+    };
+
+    match status {
+        s if s.is_success() => {
+            let parsed: OwnersChangeResponse = serde_json::from_str(body).map_err(|e| {
+                RegistryError::Parse(format!("failed to parse owners change response: {e}"))
+            })?;
+            let msg = parsed.msg.unwrap_or_default();
+            if parsed.ok {
+                Ok(msg)
+            } else {
+                Err(RegistryError::Rejected(format!(
+                    "registry rejected owners change: {msg}"
+                )))
+            }
+        }
+        reqwest::StatusCode::NOT_FOUND => Err(RegistryError::NotFound(format!(
+            "crate or user not found when changing owners of {name}{detail}"
+        ))),
+        reqwest::StatusCode::UNAUTHORIZED => Err(RegistryError::Unauthorized(auth_msg())),
+        reqwest::StatusCode::FORBIDDEN => Err(RegistryError::Forbidden(auth_msg())),
+        status => Err(RegistryError::from_status(status, retry_after(headers))),
+    }
+}
+
+/// Map a non-success, non-304 sparse-index status to an error.
+pub(crate) fn sparse_index_status_error(
+    status: reqwest::StatusCode,
+    headers: &HeaderMap,
+    url: &str,
+) -> RegistryError {
+    match status {
+        reqwest::StatusCode::NOT_FOUND => {
+            RegistryError::NotFound(format!("index file not found: {url}"))
+        }
+        status => RegistryError::from_status(status, retry_after(headers)),
+    }
+}
+
+/// Reject zero timeouts, which would fail every request immediately.
+pub(crate) fn validate_timeouts(connect: Duration, read: Duration) -> Result<()> {
+    if connect.is_zero() {
+        anyhow::bail!("connect timeout must be greater than zero");
+    }
+    if read.is_zero() {
+        anyhow::bail!("read timeout must be greater than zero");
+    }
+    Ok(())
+}
+
+/// Build the blocking client used by [`HttpRegistryClient`].
+///
+/// `timeout` bounds the whole request, body included; `connect_timeout`
+/// bounds only establishing the connection.
+pub(crate) fn build_blocking_client(
+    timeout: Duration,
+    connect_timeout: Duration,
+    proxy: &ProxyConfig,
+) -> Result<reqwest::blocking::Client> {
+    let builder = reqwest::blocking::Client::builder()
+        .timeout(timeout)
+        .connect_timeout(connect_timeout)
+        .user_agent(USER_AGENT);
+    proxy
+        .apply_blocking(builder)?
+        .build()
+        .context("failed to build HTTP client")
+}
+
+pub(crate) fn owners_from_response(response: OwnersResponse) -> Vec<Owner> {
+    response
+        .users
+        .into_iter()
+        .map(|owner| Owner {
+            login: owner.login,
+            name: owner.name,
+            avatar: owner.avatar,
+        })
+        .collect()
+}
 
 /// Lightweight HTTP registry client that operates on a raw base-URL.
 ///
@@ -21,24 +207,40 @@ use crate::{CRATES_IO_API, DEFAULT_TIMEOUT_SECS, USER_AGENT, sparse_index_path};
 pub struct HttpRegistryClient {
     base_url: String,
     timeout: Duration,
+    connect_timeout: Duration,
     client: reqwest::blocking::Client,
     cache_dir: Option<std::path::PathBuf>,
+    index_cache: IndexCache,
+    rate_limiter: Option<RateLimiter>,
+    proxy: ProxyConfig,
 }
 
 impl HttpRegistryClient {
     /// Create a new registry client for the given base URL
-    pub fn new(base_url: &str) -> Self {
-        let client = reqwest::blocking::Client::builder()
-            .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
-            .user_agent(USER_AGENT)
-            .build()
-            .unwrap_or_else(|_| reqwest::blocking::Client::new());
+    ///
+    /// Proxy settings are read from `HTTPS_PROXY` / `HTTP_PROXY` / `NO_PROXY`.
+    /// Fails if those hold an invalid URL rather than silently connecting
+    /// directly.
+    pub fn new(base_url: &str) -> Result<Self> {
+        Self::with_proxy_config(base_url, ProxyConfig::from_env())
+    }
+
+    fn with_proxy_config(base_url: &str, proxy: ProxyConfig) -> Result<Self> {
+        let timeout = Duration::from_secs(DEFAULT_TIMEOUT_SECS);
+        let client = build_blocking_client(timeout, timeout, &proxy)?;
+        Ok(Self::with_parts(base_url, proxy, client))
+    }
 
+    fn with_parts(base_url: &str, proxy: ProxyConfig, client: reqwest::blocking::Client) -> Self {
         Self {
             base_url: base_url.trim_end_matches('/').to_string(),
             timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            connect_timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
             client,
             cache_dir: None,
+            index_cache: IndexCache::new(),
+            rate_limiter: None,
+            proxy,
         }
     }
 
@@ -49,219 +251,396 @@ impl HttpRegistryClient {
     }
 
     /// Create a client for crates.io
-    pub fn crates_io() -> Self {
+    ///
+    /// Fails like [`Self::new`] on invalid proxy settings.
+    pub fn crates_io() -> Result<Self> {
         Self::new(CRATES_IO_API)
     }
 
-    /// Set the request timeout
-    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+    /// Set the request timeout, used for both connecting and the whole
+    /// request
+    pub fn with_timeout(mut self, timeout: Duration) -> Result<Self> {
+        self.client = build_blocking_client(timeout, timeout, &self.proxy)?;
         self.timeout = timeout;
-        self.client = reqwest::blocking::Client::builder()
-            .timeout(timeout)
-            .user_agent(USER_AGENT)
-            .build()
-            .unwrap_or_else(|_| reqwest::blocking::Client::new());
-        self
+        self.connect_timeout = timeout;
+        Ok(self)
     }
 
-    /// Check if a crate exists in the registry
-    pub fn crate_exists(&self, name: &str) -> Result<bool> {
-        let url = format!("{}/api/v1/crates/{}", self.base_url, name);
+    /// Set separate timeouts for connecting and for the whole request.
+    ///
+    /// A short `connect` fails fast on an unreachable proxy or VPN, while a
+    /// long `read` leaves room for large index downloads. Both must be
+    /// non-zero.
+    pub fn with_timeouts(mut self, connect: Duration, read: Duration) -> Result<Self> {
+        validate_timeouts(connect, read)?;
+        self.client = build_blocking_client(read, connect, &self.proxy)?;
+        self.timeout = read;
+        self.connect_timeout = connect;
+        Ok(self)
+    }
 
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .context("failed to send request to registry")?;
+    /// The connect and overall request timeouts, in that order
+    pub fn timeouts(&self) -> (Duration, Duration) {
+        (self.connect_timeout, self.timeout)
+    }
 
-        match response.status() {
-            reqwest::StatusCode::OK => Ok(true),
-            reqwest::StatusCode::NOT_FOUND => Ok(false),
-            status => Err(anyhow::anyhow!("unexpected status code: {}", status)),
+    /// Route all requests through `proxy_url` (`http://`, `https://` or
+    /// `socks5://`), replacing any proxy picked up from the environment.
+    pub fn with_proxy(mut self, proxy_url: &str) -> Result<Self> {
+        let proxy = ProxyConfig::all(proxy_url);
+        self.client = build_blocking_client(self.timeout, self.connect_timeout, &proxy)?;
+        self.proxy = proxy;
+        Ok(self)
+    }
+
+    /// The proxy settings this client was built with
+    pub fn proxy(&self) -> &ProxyConfig {
+        &self.proxy
+    }
+
+    /// Limit outbound requests to `requests_per_minute`.
+    ///
+    /// Every request method waits on the limiter before sending. Clones of
+    /// this client share the same bucket, so parallel publishes stay under
+    /// one combined limit.
+    pub fn with_rate_limit(mut self, requests_per_minute: u32) -> Self {
+        self.rate_limiter = Some(RateLimiter::new(requests_per_minute));
+        self
+    }
+
+    fn send(
+        &self,
+        request: reqwest::blocking::RequestBuilder,
+    ) -> reqwest::Result<reqwest::blocking::Response> {
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.acquire();
         }
+        request.send()
+    }
+
+    /// Check if a crate exists in the registry
+    pub fn crate_exists(&self, name: &str) -> RegistryResult<bool> {
+        self.crate_exists_with_token(name, None)
+    }
+
+    /// Check if a crate exists, sending `token` as the `Authorization` header.
+    ///
+    /// Private registries often reject anonymous API reads; crates.io does not
+    /// need a token, so pass `None` there.
+    pub fn crate_exists_with_token(&self, name: &str, token: Option<&str>) -> RegistryResult<bool> {
+        self.exists_with_token(&crate_url(&self.base_url, name), token)
     }
 
     /// Check if a specific version of a crate exists
-    pub fn version_exists(&self, name: &str, version: &str) -> Result<bool> {
-        let url = format!("{}/api/v1/crates/{}/{}", self.base_url, name, version);
+    pub fn version_exists(&self, name: &str, version: &str) -> RegistryResult<bool> {
+        self.version_exists_with_token(name, version, None)
+    }
 
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .context("failed to send request to registry")?;
+    /// Check if a specific version exists, sending `token` as the
+    /// `Authorization` header when present.
+    pub fn version_exists_with_token(
+        &self,
+        name: &str,
+        version: &str,
+        token: Option<&str>,
+    ) -> RegistryResult<bool> {
+        self.exists_with_token(&version_url(&self.base_url, name, version), token)
+    }
 
-        match response.status() {
-            reqwest::StatusCode::OK => Ok(true),
-            reqwest::StatusCode::NOT_FOUND => Ok(false),
-            status => Err(anyhow::anyhow!("unexpected status code: {}", status)),
+    fn exists_with_token(&self, url: &str, token: Option<&str>) -> RegistryResult<bool> {
+        let mut request = self.client.get(url);
+        if let Some(token) = token {
+            request = request.header("Authorization", token);
         }
+
+        let response = self
+            .send(request)
+            .map_err(|e| RegistryError::network("failed to send request to registry", e))?;
+
+        exists_from_status(response.status(), response.headers())
     }
 
     /// Get crate information
-    pub fn get_crate_info(&self, name: &str) -> Result<Option<CrateInfo>> {
-        let url = format!("{}/api/v1/crates/{}", self.base_url, name);
+    pub fn get_crate_info(&self, name: &str) -> RegistryResult<Option<CrateInfo>> {
+        let url = crate_url(&self.base_url, name);
 
         let response = self
-            .client
-            .get(&url)
-            .send()
-            .context("failed to send request to registry")?;
+            .send(self.client.get(&url))
+            .map_err(|e| RegistryError::network("failed to send request to registry", e))?;
 
-        if response.status() == reqwest::StatusCode::NOT_FOUND {
+        if !crate_info_status(response.status(), response.headers())? {
             return Ok(None);
         }
 
-        if !response.status().is_success() {
-            return Err(anyhow::anyhow!(
-                "unexpected status code: {}",
-                response.status()
-            ));
+        let crate_response: CrateResponse = response
+            .json()
+            .map_err(|e| RegistryError::Parse(format!("failed to parse crate response: {e}")))?;
+
+        Ok(Some(crate_response.into()))
+    }
+
+    /// List every published version of a crate, including yanked ones.
+    ///
+    /// Returns an empty list when the crate does not exist.
+    pub fn list_versions(&self, name: &str) -> RegistryResult<Vec<VersionInfo>> {
+        let url = versions_url(&self.base_url, name);
+
+        let response = self
+            .send(self.client.get(&url))
+            .map_err(|e| RegistryError::network("failed to send request to registry", e))?;
+
+        if !crate_info_status(response.status(), response.headers())? {
+            return Ok(Vec::new());
+        }
+
+        let versions_response: VersionsResponse = response
+            .json()
+            .map_err(|e| RegistryError::Parse(format!("failed to parse versions response: {e}")))?;
+        Ok(versions_response.versions)
+    }
+
+    /// List every published version with download counts, publish dates
+    /// and licenses, newest first as the registry orders them.
+    ///
+    /// Returns an empty list when the crate does not exist.
+    pub fn get_versions_detailed(&self, name: &str) -> RegistryResult<Vec<VersionDetail>> {
+        let url = versions_url(&self.base_url, name);
+
+        let response = self
+            .send(self.client.get(&url))
+            .map_err(|e| RegistryError::network("failed to send request to registry", e))?;
+
+        if !crate_info_status(response.status(), response.headers())? {
+            return Ok(Vec::new());
         }
 
-        let crate_response: CrateResponse =
-            response.json().context("failed to parse crate response")?;
+        let versions_response: VersionsResponse<VersionDetail> = response
+            .json()
+            .map_err(|e| RegistryError::Parse(format!("failed to parse versions response: {e}")))?;
+        Ok(versions_response.versions)
+    }
 
-        Ok(Some(CrateInfo {
-            name: crate_response.crate_data.name,
-            newest_version: crate_response.crate_data.newest_version,
-            created_at: crate_response.crate_data.created_at,
-            updated_at: crate_response.crate_data.updated_at,
-        }))
+    /// Check whether a published version has been yanked.
+    ///
+    /// Versions that were never published are reported as not yanked.
+    pub fn is_version_yanked(&self, name: &str, version: &str) -> RegistryResult<bool> {
+        Ok(is_yanked_in(&self.list_versions(name)?, version))
     }
 
     fn fetch_owners_with_token(
         &self,
         name: &str,
         token: Option<&str>,
-    ) -> Result<Option<OwnersResponse>> {
-        let url = format!("{}/api/v1/crates/{}/owners", self.base_url, name);
+    ) -> RegistryResult<Option<OwnersResponse>> {
+        let url = owners_url(&self.base_url, name);
         let mut request = self.client.get(&url);
         if let Some(token) = token {
             request = request.header("Authorization", token);
         }
 
-        let response = request.send().context("failed to query owners")?;
-        match response.status() {
-            reqwest::StatusCode::OK => {
-                let owners_response: OwnersResponse =
-                    response.json().context("failed to parse owners response")?;
-                Ok(Some(owners_response))
-            }
-            reqwest::StatusCode::NOT_FOUND => Ok(None),
-            reqwest::StatusCode::FORBIDDEN | reqwest::StatusCode::UNAUTHORIZED => {
-                Err(anyhow::anyhow!(
-                    "forbidden when querying owners; token may be invalid or missing required scope"
-                ))
-            }
-            status => Err(anyhow::anyhow!(
-                "unexpected status while querying owners: {status}"
-            )),
+        let response = self
+            .send(request)
+            .map_err(|e| RegistryError::network("failed to query owners", e))?;
+        if !owners_status(response.status(), response.headers())? {
+            return Ok(None);
         }
+        let owners_response: OwnersResponse = response
+            .json()
+            .map_err(|e| RegistryError::Parse(format!("failed to parse owners response: {e}")))?;
+        Ok(Some(owners_response))
     }
 
     /// Get the list of owners for a crate.
-    pub fn get_owners(&self, name: &str) -> Result<Vec<Owner>> {
+    pub fn get_owners(&self, name: &str) -> RegistryResult<Vec<Owner>> {
         let owners_response = self
             .fetch_owners_with_token(name, None)?
             .unwrap_or_default();
-        Ok(owners_response
-            .users
-            .into_iter()
-            .map(|owner| Owner {
-                login: owner.login,
-                name: owner.name,
-                avatar: owner.avatar,
-            })
-            .collect())
+        Ok(owners_from_response(owners_response))
     }
 
     /// List owners for a crate with token-aware lookup.
-    pub fn list_owners(&self, name: &str, token: &str) -> Result<OwnersResponse> {
+    pub fn list_owners(&self, name: &str, token: &str) -> RegistryResult<OwnersResponse> {
         self.fetch_owners_with_token(name, Some(token))?
-            .ok_or_else(|| anyhow::anyhow!("crate not found when querying owners: {name}"))
+            .ok_or_else(|| {
+                RegistryError::NotFound(format!("crate not found when querying owners: {name}"))
+            })
     }
 
     /// Check if a user is an owner of a crate
-    pub fn is_owner(&self, name: &str, username: &str) -> Result<bool> {
+    pub fn is_owner(&self, name: &str, username: &str) -> RegistryResult<bool> {
         let owners = self.get_owners(name)?;
         Ok(owners.iter().any(|o| o.login == username))
     }
 
+    /// Invite `login` to become an owner of a crate.
+    ///
+    /// Returns the registry's confirmation message (e.g. "user bob has been
+    /// invited to be an owner of crate demo").
+    pub fn add_owner(&self, name: &str, login: &str, token: &str) -> RegistryResult<String> {
+        self.change_owners(reqwest::Method::PUT, name, login, token)
+    }
+
+    /// Remove `login` from the owners of a crate.
+    ///
+    /// Returns the registry's confirmation message.
+    pub fn remove_owner(&self, name: &str, login: &str, token: &str) -> RegistryResult<String> {
+        self.change_owners(reqwest::Method::DELETE, name, login, token)
+    }
+
+    fn change_owners(
+        &self,
+        method: reqwest::Method,
+        name: &str,
+        login: &str,
+        token: &str,
+    ) -> RegistryResult<String> {
+        let url = owners_url(&self.base_url, name);
+        let body = OwnersChangeRequest {
+            users: vec![login.to_string()],
+        };
+
+        let request = self
+            .client
+            .request(method, &url)
+            .header("Authorization", token)
+            .json(&body);
+        let response = self
+            .send(request)
+            .map_err(|e| RegistryError::network("failed to send owners request", e))?;
+
+        let status = response.status();
+        let headers = response.headers().clone();
+        let text = response.text().unwrap_or_default();
+        owner_change_result(status, &headers, &text, name)
+    }
+
     /// Check if a version exists in sparse-index metadata.
     pub fn is_version_visible_in_sparse_index(
         &self,
         index_base: &str,
         name: &str,
         version: &str,
-    ) -> Result<bool> {
+    ) -> RegistryResult<bool> {
         let content = self.fetch_sparse_index_file(index_base, name)?;
         Ok(shipper_sparse_index::contains_version(&content, version))
     }
 
     /// Fetch sparse-index content for a crate.
-    pub fn fetch_sparse_index_file(&self, index_base: &str, name: &str) -> Result<String> {
-        let index_base = index_base.trim_end_matches('/');
-        let index_path = sparse_index_path(name);
-        let url = format!("{}/{}", index_base, index_path);
+    ///
+    /// Sends `If-None-Match` with the ETag from the in-memory [`IndexCache`]
+    /// (or, failing that, from the on-disk cache) and reuses the cached body
+    /// when the registry answers `304 Not Modified`.
+    pub fn fetch_sparse_index_file(&self, index_base: &str, name: &str) -> RegistryResult<String> {
+        let index_path = sparse_index_path_unchecked(name);
 
         let cache_file = self.cache_dir.as_ref().map(|d| d.join(&index_path));
         let etag_file = cache_file.as_ref().map(|f| f.with_extension("etag"));
 
-        let mut request = self.client.get(&url);
-
-        if let Some(ref path) = etag_file
-            && let Ok(etag) = std::fs::read_to_string(path)
-        {
-            request = request.header(reqwest::header::IF_NONE_MATCH, etag.trim());
-        }
-
-        let response = request.send().context("index request failed")?;
-
-        match response.status() {
-            reqwest::StatusCode::OK => {
-                let etag = response
-                    .headers()
-                    .get(reqwest::header::ETAG)
-                    .and_then(|h| h.to_str().ok())
-                    .map(|s| s.to_string());
-                let content = response
-                    .text()
-                    .context("failed to read index response body")?;
+        let cached = self.index_cache.get(name);
+        let etag = cached.as_ref().map(|c| c.etag.clone()).or_else(|| {
+            etag_file
+                .as_ref()
+                .and_then(|path| std::fs::read_to_string(path).ok())
+                .map(|etag| etag.trim().to_string())
+        });
 
+        match self.fetch_sparse_index_file_conditional(index_base, name, etag.as_deref())? {
+            SparseIndexFetch::Modified { body, etag } => {
                 if let Some(ref path) = cache_file {
                     if let Some(parent) = path.parent() {
                         let _ = std::fs::create_dir_all(parent);
                     }
-                    let _ = std::fs::write(path, &content);
-                    if let (Some(ref etag_val), Some(etag_path)) = (etag, etag_file) {
+                    let _ = std::fs::write(path, &body);
+                    if let (Some(etag_val), Some(etag_path)) = (&etag, etag_file) {
                         let _ = std::fs::write(etag_path, etag_val);
                     }
                 }
-                Ok(content)
+                match etag {
+                    Some(etag) => self.index_cache.insert(name, etag, body.clone()),
+                    None => self.index_cache.remove(name),
+                }
+                Ok(body)
             }
-            reqwest::StatusCode::NOT_MODIFIED => {
-                if let Some(ref path) = cache_file {
-                    std::fs::read_to_string(path).context("failed to read cached index file")
+            SparseIndexFetch::NotModified => {
+                if let Some(cached) = cached {
+                    Ok(cached.body)
+                } else if let Some(ref path) = cache_file {
+                    std::fs::read_to_string(path).map_err(|e| {
+                        RegistryError::Parse(format!("failed to read cached index file: {e}"))
+                    })
                 } else {
-                    Err(anyhow::anyhow!(
-                        "received 304 Not Modified but no cache file available"
-                    ))
+                    // Nothing was cached, so the 304 answers a request we
+                    // never made conditionally.
+                    Err(RegistryError::Status(304))
                 }
             }
-            reqwest::StatusCode::NOT_FOUND => Err(anyhow::anyhow!("index file not found: {url}")),
-            status => Err(anyhow::anyhow!(
-                "unexpected status while fetching index: {status}"
-            )),
         }
     }
 
+    /// Fetch sparse-index content for a crate, conditional on `etag`.
+    ///
+    /// When `etag` is given it is sent as `If-None-Match`; a `304` response
+    /// yields [`SparseIndexFetch::NotModified`] and the caller keeps using its
+    /// own copy. No caching happens here.
+    pub fn fetch_sparse_index_file_conditional(
+        &self,
+        index_base: &str,
+        name: &str,
+        etag: Option<&str>,
+    ) -> RegistryResult<SparseIndexFetch> {
+        let url = sparse_index_url(index_base, name);
+
+        let mut request = self.client.get(&url);
+        if let Some(etag) = etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+
+        let response = self
+            .send(request)
+            .map_err(|e| RegistryError::network("index request failed", e))?;
+
+        match response.status() {
+            reqwest::StatusCode::OK => {
+                let etag = response
+                    .headers()
+                    .get(reqwest::header::ETAG)
+                    .and_then(|h| h.to_str().ok())
+                    .map(|s| s.to_string());
+                let body = response.text().map_err(|e| {
+                    RegistryError::Parse(format!("failed to read index response body: {e}"))
+                })?;
+                Ok(SparseIndexFetch::Modified { body, etag })
+            }
+            reqwest::StatusCode::NOT_MODIFIED => Ok(SparseIndexFetch::NotModified),
+            status => Err(sparse_index_status_error(status, response.headers(), &url)),
+        }
+    }
+
+    /// The in-memory sparse-index cache shared by this client and its clones
+    pub fn index_cache(&self) -> &IndexCache {
+        &self.index_cache
+    }
+
     /// Get the base URL
     pub fn base_url(&self) -> &str {
         &self.base_url
     }
 }
 
+/// Outcome of a conditional sparse-index fetch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SparseIndexFetch {
+    /// `200 OK`: a fresh body, plus the ETag if the registry sent one
+    Modified {
+        /// Raw sparse-index content
+        body: String,
+        /// ETag to send as `If-None-Match` next time
+        etag: Option<String>,
+    },
+    /// `304 Not Modified`: the caller's cached body is still current
+    NotModified,
+}
+
 /// Crate information from the registry
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CrateInfo {
@@ -275,6 +654,45 @@ pub struct CrateInfo {
     pub updated_at: String,
 }
 
+/// A single published version of a crate
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionInfo {
+    /// Version number
+    pub num: String,
+    /// Whether the version has been yanked
+    pub yanked: bool,
+    /// When the version was published
+    pub created_at: String,
+}
+
+/// A published version with the statistics dashboards want
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionDetail {
+    /// Version number
+    pub num: String,
+    /// Whether the version has been yanked
+    pub yanked: bool,
+    /// Total downloads of this version (0 if the registry does not report it)
+    #[serde(default)]
+    pub downloads: u64,
+    /// When the version was published
+    pub created_at: String,
+    /// SPDX license expression, if the version declared one
+    #[serde(default)]
+    pub license: Option<String>,
+}
+
+/// Response from the versions API
+#[derive(Debug, Deserialize)]
+pub(crate) struct VersionsResponse<T = VersionInfo> {
+    pub(crate) versions: Vec<T>,
+}
+
+/// Whether `version` appears in `versions` with its yanked flag set.
+pub(crate) fn is_yanked_in(versions: &[VersionInfo], version: &str) -> bool {
+    versions.iter().any(|v| v.num == version && v.yanked)
+}
+
 /// Owner information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OwnersApiUser {
@@ -301,7 +719,7 @@ pub struct Owner {
 
 /// Response from the crate API
 #[derive(Debug, Deserialize)]
-struct CrateResponse {
+pub(crate) struct CrateResponse {
     #[serde(rename = "crate")]
     crate_data: CrateData,
 }
@@ -315,32 +733,73 @@ struct CrateData {
     updated_at: String,
 }
 
+impl From<CrateResponse> for CrateInfo {
+    fn from(response: CrateResponse) -> Self {
+        Self {
+            name: response.crate_data.name,
+            newest_version: response.crate_data.newest_version,
+            created_at: response.crate_data.created_at,
+            updated_at: response.crate_data.updated_at,
+        }
+    }
+}
+
 /// Response from the owners API
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct OwnersResponse {
     pub users: Vec<OwnersApiUser>,
 }
 
+/// Request body for adding or removing owners
+#[derive(Debug, Serialize)]
+struct OwnersChangeRequest {
+    users: Vec<String>,
+}
+
+/// Response from the owners add/remove API
+#[derive(Debug, Deserialize)]
+struct OwnersChangeResponse {
+    ok: bool,
+    msg: Option<String>,
+}
+
+/// Error envelope returned by crates.io for failed API calls
+#[derive(Debug, Deserialize)]
+struct RegistryErrorsResponse {
+    errors: Vec<RegistryErrorDetail>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RegistryErrorDetail {
+    detail: String,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{CRATES_IO_API, is_crate_visible, is_version_visible};
+    use crate::{
+        CRATES_IO_API, is_crate_visible, is_crate_visible_with_token, is_version_visible,
+        is_version_visible_with_token, sparse_index_path,
+    };
 
     #[test]
     fn client_creation() {
-        let client = HttpRegistryClient::crates_io();
+        let client = HttpRegistryClient::crates_io().expect("client");
         assert_eq!(client.base_url(), "https://crates.io");
     }
 
     #[test]
     fn client_with_custom_url() {
-        let client = HttpRegistryClient::new("https://custom.registry.io/");
+        let client = HttpRegistryClient::new("https://custom.registry.io/").expect("client");
         assert_eq!(client.base_url(), "https://custom.registry.io");
     }
 
     #[test]
     fn client_with_timeout() {
-        let client = HttpRegistryClient::crates_io().with_timeout(Duration::from_mins(1));
+        let client = HttpRegistryClient::crates_io()
+            .expect("client")
+            .with_timeout(Duration::from_mins(1))
+            .expect("client");
         assert_eq!(client.timeout, Duration::from_mins(1));
     }
 
@@ -387,6 +846,221 @@ mod tests {
         assert_eq!(response.versions[0].num, "1.0.0");
     }
 
+    /// Trimmed recording of `GET https://crates.io/api/v1/crates/demo/versions`.
+    const RECORDED_VERSIONS_PAYLOAD: &str = r#"{
+        "versions": [
+            {
+                "id": 1402917,
+                "crate": "demo",
+                "num": "0.2.0",
+                "dl_path": "/api/v1/crates/demo/0.2.0/download",
+                "readme_path": "/api/v1/crates/demo/0.2.0/readme",
+                "updated_at": "2024-09-03T11:12:40.120947Z",
+                "created_at": "2024-09-03T11:12:40.120947Z",
+                "downloads": 1873,
+                "features": {},
+                "yanked": false,
+                "yank_message": null,
+                "license": "MIT OR Apache-2.0",
+                "links": {
+                    "dependencies": "/api/v1/crates/demo/0.2.0/dependencies",
+                    "version_downloads": "/api/v1/crates/demo/0.2.0/downloads",
+                    "authors": "/api/v1/crates/demo/0.2.0/authors"
+                },
+                "crate_size": 10432,
+                "published_by": {"id": 7, "login": "alice", "name": "Alice", "avatar": null, "url": "https://github.com/alice"},
+                "audit_actions": [],
+                "checksum": "0f3c9a3a1a9e6f2f0b1c7b1f5c2e4d9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d3e",
+                "rust_version": "1.70",
+                "has_lib": true,
+                "bin_names": [],
+                "edition": "2021"
+            },
+            {
+                "id": 1399001,
+                "crate": "demo",
+                "num": "0.1.1",
+                "dl_path": "/api/v1/crates/demo/0.1.1/download",
+                "readme_path": "/api/v1/crates/demo/0.1.1/readme",
+                "updated_at": "2024-09-02T08:00:00.000000Z",
+                "created_at": "2024-08-30T16:45:02.551203Z",
+                "downloads": 212,
+                "features": {"default": ["std"], "std": []},
+                "yanked": true,
+                "yank_message": "broken build script",
+                "license": "MIT OR Apache-2.0",
+                "links": {
+                    "dependencies": "/api/v1/crates/demo/0.1.1/dependencies",
+                    "version_downloads": "/api/v1/crates/demo/0.1.1/downloads",
+                    "authors": "/api/v1/crates/demo/0.1.1/authors"
+                },
+                "crate_size": 10120,
+                "published_by": null,
+                "audit_actions": [],
+                "checksum": "a7b6c5d4e3f2a1b0c9d8e7f6a5b4c3d2e1f0a9b8c7d6e5f4a3b2c1d0e9f8a7b6",
+                "rust_version": null,
+                "has_lib": true,
+                "bin_names": [],
+                "edition": null
+            },
+            {
+                "id": 1398544,
+                "crate": "demo",
+                "num": "0.1.0",
+                "dl_path": "/api/v1/crates/demo/0.1.0/download",
+                "readme_path": "/api/v1/crates/demo/0.1.0/readme",
+                "updated_at": "2024-08-29T10:03:17.004311Z",
+                "created_at": "2024-08-29T10:03:17.004311Z",
+                "downloads": 98,
+                "features": {},
+                "yanked": false,
+                "yank_message": null,
+                "license": null,
+                "links": {
+                    "dependencies": "/api/v1/crates/demo/0.1.0/dependencies",
+                    "version_downloads": "/api/v1/crates/demo/0.1.0/downloads",
+                    "authors": "/api/v1/crates/demo/0.1.0/authors"
+                },
+                "crate_size": null,
+                "published_by": null,
+                "audit_actions": [],
+                "checksum": "c3d2e1f0a9b8c7d6e5f4a3b2c1d0e9f8a7b6c5d4e3f2a1b0c9d8e7f6a5b4c3d2",
+                "rust_version": null,
+                "has_lib": true,
+                "bin_names": [],
+                "edition": null
+            }
+        ],
+        "meta": {"total": 3, "next_page": null}
+    }"#;
+
+    #[test]
+    fn recorded_versions_payload_parses_all_versions() {
+        let response: super::VersionsResponse =
+            serde_json::from_str(RECORDED_VERSIONS_PAYLOAD).expect("parse");
+        let nums: Vec<&str> = response.versions.iter().map(|v| v.num.as_str()).collect();
+        assert_eq!(nums, vec!["0.2.0", "0.1.1", "0.1.0"]);
+        assert_eq!(
+            response.versions[1],
+            VersionInfo {
+                num: "0.1.1".to_string(),
+                yanked: true,
+                created_at: "2024-08-30T16:45:02.551203Z".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn recorded_versions_payload_parses_details() {
+        let response: super::VersionsResponse<VersionDetail> =
+            serde_json::from_str(RECORDED_VERSIONS_PAYLOAD).expect("parse");
+        assert_eq!(
+            response.versions,
+            vec![
+                VersionDetail {
+                    num: "0.2.0".to_string(),
+                    yanked: false,
+                    downloads: 1873,
+                    created_at: "2024-09-03T11:12:40.120947Z".to_string(),
+                    license: Some("MIT OR Apache-2.0".to_string()),
+                },
+                VersionDetail {
+                    num: "0.1.1".to_string(),
+                    yanked: true,
+                    downloads: 212,
+                    created_at: "2024-08-30T16:45:02.551203Z".to_string(),
+                    license: Some("MIT OR Apache-2.0".to_string()),
+                },
+                VersionDetail {
+                    num: "0.1.0".to_string(),
+                    yanked: false,
+                    downloads: 98,
+                    created_at: "2024-08-29T10:03:17.004311Z".to_string(),
+                    license: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn version_detail_defaults_missing_downloads_and_license() {
+        let detail: VersionDetail = serde_json::from_str(
+            r#"{"num":"1.0.0","yanked":false,"created_at":"2024-01-01T00:00:00Z"}"#,
+        )
+        .expect("parse");
+        assert_eq!(detail.downloads, 0);
+        assert_eq!(detail.license, None);
+    }
+
+    #[test]
+    fn get_versions_detailed_returns_newest_first_and_empty_on_404() {
+        let (server, base) = mock_server();
+        let handle = std::thread::spawn(move || {
+            let req = server.recv().expect("request");
+            assert_eq!(req.url(), "/api/v1/crates/demo/versions");
+            respond(req, 200, RECORDED_VERSIONS_PAYLOAD);
+            respond(server.recv().expect("request"), 404, "");
+        });
+        let client = HttpRegistryClient::new(&base).expect("client");
+        let versions = client.get_versions_detailed("demo").expect("ok");
+        let nums: Vec<&str> = versions.iter().map(|v| v.num.as_str()).collect();
+        assert_eq!(nums, ["0.2.0", "0.1.1", "0.1.0"]);
+        assert_eq!(versions[0].downloads, 1873);
+        assert!(
+            client
+                .get_versions_detailed("missing")
+                .expect("404")
+                .is_empty()
+        );
+        handle.join().expect("join");
+    }
+
+    #[test]
+    fn is_yanked_in_distinguishes_live_yanked_and_missing() {
+        let response: super::VersionsResponse =
+            serde_json::from_str(RECORDED_VERSIONS_PAYLOAD).expect("parse");
+        assert!(!is_yanked_in(&response.versions, "0.2.0"));
+        assert!(is_yanked_in(&response.versions, "0.1.1"));
+        assert!(!is_yanked_in(&response.versions, "9.9.9"));
+    }
+
+    #[test]
+    fn list_versions_returns_versions_on_200() {
+        let (server, base) = mock_server();
+        let handle = std::thread::spawn(move || {
+            let req = server.recv().expect("request");
+            assert_eq!(req.url(), "/api/v1/crates/demo/versions");
+            respond(req, 200, RECORDED_VERSIONS_PAYLOAD);
+        });
+        let client = HttpRegistryClient::new(&base).expect("client");
+        let versions = client.list_versions("demo").expect("ok");
+        assert_eq!(versions.len(), 3);
+        assert!(versions[1].yanked);
+        handle.join().expect("join");
+    }
+
+    #[test]
+    fn list_versions_returns_empty_on_404() {
+        let (server, base) = mock_server();
+        let handle = std::thread::spawn(move || {
+            respond(server.recv().expect("req"), 404, "");
+        });
+        let client = HttpRegistryClient::new(&base).expect("client");
+        assert!(client.list_versions("missing").expect("ok").is_empty());
+        handle.join().expect("join");
+    }
+
+    #[test]
+    fn is_version_yanked_reads_versions_endpoint() {
+        let (server, base) = mock_server();
+        let handle = std::thread::spawn(move || {
+            respond(server.recv().expect("req"), 200, RECORDED_VERSIONS_PAYLOAD);
+        });
+        let client = HttpRegistryClient::new(&base).expect("client");
+        assert!(client.is_version_yanked("demo", "0.1.1").expect("ok"));
+        handle.join().expect("join");
+    }
+
     #[test]
     fn crate_response_parsing() {
         let json = r#"{
@@ -461,7 +1135,9 @@ mod tests {
             }
         });
 
-        let client = HttpRegistryClient::new(&base_url).with_cache_dir(cache_dir);
+        let client = HttpRegistryClient::new(&base_url)
+            .expect("client")
+            .with_cache_dir(cache_dir);
 
         // First call: should fetch and cache
         let content1 = client
@@ -478,6 +1154,214 @@ mod tests {
         handle.join().expect("join");
     }
 
+    #[test]
+    fn in_memory_index_cache_reuses_body_on_304() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tiny_http::{Header, Response, Server, StatusCode};
+
+        let server = Server::http("127.0.0.1:0").expect("server");
+        let base_url = format!("http://{}", server.server_addr());
+        let full_bodies = Arc::new(AtomicUsize::new(0));
+
+        let handle = std::thread::spawn({
+            let full_bodies = full_bodies.clone();
+            move || {
+                for _ in 0..3 {
+                    let req = server.recv().expect("request");
+                    let if_none_match = req
+                        .headers()
+                        .iter()
+                        .find(|h| h.field.equiv("If-None-Match"))
+                        .map(|h| h.value.as_str().to_string());
+                    if if_none_match.as_deref() == Some("\"v1\"") {
+                        req.respond(Response::from_string("").with_status_code(StatusCode(304)))
+                            .expect("respond 304");
+                    } else {
+                        full_bodies.fetch_add(1, Ordering::SeqCst);
+                        let resp = Response::from_string("{\"vers\":\"0.1.0\"}")
+                            .with_status_code(StatusCode(200))
+                            .with_header(Header::from_bytes("ETag", "\"v1\"").unwrap());
+                        req.respond(resp).expect("respond 200");
+                    }
+                }
+            }
+        });
+
+        // No cache_dir: only the in-memory cache is in play.
+        let client = HttpRegistryClient::new(&base_url).expect("client");
+        for _ in 0..3 {
+            assert!(
+                client
+                    .is_version_visible_in_sparse_index(&base_url, "demo", "0.1.0")
+                    .expect("visible")
+            );
+        }
+
+        handle.join().expect("join");
+        assert_eq!(full_bodies.load(Ordering::SeqCst), 1);
+        assert_eq!(client.index_cache().len(), 1);
+    }
+
+    #[test]
+    fn in_memory_index_cache_is_shared_by_clones_and_refreshed_on_200() {
+        use tiny_http::{Header, Response, Server, StatusCode};
+
+        let server = Server::http("127.0.0.1:0").expect("server");
+        let base_url = format!("http://{}", server.server_addr());
+
+        let handle = std::thread::spawn(move || {
+            let req = server.recv().expect("request 1");
+            let resp = Response::from_string("{\"vers\":\"0.1.0\"}")
+                .with_status_code(StatusCode(200))
+                .with_header(Header::from_bytes("ETag", "\"v1\"").unwrap());
+            req.respond(resp).expect("respond 1");
+
+            // The clone must send the ETag learned by the original client;
+            // answer with a new body to force a refresh.
+            let req = server.recv().expect("request 2");
+            let if_none_match = req
+                .headers()
+                .iter()
+                .find(|h| h.field.equiv("If-None-Match"))
+                .map(|h| h.value.as_str().to_string());
+            assert_eq!(if_none_match.as_deref(), Some("\"v1\""));
+            let resp = Response::from_string("{\"vers\":\"0.2.0\"}")
+                .with_status_code(StatusCode(200))
+                .with_header(Header::from_bytes("ETag", "\"v2\"").unwrap());
+            req.respond(resp).expect("respond 2");
+        });
+
+        let client = HttpRegistryClient::new(&base_url).expect("client");
+        let clone = client.clone();
+        client
+            .fetch_sparse_index_file(&base_url, "demo")
+            .expect("fetch 1");
+        let refreshed = clone
+            .fetch_sparse_index_file(&base_url, "demo")
+            .expect("fetch 2");
+        assert_eq!(refreshed, "{\"vers\":\"0.2.0\"}");
+
+        handle.join().expect("join");
+        let cached = client.index_cache().get("demo").expect("cached");
+        assert_eq!(cached.etag, "\"v2\"");
+        assert_eq!(cached.body, refreshed);
+    }
+
+    #[test]
+    fn conditional_fetch_reports_not_modified() {
+        let (server, base) = mock_server();
+        let handle = std::thread::spawn(move || {
+            respond(server.recv().expect("req"), 304, "");
+        });
+        let client = HttpRegistryClient::new(&base).expect("client");
+        let outcome = client
+            .fetch_sparse_index_file_conditional(&base, "demo", Some("\"v1\""))
+            .expect("fetch");
+        assert_eq!(outcome, SparseIndexFetch::NotModified);
+        assert!(client.index_cache().is_empty());
+        handle.join().expect("join");
+    }
+
+    // ── owner add/remove (mock) ──────────────────────────────────────
+
+    #[test]
+    fn add_owner_puts_users_and_returns_message() {
+        let (server, base) = mock_server();
+        let handle = std::thread::spawn(move || {
+            let mut req = server.recv().expect("request");
+            assert_eq!(req.method(), &tiny_http::Method::Put);
+            assert_eq!(req.url(), "/api/v1/crates/demo/owners");
+            let auth = req
+                .headers()
+                .iter()
+                .find(|h| h.field.equiv("Authorization"))
+                .map(|h| h.value.as_str().to_string());
+            assert_eq!(auth.as_deref(), Some("token-abc"));
+            let mut body = String::new();
+            req.as_reader().read_to_string(&mut body).expect("body");
+            let parsed: serde_json::Value = serde_json::from_str(&body).expect("json");
+            assert_eq!(parsed, serde_json::json!({"users": ["bob"]}));
+            respond(
+                req,
+                200,
+                r#"{"ok":true,"msg":"user bob has been invited to be an owner of crate demo"}"#,
+            );
+        });
+        let client = HttpRegistryClient::new(&base).expect("client");
+        let msg = client.add_owner("demo", "bob", "token-abc").expect("ok");
+        assert_eq!(
+            msg,
+            "user bob has been invited to be an owner of crate demo"
+        );
+        handle.join().expect("join");
+    }
+
+    #[test]
+    fn remove_owner_sends_delete() {
+        let (server, base) = mock_server();
+        let handle = std::thread::spawn(move || {
+            let req = server.recv().expect("request");
+            assert_eq!(req.method(), &tiny_http::Method::Delete);
+            assert_eq!(req.url(), "/api/v1/crates/demo/owners");
+            respond(
+                req,
+                200,
+                r#"{"ok":true,"msg":"owners successfully removed"}"#,
+            );
+        });
+        let client = HttpRegistryClient::new(&base).expect("client");
+        let msg = client.remove_owner("demo", "bob", "token").expect("ok");
+        assert_eq!(msg, "owners successfully removed");
+        handle.join().expect("join");
+    }
+
+    #[test]
+    fn add_owner_forbidden_surfaces_detail() {
+        let (server, base) = mock_server();
+        let handle = std::thread::spawn(move || {
+            respond(
+                server.recv().expect("req"),
+                403,
+                r#"{"errors":[{"detail":"only owners have permission to modify owners"}]}"#,
+            );
+        });
+        let client = HttpRegistryClient::new(&base).expect("client");
+        let err = client.add_owner("demo", "bob", "token").unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("forbidden when changing owners of demo"));
+        assert!(msg.contains("only owners have permission to modify owners"));
+        handle.join().expect("join");
+    }
+
+    #[test]
+    fn remove_owner_not_found_is_clear_error() {
+        let (server, base) = mock_server();
+        let handle = std::thread::spawn(move || {
+            respond(server.recv().expect("req"), 404, "");
+        });
+        let client = HttpRegistryClient::new(&base).expect("client");
+        let err = client.remove_owner("nope", "bob", "token").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "crate or user not found when changing owners of nope"
+        );
+        handle.join().expect("join");
+    }
+
+    #[test]
+    fn owner_change_ok_false_is_error() {
+        let err = owner_change_result(
+            reqwest::StatusCode::OK,
+            &HeaderMap::new(),
+            r#"{"ok":false,"msg":"nothing changed"}"#,
+            "demo",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("nothing changed"));
+        assert_eq!(err.classify(), shipper_retry::ErrorClass::Permanent);
+    }
+
     // ── Helper: spin up a tiny_http mock server ──────────────────────
 
     fn mock_server() -> (tiny_http::Server, String) {
@@ -496,26 +1380,107 @@ mod tests {
 
     #[test]
     fn url_multiple_trailing_slashes_stripped() {
-        let client = HttpRegistryClient::new("https://example.com///");
+        let client = HttpRegistryClient::new("https://example.com///").expect("client");
         assert_eq!(client.base_url(), "https://example.com");
     }
 
     #[test]
     fn url_no_trailing_slash_unchanged() {
-        let client = HttpRegistryClient::new("https://example.com");
+        let client = HttpRegistryClient::new("https://example.com").expect("client");
         assert_eq!(client.base_url(), "https://example.com");
     }
 
     #[test]
     fn default_timeout_is_30s() {
-        let client = HttpRegistryClient::crates_io();
+        let client = HttpRegistryClient::crates_io().expect("client");
         assert_eq!(client.timeout, Duration::from_secs(DEFAULT_TIMEOUT_SECS));
     }
 
+    #[test]
+    fn rate_limit_throttles_requests_across_clones() {
+        // 600 rpm = 10 rps with a burst of 10: the first ten requests pass
+        // immediately and each of the remaining four waits ~100ms.
+        const REQUESTS: usize = 14;
+        let (server, base) = mock_server();
+        let handle = std::thread::spawn(move || {
+            for _ in 0..REQUESTS {
+                respond(server.recv().expect("req"), 200, "{}");
+            }
+        });
+
+        let client = HttpRegistryClient::new(&base)
+            .expect("client")
+            .with_rate_limit(600);
+        let clone = client.clone();
+        let start = std::time::Instant::now();
+        let worker = std::thread::spawn(move || {
+            for _ in 0..REQUESTS / 2 {
+                assert!(clone.crate_exists("demo").expect("ok"));
+            }
+        });
+        for _ in 0..REQUESTS / 2 {
+            assert!(client.crate_exists("demo").expect("ok"));
+        }
+        worker.join().expect("worker");
+        let elapsed = start.elapsed();
+
+        handle.join().expect("join");
+        assert!(
+            elapsed >= Duration::from_millis(350),
+            "expected throttling, finished in {elapsed:?}"
+        );
+    }
+
+    #[test]
+    fn with_proxy_routes_requests_through_proxy() {
+        // The mock server plays the proxy: a proxied plain-HTTP request
+        // arrives with the absolute target URL in the request line.
+        let (proxy, proxy_url) = mock_server();
+        let handle = std::thread::spawn(move || {
+            let req = proxy.recv().expect("req");
+            assert_eq!(req.url(), "http://registry.invalid/api/v1/crates/demo");
+            respond(req, 200, "{}");
+        });
+
+        let client = HttpRegistryClient::new("http://registry.invalid")
+            .expect("client")
+            .with_proxy(&proxy_url)
+            .expect("valid proxy");
+        assert_eq!(client.proxy(), &ProxyConfig::all(&proxy_url));
+        assert!(client.crate_exists("demo").expect("ok"));
+        handle.join().expect("join");
+    }
+
+    #[test]
+    fn new_rejects_invalid_proxy_env_instead_of_connecting_directly() {
+        let proxy = ProxyConfig::from_lookup(|name| {
+            (name == "HTTPS_PROXY").then(|| "not a url".to_string())
+        });
+        let err = HttpRegistryClient::with_proxy_config(CRATES_IO_API, proxy).unwrap_err();
+        assert!(format!("{err:#}").contains("invalid HTTPS proxy URL: not a url"));
+    }
+
+    #[test]
+    fn with_proxy_rejects_invalid_url() {
+        let err = HttpRegistryClient::crates_io()
+            .expect("client")
+            .with_proxy("not a url")
+            .unwrap_err();
+        assert!(format!("{err:#}").contains("invalid HTTPS proxy URL: not a url"));
+        assert!(
+            HttpRegistryClient::crates_io()
+                .expect("client")
+                .with_proxy("socks5://127.0.0.1:1080")
+                .is_ok()
+        );
+    }
+
     #[test]
     fn with_cache_dir_sets_cache() {
         let td = tempfile::tempdir().expect("tempdir");
-        let client = HttpRegistryClient::crates_io().with_cache_dir(td.path().to_path_buf());
+        let client = HttpRegistryClient::crates_io()
+            .expect("client")
+            .with_cache_dir(td.path().to_path_buf());
         assert_eq!(client.cache_dir, Some(td.path().to_path_buf()));
     }
 
@@ -529,7 +1494,7 @@ mod tests {
             assert_eq!(req.url(), "/api/v1/crates/serde");
             respond(req, 200, r#"{"crate":{}}"#);
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         assert!(client.crate_exists("serde").expect("ok"));
         handle.join().expect("join");
     }
@@ -540,7 +1505,7 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 404, "");
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         assert!(!client.crate_exists("nonexistent").expect("ok"));
         handle.join().expect("join");
     }
@@ -551,12 +1516,56 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 500, "");
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         let err = client.crate_exists("bad").unwrap_err();
         assert!(err.to_string().contains("unexpected status code"));
         handle.join().expect("join");
     }
 
+    #[test]
+    fn crate_exists_classifies_rate_limit_with_retry_after() {
+        let (server, base) = mock_server();
+        let handle = std::thread::spawn(move || {
+            let resp = tiny_http::Response::from_string("")
+                .with_status_code(tiny_http::StatusCode(429))
+                .with_header(
+                    tiny_http::Header::from_bytes(&b"Retry-After"[..], &b"7"[..]).expect("header"),
+                );
+            server.recv().expect("req").respond(resp).expect("respond");
+        });
+        let client = HttpRegistryClient::new(&base).expect("client");
+        let err = client.crate_exists("busy").unwrap_err();
+        assert!(matches!(
+            err,
+            RegistryError::RateLimited {
+                retry_after: Some(d)
+            } if d == Duration::from_secs(7)
+        ));
+        assert_eq!(err.classify(), shipper_retry::ErrorClass::Retryable);
+        handle.join().expect("join");
+    }
+
+    #[test]
+    fn connection_refused_is_retryable_network_error() {
+        let client = HttpRegistryClient::new("http://127.0.0.1:1").expect("client");
+        let err = client.crate_exists("demo").unwrap_err();
+        assert!(matches!(err, RegistryError::Network { timeout: false, .. }));
+        assert_eq!(err.classify(), shipper_retry::ErrorClass::Retryable);
+    }
+
+    #[test]
+    fn owners_forbidden_is_permanent() {
+        let (server, base) = mock_server();
+        let handle = std::thread::spawn(move || {
+            respond(server.recv().expect("req"), 403, "");
+        });
+        let client = HttpRegistryClient::new(&base).expect("client");
+        let err = client.list_owners("demo", "token").unwrap_err();
+        assert!(matches!(err, RegistryError::Forbidden(_)));
+        assert_eq!(err.classify(), shipper_retry::ErrorClass::Permanent);
+        handle.join().expect("join");
+    }
+
     // ── version_exists (mock) ────────────────────────────────────────
 
     #[test]
@@ -567,7 +1576,7 @@ mod tests {
             assert_eq!(req.url(), "/api/v1/crates/serde/1.0.0");
             respond(req, 200, "{}");
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         assert!(client.version_exists("serde", "1.0.0").expect("ok"));
         handle.join().expect("join");
     }
@@ -578,7 +1587,7 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 404, "");
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         assert!(!client.version_exists("serde", "99.0.0").expect("ok"));
         handle.join().expect("join");
     }
@@ -589,7 +1598,7 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 503, "");
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         let err = client.version_exists("x", "0.1.0").unwrap_err();
         assert!(err.to_string().contains("unexpected status code"));
         handle.join().expect("join");
@@ -611,7 +1620,7 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 200, body);
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         let info = client.get_crate_info("demo").expect("ok").expect("Some");
         assert_eq!(info.name, "demo");
         assert_eq!(info.newest_version, "2.0.0");
@@ -626,7 +1635,7 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 404, "");
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         assert!(client.get_crate_info("nope").expect("ok").is_none());
         handle.join().expect("join");
     }
@@ -637,7 +1646,7 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 500, "");
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         let err = client.get_crate_info("bad").unwrap_err();
         assert!(err.to_string().contains("unexpected status code"));
         handle.join().expect("join");
@@ -649,7 +1658,7 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 200, "NOT JSON");
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         let err = client.get_crate_info("bad").unwrap_err();
         assert!(err.to_string().contains("failed to parse crate response"));
         handle.join().expect("join");
@@ -666,7 +1675,7 @@ mod tests {
             assert_eq!(req.url(), "/api/v1/crates/demo/owners");
             respond(req, 200, body);
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         let owners = client.get_owners("demo").expect("ok");
         assert_eq!(owners.len(), 1);
         assert_eq!(owners[0].login, "alice");
@@ -679,7 +1688,7 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 404, "");
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         let owners = client.get_owners("nonexistent").expect("ok");
         assert!(owners.is_empty());
         handle.join().expect("join");
@@ -699,7 +1708,7 @@ mod tests {
             assert_eq!(auth.value.as_str(), "my-token");
             respond(req, 200, body);
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         let resp = client.list_owners("demo", "my-token").expect("ok");
         assert_eq!(resp.users.len(), 1);
         assert_eq!(resp.users[0].login, "bob");
@@ -712,7 +1721,7 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 403, "");
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         let err = client.list_owners("demo", "bad-token").unwrap_err();
         assert!(err.to_string().contains("forbidden"));
         handle.join().expect("join");
@@ -724,7 +1733,7 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 401, "");
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         let err = client.list_owners("demo", "expired").unwrap_err();
         assert!(err.to_string().contains("forbidden"));
         handle.join().expect("join");
@@ -736,7 +1745,7 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 404, "");
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         let err = client.list_owners("nope", "token").unwrap_err();
         assert!(err.to_string().contains("crate not found"));
         handle.join().expect("join");
@@ -748,7 +1757,7 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 502, "");
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         let err = client.list_owners("demo", "tok").unwrap_err();
         assert!(err.to_string().contains("unexpected status"));
         handle.join().expect("join");
@@ -761,7 +1770,7 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 200, body);
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         assert!(client.is_owner("demo", "carol").expect("ok"));
         handle.join().expect("join");
     }
@@ -773,7 +1782,7 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 200, body);
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         assert!(!client.is_owner("demo", "dave").expect("ok"));
         handle.join().expect("join");
     }
@@ -786,7 +1795,7 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 404, "");
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         let err = client.fetch_sparse_index_file(&base, "xy").unwrap_err();
         assert!(err.to_string().contains("index file not found"));
         handle.join().expect("join");
@@ -798,7 +1807,7 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 502, "");
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         let err = client.fetch_sparse_index_file(&base, "xy").unwrap_err();
         assert!(err.to_string().contains("unexpected status"));
         handle.join().expect("join");
@@ -811,7 +1820,7 @@ mod tests {
             respond(server.recv().expect("req"), 304, "");
         });
         // No cache_dir set
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         let err = client.fetch_sparse_index_file(&base, "ab").unwrap_err();
         assert!(err.to_string().contains("304 Not Modified"));
         handle.join().expect("join");
@@ -825,7 +1834,7 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 200, body);
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         assert!(
             client
                 .is_version_visible_in_sparse_index(&base, "demo", "0.1.0")
@@ -841,7 +1850,7 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 200, body);
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         assert!(
             !client
                 .is_version_visible_in_sparse_index(&base, "demo", "9.9.9")
@@ -872,8 +1881,98 @@ mod tests {
         handle.join().expect("join");
     }
 
+    fn respond_if_authorized(req: tiny_http::Request, token: &str) {
+        let authorized = req
+            .headers()
+            .iter()
+            .any(|h| h.field.equiv("Authorization") && h.value.as_str() == token);
+        if authorized {
+            respond(req, 200, "{}");
+        } else {
+            respond(req, 401, "{\"errors\":[{\"detail\":\"unauthorized\"}]}");
+        }
+    }
+
+    #[test]
+    fn is_version_visible_with_token_sends_authorization() {
+        let (server, base) = mock_server();
+        let handle = std::thread::spawn(move || {
+            respond_if_authorized(server.recv().expect("req"), "secret");
+        });
+        assert!(
+            is_version_visible_with_token(&base, "private", "1.0.0", Some("secret")).expect("ok")
+        );
+        handle.join().expect("join");
+    }
+
+    #[test]
+    fn is_version_visible_without_token_is_unauthorized() {
+        let (server, base) = mock_server();
+        let handle = std::thread::spawn(move || {
+            respond_if_authorized(server.recv().expect("req"), "secret");
+        });
+        assert!(is_version_visible(&base, "private", "1.0.0").is_err());
+        handle.join().expect("join");
+    }
+
+    #[test]
+    fn is_crate_visible_with_token_sends_authorization() {
+        let (server, base) = mock_server();
+        let handle = std::thread::spawn(move || {
+            respond_if_authorized(server.recv().expect("req"), "secret");
+        });
+        assert!(is_crate_visible_with_token(&base, "private", Some("secret")).expect("ok"));
+        handle.join().expect("join");
+    }
+
+    #[test]
+    fn crate_exists_with_none_token_is_unauthorized() {
+        let (server, base) = mock_server();
+        let handle = std::thread::spawn(move || {
+            respond_if_authorized(server.recv().expect("req"), "secret");
+        });
+        let client = HttpRegistryClient::new(&base).expect("client");
+        assert!(client.crate_exists_with_token("private", None).is_err());
+        handle.join().expect("join");
+    }
+
     // ── timeout handling ─────────────────────────────────────────────
 
+    #[test]
+    fn with_timeouts_sets_connect_and_read_separately() {
+        let client = HttpRegistryClient::new("https://example.com")
+            .expect("client")
+            .with_timeouts(Duration::from_secs(5), Duration::from_mins(2))
+            .expect("distinct timeouts build");
+        assert_eq!(
+            client.timeouts(),
+            (Duration::from_secs(5), Duration::from_mins(2))
+        );
+
+        let client = client
+            .with_timeout(Duration::from_secs(10))
+            .expect("client");
+        assert_eq!(
+            client.timeouts(),
+            (Duration::from_secs(10), Duration::from_secs(10))
+        );
+    }
+
+    #[test]
+    fn with_timeouts_rejects_zero_durations() {
+        let err = HttpRegistryClient::new("https://example.com")
+            .expect("client")
+            .with_timeouts(Duration::ZERO, Duration::from_secs(30))
+            .expect_err("zero connect");
+        assert!(err.to_string().contains("connect timeout"));
+
+        let err = HttpRegistryClient::new("https://example.com")
+            .expect("client")
+            .with_timeouts(Duration::from_secs(5), Duration::ZERO)
+            .expect_err("zero read");
+        assert!(err.to_string().contains("read timeout"));
+    }
+
     #[test]
     fn timeout_triggers_on_slow_server() {
         let (server, base) = mock_server();
@@ -883,7 +1982,10 @@ mod tests {
             std::thread::sleep(Duration::from_secs(3));
             let _ = req.respond(tiny_http::Response::from_string("{}"));
         });
-        let client = HttpRegistryClient::new(&base).with_timeout(Duration::from_millis(200));
+        let client = HttpRegistryClient::new(&base)
+            .expect("client")
+            .with_timeout(Duration::from_millis(200))
+            .expect("client");
         let result = client.crate_exists("slow");
         assert!(result.is_err());
         handle.join().expect("join");
@@ -894,7 +1996,7 @@ mod tests {
     #[test]
     fn crate_exists_handles_connection_refused() {
         // Use a port that is very unlikely to be listening
-        let client = HttpRegistryClient::new("http://127.0.0.1:1");
+        let client = HttpRegistryClient::new("http://127.0.0.1:1").expect("client");
         let result = client.crate_exists("anything");
         assert!(result.is_err());
         assert!(
@@ -961,19 +2063,22 @@ mod tests {
 
     #[test]
     fn sparse_index_path_short_crate() {
-        assert_eq!(sparse_index_path("a"), "1/a");
-        assert_eq!(sparse_index_path("ab"), "2/ab");
+        assert_eq!(sparse_index_path("a").expect("valid name"), "1/a");
+        assert_eq!(sparse_index_path("ab").expect("valid name"), "2/ab");
     }
 
     #[test]
     fn sparse_index_path_three_char() {
-        assert_eq!(sparse_index_path("abc"), "3/a/abc");
+        assert_eq!(sparse_index_path("abc").expect("valid name"), "3/a/abc");
     }
 
     #[test]
     fn sparse_index_path_four_plus_char() {
-        assert_eq!(sparse_index_path("demo"), "de/mo/demo");
-        assert_eq!(sparse_index_path("serde"), "se/rd/serde");
+        assert_eq!(sparse_index_path("demo").expect("valid name"), "de/mo/demo");
+        assert_eq!(
+            sparse_index_path("serde").expect("valid name"),
+            "se/rd/serde"
+        );
     }
 
     // ── constants ────────────────────────────────────────────────────
@@ -1072,14 +2177,14 @@ mod tests {
 
     #[test]
     fn snapshot_url_construction_crate() {
-        let client = HttpRegistryClient::new("https://crates.io");
+        let client = HttpRegistryClient::new("https://crates.io").expect("client");
         let url = format!("{}/api/v1/crates/{}", client.base_url(), "my-crate");
         insta::assert_snapshot!("url_crate", url);
     }
 
     #[test]
     fn snapshot_url_construction_version() {
-        let client = HttpRegistryClient::new("https://crates.io");
+        let client = HttpRegistryClient::new("https://crates.io").expect("client");
         let url = format!(
             "{}/api/v1/crates/{}/{}",
             client.base_url(),
@@ -1091,30 +2196,45 @@ mod tests {
 
     #[test]
     fn snapshot_url_construction_owners() {
-        let client = HttpRegistryClient::new("https://crates.io");
+        let client = HttpRegistryClient::new("https://crates.io").expect("client");
         let url = format!("{}/api/v1/crates/{}/owners", client.base_url(), "my-crate");
         insta::assert_snapshot!("url_owners", url);
     }
 
     #[test]
     fn snapshot_url_construction_custom_registry() {
-        let client = HttpRegistryClient::new("https://my-registry.example.com/");
+        let client = HttpRegistryClient::new("https://my-registry.example.com/").expect("client");
         let url = format!("{}/api/v1/crates/{}", client.base_url(), "private-lib");
         insta::assert_snapshot!("url_custom_registry", url);
     }
 
     #[test]
     fn snapshot_sparse_index_paths() {
-        insta::assert_snapshot!("sparse_path_1char", sparse_index_path("a"));
-        insta::assert_snapshot!("sparse_path_2char", sparse_index_path("ab"));
-        insta::assert_snapshot!("sparse_path_3char", sparse_index_path("abc"));
-        insta::assert_snapshot!("sparse_path_4char", sparse_index_path("demo"));
-        insta::assert_snapshot!("sparse_path_long", sparse_index_path("serde_json"));
+        insta::assert_snapshot!(
+            "sparse_path_1char",
+            sparse_index_path("a").expect("valid name")
+        );
+        insta::assert_snapshot!(
+            "sparse_path_2char",
+            sparse_index_path("ab").expect("valid name")
+        );
+        insta::assert_snapshot!(
+            "sparse_path_3char",
+            sparse_index_path("abc").expect("valid name")
+        );
+        insta::assert_snapshot!(
+            "sparse_path_4char",
+            sparse_index_path("demo").expect("valid name")
+        );
+        insta::assert_snapshot!(
+            "sparse_path_long",
+            sparse_index_path("serde_json").expect("valid name")
+        );
     }
 
     #[test]
     fn snapshot_error_connection_refused() {
-        let client = HttpRegistryClient::new("http://127.0.0.1:1");
+        let client = HttpRegistryClient::new("http://127.0.0.1:1").expect("client");
         let err = client.crate_exists("anything").unwrap_err();
         insta::assert_snapshot!("error_connection_refused", err.to_string());
     }
@@ -1125,7 +2245,7 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 500, "");
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         let err = client.crate_exists("bad").unwrap_err();
         insta::assert_snapshot!("error_unexpected_status", err.to_string());
         handle.join().expect("join");
@@ -1137,7 +2257,7 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 403, "");
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         let err = client.list_owners("demo", "bad-token").unwrap_err();
         insta::assert_snapshot!("error_owners_forbidden", err.to_string());
         handle.join().expect("join");
@@ -1149,7 +2269,7 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 404, "");
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         let err = client.list_owners("nope", "token").unwrap_err();
         insta::assert_snapshot!("error_owners_not_found", err.to_string());
         handle.join().expect("join");
@@ -1187,21 +2307,21 @@ mod tests {
                 slashes in "/{0,10}",
             ) {
                 let input = format!("{base}{slashes}");
-                let client = HttpRegistryClient::new(&input);
+                let client = HttpRegistryClient::new(&input).expect("client");
                 let url = client.base_url();
                 prop_assert!(!url.ends_with('/'), "URL still has trailing slash: {url}");
             }
 
             #[test]
             fn sparse_index_path_is_deterministic(name in crate_name_strategy()) {
-                let a = sparse_index_path(&name);
-                let b = sparse_index_path(&name);
+                let a = sparse_index_path(&name).expect("valid name");
+                let b = sparse_index_path(&name).expect("valid name");
                 prop_assert_eq!(&a, &b, "sparse_index_path not deterministic for {}", name);
             }
 
             #[test]
             fn sparse_index_path_is_lowercase(name in crate_name_strategy()) {
-                let path = sparse_index_path(&name);
+                let path = sparse_index_path(&name).expect("valid name");
                 let path_lower = path.to_ascii_lowercase();
                 prop_assert_eq!(path, path_lower,
                     "sparse_index_path should be all lowercase for {}", name);
@@ -1232,7 +2352,7 @@ mod tests {
             fn version_string_in_url_construction(
                 version in version_strategy(),
             ) {
-                let client = HttpRegistryClient::new("https://example.com");
+                let client = HttpRegistryClient::new("https://example.com").expect("client");
                 let expected = format!("https://example.com/api/v1/crates/test-crate/{version}");
                 let url = format!("{}/api/v1/crates/{}/{}", client.base_url(), "test-crate", version);
                 prop_assert_eq!(url, expected);
@@ -1273,7 +2393,7 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 403, "");
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         let err = client.get_owners("demo").unwrap_err();
         assert!(err.to_string().contains("forbidden"));
         handle.join().expect("join");
@@ -1288,7 +2408,7 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 403, "");
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         let err = client.is_owner("demo", "alice").unwrap_err();
         assert!(err.to_string().contains("forbidden"));
         handle.join().expect("join");
@@ -1303,7 +2423,7 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 404, "");
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         let err = client
             .is_version_visible_in_sparse_index(&base, "demo", "1.0.0")
             .unwrap_err();
@@ -1319,7 +2439,7 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 401, "");
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         let err = client.version_exists("x", "0.1.0").unwrap_err();
         assert!(err.to_string().contains("unexpected status code"));
         handle.join().expect("join");
@@ -1333,7 +2453,7 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 401, "");
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         let err = client.crate_exists("x").unwrap_err();
         assert!(err.to_string().contains("unexpected status code"));
         handle.join().expect("join");
@@ -1347,7 +2467,7 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 429, "");
         });
-        let client = HttpRegistryClient::new(&base);
+        let client = HttpRegistryClient::new(&base).expect("client");
         let err = client.get_crate_info("x").unwrap_err();
         assert!(err.to_string().contains("unexpected status code"));
         handle.join().expect("join");
@@ -1368,7 +2488,9 @@ mod tests {
             respond(server.recv().expect("req"), 200, "{\"vers\":\"0.1.0\"}");
         });
 
-        let client = HttpRegistryClient::new(&base).with_cache_dir(cache_dir.clone());
+        let client = HttpRegistryClient::new(&base)
+            .expect("client")
+            .with_cache_dir(cache_dir.clone());
         let content = client
             .fetch_sparse_index_file(&base, "demo")
             .expect("fetch");
@@ -1395,7 +2517,10 @@ mod tests {
         let handle = std::thread::spawn(move || {
             respond(server.recv().expect("req"), 200, "{}");
         });
-        let client = HttpRegistryClient::new(&base).with_timeout(Duration::from_secs(5));
+        let client = HttpRegistryClient::new(&base)
+            .expect("client")
+            .with_timeout(Duration::from_secs(5))
+            .expect("client");
         assert_eq!(client.timeout, Duration::from_secs(5));
         assert!(client.crate_exists("any").expect("ok"));
         handle.join().expect("join");
@@ -0,0 +1,109 @@
+//! In-memory ETag cache for sparse-index files.
+//!
+//! Readiness polling with [`shipper_types::ReadinessMethod::Index`] fetches
+//! the same sparse-index file on every poll. [`IndexCache`] remembers the last
+//! `(etag, body)` pair per crate so the client can send `If-None-Match` and
+//! reuse the cached body when the registry answers `304 Not Modified`.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// A cached sparse-index file together with the ETag it was served with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedIndexFile {
+    /// ETag returned by the registry for this body
+    pub etag: String,
+    /// Raw sparse-index content (line-delimited JSON)
+    pub body: String,
+}
+
+/// Shared in-memory cache of sparse-index files, keyed by crate name.
+///
+/// Cloning the cache (or a client that owns one) shares the underlying map,
+/// so parallel readiness checks reuse each other's entries.
+#[derive(Debug, Clone, Default)]
+pub struct IndexCache {
+    entries: Arc<Mutex<HashMap<String, CachedIndexFile>>>,
+}
+
+impl IndexCache {
+    /// Create an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Look up the cached file for a crate
+    pub fn get(&self, crate_name: &str) -> Option<CachedIndexFile> {
+        self.lock().get(crate_name).cloned()
+    }
+
+    /// Store (or refresh) the cached file for a crate
+    pub fn insert(&self, crate_name: &str, etag: String, body: String) {
+        self.lock()
+            .insert(crate_name.to_string(), CachedIndexFile { etag, body });
+    }
+
+    /// Drop the cached file for a crate
+    pub fn remove(&self, crate_name: &str) {
+        self.lock().remove(crate_name);
+    }
+
+    /// Number of cached crates
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Whether the cache holds no entries
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, CachedIndexFile>> {
+        // A poisoned lock only means another poller panicked mid-insert; the
+        // map itself is still a valid cache.
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_then_get_roundtrips() {
+        let cache = IndexCache::new();
+        assert!(cache.is_empty());
+        cache.insert("demo", "W/\"1\"".to_string(), "body".to_string());
+        assert_eq!(
+            cache.get("demo"),
+            Some(CachedIndexFile {
+                etag: "W/\"1\"".to_string(),
+                body: "body".to_string(),
+            })
+        );
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn insert_refreshes_existing_entry() {
+        let cache = IndexCache::new();
+        cache.insert("demo", "a".to_string(), "old".to_string());
+        cache.insert("demo", "b".to_string(), "new".to_string());
+        let entry = cache.get("demo").expect("entry");
+        assert_eq!(entry.etag, "b");
+        assert_eq!(entry.body, "new");
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn clones_share_entries() {
+        let cache = IndexCache::new();
+        let clone = cache.clone();
+        clone.insert("demo", "e".to_string(), "b".to_string());
+        assert!(cache.get("demo").is_some());
+        cache.remove("demo");
+        assert!(clone.get("demo").is_none());
+    }
+}
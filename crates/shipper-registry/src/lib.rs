@@ -14,6 +14,18 @@
 //! - [`http`] — a lightweight HTTP client [`http::HttpRegistryClient`] that
 //!   takes a bare base-URL string. Intended for callers that do not need the
 //!   full `Registry` context (e.g. the parallel engine helper crate).
+//! - [`error`] — [`RegistryError`], the typed error returned by the
+//!   base-URL clients; [`RegistryError::classify`] maps it onto
+//!   [`shipper_retry::ErrorClass`] for the retry policy.
+//! - [`index_cache`] — [`IndexCache`], the in-memory ETag cache that lets
+//!   repeated sparse-index polls reuse a body on `304 Not Modified`; both
+//!   blocking clients own one.
+//! - [`rate_limit`] — [`RateLimiter`], the shared token bucket enabled with
+//!   `with_rate_limit` on either blocking client.
+//! - [`proxy`] — [`ProxyConfig`], validated HTTP/SOCKS proxy settings read
+//!   from the environment or set with `with_proxy`.
+//! - [`async_http`] — [`AsyncRegistryClient`], the async counterpart of
+//!   [`http::HttpRegistryClient`] for callers already running on a runtime.
 //!
 //! # Example
 //!
@@ -26,17 +38,31 @@
 //! let visible = client.version_exists("serde", "1.0.0").unwrap_or(false);
 //! ```
 
+pub mod async_http;
 pub mod context;
+pub mod error;
 pub mod http;
+pub mod index_cache;
+pub mod proxy;
+pub mod rate_limit;
+pub mod tls;
 
 // Primary public API: the canonical, Registry-aware client.
-pub use context::{Owner, OwnersResponse, RegistryClient};
+pub use context::{Owner, OwnersResponse, OwnershipReport, RegistryClient};
 
 // Lightweight HTTP client for callers that only have a base URL.
 pub use http::HttpRegistryClient;
 
+// Async counterpart of the lightweight client.
+pub use async_http::AsyncRegistryClient;
+
 // Additional types useful to external callers.
-pub use http::{CrateInfo, OwnersApiUser};
+pub use error::{RegistryError, RegistryResult};
+pub use http::{CrateInfo, OwnersApiUser, SparseIndexFetch, VersionDetail, VersionInfo};
+pub use index_cache::{CachedIndexFile, IndexCache};
+pub use proxy::ProxyConfig;
+pub use rate_limit::RateLimiter;
+pub use tls::{TlsConfig, TlsVersion};
 
 /// Default API endpoint for crates.io
 pub const CRATES_IO_API: &str = "https://crates.io";
@@ -44,21 +70,50 @@ pub const CRATES_IO_API: &str = "https://crates.io";
 /// Default timeout for API requests (used by [`HttpRegistryClient`]).
 pub const DEFAULT_TIMEOUT_SECS: u64 = 30;
 
+/// Timeout for [`RegistryClient::ping`], kept well under
+/// [`DEFAULT_TIMEOUT_SECS`] so an unreachable registry fails fast.
+pub const PING_TIMEOUT_SECS: u64 = 5;
+
+/// Most requests [`RegistryClient::check_versions_visible`] has in flight
+/// at once.
+pub const BATCH_CHECK_CONCURRENCY: usize = 8;
+
 /// Default user agent for API requests.
 pub const USER_AGENT: &str = concat!("shipper/", env!("CARGO_PKG_VERSION"));
 
-/// Compute the sparse-index path for a crate name.
-pub fn sparse_index_path(crate_name: &str) -> String {
+pub use shipper_sparse_index::InvalidCrateName;
+
+/// Compute the sparse-index path for a crate name, rejecting invalid names.
+pub fn sparse_index_path(crate_name: &str) -> Result<String, InvalidCrateName> {
     shipper_sparse_index::sparse_index_path(crate_name)
 }
 
+/// Compute the sparse-index path for a crate name already known to be valid.
+pub fn sparse_index_path_unchecked(crate_name: &str) -> String {
+    shipper_sparse_index::sparse_index_path_unchecked(crate_name)
+}
+
 /// Check if a crate version is visible on the registry via its API.
 ///
 /// Convenience wrapper that constructs an [`HttpRegistryClient`] and calls
 /// [`HttpRegistryClient::version_exists`].
 pub fn is_version_visible(base_url: &str, name: &str, version: &str) -> anyhow::Result<bool> {
-    let client = HttpRegistryClient::new(base_url);
-    client.version_exists(name, version)
+    is_version_visible_with_token(base_url, name, version, None)
+}
+
+/// Check if a crate version is visible, authenticating with `token`.
+///
+/// The token is sent as the `Authorization` header so readiness checks work
+/// against private registries that reject anonymous API reads. Pass `None`
+/// for crates.io.
+pub fn is_version_visible_with_token(
+    base_url: &str,
+    name: &str,
+    version: &str,
+    token: Option<&str>,
+) -> anyhow::Result<bool> {
+    let client = HttpRegistryClient::new(base_url)?;
+    Ok(client.version_exists_with_token(name, version, token)?)
 }
 
 /// Check if a crate exists on the registry via its API.
@@ -66,6 +121,17 @@ pub fn is_version_visible(base_url: &str, name: &str, version: &str) -> anyhow::
 /// Convenience wrapper that constructs an [`HttpRegistryClient`] and calls
 /// [`HttpRegistryClient::crate_exists`].
 pub fn is_crate_visible(base_url: &str, name: &str) -> anyhow::Result<bool> {
-    let client = HttpRegistryClient::new(base_url);
-    client.crate_exists(name)
+    is_crate_visible_with_token(base_url, name, None)
+}
+
+/// Check if a crate exists on the registry, authenticating with `token`.
+///
+/// See [`is_version_visible_with_token`].
+pub fn is_crate_visible_with_token(
+    base_url: &str,
+    name: &str,
+    token: Option<&str>,
+) -> anyhow::Result<bool> {
+    let client = HttpRegistryClient::new(base_url)?;
+    Ok(client.crate_exists_with_token(name, token)?)
 }
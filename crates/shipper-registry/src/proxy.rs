@@ -0,0 +1,199 @@
+//! Proxy configuration for registry HTTP clients.
+//!
+//! Corporate networks often require outbound traffic to go through an
+//! HTTP(S) or SOCKS proxy. [`ProxyConfig`] collects the proxy settings — from
+//! the conventional `HTTPS_PROXY` / `HTTP_PROXY` / `NO_PROXY` environment
+//! variables or from an explicit `with_proxy` call — and validates them up
+//! front, so a malformed proxy URL fails client construction instead of being
+//! silently ignored.
+
+use anyhow::{Context, Result};
+
+/// Proxy settings applied when building a registry HTTP client.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProxyConfig {
+    /// Proxy for `https://` requests (`HTTPS_PROXY`)
+    pub https: Option<String>,
+    /// Proxy for `http://` requests (`HTTP_PROXY`)
+    pub http: Option<String>,
+    /// Comma-separated hosts that bypass the proxy (`NO_PROXY`)
+    pub no_proxy: Option<String>,
+}
+
+impl ProxyConfig {
+    /// Route all traffic through `proxy_url` (`http://`, `https://`,
+    /// `socks5://` or `socks5h://`).
+    pub fn all(proxy_url: &str) -> Self {
+        Self {
+            https: Some(proxy_url.to_string()),
+            http: Some(proxy_url.to_string()),
+            no_proxy: None,
+        }
+    }
+
+    /// Read `HTTPS_PROXY`, `HTTP_PROXY` and `NO_PROXY` (upper- or lowercase)
+    /// from the process environment.
+    pub fn from_env() -> Self {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    /// Build a config from an arbitrary variable lookup.
+    ///
+    /// Uppercase names win over lowercase ones; empty values are ignored.
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let read = |upper: &str, lower: &str| {
+            lookup(upper)
+                .or_else(|| lookup(lower))
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        Self {
+            https: read("HTTPS_PROXY", "https_proxy"),
+            http: read("HTTP_PROXY", "http_proxy"),
+            no_proxy: read("NO_PROXY", "no_proxy"),
+        }
+    }
+
+    /// Whether no proxy is configured
+    pub fn is_empty(&self) -> bool {
+        self.https.is_none() && self.http.is_none()
+    }
+
+    /// Validate the configured URLs and turn them into `reqwest` proxies.
+    pub fn to_proxies(&self) -> Result<Vec<reqwest::Proxy>> {
+        let no_proxy = self
+            .no_proxy
+            .as_deref()
+            .and_then(reqwest::NoProxy::from_string);
+        let mut proxies = Vec::new();
+
+        if let Some(url) = &self.https {
+            validate_proxy_url(url, "HTTPS")?;
+            let proxy = reqwest::Proxy::https(url)
+                .with_context(|| format!("invalid HTTPS proxy URL: {url}"))?;
+            proxies.push(proxy.no_proxy(no_proxy.clone()));
+        }
+        if let Some(url) = &self.http {
+            validate_proxy_url(url, "HTTP")?;
+            let proxy = reqwest::Proxy::http(url)
+                .with_context(|| format!("invalid HTTP proxy URL: {url}"))?;
+            proxies.push(proxy.no_proxy(no_proxy.clone()));
+        }
+        Ok(proxies)
+    }
+
+    /// Apply the proxies to a blocking client builder.
+    pub(crate) fn apply_blocking(
+        &self,
+        mut builder: reqwest::blocking::ClientBuilder,
+    ) -> Result<reqwest::blocking::ClientBuilder> {
+        for proxy in self.to_proxies()? {
+            builder = builder.proxy(proxy);
+        }
+        Ok(builder)
+    }
+
+    /// Apply the proxies to an async client builder.
+    pub(crate) fn apply_async(
+        &self,
+        mut builder: reqwest::ClientBuilder,
+    ) -> Result<reqwest::ClientBuilder> {
+        for proxy in self.to_proxies()? {
+            builder = builder.proxy(proxy);
+        }
+        Ok(builder)
+    }
+}
+
+/// `reqwest` accepts scheme-less strings by assuming `http://`; reject
+/// anything that doesn't parse as a URL with a supported proxy scheme so
+/// typos surface immediately.
+fn validate_proxy_url(url: &str, kind: &str) -> Result<()> {
+    let parsed =
+        reqwest::Url::parse(url).with_context(|| format!("invalid {kind} proxy URL: {url}"))?;
+    match parsed.scheme() {
+        "http" | "https" | "socks4" | "socks4a" | "socks5" | "socks5h" => {}
+        other => anyhow::bail!("invalid {kind} proxy URL: {url} (unsupported scheme `{other}`)"),
+    }
+    if parsed.host_str().is_none_or(str::is_empty) {
+        anyhow::bail!("invalid {kind} proxy URL: {url} (missing host)");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn lookup(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |key| vars.get(key).cloned()
+    }
+
+    #[test]
+    fn from_lookup_reads_upper_and_lowercase() {
+        let config = ProxyConfig::from_lookup(lookup(&[
+            ("HTTPS_PROXY", "http://proxy.corp:3128"),
+            ("http_proxy", "http://plain.corp:8080"),
+            ("no_proxy", "localhost,127.0.0.1"),
+        ]));
+        assert_eq!(config.https.as_deref(), Some("http://proxy.corp:3128"));
+        assert_eq!(config.http.as_deref(), Some("http://plain.corp:8080"));
+        assert_eq!(config.no_proxy.as_deref(), Some("localhost,127.0.0.1"));
+    }
+
+    #[test]
+    fn from_lookup_prefers_uppercase_and_skips_empty() {
+        let config = ProxyConfig::from_lookup(lookup(&[
+            ("HTTPS_PROXY", "http://upper:1"),
+            ("https_proxy", "http://lower:1"),
+            ("HTTP_PROXY", "  "),
+        ]));
+        assert_eq!(config.https.as_deref(), Some("http://upper:1"));
+        assert!(config.http.is_none());
+    }
+
+    #[test]
+    fn empty_lookup_is_empty_config() {
+        let config = ProxyConfig::from_lookup(|_| None);
+        assert!(config.is_empty());
+        assert!(config.to_proxies().expect("no proxies").is_empty());
+    }
+
+    #[test]
+    fn valid_http_and_socks_urls_build_proxies() {
+        assert_eq!(
+            ProxyConfig::all("http://proxy.corp:3128")
+                .to_proxies()
+                .expect("http")
+                .len(),
+            2
+        );
+        assert!(
+            ProxyConfig::all("socks5h://127.0.0.1:1080")
+                .to_proxies()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn invalid_urls_are_rejected_with_clear_errors() {
+        for bad in [
+            "not a url",
+            "proxy.corp:3128",
+            "ftp://proxy.corp",
+            "http://",
+        ] {
+            let err = ProxyConfig::all(bad).to_proxies().unwrap_err();
+            assert!(
+                format!("{err:#}").contains("invalid HTTPS proxy URL"),
+                "{bad}: {err:#}"
+            );
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! Client-side rate limiting for registry requests.
+//!
+//! crates.io rate-limits API traffic, and readiness polling plus existence
+//! checks can trip it during a large publish. [`RateLimiter`] is a token
+//! bucket that every outbound registry request passes through; it sleeps the
+//! calling thread until a token is available.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Token-bucket limiter shared by a client and all of its clones.
+///
+/// The bucket refills at `requests_per_minute / 60` tokens per second and
+/// holds at most one second's worth of tokens (never less than one), so short
+/// bursts are allowed but sustained traffic is held to the configured rate.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    requests_per_minute: u32,
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    capacity: f64,
+    tokens: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    /// Create a limiter allowing `requests_per_minute` requests.
+    ///
+    /// A value of `0` is clamped to one request per minute.
+    pub fn new(requests_per_minute: u32) -> Self {
+        let requests_per_minute = requests_per_minute.max(1);
+        let refill_per_sec = f64::from(requests_per_minute) / 60.0;
+        let capacity = refill_per_sec.max(1.0);
+        Self {
+            requests_per_minute,
+            bucket: Arc::new(Mutex::new(Bucket {
+                capacity,
+                tokens: capacity,
+                refill_per_sec,
+                last_refill: Instant::now(),
+            })),
+        }
+    }
+
+    /// Configured limit in requests per minute
+    pub fn requests_per_minute(&self) -> u32 {
+        self.requests_per_minute
+    }
+
+    /// Block until a request may be sent, then consume one token.
+    pub fn acquire(&self) {
+        while let Some(wait) = self.try_acquire() {
+            std::thread::sleep(wait);
+        }
+    }
+
+    /// Consume a token if one is available.
+    ///
+    /// Returns `None` on success, or how long to wait before trying again.
+    pub fn try_acquire(&self) -> Option<Duration> {
+        let mut bucket = self
+            .bucket
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * bucket.refill_per_sec).min(bucket.capacity);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            None
+        } else {
+            let missing = 1.0 - bucket.tokens;
+            Some(Duration::from_secs_f64(missing / bucket.refill_per_sec))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn limiter_is_send_and_sync() {
+        assert_send_sync::<RateLimiter>();
+    }
+
+    #[test]
+    fn zero_is_clamped_to_one_per_minute() {
+        assert_eq!(RateLimiter::new(0).requests_per_minute(), 1);
+    }
+
+    #[test]
+    fn burst_is_one_second_of_tokens() {
+        // 600 rpm = 10 rps, so ten requests pass immediately.
+        let limiter = RateLimiter::new(600);
+        for _ in 0..10 {
+            assert!(limiter.try_acquire().is_none());
+        }
+        let wait = limiter.try_acquire().expect("bucket drained");
+        assert!(wait <= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn slow_limits_still_allow_one_request() {
+        let limiter = RateLimiter::new(1);
+        assert!(limiter.try_acquire().is_none());
+        let wait = limiter.try_acquire().expect("bucket drained");
+        assert!(wait > Duration::from_secs(50));
+    }
+
+    #[test]
+    fn clones_share_the_bucket() {
+        let limiter = RateLimiter::new(60);
+        let clone = limiter.clone();
+        assert!(limiter.try_acquire().is_none());
+        assert!(clone.try_acquire().is_some());
+    }
+}
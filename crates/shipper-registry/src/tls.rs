@@ -0,0 +1,93 @@
+//! TLS hardening options for registry HTTP clients.
+//!
+//! By default clients negotiate whatever TLS version `rustls` offers and
+//! trust the bundled web PKI roots. Regulated environments can raise the
+//! version floor and pin the registry's certificate instead.
+
+use anyhow::{Context, Result};
+
+pub use reqwest::tls::Version as TlsVersion;
+
+/// TLS settings applied when building a registry HTTP client.
+#[derive(Debug, Clone, Default)]
+pub struct TlsConfig {
+    /// Lowest TLS version to negotiate; `None` keeps the backend default.
+    pub min_version: Option<TlsVersion>,
+    /// DER-encoded certificate to trust instead of the built-in roots.
+    pub pinned_cert_der: Option<Vec<u8>>,
+}
+
+impl TlsConfig {
+    /// Whether the client keeps `reqwest`'s TLS defaults.
+    pub fn is_default(&self) -> bool {
+        self.min_version.is_none() && self.pinned_cert_der.is_none()
+    }
+
+    fn pinned_cert(&self) -> Result<Option<reqwest::Certificate>> {
+        self.pinned_cert_der
+            .as_deref()
+            .map(|der| {
+                reqwest::Certificate::from_der(der).context("invalid pinned certificate DER")
+            })
+            .transpose()
+    }
+
+    /// Apply the settings to a blocking client builder.
+    ///
+    /// A pinned certificate replaces the built-in roots, so only servers
+    /// presenting that certificate (or one it issued) are accepted.
+    /// Malformed certificate bytes are reported when the client is built.
+    pub(crate) fn apply_blocking(
+        &self,
+        mut builder: reqwest::blocking::ClientBuilder,
+    ) -> Result<reqwest::blocking::ClientBuilder> {
+        if let Some(version) = self.min_version {
+            builder = builder.tls_version_min(version);
+        }
+        if let Some(cert) = self.pinned_cert()? {
+            builder = builder.tls_certs_only([cert]);
+        }
+        Ok(builder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RegistryClient;
+    use shipper_types::Registry;
+
+    /// Self-signed P-256 certificate for `registry.test`.
+    const PINNED_CERT_DER: &[u8] = include_bytes!("../tests/data/pinned-cert.der");
+
+    fn client() -> RegistryClient {
+        RegistryClient::new(Registry::crates_io()).expect("client")
+    }
+
+    #[test]
+    fn default_config_leaves_builder_alone() {
+        let tls = TlsConfig::default();
+        assert!(tls.is_default());
+        tls.apply_blocking(reqwest::blocking::Client::builder())
+            .expect("apply")
+            .build()
+            .expect("build");
+    }
+
+    #[test]
+    fn client_builds_with_min_tls_and_valid_pinned_cert() {
+        client()
+            .with_min_tls(TlsVersion::TLS_1_2)
+            .expect("min tls")
+            .with_pinned_cert(PINNED_CERT_DER)
+            .expect("pinned cert");
+    }
+
+    #[test]
+    fn malformed_pinned_cert_fails_construction() {
+        let err = client()
+            .with_pinned_cert(b"not a certificate")
+            .expect_err("malformed cert");
+        assert!(format!("{err:#}").contains("pinned certificate"), "{err:#}");
+    }
+}
@@ -26,8 +26,10 @@
 //! };
 //! ```
 
-use std::time::Duration;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
+use rand::{Rng, RngExt};
 use serde::{Deserialize, Serialize};
 
 /// Strategy type for retry behavior.
@@ -163,6 +165,34 @@ pub enum ErrorClass {
     Permanent,
 }
 
+impl ErrorClass {
+    /// Classify an HTTP status from a registry response.
+    ///
+    /// This is the one status table: `shipper_types::ErrorClass` and the
+    /// registry client's error classification both delegate here.
+    ///
+    /// - 408, 429 (request timeout, rate limit) and any 5xx → `Retryable`
+    /// - any other 4xx (auth, not found, version conflict, ...) → `Permanent`
+    /// - anything else (no error status) → `Ambiguous`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use shipper_retry::ErrorClass;
+    ///
+    /// assert_eq!(ErrorClass::from_http_status(429), ErrorClass::Retryable);
+    /// assert_eq!(ErrorClass::from_http_status(403), ErrorClass::Permanent);
+    /// assert_eq!(ErrorClass::from_http_status(302), ErrorClass::Ambiguous);
+    /// ```
+    pub fn from_http_status(status: u16) -> Self {
+        match status {
+            408 | 429 | 500..=599 => Self::Retryable,
+            400..=499 => Self::Permanent,
+            _ => Self::Ambiguous,
+        }
+    }
+}
+
 /// Per-error-type retry configuration.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PerErrorConfig {
@@ -210,6 +240,38 @@ pub struct PerErrorConfig {
 /// assert_eq!(delay, Duration::from_secs(2));
 /// ```
 pub fn calculate_delay(config: &RetryStrategyConfig, attempt: u32) -> Duration {
+    calculate_delay_with_rng(config, attempt, &mut rand::rng())
+}
+
+/// Like [`calculate_delay`], but draws jitter from `rng`.
+///
+/// Pass a seeded generator to get the same jittered delays on every run.
+///
+/// # Examples
+///
+/// ```
+/// use rand::SeedableRng;
+/// use rand::rngs::StdRng;
+/// use shipper_retry::{RetryStrategyConfig, RetryStrategyType, calculate_delay_with_rng};
+/// use std::time::Duration;
+///
+/// let config = RetryStrategyConfig {
+///     strategy: RetryStrategyType::Constant,
+///     base_delay: Duration::from_secs(10),
+///     max_delay: Duration::from_secs(60),
+///     jitter: 0.5,
+///     max_attempts: 10,
+/// };
+///
+/// let a = calculate_delay_with_rng(&config, 1, &mut StdRng::seed_from_u64(7));
+/// let b = calculate_delay_with_rng(&config, 1, &mut StdRng::seed_from_u64(7));
+/// assert_eq!(a, b);
+/// ```
+pub fn calculate_delay_with_rng<R: Rng + ?Sized>(
+    config: &RetryStrategyConfig,
+    attempt: u32,
+    rng: &mut R,
+) -> Duration {
     let delay = match config.strategy {
         RetryStrategyType::Immediate => Duration::ZERO,
         RetryStrategyType::Exponential => {
@@ -225,7 +287,7 @@ pub fn calculate_delay(config: &RetryStrategyConfig, attempt: u32) -> Duration {
 
     // Apply jitter if enabled
     if config.jitter > 0.0 {
-        apply_jitter(capped, config.jitter)
+        apply_jitter(capped, config.jitter, rng)
     } else {
         capped
     }
@@ -233,10 +295,10 @@ pub fn calculate_delay(config: &RetryStrategyConfig, attempt: u32) -> Duration {
 
 /// Apply jitter to a delay value.
 /// Jitter factor of 0.5 means delay * (0.5 to 1.5).
-fn apply_jitter(delay: Duration, jitter: f64) -> Duration {
+fn apply_jitter<R: Rng + ?Sized>(delay: Duration, jitter: f64, rng: &mut R) -> Duration {
     // Generate a random factor between (1 - jitter) and (1 + jitter)
     let jitter_range = 2.0 * jitter;
-    let random_value: f64 = rand::random();
+    let random_value: f64 = rng.random();
     let random_factor = 1.0 - jitter + (random_value * jitter_range);
     let millis = (delay.as_millis() as f64 * random_factor).round() as u64;
     Duration::from_millis(millis)
@@ -305,15 +367,127 @@ pub fn config_for_error(
     default_config.clone()
 }
 
+/// The stop condition of a retry loop, derived from a [`RetryStrategyConfig`].
+///
+/// # Example
+///
+/// ```
+/// use shipper_retry::{RetryBudget, RetryStrategyConfig, RetryStrategyType};
+/// use std::time::Duration;
+///
+/// let budget = RetryBudget::new(RetryStrategyConfig {
+///     strategy: RetryStrategyType::Constant,
+///     max_attempts: 3,
+///     base_delay: Duration::from_secs(1),
+///     jitter: 0.0,
+///     ..Default::default()
+/// });
+///
+/// assert_eq!(budget.should_retry(1), Some(Duration::from_secs(1)));
+/// assert_eq!(budget.should_retry(2), Some(Duration::from_secs(1)));
+/// assert_eq!(budget.should_retry(3), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryBudget {
+    config: RetryStrategyConfig,
+}
+
+impl RetryBudget {
+    /// Create a budget from a retry configuration.
+    pub fn new(config: RetryStrategyConfig) -> Self {
+        Self { config }
+    }
+
+    /// The configuration this budget draws from.
+    pub fn config(&self) -> &RetryStrategyConfig {
+        &self.config
+    }
+
+    /// Decide what to do after `attempt` (1-indexed) has failed.
+    ///
+    /// Returns the delay before the next attempt while `attempt` is below
+    /// `max_attempts`, or `None` once the budget is exhausted.
+    pub fn should_retry(&self, attempt: u32) -> Option<Duration> {
+        if attempt >= self.config.max_attempts {
+            return None;
+        }
+        Some(calculate_delay(&self.config, attempt))
+    }
+}
+
+impl From<RetryStrategyConfig> for RetryBudget {
+    fn from(config: RetryStrategyConfig) -> Self {
+        Self::new(config)
+    }
+}
+
+/// How often [`RetryExecutor::run_cancellable`] checks its cancel flag while
+/// backing off.
+pub const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Returned by [`RetryExecutor::run_cancellable`] when the cancel flag stopped
+/// the loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryCancelled {
+    /// Attempts made before cancellation.
+    pub attempts: u32,
+}
+
+impl std::fmt::Display for RetryCancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "retry cancelled after {} attempt(s)", self.attempts)
+    }
+}
+
+impl std::error::Error for RetryCancelled {}
+
+/// Failure of [`RetryExecutor::run_cancellable`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RetryError<E> {
+    /// The last attempt failed and the retry budget is exhausted.
+    Failed(E),
+    /// The cancel flag was set before or between attempts.
+    Cancelled(RetryCancelled),
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for RetryError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Failed(err) => err.fmt(f),
+            Self::Cancelled(cancelled) => cancelled.fmt(f),
+        }
+    }
+}
+
+impl<E: std::fmt::Debug + std::fmt::Display> std::error::Error for RetryError<E> {}
+
+/// Sleep for `delay` in [`CANCEL_POLL_INTERVAL`] slices; returns `true` as
+/// soon as `cancel` is set.
+fn sleep_unless_cancelled(delay: Duration, cancel: &AtomicBool) -> bool {
+    let deadline = Instant::now() + delay;
+    loop {
+        if cancel.load(Ordering::Acquire) {
+            return true;
+        }
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return false;
+        }
+        std::thread::sleep(remaining.min(CANCEL_POLL_INTERVAL));
+    }
+}
+
 /// A retry executor that runs a fallible operation with configured retry behavior.
 pub struct RetryExecutor {
-    config: RetryStrategyConfig,
+    budget: RetryBudget,
 }
 
 impl RetryExecutor {
     /// Create a new retry executor with the given configuration.
     pub fn new(config: RetryStrategyConfig) -> Self {
-        Self { config }
+        Self {
+            budget: RetryBudget::new(config),
+        }
     }
 
     /// Create a retry executor from a predefined policy.
@@ -351,11 +525,9 @@ impl RetryExecutor {
             match operation(attempt) {
                 Ok(result) => return Ok(result),
                 Err(e) => {
-                    if attempt >= self.config.max_attempts {
+                    let Some(delay) = self.budget.should_retry(attempt) else {
                         return Err(e);
-                    }
-
-                    let delay = calculate_delay(&self.config, attempt);
+                    };
                     std::thread::sleep(delay);
                     attempt += 1;
                 }
@@ -363,6 +535,44 @@ impl RetryExecutor {
         }
     }
 
+    /// Like [`run`](Self::run), but stops early once `cancel` is set.
+    ///
+    /// The flag is checked before each attempt and every
+    /// [`CANCEL_POLL_INTERVAL`] while backing off, so a shutdown handler
+    /// that sets it interrupts a long backoff promptly with
+    /// [`RetryError::Cancelled`]. An attempt already running is not
+    /// interrupted.
+    pub fn run_cancellable<T, E, F>(
+        &self,
+        mut operation: F,
+        cancel: &AtomicBool,
+    ) -> Result<T, RetryError<E>>
+    where
+        F: FnMut(u32) -> Result<T, E>,
+    {
+        let mut attempt = 1;
+
+        loop {
+            if cancel.load(Ordering::Acquire) {
+                return Err(RetryError::Cancelled(RetryCancelled {
+                    attempts: attempt - 1,
+                }));
+            }
+            match operation(attempt) {
+                Ok(result) => return Ok(result),
+                Err(e) => {
+                    let Some(delay) = self.budget.should_retry(attempt) else {
+                        return Err(RetryError::Failed(e));
+                    };
+                    if sleep_unless_cancelled(delay, cancel) {
+                        return Err(RetryError::Cancelled(RetryCancelled { attempts: attempt }));
+                    }
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
     /// Execute a fallible operation with retry behavior and custom error classification.
     ///
     /// The operation returns a tuple of (result, should_retry).
@@ -377,11 +587,9 @@ impl RetryExecutor {
             match operation(attempt) {
                 Ok((result, _)) => return Ok(result),
                 Err(e) => {
-                    if attempt >= self.config.max_attempts {
+                    let Some(delay) = self.budget.should_retry(attempt) else {
                         return Err(e);
-                    }
-
-                    let delay = calculate_delay(&self.config, attempt);
+                    };
                     std::thread::sleep(delay);
                     attempt += 1;
                 }
@@ -394,6 +602,18 @@ impl RetryExecutor {
 mod tests {
     use super::*;
 
+    #[test]
+    fn error_class_from_http_status_table() {
+        for status in 0u16..=999 {
+            let expected = match status {
+                408 | 429 | 500..=599 => ErrorClass::Retryable,
+                400..=499 => ErrorClass::Permanent,
+                _ => ErrorClass::Ambiguous,
+            };
+            assert_eq!(ErrorClass::from_http_status(status), expected, "{status}");
+        }
+    }
+
     #[test]
     fn test_retry_policy_to_config_default() {
         let config = RetryPolicy::Default.to_config();
@@ -592,6 +812,89 @@ mod tests {
         assert_eq!(result, Err("permanent error"));
     }
 
+    #[test]
+    fn test_run_cancellable_interrupts_long_backoff() {
+        let executor = RetryExecutor::new(RetryStrategyConfig {
+            strategy: RetryStrategyType::Constant,
+            max_attempts: 3,
+            base_delay: Duration::from_mins(1),
+            max_delay: Duration::from_mins(1),
+            jitter: 0.0,
+        });
+        let cancel = AtomicBool::new(false);
+
+        let started = Instant::now();
+        let result = std::thread::scope(|scope| {
+            scope.spawn(|| {
+                std::thread::sleep(Duration::from_millis(50));
+                cancel.store(true, Ordering::Release);
+            });
+            executor.run_cancellable(|_attempt| Err::<(), _>("transient"), &cancel)
+        });
+
+        assert_eq!(
+            result,
+            Err(RetryError::Cancelled(RetryCancelled { attempts: 1 }))
+        );
+        assert!(
+            started.elapsed() < Duration::from_secs(2),
+            "took {:?}",
+            started.elapsed()
+        );
+    }
+
+    #[test]
+    fn test_run_cancellable_behaves_like_run_when_not_cancelled() {
+        let executor = RetryExecutor::new(RetryStrategyConfig {
+            strategy: RetryStrategyType::Immediate,
+            max_attempts: 3,
+            base_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+            jitter: 0.0,
+        });
+        let cancel = AtomicBool::new(false);
+
+        let result = executor.run_cancellable(
+            |attempt| {
+                if attempt < 2 {
+                    Err("transient")
+                } else {
+                    Ok(attempt)
+                }
+            },
+            &cancel,
+        );
+        assert_eq!(result, Ok(2));
+
+        let result = executor.run_cancellable(|_| Err::<(), _>("permanent"), &cancel);
+        assert_eq!(result, Err(RetryError::Failed("permanent")));
+    }
+
+    #[test]
+    fn test_run_cancellable_skips_operation_when_already_cancelled() {
+        let executor = RetryExecutor::from_policy(RetryPolicy::Default);
+        let cancel = AtomicBool::new(true);
+        let mut calls = 0;
+
+        let result = executor.run_cancellable(
+            |_| {
+                calls += 1;
+                Ok::<_, &str>(())
+            },
+            &cancel,
+        );
+
+        assert_eq!(
+            result,
+            Err(RetryError::Cancelled(RetryCancelled { attempts: 0 }))
+        );
+        assert_eq!(calls, 0);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "retry cancelled after 0 attempt(s)"
+        );
+    }
+
     #[test]
     fn test_jitter_applied_correctly() {
         let config = RetryStrategyConfig {
@@ -610,6 +913,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_seeded_jitter_is_reproducible() {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        let config = RetryStrategyConfig {
+            strategy: RetryStrategyType::Exponential,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_mins(1),
+            jitter: 0.5,
+            max_attempts: 10,
+        };
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let delays: Vec<_> = (1..=3)
+            .map(|attempt| calculate_delay_with_rng(&config, attempt, &mut rng))
+            .collect();
+        assert_eq!(
+            delays,
+            [
+                Duration::from_millis(1027),
+                Duration::from_millis(2085),
+                Duration::from_millis(4546),
+            ]
+        );
+
+        let mut again = StdRng::seed_from_u64(42);
+        assert_eq!(calculate_delay_with_rng(&config, 1, &mut again), delays[0]);
+    }
+
+    #[test]
+    fn test_unseeded_jitter_stays_within_bounds() {
+        let config = RetryStrategyConfig {
+            strategy: RetryStrategyType::Linear,
+            base_delay: Duration::from_secs(2),
+            max_delay: Duration::from_mins(1),
+            jitter: 0.25,
+            max_attempts: 10,
+        };
+
+        for attempt in 1..=5 {
+            let nominal = Duration::from_secs(2) * attempt;
+            let delay = calculate_delay_with_rng(&config, attempt, &mut rand::rng());
+            assert!(
+                delay >= nominal.mul_f64(0.75),
+                "{delay:?} < 75% of {nominal:?}"
+            );
+            assert!(
+                delay <= nominal.mul_f64(1.25),
+                "{delay:?} > 125% of {nominal:?}"
+            );
+        }
+    }
+
     // --- Edge-case tests ---
 
     #[test]
@@ -1220,11 +1577,11 @@ mod tests {
         ] {
             let executor = RetryExecutor::from_policy(policy);
             let expected = policy.to_config();
-            assert_eq!(executor.config.strategy, expected.strategy);
-            assert_eq!(executor.config.max_attempts, expected.max_attempts);
-            assert_eq!(executor.config.base_delay, expected.base_delay);
-            assert_eq!(executor.config.max_delay, expected.max_delay);
-            assert_eq!(executor.config.jitter, expected.jitter);
+            assert_eq!(executor.budget.config().strategy, expected.strategy);
+            assert_eq!(executor.budget.config().max_attempts, expected.max_attempts);
+            assert_eq!(executor.budget.config().base_delay, expected.base_delay);
+            assert_eq!(executor.budget.config().max_delay, expected.max_delay);
+            assert_eq!(executor.budget.config().jitter, expected.jitter);
         }
     }
 
@@ -1302,6 +1659,47 @@ mod tests {
         assert_config_eq(&p, &per_error_permanent);
     }
 
+    #[test]
+    fn test_retry_budget_stops_at_max_attempts() {
+        let budget = RetryBudget::new(RetryStrategyConfig {
+            strategy: RetryStrategyType::Linear,
+            max_attempts: 3,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_mins(1),
+            jitter: 0.0,
+        });
+
+        assert_eq!(budget.should_retry(1), Some(Duration::from_secs(1)));
+        assert_eq!(budget.should_retry(2), Some(Duration::from_secs(2)));
+        assert_eq!(budget.should_retry(3), None);
+        assert_eq!(budget.should_retry(4), None);
+    }
+
+    #[test]
+    fn test_retry_budget_immediate_returns_zero_until_cap() {
+        let budget = RetryBudget::from(RetryStrategyConfig {
+            strategy: RetryStrategyType::Immediate,
+            max_attempts: 5,
+            ..Default::default()
+        });
+
+        for attempt in 1..5 {
+            assert_eq!(budget.should_retry(attempt), Some(Duration::ZERO));
+        }
+        assert_eq!(budget.should_retry(5), None);
+    }
+
+    #[test]
+    fn test_retry_budget_zero_or_one_attempt_never_retries() {
+        for max_attempts in [0, 1] {
+            let budget = RetryBudget::new(RetryStrategyConfig {
+                max_attempts,
+                ..Default::default()
+            });
+            assert_eq!(budget.should_retry(1), None);
+        }
+    }
+
     // Note: serde roundtrip coverage for RetryPolicy, RetryStrategyType, and
     // ErrorClass is provided by the snapshot_tests module below using
     // insta's assert_yaml_snapshot. This module avoids adding serde_json as
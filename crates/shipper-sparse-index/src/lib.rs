@@ -4,9 +4,57 @@
 //! - Converting crate names to sparse-index paths
 //! - Checking JSONL sparse-index content for a target version
 
+use std::fmt;
+
 use serde::Deserialize;
 
-/// Compute the Cargo sparse-index path for a crate name.
+/// A crate name that cannot appear in a sparse index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidCrateName {
+    /// The name as given.
+    pub name: String,
+    /// Which naming rule it breaks.
+    pub reason: &'static str,
+}
+
+impl fmt::Display for InvalidCrateName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid crate name `{}`: {}", self.name, self.reason)
+    }
+}
+
+impl std::error::Error for InvalidCrateName {}
+
+/// Compute the Cargo sparse-index path for a crate name, validating it first.
+///
+/// The name is lowercased using ASCII rules and must then be a valid package
+/// name: non-empty, not starting with a digit or hyphen, and made only of
+/// ASCII letters, digits, hyphens and underscores. See
+/// [`sparse_index_path_unchecked`] for the layout.
+pub fn sparse_index_path(crate_name: &str) -> Result<String, InvalidCrateName> {
+    let invalid = |reason| InvalidCrateName {
+        name: crate_name.to_string(),
+        reason,
+    };
+    let lower = crate_name.to_ascii_lowercase();
+    let Some(first) = lower.chars().next() else {
+        return Err(invalid("name is empty"));
+    };
+    if first.is_ascii_digit() || first == '-' {
+        return Err(invalid("name must not start with a digit or hyphen"));
+    }
+    if !lower
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+    {
+        return Err(invalid(
+            "name may only contain ASCII letters, digits, `-` and `_`",
+        ));
+    }
+    Ok(sparse_index_path_unchecked(&lower))
+}
+
+/// Compute the Cargo sparse-index path for a crate name without validating it.
 ///
 /// Layout:
 /// - `1/{name}` for length 1
@@ -14,8 +62,10 @@ use serde::Deserialize;
 /// - `3/{name[0]}/{name}` for length 3
 /// - `{name[0..2]}/{name[2..4]}/{name}` for length >= 4
 ///
-/// Names are lowercased using ASCII rules.
-pub fn sparse_index_path(crate_name: &str) -> String {
+/// Names are lowercased using ASCII rules. For names already known to be
+/// valid (e.g. from workspace metadata); an empty name yields `0/` and a
+/// non-ASCII one may panic.
+pub fn sparse_index_path_unchecked(crate_name: &str) -> String {
     let lower = crate_name.to_ascii_lowercase();
     match lower.len() {
         0 => "0/".to_string(),
@@ -48,21 +98,49 @@ mod tests {
 
     #[test]
     fn sparse_index_path_matches_cargo_layout() {
-        assert_eq!(sparse_index_path("a"), "1/a");
-        assert_eq!(sparse_index_path("ab"), "2/ab");
-        assert_eq!(sparse_index_path("abc"), "3/a/abc");
-        assert_eq!(sparse_index_path("demo"), "de/mo/demo");
+        assert_eq!(sparse_index_path_unchecked("a"), "1/a");
+        assert_eq!(sparse_index_path_unchecked("ab"), "2/ab");
+        assert_eq!(sparse_index_path_unchecked("abc"), "3/a/abc");
+        assert_eq!(sparse_index_path_unchecked("demo"), "de/mo/demo");
     }
 
     #[test]
     fn sparse_index_path_lowercases_ascii_names() {
-        assert_eq!(sparse_index_path("Serde"), "se/rd/serde");
-        assert_eq!(sparse_index_path("A"), "1/a");
+        assert_eq!(sparse_index_path_unchecked("Serde"), "se/rd/serde");
+        assert_eq!(sparse_index_path_unchecked("A"), "1/a");
     }
 
     #[test]
     fn sparse_index_path_handles_empty_name_without_panicking() {
-        assert_eq!(sparse_index_path(""), "0/");
+        assert_eq!(sparse_index_path_unchecked(""), "0/");
+    }
+
+    #[test]
+    fn checked_path_matches_layout_for_each_length_class() {
+        assert_eq!(sparse_index_path("a").unwrap(), "1/a");
+        assert_eq!(sparse_index_path("ab").unwrap(), "2/ab");
+        assert_eq!(sparse_index_path("abc").unwrap(), "3/a/abc");
+        assert_eq!(sparse_index_path("abcd").unwrap(), "ab/cd/abcd");
+        assert_eq!(sparse_index_path("serde_json").unwrap(), "se/rd/serde_json");
+    }
+
+    #[test]
+    fn checked_path_lowercases_uppercase_names() {
+        assert_eq!(sparse_index_path("Serde").unwrap(), "se/rd/serde");
+        assert_eq!(sparse_index_path("SYN").unwrap(), "3/s/syn");
+    }
+
+    #[test]
+    fn checked_path_rejects_invalid_names() {
+        let err = sparse_index_path("my.crate").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid crate name `my.crate`: name may only contain ASCII letters, digits, `-` and `_`"
+        );
+        assert_eq!(sparse_index_path("").unwrap_err().reason, "name is empty");
+        assert!(sparse_index_path("3d").is_err());
+        assert!(sparse_index_path("-crate").is_err());
+        assert!(sparse_index_path("café").is_err());
     }
 
     #[test]
@@ -93,18 +171,18 @@ not json
 
     #[test]
     fn sparse_index_path_exact_four_char_boundary() {
-        assert_eq!(sparse_index_path("abcd"), "ab/cd/abcd");
+        assert_eq!(sparse_index_path_unchecked("abcd"), "ab/cd/abcd");
     }
 
     #[test]
     fn sparse_index_path_five_chars() {
-        assert_eq!(sparse_index_path("hello"), "he/ll/hello");
+        assert_eq!(sparse_index_path_unchecked("hello"), "he/ll/hello");
     }
 
     #[test]
     fn sparse_index_path_long_name() {
         let name = "a".to_string() + &"b".repeat(99);
-        let path = sparse_index_path(&name);
+        let path = sparse_index_path_unchecked(&name);
         assert!(path.starts_with("ab/bb/"));
         assert!(path.ends_with(&name));
     }
@@ -113,55 +191,58 @@ not json
 
     #[test]
     fn sparse_index_path_with_hyphens() {
-        assert_eq!(sparse_index_path("my-crate"), "my/-c/my-crate");
+        assert_eq!(sparse_index_path_unchecked("my-crate"), "my/-c/my-crate");
     }
 
     #[test]
     fn sparse_index_path_with_underscores() {
-        assert_eq!(sparse_index_path("my_crate"), "my/_c/my_crate");
+        assert_eq!(sparse_index_path_unchecked("my_crate"), "my/_c/my_crate");
     }
 
     #[test]
     fn sparse_index_path_hyphen_underscore_produce_different_paths() {
-        let hyphen = sparse_index_path("my-crate");
-        let underscore = sparse_index_path("my_crate");
+        let hyphen = sparse_index_path_unchecked("my-crate");
+        let underscore = sparse_index_path_unchecked("my_crate");
         assert_ne!(hyphen, underscore);
     }
 
     #[test]
     fn sparse_index_path_digits_in_name() {
-        assert_eq!(sparse_index_path("h264"), "h2/64/h264");
-        assert_eq!(sparse_index_path("3d"), "2/3d");
+        assert_eq!(sparse_index_path_unchecked("h264"), "h2/64/h264");
+        assert_eq!(sparse_index_path_unchecked("3d"), "2/3d");
     }
 
     #[test]
     fn sparse_index_path_all_digits() {
-        assert_eq!(sparse_index_path("1234"), "12/34/1234");
+        assert_eq!(sparse_index_path_unchecked("1234"), "12/34/1234");
     }
 
     #[test]
     #[should_panic(expected = "byte index")]
     fn sparse_index_path_panics_on_multibyte_unicode() {
         // Crate names must be ASCII; multi-byte chars cause an indexing panic
-        let _ = sparse_index_path("café");
+        let _ = sparse_index_path_unchecked("café");
     }
 
     #[test]
     fn sparse_index_path_ascii_only_unicode_safe() {
         // Pure ASCII with non-alpha chars does not panic
-        let path = sparse_index_path("a-b_c");
+        let path = sparse_index_path_unchecked("a-b_c");
         assert_eq!(path, "a-/b_/a-b_c");
     }
 
     #[test]
     fn sparse_index_path_mixed_case_three_char() {
-        assert_eq!(sparse_index_path("SYN"), "3/s/syn");
-        assert_eq!(sparse_index_path("Syn"), "3/s/syn");
+        assert_eq!(sparse_index_path_unchecked("SYN"), "3/s/syn");
+        assert_eq!(sparse_index_path_unchecked("Syn"), "3/s/syn");
     }
 
     #[test]
     fn sparse_index_path_already_lowercase() {
-        assert_eq!(sparse_index_path("serde"), sparse_index_path("SERDE"));
+        assert_eq!(
+            sparse_index_path_unchecked("serde"),
+            sparse_index_path_unchecked("SERDE")
+        );
     }
 
     #[test]
@@ -169,7 +250,7 @@ not json
         for c in b'A'..=b'Z' {
             let upper = String::from(c as char);
             let lower = upper.to_ascii_lowercase();
-            assert_eq!(sparse_index_path(&upper), format!("1/{lower}"));
+            assert_eq!(sparse_index_path_unchecked(&upper), format!("1/{lower}"));
         }
     }
 
@@ -379,31 +460,31 @@ not json
 
     #[test]
     fn sparse_index_path_empty_name_returns_zero_slash() {
-        assert_eq!(sparse_index_path(""), "0/");
+        assert_eq!(sparse_index_path_unchecked(""), "0/");
     }
 
     #[test]
     fn sparse_index_path_three_digit_only_name() {
-        assert_eq!(sparse_index_path("123"), "3/1/123");
+        assert_eq!(sparse_index_path_unchecked("123"), "3/1/123");
     }
 
     #[test]
     fn sparse_index_path_long_hyphen_underscore_mixed() {
         assert_eq!(
-            sparse_index_path("foo-bar-baz_qux"),
+            sparse_index_path_unchecked("foo-bar-baz_qux"),
             "fo/o-/foo-bar-baz_qux"
         );
     }
 
     #[test]
     fn sparse_index_path_symbol_only_two_char_name() {
-        assert_eq!(sparse_index_path("--"), "2/--");
-        assert_eq!(sparse_index_path("__"), "2/__");
+        assert_eq!(sparse_index_path_unchecked("--"), "2/--");
+        assert_eq!(sparse_index_path_unchecked("__"), "2/__");
     }
 
     #[test]
     fn sparse_index_path_symbol_only_four_char_name() {
-        assert_eq!(sparse_index_path("-_-_"), "-_/-_/-_-_");
+        assert_eq!(sparse_index_path_unchecked("-_-_"), "-_/-_/-_-_");
     }
 }
 
@@ -416,27 +497,27 @@ mod snapshot_tests {
 
     #[test]
     fn snapshot_path_empty_name() {
-        assert_snapshot!(sparse_index_path(""), @"0/");
+        assert_snapshot!(sparse_index_path_unchecked(""), @"0/");
     }
 
     #[test]
     fn snapshot_path_one_char() {
-        assert_snapshot!(sparse_index_path("a"), @"1/a");
+        assert_snapshot!(sparse_index_path_unchecked("a"), @"1/a");
     }
 
     #[test]
     fn snapshot_path_two_chars() {
-        assert_snapshot!(sparse_index_path("ab"), @"2/ab");
+        assert_snapshot!(sparse_index_path_unchecked("ab"), @"2/ab");
     }
 
     #[test]
     fn snapshot_path_three_chars() {
-        assert_snapshot!(sparse_index_path("abc"), @"3/a/abc");
+        assert_snapshot!(sparse_index_path_unchecked("abc"), @"3/a/abc");
     }
 
     #[test]
     fn snapshot_path_four_chars() {
-        assert_snapshot!(sparse_index_path("demo"), @"de/mo/demo");
+        assert_snapshot!(sparse_index_path_unchecked("demo"), @"de/mo/demo");
     }
 
     // ── sparse_index_path: real-world crates ──
@@ -457,7 +538,7 @@ mod snapshot_tests {
         ];
         let paths: Vec<String> = crates
             .iter()
-            .map(|c| format!("{c} -> {}", sparse_index_path(c)))
+            .map(|c| format!("{c} -> {}", sparse_index_path_unchecked(c)))
             .collect();
         assert_snapshot!(paths.join("\n"));
     }
@@ -466,12 +547,12 @@ mod snapshot_tests {
 
     #[test]
     fn snapshot_path_mixed_case() {
-        assert_snapshot!(sparse_index_path("Serde"), @"se/rd/serde");
+        assert_snapshot!(sparse_index_path_unchecked("Serde"), @"se/rd/serde");
     }
 
     #[test]
     fn snapshot_path_all_upper() {
-        assert_snapshot!(sparse_index_path("TOKIO"), @"to/ki/tokio");
+        assert_snapshot!(sparse_index_path_unchecked("TOKIO"), @"to/ki/tokio");
     }
 
     // ── sparse_index_path: index URL construction ──
@@ -482,7 +563,7 @@ mod snapshot_tests {
         let crates = ["serde", "a", "ab", "syn", "rand_core"];
         let urls: Vec<String> = crates
             .iter()
-            .map(|c| format!("{base}{}", sparse_index_path(c)))
+            .map(|c| format!("{base}{}", sparse_index_path_unchecked(c)))
             .collect();
         assert_snapshot!(urls.join("\n"));
     }
@@ -543,7 +624,7 @@ mod snapshot_tests {
         ];
         let paths: Vec<String> = crates
             .iter()
-            .map(|c| format!("{c} -> {}", sparse_index_path(c)))
+            .map(|c| format!("{c} -> {}", sparse_index_path_unchecked(c)))
             .collect();
         assert_snapshot!(paths.join("\n"));
     }
@@ -553,7 +634,7 @@ mod snapshot_tests {
         let names = ["x", "ab", "syn", "clap", "tokio", "serde_json"];
         let paths: Vec<String> = names
             .iter()
-            .map(|c| format!("len={} {c} -> {}", c.len(), sparse_index_path(c)))
+            .map(|c| format!("len={} {c} -> {}", c.len(), sparse_index_path_unchecked(c)))
             .collect();
         assert_snapshot!(paths.join("\n"));
     }
@@ -590,7 +671,7 @@ mod snapshot_tests {
         let names = ["x", "ab", "syn", "rand", "serde", "my-crate", "proc-macro2"];
         let urls: Vec<String> = names
             .iter()
-            .map(|c| format!("{c} -> {base}{}", sparse_index_path(c)))
+            .map(|c| format!("{c} -> {base}{}", sparse_index_path_unchecked(c)))
             .collect();
         assert_snapshot!(urls.join("\n"));
     }
@@ -607,15 +688,15 @@ mod property_tests {
     proptest! {
         #[test]
         fn sparse_index_path_is_deterministic(name in "[A-Za-z0-9_-]{0,32}") {
-            let first = sparse_index_path(&name);
-            let second = sparse_index_path(&name);
+            let first = sparse_index_path_unchecked(&name);
+            let second = sparse_index_path_unchecked(&name);
             prop_assert_eq!(first, second);
         }
 
         #[test]
         fn sparse_index_path_ends_with_lowercase_name_for_non_empty_inputs(name in "[A-Za-z0-9_-]{1,32}") {
             let lower = name.to_ascii_lowercase();
-            let path = sparse_index_path(&name);
+            let path = sparse_index_path_unchecked(&name);
             prop_assert!(path.ends_with(&lower));
         }
 
@@ -654,7 +735,7 @@ mod property_tests {
 
         #[test]
         fn sparse_index_path_correct_prefix_by_length(name in "[a-z][a-z0-9]{0,31}") {
-            let path = sparse_index_path(&name);
+            let path = sparse_index_path_unchecked(&name);
             match name.len() {
                 1 => prop_assert!(path.starts_with("1/"), "expected '1/' for len=1, got {path}"),
                 2 => prop_assert!(path.starts_with("2/"), "expected '2/' for len=2, got {path}"),
@@ -1,7 +1,7 @@
 use std::collections::BTreeSet;
 
 use proptest::prelude::*;
-use shipper_sparse_index::{contains_version, sparse_index_path};
+use shipper_sparse_index::{contains_version, sparse_index_path, sparse_index_path_unchecked};
 
 /// Strategy for valid crate names (ASCII alphanumeric, hyphens, underscores).
 fn crate_name_strategy() -> impl Strategy<Value = String> {
@@ -18,46 +18,52 @@ fn version_strategy() -> impl Strategy<Value = String> {
 // ---------------------------------------------------------------------------
 
 proptest! {
+    /// Valid names pass validation and get the unchecked layout.
+    #[test]
+    fn checked_path_accepts_valid_names(name in crate_name_strategy()) {
+        prop_assert_eq!(sparse_index_path(&name), Ok(sparse_index_path_unchecked(&name)));
+    }
+
     /// Case-insensitive: upper and lower name produce the same path.
     #[test]
     fn path_is_case_insensitive(name in crate_name_strategy()) {
-        let lower_path = sparse_index_path(&name.to_ascii_lowercase());
-        let upper_path = sparse_index_path(&name.to_ascii_uppercase());
+        let lower_path = sparse_index_path_unchecked(&name.to_ascii_lowercase());
+        let upper_path = sparse_index_path_unchecked(&name.to_ascii_uppercase());
         prop_assert_eq!(lower_path, upper_path);
     }
 
     /// The output path is always fully ASCII-lowercase.
     #[test]
     fn path_output_is_lowercase(name in crate_name_strategy()) {
-        let path = sparse_index_path(&name);
+        let path = sparse_index_path_unchecked(&name);
         prop_assert_eq!(path.clone(), path.to_ascii_lowercase());
     }
 
     /// The path always uses forward-slash separators (never backslash).
     #[test]
     fn path_uses_forward_slashes(name in "[A-Za-z0-9_-]{0,32}") {
-        let path = sparse_index_path(&name);
+        let path = sparse_index_path_unchecked(&name);
         prop_assert!(!path.contains('\\'));
     }
 
     /// Length-1 names produce "1/{name}".
     #[test]
     fn path_prefix_for_length_1(name in "[A-Za-z]") {
-        let path = sparse_index_path(&name);
+        let path = sparse_index_path_unchecked(&name);
         prop_assert!(path.starts_with("1/"), "expected '1/' prefix, got {}", path);
     }
 
     /// Length-2 names produce "2/{name}".
     #[test]
     fn path_prefix_for_length_2(name in "[A-Za-z][A-Za-z0-9]") {
-        let path = sparse_index_path(&name);
+        let path = sparse_index_path_unchecked(&name);
         prop_assert!(path.starts_with("2/"), "expected '2/' prefix, got {}", path);
     }
 
     /// Length-3 names produce "3/{first_char}/{name}".
     #[test]
     fn path_prefix_for_length_3(name in "[A-Za-z][A-Za-z0-9]{2}") {
-        let path = sparse_index_path(&name);
+        let path = sparse_index_path_unchecked(&name);
         let lower = name.to_ascii_lowercase();
         let expected_prefix = format!("3/{}/", &lower[..1]);
         prop_assert!(
@@ -69,7 +75,7 @@ proptest! {
     /// Length >= 4 names have the correct two-character prefix buckets.
     #[test]
     fn path_prefix_for_length_ge4(name in "[A-Za-z][A-Za-z0-9]{3,31}") {
-        let path = sparse_index_path(&name);
+        let path = sparse_index_path_unchecked(&name);
         let lower = name.to_ascii_lowercase();
         let expected_prefix = format!("{}/{}/", &lower[..2], &lower[2..4]);
         prop_assert!(
@@ -81,7 +87,7 @@ proptest! {
     /// The number of path segments matches the Cargo sparse-index spec.
     #[test]
     fn path_has_correct_segment_count(name in "[A-Za-z][A-Za-z0-9_-]{0,31}") {
-        let path = sparse_index_path(&name);
+        let path = sparse_index_path_unchecked(&name);
         let segments: Vec<&str> = path.split('/').collect();
         let expected = match name.len() {
             1 => 2, // "1" / name
@@ -98,7 +104,7 @@ proptest! {
     /// No path segment is empty (except for the empty-name edge case).
     #[test]
     fn path_has_no_empty_segments(name in "[A-Za-z][A-Za-z0-9_-]{0,31}") {
-        let path = sparse_index_path(&name);
+        let path = sparse_index_path_unchecked(&name);
         for segment in path.split('/') {
             prop_assert!(!segment.is_empty(), "empty segment in path '{}'", path);
         }
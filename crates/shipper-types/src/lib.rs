@@ -104,21 +104,115 @@ impl Registry {
         }
     }
 
-    /// Get the index base URL, deriving it from the API base if not explicitly set.
-    /// Strips the `sparse+` prefix if present (used by Cargo's sparse index config).
+    /// Get the index base URL, falling back to the API base when it cannot be
+    /// resolved.
+    ///
+    /// See [`Registry::resolve_index_base`] for the derivation. When that
+    /// fails (a non-crates.io registry without `index_base`, or an unparsable
+    /// `api_base`) this returns `api_base` without a trailing slash, which is
+    /// only suitable for display; network callers should use
+    /// `resolve_index_base` so the misconfiguration surfaces as an error.
     pub fn get_index_base(&self) -> String {
+        self.resolve_index_base()
+            .unwrap_or_else(|_| self.api_base.trim_end_matches('/').to_string())
+    }
+
+    /// Resolve the sparse index base URL for this registry.
+    ///
+    /// - An explicit `index_base` wins, with any `sparse+` prefix (as used in
+    ///   Cargo's registry config) stripped.
+    /// - Otherwise `api_base` is parsed as a URL. For crates.io the index is
+    ///   served from `index.crates.io` on the same scheme and port.
+    /// - Any other registry must set `index_base`: private registries lay out
+    ///   their index differently, and guessing a host (such as prefixing
+    ///   `index.`) produces URLs that silently never become ready.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use shipper_types::Registry;
+    ///
+    /// let registry = Registry {
+    ///     name: "crates-io".to_string(),
+    ///     api_base: "https://crates.io".to_string(),
+    ///     index_base: None,
+    /// };
+    /// assert_eq!(registry.resolve_index_base().unwrap(), "https://index.crates.io");
+    ///
+    /// let private = Registry {
+    ///     name: "private".to_string(),
+    ///     api_base: "https://my.registry.example.com".to_string(),
+    ///     index_base: None,
+    /// };
+    /// assert!(private.resolve_index_base().is_err());
+    /// ```
+    pub fn resolve_index_base(&self) -> anyhow::Result<String> {
         if let Some(index_base) = &self.index_base {
-            index_base
+            return Ok(index_base
                 .strip_prefix("sparse+")
                 .unwrap_or(index_base)
-                .to_string()
-        } else {
-            // Default: derive from API base (e.g., https://crates.io -> https://index.crates.io)
-            self.api_base
-                .replace("https://", "https://index.")
-                .replace("http://", "http://index.")
+                .to_string());
+        }
+
+        let mut url = url::Url::parse(&self.api_base).map_err(|err| {
+            anyhow::anyhow!(
+                "registry '{}' has an invalid api_base {}: {err}",
+                self.name,
+                self.api_base
+            )
+        })?;
+
+        if url.host_str() != Some("crates.io") {
+            anyhow::bail!(
+                "registry '{}' has no index_base and it cannot be derived from api_base {}; \
+                 set index_base to the registry's sparse index URL",
+                self.name,
+                self.api_base
+            );
         }
+
+        url.set_host(Some("index.crates.io"))
+            .map_err(|err| anyhow::anyhow!("failed to derive crates.io index URL: {err}"))?;
+        url.set_path("");
+        url.set_query(None);
+        url.set_fragment(None);
+        Ok(url.as_str().trim_end_matches('/').to_string())
     }
+
+    /// URL of `crate_name`'s sparse-index file, for display.
+    ///
+    /// Joins [`Registry::get_index_base`] and the Cargo index path of the
+    /// crate with exactly one `/`. Like `get_index_base` it falls back to
+    /// `api_base` when the index cannot be resolved; network callers should
+    /// use [`Registry::resolve_sparse_index_url`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use shipper_types::Registry;
+    ///
+    /// assert_eq!(
+    ///     Registry::crates_io().sparse_index_url("serde"),
+    ///     "https://index.crates.io/se/rd/serde"
+    /// );
+    /// ```
+    pub fn sparse_index_url(&self, crate_name: &str) -> String {
+        join_index_url(&self.get_index_base(), crate_name)
+    }
+
+    /// URL of `crate_name`'s sparse-index file, failing when the index base
+    /// cannot be resolved (see [`Registry::resolve_index_base`]).
+    pub fn resolve_sparse_index_url(&self, crate_name: &str) -> anyhow::Result<String> {
+        Ok(join_index_url(&self.resolve_index_base()?, crate_name))
+    }
+}
+
+fn join_index_url(index_base: &str, crate_name: &str) -> String {
+    format!(
+        "{}/{}",
+        index_base.trim_end_matches('/'),
+        shipper_sparse_index::sparse_index_path_unchecked(crate_name)
+    )
 }
 
 /// Input specification for a crate publish operation.
@@ -227,6 +321,9 @@ pub enum PublishPolicy {
 /// // Verify each crate individually (more thorough)
 /// let package = VerifyMode::Package;
 ///
+/// // Verify each crate right before it is published
+/// let just_in_time = VerifyMode::PackageJustInTime;
+///
 /// // Skip verification entirely
 /// let none = VerifyMode::None;
 /// ```
@@ -244,6 +341,21 @@ pub enum VerifyMode {
     /// Runs `cargo verify` for each crate individually before publishing.
     /// More thorough but slower than workspace mode.
     Package,
+    /// Per-crate verify, immediately before each publish
+    ///
+    /// Skips the up-front dry-run and instead runs
+    /// `cargo publish -p <name> --dry-run` right before publishing each
+    /// crate, so it resolves against the crates this run has already
+    /// published. A failed dry-run stops the run with a permanent failure
+    /// for that crate.
+    ///
+    /// ```
+    /// use shipper_types::VerifyMode;
+    ///
+    /// let mode: VerifyMode = serde_json::from_str(r#""package_just_in_time""#).unwrap();
+    /// assert_eq!(mode, VerifyMode::PackageJustInTime);
+    /// ```
+    PackageJustInTime,
     /// No verify
     ///
     /// Skips verification entirely. Use with caution.
@@ -419,6 +531,61 @@ impl Default for ReadinessConfig {
     }
 }
 
+impl ReadinessConfig {
+    /// Un-jittered delay after failed poll `attempt` (1-based).
+    ///
+    /// Grows as `poll_interval * 2^(attempt - 1)` and is capped at
+    /// `max_delay`. Attempt `0` is treated as `1`.
+    pub fn backoff_delay(&self, attempt: u32) -> Duration {
+        let pow = attempt.saturating_sub(1).min(16);
+        self.poll_interval
+            .saturating_mul(2_u32.saturating_pow(pow))
+            .min(self.max_delay)
+    }
+
+    /// Scale `delay` by a random factor in `1 ± jitter_factor`.
+    pub fn apply_jitter(&self, delay: Duration) -> Duration {
+        let jitter_range = self.jitter_factor;
+        if jitter_range <= 0.0 {
+            return delay;
+        }
+        let jitter = 1.0 + (rand::random::<f64>() * 2.0 * jitter_range - jitter_range);
+        Duration::from_millis((delay.as_millis() as f64 * jitter).round() as u64)
+    }
+
+    /// The sequence of sleeps a readiness check performs.
+    ///
+    /// The first entry is `initial_delay` (the wait before the first poll);
+    /// each later entry is the jittered [`backoff_delay`](Self::backoff_delay)
+    /// before the next poll. The schedule stops before the cumulative wait
+    /// would exceed `max_total_wait`, and after the first zero-length backoff
+    /// (a zero `poll_interval` would otherwise never end).
+    pub fn poll_schedule(&self) -> Vec<Duration> {
+        let mut schedule = Vec::new();
+        if self.initial_delay > self.max_total_wait {
+            return schedule;
+        }
+        schedule.push(self.initial_delay);
+        let mut total = self.initial_delay;
+
+        for attempt in 1.. {
+            let base = self.backoff_delay(attempt);
+            let delay = self.apply_jitter(base);
+            match total.checked_add(delay) {
+                Some(next) if next <= self.max_total_wait => {
+                    total = next;
+                    schedule.push(delay);
+                }
+                _ => break,
+            }
+            if base.is_zero() {
+                break;
+            }
+        }
+        schedule
+    }
+}
+
 /// Configuration for parallel publishing.
 ///
 /// Parallel publishing allows independent crates in a workspace to be
@@ -466,7 +633,9 @@ pub struct ParallelConfig {
     /// Maximum number of concurrent publish operations (default: 4)
     ///
     /// The maximum number of crates that can be publishing simultaneously.
-    /// This limits resource usage and API rate limiting impact.
+    /// This limits resource usage and API rate limiting impact. `0` means
+    /// "auto": one per available CPU (see [`ParallelConfig::resolve_concurrency`]);
+    /// config validation accepts it.
     pub max_concurrent: usize,
     /// Timeout per package publish operation (default: 30 minutes)
     ///
@@ -490,46 +659,92 @@ impl Default for ParallelConfig {
     }
 }
 
+impl ParallelConfig {
+    /// Effective concurrency limit: `max_concurrent`, or the number of
+    /// available CPUs when it is `0` ("auto"). Always at least 1.
+    pub fn resolve_concurrency(&self) -> usize {
+        if self.max_concurrent > 0 {
+            return self.max_concurrent;
+        }
+        std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get)
+    }
+}
+
+/// Extra pieces wrapped around the cargo command line shipper builds.
+///
+/// The default adds nothing, so commands run exactly as
+/// `cargo publish ...`. A toolchain turns that into
+/// `cargo +<toolchain> publish ...`; `leading_args` go between the toolchain
+/// and the subcommand (e.g. `-Z` flags or `--config` overrides) and
+/// `trailing_args` after shipper's own arguments. Use `SHIPPER_CARGO_BIN`
+/// to swap the binary itself.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CargoInvocation {
+    /// Rustup toolchain, passed as `+<toolchain>`.
+    pub toolchain: Option<String>,
+    /// Arguments placed before the subcommand.
+    pub leading_args: Vec<String>,
+    /// Arguments placed after shipper's own arguments.
+    pub trailing_args: Vec<String>,
+}
+
+impl CargoInvocation {
+    /// The full argument list for a cargo run whose own arguments are
+    /// `args` (subcommand first).
+    pub fn argv(&self, args: &[&str]) -> Vec<String> {
+        self.toolchain
+            .iter()
+            .map(|toolchain| format!("+{toolchain}"))
+            .chain(self.leading_args.iter().cloned())
+            .chain(args.iter().map(|arg| arg.to_string()))
+            .chain(self.trailing_args.iter().cloned())
+            .collect()
+    }
+}
+
 /// Runtime configuration options for a Shipper publish operation.
 ///
 /// This struct contains all the tunable parameters that control how
 /// Shipper executes a publish operation, including retry behavior,
 /// verification settings, and output preferences.
 ///
+/// Options serialize with durations as milliseconds, so the exact options
+/// used for a publish can be persisted alongside its receipt. Secrets held
+/// in [`Self::webhook`] and [`Self::encryption`] are serialized as-is;
+/// clear them before persisting options anywhere shared.
+///
+/// # Defaults
+///
+/// [`RuntimeOptions::default`] matches what a workspace without a config
+/// file gets from the CLI:
+/// - `policy`: [`PublishPolicy::Safe`]; `verify_mode`: [`VerifyMode::Workspace`]
+/// - `max_attempts`: 6, `base_delay`: 2 seconds, `max_delay`: 2 minutes,
+///   exponential strategy with 0.5 jitter
+/// - `verify_timeout`: 2 minutes, `verify_poll_interval`: 5 seconds
+/// - `state_dir`: `.shipper`, `output_lines`: 50, `lock_timeout`: 1 hour
+/// - `readiness`, `parallel`, `webhook`, `encryption`: their own defaults
+/// - every flag `false`, no features, registries, resume point or rehearsal
+///
 /// # Example
 ///
+/// Start from the defaults and override only what differs; the `with_*`
+/// setters keep working when fields are added.
+///
 /// ```ignore
-/// use std::path::PathBuf;
-/// use shipper::types::{RuntimeOptions, PublishPolicy, ParallelConfig};
-///
-/// let options = RuntimeOptions {
-///     allow_dirty: false,
-///     skip_ownership_check: false,
-///     strict_ownership: true,
-///     no_verify: false,
-///     max_attempts: 3,
-///     base_delay: std::time::Duration::from_secs(1),
-///     max_delay: std::time::Duration::from_secs(60),
-///     retry_strategy: shipper::retry::RetryStrategyType::Exponential,
-///     retry_jitter: 0.3,
-///     retry_per_error: shipper::retry::PerErrorConfig::default(),
-///     verify_timeout: std::time::Duration::from_secs(600),
-///     verify_poll_interval: std::time::Duration::from_secs(10),
-///     state_dir: PathBuf::from(".shipper"),
-///     force_resume: false,
-///     policy: PublishPolicy::Safe,
-///     verify_mode: shipper::types::VerifyMode::Workspace,
-///     readiness: shipper::types::ReadinessConfig::default(),
-///     output_lines: 1000,
-///     force: false,
-///     lock_timeout: std::time::Duration::from_secs(3600),
-///     parallel: ParallelConfig::default(),
-///     webhook: shipper::webhook::WebhookConfig::default(),
-///     encryption: shipper::encryption::EncryptionConfig::default(),
-///     registries: vec![],
-/// };
+/// use std::time::Duration;
+/// use shipper::types::{ParallelConfig, PublishPolicy, RuntimeOptions};
+///
+/// let options = RuntimeOptions::default()
+///     .with_policy(PublishPolicy::Balanced)
+///     .with_max_attempts(3)
+///     .with_parallel(ParallelConfig {
+///         enabled: true,
+///         ..ParallelConfig::default()
+///     })
+///     .with_lock_timeout(Duration::from_mins(30));
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RuntimeOptions {
     /// Allow publishing from a dirty git working tree.
     pub allow_dirty: bool,
@@ -539,11 +754,30 @@ pub struct RuntimeOptions {
     pub strict_ownership: bool,
     /// Pass `--no-verify` to `cargo publish` (skip pre-publish build).
     pub no_verify: bool,
+    /// Features passed as `--features` to `cargo publish` and per-package
+    /// dry-runs, each scoped as `<package>/<feature>`; a crate only receives
+    /// its own entries.
+    pub features: Vec<String>,
+    /// Pass `--no-default-features` to `cargo publish` and per-package dry-runs.
+    pub no_default_features: bool,
+    /// Pass `--all-features` to `cargo publish` and per-package dry-runs.
+    pub all_features: bool,
+    /// Toolchain and extra arguments wrapped around every cargo command.
+    #[serde(default)]
+    pub cargo: CargoInvocation,
     /// Maximum number of publish attempts per crate.
     pub max_attempts: u32,
     /// Initial backoff delay between retries.
+    #[serde(
+        deserialize_with = "deserialize_duration",
+        serialize_with = "serialize_duration"
+    )]
     pub base_delay: Duration,
     /// Upper bound on backoff delay.
+    #[serde(
+        deserialize_with = "deserialize_duration",
+        serialize_with = "serialize_duration"
+    )]
     pub max_delay: Duration,
     /// Retry strategy type: immediate, exponential, linear, constant
     pub retry_strategy: shipper_retry::RetryStrategyType,
@@ -552,8 +786,16 @@ pub struct RuntimeOptions {
     /// Per-error-type retry configuration
     pub retry_per_error: shipper_retry::PerErrorConfig,
     /// Timeout for the workspace-level dry-run verification step.
+    #[serde(
+        deserialize_with = "deserialize_duration",
+        serialize_with = "serialize_duration"
+    )]
     pub verify_timeout: Duration,
     /// Poll interval for the dry-run verification step.
+    #[serde(
+        deserialize_with = "deserialize_duration",
+        serialize_with = "serialize_duration"
+    )]
     pub verify_poll_interval: Duration,
     /// Directory for persisted state, receipts, and event logs.
     pub state_dir: PathBuf,
@@ -570,14 +812,21 @@ pub struct RuntimeOptions {
     /// Force override of existing locks
     pub force: bool,
     /// Lock timeout duration (after which locks are considered stale)
+    #[serde(
+        deserialize_with = "deserialize_duration",
+        serialize_with = "serialize_duration"
+    )]
     pub lock_timeout: Duration,
     /// Parallel publishing configuration
     pub parallel: ParallelConfig,
     /// Webhook configuration for publish notifications
+    #[serde(default)]
     pub webhook: WebhookConfig,
     /// Encryption configuration for state files
+    #[serde(default)]
     pub encryption: EncryptionSettings,
     /// Target registries for multi-registry publishing
+    #[serde(default)]
     pub registries: Vec<Registry>,
     /// Optional package name to resume from (skips all packages before this one)
     pub resume_from: Option<String>,
@@ -602,6 +851,161 @@ pub struct RuntimeOptions {
     pub rehearsal_smoke_install: Option<String>,
 }
 
+impl Default for RuntimeOptions {
+    fn default() -> Self {
+        Self {
+            allow_dirty: false,
+            skip_ownership_check: false,
+            strict_ownership: false,
+            no_verify: false,
+            features: Vec::new(),
+            no_default_features: false,
+            all_features: false,
+            cargo: CargoInvocation::default(),
+            max_attempts: 6,
+            base_delay: Duration::from_secs(2),
+            max_delay: Duration::from_mins(2),
+            retry_strategy: shipper_retry::RetryStrategyType::Exponential,
+            retry_jitter: 0.5,
+            retry_per_error: shipper_retry::PerErrorConfig::default(),
+            verify_timeout: Duration::from_mins(2),
+            verify_poll_interval: Duration::from_secs(5),
+            state_dir: PathBuf::from(".shipper"),
+            force_resume: false,
+            policy: PublishPolicy::Safe,
+            verify_mode: VerifyMode::Workspace,
+            readiness: ReadinessConfig::default(),
+            output_lines: 50,
+            force: false,
+            lock_timeout: Duration::from_hours(1),
+            parallel: ParallelConfig::default(),
+            webhook: WebhookConfig::default(),
+            encryption: EncryptionSettings::default(),
+            registries: Vec::new(),
+            resume_from: None,
+            rehearsal_registry: None,
+            rehearsal_skip: false,
+            rehearsal_smoke_install: None,
+        }
+    }
+}
+
+impl RuntimeOptions {
+    /// Set [`Self::policy`].
+    pub fn with_policy(mut self, policy: PublishPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Set [`Self::verify_mode`].
+    pub fn with_verify_mode(mut self, verify_mode: VerifyMode) -> Self {
+        self.verify_mode = verify_mode;
+        self
+    }
+
+    /// Set [`Self::state_dir`].
+    pub fn with_state_dir(mut self, state_dir: impl Into<PathBuf>) -> Self {
+        self.state_dir = state_dir.into();
+        self
+    }
+
+    /// Set [`Self::allow_dirty`].
+    pub fn with_allow_dirty(mut self, allow_dirty: bool) -> Self {
+        self.allow_dirty = allow_dirty;
+        self
+    }
+
+    /// Set [`Self::no_verify`].
+    pub fn with_no_verify(mut self, no_verify: bool) -> Self {
+        self.no_verify = no_verify;
+        self
+    }
+
+    /// Set [`Self::features`].
+    pub fn with_features<I, S>(mut self, features: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.features = features.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set [`Self::cargo`].
+    pub fn with_cargo(mut self, cargo: CargoInvocation) -> Self {
+        self.cargo = cargo;
+        self
+    }
+
+    /// Set [`Self::max_attempts`].
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Set [`Self::base_delay`] and [`Self::max_delay`] together, since
+    /// `max_delay` must not be below `base_delay`.
+    pub fn with_retry_delays(mut self, base_delay: Duration, max_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Set [`Self::retry_strategy`].
+    pub fn with_retry_strategy(mut self, strategy: shipper_retry::RetryStrategyType) -> Self {
+        self.retry_strategy = strategy;
+        self
+    }
+
+    /// Set [`Self::readiness`].
+    pub fn with_readiness(mut self, readiness: ReadinessConfig) -> Self {
+        self.readiness = readiness;
+        self
+    }
+
+    /// Set [`Self::parallel`].
+    pub fn with_parallel(mut self, parallel: ParallelConfig) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    /// Set [`Self::output_lines`].
+    pub fn with_output_lines(mut self, output_lines: usize) -> Self {
+        self.output_lines = output_lines;
+        self
+    }
+
+    /// Set [`Self::lock_timeout`].
+    pub fn with_lock_timeout(mut self, lock_timeout: Duration) -> Self {
+        self.lock_timeout = lock_timeout;
+        self
+    }
+
+    /// Set [`Self::webhook`].
+    pub fn with_webhook(mut self, webhook: WebhookConfig) -> Self {
+        self.webhook = webhook;
+        self
+    }
+
+    /// Set [`Self::encryption`].
+    pub fn with_encryption(mut self, encryption: EncryptionSettings) -> Self {
+        self.encryption = encryption;
+        self
+    }
+
+    /// Set [`Self::registries`].
+    pub fn with_registries(mut self, registries: Vec<Registry>) -> Self {
+        self.registries = registries;
+        self
+    }
+
+    /// Set [`Self::resume_from`].
+    pub fn with_resume_from(mut self, package: impl Into<String>) -> Self {
+        self.resume_from = Some(package.into());
+        self
+    }
+}
+
 /// Classification of a publish operation, used by the runtime to make
 /// registry-aware decisions (backoff windows, duration estimation,
 /// per-regime telemetry).
@@ -744,7 +1148,8 @@ pub struct PublishLevel {
 ///
 /// The plan ID is stable across runs if the workspace metadata doesn't
 /// change. This allows Shipper to detect when a resumed operation is
-/// using the same plan.
+/// using the same plan. See [`ReleasePlan::compute_id`] for exactly which
+/// fields participate.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReleasePlan {
     pub plan_version: String,
@@ -781,13 +1186,154 @@ pub struct SkippedPackage {
 pub struct PlannedWorkspace {
     /// Absolute path to the workspace root directory.
     pub workspace_root: PathBuf,
+    /// The target directory reported by `cargo metadata` (honors
+    /// `CARGO_TARGET_DIR` and `build.target-dir`).
+    pub target_directory: PathBuf,
     /// The deterministic, SHA256-identified publish plan.
     pub plan: ReleasePlan,
     /// Packages that were excluded from the plan.
     pub skipped: Vec<SkippedPackage>,
 }
 
+/// Current plan schema version, written to [`ReleasePlan::plan_version`].
+pub const CURRENT_PLAN_VERSION: &str = "shipper.plan.v1";
+
+/// Oldest plan schema version this shipper can read.
+pub const MINIMUM_SUPPORTED_PLAN_VERSION: &str = "shipper.plan.v1";
+
 impl ReleasePlan {
+    /// Check that `plan_version` is a plan schema version this shipper
+    /// understands.
+    ///
+    /// Fails if the string is not a `shipper.plan.v<N>` tag, or if `N` is
+    /// older than [`MINIMUM_SUPPORTED_PLAN_VERSION`] or newer than
+    /// [`CURRENT_PLAN_VERSION`], so a plan written by a newer shipper is
+    /// rejected instead of misread. Call it after deserializing a plan.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use shipper_types::{CURRENT_PLAN_VERSION, Registry, ReleasePlan};
+    ///
+    /// let mut plan = ReleasePlan {
+    ///     plan_version: CURRENT_PLAN_VERSION.to_string(),
+    ///     plan_id: String::new(),
+    ///     created_at: chrono::Utc::now(),
+    ///     registry: Registry::crates_io(),
+    ///     packages: vec![],
+    ///     dependencies: Default::default(),
+    /// };
+    /// assert!(plan.validate_version().is_ok());
+    ///
+    /// plan.plan_version = "shipper.plan.v99".to_string();
+    /// assert!(plan.validate_version().is_err());
+    /// ```
+    pub fn validate_version(&self) -> anyhow::Result<()> {
+        let version = &self.plan_version;
+        if !version.starts_with("shipper.plan.") {
+            anyhow::bail!(
+                "invalid plan version format: {version}; expected a shipper.plan.v<N> tag"
+            );
+        }
+        schema::validate_schema_version_range(
+            version,
+            MINIMUM_SUPPORTED_PLAN_VERSION,
+            CURRENT_PLAN_VERSION,
+            "plan",
+        )
+    }
+
+    /// Compute the deterministic `plan_id` for the given plan content.
+    ///
+    /// The id is the hex-encoded SHA-256 of a canonical serialization of:
+    ///
+    /// - the registry `name` and `api_base`
+    /// - every package's `name@version`, sorted
+    /// - every dependency edge `package -> dependency`, sorted
+    ///
+    /// Nothing else participates. In particular `created_at`, `plan_version`,
+    /// the registry `index_base`, manifest paths, package regimes and the
+    /// order of `packages` do not affect the id, so rebuilding the plan for
+    /// an unchanged workspace yields the same id.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use shipper_types::{ReleasePlan, Registry};
+    ///
+    /// let id = ReleasePlan::compute_id(&Registry::crates_io(), &[], &BTreeMap::new());
+    /// assert_eq!(id.len(), 64);
+    /// ```
+    pub fn compute_id(
+        registry: &Registry,
+        packages: &[PlannedPackage],
+        dependencies: &BTreeMap<String, Vec<String>>,
+    ) -> String {
+        use sha2::{Digest, Sha256};
+
+        let mut packages: Vec<(&str, &str)> = packages
+            .iter()
+            .map(|p| (p.name.as_str(), p.version.as_str()))
+            .collect();
+        packages.sort_unstable();
+        let mut edges: Vec<(&str, &str)> = dependencies
+            .iter()
+            .flat_map(|(name, deps)| deps.iter().map(move |d| (name.as_str(), d.as_str())))
+            .collect();
+        edges.sort_unstable();
+
+        let mut hasher = Sha256::new();
+        hasher.update(b"registry ");
+        hasher.update(registry.name.as_bytes());
+        hasher.update(b" ");
+        hasher.update(registry.api_base.as_bytes());
+        hasher.update(b"\n");
+        for (name, version) in packages {
+            hasher.update(b"package ");
+            hasher.update(name.as_bytes());
+            hasher.update(b"@");
+            hasher.update(version.as_bytes());
+            hasher.update(b"\n");
+        }
+        for (name, dep) in edges {
+            hasher.update(b"dependency ");
+            hasher.update(name.as_bytes());
+            hasher.update(b" -> ");
+            hasher.update(dep.as_bytes());
+            hasher.update(b"\n");
+        }
+        hex::encode(hasher.finalize())
+    }
+
+    /// The `plan_id` releases before [`compute_id`](Self::compute_id)
+    /// produced: SHA-256 over `api_base` and each package's `name@version`
+    /// in plan order.
+    ///
+    /// Only used to recognize state and receipts written by those releases;
+    /// see [`matches_id`](Self::matches_id).
+    pub fn legacy_id(api_base: &str, packages: &[PlannedPackage]) -> String {
+        use sha2::{Digest, Sha256};
+
+        let mut hasher = Sha256::new();
+        hasher.update(api_base.as_bytes());
+        hasher.update(b"\n");
+        for p in packages {
+            hasher.update(p.name.as_bytes());
+            hasher.update(b"@");
+            hasher.update(p.version.as_bytes());
+            hasher.update(b"\n");
+        }
+        hex::encode(hasher.finalize())
+    }
+
+    /// Whether `id` names this plan: either its `plan_id` or the
+    /// [`legacy_id`](Self::legacy_id) an earlier release gave the same plan,
+    /// so a run started before an upgrade can still be resumed.
+    pub fn matches_id(&self, id: &str) -> bool {
+        self.plan_id == id || Self::legacy_id(&self.registry.api_base, &self.packages) == id
+    }
+
     /// Group packages by dependency level for parallel publishing.
     ///
     /// Packages at the same level have no dependencies on each other and can
@@ -801,6 +1347,154 @@ impl ReleasePlan {
             })
             .collect()
     }
+
+    /// Describe what changed from `self` (e.g. the plan stored with a
+    /// resumable run) to `other` (the freshly built plan).
+    ///
+    /// Only semantic content is compared: packages by name and version,
+    /// dependency edges, and the registry name and URLs. Package order,
+    /// `plan_id`, `created_at` and manifest paths are ignored, so a diff can
+    /// be empty even when the `plan_id`s differ.
+    pub fn diff(&self, other: &ReleasePlan) -> PlanDiff {
+        use std::collections::BTreeSet;
+
+        let old: BTreeMap<&str, &str> = self
+            .packages
+            .iter()
+            .map(|p| (p.name.as_str(), p.version.as_str()))
+            .collect();
+        let new: BTreeMap<&str, &str> = other
+            .packages
+            .iter()
+            .map(|p| (p.name.as_str(), p.version.as_str()))
+            .collect();
+
+        let mut diff = PlanDiff::default();
+        for (name, version) in &new {
+            match old.get(name) {
+                None => diff.added.push(format!("{name}@{version}")),
+                Some(previous) if previous != version => diff.version_changes.push(VersionChange {
+                    name: name.to_string(),
+                    from: previous.to_string(),
+                    to: version.to_string(),
+                }),
+                Some(_) => {}
+            }
+        }
+        for (name, version) in &old {
+            if !new.contains_key(name) {
+                diff.removed.push(format!("{name}@{version}"));
+            }
+        }
+
+        let edges = |plan: &ReleasePlan| -> BTreeSet<(String, String)> {
+            plan.dependencies
+                .iter()
+                .flat_map(|(name, deps)| deps.iter().map(move |dep| (name.clone(), dep.clone())))
+                .collect()
+        };
+        let (old_edges, new_edges) = (edges(self), edges(other));
+        diff.added_dependencies = new_edges.difference(&old_edges).cloned().collect();
+        diff.removed_dependencies = old_edges.difference(&new_edges).cloned().collect();
+
+        let registry_key =
+            |r: &Registry| (r.name.clone(), r.api_base.clone(), r.index_base.clone());
+        if registry_key(&self.registry) != registry_key(&other.registry) {
+            diff.registry_change = Some(RegistryChange {
+                from: describe_registry(&self.registry),
+                to: describe_registry(&other.registry),
+            });
+        }
+
+        diff
+    }
+}
+
+fn describe_registry(registry: &Registry) -> String {
+    match &registry.index_base {
+        Some(index) => format!("{} ({}, index {})", registry.name, registry.api_base, index),
+        None => format!("{} ({})", registry.name, registry.api_base),
+    }
+}
+
+/// Semantic differences between two [`ReleasePlan`]s, from
+/// [`ReleasePlan::diff`].
+///
+/// Explains why a stored plan no longer matches the workspace, so a
+/// `--force-resume` decision can be made knowingly.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanDiff {
+    /// Packages only in the new plan, as `name@version`
+    pub added: Vec<String>,
+    /// Packages only in the old plan, as `name@version`
+    pub removed: Vec<String>,
+    /// Packages in both plans whose version changed
+    pub version_changes: Vec<VersionChange>,
+    /// Dependency edges `(package, depends_on)` only in the new plan
+    pub added_dependencies: Vec<(String, String)>,
+    /// Dependency edges `(package, depends_on)` only in the old plan
+    pub removed_dependencies: Vec<(String, String)>,
+    /// Registry the plan targets, if it changed
+    pub registry_change: Option<RegistryChange>,
+}
+
+impl PlanDiff {
+    /// Whether the two plans are semantically the same.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.version_changes.is_empty()
+            && self.added_dependencies.is_empty()
+            && self.removed_dependencies.is_empty()
+            && self.registry_change.is_none()
+    }
+}
+
+impl std::fmt::Display for PlanDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "no semantic changes");
+        }
+        if let Some(change) = &self.registry_change {
+            writeln!(f, "~ registry: {} -> {}", change.from, change.to)?;
+        }
+        for package in &self.added {
+            writeln!(f, "+ package {package}")?;
+        }
+        for package in &self.removed {
+            writeln!(f, "- package {package}")?;
+        }
+        for change in &self.version_changes {
+            writeln!(f, "~ {}: {} -> {}", change.name, change.from, change.to)?;
+        }
+        for (name, dep) in &self.added_dependencies {
+            writeln!(f, "+ dependency {name} -> {dep}")?;
+        }
+        for (name, dep) in &self.removed_dependencies {
+            writeln!(f, "- dependency {name} -> {dep}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A package whose version differs between two plans.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionChange {
+    /// Crate name
+    pub name: String,
+    /// Version in the old plan
+    pub from: String,
+    /// Version in the new plan
+    pub to: String,
+}
+
+/// The registry targeted by two plans, when it changed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryChange {
+    /// Old registry, as `name (api_base)` (plus the index URL when set)
+    pub from: String,
+    /// New registry, as `name (api_base)` (plus the index URL when set)
+    pub to: String,
 }
 
 /// A group of packages that can be processed in parallel.
@@ -939,6 +1633,12 @@ where
 ///           Pending (retry)
 /// ```
 ///
+/// A graceful interruption moves a `Pending` or `Uploaded` package to
+/// `Cancelled { at_state }`, recording the state it was interrupted in.
+/// `Cancelled` ends the interrupted run but is resumable: on resume a
+/// package cancelled while `uploaded` goes straight to verification and
+/// any other cancelled package is published again.
+///
 /// # Example
 ///
 /// ```ignore
@@ -955,7 +1655,7 @@ where
 ///
 /// // When skipped (e.g., already published)
 /// let skipped = PackageState::Skipped {
-///     reason: "version already exists".to_string()
+///     reason: shipper::types::SkipReason::AlreadyPublished,
 /// };
 ///
 /// // On failure
@@ -970,9 +1670,100 @@ pub enum PackageState {
     Pending,
     Uploaded,
     Published,
-    Skipped { reason: String },
-    Failed { class: ErrorClass, message: String },
-    Ambiguous { message: String },
+    Skipped {
+        reason: SkipReason,
+    },
+    Failed {
+        class: ErrorClass,
+        message: String,
+    },
+    Ambiguous {
+        message: String,
+    },
+    /// The run was interrupted while this package was in `at_state`
+    /// (the short name of the state it was in, e.g. `uploaded`).
+    ///
+    /// Terminal for the interrupted run but resumable: the next run picks
+    /// the package up again instead of treating it as done or failed.
+    Cancelled {
+        at_state: String,
+    },
+}
+
+/// Why a package was skipped instead of published.
+///
+/// Serializes as a snake_case tag (`"already_published"`), or as
+/// `{"manual": "..."}` for [`SkipReason::Manual`], so receipts record skip
+/// decisions in a machine-readable form.
+///
+/// Deserialization also accepts the free-form strings written before this
+/// enum existed: a string naming a known reason maps to that variant and
+/// anything else becomes [`SkipReason::Manual`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    /// The exact version is already live on the registry.
+    AlreadyPublished,
+    /// The version exists on the registry but has been yanked.
+    YankedExists,
+    /// The package was excluded by configuration or package selection.
+    ExcludedByConfig,
+    /// Any other reason, as free-form text.
+    Manual(String),
+}
+
+impl From<&str> for SkipReason {
+    /// Map a legacy free-form reason onto a structured one.
+    fn from(reason: &str) -> Self {
+        match reason.trim().to_ascii_lowercase().as_str() {
+            "already published" | "already_published" => Self::AlreadyPublished,
+            "yanked exists" | "yanked_exists" => Self::YankedExists,
+            "excluded by config" | "excluded_by_config" => Self::ExcludedByConfig,
+            _ => Self::Manual(reason.to_string()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for SkipReason {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "snake_case")]
+        enum Tagged {
+            AlreadyPublished,
+            YankedExists,
+            ExcludedByConfig,
+            Manual(String),
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum SkipReasonHelper {
+            Tagged(Tagged),
+            Legacy(String),
+        }
+
+        Ok(match SkipReasonHelper::deserialize(deserializer)? {
+            SkipReasonHelper::Tagged(Tagged::AlreadyPublished) => Self::AlreadyPublished,
+            SkipReasonHelper::Tagged(Tagged::YankedExists) => Self::YankedExists,
+            SkipReasonHelper::Tagged(Tagged::ExcludedByConfig) => Self::ExcludedByConfig,
+            SkipReasonHelper::Tagged(Tagged::Manual(reason)) => Self::Manual(reason),
+            SkipReasonHelper::Legacy(reason) => Self::from(reason.as_str()),
+        })
+    }
+}
+
+impl std::fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AlreadyPublished => f.write_str("already published"),
+            Self::YankedExists => f.write_str("version exists but is yanked"),
+            Self::ExcludedByConfig => f.write_str("excluded by configuration"),
+            Self::Manual(reason) => f.write_str(reason),
+        }
+    }
 }
 
 /// Classification of errors encountered during publishing.
@@ -1024,8 +1815,13 @@ pub enum PackageState {
 /// - HTTP 429 (Too Many Requests) ÃƒÂ¢Ã¢â‚¬Â Ã¢â‚¬â„¢ Retryable
 /// - HTTP 401/403 (Auth errors) ÃƒÂ¢Ã¢â‚¬Â Ã¢â‚¬â„¢ Permanent
 /// - HTTP 409 (Version conflict) ÃƒÂ¢Ã¢â‚¬Â Ã¢â‚¬â„¢ Permanent
-/// - Network timeouts ÃƒÂ¢Ã¢â‚¬Â Ã¢â‚¬â„¢ Retryable
+/// - Network timeouts ÃƒÂ¢Ã¢â‚¬Â Ã¢â‚¬â„¢ Ambiguous (the request may have landed)
 /// - Unknown errors ÃƒÂ¢Ã¢â‚¬Â Ã¢â‚¬â„¢ Ambiguous (triggers registry reconciliation)
+///
+/// [`ErrorClass::from_http_status`] and [`ErrorClass::from_io_error`] encode
+/// these rules (plus 408/5xx as Retryable and other 4xx as Permanent) so
+/// callers don't reimplement them; the status table lives in
+/// [`shipper_retry::ErrorClass::from_http_status`].
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum ErrorClass {
@@ -1034,6 +1830,67 @@ pub enum ErrorClass {
     Ambiguous,
 }
 
+impl ErrorClass {
+    /// Classify an HTTP status from a registry response.
+    ///
+    /// Delegates to [`shipper_retry::ErrorClass::from_http_status`], the
+    /// table the registry client uses too: 408, 429 and 5xx are `Retryable`,
+    /// other 4xx `Permanent`, anything else `Ambiguous`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use shipper_types::ErrorClass;
+    ///
+    /// assert_eq!(ErrorClass::from_http_status(429), ErrorClass::Retryable);
+    /// assert_eq!(ErrorClass::from_http_status(403), ErrorClass::Permanent);
+    /// assert_eq!(ErrorClass::from_http_status(302), ErrorClass::Ambiguous);
+    /// ```
+    pub fn from_http_status(status: u16) -> Self {
+        shipper_retry::ErrorClass::from_http_status(status).into()
+    }
+
+    /// Classify an I/O error from a network or subprocess operation.
+    ///
+    /// - timeouts → `Ambiguous`: the request may have reached the registry,
+    ///   as with the registry client's timed-out requests
+    /// - transient connection failures (refused, reset, aborted,
+    ///   unreachable, interrupted) → `Retryable`
+    /// - permission denied, not found and invalid input → `Permanent`
+    /// - anything else → `Ambiguous`
+    pub fn from_io_error(e: &std::io::Error) -> Self {
+        use std::io::ErrorKind;
+
+        match e.kind() {
+            ErrorKind::Interrupted
+            | ErrorKind::ConnectionRefused
+            | ErrorKind::ConnectionReset
+            | ErrorKind::ConnectionAborted
+            | ErrorKind::NotConnected
+            | ErrorKind::BrokenPipe
+            | ErrorKind::UnexpectedEof
+            | ErrorKind::AddrNotAvailable
+            | ErrorKind::NetworkDown
+            | ErrorKind::NetworkUnreachable
+            | ErrorKind::HostUnreachable => Self::Retryable,
+            ErrorKind::PermissionDenied | ErrorKind::NotFound | ErrorKind::InvalidInput => {
+                Self::Permanent
+            }
+            _ => Self::Ambiguous,
+        }
+    }
+}
+
+impl From<shipper_retry::ErrorClass> for ErrorClass {
+    fn from(class: shipper_retry::ErrorClass) -> Self {
+        match class {
+            shipper_retry::ErrorClass::Retryable => Self::Retryable,
+            shipper_retry::ErrorClass::Permanent => Self::Permanent,
+            shipper_retry::ErrorClass::Ambiguous => Self::Ambiguous,
+        }
+    }
+}
+
 /// Report of drift between the authoritative event log and the projected state.
 ///
 /// Per [`docs/INVARIANTS.md`](https://github.com/EffortlessMetrics/shipper/blob/main/docs/INVARIANTS.md),
@@ -1256,6 +2113,137 @@ pub struct ExecutionState {
     pub packages: BTreeMap<String, PackageProgress>,
 }
 
+/// Current execution-state schema version, written to
+/// [`ExecutionState::state_version`].
+pub const CURRENT_STATE_VERSION: &str = "shipper.state.v1";
+
+impl ExecutionState {
+    /// Rebuild the state a finished run left behind from its [`Receipt`].
+    ///
+    /// Each [`PackageReceipt`] becomes a `name@version` entry with the same
+    /// state and attempt count, stamped with the package's `finished_at`.
+    /// The run's `started_at`/`finished_at` become `created_at`/`updated_at`.
+    /// Receipts do not carry the per-attempt timeline, so
+    /// `attempt_history` is left empty.
+    pub fn from_receipt(receipt: &Receipt) -> Self {
+        let packages = receipt
+            .packages
+            .iter()
+            .map(|p| {
+                (
+                    format!("{}@{}", p.name, p.version),
+                    PackageProgress {
+                        name: p.name.clone(),
+                        version: p.version.clone(),
+                        attempts: p.attempts,
+                        state: p.state.clone(),
+                        last_updated_at: p.finished_at,
+                    },
+                )
+            })
+            .collect();
+        Self {
+            state_version: CURRENT_STATE_VERSION.to_string(),
+            plan_id: receipt.plan_id.clone(),
+            registry: receipt.registry.clone(),
+            created_at: receipt.started_at,
+            updated_at: receipt.finished_at,
+            attempt_history: Vec::new(),
+            packages,
+        }
+    }
+
+    /// Whether every package has reached a state that ends the run.
+    ///
+    /// `Published`, `Skipped`, `Failed`, `Ambiguous` and `Cancelled` are
+    /// terminal; `Pending` and `Uploaded` are not. `Cancelled` is terminal
+    /// but resumable: a later run picks those packages up again.
+    pub fn is_complete(&self) -> bool {
+        self.packages
+            .values()
+            .all(|p| !matches!(p.state, PackageState::Pending | PackageState::Uploaded))
+    }
+
+    /// Record that the state changed now by bumping `updated_at`.
+    pub fn touch(&mut self) {
+        self.updated_at = Utc::now();
+    }
+
+    /// Insert or replace the progress for `key` (`name@version`) and
+    /// [`touch`](Self::touch) the state. Returns the previous progress.
+    pub fn set_package(
+        &mut self,
+        key: impl Into<String>,
+        progress: PackageProgress,
+    ) -> Option<PackageProgress> {
+        let previous = self.packages.insert(key.into(), progress);
+        self.touch();
+        previous
+    }
+
+    /// Time between `created_at` and the last update, zero if the clock
+    /// went backwards.
+    pub fn elapsed(&self) -> Duration {
+        (self.updated_at - self.created_at)
+            .to_std()
+            .unwrap_or_default()
+    }
+}
+
+/// A resume point: the stored plan together with the state written for it.
+///
+/// [`Checkpoint::validate`] catches a stale or foreign state directory
+/// before anything is published.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub plan: ReleasePlan,
+    pub state: ExecutionState,
+}
+
+impl Checkpoint {
+    /// Check that `state` was written for `plan`.
+    ///
+    /// The plan's `plan_version` must be supported
+    /// ([`ReleasePlan::validate_version`]), the `plan_id`s must match
+    /// ([`ReleasePlan::matches_id`]) and every `state.packages` key
+    /// (`name@version`) must name a package in the plan. Plan packages with
+    /// no state entry yet are fine; they have simply not started.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        self.plan.validate_version()?;
+
+        if !self.plan.matches_id(&self.state.plan_id) {
+            anyhow::bail!(
+                "state plan_id {} does not match plan_id {}; the state directory belongs to a different plan",
+                self.state.plan_id,
+                self.plan.plan_id
+            );
+        }
+
+        let planned: std::collections::BTreeSet<String> = self
+            .plan
+            .packages
+            .iter()
+            .map(|p| format!("{}@{}", p.name, p.version))
+            .collect();
+        let unknown: Vec<&str> = self
+            .state
+            .packages
+            .keys()
+            .filter(|key| !planned.contains(*key))
+            .map(String::as_str)
+            .collect();
+        if !unknown.is_empty() {
+            anyhow::bail!(
+                "state references packages not in plan {}: {}",
+                self.plan.plan_id,
+                unknown.join(", ")
+            );
+        }
+
+        Ok(())
+    }
+}
+
 /// Receipt for a successfully published package.
 ///
 /// This contains all evidence and metadata for a published crate,
@@ -1283,6 +2271,7 @@ pub struct ExecutionState {
 /// ///     compromised_at: None,
 ///     compromised_by: None,
 ///     superseded_by: None,
+///     artifact_sha256: None,
 /// };
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1316,6 +2305,31 @@ pub struct PackageReceipt {
     /// (PR 3); `None` before that PR lands OR when no fix release exists.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub superseded_by: Option<String>,
+    /// Hex-encoded SHA-256 of the `.crate` tarball shipper uploaded.
+    /// Lets auditors check that the registry serves the exact bytes that
+    /// were built. `None` for packages that were skipped, reconciled, or
+    /// published before this field existed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artifact_sha256: Option<String>,
+}
+
+impl PackageReceipt {
+    /// `duration_ms` formatted by [`format_duration_ms`].
+    pub fn human_duration(&self) -> String {
+        format_duration_ms(self.duration_ms)
+    }
+}
+
+/// Format a millisecond span for people: `500ms`, `1m 5s`, `2h 3m 4s`.
+///
+/// Spans under a second keep millisecond precision; longer spans are
+/// truncated to whole seconds.
+pub fn format_duration_ms(ms: u128) -> String {
+    if ms < 1000 {
+        return format!("{ms}ms");
+    }
+    let secs = u64::try_from(ms / 1000).unwrap_or(u64::MAX);
+    humantime::format_duration(Duration::from_secs(secs)).to_string()
 }
 
 /// Evidence collected during package publishing.
@@ -1539,6 +2553,36 @@ pub struct Receipt {
     pub execution_result: ExecutionResult,
 }
 
+impl Receipt {
+    /// Wall-clock length of the run, `started_at` to `finished_at`,
+    /// formatted by [`format_duration_ms`].
+    pub fn human_duration(&self) -> String {
+        let ms = (self.finished_at - self.started_at)
+            .num_milliseconds()
+            .max(0);
+        format_duration_ms(ms as u128)
+    }
+
+    /// A copy with packages sorted by name, then version, so receipts that
+    /// differ only in execution order compare equal. The live receipt keeps
+    /// execution order.
+    pub fn canonicalize(&self) -> Receipt {
+        let mut receipt = self.clone();
+        receipt
+            .packages
+            .sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+        receipt
+    }
+
+    /// Pretty JSON of [`Self::canonicalize`] with every object's keys
+    /// sorted, for byte-for-byte comparison of receipts across runs.
+    pub fn to_canonical_json(&self) -> anyhow::Result<String> {
+        let mut value = serde_json::to_value(self.canonicalize())?;
+        value.sort_all_objects();
+        Ok(serde_json::to_string_pretty(&value)?)
+    }
+}
+
 // Event types for evidence-first receipts
 
 /// An event in the publish event log.
@@ -1651,6 +2695,9 @@ pub enum EventType {
     PackageSkipped {
         reason: String,
     },
+    PackageCancelled {
+        at_state: String,
+    },
 
     // Operator-wait visibility. Emitted before Shipper deliberately sleeps so
     // status/watch consumers can tell the difference between "stuck" and
@@ -1828,6 +2875,101 @@ pub enum EventType {
     },
 }
 
+/// The variant of an [`EventType`] without its data.
+///
+/// Serializes with the same snake_case name as the event's `type` tag, so it
+/// can be used to filter event logs by kind (see [`EventType::kind`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    PlanCreated,
+    ExecutionStarted,
+    ExecutionFinished,
+    AuthEvidenceRecorded,
+    PackageStarted,
+    PackageAttempted,
+    PackageOutput,
+    PackagePublished,
+    PackageFailed,
+    PackageSkipped,
+    PackageCancelled,
+    PublishWaiting,
+    RateLimitObserved,
+    PublishReconciling,
+    PublishReconciled,
+    StateEventDriftDetected,
+    PackageYanked,
+    RehearsalStarted,
+    RehearsalPackagePublished,
+    RehearsalPackageFailed,
+    RehearsalComplete,
+    RehearsalSmokeCheckStarted,
+    RehearsalSmokeCheckSucceeded,
+    RehearsalSmokeCheckFailed,
+    RetryBackoffStarted,
+    RetryScheduled,
+    ReadinessStarted,
+    ReadinessPoll,
+    ReadinessPollScheduled,
+    ReadinessComplete,
+    ReadinessTimeout,
+    IndexReadinessStarted,
+    IndexReadinessCheck,
+    IndexReadinessComplete,
+    PreflightStarted,
+    PreflightWorkspaceVerify,
+    PreflightNewCrateDetected,
+    PreflightOwnershipCheck,
+    PreflightComplete,
+}
+
+impl EventType {
+    /// The data-free [`EventKind`] of this event.
+    pub fn kind(&self) -> EventKind {
+        match self {
+            Self::PlanCreated { .. } => EventKind::PlanCreated,
+            Self::ExecutionStarted => EventKind::ExecutionStarted,
+            Self::ExecutionFinished { .. } => EventKind::ExecutionFinished,
+            Self::AuthEvidenceRecorded { .. } => EventKind::AuthEvidenceRecorded,
+            Self::PackageStarted { .. } => EventKind::PackageStarted,
+            Self::PackageAttempted { .. } => EventKind::PackageAttempted,
+            Self::PackageOutput { .. } => EventKind::PackageOutput,
+            Self::PackagePublished { .. } => EventKind::PackagePublished,
+            Self::PackageFailed { .. } => EventKind::PackageFailed,
+            Self::PackageSkipped { .. } => EventKind::PackageSkipped,
+            Self::PackageCancelled { .. } => EventKind::PackageCancelled,
+            Self::PublishWaiting { .. } => EventKind::PublishWaiting,
+            Self::RateLimitObserved { .. } => EventKind::RateLimitObserved,
+            Self::PublishReconciling { .. } => EventKind::PublishReconciling,
+            Self::PublishReconciled { .. } => EventKind::PublishReconciled,
+            Self::StateEventDriftDetected { .. } => EventKind::StateEventDriftDetected,
+            Self::PackageYanked { .. } => EventKind::PackageYanked,
+            Self::RehearsalStarted { .. } => EventKind::RehearsalStarted,
+            Self::RehearsalPackagePublished { .. } => EventKind::RehearsalPackagePublished,
+            Self::RehearsalPackageFailed { .. } => EventKind::RehearsalPackageFailed,
+            Self::RehearsalComplete { .. } => EventKind::RehearsalComplete,
+            Self::RehearsalSmokeCheckStarted { .. } => EventKind::RehearsalSmokeCheckStarted,
+            Self::RehearsalSmokeCheckSucceeded { .. } => EventKind::RehearsalSmokeCheckSucceeded,
+            Self::RehearsalSmokeCheckFailed { .. } => EventKind::RehearsalSmokeCheckFailed,
+            Self::RetryBackoffStarted { .. } => EventKind::RetryBackoffStarted,
+            Self::RetryScheduled { .. } => EventKind::RetryScheduled,
+            Self::ReadinessStarted { .. } => EventKind::ReadinessStarted,
+            Self::ReadinessPoll { .. } => EventKind::ReadinessPoll,
+            Self::ReadinessPollScheduled { .. } => EventKind::ReadinessPollScheduled,
+            Self::ReadinessComplete { .. } => EventKind::ReadinessComplete,
+            Self::ReadinessTimeout { .. } => EventKind::ReadinessTimeout,
+            Self::IndexReadinessStarted { .. } => EventKind::IndexReadinessStarted,
+            Self::IndexReadinessCheck { .. } => EventKind::IndexReadinessCheck,
+            Self::IndexReadinessComplete { .. } => EventKind::IndexReadinessComplete,
+            Self::PreflightStarted => EventKind::PreflightStarted,
+            Self::PreflightWorkspaceVerify { .. } => EventKind::PreflightWorkspaceVerify,
+            Self::PreflightNewCrateDetected { .. } => EventKind::PreflightNewCrateDetected,
+            Self::PreflightOwnershipCheck { .. } => EventKind::PreflightOwnershipCheck,
+            Self::PreflightComplete { .. } => EventKind::PreflightComplete,
+        }
+    }
+}
+
 /// The result of a publish execution.
 ///
 /// This summarizes the overall outcome of attempting to publish
@@ -2167,6 +3309,93 @@ mod tests {
         assert_eq!(config.jitter_factor, 0.25);
     }
 
+    #[test]
+    fn parallel_config_resolve_concurrency_auto_and_explicit() {
+        let auto = ParallelConfig {
+            max_concurrent: 0,
+            ..ParallelConfig::default()
+        };
+        let cpus = std::thread::available_parallelism().map_or(1, |n| n.get());
+        assert!(auto.resolve_concurrency() >= 1);
+        assert_eq!(auto.resolve_concurrency(), cpus);
+
+        assert_eq!(ParallelConfig::default().resolve_concurrency(), 4);
+    }
+
+    fn unjittered_readiness(max_total_wait: Duration) -> ReadinessConfig {
+        ReadinessConfig {
+            initial_delay: Duration::from_secs(1),
+            poll_interval: Duration::from_secs(2),
+            max_delay: Duration::from_secs(10),
+            max_total_wait,
+            jitter_factor: 0.0,
+            ..ReadinessConfig::default()
+        }
+    }
+
+    #[test]
+    fn readiness_backoff_delay_doubles_and_caps() {
+        let config = unjittered_readiness(Duration::from_mins(5));
+        let delays: Vec<_> = (1..=6).map(|a| config.backoff_delay(a)).collect();
+        assert_eq!(
+            delays,
+            [2, 4, 8, 10, 10, 10].map(Duration::from_secs).to_vec()
+        );
+        assert_eq!(config.backoff_delay(0), config.backoff_delay(1));
+        assert_eq!(config.backoff_delay(u32::MAX), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn readiness_poll_schedule_caps_and_respects_total_wait() {
+        let config = unjittered_readiness(Duration::from_secs(40));
+        let schedule = config.poll_schedule();
+        assert_eq!(
+            schedule,
+            [1, 2, 4, 8, 10, 10].map(Duration::from_secs).to_vec()
+        );
+        assert!(schedule.iter().sum::<Duration>() <= config.max_total_wait);
+        assert!(schedule[1..].iter().all(|d| *d <= config.max_delay));
+    }
+
+    #[test]
+    fn readiness_poll_schedule_allows_exact_total_wait() {
+        let config = unjittered_readiness(Duration::from_secs(15));
+        assert_eq!(
+            config.poll_schedule(),
+            [1, 2, 4, 8].map(Duration::from_secs).to_vec()
+        );
+    }
+
+    #[test]
+    fn readiness_poll_schedule_empty_when_initial_delay_exceeds_budget() {
+        let mut config = unjittered_readiness(Duration::from_secs(5));
+        config.initial_delay = Duration::from_secs(6);
+        assert!(config.poll_schedule().is_empty());
+    }
+
+    #[test]
+    fn readiness_poll_schedule_terminates_with_zero_poll_interval() {
+        let mut config = unjittered_readiness(Duration::from_secs(5));
+        config.poll_interval = Duration::ZERO;
+        assert_eq!(
+            config.poll_schedule(),
+            vec![Duration::from_secs(1), Duration::ZERO]
+        );
+    }
+
+    #[test]
+    fn readiness_poll_schedule_jitter_stays_in_bounds() {
+        let mut config = unjittered_readiness(Duration::from_mins(5));
+        config.jitter_factor = 0.5;
+        let schedule = config.poll_schedule();
+        assert_eq!(schedule[0], config.initial_delay);
+        for (i, delay) in schedule.iter().enumerate().skip(1) {
+            let base = config.backoff_delay(i as u32);
+            assert!(*delay >= base.mul_f64(0.5) && *delay <= base.mul_f64(1.5));
+        }
+        assert!(schedule.iter().sum::<Duration>() <= config.max_total_wait);
+    }
+
     // ===== PackageState transition tests =====
 
     #[test]
@@ -2199,7 +3428,7 @@ mod tests {
     #[test]
     fn package_state_pending_to_skipped_is_valid() {
         let skipped = PackageState::Skipped {
-            reason: "already published".to_string(),
+            reason: SkipReason::AlreadyPublished,
         };
         assert!(matches!(skipped, PackageState::Skipped { .. }));
     }
@@ -2238,13 +3467,13 @@ mod tests {
     #[test]
     fn package_state_skipped_equality_by_reason() {
         let s1 = PackageState::Skipped {
-            reason: "exists".to_string(),
+            reason: SkipReason::Manual("exists".to_string()),
         };
         let s2 = PackageState::Skipped {
-            reason: "exists".to_string(),
+            reason: SkipReason::Manual("exists".to_string()),
         };
         let s3 = PackageState::Skipped {
-            reason: "other".to_string(),
+            reason: SkipReason::Manual("other".to_string()),
         };
         assert_eq!(s1, s2);
         assert_ne!(s1, s3);
@@ -2339,6 +3568,49 @@ mod tests {
         assert!(!PublishRegime::Update.is_new_crate());
     }
 
+    // ===== ReleasePlan version validation =====
+
+    fn plan_with_version(plan_version: &str) -> ReleasePlan {
+        ReleasePlan {
+            plan_version: plan_version.to_string(),
+            plan_id: String::new(),
+            created_at: Utc::now(),
+            registry: Registry::crates_io(),
+            packages: vec![],
+            dependencies: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn validate_version_accepts_current_plan_version() {
+        plan_with_version(CURRENT_PLAN_VERSION)
+            .validate_version()
+            .expect("current version");
+    }
+
+    #[test]
+    fn validate_version_rejects_too_old_and_too_new_versions() {
+        let err = plan_with_version("shipper.plan.v0")
+            .validate_version()
+            .expect_err("too old");
+        assert!(err.to_string().contains("too old"), "{err}");
+
+        let err = plan_with_version("shipper.plan.v2")
+            .validate_version()
+            .expect_err("too new");
+        assert!(err.to_string().contains("upgrade shipper"), "{err}");
+    }
+
+    #[test]
+    fn validate_version_rejects_malformed_and_foreign_versions() {
+        for version in ["1", "", "shipper.plan.vx", "shipper.state.v1"] {
+            let err = plan_with_version(version)
+                .validate_version()
+                .expect_err(version);
+            assert!(err.to_string().contains("invalid plan version"), "{err}");
+        }
+    }
+
     // ===== ReleasePlan determinism =====
 
     #[test]
@@ -2494,6 +3766,195 @@ mod tests {
         assert_eq!(levels[0].packages.len(), 3);
     }
 
+    // ===== ReleasePlan::compute_id =====
+
+    fn plan_id_of(plan: &ReleasePlan) -> String {
+        ReleasePlan::compute_id(&plan.registry, &plan.packages, &plan.dependencies)
+    }
+
+    #[test]
+    fn compute_id_is_sha256_hex() {
+        let id = plan_id_of(&diff_plan(&[("a", "1.0.0")], &[]));
+        assert_eq!(id.len(), 64);
+        assert!(id.chars().all(|c| c.is_ascii_hexdigit()));
+    }
+
+    #[test]
+    fn compute_id_is_stable_when_packages_are_reordered() {
+        let plan = diff_plan(
+            &[("a", "1.0.0"), ("b", "1.0.0"), ("c", "2.0.0")],
+            &[("b", &["a"]), ("c", &["a", "b"])],
+        );
+        let mut reordered = plan.clone();
+        reordered.packages.reverse();
+        reordered
+            .dependencies
+            .insert("c".to_string(), vec!["b".to_string(), "a".to_string()]);
+        assert_eq!(plan_id_of(&plan), plan_id_of(&reordered));
+    }
+
+    #[test]
+    fn compute_id_changes_when_a_version_changes() {
+        let old = diff_plan(&[("a", "1.0.0"), ("b", "1.0.0")], &[("b", &["a"])]);
+        let new = diff_plan(&[("a", "1.0.1"), ("b", "1.0.0")], &[("b", &["a"])]);
+        assert_ne!(plan_id_of(&old), plan_id_of(&new));
+    }
+
+    #[test]
+    fn compute_id_changes_when_a_dependency_edge_changes() {
+        let old = diff_plan(&[("a", "1.0.0"), ("b", "1.0.0")], &[("b", &["a"])]);
+        let new = diff_plan(&[("a", "1.0.0"), ("b", "1.0.0")], &[]);
+        assert_ne!(plan_id_of(&old), plan_id_of(&new));
+    }
+
+    #[test]
+    fn compute_id_changes_with_registry_name_and_api_base() {
+        let plan = diff_plan(&[("a", "1.0.0")], &[]);
+        let mut renamed = plan.clone();
+        renamed.registry.name = "mirror".to_string();
+        let mut moved = plan.clone();
+        moved.registry.api_base = "https://mirror.example.com".to_string();
+        assert_ne!(plan_id_of(&plan), plan_id_of(&renamed));
+        assert_ne!(plan_id_of(&plan), plan_id_of(&moved));
+    }
+
+    #[test]
+    fn compute_id_ignores_created_at_and_manifest_paths() {
+        let plan = diff_plan(&[("a", "1.0.0")], &[]);
+        let mut other = plan.clone();
+        other.created_at = plan.created_at - chrono::Duration::days(1);
+        other.plan_version = "shipper.plan.v2".to_string();
+        other.packages[0].manifest_path = PathBuf::from("elsewhere/Cargo.toml");
+        assert_eq!(plan_id_of(&plan), plan_id_of(&other));
+    }
+
+    #[test]
+    fn legacy_id_keeps_the_pre_compute_id_hash() {
+        let plan = diff_plan(&[("a", "1.0.0"), ("b", "1.0.0")], &[("b", &["a"])]);
+        assert_eq!(
+            ReleasePlan::legacy_id(&plan.registry.api_base, &plan.packages),
+            "6348f396eee885c671300ef9a10172cd2752450c19cab23cc296c9e29cc2da03"
+        );
+    }
+
+    #[test]
+    fn matches_id_accepts_current_and_legacy_ids_only() {
+        let mut plan = diff_plan(&[("a", "1.0.0"), ("b", "1.0.0")], &[("b", &["a"])]);
+        plan.plan_id = plan_id_of(&plan);
+        let legacy = ReleasePlan::legacy_id(&plan.registry.api_base, &plan.packages);
+
+        assert!(plan.matches_id(&plan.plan_id.clone()));
+        assert!(plan.matches_id(&legacy));
+        assert!(!plan.matches_id("0000"));
+    }
+
+    // ===== ReleasePlan::diff =====
+
+    fn diff_plan(packages: &[(&str, &str)], deps: &[(&str, &[&str])]) -> ReleasePlan {
+        ReleasePlan {
+            plan_version: "shipper.plan.v1".to_string(),
+            plan_id: "diff".to_string(),
+            created_at: Utc::now(),
+            registry: Registry::crates_io(),
+            packages: packages
+                .iter()
+                .map(|(name, version)| PlannedPackage {
+                    name: name.to_string(),
+                    version: version.to_string(),
+                    manifest_path: PathBuf::from(format!("{name}/Cargo.toml")),
+                    regime: None,
+                })
+                .collect(),
+            dependencies: deps
+                .iter()
+                .map(|(name, on)| (name.to_string(), on.iter().map(|d| d.to_string()).collect()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn plan_diff_identical_plans_is_empty() {
+        let plan = diff_plan(&[("a", "1.0.0"), ("b", "1.0.0")], &[("b", &["a"])]);
+        let diff = plan.diff(&plan.clone());
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "no semantic changes\n");
+    }
+
+    #[test]
+    fn plan_diff_reports_added_package() {
+        let old = diff_plan(&[("a", "1.0.0")], &[]);
+        let new = diff_plan(&[("a", "1.0.0"), ("b", "0.1.0")], &[("b", &["a"])]);
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.added, vec!["b@0.1.0".to_string()]);
+        assert!(diff.removed.is_empty());
+        assert_eq!(
+            diff.added_dependencies,
+            vec![("b".to_string(), "a".to_string())]
+        );
+        assert_eq!(diff.to_string(), "+ package b@0.1.0\n+ dependency b -> a\n");
+
+        let reverse = new.diff(&old);
+        assert_eq!(reverse.removed, vec!["b@0.1.0".to_string()]);
+        assert_eq!(
+            reverse.removed_dependencies,
+            vec![("b".to_string(), "a".to_string())]
+        );
+    }
+
+    #[test]
+    fn plan_diff_reports_version_bump() {
+        let old = diff_plan(&[("a", "1.0.0"), ("b", "1.0.0")], &[]);
+        let new = diff_plan(&[("a", "1.1.0"), ("b", "1.0.0")], &[]);
+
+        let diff = old.diff(&new);
+        assert_eq!(
+            diff.version_changes,
+            vec![VersionChange {
+                name: "a".to_string(),
+                from: "1.0.0".to_string(),
+                to: "1.1.0".to_string(),
+            }]
+        );
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+        assert_eq!(diff.to_string(), "~ a: 1.0.0 -> 1.1.0\n");
+    }
+
+    #[test]
+    fn plan_diff_ignores_reordering() {
+        let old = diff_plan(
+            &[("a", "1.0.0"), ("b", "1.0.0"), ("c", "1.0.0")],
+            &[("c", &["a", "b"])],
+        );
+        let mut new = diff_plan(
+            &[("b", "1.0.0"), ("a", "1.0.0"), ("c", "1.0.0")],
+            &[("c", &["b", "a"])],
+        );
+        new.plan_id = "different".to_string();
+        new.packages[0].manifest_path = PathBuf::from("moved/b/Cargo.toml");
+
+        assert!(old.diff(&new).is_empty());
+    }
+
+    #[test]
+    fn plan_diff_reports_registry_change() {
+        let old = diff_plan(&[("a", "1.0.0")], &[]);
+        let mut new = old.clone();
+        new.registry = Registry {
+            name: "private".to_string(),
+            api_base: "https://registry.example.com".to_string(),
+            index_base: None,
+        };
+
+        let diff = old.diff(&new);
+        let change = diff.registry_change.clone().expect("registry change");
+        assert_eq!(change.to, "private (https://registry.example.com)");
+        assert!(
+            diff.to_string()
+                .starts_with("~ registry: crates-io (https://crates.io")
+        );
+    }
+
     // ===== Receipt serialization roundtrips =====
 
     #[test]
@@ -2522,6 +3983,7 @@ mod tests {
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             }],
             event_log_path: PathBuf::from(".shipper/events.jsonl"),
             git_context: None,
@@ -2535,12 +3997,159 @@ mod tests {
             auth_evidence: None,
             execution_result: ExecutionResult::Success,
         };
-        let json = serde_json::to_string(&receipt).unwrap();
-        let parsed: Receipt = serde_json::from_str(&json).unwrap();
-        assert!(matches!(
-            &parsed.packages[0].state,
-            PackageState::Ambiguous { message } if message.contains("readiness timed out")
-        ));
+        let json = serde_json::to_string(&receipt).unwrap();
+        let parsed: Receipt = serde_json::from_str(&json).unwrap();
+        assert!(matches!(
+            &parsed.packages[0].state,
+            PackageState::Ambiguous { message } if message.contains("readiness timed out")
+        ));
+    }
+
+    fn package_receipt_with_artifact(artifact_sha256: Option<String>) -> PackageReceipt {
+        let t = "2025-01-15T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        PackageReceipt {
+            name: "demo".to_string(),
+            version: "0.1.0".to_string(),
+            attempts: 1,
+            state: PackageState::Published,
+            started_at: t,
+            finished_at: t,
+            duration_ms: 10,
+            evidence: PackageEvidence {
+                attempts: vec![],
+                readiness_checks: vec![],
+            },
+            compromised_at: None,
+            compromised_by: None,
+            superseded_by: None,
+            artifact_sha256,
+        }
+    }
+
+    #[test]
+    fn package_receipt_artifact_sha256_roundtrip() {
+        let receipt = package_receipt_with_artifact(Some("ab".repeat(32)));
+        let json = serde_json::to_value(&receipt).unwrap();
+        assert_eq!(json["artifact_sha256"], "ab".repeat(32));
+
+        let parsed: PackageReceipt = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.artifact_sha256, Some("ab".repeat(32)));
+    }
+
+    #[test]
+    fn package_receipt_without_artifact_sha256_roundtrips() {
+        let receipt = package_receipt_with_artifact(None);
+        let json = serde_json::to_value(&receipt).unwrap();
+        assert!(json.get("artifact_sha256").is_none());
+
+        let parsed: PackageReceipt = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.artifact_sha256, None);
+    }
+
+    #[test]
+    fn format_duration_ms_covers_sub_second_minutes_and_hours() {
+        assert_eq!(format_duration_ms(0), "0ms");
+        assert_eq!(format_duration_ms(500), "500ms");
+        assert_eq!(format_duration_ms(65_000), "1m 5s");
+        assert_eq!(format_duration_ms(65_999), "1m 5s");
+        assert_eq!(
+            format_duration_ms(2 * 3_600_000 + 3 * 60_000 + 4_000),
+            "2h 3m 4s"
+        );
+        assert!(!format_duration_ms(u128::MAX).is_empty());
+    }
+
+    #[test]
+    fn package_receipt_human_duration() {
+        let mut receipt = package_receipt_with_artifact(None);
+        assert_eq!(receipt.human_duration(), "10ms");
+        receipt.duration_ms = 125_000;
+        assert_eq!(receipt.human_duration(), "2m 5s");
+    }
+
+    #[test]
+    fn receipt_human_duration_spans_whole_run() {
+        let started = "2025-01-15T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let mut receipt = Receipt {
+            receipt_version: "shipper.receipt.v1".to_string(),
+            plan_id: "timing".to_string(),
+            registry: Registry::crates_io(),
+            started_at: started,
+            finished_at: started + chrono::Duration::seconds(3 * 3600 + 7),
+            packages: vec![package_receipt_with_artifact(None)],
+            event_log_path: PathBuf::from(".shipper/events.jsonl"),
+            git_context: None,
+            environment: EnvironmentFingerprint {
+                shipper_version: "0.3.0".to_string(),
+                cargo_version: None,
+                rust_version: None,
+                os: "linux".to_string(),
+                arch: "x86_64".to_string(),
+            },
+            auth_evidence: None,
+            execution_result: ExecutionResult::Success,
+        };
+        assert_eq!(receipt.human_duration(), "3h 7s");
+
+        // A clock that stepped backwards never yields a negative span.
+        receipt.finished_at = started - chrono::Duration::seconds(1);
+        assert_eq!(receipt.human_duration(), "0ms");
+    }
+
+    #[test]
+    fn receipt_canonical_json_ignores_package_order() {
+        let t = "2025-01-15T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let package = |name: &str, version: &str| PackageReceipt {
+            name: name.to_string(),
+            version: version.to_string(),
+            ..package_receipt_with_artifact(None)
+        };
+        let receipt = |packages| Receipt {
+            receipt_version: "shipper.receipt.v2".to_string(),
+            plan_id: "canonical".to_string(),
+            registry: Registry::crates_io(),
+            started_at: t,
+            finished_at: t,
+            packages,
+            event_log_path: PathBuf::from(".shipper/events.jsonl"),
+            git_context: None,
+            environment: EnvironmentFingerprint {
+                shipper_version: "0.3.0".to_string(),
+                cargo_version: None,
+                rust_version: None,
+                os: "linux".to_string(),
+                arch: "x86_64".to_string(),
+            },
+            auth_evidence: None,
+            execution_result: ExecutionResult::Success,
+        };
+
+        let a = receipt(vec![
+            package("core", "0.2.0"),
+            package("app", "1.0.0"),
+            package("core", "0.1.0"),
+        ]);
+        let b = receipt(vec![
+            package("core", "0.1.0"),
+            package("core", "0.2.0"),
+            package("app", "1.0.0"),
+        ]);
+
+        let json = a.to_canonical_json().unwrap();
+        assert_eq!(json, b.to_canonical_json().unwrap());
+
+        let order: Vec<_> = a
+            .canonicalize()
+            .packages
+            .iter()
+            .map(|p| format!("{}@{}", p.name, p.version))
+            .collect();
+        assert_eq!(order, ["app@1.0.0", "core@0.1.0", "core@0.2.0"]);
+        assert_eq!(a.packages[0].name, "core", "live receipt keeps its order");
+
+        // Keys are sorted, not in struct order (`name` is declared first).
+        let attempts = json.find("\"attempts\"").unwrap();
+        assert!(attempts < json.find("\"name\"").unwrap());
     }
 
     #[test]
@@ -2578,7 +4187,7 @@ mod tests {
             PackageState::Uploaded,
             PackageState::Pending,
             PackageState::Skipped {
-                reason: "exists".to_string(),
+                reason: SkipReason::Manual("exists".to_string()),
             },
             PackageState::Failed {
                 class: ErrorClass::Permanent,
@@ -2606,6 +4215,7 @@ mod tests {
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             })
             .collect();
         let receipt = Receipt {
@@ -2675,10 +4285,28 @@ mod tests {
         assert_eq!(VerifyMode::default(), VerifyMode::Workspace);
     }
 
+    #[test]
+    fn verify_mode_package_just_in_time_roundtrips() {
+        let json = serde_json::to_string(&VerifyMode::PackageJustInTime).unwrap();
+        assert_eq!(json, r#""package_just_in_time""#);
+        let parsed: VerifyMode = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, VerifyMode::PackageJustInTime);
+    }
+
     #[test]
     fn verify_mode_exhaustive_serde() {
-        let modes = [VerifyMode::Workspace, VerifyMode::Package, VerifyMode::None];
-        let expected_json = [r#""workspace""#, r#""package""#, r#""none""#];
+        let modes = [
+            VerifyMode::Workspace,
+            VerifyMode::Package,
+            VerifyMode::PackageJustInTime,
+            VerifyMode::None,
+        ];
+        let expected_json = [
+            r#""workspace""#,
+            r#""package""#,
+            r#""package_just_in_time""#,
+            r#""none""#,
+        ];
         for (mode, expected) in modes.iter().zip(expected_json.iter()) {
             let json = serde_json::to_string(mode).unwrap();
             assert_eq!(&json, expected);
@@ -2742,7 +4370,7 @@ mod tests {
             PackageState::Uploaded,
             PackageState::Published,
             PackageState::Skipped {
-                reason: "r".to_string(),
+                reason: SkipReason::Manual("r".to_string()),
             },
             PackageState::Failed {
                 class: ErrorClass::Ambiguous,
@@ -2774,6 +4402,10 @@ mod tests {
             skip_ownership_check: false,
             strict_ownership: false,
             no_verify: false,
+            features: Vec::new(),
+            no_default_features: false,
+            all_features: false,
+            cargo: Default::default(),
             max_attempts: 3,
             base_delay: Duration::from_secs(1),
             max_delay: Duration::from_mins(1),
@@ -2820,6 +4452,65 @@ mod tests {
         assert!(opts.resume_from.is_none());
     }
 
+    #[test]
+    fn runtime_options_default_matches_documented_defaults() {
+        let opts = RuntimeOptions::default();
+        assert_eq!(opts.policy, PublishPolicy::Safe);
+        assert_eq!(opts.verify_mode, VerifyMode::Workspace);
+        assert_eq!(opts.max_attempts, 6);
+        assert_eq!(opts.base_delay, Duration::from_secs(2));
+        assert_eq!(opts.max_delay, Duration::from_mins(2));
+        assert_eq!(
+            opts.retry_strategy,
+            shipper_retry::RetryStrategyType::Exponential
+        );
+        assert_eq!(opts.retry_jitter, 0.5);
+        assert_eq!(opts.verify_timeout, Duration::from_mins(2));
+        assert_eq!(opts.verify_poll_interval, Duration::from_secs(5));
+        assert_eq!(opts.state_dir, PathBuf::from(".shipper"));
+        assert_eq!(opts.output_lines, 50);
+        assert_eq!(opts.lock_timeout, Duration::from_hours(1));
+        assert!(opts.readiness.enabled);
+        assert!(!opts.parallel.enabled);
+        assert_eq!(opts.parallel.max_concurrent, 4);
+        assert!(!opts.encryption.enabled);
+        assert!(!opts.allow_dirty && !opts.no_verify && !opts.force && !opts.rehearsal_skip);
+        assert!(opts.features.is_empty() && opts.registries.is_empty());
+        assert!(opts.resume_from.is_none() && opts.rehearsal_registry.is_none());
+    }
+
+    #[test]
+    fn runtime_options_setters_override_only_their_field() {
+        let opts = RuntimeOptions::default()
+            .with_policy(PublishPolicy::Fast)
+            .with_verify_mode(VerifyMode::None)
+            .with_state_dir("/tmp/state")
+            .with_features(["a", "b"])
+            .with_max_attempts(2)
+            .with_retry_delays(Duration::from_millis(100), Duration::from_secs(1))
+            .with_parallel(ParallelConfig {
+                enabled: true,
+                ..ParallelConfig::default()
+            })
+            .with_output_lines(10)
+            .with_resume_from("core");
+
+        assert_eq!(opts.policy, PublishPolicy::Fast);
+        assert_eq!(opts.verify_mode, VerifyMode::None);
+        assert_eq!(opts.state_dir, PathBuf::from("/tmp/state"));
+        assert_eq!(opts.features, ["a", "b"]);
+        assert_eq!(opts.max_attempts, 2);
+        assert_eq!(opts.base_delay, Duration::from_millis(100));
+        assert_eq!(opts.max_delay, Duration::from_secs(1));
+        assert!(opts.parallel.enabled);
+        assert_eq!(opts.output_lines, 10);
+        assert_eq!(opts.resume_from.as_deref(), Some("core"));
+        // Untouched fields keep their defaults.
+        assert_eq!(opts.lock_timeout, Duration::from_hours(1));
+        assert!(opts.readiness.enabled);
+        assert!(!opts.allow_dirty);
+    }
+
     #[test]
     fn runtime_options_all_booleans_toggled() {
         let opts = RuntimeOptions {
@@ -2866,26 +4557,111 @@ mod tests {
         assert_eq!(opts.resume_from.as_deref(), Some("my-crate"));
     }
 
-    // ===== Registry =====
+    #[test]
+    fn runtime_options_serde_roundtrip() {
+        let opts = RuntimeOptions {
+            features: vec!["tls".to_string()],
+            base_delay: Duration::from_millis(1500),
+            registries: vec![Registry::crates_io()],
+            resume_from: Some("my-crate".to_string()),
+            rehearsal_registry: Some("staging".to_string()),
+            ..make_default_runtime_options()
+        };
+        let json = serde_json::to_value(&opts).expect("serialize");
+        assert_eq!(json["base_delay"], 1500);
+        assert_eq!(json["lock_timeout"], 3_600_000);
+
+        let parsed: RuntimeOptions = serde_json::from_value(json.clone()).expect("deserialize");
+        assert_eq!(parsed.base_delay, Duration::from_millis(1500));
+        assert_eq!(parsed.lock_timeout, Duration::from_hours(1));
+        assert_eq!(parsed.features, vec!["tls"]);
+        assert_eq!(parsed.resume_from.as_deref(), Some("my-crate"));
+        assert_eq!(serde_json::to_value(&parsed).expect("reserialize"), json);
+    }
 
     #[test]
-    fn registry_get_index_base_derives_from_api_https() {
-        let reg = Registry {
+    fn runtime_options_deserialize_without_newer_fields() {
+        let mut json = serde_json::to_value(make_default_runtime_options()).expect("serialize");
+        let obj = json.as_object_mut().expect("object");
+        obj.remove("registries");
+        obj.remove("webhook");
+        obj.remove("encryption");
+
+        let parsed: RuntimeOptions = serde_json::from_value(json).expect("deserialize");
+        assert!(parsed.registries.is_empty());
+        assert!(parsed.webhook.url.is_empty());
+        assert!(!parsed.encryption.enabled);
+    }
+
+    // ===== Registry =====
+
+    fn registry_without_index(api_base: &str) -> Registry {
+        Registry {
             name: "custom".to_string(),
-            api_base: "https://registry.example.com".to_string(),
+            api_base: api_base.to_string(),
             index_base: None,
-        };
-        assert_eq!(reg.get_index_base(), "https://index.registry.example.com");
+        }
+    }
+
+    #[test]
+    fn registry_resolve_index_base_derives_crates_io() {
+        let reg = registry_without_index("https://crates.io");
+        assert_eq!(reg.resolve_index_base().unwrap(), "https://index.crates.io");
+
+        let reg = registry_without_index("http://crates.io/");
+        assert_eq!(reg.resolve_index_base().unwrap(), "http://index.crates.io");
     }
 
     #[test]
-    fn registry_get_index_base_derives_from_api_http() {
+    fn registry_resolve_index_base_keeps_port_and_drops_path_for_crates_io() {
+        let reg = registry_without_index("https://crates.io:8443/api/v1/");
+        assert_eq!(
+            reg.resolve_index_base().unwrap(),
+            "https://index.crates.io:8443"
+        );
+    }
+
+    #[test]
+    fn registry_resolve_index_base_requires_index_for_other_hosts() {
+        for api_base in [
+            "https://my.registry.example.com",
+            "http://localhost:8080",
+            "https://registry.example.com/api/cargo/",
+        ] {
+            let reg = registry_without_index(api_base);
+            let err = reg.resolve_index_base().unwrap_err().to_string();
+            assert!(err.contains("set index_base"), "{api_base}: {err}");
+            assert!(err.contains(api_base), "{api_base}: {err}");
+        }
+    }
+
+    #[test]
+    fn registry_resolve_index_base_rejects_invalid_api_base() {
+        let reg = registry_without_index("not a url");
+        let err = reg.resolve_index_base().unwrap_err().to_string();
+        assert!(err.contains("invalid api_base"), "{err}");
+    }
+
+    #[test]
+    fn registry_resolve_index_base_prefers_explicit_value_with_port_and_path() {
         let reg = Registry {
             name: "local".to_string(),
             api_base: "http://localhost:8080".to_string(),
-            index_base: None,
+            index_base: Some("sparse+http://localhost:8080/index/".to_string()),
         };
-        assert_eq!(reg.get_index_base(), "http://index.localhost:8080");
+        assert_eq!(
+            reg.resolve_index_base().unwrap(),
+            "http://localhost:8080/index/"
+        );
+    }
+
+    #[test]
+    fn registry_get_index_base_falls_back_to_api_base() {
+        let reg = registry_without_index("https://registry.example.com/");
+        assert_eq!(reg.get_index_base(), "https://registry.example.com");
+
+        let reg = registry_without_index("http://localhost:8080");
+        assert_eq!(reg.get_index_base(), "http://localhost:8080");
     }
 
     #[test]
@@ -2898,6 +4674,47 @@ mod tests {
         assert_eq!(reg.get_index_base(), "https://my-index.example.com");
     }
 
+    #[test]
+    fn registry_sparse_index_url_for_crates_io() {
+        let reg = Registry::crates_io();
+        assert_eq!(
+            reg.sparse_index_url("serde"),
+            "https://index.crates.io/se/rd/serde"
+        );
+        assert_eq!(reg.sparse_index_url("Ab"), "https://index.crates.io/2/ab");
+        assert_eq!(
+            reg.resolve_sparse_index_url("tokio").unwrap(),
+            "https://index.crates.io/to/ki/tokio"
+        );
+    }
+
+    #[test]
+    fn registry_sparse_index_url_for_custom_index_strips_sparse_and_trailing_slash() {
+        let reg = Registry {
+            name: "local".to_string(),
+            api_base: "http://localhost:8080".to_string(),
+            index_base: Some("sparse+http://localhost:8080/index/".to_string()),
+        };
+        assert_eq!(
+            reg.resolve_sparse_index_url("my-crate").unwrap(),
+            "http://localhost:8080/index/my/-c/my-crate"
+        );
+        assert_eq!(
+            reg.sparse_index_url("abc"),
+            "http://localhost:8080/index/3/a/abc"
+        );
+    }
+
+    #[test]
+    fn registry_resolve_sparse_index_url_requires_resolvable_index() {
+        let reg = registry_without_index("https://registry.example.com/");
+        assert!(reg.resolve_sparse_index_url("demo").is_err());
+        assert_eq!(
+            reg.sparse_index_url("demo"),
+            "https://registry.example.com/de/mo/demo"
+        );
+    }
+
     #[test]
     fn registry_crates_io_get_index_base() {
         let reg = Registry::crates_io();
@@ -2938,6 +4755,62 @@ mod tests {
         assert_eq!(original, cloned);
     }
 
+    #[test]
+    fn error_class_from_io_error_kinds() {
+        use std::io::{Error, ErrorKind};
+
+        for kind in [
+            ErrorKind::Interrupted,
+            ErrorKind::ConnectionRefused,
+            ErrorKind::ConnectionReset,
+            ErrorKind::ConnectionAborted,
+            ErrorKind::NotConnected,
+            ErrorKind::BrokenPipe,
+            ErrorKind::UnexpectedEof,
+            ErrorKind::AddrNotAvailable,
+            ErrorKind::NetworkDown,
+            ErrorKind::NetworkUnreachable,
+            ErrorKind::HostUnreachable,
+        ] {
+            assert_eq!(
+                ErrorClass::from_io_error(&Error::from(kind)),
+                ErrorClass::Retryable,
+                "{kind:?}"
+            );
+        }
+        for kind in [
+            ErrorKind::PermissionDenied,
+            ErrorKind::NotFound,
+            ErrorKind::InvalidInput,
+        ] {
+            assert_eq!(
+                ErrorClass::from_io_error(&Error::from(kind)),
+                ErrorClass::Permanent,
+                "{kind:?}"
+            );
+        }
+        for kind in [
+            ErrorKind::TimedOut,
+            ErrorKind::WouldBlock,
+            ErrorKind::Other,
+            ErrorKind::InvalidData,
+            ErrorKind::AlreadyExists,
+            ErrorKind::OutOfMemory,
+        ] {
+            assert_eq!(
+                ErrorClass::from_io_error(&Error::from(kind)),
+                ErrorClass::Ambiguous,
+                "{kind:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn error_class_from_io_error_uses_kind_not_message() {
+        let err = std::io::Error::other("operation timed out");
+        assert_eq!(ErrorClass::from_io_error(&err), ErrorClass::Ambiguous);
+    }
+
     // ===== ExecutionResult =====
 
     #[test]
@@ -3031,11 +4904,11 @@ mod tests {
     #[test]
     fn package_state_skipped_json_contains_reason() {
         let state = PackageState::Skipped {
-            reason: "version exists".to_string(),
+            reason: SkipReason::Manual("version exists".to_string()),
         };
         let json = serde_json::to_string(&state).unwrap();
         assert!(json.contains(r#""state":"skipped""#));
-        assert!(json.contains(r#""reason":"version exists""#));
+        assert!(json.contains(r#""reason":{"manual":"version exists"}"#));
     }
 
     #[test]
@@ -3048,6 +4921,307 @@ mod tests {
         assert_eq!(parsed, state);
     }
 
+    #[test]
+    fn package_state_cancelled_serde_roundtrip() {
+        let state = PackageState::Cancelled {
+            at_state: "uploaded".to_string(),
+        };
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(json, r#"{"state":"cancelled","at_state":"uploaded"}"#);
+        let parsed: PackageState = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, state);
+    }
+
+    #[test]
+    fn skip_reason_serializes_each_variant() {
+        let cases = [
+            (SkipReason::AlreadyPublished, r#""already_published""#),
+            (SkipReason::YankedExists, r#""yanked_exists""#),
+            (SkipReason::ExcludedByConfig, r#""excluded_by_config""#),
+            (
+                SkipReason::Manual("operator request".to_string()),
+                r#"{"manual":"operator request"}"#,
+            ),
+        ];
+        for (reason, expected) in cases {
+            let json = serde_json::to_string(&reason).unwrap();
+            assert_eq!(json, expected);
+            let parsed: SkipReason = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, reason);
+        }
+    }
+
+    #[test]
+    fn package_state_skipped_accepts_legacy_string_reason() {
+        let known: PackageState =
+            serde_json::from_str(r#"{"state":"skipped","reason":"already published"}"#).unwrap();
+        assert_eq!(
+            known,
+            PackageState::Skipped {
+                reason: SkipReason::AlreadyPublished
+            }
+        );
+
+        let free_form: PackageState =
+            serde_json::from_str(r#"{"state":"skipped","reason":"version exists"}"#).unwrap();
+        assert_eq!(
+            free_form,
+            PackageState::Skipped {
+                reason: SkipReason::Manual("version exists".to_string())
+            }
+        );
+    }
+
+    #[test]
+    fn skip_reason_display_is_human_readable() {
+        assert_eq!(
+            SkipReason::AlreadyPublished.to_string(),
+            "already published"
+        );
+        assert_eq!(
+            SkipReason::Manual("custom".to_string()).to_string(),
+            "custom"
+        );
+    }
+
+    #[test]
+    fn event_kind_serializes_like_event_type_tag() {
+        let events = [
+            EventType::ExecutionStarted,
+            EventType::PackagePublished { duration_ms: 5 },
+            EventType::PackageSkipped {
+                reason: "already published".to_string(),
+            },
+            EventType::PackageCancelled {
+                at_state: "uploaded".to_string(),
+            },
+        ];
+        for event in events {
+            let value = serde_json::to_value(&event).unwrap();
+            let kind = serde_json::to_value(event.kind()).unwrap();
+            assert_eq!(value["type"], kind);
+        }
+    }
+
+    #[test]
+    fn event_type_package_cancelled_serde_roundtrip() {
+        let event = EventType::PackageCancelled {
+            at_state: "pending".to_string(),
+        };
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains(r#""type":"package_cancelled""#));
+        let parsed: EventType = serde_json::from_str(&json).unwrap();
+        assert!(matches!(
+            parsed,
+            EventType::PackageCancelled { at_state } if at_state == "pending"
+        ));
+    }
+
+    fn state_with(states: &[PackageState]) -> ExecutionState {
+        let packages = states
+            .iter()
+            .enumerate()
+            .map(|(i, state)| {
+                (
+                    format!("crate-{i}@1.0.0"),
+                    PackageProgress {
+                        name: format!("crate-{i}"),
+                        version: "1.0.0".to_string(),
+                        attempts: 1,
+                        state: state.clone(),
+                        last_updated_at: Utc::now(),
+                    },
+                )
+            })
+            .collect();
+        ExecutionState {
+            state_version: "shipper.state.v1".to_string(),
+            plan_id: "plan".to_string(),
+            registry: Registry::crates_io(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            attempt_history: Vec::new(),
+            packages,
+        }
+    }
+
+    fn checkpoint_for(packages: &[(&str, &str)], state: ExecutionState) -> Checkpoint {
+        let mut plan = diff_plan(packages, &[]);
+        plan.plan_id = "plan".to_string();
+        Checkpoint { plan, state }
+    }
+
+    #[test]
+    fn checkpoint_validates_matching_plan_and_state() {
+        let state = state_with(&[PackageState::Published, PackageState::Pending]);
+        let checkpoint = checkpoint_for(
+            &[
+                ("crate-0", "1.0.0"),
+                ("crate-1", "1.0.0"),
+                ("crate-2", "1.0.0"),
+            ],
+            state,
+        );
+        checkpoint.validate().expect("consistent checkpoint");
+    }
+
+    #[test]
+    fn checkpoint_rejects_mismatched_plan_id() {
+        let mut state = state_with(&[PackageState::Pending]);
+        state.plan_id = "stale".to_string();
+        let err = checkpoint_for(&[("crate-0", "1.0.0")], state)
+            .validate()
+            .expect_err("plan ids differ");
+        assert!(
+            err.to_string()
+                .contains("state plan_id stale does not match plan_id plan")
+        );
+    }
+
+    #[test]
+    fn checkpoint_rejects_state_for_unknown_package() {
+        let state = state_with(&[PackageState::Published, PackageState::Pending]);
+        // crate-1 was renamed; crate-0 is at a new version.
+        let err = checkpoint_for(&[("crate-0", "1.1.0"), ("crate-9", "1.0.0")], state)
+            .validate()
+            .expect_err("unknown packages");
+        assert_eq!(
+            err.to_string(),
+            "state references packages not in plan plan: crate-0@1.0.0, crate-1@1.0.0"
+        );
+    }
+
+    #[test]
+    fn checkpoint_rejects_unknown_plan_version_on_load() {
+        let state = state_with(&[PackageState::Pending]);
+        let mut checkpoint = checkpoint_for(&[("crate-0", "1.0.0")], state);
+        checkpoint.plan.plan_version = "shipper.plan.v99".to_string();
+        let json = serde_json::to_string(&checkpoint).expect("serialize");
+
+        let loaded: Checkpoint = serde_json::from_str(&json).expect("deserialize");
+        let err = loaded.validate().expect_err("plan from a newer shipper");
+        assert!(err.to_string().contains("shipper.plan.v99"), "{err}");
+    }
+
+    #[test]
+    fn execution_state_is_complete_treats_cancelled_as_terminal() {
+        let cancelled = PackageState::Cancelled {
+            at_state: "uploaded".to_string(),
+        };
+        assert!(state_with(&[PackageState::Published, cancelled]).is_complete());
+        assert!(!state_with(&[PackageState::Published, PackageState::Uploaded]).is_complete());
+        assert!(!state_with(&[PackageState::Pending]).is_complete());
+    }
+
+    #[test]
+    fn execution_state_set_package_advances_updated_at() {
+        let mut state = state_with(&[PackageState::Pending]);
+        let earlier = Utc::now() - chrono::Duration::minutes(5);
+        state.created_at = earlier;
+        state.updated_at = earlier;
+
+        let progress = PackageProgress {
+            name: "crate-0".to_string(),
+            version: "1.0.0".to_string(),
+            attempts: 2,
+            state: PackageState::Published,
+            last_updated_at: Utc::now(),
+        };
+        let previous = state.set_package("crate-0@1.0.0", progress);
+
+        assert_eq!(previous.map(|p| p.state), Some(PackageState::Pending));
+        assert_eq!(state.packages["crate-0@1.0.0"].attempts, 2);
+        assert!(state.updated_at > earlier);
+        assert!(state.elapsed() >= Duration::from_mins(5));
+    }
+
+    #[test]
+    fn execution_state_from_receipt_rebuilds_package_states() {
+        let started = "2025-01-15T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let package = |name: &str, attempts: u32, state: PackageState| PackageReceipt {
+            name: name.to_string(),
+            attempts,
+            state,
+            finished_at: started + chrono::Duration::seconds(attempts as i64),
+            ..package_receipt_with_artifact(None)
+        };
+        let receipt = Receipt {
+            receipt_version: "shipper.receipt.v2".to_string(),
+            plan_id: "replay".to_string(),
+            registry: Registry::crates_io(),
+            started_at: started,
+            finished_at: started + chrono::Duration::minutes(2),
+            packages: vec![
+                package("core", 1, PackageState::Published),
+                package(
+                    "cli",
+                    3,
+                    PackageState::Failed {
+                        class: ErrorClass::Permanent,
+                        message: "rejected".to_string(),
+                    },
+                ),
+                package("macros", 2, PackageState::Published),
+                package(
+                    "docs",
+                    0,
+                    PackageState::Skipped {
+                        reason: SkipReason::AlreadyPublished,
+                    },
+                ),
+            ],
+            event_log_path: PathBuf::from(".shipper/events.jsonl"),
+            git_context: None,
+            environment: EnvironmentFingerprint {
+                shipper_version: "0.3.0".to_string(),
+                cargo_version: None,
+                rust_version: None,
+                os: "linux".to_string(),
+                arch: "x86_64".to_string(),
+            },
+            auth_evidence: None,
+            execution_result: ExecutionResult::PartialFailure,
+        };
+
+        let state = ExecutionState::from_receipt(&receipt);
+
+        let keys_where = |pred: fn(&PackageState) -> bool| -> Vec<&str> {
+            state
+                .packages
+                .iter()
+                .filter(|(_, p)| pred(&p.state))
+                .map(|(k, _)| k.as_str())
+                .collect()
+        };
+        assert_eq!(
+            keys_where(|s| matches!(s, PackageState::Published)),
+            ["core@0.1.0", "macros@0.1.0"]
+        );
+        assert_eq!(
+            keys_where(|s| matches!(s, PackageState::Failed { .. })),
+            ["cli@0.1.0"]
+        );
+        assert_eq!(state.packages["cli@0.1.0"].attempts, 3);
+        assert_eq!(
+            state.packages["cli@0.1.0"].last_updated_at,
+            started + chrono::Duration::seconds(3)
+        );
+        assert_eq!(state.plan_id, "replay");
+        assert_eq!(state.state_version, CURRENT_STATE_VERSION);
+        assert_eq!(state.created_at, receipt.started_at);
+        assert_eq!(state.updated_at, receipt.finished_at);
+        assert!(state.attempt_history.is_empty());
+        assert!(state.is_complete());
+    }
+
+    #[test]
+    fn execution_state_elapsed_is_never_negative() {
+        let mut state = state_with(&[]);
+        state.touch();
+        state.created_at = state.updated_at + chrono::Duration::seconds(30);
+        assert_eq!(state.elapsed(), Duration::ZERO);
+    }
+
     // ===== EventType serde =====
 
     #[test]
@@ -3281,7 +5455,7 @@ mod tests {
                 (
                     "skipped",
                     PackageState::Skipped {
-                        reason: "already published".to_string(),
+                        reason: SkipReason::AlreadyPublished,
                     },
                 ),
                 (
@@ -3340,6 +5514,7 @@ mod tests {
                     compromised_at: None,
                     compromised_by: None,
                     superseded_by: None,
+                    artifact_sha256: None,
                 }],
                 event_log_path: PathBuf::from(".shipper/events.jsonl"),
                 git_context: Some(GitContext {
@@ -3571,6 +5746,7 @@ mod tests {
                         compromised_at: None,
                         compromised_by: None,
                         superseded_by: None,
+                        artifact_sha256: None,
                     },
                     PackageReceipt {
                         name: "api-server".to_string(),
@@ -3618,13 +5794,16 @@ mod tests {
                         compromised_at: None,
                         compromised_by: None,
                         superseded_by: None,
+                        artifact_sha256: None,
                     },
                     PackageReceipt {
                         name: "old-compat".to_string(),
                         version: "0.1.0".to_string(),
                         attempts: 0,
                         state: PackageState::Skipped {
-                            reason: "version already exists on registry".to_string(),
+                            reason: SkipReason::Manual(
+                                "version already exists on registry".to_string(),
+                            ),
                         },
                         started_at: t,
                         finished_at: t,
@@ -3636,6 +5815,7 @@ mod tests {
                         compromised_at: None,
                         compromised_by: None,
                         superseded_by: None,
+                        artifact_sha256: None,
                     },
                 ],
                 event_log_path: PathBuf::from(".shipper/events.jsonl"),
@@ -3682,6 +5862,7 @@ mod tests {
                     compromised_at: None,
                     compromised_by: None,
                     superseded_by: None,
+                    artifact_sha256: None,
                 }],
                 event_log_path: PathBuf::from(".shipper/events.jsonl"),
                 git_context: None,
@@ -3734,13 +5915,16 @@ mod tests {
                         compromised_at: None,
                         compromised_by: None,
                         superseded_by: None,
+                        artifact_sha256: None,
                     },
                     PackageReceipt {
                         name: "dependent-crate".to_string(),
                         version: "0.2.0".to_string(),
                         attempts: 0,
                         state: PackageState::Skipped {
-                            reason: "dependency broken-crate failed".to_string(),
+                            reason: SkipReason::Manual(
+                                "dependency broken-crate failed".to_string(),
+                            ),
                         },
                         started_at: t,
                         finished_at: t,
@@ -3752,6 +5936,7 @@ mod tests {
                         compromised_at: None,
                         compromised_by: None,
                         superseded_by: None,
+                        artifact_sha256: None,
                     },
                 ],
                 event_log_path: PathBuf::from(".shipper/events.jsonl"),
@@ -3903,7 +6088,7 @@ mod tests {
                     version: "0.1.0".to_string(),
                     attempts: 0,
                     state: PackageState::Skipped {
-                        reason: "version already on registry".to_string(),
+                        reason: SkipReason::Manual("version already on registry".to_string()),
                     },
                     last_updated_at: t,
                 },
@@ -4464,7 +6649,7 @@ mod tests {
 
             #[test]
             fn package_state_skipped_roundtrip(reason in "\\PC{0,50}") {
-                let state = PackageState::Skipped { reason };
+                let state = PackageState::Skipped { reason: SkipReason::Manual(reason) };
                 let json = serde_json::to_string(&state).unwrap();
                 let parsed: PackageState = serde_json::from_str(&json).unwrap();
                 assert_eq!(parsed, state);
@@ -4535,10 +6720,11 @@ mod tests {
 
             // --- VerifyMode roundtrip ---
             #[test]
-            fn verify_mode_roundtrip(variant in 0u8..3) {
+            fn verify_mode_roundtrip(variant in 0u8..4) {
                 let mode = match variant {
                     0 => VerifyMode::Workspace,
                     1 => VerifyMode::Package,
+                    2 => VerifyMode::PackageJustInTime,
                     _ => VerifyMode::None,
                 };
                 let json = serde_json::to_string(&mode).unwrap();
@@ -4648,7 +6834,7 @@ mod tests {
                     0 => PackageState::Pending,
                     1 => PackageState::Uploaded,
                     2 => PackageState::Published,
-                    _ => PackageState::Skipped { reason: "already exists".to_string() },
+                    _ => PackageState::Skipped { reason: SkipReason::Manual("already exists".to_string()) },
                 };
                 let progress = PackageProgress {
                     name,
@@ -4806,6 +6992,7 @@ mod tests {
                                     compromised_at: None,
                     compromised_by: None,
                     superseded_by: None,
+                    artifact_sha256: None,
                 };
                 let json = serde_json::to_string(&receipt).unwrap();
                 let parsed: PackageReceipt = serde_json::from_str(&json).unwrap();
@@ -4839,6 +7026,7 @@ mod tests {
                                             compromised_at: None,
                         compromised_by: None,
                         superseded_by: None,
+                        artifact_sha256: None,
                     })
                     .collect();
                 let receipt = Receipt {
@@ -4948,7 +7136,7 @@ mod tests {
                         message: "err".to_string(),
                     },
                     PackageState::Skipped {
-                        reason: "already published".to_string(),
+                        reason: SkipReason::AlreadyPublished,
                     },
                 ],
                 PackageState::Uploaded => vec![
@@ -4964,6 +7152,9 @@ mod tests {
                 PackageState::Failed { .. } => vec![
                     PackageState::Pending, // retry resets to Pending
                 ],
+                PackageState::Cancelled { .. } => vec![
+                    PackageState::Pending, // resume picks the package up again
+                ],
                 // Terminal states
                 PackageState::Published => vec![],
                 PackageState::Skipped { .. } => vec![],
@@ -4989,7 +7180,7 @@ mod tests {
                     0 => PackageState::Pending,
                     1 => PackageState::Uploaded,
                     2 => PackageState::Published,
-                    3 => PackageState::Skipped { reason: "exists".to_string() },
+                    3 => PackageState::Skipped { reason: SkipReason::Manual("exists".to_string()) },
                     4 => PackageState::Failed { class: ErrorClass::Retryable, message: "err".to_string() },
                     _ => PackageState::Ambiguous { message: "unclear".to_string() },
                 };
@@ -5126,6 +7317,7 @@ mod tests {
                     compromised_at: None,
                     compromised_by: None,
                     superseded_by: None,
+                    artifact_sha256: None,
                 })
                 .collect();
 
@@ -5194,7 +7386,7 @@ mod tests {
                 for i in 0..pkg_count {
                     let state_variant = match i % 3 {
                         0 => PackageState::Published,
-                        1 => PackageState::Skipped { reason: "exists".to_string() },
+                        1 => PackageState::Skipped { reason: SkipReason::Manual("exists".to_string()) },
                         _ => PackageState::Failed {
                             class: ErrorClass::Permanent,
                             message: "auth failure".to_string(),
@@ -5384,6 +7576,10 @@ mod tests {
                     skip_ownership_check: false,
                     strict_ownership: false,
                     no_verify: false,
+                    features: Vec::new(),
+                    no_default_features: false,
+                    all_features: false,
+                    cargo: Default::default(),
                     max_attempts: 3,
                     base_delay: Duration::from_millis(base_delay_ms),
                     max_delay: Duration::from_millis(max_delay_ms),
@@ -5455,7 +7651,7 @@ mod tests {
                     .map(|i| {
                         let state = match i % 5 {
                             0 => PackageState::Published,
-                            1 => PackageState::Skipped { reason: "already exists".to_string() },
+                            1 => PackageState::Skipped { reason: SkipReason::Manual("already exists".to_string()) },
                             2 => PackageState::Failed {
                                 class: ErrorClass::Permanent,
                                 message: "auth error".to_string(),
@@ -5478,6 +7674,7 @@ mod tests {
                                                     compromised_at: None,
                             compromised_by: None,
                             superseded_by: None,
+                            artifact_sha256: None,
                         }
                     })
                     .collect();
@@ -5536,7 +7733,7 @@ mod tests {
                         0 => PackageState::Pending,
                         1 => PackageState::Uploaded,
                         2 => PackageState::Published,
-                        3 => PackageState::Skipped { reason: "exists".to_string() },
+                        3 => PackageState::Skipped { reason: SkipReason::Manual("exists".to_string()) },
                         _ => PackageState::Failed {
                             class: ErrorClass::Retryable,
                             message: "timeout".to_string(),
@@ -5588,6 +7785,7 @@ mod tests {
                 PackageState::Skipped { .. } => 2,   // terminal
                 PackageState::Failed { .. } => 1,    // same level as Uploaded
                 PackageState::Ambiguous { .. } => 2, // terminal
+                PackageState::Cancelled { .. } => 1, // resumable, like Failed
             }
         }
 
@@ -5601,7 +7799,7 @@ mod tests {
                     0 => PackageState::Pending,
                     1 => PackageState::Uploaded,
                     2 => PackageState::Published,
-                    3 => PackageState::Skipped { reason: "exists".to_string() },
+                    3 => PackageState::Skipped { reason: SkipReason::Manual("exists".to_string()) },
                     4 => PackageState::Failed {
                         class: ErrorClass::Retryable,
                         message: "err".to_string(),
@@ -5648,7 +7846,7 @@ mod tests {
             fn terminal_states_have_no_transitions(variant in 0u8..3) {
                 let state = match variant {
                     0 => PackageState::Published,
-                    1 => PackageState::Skipped { reason: "exists".to_string() },
+                    1 => PackageState::Skipped { reason: SkipReason::Manual("exists".to_string()) },
                     _ => PackageState::Ambiguous { message: "unclear".to_string() },
                 };
                 let nexts = valid_next_states(&state);
@@ -5672,7 +7870,7 @@ mod tests {
                     0 => PackageState::Pending,
                     1 => PackageState::Uploaded,
                     2 => PackageState::Published,
-                    3 => PackageState::Skipped { reason: message.clone() },
+                    3 => PackageState::Skipped { reason: SkipReason::Manual(message.clone()) },
                     4 => PackageState::Failed {
                         class: ErrorClass::Retryable,
                         message: message.clone(),
@@ -5998,6 +8196,7 @@ mod tests {
                                             compromised_at: None,
                         compromised_by: None,
                         superseded_by: None,
+                        artifact_sha256: None,
                     })
                     .collect();
 
@@ -97,6 +97,45 @@ pub fn validate_schema_version(version: &str, minimum_supported: &str, label: &s
     Ok(())
 }
 
+/// Validate that `version` lies between `minimum_supported` and `current`,
+/// inclusive.
+///
+/// Unlike [`validate_schema_version`], versions newer than `current` are
+/// rejected too, for callers that must not best-effort parse files written
+/// by a newer shipper.
+///
+/// # Examples
+///
+/// ```
+/// use shipper_types::schema::validate_schema_version_range;
+///
+/// let check = |v| validate_schema_version_range(v, "shipper.receipt.v1", "shipper.receipt.v2", "receipt");
+/// assert!(check("shipper.receipt.v2").is_ok());
+/// assert!(check("shipper.receipt.v0").is_err());
+/// assert!(check("shipper.receipt.v3").is_err());
+/// ```
+pub fn validate_schema_version_range(
+    version: &str,
+    minimum_supported: &str,
+    current: &str,
+    label: &str,
+) -> Result<()> {
+    validate_schema_version(version, minimum_supported, label)?;
+
+    let version_num = parse_schema_version(version)
+        .with_context(|| format!("invalid {label} version format: {version}"))?;
+    let current_num = parse_schema_version(current)
+        .with_context(|| format!("invalid current version format: {current}"))?;
+
+    if version_num > current_num {
+        anyhow::bail!(
+            "{label} version {version} is newer than this shipper supports. Newest supported version is {current}; upgrade shipper to read it"
+        );
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -209,6 +248,25 @@ mod tests {
         assert!(err.to_string().contains("invalid minimum version format"));
     }
 
+    #[test]
+    fn validate_schema_version_range_accepts_bounds_and_rejects_outside() {
+        let check = |v| {
+            validate_schema_version_range(v, "shipper.receipt.v1", "shipper.receipt.v2", "receipt")
+        };
+        assert!(check("shipper.receipt.v1").is_ok());
+        assert!(check("shipper.receipt.v2").is_ok());
+
+        let too_old = check("shipper.receipt.v0").unwrap_err().to_string();
+        assert!(too_old.contains("too old"), "{too_old}");
+
+        let too_new = check("shipper.receipt.v99").unwrap_err().to_string();
+        assert!(
+            too_new.contains("newer than this shipper supports"),
+            "{too_new}"
+        );
+        assert!(too_new.contains("shipper.receipt.v2"), "{too_new}");
+    }
+
     #[test]
     fn validate_schema_version_label_appears_in_error_message() {
         let err = validate_schema_version("shipper.x.v0", "shipper.x.v5", "my_custom_label")
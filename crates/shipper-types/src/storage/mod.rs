@@ -5,9 +5,9 @@
 //! choice through the stable `shipper-types` contract crate.
 //!
 //! The runtime trait (`StorageBackend`) and filesystem implementation live in
-//! `shipper::ops::storage` as crate-private internals — only filesystem storage
-//! is fully implemented today, so we do not promise a public `StorageBackend`
-//! trait until cloud backends are real.
+//! `shipper::ops::storage` as crate-private internals — filesystem, GCS and
+//! Azure storage are implemented today, but S3 is not, so we do not promise a
+//! public `StorageBackend` trait until every cloud backend is real.
 
 use serde::{Deserialize, Serialize};
 
@@ -81,15 +81,21 @@ pub struct CloudStorageConfig {
     /// Custom endpoint (for S3-compatible services like MinIO, DigitalOcean Spaces)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub endpoint: Option<String>,
-    /// Access key ID
+    /// Access key ID (HMAC access ID for GCS, account name for Azure)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub access_key_id: Option<String>,
-    /// Secret access key
+    /// Secret access key (HMAC secret for GCS, base64 account key for Azure)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub secret_access_key: Option<String>,
-    /// Session token (for temporary credentials)
+    /// Session token (temporary credentials; SAS token for Azure)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub session_token: Option<String>,
+    /// Refuse every write, delete, copy and move; reads still work
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub read_only: bool,
+    /// Gzip stored blobs at this level (0-9); `None` stores them as-is
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression_level: Option<u32>,
 }
 
 impl Default for CloudStorageConfig {
@@ -103,6 +109,8 @@ impl Default for CloudStorageConfig {
             access_key_id: None,
             secret_access_key: None,
             session_token: None,
+            read_only: false,
+            compression_level: None,
         }
     }
 }
@@ -188,6 +196,18 @@ impl CloudStorageConfig {
         self
     }
 
+    /// Open the storage read-only
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Gzip stored blobs at `level` (0-9)
+    pub fn with_compression(mut self, level: u32) -> Self {
+        self.compression_level = Some(level);
+        self
+    }
+
     /// Build full path from relative path
     pub fn full_path(&self, relative_path: &str) -> String {
         if self.base_path.is_empty() {
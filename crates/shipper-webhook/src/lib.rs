@@ -1,7 +1,7 @@
 //! Webhook notifications for shipper.
 //!
 //! This crate provides webhook notification support for publish events,
-//! supporting Slack, Discord, and generic webhooks.
+//! supporting Slack, Discord, Microsoft Teams, and generic webhooks.
 //!
 //! # Example
 //!
@@ -28,6 +28,7 @@ use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use sha2::Sha256;
+use shipper_retry::{RetryBudget, RetryStrategyConfig};
 
 /// Webhook type
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -39,6 +40,8 @@ pub enum WebhookType {
     Slack,
     /// Discord webhook
     Discord,
+    /// Microsoft Teams incoming webhook (MessageCard)
+    Teams,
 }
 
 /// Webhook configuration
@@ -55,8 +58,56 @@ pub struct WebhookConfig {
     /// Timeout in seconds
     #[serde(default = "default_timeout")]
     pub timeout_secs: u64,
+    /// Retry policy for transient delivery failures; `None` sends once
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryStrategyConfig>,
+    /// Maximum crate names listed per run-summary field
+    /// (defaults to [`DEFAULT_MAX_LIST_ITEMS`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_list_items: Option<usize>,
+    /// Render and log the request instead of sending it
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dry_run: bool,
+    /// TLS hardening for delivery; set with [`WebhookConfig::with_min_tls`]
+    /// and [`WebhookConfig::with_pinned_cert`]
+    #[serde(skip)]
+    pub tls: WebhookTls,
 }
 
+/// Lowest TLS version to accept, re-exported from `reqwest`
+pub use reqwest::tls::Version as TlsVersion;
+
+/// TLS settings for webhook delivery; the defaults keep `reqwest`'s behaviour
+#[derive(Debug, Clone, Default)]
+pub struct WebhookTls {
+    /// Lowest TLS version to negotiate
+    pub min_version: Option<TlsVersion>,
+    /// DER-encoded certificate to trust instead of the built-in roots
+    pub pinned_cert_der: Option<Vec<u8>>,
+}
+
+impl WebhookConfig {
+    /// Refuse to negotiate TLS below `version`
+    pub fn with_min_tls(mut self, version: TlsVersion) -> Self {
+        self.tls.min_version = Some(version);
+        self
+    }
+
+    /// Trust only `der_bytes`, a DER-encoded certificate for the webhook
+    /// host or its CA, instead of the built-in roots
+    ///
+    /// The bytes are checked when the HTTP client is built, so a malformed
+    /// certificate fails delivery rather than falling back to the defaults.
+    pub fn with_pinned_cert(mut self, der_bytes: &[u8]) -> Self {
+        self.tls.pinned_cert_der = Some(der_bytes.to_vec());
+        self
+    }
+}
+
+/// Crate names listed per run-summary field when
+/// [`WebhookConfig::max_list_items`] is unset
+pub const DEFAULT_MAX_LIST_ITEMS: usize = 10;
+
 fn default_timeout() -> u64 {
     30
 }
@@ -68,6 +119,10 @@ impl Default for WebhookConfig {
             webhook_type: WebhookType::default(),
             secret: None,
             timeout_secs: default_timeout(),
+            retry: None,
+            max_list_items: None,
+            dry_run: false,
+            tls: WebhookTls::default(),
         }
     }
 }
@@ -94,64 +149,326 @@ pub struct WebhookPayload {
     /// Error message (if failed)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// Per-crate outcome lists for a whole-run summary
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<RunSummary>,
     /// Additional fields
     #[serde(flatten)]
     pub extra: std::collections::BTreeMap<String, serde_json::Value>,
 }
 
-/// Send a webhook notification
-pub fn send_webhook(config: &WebhookConfig, payload: &WebhookPayload) -> Result<()> {
-    let client = reqwest::blocking::Client::builder()
-        .timeout(Duration::from_secs(config.timeout_secs))
-        .build()
-        .context("failed to create HTTP client")?;
+impl WebhookPayload {
+    /// Scrub secrets from the free-text fields before sending
+    ///
+    /// Applies `redact` to `message`, `error` and every string inside
+    /// `extra`; these can carry cargo stderr tails with tokens in them.
+    /// Callers pass `shipper_core::cargo::redact_sensitive`.
+    pub fn redacted(mut self, redact: impl Fn(&str) -> String) -> Self {
+        self.message = redact(&self.message);
+        self.error = self.error.as_deref().map(&redact);
+        for value in self.extra.values_mut() {
+            redact_json_strings(value, &redact);
+        }
+        self
+    }
+}
 
-    let body = match config.webhook_type {
-        WebhookType::Generic => serde_json::to_string(payload)?,
-        WebhookType::Slack => slack_payload(payload)?,
-        WebhookType::Discord => discord_payload(payload)?,
-    };
+fn redact_json_strings(value: &mut serde_json::Value, redact: &impl Fn(&str) -> String) {
+    match value {
+        serde_json::Value::String(s) => *s = redact(s),
+        serde_json::Value::Array(items) => {
+            for item in items {
+                redact_json_strings(item, redact);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for item in map.values_mut() {
+                redact_json_strings(item, redact);
+            }
+        }
+        _ => {}
+    }
+}
 
-    let signature = config
-        .secret
+/// Outcome of every crate in a publish run, for one notification per run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunSummary {
+    /// Crates published in this run
+    pub published: Vec<String>,
+    /// Crates skipped (already published or filtered out)
+    pub skipped: Vec<String>,
+    /// Crates that failed to publish
+    pub failed: Vec<String>,
+}
+
+/// A fully rendered webhook request: what would go over the wire.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookRequest {
+    /// Destination URL
+    pub url: String,
+    /// JSON body, already formatted for the webhook type
+    pub body: String,
+    /// `X-Hub-Signature-256` value when a secret is configured
+    pub signature: Option<String>,
+}
+
+impl WebhookRequest {
+    /// Render `payload` for `config` without sending anything.
+    pub fn render(config: &WebhookConfig, payload: &WebhookPayload) -> Result<Self> {
+        let (body, signature) = request_body(config, payload)?;
+        Ok(Self {
+            url: config.url.clone(),
+            body,
+            signature,
+        })
+    }
+}
+
+/// Delivers rendered webhook requests
+pub trait WebhookTransport {
+    /// Deliver one request.
+    fn send(&self, request: &WebhookRequest) -> Result<()>;
+}
+
+/// Transport that records requests instead of sending them
+///
+/// Lets pipeline tests assert exactly what would be delivered.
+#[derive(Debug, Default)]
+pub struct CapturingTransport {
+    requests: std::sync::Mutex<Vec<WebhookRequest>>,
+}
+
+impl CapturingTransport {
+    /// Create an empty capture.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests captured so far, oldest first.
+    pub fn requests(&self) -> Vec<WebhookRequest> {
+        self.requests
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
+
+impl WebhookTransport for CapturingTransport {
+    fn send(&self, request: &WebhookRequest) -> Result<()> {
+        self.requests
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(request.clone());
+        Ok(())
+    }
+}
+
+/// `tracing` target for dry-run webhook deliveries.
+pub const DRY_RUN_TARGET: &str = "shipper::webhook";
+
+/// Transport used for [`WebhookConfig::dry_run`]: traces the request on
+/// [`DRY_RUN_TARGET`], sends nothing
+///
+/// Only the webhook kind and host are logged, since a Slack or Discord
+/// webhook URL is itself a credential, and the body is redacted.
+struct DryRunTransport {
+    kind: WebhookType,
+}
+
+impl DryRunTransport {
+    /// The host and redacted body to log for `request`.
+    fn loggable(request: &WebhookRequest) -> (String, String) {
+        let host = reqwest::Url::parse(&request.url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_else(|| "<invalid url>".to_string());
+        (
+            host,
+            shipper_output_sanitizer::redact_sensitive(&request.body),
+        )
+    }
+}
+
+impl WebhookTransport for DryRunTransport {
+    fn send(&self, request: &WebhookRequest) -> Result<()> {
+        let (host, body) = Self::loggable(request);
+        tracing::info!(
+            target: DRY_RUN_TARGET,
+            kind = ?self.kind,
+            host = %host,
+            body = %body,
+            "webhook dry run; not sending"
+        );
+        Ok(())
+    }
+}
+
+/// Render `payload` and hand it to `transport`
+///
+/// Retry and timeout settings are the transport's concern; only
+/// [`send_webhook`]'s HTTP delivery applies [`WebhookConfig::retry`].
+pub fn send_webhook_with(
+    config: &WebhookConfig,
+    payload: &WebhookPayload,
+    transport: &dyn WebhookTransport,
+) -> Result<()> {
+    transport.send(&WebhookRequest::render(config, payload)?)
+}
+
+/// The pinned certificate from `tls`, if any, checked up front so a bad
+/// one is reported as such.
+fn pinned_cert(tls: &WebhookTls) -> Result<Option<reqwest::Certificate>> {
+    tls.pinned_cert_der
         .as_deref()
-        .filter(|secret| !secret.trim().is_empty())
-        .map(|secret| webhook_signature(secret, &body))
-        .transpose()?;
+        .map(|der| reqwest::Certificate::from_der(der).context("invalid pinned certificate DER"))
+        .transpose()
+}
 
-    let mut request = client
-        .post(&config.url)
-        .header("Content-Type", "application/json")
-        .body(body);
+/// Build the blocking HTTP client for `config`'s timeout and TLS settings
+fn blocking_client(config: &WebhookConfig) -> Result<reqwest::blocking::Client> {
+    let mut builder =
+        reqwest::blocking::Client::builder().timeout(Duration::from_secs(config.timeout_secs));
+    if let Some(version) = config.tls.min_version {
+        builder = builder.tls_version_min(version);
+    }
+    if let Some(cert) = pinned_cert(&config.tls)? {
+        builder = builder.tls_certs_only([cert]);
+    }
+    builder.build().context(client_error(config))
+}
 
-    if let Some(signature) = signature {
-        request = request.header("X-Hub-Signature-256", signature);
+/// Async counterpart of [`blocking_client`]
+fn async_client(config: &WebhookConfig) -> Result<reqwest::Client> {
+    let mut builder = reqwest::Client::builder().timeout(Duration::from_secs(config.timeout_secs));
+    if let Some(version) = config.tls.min_version {
+        builder = builder.tls_version_min(version);
     }
+    if let Some(cert) = pinned_cert(&config.tls)? {
+        builder = builder.tls_certs_only([cert]);
+    }
+    builder.build().context(client_error(config))
+}
 
-    let response = request.send().context("failed to send webhook request")?;
+fn client_error(config: &WebhookConfig) -> &'static str {
+    if config.tls.pinned_cert_der.is_some() {
+        "failed to create HTTP client with pinned certificate"
+    } else {
+        "failed to create HTTP client"
+    }
+}
 
-    if !response.status().is_success() {
-        return Err(anyhow::anyhow!(
-            "webhook request failed with status {}: {}",
-            response.status(),
-            response.text().unwrap_or_default()
-        ));
+/// Send a webhook notification
+///
+/// With [`WebhookConfig::retry`] set, network errors, 5xx and 429 responses
+/// are retried (honouring `Retry-After`); other 4xx responses fail at once.
+/// With [`WebhookConfig::dry_run`] set, the request is rendered and logged
+/// but not sent.
+pub fn send_webhook(config: &WebhookConfig, payload: &WebhookPayload) -> Result<()> {
+    if config.dry_run {
+        return send_webhook_with(
+            config,
+            payload,
+            &DryRunTransport {
+                kind: config.webhook_type,
+            },
+        );
     }
 
-    Ok(())
+    let client = blocking_client(config)?;
+
+    let (body, signature) = request_body(config, payload)?;
+
+    let mut attempt = 1;
+    loop {
+        let mut request = client
+            .post(&config.url)
+            .header("Content-Type", "application/json")
+            .body(body.clone());
+
+        if let Some(signature) = &signature {
+            request = request.header("X-Hub-Signature-256", signature);
+        }
+
+        let failure = match request.send() {
+            Ok(response) if response.status().is_success() => return Ok(()),
+            Ok(response) => {
+                let status = response.status();
+                let retry_after = retry_after(response.headers());
+                let text = response.text().unwrap_or_default();
+                AttemptFailure::from_status(status, &text, retry_after)
+            }
+            Err(err) => AttemptFailure::network(err),
+        };
+
+        match next_delay(config, attempt, &failure) {
+            Some(delay) => std::thread::sleep(delay),
+            None => return Err(failure.error),
+        }
+        attempt += 1;
+    }
 }
 
 /// Send a webhook notification asynchronously
+///
+/// Retries like [`send_webhook`], sleeping on the async runtime between
+/// attempts, and honours [`WebhookConfig::dry_run`] the same way.
 pub async fn send_webhook_async(config: &WebhookConfig, payload: &WebhookPayload) -> Result<()> {
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(config.timeout_secs))
-        .build()
-        .context("failed to create HTTP client")?;
+    if config.dry_run {
+        return send_webhook_with(
+            config,
+            payload,
+            &DryRunTransport {
+                kind: config.webhook_type,
+            },
+        );
+    }
+
+    let client = async_client(config)?;
+
+    let (body, signature) = request_body(config, payload)?;
+
+    let mut attempt = 1;
+    loop {
+        let mut request = client
+            .post(&config.url)
+            .header("Content-Type", "application/json")
+            .body(body.clone());
+
+        if let Some(signature) = &signature {
+            request = request.header("X-Hub-Signature-256", signature);
+        }
+
+        let failure = match request.send().await {
+            Ok(response) if response.status().is_success() => return Ok(()),
+            Ok(response) => {
+                let status = response.status();
+                let retry_after = retry_after(response.headers());
+                let text = response.text().await.unwrap_or_default();
+                AttemptFailure::from_status(status, &text, retry_after)
+            }
+            Err(err) => AttemptFailure::network(err),
+        };
+
+        match next_delay(config, attempt, &failure) {
+            Some(delay) => tokio::time::sleep(delay).await,
+            None => return Err(failure.error),
+        }
+        attempt += 1;
+    }
+}
 
+/// Render the request body for the configured webhook type, plus its
+/// signature when a non-blank secret is set.
+fn request_body(
+    config: &WebhookConfig,
+    payload: &WebhookPayload,
+) -> Result<(String, Option<String>)> {
+    let max_items = config.max_list_items.unwrap_or(DEFAULT_MAX_LIST_ITEMS);
     let body = match config.webhook_type {
         WebhookType::Generic => serde_json::to_string(payload)?,
-        WebhookType::Slack => slack_payload(payload)?,
-        WebhookType::Discord => discord_payload(payload)?,
+        WebhookType::Slack => slack_payload(payload, max_items)?,
+        WebhookType::Discord => discord_payload(payload, max_items)?,
+        WebhookType::Teams => teams_payload(payload, max_items)?,
     };
 
     let signature = config
@@ -161,29 +478,60 @@ pub async fn send_webhook_async(config: &WebhookConfig, payload: &WebhookPayload
         .map(|secret| webhook_signature(secret, &body))
         .transpose()?;
 
-    let mut request = client
-        .post(&config.url)
-        .header("Content-Type", "application/json")
-        .body(body);
+    Ok((body, signature))
+}
 
-    if let Some(signature) = signature {
-        request = request.header("X-Hub-Signature-256", signature);
-    }
+/// A failed delivery attempt and whether it is worth repeating.
+struct AttemptFailure {
+    error: anyhow::Error,
+    retryable: bool,
+    retry_after: Option<Duration>,
+}
 
-    let response = request
-        .send()
-        .await
-        .context("failed to send webhook request")?;
+impl AttemptFailure {
+    fn network(err: reqwest::Error) -> Self {
+        Self {
+            error: anyhow::Error::new(err).context("failed to send webhook request"),
+            retryable: true,
+            retry_after: None,
+        }
+    }
 
-    if !response.status().is_success() {
-        return Err(anyhow::anyhow!(
-            "webhook request failed with status {}: {}",
-            response.status(),
-            response.text().await.unwrap_or_default()
-        ));
+    /// 5xx and 429 are transient; any other status means the payload or URL
+    /// is wrong and repeating the request cannot help.
+    fn from_status(status: reqwest::StatusCode, body: &str, retry_after: Option<Duration>) -> Self {
+        Self {
+            error: anyhow::anyhow!("webhook request failed with status {status}: {body}"),
+            retryable: status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS,
+            retry_after,
+        }
     }
+}
+
+/// Delay before the next attempt, or `None` when the failure is final.
+///
+/// A server-supplied `Retry-After` replaces the backoff but is capped at
+/// `max_delay`, so a hostile or misconfigured endpoint cannot stall the run.
+fn next_delay(config: &WebhookConfig, attempt: u32, failure: &AttemptFailure) -> Option<Duration> {
+    let retry = config.retry.as_ref()?;
+    if !failure.retryable {
+        return None;
+    }
+    let backoff = RetryBudget::new(retry.clone()).should_retry(attempt)?;
+    Some(
+        failure
+            .retry_after
+            .map_or(backoff, |after| after.min(retry.max_delay)),
+    )
+}
 
-    Ok(())
+/// Parse a `Retry-After` header given in seconds.
+fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    headers
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
 }
 
 fn webhook_signature(secret: &str, body: &str) -> Result<String> {
@@ -203,7 +551,7 @@ fn hex_encode(bytes: &[u8]) -> String {
 }
 
 /// Format payload for Slack
-fn slack_payload(payload: &WebhookPayload) -> Result<String> {
+fn slack_payload(payload: &WebhookPayload, max_items: usize) -> Result<String> {
     let color = if payload.success { "good" } else { "danger" };
 
     let mut fields = vec![];
@@ -240,6 +588,14 @@ fn slack_payload(payload: &WebhookPayload) -> Result<String> {
         }));
     }
 
+    for (label, value) in summary_lists(payload, max_items) {
+        fields.push(json!({
+            "title": label,
+            "value": value,
+            "short": false
+        }));
+    }
+
     let slack_json = json!({
         "attachments": [{
             "color": color,
@@ -253,7 +609,7 @@ fn slack_payload(payload: &WebhookPayload) -> Result<String> {
 }
 
 /// Format payload for Discord
-fn discord_payload(payload: &WebhookPayload) -> Result<String> {
+fn discord_payload(payload: &WebhookPayload, max_items: usize) -> Result<String> {
     let color = if payload.success {
         65280_u32
     } else {
@@ -294,6 +650,14 @@ fn discord_payload(payload: &WebhookPayload) -> Result<String> {
         }));
     }
 
+    for (label, value) in summary_lists(payload, max_items) {
+        fields.push(json!({
+            "name": label,
+            "value": value,
+            "inline": false
+        }));
+    }
+
     let discord_json = json!({
         "embeds": [{
             "title": payload.title.as_ref().unwrap_or(&"Shipper Notification".to_string()),
@@ -306,6 +670,104 @@ fn discord_payload(payload: &WebhookPayload) -> Result<String> {
     Ok(serde_json::to_string(&discord_json)?)
 }
 
+/// Format payload for Microsoft Teams
+fn teams_payload(payload: &WebhookPayload, max_items: usize) -> Result<String> {
+    let color = if payload.success { "00FF00" } else { "FF0000" };
+    let title = payload
+        .title
+        .clone()
+        .unwrap_or_else(|| "Shipper Notification".to_string());
+
+    let mut facts = vec![];
+
+    if let Some(package) = &payload.package {
+        facts.push(json!({
+            "name": "Package",
+            "value": package
+        }));
+    }
+
+    if let Some(version) = &payload.version {
+        facts.push(json!({
+            "name": "Version",
+            "value": version
+        }));
+    }
+
+    if let Some(registry) = &payload.registry {
+        facts.push(json!({
+            "name": "Registry",
+            "value": registry
+        }));
+    }
+
+    if let Some(error) = &payload.error {
+        facts.push(json!({
+            "name": "Error",
+            "value": error
+        }));
+    }
+
+    for (label, value) in summary_lists(payload, max_items) {
+        facts.push(json!({
+            "name": label,
+            "value": value
+        }));
+    }
+
+    let teams_json = json!({
+        "@type": "MessageCard",
+        "@context": "https://schema.org/extensions",
+        "themeColor": color,
+        "summary": title,
+        "title": title,
+        "text": payload.message,
+        "sections": [{
+            "facts": facts
+        }]
+    });
+
+    Ok(serde_json::to_string(&teams_json)?)
+}
+
+/// Render the non-empty run-summary lists as `(label, collapsed list)` pairs.
+fn summary_lists(payload: &WebhookPayload, max_items: usize) -> Vec<(String, String)> {
+    let Some(summary) = &payload.summary else {
+        return Vec::new();
+    };
+
+    [
+        ("Published", &summary.published),
+        ("Skipped", &summary.skipped),
+        ("Failed", &summary.failed),
+    ]
+    .into_iter()
+    .filter(|(_, names)| !names.is_empty())
+    .map(|(label, names)| {
+        (
+            format!("{label} ({})", names.len()),
+            collapsed_list(names, max_items),
+        )
+    })
+    .collect()
+}
+
+/// Join up to `max_items` names, noting how many were left out.
+fn collapsed_list(names: &[String], max_items: usize) -> String {
+    let shown = names
+        .iter()
+        .take(max_items)
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(", ");
+    let hidden = names.len().saturating_sub(max_items);
+    match (shown.is_empty(), hidden) {
+        (_, 0) => shown,
+        (true, hidden) => format!("{hidden} crates"),
+        (false, hidden) => format!("{shown}, … and {hidden} more"),
+    }
+}
+
 /// Create a success payload for a published package
 pub fn publish_success_payload(package: &str, version: &str, registry: &str) -> WebhookPayload {
     WebhookPayload {
@@ -332,6 +794,42 @@ pub fn publish_failure_payload(package: &str, version: &str, error: &str) -> Web
     }
 }
 
+/// Create a single payload summarising a whole publish run
+///
+/// The run counts as successful when nothing failed. Chat formatters list
+/// the crate names collapsed to [`WebhookConfig::max_list_items`]; the
+/// generic format carries the full lists.
+pub fn run_summary_payload(
+    published: &[&str],
+    skipped: &[&str],
+    failed: &[&str],
+    registry: &str,
+) -> WebhookPayload {
+    let to_owned = |names: &[&str]| names.iter().map(|n| n.to_string()).collect();
+    let success = failed.is_empty();
+    WebhookPayload {
+        message: format!(
+            "Publish run finished: {} published, {} skipped, {} failed",
+            published.len(),
+            skipped.len(),
+            failed.len()
+        ),
+        title: Some(if success {
+            "Publish Run Succeeded".to_string()
+        } else {
+            "Publish Run Failed".to_string()
+        }),
+        success,
+        registry: Some(registry.to_string()),
+        summary: Some(RunSummary {
+            published: to_owned(published),
+            skipped: to_owned(skipped),
+            failed: to_owned(failed),
+        }),
+        ..Default::default()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -350,6 +848,34 @@ mod tests {
         assert_eq!(config.timeout_secs, 30);
     }
 
+    /// Self-signed P-256 certificate for `registry.test`
+    const PINNED_CERT_DER: &[u8] = include_bytes!("../tests/data/pinned-cert.der");
+
+    #[test]
+    fn clients_build_with_min_tls_and_valid_pinned_cert() {
+        let config = WebhookConfig::default()
+            .with_min_tls(TlsVersion::TLS_1_2)
+            .with_pinned_cert(PINNED_CERT_DER);
+        assert_eq!(config.tls.min_version, Some(TlsVersion::TLS_1_2));
+        blocking_client(&config).expect("blocking client");
+        async_client(&config).expect("async client");
+    }
+
+    #[test]
+    fn malformed_pinned_cert_fails_client_construction() {
+        let config = WebhookConfig::default().with_pinned_cert(b"not a certificate");
+        let err = blocking_client(&config).expect_err("malformed cert");
+        assert!(format!("{err:#}").contains("pinned certificate"), "{err:#}");
+        assert!(async_client(&config).is_err());
+    }
+
+    #[test]
+    fn tls_settings_are_not_serialized() {
+        let config = WebhookConfig::default().with_pinned_cert(PINNED_CERT_DER);
+        let json = serde_json::to_string(&config).expect("serialize");
+        assert!(!json.contains("tls"), "{json}");
+    }
+
     #[test]
     fn webhook_payload_default() {
         let payload = WebhookPayload::default();
@@ -374,20 +900,257 @@ mod tests {
         assert!(payload.message.contains("Failed"));
     }
 
+    /// Stand-in for `redact_sensitive`, which lives outside this crate.
+    fn scrub_bearer(text: &str) -> String {
+        text.split(' ')
+            .scan(false, |after_bearer, word| {
+                let out = if *after_bearer { "[REDACTED]" } else { word };
+                *after_bearer = word.eq_ignore_ascii_case("bearer");
+                Some(out.to_string())
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn redacted_scrubs_bearer_token_from_slack_body() {
+        let error = "401 Unauthorized: Authorization: Bearer cio_s3cr3t_t0ken rejected";
+        let mut payload = publish_failure_payload("demo", "1.0.0", error);
+        payload.extra.insert(
+            "legacy".to_string(),
+            serde_json::json!({ "message": error, "attempts": 3 }),
+        );
+        let config = WebhookConfig {
+            webhook_type: WebhookType::Slack,
+            ..Default::default()
+        };
+
+        let leaky = WebhookRequest::render(&config, &payload).unwrap();
+        assert!(leaky.body.contains("cio_s3cr3t_t0ken"));
+
+        let payload = payload.redacted(scrub_bearer);
+        assert_eq!(
+            payload.error.as_deref(),
+            Some("401 Unauthorized: Authorization: Bearer [REDACTED] rejected")
+        );
+        assert_eq!(payload.extra["legacy"]["attempts"], 3);
+        let body = WebhookRequest::render(&config, &payload).unwrap().body;
+        assert!(!body.contains("cio_s3cr3t_t0ken"), "{body}");
+        assert!(body.contains("Bearer [REDACTED]"));
+    }
+
     #[test]
     fn slack_payload_format() {
         let payload = publish_success_payload("test", "1.0.0", "crates-io");
-        let json = slack_payload(&payload).expect("format");
+        let json = slack_payload(&payload, DEFAULT_MAX_LIST_ITEMS).expect("format");
 
         assert!(json.contains("\"attachments\""));
         assert!(json.contains("\"color\":\"good\""));
         assert!(json.contains("test"));
     }
 
+    #[test]
+    fn capturing_transport_records_slack_formatted_body() {
+        let config = WebhookConfig {
+            url: "https://hooks.slack.example/T000/B000".to_string(),
+            webhook_type: WebhookType::Slack,
+            secret: Some("s3cret".to_string()),
+            dry_run: true,
+            ..Default::default()
+        };
+        let payload = publish_success_payload("demo", "1.2.3", "crates-io");
+        let transport = CapturingTransport::new();
+
+        send_webhook_with(&config, &payload, &transport).expect("capture");
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1);
+        let request = &requests[0];
+        assert_eq!(request.url, config.url);
+        assert_eq!(
+            request.body,
+            slack_payload(&payload, DEFAULT_MAX_LIST_ITEMS).unwrap()
+        );
+        assert_eq!(
+            request.signature.as_deref(),
+            Some(webhook_signature("s3cret", &request.body).unwrap().as_str())
+        );
+    }
+
+    #[test]
+    fn dry_run_send_webhook_makes_no_network_call() {
+        // Nothing listens on port 1; a real send would fail to connect.
+        let config = WebhookConfig {
+            url: "http://127.0.0.1:1/hook".to_string(),
+            webhook_type: WebhookType::Slack,
+            timeout_secs: 1,
+            dry_run: true,
+            ..Default::default()
+        };
+        let payload = publish_failure_payload("demo", "1.2.3", "boom");
+
+        send_webhook(&config, &payload).expect("dry run succeeds without sending");
+    }
+
+    #[test]
+    fn dry_run_logs_host_and_redacted_body_only() {
+        let config = WebhookConfig {
+            url: "https://hooks.slack.com/services/T000/B000/s3cr3tpath".to_string(),
+            webhook_type: WebhookType::Slack,
+            ..Default::default()
+        };
+        let payload = publish_failure_payload(
+            "demo",
+            "1.2.3",
+            "401 Unauthorized: Authorization: Bearer cio_s3cr3t_t0ken",
+        );
+        let request = WebhookRequest::render(&config, &payload).unwrap();
+
+        let (host, body) = DryRunTransport::loggable(&request);
+        assert_eq!(host, "hooks.slack.com");
+        assert!(!body.contains("cio_s3cr3t_t0ken"), "{body}");
+        assert!(!body.contains("s3cr3tpath"), "{body}");
+        assert!(body.contains("[REDACTED]"), "{body}");
+    }
+
+    #[test]
+    fn dry_run_defaults_off_and_is_omitted_when_false() {
+        let config: WebhookConfig = serde_json::from_str(r#"{"url":"https://x"}"#).unwrap();
+        assert!(!config.dry_run);
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(!json.contains("dry_run"));
+
+        let config: WebhookConfig =
+            serde_json::from_str(r#"{"url":"https://x","dry_run":true}"#).unwrap();
+        assert!(config.dry_run);
+    }
+
+    #[test]
+    fn run_summary_payload_counts_and_lists() {
+        let payload = run_summary_payload(&["a", "b"], &["c"], &[], "crates-io");
+        assert!(payload.success);
+        assert_eq!(
+            payload.message,
+            "Publish run finished: 2 published, 1 skipped, 0 failed"
+        );
+        assert_eq!(payload.registry.as_deref(), Some("crates-io"));
+        let summary = payload.summary.expect("summary");
+        assert_eq!(summary.published, vec!["a", "b"]);
+        assert_eq!(summary.skipped, vec!["c"]);
+        assert!(summary.failed.is_empty());
+    }
+
+    #[test]
+    fn run_summary_payload_with_failures_is_unsuccessful() {
+        let payload = run_summary_payload(&["a"], &[], &["b"], "crates-io");
+        assert!(!payload.success);
+        assert_eq!(payload.title.as_deref(), Some("Publish Run Failed"));
+    }
+
+    #[test]
+    fn run_summary_truncates_long_lists_in_chat_formats() {
+        let names: Vec<String> = (0..200).map(|i| format!("crate-{i}")).collect();
+        let published: Vec<&str> = names.iter().map(String::as_str).collect();
+        let payload = run_summary_payload(&published, &[], &[], "crates-io");
+
+        let slack: serde_json::Value =
+            serde_json::from_str(&slack_payload(&payload, 10).unwrap()).unwrap();
+        let fields = slack["attachments"][0]["fields"].as_array().unwrap();
+        let published_field = fields
+            .iter()
+            .find(|f| f["title"] == "Published (200)")
+            .expect("published field");
+        let value = published_field["value"].as_str().unwrap();
+        assert!(value.starts_with("crate-0, crate-1,"));
+        assert!(value.contains("crate-9"));
+        assert!(!value.contains("crate-10,"));
+        assert!(value.ends_with("… and 190 more"));
+        // Empty lists are not rendered at all.
+        assert_eq!(fields.len(), 2);
+
+        let discord: serde_json::Value =
+            serde_json::from_str(&discord_payload(&payload, 3).unwrap()).unwrap();
+        let fields = discord["embeds"][0]["fields"].as_array().unwrap();
+        assert!(
+            fields
+                .iter()
+                .any(|f| f["value"] == "crate-0, crate-1, crate-2, … and 197 more")
+        );
+    }
+
+    #[test]
+    fn run_summary_generic_format_keeps_full_lists() {
+        let names: Vec<String> = (0..200).map(|i| format!("crate-{i}")).collect();
+        let published: Vec<&str> = names.iter().map(String::as_str).collect();
+        let payload = run_summary_payload(&published, &[], &[], "crates-io");
+        let config = WebhookConfig {
+            max_list_items: Some(5),
+            ..Default::default()
+        };
+
+        let (body, _) = request_body(&config, &payload).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(json["summary"]["published"].as_array().unwrap().len(), 200);
+    }
+
+    #[test]
+    fn request_body_applies_configured_list_limit() {
+        let payload = run_summary_payload(&["a", "b", "c"], &[], &[], "crates-io");
+        let config = WebhookConfig {
+            webhook_type: WebhookType::Slack,
+            max_list_items: Some(1),
+            ..Default::default()
+        };
+
+        let (body, _) = request_body(&config, &payload).unwrap();
+        assert!(body.contains("a, … and 2 more"));
+    }
+
+    #[test]
+    fn collapsed_list_with_zero_limit_only_counts() {
+        let names = vec!["a".to_string(), "b".to_string()];
+        assert_eq!(collapsed_list(&names, 0), "2 crates");
+        assert_eq!(collapsed_list(&names, 5), "a, b");
+    }
+
+    #[test]
+    fn teams_payload_format() {
+        let payload = publish_success_payload("test", "1.0.0", "crates-io");
+        let json: serde_json::Value =
+            serde_json::from_str(&teams_payload(&payload, DEFAULT_MAX_LIST_ITEMS).expect("format"))
+                .expect("json");
+
+        assert_eq!(json["@type"], "MessageCard");
+        assert_eq!(json["themeColor"], "00FF00");
+        assert_eq!(json["title"], "Package Published");
+        let facts = json["sections"][0]["facts"].as_array().expect("facts");
+        assert!(
+            facts
+                .iter()
+                .any(|f| f["name"] == "Package" && f["value"] == "test")
+        );
+    }
+
+    #[test]
+    fn teams_failure_uses_red_theme_and_error_fact() {
+        let payload = publish_failure_payload("test", "1.0.0", "boom");
+        let json: serde_json::Value =
+            serde_json::from_str(&teams_payload(&payload, DEFAULT_MAX_LIST_ITEMS).expect("format"))
+                .expect("json");
+
+        assert_eq!(json["themeColor"], "FF0000");
+        let facts = json["sections"][0]["facts"].as_array().expect("facts");
+        assert!(
+            facts
+                .iter()
+                .any(|f| f["name"] == "Error" && f["value"] == "boom")
+        );
+    }
+
     #[test]
     fn discord_payload_format() {
         let payload = publish_success_payload("test", "1.0.0", "crates-io");
-        let json = discord_payload(&payload).expect("format");
+        let json = discord_payload(&payload, DEFAULT_MAX_LIST_ITEMS).expect("format");
 
         assert!(json.contains("\"embeds\""));
         assert!(json.contains("\"color\":65280"));
@@ -401,6 +1164,10 @@ mod tests {
             webhook_type: WebhookType::Slack,
             secret: None,
             timeout_secs: 60,
+            retry: None,
+            max_list_items: None,
+            dry_run: false,
+            tls: Default::default(),
         };
 
         let json = serde_json::to_string(&config).expect("serialize");
@@ -424,14 +1191,14 @@ mod tests {
     #[test]
     fn slack_payload_failure_color() {
         let payload = publish_failure_payload("test", "1.0.0", "error");
-        let json = slack_payload(&payload).expect("format");
+        let json = slack_payload(&payload, DEFAULT_MAX_LIST_ITEMS).expect("format");
         assert!(json.contains("\"color\":\"danger\""));
     }
 
     #[test]
     fn discord_payload_failure_color() {
         let payload = publish_failure_payload("test", "1.0.0", "error");
-        let json = discord_payload(&payload).expect("format");
+        let json = discord_payload(&payload, DEFAULT_MAX_LIST_ITEMS).expect("format");
         assert!(json.contains("\"color\":16711680"));
     }
 
@@ -476,6 +1243,7 @@ mod tests {
             registry: Some("crates-io".to_string()),
             error: None,
             extra,
+            summary: None,
         };
 
         assert_eq!(payload.message, "msg");
@@ -520,6 +1288,10 @@ mod tests {
             webhook_type: WebhookType::Discord,
             secret: Some("s3cret".to_string()),
             timeout_secs: 10,
+            retry: None,
+            max_list_items: None,
+            dry_run: false,
+            tls: Default::default(),
         };
         let json = serde_json::to_string(&config).unwrap();
         let deserialized: WebhookConfig = serde_json::from_str(&json).unwrap();
@@ -599,7 +1371,7 @@ mod tests {
             error: Some("oops".to_string()),
             ..Default::default()
         };
-        let json = slack_payload(&payload).unwrap();
+        let json = slack_payload(&payload, DEFAULT_MAX_LIST_ITEMS).unwrap();
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
 
         let attachment = &parsed["attachments"][0];
@@ -618,7 +1390,7 @@ mod tests {
             success: true,
             ..Default::default()
         };
-        let json = slack_payload(&payload).unwrap();
+        let json = slack_payload(&payload, DEFAULT_MAX_LIST_ITEMS).unwrap();
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
 
         let attachment = &parsed["attachments"][0];
@@ -643,7 +1415,7 @@ mod tests {
             error: Some("warn".to_string()),
             ..Default::default()
         };
-        let json = discord_payload(&payload).unwrap();
+        let json = discord_payload(&payload, DEFAULT_MAX_LIST_ITEMS).unwrap();
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
 
         let embed = &parsed["embeds"][0];
@@ -662,7 +1434,7 @@ mod tests {
             success: false,
             ..Default::default()
         };
-        let json = discord_payload(&payload).unwrap();
+        let json = discord_payload(&payload, DEFAULT_MAX_LIST_ITEMS).unwrap();
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
 
         let embed = &parsed["embeds"][0];
@@ -776,6 +1548,10 @@ mod tests {
             webhook_type: WebhookType::Generic,
             timeout_secs: 5,
             secret: None,
+            retry: None,
+            max_list_items: None,
+            dry_run: false,
+            tls: Default::default(),
         };
         let payload = publish_success_payload("mypkg", "1.0.0", "crates-io");
 
@@ -809,6 +1585,10 @@ mod tests {
             webhook_type: WebhookType::Generic,
             timeout_secs: 5,
             secret: Some("my-secret".to_string()),
+            retry: None,
+            max_list_items: None,
+            dry_run: false,
+            tls: Default::default(),
         };
         let payload = WebhookPayload {
             message: "signed".to_string(),
@@ -847,6 +1627,10 @@ mod tests {
             webhook_type: WebhookType::Generic,
             timeout_secs: 5,
             secret: Some("   ".to_string()), // whitespace-only
+            retry: None,
+            max_list_items: None,
+            dry_run: false,
+            tls: Default::default(),
         };
         let payload = WebhookPayload {
             message: "test".to_string(),
@@ -910,6 +1694,10 @@ mod tests {
             webhook_type: WebhookType::Slack,
             timeout_secs: 5,
             secret: None,
+            retry: None,
+            max_list_items: None,
+            dry_run: false,
+            tls: Default::default(),
         };
         let payload = publish_success_payload("crate-x", "0.1.0", "crates-io");
 
@@ -939,6 +1727,10 @@ mod tests {
             webhook_type: WebhookType::Discord,
             timeout_secs: 5,
             secret: None,
+            retry: None,
+            max_list_items: None,
+            dry_run: false,
+            tls: Default::default(),
         };
         let payload = publish_failure_payload("crate-y", "0.2.0", "network error");
 
@@ -958,6 +1750,195 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn send_webhook_teams_format_to_server() {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let addr = server.server_addr().to_ip().unwrap();
+
+        let config = WebhookConfig {
+            url: format!("http://{addr}/teams"),
+            webhook_type: WebhookType::Teams,
+            timeout_secs: 5,
+            ..Default::default()
+        };
+        let payload = publish_success_payload("crate-z", "0.3.0", "crates-io");
+
+        let handle = std::thread::spawn(move || {
+            let mut req = server.recv().unwrap();
+            let mut body = String::new();
+            req.as_reader().read_to_string(&mut body).unwrap();
+            let parsed: serde_json::Value = serde_json::from_str(&body).unwrap();
+            // Teams format is a MessageCard
+            assert_eq!(parsed["@type"], "MessageCard");
+            let response = tiny_http::Response::from_string("1");
+            req.respond(response).unwrap();
+        });
+
+        let result = send_webhook(&config, &payload);
+        handle.join().unwrap();
+        assert!(result.is_ok());
+    }
+
+    fn fast_retry(max_attempts: u32) -> Option<RetryStrategyConfig> {
+        Some(RetryStrategyConfig {
+            strategy: shipper_retry::RetryStrategyType::Constant,
+            max_attempts,
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(10),
+            jitter: 0.0,
+        })
+    }
+
+    /// Answer the first `failures` requests with 503, then 200.
+    fn flaky_server(failures: usize) -> (String, std::thread::JoinHandle<usize>) {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/flaky", server.server_addr().to_ip().unwrap());
+        let handle = std::thread::spawn(move || {
+            for seen in 1.. {
+                let req = server.recv().unwrap();
+                if seen <= failures {
+                    let response = tiny_http::Response::from_string("unavailable")
+                        .with_status_code(tiny_http::StatusCode(503));
+                    req.respond(response).unwrap();
+                } else {
+                    req.respond(tiny_http::Response::from_string("ok")).unwrap();
+                    return seen;
+                }
+            }
+            unreachable!()
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn send_webhook_retries_transient_failures() {
+        let (url, handle) = flaky_server(2);
+        let config = WebhookConfig {
+            url,
+            timeout_secs: 5,
+            retry: fast_retry(3),
+            ..Default::default()
+        };
+
+        send_webhook(&config, &WebhookPayload::default()).expect("third attempt succeeds");
+        assert_eq!(handle.join().unwrap(), 3);
+    }
+
+    #[test]
+    fn send_webhook_gives_up_after_max_attempts() {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let addr = server.server_addr().to_ip().unwrap();
+        let config = WebhookConfig {
+            url: format!("http://{addr}/down"),
+            timeout_secs: 5,
+            retry: fast_retry(2),
+            ..Default::default()
+        };
+
+        let handle = std::thread::spawn(move || {
+            for _ in 0..2 {
+                let req = server.recv().unwrap();
+                let response = tiny_http::Response::from_string("")
+                    .with_status_code(tiny_http::StatusCode(502));
+                req.respond(response).unwrap();
+            }
+            server.recv_timeout(Duration::from_millis(200)).unwrap()
+        });
+
+        let err = send_webhook(&config, &WebhookPayload::default()).unwrap_err();
+        assert!(err.to_string().contains("502"));
+        assert!(handle.join().unwrap().is_none(), "no third attempt");
+    }
+
+    #[test]
+    fn send_webhook_does_not_retry_client_errors() {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let addr = server.server_addr().to_ip().unwrap();
+        let config = WebhookConfig {
+            url: format!("http://{addr}/bad"),
+            timeout_secs: 5,
+            retry: fast_retry(5),
+            ..Default::default()
+        };
+
+        let handle = std::thread::spawn(move || {
+            let req = server.recv().unwrap();
+            let response = tiny_http::Response::from_string("bad payload")
+                .with_status_code(tiny_http::StatusCode(400));
+            req.respond(response).unwrap();
+            server.recv_timeout(Duration::from_millis(200)).unwrap()
+        });
+
+        let err = send_webhook(&config, &WebhookPayload::default()).unwrap_err();
+        assert!(err.to_string().contains("400"));
+        assert!(handle.join().unwrap().is_none(), "400 must not be retried");
+    }
+
+    #[tokio::test]
+    async fn send_webhook_async_retries_transient_failures() {
+        let (url, handle) = flaky_server(2);
+        let config = WebhookConfig {
+            url,
+            timeout_secs: 5,
+            retry: fast_retry(3),
+            ..Default::default()
+        };
+
+        send_webhook_async(&config, &WebhookPayload::default())
+            .await
+            .expect("third attempt succeeds");
+        assert_eq!(handle.join().unwrap(), 3);
+    }
+
+    #[test]
+    fn next_delay_prefers_retry_after() {
+        let config = WebhookConfig {
+            retry: fast_retry(3),
+            ..Default::default()
+        };
+        let limited = AttemptFailure::from_status(
+            reqwest::StatusCode::TOO_MANY_REQUESTS,
+            "",
+            Some(Duration::from_millis(4)),
+        );
+        assert_eq!(
+            next_delay(&config, 1, &limited),
+            Some(Duration::from_millis(4))
+        );
+
+        let unavailable =
+            AttemptFailure::from_status(reqwest::StatusCode::SERVICE_UNAVAILABLE, "", None);
+        assert_eq!(
+            next_delay(&config, 1, &unavailable),
+            Some(Duration::from_millis(10))
+        );
+        assert_eq!(next_delay(&config, 3, &unavailable), None);
+    }
+
+    #[test]
+    fn next_delay_caps_retry_after_at_max_delay() {
+        let config = WebhookConfig {
+            retry: fast_retry(3),
+            ..Default::default()
+        };
+        let limited = AttemptFailure::from_status(
+            reqwest::StatusCode::TOO_MANY_REQUESTS,
+            "",
+            Some(Duration::from_hours(24)),
+        );
+        assert_eq!(
+            next_delay(&config, 1, &limited),
+            Some(Duration::from_millis(10))
+        );
+    }
+
+    #[test]
+    fn next_delay_is_none_without_retry_config() {
+        let failure =
+            AttemptFailure::from_status(reqwest::StatusCode::SERVICE_UNAVAILABLE, "", None);
+        assert_eq!(next_delay(&WebhookConfig::default(), 1, &failure), None);
+    }
+
     #[tokio::test]
     async fn send_webhook_async_success_with_mock_server() {
         let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
@@ -968,6 +1949,10 @@ mod tests {
             webhook_type: WebhookType::Generic,
             timeout_secs: 5,
             secret: None,
+            retry: None,
+            max_list_items: None,
+            dry_run: false,
+            tls: Default::default(),
         };
         let payload = publish_success_payload("async-pkg", "1.0.0", "crates-io");
 
@@ -1036,7 +2021,7 @@ mod tests {
         #[test]
         fn slack_success_payload_json() {
             let payload = publish_success_payload("my-crate", "1.2.3", "crates-io");
-            let body = slack_payload(&payload).unwrap();
+            let body = slack_payload(&payload, DEFAULT_MAX_LIST_ITEMS).unwrap();
             let json: serde_json::Value = serde_json::from_str(&body).unwrap();
             insta::assert_yaml_snapshot!("slack_success_payload", json);
         }
@@ -1044,7 +2029,7 @@ mod tests {
         #[test]
         fn slack_failure_payload_json() {
             let payload = publish_failure_payload("my-crate", "1.2.3", "network error");
-            let body = slack_payload(&payload).unwrap();
+            let body = slack_payload(&payload, DEFAULT_MAX_LIST_ITEMS).unwrap();
             let json: serde_json::Value = serde_json::from_str(&body).unwrap();
             insta::assert_yaml_snapshot!("slack_failure_payload", json);
         }
@@ -1052,7 +2037,7 @@ mod tests {
         #[test]
         fn discord_success_payload_json() {
             let payload = publish_success_payload("my-crate", "1.2.3", "crates-io");
-            let body = discord_payload(&payload).unwrap();
+            let body = discord_payload(&payload, DEFAULT_MAX_LIST_ITEMS).unwrap();
             let json: serde_json::Value = serde_json::from_str(&body).unwrap();
             insta::assert_yaml_snapshot!("discord_success_payload", json);
         }
@@ -1060,11 +2045,27 @@ mod tests {
         #[test]
         fn discord_failure_payload_json() {
             let payload = publish_failure_payload("my-crate", "1.2.3", "network error");
-            let body = discord_payload(&payload).unwrap();
+            let body = discord_payload(&payload, DEFAULT_MAX_LIST_ITEMS).unwrap();
             let json: serde_json::Value = serde_json::from_str(&body).unwrap();
             insta::assert_yaml_snapshot!("discord_failure_payload", json);
         }
 
+        #[test]
+        fn teams_success_payload_json() {
+            let payload = publish_success_payload("my-crate", "1.2.3", "crates-io");
+            let body = teams_payload(&payload, DEFAULT_MAX_LIST_ITEMS).unwrap();
+            let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+            insta::assert_yaml_snapshot!("teams_success_payload", json);
+        }
+
+        #[test]
+        fn teams_failure_payload_json() {
+            let payload = publish_failure_payload("my-crate", "1.2.3", "network error");
+            let body = teams_payload(&payload, DEFAULT_MAX_LIST_ITEMS).unwrap();
+            let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+            insta::assert_yaml_snapshot!("teams_failure_payload", json);
+        }
+
         #[test]
         fn generic_minimal_payload_json() {
             let payload = WebhookPayload {
@@ -1090,6 +2091,7 @@ mod tests {
                 registry: Some("crates-io".to_string()),
                 error: None,
                 extra,
+                summary: None,
             };
             let json: serde_json::Value = serde_json::to_value(&payload).unwrap();
             insta::assert_yaml_snapshot!("generic_payload_with_extras", json);
@@ -1114,6 +2116,10 @@ mod tests {
                 webhook_type: WebhookType::Slack,
                 secret: Some("s3cret-key".to_string()),
                 timeout_secs: 10,
+                retry: None,
+                max_list_items: None,
+                dry_run: false,
+                tls: Default::default(),
             };
             let json: serde_json::Value = serde_json::to_value(&config).unwrap();
             insta::assert_yaml_snapshot!("config_slack_with_secret", json);
@@ -1126,6 +2132,10 @@ mod tests {
                 webhook_type: WebhookType::Discord,
                 secret: None,
                 timeout_secs: 60,
+                retry: None,
+                max_list_items: None,
+                dry_run: false,
+                tls: Default::default(),
             };
             let json: serde_json::Value = serde_json::to_value(&config).unwrap();
             insta::assert_yaml_snapshot!("config_discord_no_secret", json);
@@ -1225,6 +2235,7 @@ mod tests {
                         registry,
                         error,
                         extra: std::collections::BTreeMap::new(),
+                        summary: None,
                     },
                 )
         }
@@ -1257,7 +2268,7 @@ mod tests {
             // Slack payload is always valid JSON with "attachments" array
             #[test]
             fn slack_payload_always_valid(payload in arb_payload()) {
-                let json = slack_payload(&payload).unwrap();
+                let json = slack_payload(&payload, DEFAULT_MAX_LIST_ITEMS).unwrap();
                 let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
                 prop_assert!(parsed["attachments"].is_array());
                 let att = &parsed["attachments"][0];
@@ -1269,7 +2280,7 @@ mod tests {
             // Discord payload is always valid JSON with "embeds" array
             #[test]
             fn discord_payload_always_valid(payload in arb_payload()) {
-                let json = discord_payload(&payload).unwrap();
+                let json = discord_payload(&payload, DEFAULT_MAX_LIST_ITEMS).unwrap();
                 let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
                 prop_assert!(parsed["embeds"].is_array());
                 let embed = &parsed["embeds"][0];
@@ -1609,7 +2620,7 @@ mod tests {
                 package: Some("crâte-ñame".to_string()),
                 ..Default::default()
             };
-            let json = slack_payload(&payload).unwrap();
+            let json = slack_payload(&payload, DEFAULT_MAX_LIST_ITEMS).unwrap();
             let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
             assert_eq!(parsed["attachments"][0]["text"], "Émojis: 🚀🦀✅");
             assert_eq!(parsed["attachments"][0]["title"], "Ünïcödé Tïtlé");
@@ -1624,7 +2635,7 @@ mod tests {
                 error: Some("сетевая ошибка".to_string()),
                 ..Default::default()
             };
-            let json = discord_payload(&payload).unwrap();
+            let json = discord_payload(&payload, DEFAULT_MAX_LIST_ITEMS).unwrap();
             let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
             assert_eq!(parsed["embeds"][0]["description"], "已发布 📦");
             assert_eq!(parsed["embeds"][0]["title"], "发布通知");
@@ -2097,6 +3108,10 @@ mod tests {
                 webhook_type: WebhookType::Generic,
                 secret: Some("top-secret".to_string()),
                 timeout_secs: 15,
+                retry: None,
+                max_list_items: None,
+                dry_run: false,
+                tls: Default::default(),
             };
             insta::assert_debug_snapshot!("config_generic_with_secret", config);
         }
@@ -2108,6 +3123,10 @@ mod tests {
                 webhook_type: WebhookType::Slack,
                 secret: None,
                 timeout_secs: 30,
+                retry: None,
+                max_list_items: None,
+                dry_run: false,
+                tls: Default::default(),
             };
             insta::assert_debug_snapshot!("config_slack_no_secret", config);
         }
@@ -2119,6 +3138,10 @@ mod tests {
                 webhook_type: WebhookType::Discord,
                 secret: Some("discord-secret".to_string()),
                 timeout_secs: 45,
+                retry: None,
+                max_list_items: None,
+                dry_run: false,
+                tls: Default::default(),
             };
             insta::assert_debug_snapshot!("config_discord_with_secret", config);
         }
@@ -2136,6 +3159,10 @@ mod tests {
                 webhook_type: WebhookType::Generic,
                 secret: None,
                 timeout_secs: 1,
+                retry: None,
+                max_list_items: None,
+                dry_run: false,
+                tls: Default::default(),
             };
             insta::assert_debug_snapshot!("config_minimal_timeout", config);
         }
@@ -2179,6 +3206,7 @@ mod tests {
                 registry: Some("crates-io".to_string()),
                 error: None,
                 extra,
+                summary: None,
             };
             insta::assert_debug_snapshot!("payload_with_extra_ci_fields", payload);
         }
@@ -2192,7 +3220,7 @@ mod tests {
                 version: Some("1.0.0".to_string()),
                 ..Default::default()
             };
-            let body = slack_payload(&payload).unwrap();
+            let body = slack_payload(&payload, DEFAULT_MAX_LIST_ITEMS).unwrap();
             let json: serde_json::Value = serde_json::from_str(&body).unwrap();
             insta::assert_debug_snapshot!("slack_unicode_payload", json);
         }
@@ -2205,7 +3233,7 @@ mod tests {
                 error: Some("сетевая ошибка".to_string()),
                 ..Default::default()
             };
-            let body = discord_payload(&payload).unwrap();
+            let body = discord_payload(&payload, DEFAULT_MAX_LIST_ITEMS).unwrap();
             let json: serde_json::Value = serde_json::from_str(&body).unwrap();
             insta::assert_debug_snapshot!("discord_unicode_payload", json);
         }
@@ -2224,6 +3252,7 @@ mod tests {
             registry: Some("crates-io".to_string()),
             error: Some("some error".to_string()),
             extra: std::collections::BTreeMap::new(),
+            summary: None,
         };
         let json = serde_json::to_string(&payload).unwrap();
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
@@ -2248,7 +3277,7 @@ mod tests {
             package: Some("pkg".to_string()),
             ..Default::default()
         };
-        let json = slack_payload(&payload).unwrap();
+        let json = slack_payload(&payload, DEFAULT_MAX_LIST_ITEMS).unwrap();
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
         let fields = parsed["attachments"][0]["fields"].as_array().unwrap();
         assert_eq!(fields.len(), 1);
@@ -2265,7 +3294,7 @@ mod tests {
             error: Some("fail".to_string()),
             ..Default::default()
         };
-        let json = discord_payload(&payload).unwrap();
+        let json = discord_payload(&payload, DEFAULT_MAX_LIST_ITEMS).unwrap();
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
         let fields = parsed["embeds"][0]["fields"].as_array().unwrap();
         assert_eq!(fields.len(), 2);
@@ -2382,6 +3411,10 @@ mod tests {
             webhook_type: WebhookType::Generic,
             timeout_secs: 5,
             secret: Some("async-secret".to_string()),
+            retry: None,
+            max_list_items: None,
+            dry_run: false,
+            tls: Default::default(),
         };
         let payload = WebhookPayload {
             message: "async signed".to_string(),
@@ -2419,6 +3452,10 @@ mod tests {
             webhook_type: WebhookType::Slack,
             timeout_secs: 5,
             secret: None,
+            retry: None,
+            max_list_items: None,
+            dry_run: false,
+            tls: Default::default(),
         };
         let payload = publish_success_payload("async-slack-pkg", "0.3.0", "crates-io");
 
@@ -2450,6 +3487,10 @@ mod tests {
             webhook_type: WebhookType::Discord,
             timeout_secs: 5,
             secret: None,
+            retry: None,
+            max_list_items: None,
+            dry_run: false,
+            tls: Default::default(),
         };
         let payload = publish_failure_payload("async-discord-pkg", "0.4.0", "registry timed out");
 
@@ -2483,6 +3524,10 @@ mod tests {
             webhook_type: WebhookType::Slack,
             timeout_secs: 5,
             secret: Some("async-slack-secret".to_string()),
+            retry: None,
+            max_list_items: None,
+            dry_run: false,
+            tls: Default::default(),
         };
         let payload = publish_failure_payload("signed-pkg", "1.2.3", "boom");
 
@@ -2630,7 +3675,7 @@ mod tests {
             success: true,
             ..Default::default()
         };
-        let json = slack_payload(&payload).unwrap();
+        let json = slack_payload(&payload, DEFAULT_MAX_LIST_ITEMS).unwrap();
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed["attachments"][0]["text"], "line1\nline2\nline3");
     }
@@ -2644,7 +3689,7 @@ mod tests {
             error: Some(long_error),
             ..Default::default()
         };
-        let json = discord_payload(&payload).unwrap();
+        let json = discord_payload(&payload, DEFAULT_MAX_LIST_ITEMS).unwrap();
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
         let err_field = &parsed["embeds"][0]["fields"][0];
         assert_eq!(err_field["name"], "Error");
@@ -2686,6 +3731,7 @@ mod tests {
                 registry: Some("crates-io".to_string()),
                 error: None,
                 extra,
+                summary: None,
             };
             let json: serde_json::Value = serde_json::to_value(&payload).unwrap();
             insta::assert_yaml_snapshot!("hardened_generic_all_fields", json);
@@ -2702,7 +3748,7 @@ mod tests {
                 error: Some("error[E0433]: failed to resolve\n  --> src/lib.rs:1:5\n  |\n1 | use foo::bar;\n  |     ^^^ not found".to_string()),
                 ..Default::default()
             };
-            let body = slack_payload(&payload).unwrap();
+            let body = slack_payload(&payload, DEFAULT_MAX_LIST_ITEMS).unwrap();
             let json: serde_json::Value = serde_json::from_str(&body).unwrap();
             insta::assert_yaml_snapshot!("hardened_slack_multiline_error", json);
         }
@@ -2715,7 +3761,7 @@ mod tests {
                 success: true,
                 ..Default::default()
             };
-            let body = discord_payload(&payload).unwrap();
+            let body = discord_payload(&payload, DEFAULT_MAX_LIST_ITEMS).unwrap();
             let json: serde_json::Value = serde_json::from_str(&body).unwrap();
             insta::assert_yaml_snapshot!("hardened_discord_progress", json);
         }
@@ -2732,6 +3778,7 @@ mod tests {
                 Just(WebhookType::Generic),
                 Just(WebhookType::Slack),
                 Just(WebhookType::Discord),
+                Just(WebhookType::Teams),
             ]
         }
 
@@ -2757,6 +3804,7 @@ mod tests {
                         registry,
                         error,
                         extra: std::collections::BTreeMap::new(),
+                        summary: None,
                     },
                 )
         }
@@ -2769,8 +3817,9 @@ mod tests {
             ) {
                 let body = match wt {
                     WebhookType::Generic => serde_json::to_string(&payload).unwrap(),
-                    WebhookType::Slack => slack_payload(&payload).unwrap(),
-                    WebhookType::Discord => discord_payload(&payload).unwrap(),
+                    WebhookType::Slack => slack_payload(&payload, DEFAULT_MAX_LIST_ITEMS).unwrap(),
+                    WebhookType::Discord => discord_payload(&payload, DEFAULT_MAX_LIST_ITEMS).unwrap(),
+                    WebhookType::Teams => teams_payload(&payload, DEFAULT_MAX_LIST_ITEMS).unwrap(),
                 };
                 let parsed: serde_json::Value = serde_json::from_str(&body).unwrap();
                 prop_assert!(parsed.is_object());
@@ -17,7 +17,7 @@ use shipper::store::{FileStore, StateStore};
 use shipper::types::{
     EnvironmentFingerprint, ErrorClass, EventType, ExecutionResult, ExecutionState,
     PackageEvidence, PackageProgress, PackageReceipt, PackageState, PublishEvent, ReadinessMethod,
-    Registry, ReleaseSpec,
+    Registry, ReleaseSpec, SkipReason,
 };
 
 // ---------------------------------------------------------------------------
@@ -125,6 +125,7 @@ fn sample_receipt(plan_id: &str) -> shipper::types::Receipt {
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256: None,
         }],
         event_log_path: PathBuf::from(".shipper/events.jsonl"),
         git_context: None,
@@ -1032,7 +1033,7 @@ fn state_roundtrip_preserves_all_package_state_variants() {
             version: "1.0.0".to_string(),
             attempts: 0,
             state: PackageState::Skipped {
-                reason: "already published".to_string(),
+                reason: SkipReason::AlreadyPublished,
             },
             last_updated_at: Utc::now(),
         },
@@ -1113,7 +1114,7 @@ fn state_roundtrip_preserves_all_package_state_variants() {
 
     // Verify Skipped reason preserved
     if let PackageState::Skipped { reason } = &loaded.packages["skipped-pkg@1.0.0"].state {
-        assert_eq!(reason, "already published");
+        assert_eq!(*reason, SkipReason::AlreadyPublished);
     }
 }
 
@@ -1392,6 +1393,7 @@ fn receipt_with_mixed_outcomes_roundtrips() {
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             },
             PackageReceipt {
                 name: "b".to_string(),
@@ -1411,13 +1413,14 @@ fn receipt_with_mixed_outcomes_roundtrips() {
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             },
             PackageReceipt {
                 name: "c".to_string(),
                 version: "0.1.0".to_string(),
                 attempts: 0,
                 state: PackageState::Skipped {
-                    reason: "version already exists".to_string(),
+                    reason: SkipReason::Manual("version already exists".to_string()),
                 },
                 started_at: Utc::now(),
                 finished_at: Utc::now(),
@@ -1429,6 +1432,7 @@ fn receipt_with_mixed_outcomes_roundtrips() {
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             },
         ],
         event_log_path: PathBuf::from(".shipper/events.jsonl"),
@@ -23,7 +23,7 @@ use shipper::store::{FileStore, StateStore};
 use shipper::types::{
     AuthType, EnvironmentFingerprint, ErrorClass, EventType, ExecutionResult, ExecutionState,
     Finishability, PackageEvidence, PackageProgress, PackageReceipt, PackageState, PublishEvent,
-    ReadinessMethod, Registry, ReleaseSpec,
+    ReadinessMethod, Registry, ReleaseSpec, SkipReason,
 };
 
 // ---------------------------------------------------------------------------
@@ -187,6 +187,7 @@ fn sample_receipt(plan_id: &str, pkg_names: &[&str]) -> shipper::types::Receipt
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256: None,
         })
         .collect();
 
@@ -1252,6 +1253,7 @@ fn receipt_mixed_outcomes_through_file_store() {
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             },
             PackageReceipt {
                 name: "mid".to_string(),
@@ -1271,13 +1273,14 @@ fn receipt_mixed_outcomes_through_file_store() {
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             },
             PackageReceipt {
                 name: "top".to_string(),
                 version: "0.2.0".to_string(),
                 attempts: 0,
                 state: PackageState::Skipped {
-                    reason: "dependency mid failed".to_string(),
+                    reason: SkipReason::Manual("dependency mid failed".to_string()),
                 },
                 started_at: Utc::now(),
                 finished_at: Utc::now(),
@@ -1289,6 +1292,7 @@ fn receipt_mixed_outcomes_through_file_store() {
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             },
         ],
         event_log_path: std::path::PathBuf::from(".shipper/events.jsonl"),
@@ -1330,7 +1334,10 @@ fn receipt_mixed_outcomes_through_file_store() {
         PackageState::Skipped { .. }
     ));
     if let PackageState::Skipped { reason } = &loaded.packages[2].state {
-        assert_eq!(reason, "dependency mid failed");
+        assert_eq!(
+            *reason,
+            SkipReason::Manual("dependency mid failed".to_string())
+        );
     }
 }
 
@@ -1691,7 +1698,7 @@ fn state_resume_preserves_skipped_packages() {
                 "mid",
                 "0.2.0",
                 PackageState::Skipped {
-                    reason: "version already exists".to_string(),
+                    reason: SkipReason::Manual("version already exists".to_string()),
                 },
                 0,
             ),
@@ -2120,6 +2127,7 @@ fn receipt_all_fields_persisted_and_roundtrip() {
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             },
             PackageReceipt {
                 name: "beta".to_string(),
@@ -2136,6 +2144,7 @@ fn receipt_all_fields_persisted_and_roundtrip() {
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             },
         ],
         event_log_path: std::path::PathBuf::from(".shipper/events.jsonl"),
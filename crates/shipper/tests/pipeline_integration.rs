@@ -23,6 +23,7 @@ use shipper::types::{
     AttemptEvidence, EnvironmentFingerprint, ErrorClass, EventType, ExecutionResult,
     ExecutionState, Finishability, GitContext, PackageEvidence, PackageProgress, PackageReceipt,
     PackageState, PublishEvent, ReadinessEvidence, ReadinessMethod, Registry, ReleaseSpec,
+    SkipReason,
 };
 
 // ---------------------------------------------------------------------------
@@ -115,6 +116,7 @@ fn make_receipt(plan_id: &str, pkgs: &[(&str, &str, PackageState)]) -> shipper::
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256: None,
         })
         .collect();
 
@@ -680,6 +682,7 @@ fn receipt_with_full_evidence_roundtrips() {
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256: None,
         }],
         event_log_path: std::path::PathBuf::from(".shipper/events.jsonl"),
         git_context: None,
@@ -1354,6 +1357,7 @@ fn full_pipeline_plan_preflight_publish_verify_receipt() {
             compromised_at: None,
             compromised_by: None,
             superseded_by: None,
+            artifact_sha256: None,
         })
         .collect();
 
@@ -1725,6 +1729,7 @@ fn receipt_fields_populated_for_mixed_outcomes() {
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             },
             PackageReceipt {
                 name: "app".to_string(),
@@ -1772,6 +1777,7 @@ fn receipt_fields_populated_for_mixed_outcomes() {
                 compromised_at: None,
                 compromised_by: None,
                 superseded_by: None,
+                artifact_sha256: None,
             },
         ],
         event_log_path: std::path::PathBuf::from(".shipper/events.jsonl"),
@@ -2033,7 +2039,7 @@ fn skipped_package_state_roundtrips() {
             "old-crate",
             "1.0.0",
             PackageState::Skipped {
-                reason: "already published".to_string(),
+                reason: SkipReason::AlreadyPublished,
             },
             0,
         )],
@@ -2044,7 +2050,7 @@ fn skipped_package_state_roundtrips() {
         .expect("load")
         .expect("exists");
     if let PackageState::Skipped { ref reason } = loaded.packages["old-crate@1.0.0"].state {
-        assert_eq!(reason, "already published");
+        assert_eq!(*reason, SkipReason::AlreadyPublished);
     } else {
         panic!("expected Skipped state");
     }
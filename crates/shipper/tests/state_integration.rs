@@ -13,7 +13,7 @@ use shipper::state::execution_state::{
 use shipper::types::ExecutionResult;
 use shipper_types::{
     EnvironmentFingerprint, ErrorClass, ExecutionState, PackageEvidence, PackageProgress,
-    PackageReceipt, PackageState, Receipt, Registry,
+    PackageReceipt, PackageState, Receipt, Registry, SkipReason,
 };
 
 // ---------------------------------------------------------------------------
@@ -84,6 +84,7 @@ fn make_package_receipt(name: &str, version: &str, state: PackageState) -> Packa
         compromised_at: None,
         compromised_by: None,
         superseded_by: None,
+        artifact_sha256: None,
     }
 }
 
@@ -220,7 +221,7 @@ fn all_package_state_variants_roundtrip() {
         (
             "skipped@1.0.0",
             PackageState::Skipped {
-                reason: "already published".to_string(),
+                reason: SkipReason::AlreadyPublished,
             },
         ),
         (
@@ -269,7 +270,7 @@ fn all_package_state_variants_roundtrip() {
         other => panic!("expected Pending, got {other:?}"),
     }
     match &loaded.packages["skipped@1.0.0"].state {
-        PackageState::Skipped { reason } => assert_eq!(reason, "already published"),
+        PackageState::Skipped { reason } => assert_eq!(*reason, SkipReason::AlreadyPublished),
         other => panic!("expected Skipped, got {other:?}"),
     }
     match &loaded.packages["failed-retryable@1.0.0"].state {
@@ -564,7 +565,7 @@ fn large_state_roundtrip() {
             PackageState::Pending
         } else {
             PackageState::Skipped {
-                reason: format!("reason {i}"),
+                reason: SkipReason::Manual(format!("reason {i}")),
             }
         };
         pkgs.insert(key, make_progress(&format!("crate-{i}"), "1.0.0", state));
@@ -593,7 +594,7 @@ fn receipt_all_package_state_variants_roundtrip() {
             "b",
             "1.0.0",
             PackageState::Skipped {
-                reason: "exists".to_string(),
+                reason: SkipReason::Manual("exists".to_string()),
             },
         ),
         make_package_receipt(
@@ -835,7 +836,7 @@ fn receipt_mixed_states_all_outcomes() {
             "utils",
             "0.5.0",
             PackageState::Skipped {
-                reason: "already published".to_string(),
+                reason: SkipReason::AlreadyPublished,
             },
         ),
         make_package_receipt(
@@ -1025,6 +1026,7 @@ fn make_deterministic_package_receipt(
         compromised_at: None,
         compromised_by: None,
         superseded_by: None,
+        artifact_sha256: None,
     }
 }
 
@@ -1088,7 +1090,7 @@ fn snapshot_receipt_mixed_outcomes() {
                 "utils",
                 "0.5.0",
                 PackageState::Skipped {
-                    reason: "already published".to_string(),
+                    reason: SkipReason::AlreadyPublished,
                 },
             ),
             make_deterministic_package_receipt(
@@ -3,7 +3,7 @@
 use libfuzzer_sys::fuzz_target;
 use shipper_core::runtime::execution;
 use shipper_retry::RetryStrategyType;
-use shipper_types::{ExecutionState, PackageProgress, PackageState, Registry};
+use shipper_types::{ExecutionState, PackageProgress, PackageState, Registry, SkipReason};
 
 use std::collections::BTreeMap;
 use std::path::PathBuf;
@@ -62,6 +62,6 @@ fuzz_target!(|input: (Vec<u8>, Vec<u8>, u8, bool)| {
     );
     let _ = execution::short_state(&PackageState::Published);
     let _ = execution::short_state(&PackageState::Skipped {
-        reason: "already".to_string(),
+        reason: SkipReason::AlreadyPublished,
     });
 });